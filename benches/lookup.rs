@@ -0,0 +1,48 @@
+//! Measures reading a single scalar from a large figment.
+//!
+//! Run with `cargo bench --bench lookup`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use figment::{Figment, providers::Serialized, value::{Dict, Value}};
+
+fn figment() -> Figment {
+    let section = |n: usize| -> Dict {
+        (0..50).map(|i| (format!("key{}", i), Value::from(n * i))).collect()
+    };
+
+    let defaults: Dict = (0..200).map(|n| (format!("section{}", n), section(n).into())).collect();
+    let overrides: Dict = (0..20).map(|n| (format!("section{}", n * 10), section(n).into())).collect();
+    Figment::from(Serialized::defaults(defaults))
+        .merge(Serialized::defaults(overrides))
+        .merge(Serialized::global("section100.key25", 7))
+}
+
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    let (mut iters, start) = (0u32, Instant::now());
+    while start.elapsed() < Duration::from_secs(1) {
+        black_box(f());
+        iters += 1;
+    }
+
+    println!("{:>24}: {:>10?}/iter", name, start.elapsed() / iters);
+}
+
+fn main() {
+    let figment = figment();
+    bench("merge then find", || {
+        figment.extract::<Value>().unwrap().find("section100.key25")
+    });
+
+    bench("extract_inner", || {
+        figment.extract_inner::<usize>("section100.key25").unwrap()
+    });
+
+    bench("extract_inner (merged)", || {
+        figment.extract_inner::<Dict>("section100").unwrap()
+    });
+
+    bench("find_value", || figment.find_value("section199.key49").unwrap());
+    bench("contains", || figment.contains("section3.key4"));
+}
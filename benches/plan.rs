@@ -0,0 +1,53 @@
+//! Measures repeatedly extracting a configuration from a large figment via
+//! `Figment::extract()` and via a precompiled `Plan`.
+//!
+//! Run with `cargo bench --bench plan`.
+
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use figment::{Figment, providers::Serialized, schema::UnknownPolicy, value::{Dict, Value}};
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Config {
+    name: String,
+    sections: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+fn figment() -> Figment {
+    let sections: Dict = (0..200)
+        .map(|n| {
+            let keys: Dict = (0..50).map(|i| (format!("key{}", i), Value::from(n * i))).collect();
+            (format!("section{}", n), keys.into())
+        })
+        .collect();
+
+    Figment::new()
+        .merge(Serialized::default("name", "app"))
+        .merge(Serialized::default("sections", sections))
+        .merge(Serialized::default("name", "prod-app").profile("prod"))
+        .unknown_keys(UnknownPolicy::DenyAll)
+        .select("prod")
+}
+
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    let (mut iters, start) = (0u32, Instant::now());
+    while start.elapsed() < Duration::from_secs(1) {
+        black_box(f());
+        iters += 1;
+    }
+
+    println!("{:>24}: {:>10?}/iter", name, start.elapsed() / iters);
+}
+
+fn main() {
+    let figment = figment();
+    let plan = figment.plan::<Config>().unwrap();
+
+    bench("extract", || figment.extract::<Config>().unwrap());
+    bench("plan.extract", || plan.extract().unwrap());
+    bench("plan (compile)", || figment.plan::<Config>().unwrap());
+}
@@ -0,0 +1,52 @@
+//! Measures refreshing one provider of a large figment, incrementally via
+//! `Figment::replace_layer()` and by combining every provider again.
+//!
+//! Run with `cargo bench --bench remerge`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use figment::{Figment, providers::Serialized, util::map, value::{Dict, Value}};
+
+fn section(n: usize) -> Dict {
+    (0..50).map(|i| (format!("key{}", i), Value::from(n * i))).collect()
+}
+
+fn providers(port: u16) -> Vec<Serialized<Dict>> {
+    let mut providers: Vec<_> = (0..10)
+        .map(|p| {
+            let dict: Dict = (0..200).map(|n| (format!("section{}", n), section(n * p).into())).collect();
+            Serialized::defaults(dict)
+        })
+        .collect();
+
+    let server: Dict = map!["port".into() => port.into()];
+    let overrides: Dict = map!["server".into() => server.into()];
+    providers.push(Serialized::defaults(overrides));
+    providers
+}
+
+fn rebuild(port: u16) -> Figment {
+    providers(port).into_iter().fold(Figment::new().retain_layers(true), Figment::merge)
+}
+
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    let (mut iters, start) = (0u32, Instant::now());
+    while start.elapsed() < Duration::from_secs(1) {
+        black_box(f());
+        iters += 1;
+    }
+
+    println!("{:>24}: {:>10?}/iter", name, start.elapsed() / iters);
+}
+
+fn main() {
+    let figment = rebuild(80);
+    let layers = figment.layers();
+    let refreshed = providers(8080).pop().unwrap();
+
+    bench("rebuild", || rebuild(8080));
+    bench("with_layers", || figment.with_layers(&layers));
+    bench("replace_layer", || figment.clone().replace_layer(10, refreshed.clone()));
+    bench("clone (baseline)", || figment.clone());
+}
@@ -73,7 +73,16 @@ if [ "$1" = "--core" ]; then
     env
     json
     yaml
+    toml
     test
+    global
+    http
+    app
+    watch
+    schema
+    http,toml
+    http,json,yaml
+    schema,schemars
   )
 
   echo ":: Building and testing core [no features]..."
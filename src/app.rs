@@ -0,0 +1,231 @@
+//! A standard layering of configuration sources for applications.
+//!
+//! See [`AppConfig`].
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::{Figment, Profile, Provider};
+use crate::error::Result;
+use crate::providers::Env;
+
+/// A stage of the layering applied by [`AppConfig`], from lowest to highest
+/// precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Stage {
+    /// Built-in defaults, via [`AppConfig::defaults()`].
+    Defaults,
+    /// System-wide configuration files, via [`AppConfig::system()`].
+    System,
+    /// Per-user configuration files, via [`AppConfig::user()`].
+    User,
+    /// Environment variables, via [`AppConfig::env()`].
+    Env,
+    /// Command-line arguments, via [`AppConfig::cli()`].
+    Cli,
+}
+
+impl Stage {
+    const ALL: [Stage; 5] = [Stage::Defaults, Stage::System, Stage::User, Stage::Env, Stage::Cli];
+}
+
+/// A builder for the conventional layering of an application's configuration:
+/// defaults, then system files, then user files, then environment variables,
+/// then command-line arguments, each overriding the last.
+///
+/// Rather than each application hand-rolling a slightly different precedence,
+/// `AppConfig` fixes the order of the [`Stage`]s. Providers are added to a
+/// stage with the stage's method, such as [`AppConfig::user()`], and are
+/// [merged](Figment::merge()) in the order of their stage and then in the
+/// order they were added. After a stage's providers are merged, the stage's
+/// [hooks](AppConfig::hook()) are applied.
+///
+/// Unless another provider is added to [`Stage::Env`], the environment stage
+/// consists of `Env::prefixed("{NAME}_").split("__")`, where `{NAME}` is the
+/// application's name in uppercase with `-` replaced by `_`.
+///
+/// The selected profile is read from the environment variable
+/// `{NAME}_PROFILE`, which is otherwise ignored, or is the profile set via
+/// [`AppConfig::profile()`], or is [`Profile::Default`]. The variable can be
+/// changed with [`AppConfig::profile_env()`].
+///
+/// # Example
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use figment::{Jail, app::{AppConfig, Stage}};
+/// use figment::providers::{Format, Toml, Serialized};
+///
+/// #[derive(Debug, Default, Serialize, Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+///     verbose: bool,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Cli {
+///     verbose: Option<bool>,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("system.toml", "name = \"system\"\nport = 80")?;
+///     jail.create_file("user.toml", "[debug]\nport = 8080")?;
+///     jail.set_env("MY_APP_NAME", "env");
+///     jail.set_env("MY_APP_PROFILE", "debug");
+///
+///     let cli = || Cli { verbose: Some(true) };
+///     let config: Config = AppConfig::new("my-app")
+///         .defaults(Serialized::defaults(Config::default()))
+///         .system(Toml::file("system.toml"))
+///         .user(Toml::file("user.toml").nested())
+///         .cli(Serialized::defaults(cli()).skip_none())
+///         .extract()?;
+///
+///     assert_eq!(config.name, "env");
+///     assert_eq!(config.port, 8080);
+///     assert!(config.verbose);
+///
+///     // Hooks can adjust the figment after any stage.
+///     let config: Config = AppConfig::new("my-app")
+///         .defaults(Serialized::defaults(Config::default()))
+///         .cli(Serialized::defaults(cli()).skip_none())
+///         .hook(Stage::Defaults, |figment| figment.merge(("port", 9000)))
+///         .extract()?;
+///
+///     assert_eq!(config.port, 9000);
+///     assert!(config.verbose);
+///
+///     Ok(())
+/// });
+/// ```
+pub struct AppConfig {
+    name: String,
+    profile: Profile,
+    profile_env: String,
+    providers: Vec<(Stage, Box<dyn Provider>)>,
+    hooks: Vec<(Stage, Box<Hook>)>,
+}
+
+type Hook = dyn Fn(Figment) -> Figment;
+
+impl AppConfig {
+    /// Returns a builder for the configuration of the application `name`.
+    pub fn new(name: &str) -> Self {
+        let prefix = name.to_ascii_uppercase().replace('-', "_");
+        AppConfig {
+            name: name.into(),
+            profile: Profile::Default,
+            profile_env: format!("{}_PROFILE", prefix),
+            providers: vec![],
+            hooks: vec![],
+        }
+    }
+
+    /// Adds `provider` to [`Stage::Defaults`].
+    pub fn defaults<P: Provider + 'static>(self, provider: P) -> Self {
+        self.add(Stage::Defaults, provider)
+    }
+
+    /// Adds `provider` to [`Stage::System`].
+    pub fn system<P: Provider + 'static>(self, provider: P) -> Self {
+        self.add(Stage::System, provider)
+    }
+
+    /// Adds `provider` to [`Stage::User`].
+    pub fn user<P: Provider + 'static>(self, provider: P) -> Self {
+        self.add(Stage::User, provider)
+    }
+
+    /// Adds `provider` to [`Stage::Env`], replacing the default environment
+    /// provider.
+    pub fn env<P: Provider + 'static>(self, provider: P) -> Self {
+        self.add(Stage::Env, provider)
+    }
+
+    /// Adds `provider` to [`Stage::Cli`].
+    pub fn cli<P: Provider + 'static>(self, provider: P) -> Self {
+        self.add(Stage::Cli, provider)
+    }
+
+    /// Adds `provider` to `stage`.
+    pub fn add<P: Provider + 'static>(mut self, stage: Stage, provider: P) -> Self {
+        self.providers.push((stage, Box::new(provider)));
+        self
+    }
+
+    /// Adds a hook applied to the figment after the providers of `stage` are
+    /// merged, allowing arbitrary adjustments at that point of the layering.
+    /// Hooks for the same stage are applied in the order they were added.
+    pub fn hook<F>(mut self, stage: Stage, hook: F) -> Self
+        where F: Fn(Figment) -> Figment + 'static
+    {
+        self.hooks.push((stage, Box::new(hook)));
+        self
+    }
+
+    /// Sets the profile selected when the profile environment variable is not
+    /// set. Defaults to [`Profile::Default`].
+    pub fn profile<P: Into<Profile>>(mut self, profile: P) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    /// Sets the environment variable the selected profile is read from.
+    /// Defaults to `{NAME}_PROFILE`.
+    pub fn profile_env(mut self, name: &str) -> Self {
+        self.profile_env = name.into();
+        self
+    }
+
+    /// Builds the figment.
+    pub fn figment(&self) -> Figment {
+        let prefix = format!("{}_", self.name.to_ascii_uppercase().replace('-', "_"));
+        let profile_key = self.profile_env.strip_prefix(&prefix)
+            .map(|key| key.to_ascii_lowercase());
+
+        let default_env = match profile_key {
+            Some(key) => Env::prefixed(&prefix).ignore(&[&key]).split("__"),
+            None => Env::prefixed(&prefix).split("__"),
+        };
+
+        let has_env = self.providers.iter().any(|(stage, _)| *stage == Stage::Env);
+        let mut figment = Figment::new();
+        for stage in Stage::ALL {
+            if stage == Stage::Env && !has_env {
+                figment = figment.merge(&default_env);
+            }
+
+            for (_, provider) in self.providers.iter().filter(|(s, _)| *s == stage) {
+                figment = figment.merge(&**provider);
+            }
+
+            for (_, hook) in self.hooks.iter().filter(|(s, _)| *s == stage) {
+                figment = hook(figment);
+            }
+        }
+
+        figment.select(Profile::from_env_or(&self.profile_env, self.profile.clone()))
+    }
+
+    /// Builds the figment and extracts a `T` from it.
+    pub fn extract<'a, T: Deserialize<'a>>(&self) -> Result<T> {
+        self.figment().extract()
+    }
+}
+
+impl fmt::Debug for AppConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stages: Vec<_> = self.providers.iter()
+            .map(|(stage, provider)| (stage, provider.metadata().name))
+            .collect();
+
+        f.debug_struct("AppConfig")
+            .field("name", &self.name)
+            .field("profile", &self.profile)
+            .field("profile_env", &self.profile_env)
+            .field("providers", &stages)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,153 @@
+use std::future::Future;
+
+use crate::{Profile, Provider, Error, Metadata};
+use crate::value::{Map, Dict};
+
+/// Trait implemented by configuration source providers whose data is produced
+/// asynchronously, such as providers that fetch data over the network.
+///
+/// An `AsyncProvider` is like a [`Provider`] whose [`data()`] method is
+/// `async`. It is combined into a figment by awaiting
+/// [`Figment::merge_async()`] or [`Figment::join_async()`], after which the
+/// figment is used as usual. Figment doesn't depend on or spawn tasks on any
+/// particular async runtime: the returned future is polled by the caller's.
+///
+/// To combine a synchronous [`Provider`] in the same asynchronous chain, wrap
+/// it in [`Blocking`], or simply combine it via the synchronous methods on
+/// the awaited figment.
+///
+/// Because [`AsyncProvider::data()`] returns an `impl Future`, this trait
+/// requires Rust 1.75 or newer. It's only available with the `async` feature,
+/// so the rest of the crate isn't held to the same minimum version.
+///
+/// [`data()`]: AsyncProvider::data()
+/// [`Figment::merge_async()`]: crate::Figment::merge_async()
+/// [`Figment::join_async()`]: crate::Figment::join_async()
+/// [`Blocking`]: crate::providers::Blocking
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use figment::{Figment, AsyncProvider, Metadata, Profile, Source, Error};
+/// use figment::providers::{Blocking, Serialized};
+/// use figment::value::{Map, Dict};
+///
+/// /// A provider that fetches its data from a given URL.
+/// struct NetProvider {
+///     url: String,
+/// }
+///
+/// impl AsyncProvider for NetProvider {
+///     fn metadata(&self) -> Metadata {
+///         Metadata::named("Network").source(Source::Url(self.url.clone()))
+///     }
+///
+///     async fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+///         // Fetch from the network without blocking, deserialize into a `Dict`.
+///         let dict = figment::util::map!["port".to_string() => 8080.into()];
+///         Ok(Profile::Default.collect(dict))
+///     }
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     use std::{pin::pin, sync::Arc, task::{Context, Poll, Wake}, thread::{self, Thread}};
+/// #     struct Unpark(Thread);
+/// #     impl Wake for Unpark { fn wake(self: Arc<Self>) { self.0.unpark() } }
+/// #     let waker = Arc::new(Unpark(thread::current())).into();
+/// #     let mut future = pin!(future);
+/// #     loop {
+/// #         match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+/// #             Poll::Ready(v) => return v,
+/// #             Poll::Pending => thread::park(),
+/// #         }
+/// #     }
+/// # }
+/// # block_on(async {
+/// let figment = Figment::new()
+///     .merge(Serialized::default("name", "app"))
+///     .merge_async(NetProvider { url: "https://config.example.com".into() }).await
+///     .merge_async(Blocking(("port", 9090))).await;
+///
+/// let config: Config = figment.extract().unwrap();
+/// assert_eq!(config.name, "app");
+/// assert_eq!(config.port, 9090);
+/// # });
+/// ```
+pub trait AsyncProvider {
+    /// Returns the [`Metadata`] for this provider, identifying itself and its
+    /// configuration sources. See [`Provider::metadata()`].
+    fn metadata(&self) -> Metadata;
+
+    /// Returns a future resolving to the configuration data. See
+    /// [`Provider::data()`]. Implementations typically write `async fn
+    /// data(&self)`.
+    fn data(&self) -> impl Future<Output = Result<Map<Profile, Dict>, Error>>;
+
+    /// Optionally returns a profile to set on the [`Figment`](crate::Figment)
+    /// this provider is merged into. See [`Provider::profile()`].
+    fn profile(&self) -> Option<Profile> {
+        None
+    }
+}
+
+/// An [`AsyncProvider`] adapter over a synchronous [`Provider`], allowing
+/// synchronous providers to be combined via
+/// [`Figment::merge_async()`](crate::Figment::merge_async()) and
+/// [`Figment::join_async()`](crate::Figment::join_async()) and passed
+/// wherever an `AsyncProvider` is expected.
+///
+/// The wrapped provider's [`Provider::data()`] is called, and so blocks,
+/// when the data is requested, as it would be when combined synchronously.
+/// See [`AsyncProvider`] for an example.
+#[derive(Debug, Clone)]
+pub struct Blocking<P>(pub P);
+
+impl<P: Provider> AsyncProvider for Blocking<P> {
+    fn metadata(&self) -> Metadata {
+        self.0.metadata()
+    }
+
+    fn data(&self) -> impl Future<Output = Result<Map<Profile, Dict>, Error>> {
+        std::future::ready(self.0.data())
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.0.profile()
+    }
+}
+
+/// The resolved metadata, data, and profile of an [`AsyncProvider`].
+pub(crate) struct Resolved {
+    pub metadata: Metadata,
+    pub data: Result<Map<Profile, Dict>, Error>,
+    pub profile: Option<Profile>,
+}
+
+impl Resolved {
+    /// Awaits the data of `provider`.
+    pub async fn from<T: AsyncProvider>(provider: T) -> Resolved {
+        let data = provider.data().await;
+        Resolved { data, metadata: provider.metadata(), profile: provider.profile() }
+    }
+}
+
+impl Provider for Resolved {
+    fn metadata(&self) -> Metadata {
+        self.metadata.clone()
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        self.data.clone()
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.profile.clone()
+    }
+}
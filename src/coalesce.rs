@@ -1,14 +1,172 @@
-use crate::Profile;
-use crate::value::{Value, Map};
+use crate::{Profile, Resolution, Error, MergeDecision};
+use crate::error::Kind;
+use crate::value::{Value, Map, Dict, Empty};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// The strategy a provider was combined into a figment with. See
+/// [conflict resolution](crate::Figment#conflict-resolution) and
+/// [`Figment::layers()`](crate::Figment::layers()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Order {
+    /// Via [`Figment::merge()`](crate::Figment::merge()).
     Merge,
+    /// Via [`Figment::join()`](crate::Figment::join()).
     Join,
+    /// Via [`Figment::adjoin()`](crate::Figment::adjoin()).
     Adjoin,
+    /// Via [`Figment::admerge()`](crate::Figment::admerge()).
     Admerge,
 }
 
+/// The strategy with which conflicting arrays are combined, overriding the
+/// [`Order`]'s. See [`Figment::arrays()`](crate::Figment::arrays()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArrayMerge {
+    /// Arrays are combined like non-composite values: `join` and `adjoin`
+    /// keep the existing array while `merge` and `admerge` use the incoming.
+    Replace,
+    /// The incoming array is appended to the existing array.
+    Append,
+    /// The incoming array is prepended to the existing array.
+    Prepend,
+    /// Elements at the same index are combined with the provider's
+    /// [`Order`], recursively. Trailing elements of the longer array are kept.
+    Index,
+}
+
+impl ArrayMerge {
+    /// Combines the values `old` and `new` with `order`, combining arrays as
+    /// directed by `self`.
+    pub(crate) fn coalesce(self, old: Value, new: Value, order: Order) -> Value {
+        use {Value::Array as A, Order::*};
+
+        match (self, old, new, order) {
+            (ArrayMerge::Replace, old, new, Join | Adjoin) => old.coalesce(new, Join),
+            (ArrayMerge::Replace, old, new, Merge | Admerge) => old.coalesce(new, Merge),
+            (ArrayMerge::Append, A(t, mut a), A(_, b), _) => A(t, { a.extend(b); a }),
+            (ArrayMerge::Prepend, A(t, a), A(_, mut b), _) => A(t, { b.extend(a); b }),
+            (ArrayMerge::Index, A(t, a), A(_, b), Join | Adjoin)
+                | (ArrayMerge::Index, A(_, a), A(t, b), Merge | Admerge) =>
+            {
+                let (mut a, mut b) = (a.into_iter(), b.into_iter());
+                let mut combined = vec![];
+                loop {
+                    match (a.next(), b.next()) {
+                        (Some(old), Some(new)) => combined.push(old.coalesce(new, order)),
+                        (Some(v), None) | (None, Some(v)) => combined.push(v),
+                        (None, None) => break A(t, combined),
+                    }
+                }
+            }
+            (_, old, new, _) => old.coalesce(new, order),
+        }
+    }
+
+    /// The decision `self` makes when combining two arrays with `order`.
+    pub(crate) fn decision(self, order: Order) -> MergeDecision {
+        match (self, order) {
+            (ArrayMerge::Replace, Order::Join | Order::Adjoin) => MergeDecision::KeptExisting,
+            (ArrayMerge::Replace, Order::Merge | Order::Admerge) => MergeDecision::Replaced,
+            (ArrayMerge::Append | ArrayMerge::Prepend, _) => MergeDecision::Concatenated,
+            (ArrayMerge::Index, _) => MergeDecision::Combined,
+        }
+    }
+}
+
+/// How a value of one type that conflicts with a value of another type is
+/// resolved, for instance an integer `9090` from a TOML file meeting a string
+/// `"8080"` from a JSON file. See
+/// [`Figment::type_conflicts()`](crate::Figment::type_conflicts()).
+///
+/// Values conflict in type when they are of different kinds: strings,
+/// characters, booleans, numbers, datetimes, dictionaries, and arrays are each
+/// a distinct kind. Numbers of different widths or signedness, like `8080` and
+/// `-1` or `1.5`, do not conflict, nor does a null/unit value with any other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TypeConflict {
+    /// The value preferred by the combining strategy is used irrespective of
+    /// its type, as described in [conflict
+    /// resolution](crate::Figment#conflict-resolution). This is the default.
+    #[default]
+    PreferHigherLayer,
+    /// When a string conflicts with a boolean or number, the string is parsed
+    /// as a value of that type. If the string is the value preferred by the
+    /// combining strategy and it parses, the parsed value is used; otherwise
+    /// the boolean or number is used. All other conflicts are resolved as with
+    /// [`TypeConflict::PreferHigherLayer`].
+    PreferTyped,
+    /// Any conflict is an error.
+    Error,
+}
+
+impl TypeConflict {
+    /// Resolves the conflict, if any, between the `existing` and `incoming`
+    /// values at `key` combined with `order`.
+    pub(crate) fn resolve(
+        self,
+        key: &str,
+        existing: &Value,
+        incoming: &Value,
+        order: Order,
+    ) -> Result<Resolution, Error> {
+        let (old_kind, new_kind) = (kind(existing), kind(incoming));
+        if old_kind == new_kind || old_kind.is_none() || new_kind.is_none() {
+            return Ok(Resolution::Default);
+        }
+
+        let incoming_wins = matches!(order, Order::Merge | Order::Admerge);
+        match self {
+            TypeConflict::PreferHigherLayer => Ok(Resolution::Default),
+            TypeConflict::PreferTyped => {
+                let (winner, loser) = match incoming_wins {
+                    true => (incoming, existing),
+                    false => (existing, incoming),
+                };
+
+                let keep_loser = match incoming_wins {
+                    true => Resolution::KeepExisting,
+                    false => Resolution::UseIncoming,
+                };
+
+                match (winner, loser) {
+                    (Value::String(tag, string), Value::Bool(..) | Value::Num(..)) => {
+                        let mut parsed: Value = string.parse().expect("infallible");
+                        if kind(&parsed) != kind(loser) {
+                            return Ok(keep_loser);
+                        }
+
+                        parsed.map_tag(|t| *t = *tag);
+                        Ok(Resolution::Value(parsed))
+                    }
+                    _ => Ok(Resolution::Default),
+                }
+            }
+            TypeConflict::Error => {
+                let expected = format!("{} like the previously provided value", old_kind.unwrap());
+                let error = Error::from(Kind::InvalidType(incoming.to_actual(), expected))
+                    .with_path(key)
+                    .retagged(incoming.tag());
+
+                Err(error)
+            }
+        }
+    }
+}
+
+/// The kind of `value` for the purposes of [`TypeConflict`], or `None` if it
+/// is null/unit and thus conflicts with nothing.
+fn kind(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::String(..) => Some("a string"),
+        Value::Char(..) => Some("a character"),
+        Value::Bool(..) => Some("a boolean"),
+        Value::Num(..) => Some("a number"),
+        Value::Datetime(..) => Some("a datetime"),
+        Value::Dict(..) => Some("a dictionary"),
+        Value::Array(..) => Some("an array"),
+        Value::Empty(..) => None,
+    }
+}
+
 pub trait Coalescible: Sized {
     fn coalesce(self, other: Self, order: Order) -> Self;
     fn merge(self, other: Self) -> Self { self.coalesce(other, Order::Merge) }
@@ -49,3 +207,88 @@ impl<K: Eq + std::hash::Hash + Ord, V: Coalescible> Coalescible for Map<K, V> {
         joined
     }
 }
+
+/// Coalesces the arrays `old` and `new` with `order` element-wise: an element
+/// of `new` that is a dictionary with a `field` equal to that of an element of
+/// `old` is coalesced with that element. All other elements of `new` are
+/// appended in order.
+pub fn coalesce_by(field: &str, old: Value, new: Value, order: Order) -> Value {
+    use {Value::Array as A, Order::*};
+
+    let id = |value: &Value| value.as_dict().and_then(|dict| dict.get(field)).cloned();
+    let (tag, mut combined, new) = match (old, new, order) {
+        (A(t, a), A(_, b), Join | Adjoin) | (A(_, a), A(t, b), Merge | Admerge) => (t, a, b),
+        (old, new, _) => return old.coalesce(new, order),
+    };
+
+    for value in new {
+        let existing = id(&value).and_then(|new_id| {
+            combined.iter().position(|v| matches!(id(v), Some(id) if id.content_eq(&new_id)))
+        });
+
+        match existing {
+            Some(i) => {
+                let old = std::mem::replace(&mut combined[i], Value::from(Empty::Unit));
+                combined[i] = old.coalesce(value, order);
+            }
+            None => combined.push(value),
+        }
+    }
+
+    A(tag, combined)
+}
+
+/// Coalesces `new` into `old` with `order`, consulting `hook` for every key
+/// in `new` to determine how the key's values are combined.
+pub fn coalesce_with<H>(old: Map<Profile, Dict>, new: Map<Profile, Dict>, order: Order, hook: &H) -> Map<Profile, Dict>
+    where H: Fn(&Profile, &str, Option<&Value>, &Value) -> Resolution
+{
+    let mut old = old;
+    for (profile, new_dict) in new {
+        let old_dict = old.remove(&profile).unwrap_or_default();
+        let dict = coalesce_dict(&profile, "", old_dict, new_dict, order, hook);
+        old.insert(profile, dict);
+    }
+
+    old
+}
+
+fn coalesce_dict<H>(profile: &Profile, prefix: &str, mut old: Dict, new: Dict, o: Order, hook: &H) -> Dict
+    where H: Fn(&Profile, &str, Option<&Value>, &Value) -> Resolution
+{
+    use {Value::Dict as D, Order::*};
+
+    let incoming = |path: &str, value: Value| match value {
+        D(t, dict) => D(t, coalesce_dict(profile, path, Dict::new(), dict, o, hook)),
+        value => value,
+    };
+
+    for (key, new_value) in new {
+        let path = match prefix.is_empty() {
+            true => key.clone(),
+            false => format!("{}.{}", prefix, key),
+        };
+
+        let old_value = old.remove(&key);
+        let value = match hook(profile, &path, old_value.as_ref(), &new_value) {
+            Resolution::Default => match (old_value, new_value, o) {
+                (Some(D(t, a)), D(_, b), Join | Adjoin) | (Some(D(_, a)), D(t, b), Merge | Admerge) => {
+                    Some(D(t, coalesce_dict(profile, &path, a, b, o, hook)))
+                }
+                (Some(v), new, Join | Adjoin) => Some(v.coalesce(new, o)),
+                (Some(v), new, Merge | Admerge) => Some(v.coalesce(incoming(&path, new), o)),
+                (None, new, _) => Some(incoming(&path, new)),
+            },
+            Resolution::KeepExisting => old_value,
+            Resolution::UseIncoming => Some(new_value),
+            Resolution::Remove => None,
+            Resolution::Value(value) => Some(value),
+        };
+
+        if let Some(value) = value {
+            old.insert(key, value);
+        }
+    }
+
+    old
+}
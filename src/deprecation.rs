@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::{Profile, Metadata};
+
+/// A deprecated key that is set in a figment's configuration, returned by
+/// [`Figment::deprecations()`](crate::Figment::deprecations()).
+///
+/// Keys are marked deprecated via
+/// [`Figment::deprecate()`](crate::Figment::deprecate()). `Display`s as:
+///
+/// ```text
+/// `$key` is deprecated: $note (in $metadata.source $metadata.name)
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deprecation {
+    /// The deprecated key path, as marked.
+    pub key: String,
+    /// The note given when the key was marked deprecated, typically what to
+    /// use instead.
+    pub note: String,
+    /// The profile the value came from.
+    pub profile: Profile,
+    /// The metadata of the provider that set the value, if known.
+    pub metadata: Option<Metadata>,
+}
+
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is deprecated", self.key)?;
+        if !self.note.is_empty() {
+            write!(f, ": {}", self.note)?;
+        }
+
+        match &self.metadata {
+            Some(md) => match &md.source {
+                Some(source) => write!(f, " (in {} {})", source, md.name),
+                None => write!(f, " (in {})", md.name),
+            },
+            None => Ok(()),
+        }
+    }
+}
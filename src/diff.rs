@@ -0,0 +1,108 @@
+use crate::{Figment, Metadata};
+use crate::value::{Value, Map, Visit};
+
+/// A key whose value differs between two figments, returned by
+/// [`Figment::diff()`].
+///
+/// Values are compared at their leaves: every key path to a value that is not
+/// a non-empty dictionary, including arrays, is compared as a whole. Numbers
+/// are compared by value, irrespective of their width or signedness.
+///
+/// [`Figment::diff()`]: crate::Figment::diff()
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, ChangeKind};
+///
+/// let old = Figment::from(("port", 80)).merge(("host", "localhost"));
+/// let new = Figment::from(("port", 8080)).merge(("host", "localhost"));
+///
+/// let changes = old.diff(&new).unwrap();
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].key, "port");
+/// assert_eq!(changes[0].kind, ChangeKind::Changed);
+/// assert_eq!(changes[0].old.as_ref().and_then(|v| v.to_i128()), Some(80));
+/// assert_eq!(changes[0].new.as_ref().and_then(|v| v.to_i128()), Some(8080));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// The key path of the value, for instance `a.b.c`.
+    pub key: String,
+    /// Whether the key was added, removed, or changed.
+    pub kind: ChangeKind,
+    /// The value in the original figment, if there was one.
+    pub old: Option<Value>,
+    /// The value in the other figment, if there is one.
+    pub new: Option<Value>,
+    /// The metadata of the provider of `old`, if known.
+    pub old_metadata: Option<Metadata>,
+    /// The metadata of the provider of `new`, if known.
+    pub new_metadata: Option<Metadata>,
+}
+
+/// The kind of a [`Change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The key is only present in the other figment.
+    Added,
+    /// The key is only present in the original figment.
+    Removed,
+    /// The key is present in both figments with different values.
+    Changed,
+}
+
+/// Returns the value at every leaf of `figment`'s merged value by key path.
+fn leaves(figment: &Figment) -> crate::Result<Map<String, Value>> {
+    let mut leaves = Map::new();
+    figment.merged()?.walk(&mut |path: &[String], value: &Value| {
+        match value.as_dict() {
+            Some(dict) if !dict.is_empty() || path.is_empty() => Visit::Continue,
+            _ => {
+                leaves.insert(path.join("."), value.clone());
+                Visit::SkipChildren
+            }
+        }
+    });
+
+    Ok(leaves)
+}
+
+/// Returns the changes from `old` to `new`, ordered by key path.
+pub(crate) fn diff(old: &Figment, new: &Figment) -> crate::Result<Vec<Change>> {
+    let (mut old_leaves, new_leaves) = (leaves(old)?, leaves(new)?);
+    let metadata = |figment: &Figment, value: Option<&Value>| {
+        value.and_then(|v| figment.get_metadata(v.tag())).cloned()
+    };
+
+    let mut changes = vec![];
+    for (key, new_value) in new_leaves {
+        let old_value = old_leaves.remove(&key);
+        let kind = match &old_value {
+            Some(old_value) if old_value.content_eq(&new_value) => continue,
+            Some(_) => ChangeKind::Changed,
+            None => ChangeKind::Added,
+        };
+
+        changes.push(Change {
+            key,
+            kind,
+            old_metadata: metadata(old, old_value.as_ref()),
+            new_metadata: metadata(new, Some(&new_value)),
+            old: old_value,
+            new: Some(new_value),
+        });
+    }
+
+    changes.extend(old_leaves.into_iter().map(|(key, old_value)| Change {
+        key,
+        kind: ChangeKind::Removed,
+        old_metadata: metadata(old, Some(&old_value)),
+        new_metadata: None,
+        old: Some(old_value),
+        new: None,
+    }));
+
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(changes)
+}
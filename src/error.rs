@@ -5,7 +5,11 @@ use std::borrow::Cow;
 
 use serde::{ser, de};
 
-use crate::{Figment, Profile, Metadata, value::Tag};
+use crate::{Figment, Profile, Metadata, value::{Tag, Limit}};
+
+#[cfg(feature = "diagnostics")]
+#[cfg_attr(nightly, doc(cfg(feature = "diagnostics")))]
+pub use crate::pretty::Pretty;
 
 /// A simple alias to `Result` with an error type of [`Error`].
 pub type Result<T> = std::result::Result<T, Error>;
@@ -73,6 +77,35 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// invalid type: found sequence, expected u16: `staging.port` in TOML file Config.toml
 /// ```
 ///
+/// The profile the value came from and the profile that was selected, when
+/// known, are available as [`Error::profile`] and [`Error::selected()`]. If the
+/// value came from a profile other than the default or selected profile, such
+/// as the global profile, both follow:
+///
+/// ```rust
+/// use figment::{Figment, providers::{Format, Toml}};
+///
+/// let figment = Figment::from(Toml::string(r#"
+///     [default]
+///     port = "eighty"
+///
+///     [prod]
+///     name = "app"
+///
+///     [global]
+///     workers = "four"
+/// "#).nested()).select("prod");
+///
+/// let error = figment.extract_inner::<u16>("port").unwrap_err();
+/// assert_eq!(error.profile.as_ref().unwrap(), "default");
+/// assert_eq!(error.selected().unwrap(), "prod");
+/// assert!(!error.to_string().contains("profile"));
+///
+/// let error = figment.extract_inner::<u16>("workers").unwrap_err();
+/// assert_eq!(error.profile.as_ref().unwrap(), "global");
+/// assert!(error.to_string().ends_with("(profile `global`, selected `prod`)"));
+/// ```
+///
 /// # Iterator
 ///
 /// An `Error` may contain more than one error. To process all errors, iterate
@@ -89,7 +122,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub struct Error {
     /// The tag of the value that errored. We use this to lookup the `metadata`.
     tag: Tag,
-    /// The profile that was selected when the error occured, if any.
+    /// The profile the value that errored came from, if known, or else the
+    /// profile that was selected when the error occured, if any.
     pub profile: Option<Profile>,
     /// The metadata for the provider of the value that errored, if known.
     pub metadata: Option<Metadata>,
@@ -97,9 +131,32 @@ pub struct Error {
     pub path: Vec<String>,
     /// The error kind.
     pub kind: Kind,
+    context: Option<Box<Context>>,
     prev: Option<Box<Error>>,
 }
 
+/// Rarely present details of an error, boxed to keep `Error` small.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Context {
+    /// The profile that was selected when the error occured, if any.
+    selected: Option<Profile>,
+    /// A truncated rendering of the value that errored, if enabled.
+    sample: Option<String>,
+    /// A similarly spelled name, if any.
+    suggestion: Option<String>,
+    /// Why the error occured, when its kind alone doesn't say.
+    reason: Option<Reason>,
+}
+
+/// Why an error occured, when its [`Kind`] alone doesn't say.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Reason {
+    /// Extraction was cancelled via a [`Monitor`](crate::Monitor).
+    Cancelled,
+    /// Provided data exceeded a limit: (limit, maximum).
+    LimitExceeded(Limit, usize),
+}
+
 /// An error kind, encapsulating serde's [`serde::de::Error`].
 #[derive(Clone, Debug, PartialEq)]
 pub enum Kind {
@@ -141,6 +198,37 @@ pub enum Kind {
 }
 
 impl Error {
+    /// Returns the error reported when extraction is cancelled via a
+    /// [`Monitor`](crate::Monitor).
+    pub(crate) fn cancellation() -> Self {
+        let mut error = Error::from("extraction was cancelled");
+        error.context().reason = Some(Reason::Cancelled);
+        error
+    }
+
+    /// Returns the error reported when provided data exceeds `limit`, which
+    /// is set to `max`.
+    pub(crate) fn limit_exceeded(limit: Limit, max: usize) -> Self {
+        let mut error = Error::from(format!("{} exceeds the limit of {}", limit, max));
+        error.context().reason = Some(Reason::LimitExceeded(limit, max));
+        error
+    }
+
+    /// Returns the error's context, creating it if there is none.
+    fn context(&mut self) -> &mut Context {
+        self.context.get_or_insert_with(Box::default)
+    }
+
+    pub(crate) fn set_sample(&mut self, sample: String) {
+        self.context().sample = Some(sample);
+    }
+
+    pub(crate) fn set_suggestion(&mut self, suggestion: Option<&str>) {
+        if let Some(name) = suggestion {
+            self.context().suggestion = Some(name.into());
+        }
+    }
+
     pub(crate) fn prefixed(mut self, key: &str) -> Self {
         self.path.insert(0, key.into());
         self
@@ -154,12 +242,48 @@ impl Error {
         self
     }
 
+    /// Replaces every mention of the values in `secrets`, and any actual value
+    /// or sample, in `self` and every error it is chained to with `redacted`.
+    pub(crate) fn redacted(mut self, secrets: &[String], redacted: &str) -> Self {
+        let scrub = |mut string: String| {
+            for secret in secrets.iter().filter(|s| !s.is_empty()) {
+                string = string.replace(secret.as_str(), redacted);
+            }
+
+            string
+        };
+
+        let mut error = Some(&mut self);
+        while let Some(e) = error {
+            let actual = || Actual::Other(redacted.into());
+            e.kind = match std::mem::replace(&mut e.kind, Kind::Message(String::new())) {
+                Kind::Message(msg) => Kind::Message(scrub(msg)),
+                Kind::InvalidType(_, expected) => Kind::InvalidType(actual(), scrub(expected)),
+                Kind::InvalidValue(_, expected) => Kind::InvalidValue(actual(), scrub(expected)),
+                Kind::UnknownVariant(v, expected) => Kind::UnknownVariant(scrub(v), expected),
+                Kind::UnknownField(v, expected) => Kind::UnknownField(scrub(v), expected),
+                Kind::Unsupported(_) => Kind::Unsupported(actual()),
+                Kind::UnsupportedKey(_, expected) => Kind::UnsupportedKey(actual(), expected),
+                kind => kind,
+            };
+
+            if let Some(sample) = e.context.as_mut().and_then(|c| c.sample.as_mut()) {
+                *sample = redacted.into();
+            }
+
+            error = e.prev.as_deref_mut();
+        }
+
+        self
+    }
+
     pub(crate) fn resolved(mut self, config: &Figment) -> Self {
         let mut error = Some(&mut self);
         while let Some(e) = error {
             e.metadata = config.get_metadata(e.tag).cloned();
             e.profile = e.tag.profile()
                 .or_else(|| Some(config.profile().clone()));
+            e.context().selected = Some(config.profile().clone());
 
             error = e.prev.as_deref_mut();
         }
@@ -183,6 +307,242 @@ impl Error {
         matches!(self.kind, Kind::MissingField(..))
     }
 
+    /// The profile that was selected when the error occured, if any.
+    ///
+    /// See [Display](#display) for an example.
+    pub fn selected(&self) -> Option<&Profile> {
+        self.context.as_ref()?.selected.as_ref()
+    }
+
+    /// A truncated rendering of the value that errored, if enabled via
+    /// [`Figment::error_samples()`](crate::Figment::error_samples()).
+    pub fn sample(&self) -> Option<&str> {
+        self.context.as_ref()?.sample.as_deref()
+    }
+
+    /// A similarly spelled name, if any: for an unknown key, field, or
+    /// variant, an expected one; for a missing field, a provided key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Figment;
+    ///
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct Config {
+    ///     #[allow(dead_code)]
+    ///     database: String,
+    /// }
+    ///
+    /// let figment = Figment::from(("databse", "postgres"));
+    /// let error = figment.extract::<Config>().unwrap_err();
+    /// assert_eq!(error.suggestion(), Some("databse"));
+    /// ```
+    pub fn suggestion(&self) -> Option<&str> {
+        self.context.as_ref()?.suggestion.as_deref()
+    }
+
+    fn reason(&self) -> Option<Reason> {
+        self.context.as_ref()?.reason
+    }
+
+    /// Returns `true` if the error reports that extraction was cancelled via
+    /// a [`Monitor`](crate::Monitor). The error's kind is a
+    /// [`Kind::Message`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Monitor, CancellationToken};
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    ///
+    /// let monitor = Monitor::new().cancellation(token);
+    /// let figment = Figment::from(("port", 8080));
+    /// let error = figment.extract_inner_monitored::<u16>("port", &monitor).unwrap_err();
+    /// assert!(error.cancelled());
+    /// assert!(!figment.extract_inner::<u16>("host").unwrap_err().cancelled());
+    /// ```
+    pub fn cancelled(&self) -> bool {
+        self.reason() == Some(Reason::Cancelled)
+    }
+
+    /// Returns the limit and its maximum if the error reports that provided
+    /// data exceeded a limit set via
+    /// [`Figment::limits()`](crate::Figment::limits()). The error's kind is a
+    /// [`Kind::Message`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Figment;
+    /// use figment::value::{Limits, Limit};
+    ///
+    /// let figment = Figment::new()
+    ///     .limits(Limits::none().string_len(4))
+    ///     .merge(("name", "figment"));
+    ///
+    /// let error = figment.extract_inner::<String>("name").unwrap_err();
+    /// assert_eq!(error.limit(), Some((Limit::StringLength, 4)));
+    /// assert_eq!(error.path, vec!["name"]);
+    /// ```
+    pub fn limit(&self) -> Option<(Limit, usize)> {
+        match self.reason() {
+            Some(Reason::LimitExceeded(limit, max)) => Some((limit, max)),
+            _ => None,
+        }
+    }
+
+    /// Returns a process exit code categorizing the error, following the
+    /// conventions of BSD's `sysexits.h`, for CLIs that exit on configuration
+    /// errors. Only `self`, the first error in a chain, is categorized.
+    ///
+    /// | code | category      | kinds                                                 |
+    /// |------|---------------|-------------------------------------------------------|
+    /// | 65   | `EX_DATAERR`  | an invalid value: invalid types, values, lengths, and |
+    /// |      |               | variants, out of range integers, exceeded limits      |
+    /// | 78   | `EX_CONFIG`   | a malformed configuration: missing, unknown, and      |
+    /// |      |               | duplicate fields, and custom messages, which include  |
+    /// |      |               | provider errors like unreadable or unparsable files   |
+    /// | 70   | `EX_SOFTWARE` | a bug: an unsupported type or key type                |
+    /// | 75   | `EX_TEMPFAIL` | a cancelled extraction                                |
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Monitor, CancellationToken};
+    ///
+    /// let figment = Figment::from(("port", "eighty"));
+    /// assert_eq!(figment.extract_inner::<u16>("port").unwrap_err().exit_code(), 65);
+    /// assert_eq!(figment.extract_inner::<u16>("host").unwrap_err().exit_code(), 78);
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    ///
+    /// let monitor = Monitor::new().cancellation(token);
+    /// let error = figment.extract_inner_monitored::<u16>("port", &monitor).unwrap_err();
+    /// assert_eq!(error.exit_code(), 75);
+    /// ```
+    pub fn exit_code(&self) -> i32 {
+        match self.reason() {
+            Some(Reason::Cancelled) => return 75,
+            Some(Reason::LimitExceeded(..)) => return 65,
+            None => {}
+        }
+
+        match self.kind {
+            Kind::InvalidType(..) | Kind::InvalidValue(..) | Kind::InvalidLength(..)
+                | Kind::UnknownVariant(..) | Kind::ISizeOutOfRange(..)
+                | Kind::USizeOutOfRange(..) => 65,
+            Kind::MissingField(..) | Kind::UnknownField(..) | Kind::DuplicateField(..)
+                | Kind::Message(..) => 78,
+            Kind::Unsupported(..) | Kind::UnsupportedKey(..) => 70,
+        }
+    }
+
+    /// Returns a short, actionable summary of the error for the operator of
+    /// an application, as opposed to the full technical description produced
+    /// by `Display`. The summary names what to do, the key to do it to, the
+    /// source of the value, and the problem. Keys in providers without a
+    /// [`Source`](crate::Source), like [`Env`](crate::providers::Env), are
+    /// named as [interpolated](Metadata::interpolate()) by the provider, such
+    /// as by environment variable name without the prefix:
+    ///
+    /// ```text
+    /// $action `$key` in $source: $problem
+    /// ```
+    ///
+    /// Only `self`, the first error in a chain, is summarized. To summarize
+    /// every error, iterate over the error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, providers::{Format, Toml, Env}};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// #[serde(deny_unknown_fields)]
+    /// struct Config {
+    ///     host: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         port = "eighty"
+    ///         hots = "localhost"
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("Config.toml"));
+    ///     let summaries: Vec<_> = figment.extract_all_errors::<Config>()
+    ///         .unwrap_err()
+    ///         .into_iter()
+    ///         .map(|e| e.user_summary())
+    ///         .collect();
+    ///
+    ///     assert_eq!(summaries, [
+    ///         "remove `hots` in Config.toml: unknown key, expected `host` or `port`",
+    ///         "fix `port` in Config.toml: expected u16, found string \"eighty\"",
+    ///         "set `host`: a value is required",
+    ///     ]);
+    ///
+    ///     jail.set_env("APP_PORT", "-1");
+    ///     let figment = figment.merge(Env::prefixed("APP_"));
+    ///     let error = figment.extract_inner::<u16>("port").unwrap_err();
+    ///     assert_eq!(error.user_summary(),
+    ///         "fix `PORT` in `APP_` environment variable(s): \
+    ///         expected u16, found signed int `-1`");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn user_summary(&self) -> String {
+        let (action, problem) = match &self.kind {
+            Kind::MissingField(_) => ("set", "a value is required".to_string()),
+            Kind::UnknownField(_, exp) => {
+                ("remove", format!("unknown key, expected {}", OneOf(exp)))
+            }
+            Kind::DuplicateField(_) => ("remove", "duplicate key".to_string()),
+            Kind::InvalidType(v, exp) | Kind::InvalidValue(v, exp) => {
+                ("fix", format!("expected {}, found {}", exp, v))
+            }
+            Kind::InvalidLength(v, exp) => ("fix", format!("expected {}, found {} items", exp, v)),
+            Kind::UnknownVariant(v, exp) => {
+                ("fix", format!("expected {}, found `{}`", OneOf(exp), v))
+            }
+            kind => ("fix", kind.to_string()),
+        };
+
+        let mut path = self.path.clone();
+        if let Kind::MissingField(field) = &self.kind {
+            path.push(field.to_string());
+        }
+
+        // Keys in sources like files are named by path; others, like
+        // environment variables, are named by interpolation.
+        let key = match (&self.profile, &self.metadata) {
+            _ if path.is_empty() => None,
+            (Some(profile), Some(md)) if md.source.is_none() => {
+                Some(md.interpolate(profile, &path))
+            }
+            _ => Some(path.join(".")),
+        };
+
+        let source = self.metadata.as_ref().map(|md| match &md.source {
+            Some(source) => source.to_string(),
+            None => md.name.to_string(),
+        });
+
+        match (key, source) {
+            (Some(key), Some(source)) => format!("{} `{}` in {}: {}", action, key, source, problem),
+            (Some(key), None) => format!("{} `{}`: {}", action, key, problem),
+            (None, Some(source)) => format!("{}: {}", source, problem),
+            (None, None) => problem,
+        }
+    }
+
     /// Append the string `path` to the error's path.
     ///
     /// # Example
@@ -388,11 +748,15 @@ impl de::Error for Error {
     }
 
     fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
-        Kind::UnknownVariant(variant.into(), expected).into()
+        let mut error = Error::from(Kind::UnknownVariant(variant.into(), expected));
+        error.set_suggestion(crate::typos::suggest(variant, expected.iter().copied()));
+        error
     }
 
     fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
-        Kind::UnknownField(field.into(), expected).into()
+        let mut error = Error::from(Kind::UnknownField(field.into(), expected));
+        error.set_suggestion(crate::typos::suggest(field, expected.iter().copied()));
+        error
     }
 
     fn missing_field(field: &'static str) -> Self {
@@ -417,6 +781,7 @@ impl From<Kind> for Error {
             path: vec![],
             profile: None,
             metadata: None,
+            context: None,
             prev: None,
             kind,
         }
@@ -476,9 +841,39 @@ impl Display for Kind {
     }
 }
 
+impl Error {
+    /// Writes the kind of error, any suggestion, and any sample.
+    pub(crate) fn fmt_headline(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.kind.fmt(f)?;
+
+        match (&self.kind, self.suggestion()) {
+            (Kind::MissingField(_), Some(key)) => write!(f, "; did you misspell it as `{}`?", key)?,
+            (_, Some(name)) => write!(f, "; did you mean `{}`?", name)?,
+            (_, None) => {}
+        }
+
+        if let Some(sample) = self.sample() {
+            write!(f, ": {}", sample)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the profile the value came from and the selected profile.
+    pub(crate) fn fmt_profiles(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.profile.as_ref(), self.selected()) {
+            (Some(profile), Some(selected)) if profile != selected => {
+                write!(f, "profile `{}`, selected `{}`", profile, selected)
+            }
+            (Some(profile), _) | (None, Some(profile)) => write!(f, "profile `{}`", profile),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.kind.fmt(f)?;
+        self.fmt_headline(f)?;
 
         if let (Some(profile), Some(md)) = (&self.profile, &self.metadata) {
             if !self.path.is_empty() {
@@ -495,6 +890,17 @@ impl Display for Error {
             }
         }
 
+        // The profile is only noteworthy if it's neither the default nor the
+        // selected profile.
+        match (&self.profile, self.selected()) {
+            (Some(p), selected) if p != Profile::Default && selected != Some(p) => {
+                f.write_str(" (")?;
+                self.fmt_profiles(f)?;
+                f.write_str(")")?;
+            }
+            _ => {}
+        }
+
         if let Some(prev) = &self.prev {
             write!(f, "\n{}", prev)?;
         }
@@ -0,0 +1,184 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::{Error, Kind};
+use crate::value::{Value, Visit};
+
+/// Options for exporting a figment's effective configuration with
+/// [`Figment::export()`](crate::Figment::export()), for instance to dump it
+/// into a support bundle without leaking secrets.
+///
+/// Values at key paths matching a glob passed to [`ExportOptions::redact()`]
+/// are replaced with the string `"<redacted>"`. Values at key paths matching a
+/// glob passed to [`ExportOptions::encrypt()`] are replaced with the string
+/// returned by the cipher set via [`ExportOptions::encrypt_with()`]. If a key
+/// path matches both, the value is redacted.
+///
+/// # Globs
+///
+/// A glob is a key path, like `db.password`, whose components may contain `*`,
+/// which matches any sequence of characters within a key, and `?`, which
+/// matches any single character, or be `**`, which matches any number of keys,
+/// including none. For example, `*.password` matches `db.password` but not
+/// `password` or `db.main.password`, while `**.password` matches all three.
+/// The keys of array elements are their indices, so `servers.*.key` matches
+/// the `key` of every element of the `servers` array. [`Env::include_globs()`]
+/// and [`Env::exclude_globs()`] use the same globs.
+///
+/// [`Env::include_globs()`]: crate::providers::Env::include_globs()
+/// [`Env::exclude_globs()`]: crate::providers::Env::exclude_globs()
+///
+/// # Encryption
+///
+/// Figment does not implement a cipher. Instead, the cipher is any function
+/// from plaintext to ciphertext, typically one encrypting with a key using a
+/// crate like `age` or `aes-gcm` and encoding the result as text. The
+/// plaintext is the value encoded as for
+/// [`Figment::export_env()`](crate::Figment::export_env()) except that every
+/// string is quoted, so that a string like `"123"` or `"true"` isn't mistaken
+/// for a number or boolean: parsing the decrypted plaintext as a [`Value`]
+/// recovers the value, with its type.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, ExportOptions, util::map};
+///
+/// let figment = Figment::from(("db", map!["user" => "admin", "password" => "hunter2"]))
+///     .merge(("api.token", "secret-token"))
+///     .merge(("api.port", 1234));
+///
+/// let options = ExportOptions::new()
+///     .redact(["**.password", "api.*token*"])
+///     .encrypt(["*.port"])
+///     .encrypt_with(|plain: &str| plain.chars().rev().collect());
+///
+/// let value = figment.export(&options).unwrap();
+/// assert_eq!(value.find_ref("db.user").unwrap().as_str(), Some("admin"));
+/// assert_eq!(value.find_ref("db.password").unwrap().as_str(), Some("<redacted>"));
+/// assert_eq!(value.find_ref("api.token").unwrap().as_str(), Some("<redacted>"));
+/// assert_eq!(value.find_ref("api.port").unwrap().as_str(), Some("4321"));
+/// ```
+#[derive(Clone, Default)]
+pub struct ExportOptions {
+    redact: Vec<String>,
+    encrypt: Vec<String>,
+    cipher: Option<Cipher>,
+}
+
+/// A function from plaintext to ciphertext.
+type Cipher = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+impl ExportOptions {
+    /// Returns options that export every value as is.
+    ///
+    /// ```rust
+    /// use figment::{Figment, ExportOptions};
+    ///
+    /// let figment = Figment::from(("password", "hunter2"));
+    /// let value = figment.export(&ExportOptions::new()).unwrap();
+    /// assert_eq!(value.find_ref("password").unwrap().as_str(), Some("hunter2"));
+    /// ```
+    pub fn new() -> Self {
+        ExportOptions::default()
+    }
+
+    /// Redacts the values at key paths matching any of `globs`, in addition to
+    /// those previously set to be redacted. See [globs](#globs) for syntax.
+    ///
+    /// ```rust
+    /// use figment::{Figment, ExportOptions};
+    ///
+    /// let figment = Figment::from(("db.password", "hunter2"));
+    /// let value = figment.export(&ExportOptions::new().redact(["db.*"])).unwrap();
+    /// assert_eq!(value.find_ref("db.password").unwrap().as_str(), Some("<redacted>"));
+    /// ```
+    pub fn redact<I, S>(mut self, globs: I) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        self.redact.extend(globs.into_iter().map(Into::into));
+        self
+    }
+
+    /// Encrypts the values at key paths matching any of `globs`, in addition
+    /// to those previously set to be encrypted, with the cipher set via
+    /// [`ExportOptions::encrypt_with()`]. See [globs](#globs) for syntax.
+    ///
+    /// Exporting fails if a glob is set but no cipher is.
+    ///
+    /// ```rust
+    /// use figment::{Figment, ExportOptions};
+    ///
+    /// let figment = Figment::from(("token", "secret"));
+    /// assert!(figment.export(&ExportOptions::new().encrypt(["token"])).is_err());
+    /// ```
+    pub fn encrypt<I, S>(mut self, globs: I) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        self.encrypt.extend(globs.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the cipher values set to be encrypted via
+    /// [`ExportOptions::encrypt()`] are encrypted with. See
+    /// [encryption](#encryption) for details.
+    ///
+    /// ```rust
+    /// use figment::{Figment, ExportOptions, value::Value};
+    ///
+    /// let figment = Figment::from(("ports", [80, 443]));
+    /// let options = ExportOptions::new()
+    ///     .encrypt(["ports"])
+    ///     .encrypt_with(|plain: &str| format!("enc:{}", plain));
+    ///
+    /// let value = figment.export(&options).unwrap();
+    /// let ciphertext = value.find_ref("ports").unwrap().as_str().unwrap();
+    /// assert_eq!(ciphertext, "enc:[80, 443]");
+    ///
+    /// let decrypted: Value = ciphertext.strip_prefix("enc:").unwrap().parse().unwrap();
+    /// assert_eq!(decrypted.deserialize::<Vec<u16>>().unwrap(), [80, 443]);
+    /// ```
+    pub fn encrypt_with<F>(mut self, cipher: F) -> Self
+        where F: Fn(&str) -> String + Send + Sync + 'static
+    {
+        self.cipher = Some(Arc::new(cipher));
+        self
+    }
+
+    /// Redacts and encrypts the values in `value` as configured.
+    pub(crate) fn apply(&self, value: &mut Value) -> Result<(), Error> {
+        let cipher = match &self.cipher {
+            Some(cipher) => Some(cipher),
+            None if self.encrypt.is_empty() => None,
+            None => return Err(Kind::Message("no cipher set for values to encrypt".into()).into()),
+        };
+
+        value.walk_mut(&mut |path: &[String], value: &mut Value| {
+            let matches = |globs: &[String]| globs.iter().any(|glob| crate::util::path_glob_match(glob, path));
+            if path.is_empty() {
+                return Visit::Continue;
+            } else if matches(&self.redact) {
+                *value = Value::String(value.tag(), "<redacted>".into());
+            } else if let (true, Some(cipher)) = (matches(&self.encrypt), cipher) {
+                let plaintext = crate::providers::env::encode(value, true);
+                *value = Value::String(value.tag(), cipher(&plaintext));
+            } else {
+                return Visit::Continue;
+            }
+
+            Visit::SkipChildren
+        });
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ExportOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExportOptions")
+            .field("redact", &self.redact)
+            .field("encrypt", &self.encrypt)
+            .field("cipher", &self.cipher.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
@@ -1,11 +1,30 @@
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
 use std::panic::Location;
+use std::sync::{Arc, Mutex};
 
-use serde::de::Deserialize;
+use serde::Serialize;
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
 
-use crate::{Profile, Provider, Metadata};
-use crate::error::{Kind, Result};
-use crate::value::{Value, Map, Dict, Tag, ConfiguredValueDe, DefaultInterpreter, LossyInterpreter};
-use crate::coalesce::{Coalescible, Order};
+use crate::{Profile, Provider, Resolution, Metadata, Monitor, Polymorphic, Plan, LayerInfo, Stats, ExportOptions};
+use crate::{Deprecation, FigmentView};
+use crate::layer::{Layer, OverrideGuard};
+use crate::profile::Leveled;
+use crate::error::{Error, Kind, Result};
+use crate::value::{Value, Map, Dict, Tag, Empty, ConfiguredValueDe, DefaultInterpreter, LossyInterpreter, Visit};
+use crate::value::{NumericLeniency, NumPolicy, Limits, KeyNormalizer, KeyCollision, Spellings, Case};
+use crate::value::magic::{MagicSerialize, RelativePathBuf, Tagged, Duration, ByteSize, Secret};
+use crate::coalesce::{Coalescible, Order, ArrayMerge, TypeConflict};
+use crate::trace::{self, MergeEvent, MergeDecision};
+use crate::rename::Renamer;
+use crate::diff::Change;
+use crate::schema::{Schema, UnknownPolicy};
+#[cfg(feature = "schema")]
+use crate::schema::{OnMismatch, JsonSchema};
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use crate::async_provider::{AsyncProvider, Resolved};
 
 /// Combiner of [`Provider`]s for configuration value extraction.
 ///
@@ -51,12 +70,22 @@ use crate::coalesce::{Coalescible, Order};
 ///   * `merge` uses the incoming value
 ///   * `adjoin` and `admerge` concatenate the arrays
 ///
+/// Arrays can instead be appended, prepended, or combined by index, for all
+/// keys with [`Figment::arrays()`], for a key path with
+/// [`Figment::arrays_at()`], or for one provider with [`Figment::merge_with()`]
+/// and [`Figment::join_with()`]. Arrays of dictionaries at a given key path
+/// can be combined element-wise by an identifying field with
+/// [`Figment::array_key()`].
+///
 /// If both keys point to a **non-composite** (`String`, `Num`, etc.) or values
 /// of different kinds (i.e, **array** and **num**):
 ///
 ///   * `join` and `adjoin` use the existing value
 ///   * `merge` and `admerge` use the incoming value
 ///
+/// Values of different kinds can instead be reconciled by type or rejected
+/// with [`Figment::type_conflicts()`].
+///
 /// [`join`]: Figment::join()
 /// [`adjoin`]: Figment::adjoin()
 /// [`merge`]: Figment::merge()
@@ -81,6 +110,13 @@ use crate::coalesce::{Coalescible, Order};
 /// the preceding key in the path, or the root dictionary if it is the first key
 /// in the path. See [`Value::find()`] for examples.
 ///
+/// Providers are read once, when they are combined: extraction never asks a
+/// provider for its data again, and the value of the selected profile merged
+/// with the default and global profiles is computed once and shared by clones
+/// until either changes. To pick up changes to configuration sources, such as
+/// edited files, build a new figment from the providers, using
+/// [`Figment::sources_changed()`] to determine whether it's necessary.
+///
 /// ## Metadata
 ///
 /// Every value collected by a `Figment` is accompanied by the metadata produced
@@ -99,10 +135,81 @@ use crate::coalesce::{Coalescible, Order};
 #[derive(Clone, Debug)]
 pub struct Figment {
     pub(crate) profile: Profile,
+    /// Profiles `profile` inherits from, lowest first.
+    pub(crate) lineage: Vec<Profile>,
     pub(crate) metadata: Map<Tag, Metadata>,
     pub(crate) value: Result<Map<Profile, Dict>>,
+    /// Merge decisions, if tracing is enabled.
+    pub(crate) trace: Option<Vec<MergeEvent>>,
+    /// Values discarded while combining providers, by profile and key path,
+    /// if recording is enabled.
+    pub(crate) shadowed: Option<Map<Profile, Map<String, Vec<Value>>>>,
+    /// The schema provided data is checked against, if any.
+    #[cfg(feature = "schema")]
+    pub(crate) schema: Option<(Schema, OnMismatch)>,
+    /// Non-fatal issues found while combining providers.
+    pub(crate) warnings: Vec<Error>,
+    /// Unknown key policies by key path, in the order they were set.
+    pub(crate) unknown: Vec<(String, UnknownPolicy)>,
+    /// Formatting tolerated when extracting numbers from strings.
+    pub(crate) numeric: NumericLeniency,
+    /// How numbers are coerced when extracted.
+    pub(crate) num_policy: NumPolicy,
+    /// Bounds on the data emitted by subsequently combined providers.
+    pub(crate) limits: Limits,
+    /// Each combined provider, in the order combined.
+    pub(crate) layers: Vec<Layer>,
+    /// Whether the data of subsequently combined providers is retained.
+    pub(crate) retain_layers: bool,
+    /// The normalization applied to the keys of subsequently combined
+    /// providers, if any.
+    pub(crate) normalizer: Option<KeyNormalizer>,
+    /// Spellings of normalized keys, by profile and normalized key path.
+    pub(crate) spellings: Spellings,
+    /// The values of placeholders in the keys of subsequently combined
+    /// providers, if placeholders are to be replaced.
+    pub(crate) key_context: Option<Map<String, String>>,
+    /// Renames applied to the key paths of subsequently combined providers,
+    /// in the order they were set.
+    pub(crate) renames: Vec<Renamer>,
+    /// Deprecated key paths and their notes, in the order they were marked.
+    pub(crate) deprecated: Vec<(String, String)>,
+    /// Key paths of arrays combined element-wise and the identifying field of
+    /// their elements, in the order they were set.
+    pub(crate) array_keys: Vec<(String, String)>,
+    /// The strategy conflicting arrays are combined with, if set.
+    pub(crate) arrays: Option<ArrayMerge>,
+    /// Key paths of arrays combined with a specific strategy, in the order
+    /// they were set.
+    pub(crate) array_merges: Vec<(String, ArrayMerge)>,
+    /// How conflicts between values of different types are resolved.
+    pub(crate) type_conflicts: TypeConflict,
+    /// Whether keys similar to existing sibling keys are reported as warnings.
+    pub(crate) typos: bool,
+    /// Whether references in string values are resolved after merging.
+    pub(crate) interpolation: bool,
+    /// Namespaces claimed via [`Figment::register_namespace()`], with the
+    /// claimant's version and location.
+    pub(crate) namespaces: Map<String, (String, &'static Location<'static>)>,
+    /// The number of entries per dictionary and array shown in samples of
+    /// values in extraction errors, if samples are enabled.
+    pub(crate) samples: Option<usize>,
+    /// Values extracted via [`Figment::extension()`] and the merged value,
+    /// shared by clones until either changes.
+    pub(crate) extensions: Extensions,
+    /// The `MagicSerialize::humanize()` of every registered magic type.
+    pub(crate) humanizers: Vec<Humanizer>,
 }
 
+/// Extracted extension values by namespace and type, and the [`Merged`] value.
+pub(crate) type Extensions = Arc<Mutex<Map<(String, TypeId), Arc<dyn Any + Send + Sync>>>>;
+
+/// The value returned by [`Figment::merged()`], cached in [`Extensions`].
+struct Merged(Value);
+
+/// Renders a serialized magic structure in its human-readable form.
+pub(crate) type Humanizer = fn(&Dict) -> Option<Value>;
+
 impl Figment {
     /// Creates a new `Figment` with the default profile selected and no
     /// providers.
@@ -118,7 +225,40 @@ impl Figment {
         Figment {
             metadata: Map::new(),
             profile: Profile::Default,
+            lineage: vec![],
             value: Ok(Map::new()),
+            trace: None,
+            shadowed: None,
+            #[cfg(feature = "schema")]
+            schema: None,
+            warnings: vec![],
+            unknown: vec![],
+            numeric: NumericLeniency::none(),
+            num_policy: NumPolicy::default(),
+            limits: Limits::none(),
+            layers: vec![],
+            retain_layers: false,
+            normalizer: None,
+            spellings: Map::new(),
+            key_context: None,
+            renames: vec![],
+            deprecated: vec![],
+            array_keys: vec![],
+            arrays: None,
+            array_merges: vec![],
+            type_conflicts: TypeConflict::default(),
+            typos: false,
+            interpolation: false,
+            namespaces: Map::new(),
+            samples: None,
+            extensions: Extensions::default(),
+            humanizers: vec![
+                RelativePathBuf::humanize,
+                Tagged::<Value>::humanize,
+                Duration::humanize,
+                ByteSize::humanize,
+                Secret::<Value>::humanize,
+            ],
         }
     }
 
@@ -138,37 +278,237 @@ impl Figment {
         Figment::new().merge(provider)
     }
 
+    /// Creates a new `Figment` with the default profile selected and an initial
+    /// provider determined by the scheme of `uri`: one of `file:`, `env:`, or
+    /// `data:`. Equivalent to `Figment::from(Uri::new(uri))`. See
+    /// [`Uri`](crate::providers::Uri) for details.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("app.toml", "port = 80")?;
+    ///
+    ///     let figment = Figment::from_uri("file:app.toml");
+    ///     assert_eq!(figment.extract_inner::<u16>("port")?, 80);
+    ///
+    ///     let figment = Figment::from_uri("data:application/toml;base64,cG9ydCA9IDgw");
+    ///     assert_eq!(figment.extract_inner::<u16>("port")?, 80);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    #[track_caller]
+    pub fn from_uri(uri: &str) -> Self {
+        Figment::from(crate::providers::Uri::new(uri))
+    }
+
     #[track_caller]
-    fn provide<T: Provider>(mut self, provider: T, order: Order) -> Self {
+    fn provide<T: Provider>(self, provider: T, order: Order) -> Self {
+        self.provide_with(provider, order, None, Location::caller())
+    }
+
+    /// Combines `provider` into `self` using `order` and, if set, the array
+    /// strategy `arrays`, recording `location` as the provider's
+    /// [`Metadata::provide_location`].
+    fn provide_with<T>(
+        mut self,
+        provider: T,
+        order: Order,
+        arrays: Option<ArrayMerge>,
+        location: &'static Location<'static>,
+    ) -> Self
+        where T: Provider
+    {
         if let Some(map) = provider.__metadata_map() {
             self.metadata.extend(map);
         }
 
+        // Data is requested before the profile and metadata so that they can
+        // describe the data that was read, as `Http`'s checksum does, and so
+        // that providers created when their data is requested, like
+        // `OnFirstUse`, exist.
+        let data = provider.data();
         if let Some(profile) = provider.profile() {
             self.profile = self.profile.coalesce(profile, order);
         }
 
         let mut metadata = provider.metadata();
-        metadata.provide_location = Some(Location::caller());
+        metadata.provide_location = Some(location);
 
         let tag = Tag::next();
         self.metadata.insert(tag, metadata);
-        self.value = match (provider.data(), self.value) {
-            (Ok(_), e@Err(_)) => e,
-            (Err(e), Ok(_)) => Err(e.retagged(tag)),
-            (Err(e), Err(prev)) => Err(e.retagged(tag).chain(prev)),
-            (Ok(mut new), Ok(old)) => {
-                new.iter_mut()
-                    .flat_map(|(p, map)| std::iter::repeat(p).zip(map.values_mut()))
-                    .for_each(|(p, v)| v.map_tag(|t| *t = tag.for_profile(p)));
+        let data = data.map_err(|e| e.retagged(tag)).and_then(|mut new| {
+            new.iter_mut()
+                .flat_map(|(p, map)| std::iter::repeat(p).zip(map.values_mut()))
+                .for_each(|(p, v)| v.map_tag(|t| *t = tag.for_profile(p)));
 
-                Ok(old.coalesce(new, order))
+            if let Some(vars) = &self.key_context {
+                new = crate::value::expand_keys(new, vars).map_err(|e| e.retagged(tag))?;
             }
-        };
+
+            if let Some(normalizer) = &self.normalizer {
+                new = normalizer.apply(new, &mut self.spellings);
+            }
+
+            for renamed in crate::rename::apply(&self.renames, &mut new) {
+                let msg = format!("`{}` is deprecated; use `{}` instead", renamed.old, renamed.new);
+                let mut warning = Error::from(Kind::Message(msg)).retagged(renamed.tag);
+                warning.metadata = self.metadata.get(&tag).cloned();
+                warning.profile = Some(renamed.profile);
+                warning = warning.with_path(&renamed.old);
+                self.warnings.push(warning);
+            }
+
+            self.limits.check(&new).map_err(|e| e.retagged(tag))?;
+            #[cfg(feature = "schema")]
+            self.check_schema(&mut new)?;
+            Ok(new)
+        });
+
+        crate::logging::provided(&self.metadata[&tag], order, &data);
+        if let (true, Ok(new), Ok(old)) = (self.typos, &data, &self.value) {
+            let typos = crate::typos::find(&self, old, new);
+            self.warnings.extend(typos);
+        }
+
+        self.layers.push(Layer::new(tag, order, arrays, &data, self.retain_layers));
+        self.combine(data, order, arrays, &|p, key, existing, incoming| {
+            let mut resolution = provider.on_conflict(key, existing, incoming);
+            if let Resolution::Value(value) = &mut resolution {
+                value.map_tag(|t| *t = tag.for_profile(p));
+            }
+
+            resolution
+        }, |data| provider.__patch(data, tag).map_err(|e| e.retagged(tag)));
 
         self
     }
 
+    /// Combines `data` into `self` using `order` and, if set, the array
+    /// strategy `arrays`, resolving conflicts with `hook`, and then applies
+    /// `patch` to the result.
+    fn combine<H, P>(
+        &mut self,
+        data: Result<Map<Profile, Dict>>,
+        order: Order,
+        arrays: Option<ArrayMerge>,
+        hook: &H,
+        patch: P
+    ) where H: Fn(&Profile, &str, Option<&Value>, &Value) -> Resolution,
+            P: FnOnce(&mut Map<Profile, Dict>) -> Result<()>
+    {
+        self.extensions = Extensions::default();
+        let (array_keys, array_merges, default) = (&self.array_keys, &self.array_merges, self.arrays);
+        let (type_conflicts, conflicts) = (self.type_conflicts, std::cell::RefCell::new(vec![]));
+        let resolve = |p: &Profile, key: &str, existing: Option<&Value>, incoming: &Value| {
+            match (hook(p, key, existing, incoming), existing, incoming) {
+                (Resolution::Default, Some(old), new) if old.as_array().is_none() || new.as_array().is_none() => {
+                    match type_conflicts.resolve(key, old, new, order) {
+                        Ok(resolution @ Resolution::Value(_)) => match order {
+                            Order::Join | Order::Adjoin => (resolution, Some(MergeDecision::KeptExisting)),
+                            Order::Merge | Order::Admerge => (resolution, Some(MergeDecision::Replaced)),
+                        },
+                        Ok(resolution) => (resolution, None),
+                        Err(e) => {
+                            conflicts.borrow_mut().push(e);
+                            (Resolution::Default, None)
+                        }
+                    }
+                }
+                (Resolution::Default, Some(old @ Value::Array(..)), new @ Value::Array(..)) => {
+                    let (old, new) = (old.clone(), new.clone());
+                    if let Some((_, field)) = array_keys.iter().find(|(path, _)| path == key) {
+                        let value = crate::coalesce::coalesce_by(field, old, new, order);
+                        return (Resolution::Value(value), Some(MergeDecision::Combined));
+                    }
+
+                    let strategy = arrays
+                        .or_else(|| array_merges.iter().find(|(path, _)| path == key).map(|p| p.1))
+                        .or(default);
+
+                    match strategy {
+                        Some(strategy) => {
+                            let value = strategy.coalesce(old, new, order);
+                            (Resolution::Value(value), Some(strategy.decision(order)))
+                        }
+                        None => (Resolution::Default, None),
+                    }
+                }
+                (resolution, _, _) => (resolution, None),
+            }
+        };
+
+        let recording = self.trace.is_some() || self.shadowed.is_some()
+            || crate::logging::decisions_enabled();
+
+        let decisions = recording.then(|| trace::Decisions::new(order, self.shadowed.is_some()));
+        let decisions = std::cell::RefCell::new(decisions);
+        let hook = |p: &Profile, key: &str, existing: Option<&Value>, incoming: &Value| {
+            let (resolution, decision) = resolve(p, key, existing, incoming);
+            if let Some(decisions) = decisions.borrow_mut().as_mut() {
+                decisions.record(p, key, existing, incoming, &resolution, decision);
+            }
+
+            resolution
+        };
+
+        let value = std::mem::replace(&mut self.value, Ok(Map::new()));
+        self.value = match (data, value) {
+            (Ok(new), Ok(old)) => {
+                let mut data = crate::coalesce::coalesce_with(old, new, order, &hook);
+                if let Some(decisions) = decisions.into_inner() {
+                    crate::logging::decisions(decisions.events());
+                    decisions.finish(self.trace.as_mut(), self.shadowed.as_mut());
+                }
+
+                match crate::schema::join(conflicts.into_inner()) {
+                    Ok(()) => patch(&mut data).map(|_| data),
+                    Err(e) => Err(e),
+                }
+            }
+            (Ok(_), Err(prev)) => Err(prev),
+            (Err(e), Ok(_)) => Err(e),
+            (Err(e), Err(prev)) => {
+                let errors = prev.into_iter().chain(e).collect();
+                Err(crate::schema::join(errors).unwrap_err())
+            }
+        };
+    }
+
+    /// Checks `data` against the schema set via [`Figment::check_merges()`],
+    /// if any, handling mismatches as configured.
+    #[cfg(feature = "schema")]
+    fn check_schema(&mut self, data: &mut Map<Profile, Dict>) -> Result<()> {
+        let (schema, on_mismatch) = match &self.schema {
+            Some((schema, on_mismatch)) => (schema, *on_mismatch),
+            None => return Ok(()),
+        };
+
+        let mut errors = vec![];
+        for (profile, dict) in data.iter_mut() {
+            let value = Value::Dict(Tag::Default, std::mem::take(dict));
+            let mismatches = schema.mismatches(self, &value);
+            *dict = value.into_dict().expect("dict");
+            if on_mismatch == OnMismatch::Drop {
+                mismatches.iter().for_each(|e| crate::util::remove_path(dict, &e.path));
+            }
+
+            errors.extend(mismatches.into_iter().map(|mut e| {
+                e.profile = Some(profile.clone());
+                e
+            }));
+        }
+
+        match on_mismatch {
+            OnMismatch::Reject => crate::schema::join(errors),
+            OnMismatch::Drop | OnMismatch::Warn => {
+                self.warnings.extend(errors);
+                Ok(())
+            }
+        }
+    }
+
     /// Joins `provider` into the current figment.
     /// See [conflict resolution](#conflict-resolution) for details.
     ///
@@ -333,177 +673,2319 @@ impl Figment {
         self.provide(provider, Order::Admerge)
     }
 
-    /// Sets the profile to extract from to `profile`.
+    /// Returns a future that awaits the data of the [`AsyncProvider`]
+    /// `provider` and then resolves to the current figment with the data
+    /// merged. See [`Figment::merge()`] and [`AsyncProvider`] for details and
+    /// an example.
+    #[track_caller]
+    #[cfg(feature = "async")]
+    #[cfg_attr(nightly, doc(cfg(feature = "async")))]
+    pub fn merge_async<T: AsyncProvider>(self, provider: T) -> impl Future<Output = Self> {
+        let location = Location::caller();
+        async move {
+            let resolved = Resolved::from(provider).await;
+            self.provide_with(resolved, Order::Merge, None, location)
+        }
+    }
+
+    /// Returns a future that awaits the data of the [`AsyncProvider`]
+    /// `provider` and then resolves to the current figment with the data
+    /// joined. See [`Figment::join()`] and [`AsyncProvider`] for details and
+    /// an example.
+    #[track_caller]
+    #[cfg(feature = "async")]
+    #[cfg_attr(nightly, doc(cfg(feature = "async")))]
+    pub fn join_async<T: AsyncProvider>(self, provider: T) -> impl Future<Output = Self> {
+        let location = Location::caller();
+        async move {
+            let resolved = Resolved::from(provider).await;
+            self.provide_with(resolved, Order::Join, None, location)
+        }
+    }
+
+    /// Removes the value at `key` path, in every profile, from the data
+    /// combined into `self` so far. Providers combined afterwards may set the
+    /// value again. See [`Value::find()`] for details on the syntax for `key`;
+    /// additionally, a key that names an array may be followed by an index to
+    /// remove a single element. Removing a key that doesn't exist has no
+    /// effect.
     ///
-    /// # Example
+    /// This allows an override layer to unset a value, such as a default,
+    /// instead of merely replacing it. To remove keys from a provider's data,
+    /// see also [`MergePatch`](crate::providers::MergePatch).
     ///
-    /// ```
-    /// use figment::Figment;
+    /// ```rust
+    /// use figment::{Figment, util::map};
     ///
-    /// let figment = Figment::new().select("staging");
-    /// assert_eq!(figment.profile(), "staging");
+    /// let figment = Figment::new()
+    ///     .merge(("server", map!["port" => 80, "workers" => 4]))
+    ///     .merge(("hosts", ["a.com", "b.com", "c.com"]))
+    ///     .remove_key("server.workers")
+    ///     .remove_key("hosts.1")
+    ///     .remove_key("missing.key");
+    ///
+    /// assert!(figment.contains("server.port"));
+    /// assert!(!figment.contains("server.workers"));
+    ///
+    /// let hosts: Vec<String> = figment.extract_inner("hosts").unwrap();
+    /// assert_eq!(hosts, ["a.com", "c.com"]);
+    ///
+    /// let figment = figment.merge(("server.workers", 8));
+    /// assert_eq!(figment.extract_inner::<u8>("server.workers").unwrap(), 8);
     /// ```
-    pub fn select<P: Into<Profile>>(mut self, profile: P) -> Self {
-        self.profile = profile.into();
-        self
-    }
+    pub fn remove_key(mut self, key: &str) -> Self {
+        fn remove(value: &mut Value, keys: &[&str]) {
+            let (last, parents) = match keys.split_last() {
+                Some(split) => split,
+                None => return,
+            };
 
-    /// Merges the selected profile with the default and global profiles.
-    fn merged(&self) -> Result<Value> {
-        let mut map = self.value.clone().map_err(|e| e.resolved(self))?;
-        let def = map.remove(&Profile::Default).unwrap_or_default();
-        let global = map.remove(&Profile::Global).unwrap_or_default();
+            let mut value = value;
+            for k in parents {
+                value = match value {
+                    Value::Dict(_, dict) => match dict.get_mut(*k) {
+                        Some(v) => v,
+                        None => return,
+                    },
+                    Value::Array(_, array) => match k.parse::<usize>().ok() {
+                        Some(i) if i < array.len() => &mut array[i],
+                        _ => return,
+                    },
+                    _ => return,
+                };
+            }
 
-        let map = match map.remove(&self.profile) {
-            Some(v) if self.profile.is_custom() => def.merge(v).merge(global),
-            _ => def.merge(global)
+            match value {
+                Value::Dict(_, dict) => { dict.remove(*last); }
+                Value::Array(_, array) => match last.parse::<usize>() {
+                    Ok(i) if i < array.len() => { array.remove(i); }
+                    _ => {}
+                }
+                _ => {}
+            }
+        }
+
+        self.extensions = Extensions::default();
+        let keys: Vec<_> = key.split('.').filter(|k| !k.is_empty()).collect();
+        let (first, rest) = match keys.split_first() {
+            Some(split) => split,
+            None => return self,
         };
 
-        Ok(Value::Dict(Tag::Default, map))
+        let layers = self.layers.iter_mut().filter_map(|layer| layer.data.as_mut());
+        for data in std::iter::once(&mut self.value).chain(layers) {
+            for dict in data.iter_mut().flat_map(|data| data.values_mut()) {
+                match rest.is_empty() {
+                    true => { dict.remove(*first); }
+                    false => if let Some(value) = dict.get_mut(*first) {
+                        remove(value, rest);
+                    }
+                }
+            }
+        }
+
+        self
     }
 
-    /// Returns a new `Figment` containing only the sub-dictionaries at `key`.
+    /// Returns the providers combined into `self`, in the order they were
+    /// combined. See [`LayerInfo`].
     ///
-    /// This "sub-figment" is a _focusing_ of `self` with the property that:
+    /// Together with [`Figment::with_layers()`], this allows inspecting and
+    /// adjusting a figment built elsewhere, for instance by a library, without
+    /// rebuilding it from scratch.
     ///
-    ///   * `self.find(key + ".sub")` <=> `focused.find("sub")`
+    /// ```rust
+    /// use figment::{Figment, Order};
     ///
-    /// In other words, all values in `self` with a key starting with `key` are
-    /// in `focused` _without_ the prefix and vice-versa.
+    /// let figment = Figment::new()
+    ///     .merge(("name", "first"))
+    ///     .join(("name", "second"));
+    ///
+    /// let layers = figment.layers();
+    /// assert_eq!(layers.len(), 2);
+    /// assert_eq!(layers[0].order, Order::Merge);
+    /// assert_eq!(layers[1].order, Order::Join);
+    /// assert_eq!(layers[1].profiles, vec!["global"]);
+    /// assert_eq!(figment.get_metadata(layers[0].tag), Some(&layers[0].metadata));
+    /// ```
+    pub fn layers(&self) -> Vec<LayerInfo> {
+        self.layers.iter()
+            .enumerate()
+            .map(|(index, layer)| LayerInfo {
+                index,
+                tag: layer.tag,
+                metadata: self.metadata.get(&layer.tag).cloned().unwrap_or_default(),
+                order: layer.order,
+                profiles: layer.profiles.clone(),
+            })
+            .collect()
+    }
+
+    /// Enables or disables retaining a copy of the data of providers
+    /// subsequently combined into `self`, as required to rebuild `self` via
+    /// [`Figment::with_layers()`] and [`Figment::replace_layer()`]. Disabling
+    /// retention discards no data already retained. Retention is disabled by
+    /// default as it doubles the memory used by each provider's data.
     ///
-    /// # Example
+    /// [`Figment::layers()`] reports every layer irrespective of retention.
     ///
     /// ```rust
-    /// use figment::{Figment, providers::{Format, Toml}};
+    /// use figment::Figment;
     ///
-    /// figment::Jail::expect_with(|jail| {
-    ///     jail.create_file("Config.toml", r#"
-    ///         cat = [1, 2, 3]
-    ///         dog = [4, 5, 6]
+    /// let figment = Figment::new().merge(("name", "app"));
+    /// let rebuilt = figment.with_layers(&figment.layers());
+    /// assert!(rebuilt.extract_inner::<String>("name").is_err());
     ///
-    ///         [subtree]
-    ///         cat = "meow"
-    ///         dog = "woof!"
+    /// let figment = Figment::new().retain_layers(true).merge(("name", "app"));
+    /// let rebuilt = figment.with_layers(&figment.layers());
+    /// assert_eq!(rebuilt.extract_inner::<String>("name").unwrap(), "app");
+    /// ```
+    pub fn retain_layers(mut self, enabled: bool) -> Self {
+        self.retain_layers = enabled;
+        self
+    }
+
+    /// Returns a new figment with the layers of `self` identified by the
+    /// [`index`](LayerInfo::index) of each of `layers`, in the order given,
+    /// each combined using its [`order`](LayerInfo::order). Layers not in
+    /// `layers` are omitted; indices that don't identify a layer of `self` are
+    /// ignored. `layers` is typically [`Figment::layers()`], modified.
     ///
-    ///         [subtree.bark]
-    ///         dog = true
-    ///         cat = false
-    ///     "#)?;
+    /// The data of every layer in `layers` must have been retained via
+    /// [`Figment::retain_layers()`]; otherwise, the returned figment fails.
     ///
-    ///     let root = Figment::from(Toml::file("Config.toml"));
-    ///     assert_eq!(root.extract_inner::<Vec<u8>>("cat").unwrap(), vec![1, 2, 3]);
-    ///     assert_eq!(root.extract_inner::<Vec<u8>>("dog").unwrap(), vec![4, 5, 6]);
-    ///     assert_eq!(root.extract_inner::<String>("subtree.cat").unwrap(), "meow");
-    ///     assert_eq!(root.extract_inner::<String>("subtree.dog").unwrap(), "woof!");
+    /// Every setting of `self`, such as the selected profile, is retained. The
+    /// data of each layer is combined as the provider emitted it: conflict
+    /// resolution via [`Provider::on_conflict()`] is not reapplied, nor are
+    /// patches like [`JsonPatch`](crate::providers::JsonPatch), which don't
+    /// emit data of their own.
     ///
-    ///     let subtree = root.focus("subtree");
-    ///     assert_eq!(subtree.extract_inner::<String>("cat").unwrap(), "meow");
-    ///     assert_eq!(subtree.extract_inner::<String>("dog").unwrap(), "woof!");
-    ///     assert_eq!(subtree.extract_inner::<bool>("bark.cat").unwrap(), false);
-    ///     assert_eq!(subtree.extract_inner::<bool>("bark.dog").unwrap(), true);
+    /// ```rust
+    /// use figment::{Figment, Order};
     ///
-    ///     let bark = subtree.focus("bark");
-    ///     assert_eq!(bark.extract_inner::<bool>("cat").unwrap(), false);
-    ///     assert_eq!(bark.extract_inner::<bool>("dog").unwrap(), true);
+    /// let figment = Figment::new()
+    ///     .retain_layers(true)
+    ///     .merge(("name", "library"))
+    ///     .merge(("port", 80))
+    ///     .merge(("name", "default"));
+    ///
+    /// let mut layers = figment.layers();
+    /// layers.remove(1);
+    /// layers.swap(0, 1);
+    /// let rebuilt = figment.with_layers(&layers);
+    /// assert_eq!(rebuilt.extract_inner::<String>("name").unwrap(), "library");
+    /// assert!(!rebuilt.contains("port"));
+    ///
+    /// let mut layers = figment.layers();
+    /// layers[2].order = Order::Join;
+    /// let rebuilt = figment.with_layers(&layers);
+    /// assert_eq!(rebuilt.extract_inner::<String>("name").unwrap(), "library");
+    /// assert_eq!(rebuilt.layers()[2].order, Order::Join);
+    /// ```
+    pub fn with_layers(&self, layers: &[LayerInfo]) -> Figment {
+        let mut figment = Figment {
+            value: Ok(Map::new()),
+            trace: self.trace.as_ref().map(|_| vec![]),
+            shadowed: self.shadowed.as_ref().map(|_| Map::new()),
+            layers: vec![],
+            extensions: Extensions::default(),
+            ..self.clone()
+        };
+
+        for info in layers {
+            if let Some(layer) = self.layers.get(info.index) {
+                let layer = Layer { order: info.order, ..layer.clone() };
+                let data = match &layer.data {
+                    Some(data) => data.clone(),
+                    None => {
+                        figment.value = Err(crate::layer::unretained());
+                        return figment;
+                    }
+                };
+
+                figment.combine(data, layer.order, layer.arrays, &|_, _, _, _| {
+                    Resolution::Default
+                }, |_| Ok(()));
+
+                figment.layers.push(layer);
+            }
+        }
+
+        figment
+    }
+
+    /// Replaces the layer of `self` at `index`, as returned by
+    /// [`Figment::layers()`], with `provider`, combined using the replaced
+    /// layer's [`order`](LayerInfo::order). If `index` doesn't identify a
+    /// layer, `self` is returned unchanged. As with [`Figment::with_layers()`],
+    /// the data of every layer must have been retained via
+    /// [`Figment::retain_layers()`]; otherwise, the returned figment fails.
+    ///
+    /// This is intended for refreshing a single provider whose source changed,
+    /// for instance a watched file, in a large figment. Rather than combining
+    /// every provider again, only the top-level keys the old or new data
+    /// contains are recombined from every layer; all other values are kept.
+    /// The result is the same as that of [`Figment::with_layers()`] with the
+    /// layer replaced, and the same caveats apply: conflict resolution via
+    /// [`Provider::on_conflict()`] is not reapplied, nor are patches. The
+    /// [merge trace](Figment::merge_trace()) is not updated.
     ///
-    ///     let not_a_dict = root.focus("cat");
-    ///     assert!(not_a_dict.extract_inner::<bool>("cat").is_err());
-    ///     assert!(not_a_dict.extract_inner::<bool>("dog").is_err());
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized, util::map};
     ///
-    ///     Ok(())
-    /// });
+    /// let figment = Figment::new()
+    ///     .retain_layers(true)
+    ///     .merge(("server", map!["host" => "localhost", "port" => "80"]))
+    ///     .merge(("client.retries", 3))
+    ///     .merge(("server.port", "8080"));
+    ///
+    /// assert_eq!(figment.extract_inner::<u16>("server.port").unwrap(), 8080);
+    ///
+    /// let figment = figment.replace_layer(2, Serialized::global("server.port", 9090));
+    /// assert_eq!(figment.extract_inner::<u16>("server.port").unwrap(), 9090);
+    /// assert_eq!(figment.extract_inner::<String>("server.host").unwrap(), "localhost");
+    /// assert_eq!(figment.extract_inner::<u8>("client.retries").unwrap(), 3);
+    /// assert_eq!(figment.layers().len(), 3);
+    ///
+    /// let figment = figment.replace_layer(2, Serialized::global("client.retries", 5));
+    /// assert_eq!(figment.extract_inner::<u16>("server.port").unwrap(), 80);
+    /// assert_eq!(figment.extract_inner::<u8>("client.retries").unwrap(), 5);
     /// ```
-    pub fn focus(&self, key: &str) -> Self {
-        fn try_focus(figment: &Figment, key: &str) -> Result<Map<Profile, Dict>> {
-            let map = figment.value.clone().map_err(|e| e.resolved(figment))?;
-            let new_map = map.into_iter()
-                .filter_map(|(k, v)| {
-                    let focused = Value::Dict(Tag::Default, v).find(key)?;
-                    let dict = focused.into_dict()?;
-                    Some((k, dict))
-                })
-                .collect();
+    #[track_caller]
+    pub fn replace_layer<T: Provider>(mut self, index: usize, provider: T) -> Self {
+        let (order, arrays) = match self.layers.get(index) {
+            Some(layer) => (layer.order, layer.arrays),
+            None => return self,
+        };
 
-            Ok(new_map)
+        self.extensions = Extensions::default();
+        if self.layers.iter().any(|layer| layer.data.is_none()) {
+            self.value = Err(crate::layer::unretained());
+            return self;
         }
 
-        Figment {
-            profile: self.profile.clone(),
-            metadata: self.metadata.clone(),
-            value: try_focus(self, key)
+        // Combine `provider` alone to tag, normalize, and check its data.
+        let value = std::mem::replace(&mut self.value, Ok(Map::new()));
+        let layers = std::mem::take(&mut self.layers);
+        let shadowed = std::mem::take(&mut self.shadowed);
+        let trace = self.trace.take();
+        let retain = std::mem::replace(&mut self.retain_layers, true);
+        let mut figment = self.provide_with(provider, order, arrays, Location::caller());
+        let layer = figment.layers.pop().expect("provider was combined");
+        figment.value = value;
+        figment.layers = layers;
+        figment.shadowed = shadowed;
+        figment.trace = trace;
+        figment.retain_layers = retain;
+
+        let old = std::mem::replace(&mut figment.layers[index], layer);
+        figment.metadata.remove(&old.tag);
+        let keys = match (&old.data, &figment.layers[index].data, &figment.value) {
+            (Some(Ok(old)), Some(Ok(new)), Ok(_)) => affected_keys(old, new),
+            _ => return figment.with_layers(&figment.layers()),
+        };
+
+        figment.recombine(&keys);
+        figment
+    }
+
+    /// Recombines the values at the top-level `keys` of each profile from
+    /// every layer, leaving all other values untouched. Every layer's data must
+    /// be retained, and it and the combined value must be `Ok`.
+    fn recombine(&mut self, keys: &Map<Profile, Vec<String>>) {
+        let value = std::mem::replace(&mut self.value, Ok(Map::new()));
+        let shadowed = self.shadowed.as_mut().map(std::mem::take);
+        let trace = self.trace.take();
+        for i in 0..self.layers.len() {
+            let layer = &self.layers[i];
+            let (order, arrays) = (layer.order, layer.arrays);
+            let data = match &layer.data {
+                Some(data) => data,
+                None => continue,
+            };
+
+            let data = data.as_ref().map(|data| {
+                keys.iter()
+                    .filter_map(|(profile, keys)| {
+                        let dict = data.get(profile)?;
+                        let dict = keys.iter()
+                            .filter_map(|k| Some((k.clone(), dict.get(k)?.clone())))
+                            .collect();
+
+                        Some((profile.clone(), dict))
+                    })
+                    .collect()
+            });
+
+            let data = data.map_err(|e| e.clone());
+            self.combine(data, order, arrays, &|_, _, _, _| Resolution::Default, |_| Ok(()));
+        }
+
+        let recombined = std::mem::replace(&mut self.value, value);
+        let reshadowed = std::mem::replace(&mut self.shadowed, shadowed);
+        self.trace = trace;
+
+        let (data, mut recombined) = match (&mut self.value, recombined) {
+            (Ok(data), Ok(recombined)) => (data, recombined),
+            (value, recombined) => {
+                *value = recombined;
+                return;
+            }
+        };
+
+        for (profile, keys) in keys {
+            let dict = data.entry(profile.clone()).or_default();
+            let new = recombined.remove(profile).unwrap_or_default();
+            for key in keys {
+                dict.remove(key);
+            }
+
+            dict.extend(new);
+            if let Some(shadowed) = self.shadowed.as_mut().and_then(|s| s.get_mut(profile)) {
+                shadowed.retain(|path, _| !keys.iter().any(|key| {
+                    matches!(path.strip_prefix(key.as_str()), Some(rest) if rest.is_empty() || rest.starts_with('.'))
+                }));
+            }
+        }
+
+        if let (Some(shadowed), Some(reshadowed)) = (&mut self.shadowed, reshadowed) {
+            for (profile, paths) in reshadowed {
+                shadowed.entry(profile).or_default().extend(paths);
+            }
         }
     }
 
-    /// Deserializes the collected value into `T`.
+    /// Merges `provider` into `self` until the returned guard is dropped, at
+    /// which point `self` is restored to its state before the call.
+    ///
+    /// The guard dereferences to `self` with `provider` merged. This is useful
+    /// in tests and REPLs, where a value must be overridden temporarily in a
+    /// figment that is otherwise long-lived. To temporarily override the
+    /// [global figment](crate::global), use
+    /// [`global::override_scoped()`](crate::global::override_scoped()).
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// let mut figment = Figment::from(Serialized::default("db.url", "postgres://prod"));
+    ///
+    /// let guard = figment.override_scoped(Serialized::default("db.url", "test://"));
+    /// assert_eq!(guard.extract_inner::<String>("db.url").unwrap(), "test://");
+    /// assert_eq!(guard.layers().len(), 2);
+    ///
+    /// drop(guard);
+    /// assert_eq!(figment.extract_inner::<String>("db.url").unwrap(), "postgres://prod");
+    /// assert_eq!(figment.layers().len(), 1);
+    /// ```
+    #[track_caller]
+    pub fn override_scoped<T: Provider>(&mut self, provider: T) -> OverrideGuard<'_> {
+        let saved = self.clone();
+        *self = std::mem::take(self).merge(provider);
+        OverrideGuard::new(self, saved)
+    }
+
+    /// Returns size statistics of the data in `self`: the number of providers
+    /// and, for every profile, the number of keys and values, the deepest
+    /// nesting, and an estimate of memory use. See [`Stats`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the figment's error if combining a provider failed.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use serde::Deserialize;
+    /// use figment::{Figment, Profile, util::map};
     ///
-    /// use figment::{Figment, providers::{Format, Toml, Json, Env}};
+    /// let figment = Figment::new()
+    ///     .merge(("name", "app"))
+    ///     .merge(("server", map!["hosts" => vec!["a.com", "b.com"]]));
+    ///
+    /// let stats = figment.stats().unwrap();
+    /// assert_eq!(stats.providers, 2);
+    ///
+    /// let global = &stats.profiles[&Profile::Global];
+    /// assert_eq!(global.keys, 3);
+    /// assert_eq!(global.values, 5);
+    /// assert_eq!(global.depth, 3);
+    /// assert_eq!(stats.keys(), 3);
+    /// assert!(stats.bytes() > 0);
+    /// ```
+    pub fn stats(&self) -> Result<Stats> {
+        match &self.value {
+            Ok(data) => Ok(Stats::new(self.layers.len(), data)),
+            Err(e) => Err(e.clone().resolved(self)),
+        }
+    }
+
+    /// Returns `true` if `self` and `other` contain the same data in every
+    /// profile, irrespective of how the data was provided or which profile is
+    /// selected. A profile without data and an empty profile are equivalent.
+    /// Integers are compared by value irrespective of their type, so an
+    /// integer parsed from a file and the same integer serialized as a `u16`
+    /// are the same. Returns `false` if combining a provider into either
+    /// figment failed.
     ///
-    /// #[derive(Debug, PartialEq, Deserialize)]
-    /// struct Config {
-    ///     name: String,
-    ///     numbers: Option<Vec<usize>>,
-    ///     debug: bool,
-    /// }
+    /// [`Figment`]'s `==` additionally requires the same profile to be
+    /// selected, so that equal figments extract the same values.
     ///
-    /// figment::Jail::expect_with(|jail| {
-    ///     jail.create_file("Config.toml", r#"
-    ///         name = "test"
-    ///         numbers = [1, 2, 3, 10]
-    ///     "#)?;
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Serialized}, util::map};
     ///
-    ///     jail.set_env("config_name", "env-test");
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         [default]
+    ///         port = 80
     ///
-    ///     jail.create_file("Config.json", r#"
-    ///         {
-    ///             "name": "json-test",
-    ///             "debug": true
-    ///         }
+    ///         [debug]
+    ///         workers = [1, 2]
     ///     "#)?;
     ///
-    ///     let config: Config = Figment::new()
-    ///         .merge(Toml::file("Config.toml"))
-    ///         .merge(Env::prefixed("CONFIG_"))
-    ///         .join(Json::file("Config.json"))
-    ///         .extract()?;
+    ///     let toml = Figment::from(Toml::file("App.toml").nested());
+    ///     let serialized = Figment::new()
+    ///         .merge(Serialized::default("port", 8080))
+    ///         .merge(Serialized::default("workers", vec![1u8, 2]).profile("debug"))
+    ///         .merge(Serialized::default("port", 80u16));
     ///
-    ///     assert_eq!(config, Config {
-    ///         name: "env-test".into(),
-    ///         numbers: vec![1, 2, 3, 10].into(),
-    ///         debug: true
-    ///     });
+    ///     assert!(toml.content_eq(&serialized));
+    ///     assert!(toml == serialized);
+    ///     assert_eq!(toml.content_hash()?, serialized.content_hash()?);
+    ///
+    ///     let debug = serialized.select("debug");
+    ///     assert!(toml.content_eq(&debug));
+    ///     assert!(toml != debug);
     ///
+    ///     let more = toml.clone().merge(("name", "app"));
+    ///     assert!(!toml.content_eq(&more));
     ///     Ok(())
     /// });
     /// ```
-    pub fn extract<'a, T: Deserialize<'a>>(&self) -> Result<T> {
-        let value = self.merged()?;
-        T::deserialize(ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value))
+    pub fn content_eq(&self, other: &Figment) -> bool {
+        let (a, b) = match (&self.value, &other.value) {
+            (Ok(a), Ok(b)) => (a, b),
+            _ => return false,
+        };
+
+        let empty = Dict::new();
+        a.keys().chain(b.keys()).all(|profile| {
+            let a = Value::Dict(Tag::Default, a.get(profile).unwrap_or(&empty).clone());
+            let b = Value::Dict(Tag::Default, b.get(profile).unwrap_or(&empty).clone());
+            a.content_eq(&b)
+        })
     }
 
-    /// As [`extract`](Figment::extract_lossy), but interpret numbers and
-    /// booleans more flexibly.
+    /// Returns a hash of the data in `self` consistent with
+    /// [`Figment::content_eq()`]: figments with the same content have the
+    /// same hash. The hash is suitable for detecting changes within a process
+    /// but is not guaranteed to be stable across Rust or Figment versions. See
+    /// [`Figment::content_eq()`] for an example.
     ///
-    /// See [`Value::to_bool_lossy`] and [`Value::to_num_lossy`] for a full
-    /// explanation of the imputs accepted.
+    /// # Errors
     ///
+    /// Returns the figment's error if combining a provider failed.
+    pub fn content_hash(&self) -> Result<u64> {
+        use std::hash::{Hash, Hasher};
+
+        let data = self.value.as_ref().map_err(|e| e.clone().resolved(self))?;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for (profile, dict) in data.iter().filter(|(_, dict)| !dict.is_empty()) {
+            profile.hash(&mut hasher);
+            Value::Dict(Tag::Default, dict.clone()).hash_content(&mut hasher);
+        }
+
+        Ok(hasher.finish())
+    }
+
+    /// Checks the data emitted by providers subsequently combined into `self`
+    /// against `schema` before the data is combined, handling mismatches as
+    /// dictated by `on_mismatch`.
+    ///
+    /// Mismatches are thus found as soon as a provider is combined, with
+    /// precise paths and the offending provider's metadata, instead of during
+    /// extraction. Each provider's data is checked independently: missing keys
+    /// are not mismatches. See [`Schema`] for details on schemas, including
+    /// generating one from a type, and [`OnMismatch`] for the available
+    /// policies. Mismatches that don't fail the figment are available via
+    /// [`Figment::warnings()`].
     ///
     /// # Example
     ///
     /// ```rust
     /// use serde::Deserialize;
+    /// use figment::{Figment, providers::{Format, Toml, Env}};
+    /// use figment::schema::{Schema, OnMismatch};
     ///
-    /// use figment::{Figment, providers::{Format, Toml, Json, Env}};
-    ///
-    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// #[derive(Debug, Deserialize)]
     /// struct Config {
     ///     name: String,
-    ///     numbers: Option<Vec<usize>>,
-    ///     debug: bool,
+    ///     port: u16,
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         name = "app"
+    ///         port = 8080
+    ///     "#)?;
+    ///
+    ///     jail.set_env("APP_PORT", "eighty");
+    ///     let figment = Figment::new()
+    ///         .check_merges(Schema::of::<Config>(), OnMismatch::Drop)
+    ///         .merge(Toml::file("Config.toml"))
+    ///         .merge(Env::prefixed("APP_"));
+    ///
+    ///     // The bad value was dropped, so the value from `Config.toml` remains.
+    ///     let config: Config = figment.extract()?;
+    ///     assert_eq!(config.port, 8080);
+    ///
+    ///     let warning = &figment.warnings()[0];
+    ///     assert_eq!(warning.path, vec!["port"]);
+    ///     assert!(warning.metadata.as_ref().unwrap().name.contains("APP_"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    #[cfg(feature = "schema")]
+    #[cfg_attr(nightly, doc(cfg(feature = "schema")))]
+    pub fn check_merges(mut self, schema: Schema, on_mismatch: OnMismatch) -> Self {
+        self.schema = Some((schema, on_mismatch));
+        self
+    }
+
+    /// Validates the configuration of the selected profile against the JSON
+    /// Schema `schema`, returning every mismatch, if any, as a single error.
+    ///
+    /// Each mismatch is reported with the path to and the metadata of the
+    /// offending value, so errors point to the file or environment variable
+    /// the value came from. Validating before extracting surfaces every
+    /// violated constraint at once, including constraints, like ranges, that
+    /// extraction doesn't check. See [`JsonSchema`] for the supported
+    /// keywords.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, Toml}, schema::JsonSchema};
+    ///
+    /// let schema = JsonSchema::new(serde_json::json!({
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "workers": { "type": "integer", "minimum": 1 },
+    ///         "level": { "enum": ["debug", "info"] }
+    ///     },
+    ///     "required": ["workers"]
+    /// })).unwrap();
+    ///
+    /// let figment = Figment::from(Toml::string("workers = 0\nlevel = \"trace\""));
+    /// let errors: Vec<_> = figment.validate(&schema).unwrap_err().into_iter().collect();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].path, ["level"]);
+    /// assert_eq!(errors[1].path, ["workers"]);
+    ///
+    /// let figment = Figment::from(Toml::string("level = \"info\""));
+    /// let error = figment.validate(&schema).unwrap_err();
+    /// assert!(error.missing());
+    /// ```
+    #[cfg(feature = "schema")]
+    #[cfg_attr(nightly, doc(cfg(feature = "schema")))]
+    pub fn validate(&self, schema: &JsonSchema) -> Result<()> {
+        crate::schema::join(schema.mismatches(self, &self.merged()?))
+    }
+
+    /// Sets whether keys provided by subsequently combined providers that are
+    /// similar to, but not the same as, an existing sibling key are reported
+    /// as [warnings](Figment::warnings()). Disabled by default.
+    ///
+    /// Keys are similar if they are equal ignoring case, `_`, and `-`, like
+    /// `time_out` and `timeOut`, or if they are at least five characters long
+    /// and differ by a single character or a single swap of adjacent
+    /// characters, like `timeout` and `timout`. Only keys in the same
+    /// dictionary and profile are compared, and only when the key exists in
+    /// the incoming provider's data but not the existing data, and vice-versa.
+    /// This catches typos across sources that unknown key detection can't: a
+    /// misspelled key is silently ignored when its correct spelling, provided
+    /// elsewhere, is extracted.
+    ///
+    /// Each warning is attributed to the incoming key's provider and names the
+    /// provider of the existing key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Env}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", "timeout = 30\n[db]\nmax_conns = 5")?;
+    ///     jail.set_env("APP_TIME_OUT", 60);
+    ///     jail.set_env("APP_DB__MAXCONNS", 10);
+    ///
+    ///     let figment = Figment::new()
+    ///         .detect_typos(true)
+    ///         .merge(Toml::file("App.toml"))
+    ///         .merge(Env::prefixed("APP_").split("__"));
+    ///
+    ///     let warnings = figment.warnings();
+    ///     assert_eq!(warnings.len(), 2);
+    ///     assert_eq!(warnings[0].path, ["db", "maxconns"]);
+    ///     assert_eq!(warnings[1].path, ["time_out"]);
+    ///     assert!(warnings[1].to_string().contains("similar to `timeout` from TOML file"));
+    ///     assert!(warnings[1].metadata.as_ref().unwrap().name.contains("APP_"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn detect_typos(mut self, enabled: bool) -> Self {
+        self.typos = enabled;
+        self
+    }
+
+    /// Sets whether references in string values are resolved when extracting.
+    /// Disabled by default.
+    ///
+    /// When enabled, `${name}` in a string value is replaced by the value of
+    /// the key path `name` in the configuration of the selected profile, after
+    /// all providers have been merged, or, if there is no such key, by the
+    /// value of the environment variable `name`. `${name:-default}` resolves
+    /// to `default` when neither exists or the variable is empty, and `$${` is
+    /// a literal `${`. A string that consists of a single reference to a key
+    /// is replaced by the key's value, preserving its type; otherwise,
+    /// referenced values must be strings, characters, booleans, or numbers.
+    /// Referenced values may themselves contain references.
+    ///
+    /// Extracting fails if a reference is unterminated, refers to neither a key
+    /// nor a set variable, or is cyclic. The error is attributed to the value
+    /// containing the reference.
+    ///
+    /// Unlike [`Data::expand_env()`](crate::providers::Data::expand_env()),
+    /// which expands environment variables when a single file is read,
+    /// references are resolved against the merged configuration, so they may
+    /// refer to values from any provider, and a value overridden by a later
+    /// provider is seen by every reference to it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Env, Serialized}};
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Db {
+    ///     host: String,
+    ///     port: u16,
+    ///     url: String,
+    /// }
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         [db]
+    ///         host = "localhost"
+    ///         port = "${DB_PORT:-5432}"
+    ///         url = "postgres://${db.host}:${db.port}/${DB_NAME}"
+    ///         note = "$${db.host} is left alone"
+    ///     "#)?;
+    ///
+    ///     jail.set_env("DB_NAME", "app");
+    ///     jail.set_env("APP_DB_HOST", "db.internal");
+    ///
+    ///     let figment = Figment::from(Toml::file("App.toml"))
+    ///         .merge(Env::prefixed("APP_").split("_"))
+    ///         .resolve_interpolation(true);
+    ///
+    ///     let db: Db = figment.extract_inner("db")?;
+    ///     assert_eq!(db.url, "postgres://db.internal:5432/app");
+    ///     assert_eq!(db.port, 5432);
+    ///     assert_eq!(figment.extract_inner::<String>("db.note")?, "${db.host} is left alone");
+    ///
+    ///     let figment = figment.merge(Serialized::default("db.port", "${db.url}"));
+    ///     let error = figment.extract_inner::<Db>("db").unwrap_err();
+    ///     assert!(error.to_string().contains("cyclic reference"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn resolve_interpolation(mut self, enabled: bool) -> Self {
+        self.interpolation = enabled;
+        self.extensions = Extensions::default();
+        self
+    }
+
+    /// Returns the non-fatal issues found while combining providers into
+    /// `self`, in the order they were found. See [`Figment::check_merges()`]
+    /// and [`Figment::detect_typos()`].
+    pub fn warnings(&self) -> &[Error] {
+        &self.warnings
+    }
+
+    /// Sets the [`UnknownPolicy`] for extractions from `self`: whether keys
+    /// that aren't fields of the struct being extracted are errors. The
+    /// default, like `serde`, is [`UnknownPolicy::Allow`]. This is equivalent
+    /// to `unknown_keys_at("", policy)`.
+    ///
+    /// Unknown keys are checked by [`Figment::extract()`],
+    /// [`Figment::extract_inner()`], and their `lossy` variants, using the
+    /// [`Schema`] of the type being extracted. An error is returned for every
+    /// denied key, followed by any error from deserialization itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use serde::Deserialize;
+    /// use figment::{Figment, providers::{Format, Toml}, schema::UnknownPolicy};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Server {
+    ///     port: u16,
+    ///     #[serde(default)]
+    ///     extensions: HashMap<String, String>,
+    /// }
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     #[serde(default)]
+    ///     server: Option<Server>,
+    /// }
+    ///
+    /// let figment = Figment::from(Toml::string(r#"
+    ///     [sever]
+    ///     port = 80
+    /// "#));
+    ///
+    /// // By default, the misspelled section is silently ignored.
+    /// assert!(figment.extract::<Config>()?.server.is_none());
+    ///
+    /// let strict = figment.unknown_keys(UnknownPolicy::DenySections);
+    /// let error = strict.extract::<Config>().unwrap_err();
+    /// assert_eq!(error.path, vec!["sever"]);
+    /// assert!(error.to_string().contains("unknown section `sever`"));
+    ///
+    /// // With `DenySections`, unknown leaf keys are ignored.
+    /// let figment = Figment::from(Toml::string(r#"
+    ///     timeout = 10
+    ///     [server]
+    ///     port = 80
+    ///     extensions = { tracing = "on" }
+    /// "#)).unknown_keys(UnknownPolicy::DenySections);
+    ///
+    /// let config: Config = figment.extract()?;
+    /// assert_eq!(config.server.unwrap().extensions["tracing"], "on");
+    ///
+    /// // With `DenyAll`, they are not.
+    /// let figment = figment.unknown_keys(UnknownPolicy::DenyAll);
+    /// assert!(figment.extract::<Config>().is_err());
+    /// # Ok::<(), figment::Error>(())
+    /// ```
+    pub fn unknown_keys(self, policy: UnknownPolicy) -> Self {
+        self.unknown_keys_at("", policy)
+    }
+
+    /// Sets the [`UnknownPolicy`] for structs at or nested within the key path
+    /// `path`. The policy for a struct is the one set for the longest key path
+    /// containing the struct's path; later settings for the same path replace
+    /// earlier ones. Paths are relative to the root of the figment, even when
+    /// extracting via [`Figment::extract_inner()`]. See
+    /// [`Figment::unknown_keys()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, providers::{Format, Toml}, schema::UnknownPolicy};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Plugin {
+    ///     enabled: bool,
+    /// }
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     plugin: Plugin,
+    /// }
+    ///
+    /// let figment = Figment::from(Toml::string(r#"
+    ///     name = "app"
+    ///
+    ///     [plugin]
+    ///     enabled = true
+    ///     vendor-option = 5
+    /// "#));
+    ///
+    /// // Be strict everywhere except for the `plugin` section.
+    /// let figment = figment.unknown_keys(UnknownPolicy::DenyAll)
+    ///     .unknown_keys_at("plugin", UnknownPolicy::Allow);
+    ///
+    /// assert!(figment.extract::<Config>()?.plugin.enabled);
+    ///
+    /// let figment = figment.unknown_keys_at("plugin", UnknownPolicy::DenyAll);
+    /// let error = figment.extract_inner::<Plugin>("plugin").unwrap_err();
+    /// assert_eq!(error.path, vec!["plugin", "vendor-option"]);
+    /// # Ok::<(), figment::Error>(())
+    /// ```
+    pub fn unknown_keys_at(mut self, path: &str, policy: UnknownPolicy) -> Self {
+        self.extensions = Extensions::default();
+        self.unknown.retain(|(p, _)| p != path);
+        self.unknown.push((path.to_string(), policy));
+        self
+    }
+
+    /// Sets the formatting tolerated when extracting a number from a string.
+    /// By default, none is: see [`NumericLeniency`] for details.
+    ///
+    /// This allows operator-edited values like `1_000_000` or `1,000`, which
+    /// sources like [`Env`](crate::providers::Env) emit as strings, to be
+    /// extracted as numbers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, Jail, providers::Env, value::NumericLeniency};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     max_bytes: u64,
+    ///     offset: i32,
+    ///     ratio: f32,
+    /// }
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("APP_MAX_BYTES", "1_000_000");
+    ///     jail.set_env("APP_OFFSET", "+1,024");
+    ///     jail.set_env("APP_RATIO", "0.5");
+    ///
+    ///     let figment = Figment::from(Env::prefixed("APP_"));
+    ///     assert!(figment.extract::<Config>().is_err());
+    ///
+    ///     let config: Config = figment.numeric_leniency(NumericLeniency::all()).extract()?;
+    ///     assert_eq!(config.max_bytes, 1_000_000);
+    ///     assert_eq!(config.offset, 1024);
+    ///     assert_eq!(config.ratio, 0.5);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn numeric_leniency(mut self, leniency: NumericLeniency) -> Self {
+        self.numeric = leniency;
+        self.extensions = Extensions::default();
+        self
+    }
+
+    /// Sets how numbers are coerced when extracted from `self`. By default,
+    /// floats aren't extracted as integers, unsigned integers too large for a
+    /// signed integer type fail to extract, and `NaN` and infinities are
+    /// extracted as floats. See [`NumPolicy`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, Jail, providers::{Format, Json, Toml}, value::NumPolicy};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     port: u16,
+    ///     seed: i64,
+    ///     ratio: f64,
+    /// }
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.json", r#"{
+    ///         "port": 8080.0,
+    ///         "seed": 18446744073709551615,
+    ///         "ratio": 0.5
+    ///     }"#)?;
+    ///
+    ///     let figment = Figment::from(Json::file("App.json"));
+    ///     assert!(figment.extract::<Config>().is_err());
+    ///
+    ///     let policy = NumPolicy::default().float_to_int(true).wrap_unsigned(true);
+    ///     let config: Config = figment.clone().num_policy(policy).extract()?;
+    ///     assert_eq!(config.port, 8080);
+    ///     assert_eq!(config.seed, -1);
+    ///
+    ///     let error = figment.num_policy(policy).extract_inner::<u8>("port").unwrap_err();
+    ///     assert!(error.to_string().contains("expected u8"));
+    ///
+    ///     jail.create_file("App.toml", "ratio = inf")?;
+    ///     let figment = Figment::from(Toml::file("App.toml"));
+    ///     assert!(figment.extract_inner::<f64>("ratio")?.is_infinite());
+    ///
+    ///     let figment = figment.num_policy(NumPolicy::strict());
+    ///     let error = figment.extract_inner::<f64>("ratio").unwrap_err();
+    ///     assert!(error.to_string().contains("expected a finite number"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn num_policy(mut self, policy: NumPolicy) -> Self {
+        self.num_policy = policy;
+        self.extensions = Extensions::default();
+        self
+    }
+
+    /// Bounds the data emitted by providers subsequently combined into `self`
+    /// to `limits`. Data exceeding a limit fails the figment with an error for
+    /// which [`Error::limit()`] returns the limit. See [`Limits`] for details.
+    ///
+    /// Limits protect services that load user-supplied configuration from
+    /// unbounded memory use. Set them before combining untrusted providers:
+    /// providers already combined are not checked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    /// use figment::value::{Limits, Limit};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("user.toml", r#"
+    ///         name = "app"
+    ///         hosts = ["a", "b", "c", "d"]
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::new()
+    ///         .limits(Limits::none().keys(100).array_len(3))
+    ///         .merge(Toml::file("user.toml"));
+    ///
+    ///     let error = figment.extract_inner::<String>("name").unwrap_err();
+    ///     assert_eq!(error.limit(), Some((Limit::ArrayLength, 3)));
+    ///     assert_eq!(error.path, vec!["hosts"]);
+    ///     assert!(error.to_string().contains("array length exceeds the limit of 3"));
+    ///
+    ///     let figment = Figment::new()
+    ///         .limits(Limits::none().keys(1))
+    ///         .merge(Toml::file("user.toml"));
+    ///
+    ///     assert!(figment.extract_inner::<String>("name").is_err());
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Includes a sample of the offending value in errors produced when
+    /// extracting from `self`, showing at most `entries` entries of every
+    /// dictionary and array in the value. Samples are set in
+    /// [`Error::sample`](crate::Error::sample) and displayed after the error's
+    /// kind. Long strings are truncated and deeply nested values elided so
+    /// that samples of large values remain short.
+    ///
+    /// Samples are disabled by default.
+    ///
+    /// ```rust
+    /// use figment::Figment;
+    ///
+    /// let figment = Figment::from(("port", figment::util::map! {
+    ///     "a" => 1, "b" => 2, "c" => 3
+    /// }));
+    ///
+    /// let error = figment.extract_inner::<u16>("port").unwrap_err();
+    /// assert!(error.sample().is_none());
+    ///
+    /// let error = figment.error_samples(2).extract_inner::<u16>("port").unwrap_err();
+    /// assert_eq!(error.sample(), Some("{a: 1, b: 2, ... 1 more}"));
+    /// assert!(error.to_string().starts_with("invalid type: found map, expected u16: {a: 1"));
+    /// ```
+    pub fn error_samples(mut self, entries: usize) -> Self {
+        self.samples = Some(entries);
+        self
+    }
+
+    /// Normalizes the keys of providers subsequently combined into `self` with
+    /// `normalizer` before their data is merged, so that keys with distinct
+    /// but equivalent spellings, such as composed and decomposed Unicode,
+    /// refer to the same value. See [`KeyNormalizer`] for details, including
+    /// NFC and NFKC normalization.
+    ///
+    /// Keys in the same dictionary of a single provider that normalize to the
+    /// same key are merged, in key order. Every distinct spelling of a
+    /// normalized key is recorded and available via
+    /// [`Figment::key_collisions()`]. Key paths used to extract or look up
+    /// values are not normalized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Env}};
+    /// use figment::value::KeyNormalizer;
+    ///
+    /// // A stand-in for NFC normalization.
+    /// let nfc = KeyNormalizer::new(|key| key.replace("e\u{301}", "\u{e9}"));
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", "\"caf\u{e9}\" = \"open\"")?;
+    ///     jail.set_env("APP_CAFE\u{301}", "closed");
+    ///
+    ///     let figment = Figment::new()
+    ///         .normalize_keys(nfc)
+    ///         .merge(Toml::file("App.toml"))
+    ///         .merge(Env::prefixed("APP_"));
+    ///
+    ///     assert_eq!(figment.extract_inner::<String>("caf\u{e9}")?, "closed");
+    ///
+    ///     let collisions = figment.key_collisions();
+    ///     assert_eq!(collisions.len(), 1);
+    ///     assert_eq!(collisions[0].key, "caf\u{e9}");
+    ///     assert_eq!(collisions[0].spellings.len(), 2);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn normalize_keys(mut self, normalizer: KeyNormalizer) -> Self {
+        self.normalizer = Some(normalizer);
+        self
+    }
+
+    /// Converts the keys of providers subsequently combined into `self` to the
+    /// casing convention `case` before their data is merged, so that sources
+    /// written in different conventions line up with each other and with the
+    /// target structure without `#[serde(rename_all)]` or `alias` attributes.
+    /// See [`Case`] for how keys are converted.
+    ///
+    /// This is equivalent to `self.normalize_keys(case.into())` and replaces
+    /// any normalizer previously set with [`Figment::normalize_keys()`]. Keys
+    /// provided in more than one spelling are available via
+    /// [`Figment::key_collisions()`].
+    ///
+    /// [`Case`]: crate::value::Case
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Json}};
+    /// use figment::value::Case;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     log_level: String,
+    ///     max_retries: u8,
+    ///     tls_cert_path: String,
+    /// }
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         log-level = "info"
+    ///         max-retries = 3
+    ///     "#)?;
+    ///
+    ///     jail.create_file("App.json", r#"{
+    ///         "maxRetries": 5,
+    ///         "tlsCertPath": "cert.pem"
+    ///     }"#)?;
+    ///
+    ///     let config: Config = Figment::new()
+    ///         .rename_all(Case::Snake)
+    ///         .merge(Toml::file("App.toml"))
+    ///         .merge(Json::file("App.json"))
+    ///         .extract()?;
+    ///
+    ///     assert_eq!(config.log_level, "info");
+    ///     assert_eq!(config.max_retries, 5);
+    ///     assert_eq!(config.tls_cert_path, "cert.pem");
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn rename_all(self, case: Case) -> Self {
+        self.normalize_keys(case.into())
+    }
+
+    /// Replaces placeholders of the form `${NAME}` in the keys of providers
+    /// subsequently combined into `self` with the value of `NAME` in `vars`
+    /// before their data is merged.
+    ///
+    /// This allows a key layout that depends on runtime identity, such as a
+    /// tenant or region, to be written once as a key path like
+    /// `tenants.${TENANT}.db` instead of formatted for every provider. A
+    /// value containing `.` results in nested keys. A placeholder without a
+    /// value in `vars` fails the figment. Calling this method again replaces
+    /// the previous `vars`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Serialized}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("tenant.toml", r#"
+    ///         [tenants."${TENANT}"]
+    ///         name = "Acme Corp."
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::new()
+    ///         .key_context([("TENANT", "acme"), ("REGION", "eu.west")])
+    ///         .merge(Serialized::default("tenants.${TENANT}.db", "postgres://acme"))
+    ///         .merge(Toml::file("tenant.toml"))
+    ///         .merge(Serialized::default("regions.${REGION}", true));
+    ///
+    ///     assert_eq!(figment.extract_inner::<String>("tenants.acme.db")?, "postgres://acme");
+    ///     assert_eq!(figment.extract_inner::<String>("tenants.acme.name")?, "Acme Corp.");
+    ///     assert!(figment.extract_inner::<bool>("regions.eu.west")?);
+    ///
+    ///     let figment = Figment::new()
+    ///         .key_context([("TENANT", "acme")])
+    ///         .merge(Serialized::default("users.${USER}", "me"));
+    ///
+    ///     let error = figment.extract_inner::<String>("users").unwrap_err();
+    ///     assert!(error.to_string().contains("unknown key placeholder `${USER}`"));
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn key_context<I, K, V>(mut self, vars: I) -> Self
+        where I: IntoIterator<Item = (K, V)>, K: Into<String>, V: Into<String>
+    {
+        let vars = vars.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
+        self.key_context = Some(vars);
+        self
+    }
+
+    /// Moves the value at the key path `old`, in any profile, to the key path
+    /// `new` in the data of providers subsequently combined into `self`,
+    /// recording a deprecation notice for every value moved.
+    ///
+    /// This keeps a renamed configuration option working during a deprecation
+    /// window: sources that still use `old` supply the value for `new`. If a
+    /// provider sets both `old` and `new`, the value at `new` takes
+    /// precedence; if both are dictionaries, they are merged. A value at `old`
+    /// from one provider and at `new` from another are combined as usual
+    /// according to the order of the providers. Aliases may be chained, as in
+    /// `a` to `b` and `b` to `c`. Dictionaries left empty by a move are
+    /// removed.
+    ///
+    /// Each moved value is recorded as a [warning](Figment::warnings()) with
+    /// the path `old`, the profile, and the metadata of the provider that used
+    /// `old`, so that deprecated options can be reported after extraction.
+    /// See [`Figment::rename_keys()`] to rename many keys with a function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Env}};
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Pool {
+    ///     size: u32,
+    ///     timeout: u32,
+    /// }
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         [server]
+    ///         workers = 8
+    ///
+    ///         [database]
+    ///         timeout = 30
+    ///     "#)?;
+    ///
+    ///     jail.set_env("APP_DB.TIMEOUT", 60);
+    ///     let figment = Figment::new()
+    ///         .alias("server.workers", "pool.size")
+    ///         .alias("database.timeout", "pool.timeout")
+    ///         .alias("db.timeout", "database.timeout")
+    ///         .merge(Toml::file("App.toml"))
+    ///         .merge(Env::prefixed("APP_"));
+    ///
+    ///     let pool: Pool = figment.extract_inner("pool")?;
+    ///     assert_eq!(pool, Pool { size: 8, timeout: 60 });
+    ///     assert!(!figment.contains("server"));
+    ///
+    ///     let notices: Vec<_> = figment.warnings().iter().map(|w| w.path.join(".")).collect();
+    ///     assert_eq!(notices, ["database.timeout", "server.workers", "db.timeout"]);
+    ///
+    ///     let notice = &figment.warnings()[1];
+    ///     assert!(notice.to_string().starts_with("`server.workers` is deprecated; use `pool.size` instead"));
+    ///     assert!(notice.metadata.as_ref().unwrap().name.contains("TOML"));
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn alias(self, old: &str, new: &str) -> Self {
+        let (old, new) = (old.to_string(), new.to_string());
+        self.rename_keys(move |path| match path == old {
+            true => Some(new.clone()),
+            false => None,
+        })
+    }
+
+    /// Moves values in the data of providers subsequently combined into `self`
+    /// to the key path `f` returns for their key path, if any, recording a
+    /// deprecation notice for every value moved.
+    ///
+    /// `f` is called with the `.`-separated key path, without the profile, of
+    /// every dictionary and value, outermost first. A value is moved, along
+    /// with everything it contains, if `f` returns `Some` path other than its
+    /// own; values within it are not considered further. Otherwise, `f` is
+    /// called for the values within it, if any. Renames set earlier are
+    /// consulted first. See [`Figment::alias()`] for details on how values
+    /// are moved and notices recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized, util::map};
+    ///
+    /// // Version 2 moved every `http_*` key into an `http` section.
+    /// let figment = Figment::new()
+    ///     .rename_keys(|path| {
+    ///         let key = path.strip_prefix("http_")?;
+    ///         Some(format!("http.{}", key))
+    ///     })
+    ///     .merge(Serialized::defaults(map!["http_port" => 80, "http_host" => 0]))
+    ///     .merge(Serialized::default("http.host", "localhost"));
+    ///
+    /// assert_eq!(figment.extract_inner::<u16>("http.port").unwrap(), 80);
+    /// assert_eq!(figment.extract_inner::<String>("http.host").unwrap(), "localhost");
+    /// assert_eq!(figment.warnings().len(), 2);
+    /// ```
+    pub fn rename_keys<F>(mut self, f: F) -> Self
+        where F: Fn(&str) -> Option<String> + Send + Sync + 'static
+    {
+        self.renames.push(Renamer(Arc::new(f)));
+        self
+    }
+
+    /// Marks the key path `key` as deprecated with the note `note`, typically
+    /// what to use instead.
+    ///
+    /// Marking a key deprecated doesn't change how it's combined or extracted.
+    /// Instead, [`Figment::deprecations()`] reports every marked key that is
+    /// set in the configuration of the selected profile, along with the
+    /// provider that set it, so that an application can warn about their use
+    /// without failing. Marking a key again replaces its note. To also keep a
+    /// renamed key working, see [`Figment::alias()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         [default.tls]
+    ///         insecure = true
+    ///
+    ///         [prod]
+    ///         workers = 8
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("App.toml").nested())
+    ///         .deprecate("tls.insecure", "use `tls.verify = false`")
+    ///         .deprecate("workers", "use `pool.size`")
+    ///         .deprecate("legacy", "remove it");
+    ///
+    ///     let deprecations = figment.deprecations();
+    ///     assert_eq!(deprecations.len(), 1);
+    ///     assert_eq!(deprecations[0].key, "tls.insecure");
+    ///     assert_eq!(deprecations[0].to_string(),
+    ///         "`tls.insecure` is deprecated: use `tls.verify = false` (in App.toml TOML file)");
+    ///
+    ///     let prod = figment.select("prod");
+    ///     let keys: Vec<_> = prod.deprecations().into_iter().map(|d| d.key).collect();
+    ///     assert_eq!(keys, ["tls.insecure", "workers"]);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn deprecate(mut self, key: &str, note: &str) -> Self {
+        self.deprecated.retain(|(k, _)| k != key);
+        self.deprecated.push((key.into(), note.into()));
+        self
+    }
+
+    /// Returns every key marked deprecated via [`Figment::deprecate()`] that
+    /// is set in the configuration of the selected profile, that is, that
+    /// [`Figment::extract()`] would read, in the order the keys were marked.
+    /// See [`Figment::deprecate()`] for an example.
+    ///
+    /// Each [`Deprecation`] carries the profile the value came from and the
+    /// metadata of the provider that set it. If a figment fails to evaluate,
+    /// no deprecations are reported.
+    pub fn deprecations(&self) -> Vec<Deprecation> {
+        self.deprecated.iter()
+            .filter_map(|(key, note)| {
+                let (value, profile) = self.lookup_with_profile(key).ok()?;
+                Some(Deprecation {
+                    key: key.clone(),
+                    note: note.clone(),
+                    profile,
+                    metadata: self.get_metadata(value.tag()).cloned(),
+                })
+            })
+            .collect()
+    }
+
+    /// Combines the arrays at the key path `path`, in any profile, element-wise
+    /// by the value of each element's `field` when providers are subsequently
+    /// combined into `self`.
+    ///
+    /// By default, an array is combined with an existing array as a whole: it
+    /// is kept, replaced, or concatenated as described in [conflict
+    /// resolution](#conflict-resolution). With an array key set, an incoming
+    /// element that is a dictionary with a `field` equal to that of an existing
+    /// element is instead combined with that element, recursively, using the
+    /// same strategy. Every other incoming element is appended. This allows
+    /// sources to contribute to and override entries of lists like
+    /// `[[listeners]]` by name. Setting an array key for `path` again replaces
+    /// the previous `field`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Listener {
+    ///     name: String,
+    ///     port: u16,
+    ///     tls: bool,
+    /// }
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("base.toml", r#"
+    ///         [[listeners]]
+    ///         name = "public"
+    ///         port = 80
+    ///         tls = false
+    ///
+    ///         [[listeners]]
+    ///         name = "admin"
+    ///         port = 8080
+    ///         tls = false
+    ///     "#)?;
+    ///
+    ///     jail.create_file("prod.toml", r#"
+    ///         [[listeners]]
+    ///         name = "public"
+    ///         port = 443
+    ///         tls = true
+    ///
+    ///         [[listeners]]
+    ///         name = "metrics"
+    ///         port = 9090
+    ///         tls = false
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::new()
+    ///         .array_key("listeners", "name")
+    ///         .merge(Toml::file("base.toml"))
+    ///         .merge(Toml::file("prod.toml"));
+    ///
+    ///     let listeners: Vec<Listener> = figment.extract_inner("listeners")?;
+    ///     let names: Vec<_> = listeners.iter().map(|l| (&*l.name, l.port)).collect();
+    ///     assert_eq!(names, [("public", 443), ("admin", 8080), ("metrics", 9090)]);
+    ///     assert!(listeners[0].tls);
+    ///
+    ///     // `join` keeps the existing values of matching elements.
+    ///     let figment = Figment::new()
+    ///         .array_key("listeners", "name")
+    ///         .join(Toml::file("base.toml"))
+    ///         .join(Toml::file("prod.toml"));
+    ///
+    ///     let listeners: Vec<Listener> = figment.extract_inner("listeners")?;
+    ///     assert_eq!(listeners[0].port, 80);
+    ///     assert_eq!(listeners.len(), 3);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn array_key(mut self, path: &str, field: &str) -> Self {
+        self.array_keys.retain(|(p, _)| p != path);
+        self.array_keys.push((path.to_string(), field.to_string()));
+        self
+    }
+
+    /// Sets the strategy with which conflicting arrays in subsequently
+    /// combined providers are combined to `strategy`, overriding the arrays
+    /// column of the [conflict resolution](#conflict-resolution) table. See
+    /// [`ArrayMerge`] for the available strategies.
+    ///
+    /// A strategy set for a specific key path via [`Figment::arrays_at()`],
+    /// for a single provider via [`Figment::merge_with()`] or
+    /// [`Figment::join_with()`], or an identifying field set via
+    /// [`Figment::array_key()`] takes precedence.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, ArrayMerge, providers::{Format, Toml}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("base.toml", r#"
+    ///         plugins = ["auth", "logging"]
+    ///         ports = [80, 443]
+    ///     "#)?;
+    ///
+    ///     jail.create_file("prod.toml", r#"
+    ///         plugins = ["metrics"]
+    ///         ports = [8080]
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::new()
+    ///         .arrays(ArrayMerge::Append)
+    ///         .arrays_at("ports", ArrayMerge::Index)
+    ///         .merge(Toml::file("base.toml"))
+    ///         .merge(Toml::file("prod.toml"));
+    ///
+    ///     let plugins: Vec<String> = figment.extract_inner("plugins")?;
+    ///     assert_eq!(plugins, ["auth", "logging", "metrics"]);
+    ///
+    ///     let ports: Vec<u16> = figment.extract_inner("ports")?;
+    ///     assert_eq!(ports, [8080, 443]);
+    ///
+    ///     let figment = figment.arrays(ArrayMerge::Prepend).merge(Toml::file("prod.toml"));
+    ///     let plugins: Vec<String> = figment.extract_inner("plugins")?;
+    ///     assert_eq!(plugins, ["metrics", "auth", "logging", "metrics"]);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn arrays(mut self, strategy: ArrayMerge) -> Self {
+        self.arrays = Some(strategy);
+        self
+    }
+
+    /// Sets how conflicts between values of different types in subsequently
+    /// combined providers are resolved to `policy`. See [`TypeConflict`] for
+    /// the available policies and what constitutes a conflict.
+    ///
+    /// By default, conflicts are resolved by the combining strategy alone as
+    /// described in [conflict resolution](#conflict-resolution), so a string
+    /// `"8080"` merged over an integer `9090` yields the string `"8080"`.
+    /// [`TypeConflict::PreferTyped`] instead yields the integer `8080`, and
+    /// [`TypeConflict::Error`] makes the conflict an error, reported when
+    /// extracting, for configurations where a silently changed type is
+    /// unacceptable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, TypeConflict, providers::{Format, Toml, Json}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", "port = 9090\ndebug = false")?;
+    ///     jail.create_file("App.json", r#"{ "port": "8080", "debug": "yes" }"#)?;
+    ///
+    ///     let figment = Figment::new()
+    ///         .merge(Toml::file("App.toml"))
+    ///         .merge(Json::file("App.json"));
+    ///
+    ///     assert_eq!(figment.extract_inner::<String>("port")?, "8080");
+    ///     assert_eq!(figment.extract_inner::<String>("debug")?, "yes");
+    ///
+    ///     let typed = Figment::new()
+    ///         .type_conflicts(TypeConflict::PreferTyped)
+    ///         .merge(Toml::file("App.toml"))
+    ///         .merge(Json::file("App.json"));
+    ///
+    ///     assert_eq!(typed.extract_inner::<u16>("port")?, 8080);
+    ///     assert_eq!(typed.extract_inner::<bool>("debug")?, false);
+    ///
+    ///     let strict = Figment::new()
+    ///         .type_conflicts(TypeConflict::Error)
+    ///         .merge(Toml::file("App.toml"))
+    ///         .merge(Json::file("App.json"));
+    ///
+    ///     let error = strict.extract_inner::<u16>("port").unwrap_err();
+    ///     assert_eq!(error.count(), 2);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn type_conflicts(mut self, policy: TypeConflict) -> Self {
+        self.type_conflicts = policy;
+        self
+    }
+
+    /// Sets the strategy with which conflicting arrays at the key path `path`
+    /// in subsequently combined providers are combined to `strategy`. See
+    /// [`Figment::arrays()`] for details and an example.
+    pub fn arrays_at(mut self, path: &str, strategy: ArrayMerge) -> Self {
+        self.array_merges.retain(|(p, _)| p != path);
+        self.array_merges.push((path.to_string(), strategy));
+        self
+    }
+
+    /// Merges `provider` into the current figment, combining conflicting
+    /// arrays with `strategy` rather than as configured by
+    /// [`Figment::arrays()`] or [`Figment::arrays_at()`].
+    ///
+    /// ```rust
+    /// use figment::{Figment, ArrayMerge};
+    ///
+    /// let figment = Figment::new()
+    ///     .merge(("plugins", ["auth"]))
+    ///     .merge_with(("plugins", ["metrics"]), ArrayMerge::Append)
+    ///     .merge_with(("plugins", ["tracing"]), ArrayMerge::Prepend);
+    ///
+    /// let plugins: Vec<String> = figment.extract_inner("plugins").unwrap();
+    /// assert_eq!(plugins, ["tracing", "auth", "metrics"]);
+    ///
+    /// let figment = figment.merge(("plugins", ["none"]));
+    /// assert_eq!(figment.extract_inner::<Vec<String>>("plugins").unwrap(), ["none"]);
+    /// ```
+    #[track_caller]
+    pub fn merge_with<T: Provider>(self, provider: T, strategy: ArrayMerge) -> Self {
+        self.provide_with(provider, Order::Merge, Some(strategy), Location::caller())
+    }
+
+    /// Joins `provider` into the current figment, combining conflicting
+    /// arrays with `strategy` rather than as configured by
+    /// [`Figment::arrays()`] or [`Figment::arrays_at()`].
+    ///
+    /// ```rust
+    /// use figment::{Figment, ArrayMerge};
+    ///
+    /// let figment = Figment::new()
+    ///     .merge(("ports", [8080]))
+    ///     .join_with(("ports", [80, 443]), ArrayMerge::Index);
+    ///
+    /// let ports: Vec<u16> = figment.extract_inner("ports").unwrap();
+    /// assert_eq!(ports, [8080, 443]);
+    /// ```
+    #[track_caller]
+    pub fn join_with<T: Provider>(self, provider: T, strategy: ArrayMerge) -> Self {
+        self.provide_with(provider, Order::Join, Some(strategy), Location::caller())
+    }
+
+    /// Returns every key that was provided with more than one spelling that
+    /// normalized to the same key via the normalizer set with
+    /// [`Figment::normalize_keys()`]. Returns an empty vector if no normalizer
+    /// was set. See [`Figment::normalize_keys()`] for an example.
+    ///
+    /// The metadata of the provider of each spelling can be retrieved with
+    /// [`Figment::get_metadata()`].
+    pub fn key_collisions(&self) -> Vec<KeyCollision> {
+        KeyCollision::all(&self.spellings)
+    }
+
+    /// Completes the extraction of `T` from `value`, the value at `path`,
+    /// given the `result` of deserializing `T`, by checking `value` for
+    /// unknown keys if a policy is set, and logs the outcome.
+    pub(crate) fn check_unknown<'a, T>(&self, path: &str, value: &Value, result: Result<T>) -> Result<T>
+        where T: Deserialize<'a>
+    {
+        self.check_unknown_with(path, value, result, None)
+    }
+
+    /// As [`check_unknown()`](Self::check_unknown()), but with `schema`, if
+    /// any, as the precomputed schema of `T`.
+    pub(crate) fn check_unknown_with<'a, T>(
+        &self,
+        path: &str,
+        value: &Value,
+        result: Result<T>,
+        schema: Option<&Schema>,
+    ) -> Result<T>
+        where T: Deserialize<'a>
+    {
+        let result = self.check_unknown_keys(path, value, result, schema)
+            .map_err(|e| suggest_missing(e, path, value));
+
+        crate::logging::extracted(self, path, &result);
+        result
+    }
+
+    /// Checks `value`, the value at `path`, for unknown keys given the
+    /// `result` of deserializing `T` from it, if a policy is set.
+    fn check_unknown_keys<'a, T>(
+        &self,
+        path: &str,
+        value: &Value,
+        result: Result<T>,
+        schema: Option<&Schema>,
+    ) -> Result<T>
+        where T: Deserialize<'a>
+    {
+        if self.unknown.is_empty() {
+            return result;
+        }
+
+        let errors = match schema {
+            Some(schema) => schema.unknowns(self, path, value),
+            None => Schema::of::<T>().unknowns(self, path, value),
+        };
+        match result {
+            Ok(v) => crate::schema::join(errors).map(|_| v),
+            Err(e) => Err(crate::schema::join(errors.into_iter().chain(e).collect()).unwrap_err()),
+        }
+    }
+
+    /// Enables or disables recording of merge decisions for providers
+    /// subsequently combined into `self`. Disabling tracing discards any
+    /// recorded decisions. Tracing is disabled by default.
+    ///
+    /// Recorded decisions can be retrieved via [`Figment::merge_trace()`] and
+    /// are useful to determine why a value isn't taking effect. See
+    /// [`MergeEvent`] for details on what is recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, MergeDecision};
+    ///
+    /// let figment = Figment::new()
+    ///     .merge(("untraced", 1))
+    ///     .trace_merges(true)
+    ///     .merge(("app", figment::util::map!["name" => "app", "port" => "80"]))
+    ///     .join(("app.port", 8080))
+    ///     .adjoin(("app.hosts", vec!["a"]))
+    ///     .admerge(("app.hosts", vec!["b"]))
+    ///     .merge(("app.name", "new"));
+    ///
+    /// let decisions: Vec<_> = figment.merge_trace().iter()
+    ///     .map(|e| (e.path.as_str(), e.decision))
+    ///     .collect();
+    ///
+    /// assert_eq!(decisions, vec![
+    ///     ("app", MergeDecision::Added),
+    ///     ("app.port", MergeDecision::KeptExisting),
+    ///     ("app.hosts", MergeDecision::Added),
+    ///     ("app.hosts", MergeDecision::Concatenated),
+    ///     ("app.name", MergeDecision::Replaced),
+    /// ]);
+    ///
+    /// let figment = figment.trace_merges(false);
+    /// assert!(figment.merge_trace().is_empty());
+    /// ```
+    pub fn trace_merges(mut self, enabled: bool) -> Self {
+        match enabled {
+            true => self.trace = self.trace.or_else(|| Some(vec![])),
+            false => self.trace = None,
+        }
+
+        self
+    }
+
+    /// Enables or disables recording of the values discarded when providers
+    /// are subsequently combined into `self`, as reported by
+    /// [`Figment::shadowed()`]. Disabling recording discards any recorded
+    /// values. Recording is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Figment;
+    ///
+    /// let figment = Figment::new()
+    ///     .merge(("port", 80))
+    ///     .merge(("port", 8000))
+    ///     .record_shadowed(true)
+    ///     .merge(("port", 8080));
+    ///
+    /// let shadowed = figment.shadowed("port");
+    /// assert_eq!(shadowed.len(), 1);
+    /// assert_eq!(shadowed[0].0.to_i128(), Some(8000));
+    ///
+    /// let figment = figment.record_shadowed(false);
+    /// assert!(figment.shadowed("port").is_empty());
+    /// ```
+    pub fn record_shadowed(mut self, enabled: bool) -> Self {
+        match enabled {
+            true => self.shadowed = self.shadowed.or_else(|| Some(Map::new())),
+            false => self.shadowed = None,
+        }
+
+        self
+    }
+
+    /// Returns the merge decisions recorded while tracing was enabled via
+    /// [`Figment::trace_merges()`], in the order they were made. Returns an
+    /// empty slice if tracing is disabled.
+    ///
+    /// See [`Figment::trace_merges()`] for an example.
+    pub fn merge_trace(&self) -> &[MergeEvent] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+
+    /// Sets the profile to extract from to `profile`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use figment::Figment;
+    ///
+    /// let figment = Figment::new().select("staging");
+    /// assert_eq!(figment.profile(), "staging");
+    /// ```
+    pub fn select<P: Into<Profile>>(mut self, profile: P) -> Self {
+        self.profile = profile.into();
+        self.lineage.clear();
+        self.extensions = Extensions::default();
+        self
+    }
+
+    /// Sets the profile to extract from to the profile returned by `f`, which
+    /// is called with every profile present in the combined data, in sorted
+    /// order, including [`Profile::Default`] and [`Profile::Global`] if they
+    /// are present. This allows the profile to depend on which profiles the
+    /// providers supplied, for instance to prefer a `local` profile if one
+    /// was provided, without first building a figment to inspect.
+    ///
+    /// Only providers combined before the call are considered. If combining a
+    /// provider failed, `f` is called with no profiles.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Profile, providers::Serialized};
+    ///
+    /// let prefer_local = |profiles: &[Profile]| match profiles.iter().any(|p| p == "local") {
+    ///     true => "local",
+    ///     false => "dev",
+    /// };
+    ///
+    /// let figment = Figment::new()
+    ///     .merge(Serialized::default("port", 80).profile("dev"))
+    ///     .select_with(prefer_local);
+    ///
+    /// assert_eq!(figment.profile(), "dev");
+    /// assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 80);
+    ///
+    /// let figment = figment
+    ///     .merge(Serialized::default("port", 8080).profile("local"))
+    ///     .select_with(prefer_local);
+    ///
+    /// assert_eq!(figment.profile(), "local");
+    /// assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 8080);
+    /// ```
+    pub fn select_with<F, P>(self, f: F) -> Self
+        where F: FnOnce(&[Profile]) -> P, P: Into<Profile>
+    {
+        let profiles: Vec<Profile> = self.profiles().cloned().collect();
+        let profile = f(&profiles);
+        self.select(profile)
+    }
+
+    /// Sets the profile to extract from to the level at index `level` in
+    /// `levels`, where `0` is the lowest level, such that the selected profile
+    /// inherits the data of every lower level.
+    ///
+    /// On extraction, the data of each level up to and including `level` is
+    /// merged, in order, after the default profile's data and before the
+    /// global profile's data. To select a level by name, find its index with
+    /// [`Leveled::position()`]. See [`Profile::leveled()`] for details on
+    /// levels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` is not less than the number of levels in `levels`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Profile, providers::{Format, Toml}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Security.toml", r#"
+    ///         [default]
+    ///         tls = false
+    ///         audit = false
+    ///         max_attempts = 10
+    ///
+    ///         [base]
+    ///         tls = true
+    ///
+    ///         [hardened]
+    ///         audit = true
+    ///         max_attempts = 5
+    ///
+    ///         [paranoid]
+    ///         max_attempts = 1
+    ///     "#)?;
+    ///
+    ///     let levels = Profile::leveled(&["base", "hardened", "paranoid"]);
+    ///     let figment = Figment::from(Toml::file("Security.toml").nested());
+    ///
+    ///     let base = figment.clone().select_level(&levels, 0);
+    ///     assert_eq!(base.profile(), "base");
+    ///     assert_eq!(base.extract_inner::<bool>("tls")?, true);
+    ///     assert_eq!(base.extract_inner::<bool>("audit")?, false);
+    ///
+    ///     let hardened = figment.clone().select_level(&levels, 1);
+    ///     assert_eq!(hardened.extract_inner::<bool>("tls")?, true);
+    ///     assert_eq!(hardened.extract_inner::<u8>("max_attempts")?, 5);
+    ///
+    ///     let level = levels.position(&"paranoid".into()).unwrap();
+    ///     let paranoid = figment.select_level(&levels, level);
+    ///     assert_eq!(paranoid.profile(), "paranoid");
+    ///     assert_eq!(paranoid.extract_inner::<bool>("tls")?, true);
+    ///     assert_eq!(paranoid.extract_inner::<bool>("audit")?, true);
+    ///     assert_eq!(paranoid.extract_inner::<u8>("max_attempts")?, 1);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn select_level(self, levels: &Leveled, level: usize) -> Self {
+        let mut figment = self.select(levels[level].clone());
+        figment.lineage = levels.levels()[..level].to_vec();
+        figment
+    }
+
+    /// Makes the selected profile additionally inherit from `parent`, with
+    /// lower precedence than the profiles it already inherits from.
+    ///
+    /// Calling this method repeatedly builds an inheritance chain: in
+    /// `select("production").with_parent("staging").with_parent("base")`,
+    /// `production` inherits from `staging` which inherits from `base`. On
+    /// extraction, the data of every profile in the chain is merged, from the
+    /// furthest ancestor to the selected profile, after the default profile's
+    /// data and before the global profile's data. Each provider's data is read
+    /// once, so values keep the metadata of the provider that supplied them.
+    ///
+    /// The chain is cleared when a profile is selected, so this method should
+    /// be called after [`Figment::select()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, Toml}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         [default]
+    ///         workers = 1
+    ///
+    ///         [base]
+    ///         host = "example.com"
+    ///         log = "debug"
+    ///
+    ///         [staging]
+    ///         log = "info"
+    ///         workers = 4
+    ///
+    ///         [production]
+    ///         workers = 16
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("App.toml").nested())
+    ///         .select("production")
+    ///         .with_parent("staging")
+    ///         .with_parent("base");
+    ///
+    ///     assert_eq!(figment.extract_inner::<String>("host")?, "example.com");
+    ///     assert_eq!(figment.extract_inner::<String>("log")?, "info");
+    ///     assert_eq!(figment.extract_inner::<u8>("workers")?, 16);
+    ///
+    ///     let metadata = figment.find_metadata("host").unwrap();
+    ///     assert_eq!(metadata.name, "TOML file");
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn with_parent<P: Into<Profile>>(mut self, parent: P) -> Self {
+        self.lineage.insert(0, parent.into());
+        self.extensions = Extensions::default();
+        self
+    }
+
+    /// Returns the custom profiles values are drawn from when extracting, from
+    /// lowest to highest precedence: the profiles the selected profile inherits
+    /// from followed by the selected profile. A profile that appears more than
+    /// once takes its highest precedence. The default profile precedes and the
+    /// global profile follows every returned profile.
+    fn cascade(&self) -> Vec<&Profile> {
+        let mut cascade: Vec<&Profile> = vec![];
+        for profile in self.lineage.iter().chain(Some(&self.profile)).filter(|p| p.is_custom()) {
+            cascade.retain(|p| *p != profile);
+            cascade.push(profile);
+        }
+
+        cascade
+    }
+
+    /// Merges the selected profile and the profiles it inherits from with the
+    /// default and global profiles. The merged value is cached until the
+    /// figment changes.
+    pub(crate) fn merged(&self) -> Result<Value> {
+        let key = (String::new(), TypeId::of::<Merged>());
+        let cached = self.extensions.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+            .cloned();
+
+        if let Some(Merged(value)) = cached.as_ref().and_then(|v| v.downcast_ref()) {
+            return Ok(value.clone());
+        }
+
+        let value = self.merge_profiles()?;
+        self.extensions.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, Arc::new(Merged(value.clone())));
+
+        Ok(value)
+    }
+
+    /// Computes the value [`merged()`](Self::merged()) returns.
+    fn merge_profiles(&self) -> Result<Value> {
+        let mut map = self.value.clone().map_err(|e| e.resolved(self))?;
+        let mut merged = map.remove(&Profile::Default).unwrap_or_default();
+        let global = map.remove(&Profile::Global).unwrap_or_default();
+
+        for profile in self.cascade() {
+            if let Some(v) = map.remove(profile) {
+                merged = merged.merge(v);
+            }
+        }
+
+        if let Some((name, value)) = self.profile.param_parts() {
+            let overlay = |dict: &Dict| dict.get(name)?.as_dict()?.get(value)?.as_dict().cloned();
+            let overlay = overlay(&merged).unwrap_or_default()
+                .merge(overlay(&global).unwrap_or_default());
+
+            merged = merged.merge(overlay);
+        }
+
+        let value = Value::Dict(Tag::Default, merged.merge(global));
+        match self.interpolation {
+            true => crate::interpolate::resolve(&value).map_err(|e| e.resolved(self)),
+            false => Ok(value),
+        }
+    }
+
+    /// Returns the value at `path` in the value [`merged()`](Self::merged())
+    /// would return without building the merged value.
+    ///
+    /// Profiles are consulted from highest to lowest precedence. A value that
+    /// isn't a dictionary is borrowed from the first profile that contains it,
+    /// as is a dictionary contained in only one profile. Only dictionaries at
+    /// `path` that must be unioned are cloned.
+    fn lookup(&self, path: &str) -> Result<Cow<'_, Value>> {
+        self.lookup_with_profile(path).map(|(value, _)| value)
+    }
+
+    /// Like [`lookup()`](Self::lookup()) but additionally returns the profile
+    /// that supplied the value: the highest precedence profile containing the
+    /// value or, for a dictionary unioned from several profiles, containing
+    /// part of it.
+    fn lookup_with_profile(&self, path: &str) -> Result<(Cow<'_, Value>, Profile)> {
+        enum Found<'v> {
+            Value(&'v Value),
+            Missing,
+            Shadowed,
+        }
+
+        fn find<'v>(dict: &'v Dict, path: &str) -> Found<'v> {
+            let mut keys = path.split('.').take_while(|k| !k.is_empty());
+            let mut value = match keys.next().and_then(|k| dict.get(k)) {
+                Some(value) => value,
+                None => return Found::Missing,
+            };
+
+            for key in keys {
+                value = match value {
+                    Value::Dict(_, dict) => match dict.get(key) {
+                        Some(value) => value,
+                        None => return Found::Missing,
+                    },
+                    _ => return Found::Shadowed,
+                };
+            }
+
+            Found::Value(value)
+        }
+
+        let missing = || Error::from(Kind::MissingField(path.to_string().into()));
+        let map = match &self.value {
+            Ok(map) if !path.is_empty() && !path.starts_with('.')
+                && self.profile.param_parts().is_none() && !self.interpolation => map,
+            _ => {
+                let value = self.merged()?.find(path).ok_or_else(missing)?;
+                let profile = match value.tag().profile() {
+                    Some(profile) => profile,
+                    None => self.profile.clone(),
+                };
+
+                return Ok((Cow::Owned(value), profile));
+            }
+        };
+
+        let (global, default) = (Profile::Global, Profile::Default);
+        let layers = Some(&global).into_iter()
+            .chain(self.cascade().into_iter().rev())
+            .chain(Some(&default))
+            .filter_map(|p| map.get_key_value(p));
+
+        let mut dicts = Vec::new();
+        for (profile, layer) in layers {
+            match find(layer, path) {
+                Found::Missing => continue,
+                Found::Shadowed => break,
+                Found::Value(v @ Value::Dict(..)) => dicts.push((profile, v)),
+                Found::Value(v) if dicts.is_empty() => return Ok((Cow::Borrowed(v), profile.clone())),
+                Found::Value(_) => break,
+            }
+        }
+
+        let profile = match dicts.first() {
+            Some((profile, _)) => (*profile).clone(),
+            None => return Err(missing()),
+        };
+
+        match dicts.len() {
+            1 => Ok((Cow::Borrowed(dicts[0].1), profile)),
+            _ => {
+                let value = dicts.into_iter().rev().map(|(_, v)| v.clone())
+                    .reduce(|lower, higher| lower.merge(higher))
+                    .expect("non-empty");
+
+                Ok((Cow::Owned(value), profile))
+            }
+        }
+    }
+
+    /// Returns a new `Figment` containing only the sub-dictionaries at `key`.
+    ///
+    /// This "sub-figment" is a _focusing_ of `self` with the property that:
+    ///
+    ///   * `self.find(key + ".sub")` <=> `focused.find("sub")`
+    ///
+    /// In other words, all values in `self` with a key starting with `key` are
+    /// in `focused` _without_ the prefix and vice-versa.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, Toml}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         cat = [1, 2, 3]
+    ///         dog = [4, 5, 6]
+    ///
+    ///         [subtree]
+    ///         cat = "meow"
+    ///         dog = "woof!"
+    ///
+    ///         [subtree.bark]
+    ///         dog = true
+    ///         cat = false
+    ///     "#)?;
+    ///
+    ///     let root = Figment::from(Toml::file("Config.toml"));
+    ///     assert_eq!(root.extract_inner::<Vec<u8>>("cat").unwrap(), vec![1, 2, 3]);
+    ///     assert_eq!(root.extract_inner::<Vec<u8>>("dog").unwrap(), vec![4, 5, 6]);
+    ///     assert_eq!(root.extract_inner::<String>("subtree.cat").unwrap(), "meow");
+    ///     assert_eq!(root.extract_inner::<String>("subtree.dog").unwrap(), "woof!");
+    ///
+    ///     let subtree = root.focus("subtree");
+    ///     assert_eq!(subtree.extract_inner::<String>("cat").unwrap(), "meow");
+    ///     assert_eq!(subtree.extract_inner::<String>("dog").unwrap(), "woof!");
+    ///     assert_eq!(subtree.extract_inner::<bool>("bark.cat").unwrap(), false);
+    ///     assert_eq!(subtree.extract_inner::<bool>("bark.dog").unwrap(), true);
+    ///
+    ///     let bark = subtree.focus("bark");
+    ///     assert_eq!(bark.extract_inner::<bool>("cat").unwrap(), false);
+    ///     assert_eq!(bark.extract_inner::<bool>("dog").unwrap(), true);
+    ///
+    ///     let not_a_dict = root.focus("cat");
+    ///     assert!(not_a_dict.extract_inner::<bool>("cat").is_err());
+    ///     assert!(not_a_dict.extract_inner::<bool>("dog").is_err());
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn focus(&self, key: &str) -> Self {
+        fn try_focus(figment: &Figment, key: &str) -> Result<Map<Profile, Dict>> {
+            let map = figment.value.clone().map_err(|e| e.resolved(figment))?;
+            let new_map = map.into_iter()
+                .filter_map(|(k, v)| {
+                    let focused = Value::Dict(Tag::Default, v).find(key)?;
+                    let dict = focused.into_dict()?;
+                    Some((k, dict))
+                })
+                .collect();
+
+            Ok(new_map)
+        }
+
+        fn focus_unknown(
+            policies: &[(String, UnknownPolicy)],
+            key: &str
+        ) -> Vec<(String, UnknownPolicy)> {
+            let keys: Vec<String> = key.split('.')
+                .filter(|k| !k.is_empty())
+                .map(|k| k.to_string())
+                .collect();
+
+            if policies.is_empty() || keys.is_empty() {
+                return policies.to_vec();
+            }
+
+            let prefix = format!("{}.", keys.join("."));
+            let nested = policies.iter().filter_map(|(path, policy)| {
+                Some((path.strip_prefix(&prefix)?.to_string(), *policy))
+            });
+
+            let root = (String::new(), UnknownPolicy::at(policies, &keys));
+            Some(root).into_iter().chain(nested).collect()
+        }
+
+        fn focus_shadowed(
+            shadowed: &Map<Profile, Map<String, Vec<Value>>>,
+            key: &str
+        ) -> Map<Profile, Map<String, Vec<Value>>> {
+            let prefix = format!("{}.", key);
+            shadowed.iter()
+                .map(|(profile, paths)| {
+                    let paths = paths.iter()
+                        .filter_map(|(path, values)| {
+                            Some((path.strip_prefix(&prefix)?.to_string(), values.clone()))
+                        })
+                        .collect();
+
+                    (profile.clone(), paths)
+                })
+                .collect()
+        }
+
+        fn focus_spellings(spellings: &Spellings, key: &str) -> Spellings {
+            let prefix = format!("{}.", key);
+            spellings.iter()
+                .map(|(profile, keys)| {
+                    let keys = keys.iter()
+                        .filter_map(|(path, spellings)| {
+                            let spellings = spellings.iter()
+                                .filter_map(|(s, tag)| Some((s.strip_prefix(&prefix)?.into(), *tag)))
+                                .collect();
+
+                            Some((path.strip_prefix(&prefix)?.to_string(), spellings))
+                        })
+                        .collect();
+
+                    (profile.clone(), keys)
+                })
+                .collect()
+        }
+
+        fn focus_paths<T: Clone>(paths: &[(String, T)], key: &str) -> Vec<(String, T)> {
+            let prefix = format!("{}.", key);
+            paths.iter()
+                .filter_map(|(path, v)| Some((path.strip_prefix(&prefix)?.to_string(), v.clone())))
+                .collect()
+        }
+
+        fn focus_layers(layers: &[Layer], key: &str) -> Vec<Layer> {
+            layers.iter()
+                .map(|layer| {
+                    let data = layer.data.as_ref().map(|data| {
+                        let data = data.as_ref().map(|map| map.iter()
+                            .filter_map(|(profile, dict)| {
+                                let value = Value::Dict(Tag::Default, dict.clone());
+                                Some((profile.clone(), value.find(key)?.into_dict()?))
+                            })
+                            .collect());
+
+                        data.map_err(|e| e.clone())
+                    });
+
+                    Layer { data, ..layer.clone() }
+                })
+                .collect()
+        }
+
+        Figment {
+            profile: self.profile.clone(),
+            lineage: self.lineage.clone(),
+            metadata: self.metadata.clone(),
+            value: try_focus(self, key),
+            trace: self.trace.clone(),
+            shadowed: self.shadowed.as_ref().map(|s| focus_shadowed(s, key)),
+            #[cfg(feature = "schema")]
+            schema: None,
+            warnings: self.warnings.clone(),
+            unknown: focus_unknown(&self.unknown, key),
+            numeric: self.numeric,
+            num_policy: self.num_policy,
+            limits: self.limits,
+            layers: focus_layers(&self.layers, key),
+            retain_layers: self.retain_layers,
+            normalizer: self.normalizer.clone(),
+            spellings: focus_spellings(&self.spellings, key),
+            key_context: self.key_context.clone(),
+            array_keys: focus_paths(&self.array_keys, key),
+            arrays: self.arrays,
+            array_merges: focus_paths(&self.array_merges, key),
+            type_conflicts: self.type_conflicts,
+            typos: self.typos,
+            interpolation: self.interpolation,
+            renames: self.renames.clone(),
+            deprecated: focus_paths(&self.deprecated, key),
+            namespaces: self.namespaces.clone(),
+            samples: self.samples,
+            extensions: Extensions::default(),
+            humanizers: self.humanizers.clone(),
+        }
+    }
+
+    /// Deserializes the collected value into `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    ///
+    /// use figment::{Figment, providers::{Format, Toml, Json, Env}};
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     numbers: Option<Vec<usize>>,
+    ///     debug: bool,
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         name = "test"
+    ///         numbers = [1, 2, 3, 10]
+    ///     "#)?;
+    ///
+    ///     jail.set_env("config_name", "env-test");
+    ///
+    ///     jail.create_file("Config.json", r#"
+    ///         {
+    ///             "name": "json-test",
+    ///             "debug": true
+    ///         }
+    ///     "#)?;
+    ///
+    ///     let config: Config = Figment::new()
+    ///         .merge(Toml::file("Config.toml"))
+    ///         .merge(Env::prefixed("CONFIG_"))
+    ///         .join(Json::file("Config.json"))
+    ///         .extract()?;
+    ///
+    ///     assert_eq!(config, Config {
+    ///         name: "env-test".into(),
+    ///         numbers: vec![1, 2, 3, 10].into(),
+    ///         debug: true
+    ///     });
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn extract<'a, T: Deserialize<'a>>(&self) -> Result<T> {
+        let value = self.merged()?;
+        let result = T::deserialize(ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value));
+        self.check_unknown("", &value, result)
+    }
+
+    /// As [`extract`](Figment::extract_lossy), but interpret numbers and
+    /// booleans more flexibly.
+    ///
+    /// See [`Value::to_bool_lossy`] and [`Value::to_num_lossy`] for a full
+    /// explanation of the imputs accepted.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    ///
+    /// use figment::{Figment, providers::{Format, Toml, Json, Env}};
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     numbers: Option<Vec<usize>>,
+    ///     debug: bool,
     /// }
     ///
     /// figment::Jail::expect_with(|jail| {
@@ -512,98 +2994,1042 @@ impl Figment {
     ///         numbers = ["1", "2", "3", "10"]
     ///     "#)?;
     ///
-    ///     jail.set_env("config_name", "env-test");
+    ///     jail.set_env("config_name", "env-test");
+    ///
+    ///     jail.create_file("Config.json", r#"
+    ///         {
+    ///             "name": "json-test",
+    ///             "debug": "yes"
+    ///         }
+    ///     "#)?;
+    ///
+    ///     let config: Config = Figment::new()
+    ///         .merge(Toml::file("Config.toml"))
+    ///         .merge(Env::prefixed("CONFIG_"))
+    ///         .join(Json::file("Config.json"))
+    ///         .extract_lossy()?;
+    ///
+    ///     assert_eq!(config, Config {
+    ///         name: "env-test".into(),
+    ///         numbers: vec![1, 2, 3, 10].into(),
+    ///         debug: true
+    ///     });
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn extract_lossy<'a, T: Deserialize<'a>>(&self) -> Result<T> {
+        let value = self.merged()?;
+        let result = T::deserialize(ConfiguredValueDe::<'_, LossyInterpreter>::from(self, &value));
+        self.check_unknown("", &value, result)
+    }
+
+    /// As [`extract`](Figment::extract()), but reports every missing and
+    /// invalid value in one error rather than failing at the first.
+    ///
+    /// When deserialization fails at a value, the error is recorded and
+    /// deserialization is repeated with a placeholder, such as `0`, `""`, or
+    /// `None`, in place of the value, until it succeeds or fails at a value
+    /// that can't be replaced. A missing field is replaced with a placeholder
+    /// and an unknown field, rejected via `#[serde(deny_unknown_fields)]`, is
+    /// removed. On failure, every recorded error is returned, the first
+    /// [chained](Error::chain()) first, so that the errors can be iterated
+    /// over or displayed at once. A struct's visitor stops at its first
+    /// missing field, so each repetition uncovers at most one missing field
+    /// per struct.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, providers::{Format, Toml}};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     port: u16,
+    ///     workers: Vec<u8>,
+    ///     tls: Tls,
+    /// }
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Tls {
+    ///     cert: String,
+    ///     key: String,
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         port = "eighty"
+    ///         workers = [1, 2, 1000]
+    ///
+    ///         [tls]
+    ///         cert = 123
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("Config.toml"));
+    ///     assert_eq!(figment.extract::<Config>().unwrap_err().count(), 1);
+    ///
+    ///     let error = figment.extract_all_errors::<Config>().unwrap_err();
+    ///     let paths: Vec<_> = error.into_iter().map(|e| e.path.join(".")).collect();
+    ///     assert_eq!(paths, ["port", "tls.cert", "tls", "workers.2", ""]);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn extract_all_errors<'a, T: Deserialize<'a>>(&self) -> Result<T> {
+        let merged = self.merged()?;
+        let (mut value, mut skipped, mut errors) = (merged.clone(), vec![], vec![]);
+        loop {
+            let placeheld: Vec<&Value> = skipped.iter()
+                .filter_map(|path: &Vec<String>| value_at(&value, path))
+                .collect();
+
+            let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value);
+            let error = match T::deserialize(de.skipping(&placeheld)) {
+                Ok(v) if errors.is_empty() => return self.check_unknown("", &merged, Ok(v)),
+                Ok(_) => return crate::schema::join(errors).map(|_| unreachable!()),
+                Err(e) => e,
+            };
+
+            // An error at or below a placeholder is caused by the placeholder.
+            let path = error.path.clone();
+            if error.cancelled() || skipped.iter().any(|p| path.starts_with(p)) {
+                match errors.is_empty() {
+                    true => return Err(error),
+                    false => return crate::schema::join(errors).map(|_| unreachable!()),
+                }
+            }
+
+            let recovered = match &error.kind {
+                Kind::MissingField(field) => match value_at_mut(&mut value, &path) {
+                    Some(Value::Dict(_, dict)) if !dict.contains_key(&**field) => {
+                        dict.insert(field.to_string(), Value::Empty(Tag::Default, Empty::Unit));
+                        skipped.push(path.iter().cloned().chain(Some(field.to_string())).collect());
+                        true
+                    }
+                    _ => false,
+                },
+                Kind::UnknownField(..) => match path.split_last() {
+                    Some((field, parent)) => match value_at_mut(&mut value, parent) {
+                        Some(Value::Dict(_, dict)) => dict.remove(field).is_some(),
+                        _ => false,
+                    },
+                    None => false,
+                },
+                _ => match path.is_empty() {
+                    true => false,
+                    false => { skipped.push(path); true }
+                },
+            };
+
+            errors.push(error);
+            if !recovered {
+                return crate::schema::join(errors).map(|_| unreachable!());
+            }
+        }
+    }
+
+    /// As [`extract`](Figment::extract()), but treats a missing section as an
+    /// empty dictionary.
+    ///
+    /// When a struct's field is missing, deserialization is repeated with an
+    /// empty dictionary in place of the field. A struct whose fields all have
+    /// defaults, via `#[serde(default)]`, thus extracts successfully even when
+    /// its section is absent, without the section's field being wrapped in an
+    /// `Option` or marked `#[serde(default)]` itself. A missing field that
+    /// can't be deserialized from an empty dictionary, like a string or a
+    /// struct with a required field, is reported as missing as usual.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, providers::{Format, Toml}};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     db: Database,
+    /// }
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Database {
+    ///     #[serde(default)]
+    ///     pool: Pool,
+    ///     #[serde(default = "default_url")]
+    ///     url: String,
+    /// }
+    ///
+    /// #[derive(Debug, Default, Deserialize)]
+    /// struct Pool {
+    ///     size: u16,
+    /// }
+    ///
+    /// fn default_url() -> String {
+    ///     "sqlite://app.db".into()
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", "name = \"app\"")?;
+    ///
+    ///     let figment = Figment::from(Toml::file("App.toml"));
+    ///     assert!(figment.extract::<Config>().is_err());
+    ///
+    ///     let config: Config = figment.extract_relaxed()?;
+    ///     assert_eq!(config.db.url, "sqlite://app.db");
+    ///     assert_eq!(config.db.pool.size, 0);
+    ///
+    ///     // Missing values that aren't sections are still missing.
+    ///     jail.create_file("App.toml", "")?;
+    ///     let error = Figment::from(Toml::file("App.toml")).extract_relaxed::<Config>();
+    ///     assert!(error.unwrap_err().missing());
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn extract_relaxed<'a, T: Deserialize<'a>>(&self) -> Result<T> {
+        let merged = self.merged()?;
+        let (mut value, mut sections) = (merged.clone(), vec![]);
+        loop {
+            let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value);
+            let error = match T::deserialize(de) {
+                Ok(v) => return self.check_unknown("", &merged, Ok(v)),
+                Err(e) => e,
+            };
+
+            // An error at or below an inserted section means the field
+            // couldn't be a section after all: report the field as missing.
+            let inserted = sections.iter()
+                .find(|(path, _): &&(Vec<String>, Error)| error.path.starts_with(path));
+
+            if let Some((_, missing)) = inserted {
+                return Err(missing.clone());
+            }
+
+            if let Kind::MissingField(field) = &error.kind {
+                if let Some(Value::Dict(_, dict)) = value_at_mut(&mut value, &error.path) {
+                    if !dict.contains_key(&**field) {
+                        dict.insert(field.to_string(), Dict::new().into());
+                        let path = error.path.iter().cloned().chain(Some(field.to_string()));
+                        sections.push((path.collect(), error));
+                        continue;
+                    }
+                }
+            }
+
+            return Err(error);
+        }
+    }
+
+    /// As [`extract`](Figment::extract()), but fails if any key in the
+    /// combined value isn't consumed by `T`, reporting each such key with the
+    /// metadata of the provider that supplied it.
+    ///
+    /// Which keys `T` consumes is determined by serializing the extracted
+    /// value back: a key present in the combined value but absent from the
+    /// serialized value is unknown. Unlike `#[serde(deny_unknown_fields)]` and
+    /// [`Figment::unknown_keys()`], this sees through `#[serde(flatten)]`: keys
+    /// consumed by a flattened struct are known while keys no field consumes
+    /// are not, and keys collected by a flattened map are always known. For
+    /// this to be accurate, `T` must serialize every field it deserializes
+    /// under the same name. In particular, keys provided via a
+    /// `#[serde(alias)]` and fields that are `#[serde(skip_serializing)]` are
+    /// reported as unknown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::{Deserialize, Serialize};
+    /// use figment::{Figment, providers::{Format, Toml}};
+    ///
+    /// #[derive(Debug, Deserialize, Serialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     #[serde(flatten)]
+    ///     server: Server,
+    /// }
+    ///
+    /// #[derive(Debug, Deserialize, Serialize)]
+    /// struct Server {
+    ///     port: u16,
+    ///     #[serde(default)]
+    ///     timeout: u32,
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         name = "app"
+    ///         port = 8080
+    ///         time_out = 30
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("App.toml"));
+    ///     assert!(figment.extract::<Config>().is_ok());
+    ///
+    ///     let error = figment.extract_strict::<Config>().unwrap_err();
+    ///     assert_eq!(error.path, vec!["time_out"]);
+    ///     assert_eq!(error.metadata.as_ref().unwrap().name, "TOML file");
+    ///     assert!(error.to_string().contains("unknown key `time_out`"));
+    ///
+    ///     jail.create_file("App.toml", "name = \"app\"\nport = 8080\ntimeout = 30")?;
+    ///     let config = Figment::from(Toml::file("App.toml")).extract_strict::<Config>()?;
+    ///     assert_eq!(config.server.timeout, 30);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn extract_strict<'a, T>(&self) -> Result<T>
+        where T: Deserialize<'a> + Serialize
+    {
+        let value = self.merged()?;
+        let result = T::deserialize(ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value));
+        let extracted = self.check_unknown("", &value, result)?;
+
+        let mut errors = vec![];
+        let consumed = Value::serialize(&extracted)?;
+        crate::schema::unconsumed(self, &value, &consumed, &mut vec![], &mut errors);
+        crate::schema::join(errors).map(|_| extracted)
+    }
+
+    /// As [`extract`](Figment::extract), but deserializes the collected value
+    /// with the stateful [`DeserializeSeed`] `seed`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use std::fmt;
+    ///
+    /// use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, Visitor};
+    /// use figment::Figment;
+    ///
+    /// /// Collects every key in a dictionary into a set owned by the caller.
+    /// struct KeyCollector<'a>(&'a mut HashSet<String>);
+    ///
+    /// impl<'de, 'a> DeserializeSeed<'de> for KeyCollector<'a> {
+    ///     type Value = usize;
+    ///
+    ///     fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<usize, D::Error> {
+    ///         de.deserialize_map(self)
+    ///     }
+    /// }
+    ///
+    /// impl<'de, 'a> Visitor<'de> for KeyCollector<'a> {
+    ///     type Value = usize;
+    ///
+    ///     fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         f.write_str("a map")
+    ///     }
+    ///
+    ///     fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<usize, A::Error> {
+    ///         let mut count = 0;
+    ///         while let Some((key, _)) = map.next_entry::<String, de::IgnoredAny>()? {
+    ///             self.0.insert(key);
+    ///             count += 1;
+    ///         }
+    ///
+    ///         Ok(count)
+    ///     }
+    /// }
+    ///
+    /// let figment = Figment::from(("name", "Bob")).merge(("age", 30));
+    ///
+    /// let mut keys = HashSet::new();
+    /// let count = figment.extract_seed(KeyCollector(&mut keys)).unwrap();
+    /// assert_eq!(count, 2);
+    /// assert!(keys.contains("name"));
+    /// assert!(keys.contains("age"));
+    /// ```
+    pub fn extract_seed<'a, S: DeserializeSeed<'a>>(&self, seed: S) -> Result<S::Value> {
+        let value = self.merged()?;
+        seed.deserialize(ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value))
+    }
+
+    /// Deserializes the value at the `key` path in the collected value into
+    /// `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, Toml, Json}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         numbers = [1, 2, 3, 10]
+    ///     "#)?;
+    ///
+    ///     jail.create_file("Config.json", r#"{ "debug": true } "#)?;
     ///
-    ///     jail.create_file("Config.json", r#"
-    ///         {
-    ///             "name": "json-test",
-    ///             "debug": "yes"
-    ///         }
+    ///     let numbers: Vec<usize> = Figment::new()
+    ///         .merge(Toml::file("Config.toml"))
+    ///         .join(Json::file("Config.json"))
+    ///         .extract_inner("numbers")?;
+    ///
+    ///     assert_eq!(numbers, vec![1, 2, 3, 10]);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn extract_inner<'a, T: Deserialize<'a>>(&self, path: &str) -> Result<T> {
+        let value = self.lookup(path)?;
+        let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value);
+        let result = T::deserialize(de).map_err(|e| e.with_path(path));
+        self.check_unknown(path, &value, result)
+    }
+
+    /// As [`extract`](Figment::extract_lossy), but interpret numbers and
+    /// booleans more flexibly.
+    ///
+    /// See [`Value::to_bool_lossy`] and [`Value::to_num_lossy`] for a full
+    /// explanation of the imputs accepted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, Toml, Json}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         numbers = ["1", "2", "3", "10"]
     ///     "#)?;
     ///
-    ///     let config: Config = Figment::new()
+    ///     jail.create_file("Config.json", r#"{ "debug": true } "#)?;
+    ///
+    ///     let numbers: Vec<usize> = Figment::new()
     ///         .merge(Toml::file("Config.toml"))
-    ///         .merge(Env::prefixed("CONFIG_"))
     ///         .join(Json::file("Config.json"))
-    ///         .extract_lossy()?;
+    ///         .extract_inner_lossy("numbers")?;
     ///
-    ///     assert_eq!(config, Config {
-    ///         name: "env-test".into(),
-    ///         numbers: vec![1, 2, 3, 10].into(),
-    ///         debug: true
-    ///     });
+    ///     assert_eq!(numbers, vec![1, 2, 3, 10]);
     ///
     ///     Ok(())
     /// });
     /// ```
-    pub fn extract_lossy<'a, T: Deserialize<'a>>(&self) -> Result<T> {
+    pub fn extract_inner_lossy<'a, T: Deserialize<'a>>(&self, path: &str) -> Result<T> {
+        let value = self.lookup(path)?;
+        let de = ConfiguredValueDe::<'_, LossyInterpreter>::from(self, &value);
+        let result = T::deserialize(de).map_err(|e| e.with_path(path));
+        self.check_unknown(path, &value, result)
+    }
+
+    /// Extracts the configuration of a library embedding Figment from the
+    /// library's `namespace`, caching the result.
+    ///
+    /// A library that is configured through its host application's `Figment`,
+    /// as Rocket is, typically reads its configuration from a namespace of its
+    /// own, by convention its crate name, so that its keys don't clash with
+    /// the application's. Such a library:
+    ///
+    ///   * registers its defaults under its namespace with
+    ///     [`Figment::extension_defaults()`], so that the application need not
+    ///     provide every key and can override any of them;
+    ///   * extracts its configuration with `extension()` wherever it's needed.
+    ///
+    /// Equivalent to `self.extract_inner::<T>(namespace)` except that the
+    /// extracted value is cached, by `namespace` and `T`, and cloned on
+    /// subsequent calls until `self` is changed, by merging another provider
+    /// or selecting another profile, for instance. Clones of `self` share the
+    /// cache until either is changed. Errors are not cached.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::{Deserialize, Serialize};
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// // In the library, `acme`:
+    /// #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    /// pub struct AcmeConfig {
+    ///     pub retries: u8,
+    ///     pub endpoint: String,
+    /// }
+    ///
+    /// impl Default for AcmeConfig {
+    ///     fn default() -> Self {
+    ///         AcmeConfig { retries: 3, endpoint: "https://acme.example".into() }
+    ///     }
+    /// }
+    ///
+    /// pub fn init(figment: Figment) -> Figment {
+    ///     figment.extension_defaults("acme", AcmeConfig::default())
+    /// }
+    ///
+    /// pub fn client(figment: &Figment) -> figment::Result<AcmeConfig> {
+    ///     figment.extension("acme")
+    /// }
+    ///
+    /// // In the application:
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         name = "app"
+    ///         retries = 10
+    ///
+    ///         [acme]
+    ///         retries = 5
+    ///     "#)?;
+    ///
+    ///     let figment = init(Figment::from(Toml::file("App.toml")));
+    ///     let config = client(&figment)?;
+    ///     assert_eq!(config.retries, 5);
+    ///     assert_eq!(config.endpoint, "https://acme.example");
+    ///     assert_eq!(client(&figment)?, config);
+    ///
+    ///     let figment = figment.merge(("acme.retries", 7));
+    ///     assert_eq!(client(&figment)?.retries, 7);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn extension<T>(&self, namespace: &str) -> Result<T>
+        where T: DeserializeOwned + Clone + Send + Sync + 'static
+    {
+        let key = (namespace.to_string(), TypeId::of::<T>());
+        let cached = self.extensions.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+            .cloned();
+
+        if let Some(value) = cached.as_ref().and_then(|v| v.downcast_ref::<T>()) {
+            return Ok(value.clone());
+        }
+
+        let value: T = self.extract_inner(namespace)?;
+        self.extensions.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, Arc::new(value.clone()));
+
+        Ok(value)
+    }
+
+    /// Joins `defaults`, serialized, into the default profile under the key
+    /// path `namespace`, for extraction via [`Figment::extension()`].
+    ///
+    /// Because the defaults are joined, they never override values provided
+    /// for the namespace, whether provided before or after the defaults are
+    /// registered. The defaults' metadata is named `"{namespace} defaults"`.
+    /// See [`Figment::extension()`] for an example.
+    #[track_caller]
+    pub fn extension_defaults<T: Serialize>(self, namespace: &str, defaults: T) -> Self {
+        let name = format!("{} defaults", namespace);
+        self.join(crate::providers::Serialized::default(namespace, defaults).named(name))
+    }
+
+    /// Discards the merged value and [extensions](Figment::extension()) that
+    /// `self` caches across extractions, along with every clone of `self` that
+    /// shares the cache, so that they're recomputed by the next extraction.
+    ///
+    /// Every method that changes a figment's value, like [`Figment::merge()`]
+    /// or [`Figment::with_layers()`], returns a figment with its own, empty
+    /// cache, so this is only needed when the merged value depends on the
+    /// environment, as with [`Figment::resolve_interpolation()`]. Provider data
+    /// is read when a provider is combined and is unaffected: to read providers
+    /// again, as when reloading, build the figment again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("HOST", "localhost");
+    ///
+    ///     let figment = Figment::from(("url", "http://${HOST}"))
+    ///         .resolve_interpolation(true);
+    ///
+    ///     let clone = figment.clone();
+    ///     assert_eq!(clone.extract_inner::<String>("url")?, "http://localhost");
+    ///
+    ///     jail.set_env("HOST", "example.com");
+    ///     assert_eq!(clone.extract_inner::<String>("url")?, "http://localhost");
+    ///
+    ///     figment.invalidate();
+    ///     assert_eq!(clone.extract_inner::<String>("url")?, "http://example.com");
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn invalidate(&self) {
+        self.extensions.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Claims the key namespace `namespace` on behalf of version `version` of
+    /// a library, failing the figment if the claim conflicts with another.
+    ///
+    /// When several libraries are configured through an application's figment,
+    /// as described in [`Figment::extension()`], two of them reading from the
+    /// same namespace would silently share, and likely misinterpret, each
+    /// other's keys. By convention, a library claims its namespace, named
+    /// after its crate, with its crate version before registering defaults or
+    /// extracting:
+    ///
+    /// ```rust
+    /// # let figment = figment::Figment::new();
+    /// let figment = figment.register_namespace("acme", env!("CARGO_PKG_VERSION"));
+    /// ```
+    ///
+    /// A claim conflicts with an existing claim if:
+    ///
+    ///   * both claim the same namespace with semver-incompatible versions,
+    ///     like `1.4.0` and `2.0.1` or `0.3.0` and `0.4.0`, indicating two
+    ///     versions of a library, or two libraries, in the dependency tree;
+    ///   * one namespace contains the other, like `acme` and `acme.http`.
+    ///
+    /// Claiming a namespace again with a compatible version, as when a
+    /// library is initialized twice, is not a conflict. On conflict, or if
+    /// `version` isn't of the form `major.minor.patch`, the figment fails with
+    /// an error naming both claims and where they were made: extraction
+    /// returns the error. Claims are recorded in the figment the claim is
+    /// made on and are not carried by a figment combined into another as a
+    /// provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Figment;
+    ///
+    /// let figment = Figment::from(("acme.retries", 3))
+    ///     .register_namespace("acme", "1.2.0")
+    ///     .register_namespace("acme", "1.4.1")
+    ///     .register_namespace("widgets", "0.3.0");
+    ///
+    /// assert_eq!(figment.extract_inner::<u8>("acme.retries").unwrap(), 3);
+    /// let claims: Vec<_> = figment.namespaces().collect();
+    /// assert_eq!(claims, [("acme", "1.2.0"), ("widgets", "0.3.0")]);
+    ///
+    /// let error = figment.clone().register_namespace("acme", "2.0.0")
+    ///     .extract_inner::<u8>("acme.retries")
+    ///     .unwrap_err();
+    ///
+    /// assert!(error.to_string().contains("namespace `acme` version 2.0.0"));
+    ///
+    /// let figment = figment.register_namespace("widgets.extra", "1.0.0");
+    /// assert!(figment.extract_inner::<u8>("acme.retries").is_err());
+    /// ```
+    #[track_caller]
+    pub fn register_namespace(mut self, namespace: &str, version: &str) -> Self {
+        let location = Location::caller();
+        let conflict = match semver_compatibility(version) {
+            None => Some(format!("invalid version `{}` for namespace `{}` claimed at {}: \
+                expected `major.minor.patch`", version, namespace, location)),
+            Some(compatibility) => self.namespaces.iter().find_map(|(name, (v, loc))| {
+                let (outer, inner) = match name.len() <= namespace.len() {
+                    true => (name.as_str(), namespace),
+                    false => (namespace, name.as_str()),
+                };
+
+                let overlaps = inner.len() > outer.len() && inner.starts_with(outer)
+                    && inner[outer.len()..].starts_with('.');
+                let compatible = semver_compatibility(v) == Some(compatibility);
+                match (name == namespace && !compatible) || overlaps {
+                    true => Some(format!("namespace `{}` version {} claimed at {} conflicts \
+                        with namespace `{}` version {} claimed at {}",
+                        namespace, version, location, name, v, loc)),
+                    false => None,
+                }
+            }),
+        };
+
+        self.extensions = Extensions::default();
+        match conflict {
+            Some(msg) => {
+                let error = Error::from(Kind::Message(msg));
+                self.value = match std::mem::replace(&mut self.value, Ok(Map::new())) {
+                    Ok(_) => Err(error),
+                    Err(prev) => Err(prev.chain(error)),
+                };
+            }
+            None => {
+                self.namespaces.entry(namespace.into())
+                    .or_insert_with(|| (version.into(), location));
+            }
+        }
+
+        self
+    }
+
+    /// Returns the namespaces claimed via [`Figment::register_namespace()`]
+    /// and the version of the first claim of each, ordered by namespace.
+    pub fn namespaces(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.namespaces.iter().map(|(name, (version, _))| (name.as_str(), version.as_str()))
+    }
+
+    /// As [`extract_inner`](Figment::extract_inner), but deserializes the
+    /// value at the `key` path with the stateful [`DeserializeSeed`] `seed`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::marker::PhantomData;
+    ///
+    /// use serde::de::{Deserialize, DeserializeSeed, Deserializer};
+    /// use figment::Figment;
+    ///
+    /// /// Deserializes a `u16` and offsets it by a runtime value.
+    /// struct Offset(u16);
+    ///
+    /// impl<'de> DeserializeSeed<'de> for Offset {
+    ///     type Value = u16;
+    ///
+    ///     fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<u16, D::Error> {
+    ///         Ok(u16::deserialize(de)? + self.0)
+    ///     }
+    /// }
+    ///
+    /// let figment = Figment::from(("server.port", 8000));
+    /// let port = figment.extract_inner_seed("server.port", Offset(80)).unwrap();
+    /// assert_eq!(port, 8080);
+    ///
+    /// let err = figment.extract_inner_seed("server", Offset(80)).unwrap_err();
+    /// assert_eq!(err.path, vec!["server"]);
+    ///
+    /// // `PhantomData<T>` is the seed equivalent of `extract_inner::<T>()`.
+    /// let port = figment.extract_inner_seed("server.port", PhantomData::<u16>);
+    /// assert_eq!(port.unwrap(), 8000);
+    /// ```
+    pub fn extract_inner_seed<'a, S>(&self, path: &str, seed: S) -> Result<S::Value>
+        where S: DeserializeSeed<'a>
+    {
+        let value = self.lookup(path)?;
+        let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value);
+        seed.deserialize(de).map_err(|e| e.with_path(path))
+    }
+
+    /// Returns a read-only, cheaply cloneable [`FigmentView`] of `self` for
+    /// sharing across threads. Equivalent to `FigmentView::from(self.clone())`.
+    /// See [`FigmentView`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// let view = Figment::from(Serialized::default("port", 8080)).view();
+    /// assert_eq!(view.extract_inner::<u16>("port").unwrap(), 8080);
+    /// ```
+    pub fn view(&self) -> FigmentView {
+        FigmentView::from(self.clone())
+    }
+
+    /// Returns a [`Plan`] for repeatedly extracting a `T` from the selected
+    /// profile, as [`Figment::extract()`] would, resolving the value to
+    /// extract from once. See [`Plan`] for details.
+    ///
+    /// Returns an error if the figment failed to evaluate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let figment = Figment::new()
+    ///     .merge(Serialized::default("name", "app"))
+    ///     .merge(Serialized::default("port", 8080));
+    ///
+    /// let plan = figment.plan::<Config>().unwrap();
+    /// for _ in 0..3 {
+    ///     assert_eq!(plan.extract().unwrap(), Config { name: "app".into(), port: 8080 });
+    /// }
+    /// ```
+    pub fn plan<T: DeserializeOwned>(&self) -> Result<Plan<T>> {
+        Plan::new(self, "")
+    }
+
+    /// As [`Figment::plan()`], but for extracting the value at `path` as
+    /// [`Figment::extract_inner()`] would.
+    ///
+    /// Returns an error if the figment failed to evaluate or has no value at
+    /// `path`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// let figment = Figment::from(Serialized::default("server.port", 8080));
+    /// let plan = figment.plan_inner::<u16>("server.port").unwrap();
+    /// assert_eq!(plan.extract().unwrap(), 8080);
+    ///
+    /// assert!(figment.plan_inner::<u16>("server.host").is_err());
+    /// ```
+    pub fn plan_inner<T: DeserializeOwned>(&self, path: &str) -> Result<Plan<T>> {
+        Plan::new(self, path)
+    }
+
+    /// Returns the value [`Figment::extract()`], for an empty `path`, or
+    /// [`Figment::extract_inner()`] would deserialize from.
+    pub(crate) fn resolve(&self, path: &str) -> Result<Value> {
+        match path.is_empty() {
+            true => self.merged(),
+            false => self.lookup(path).map(Cow::into_owned),
+        }
+    }
+
+    /// As [`extract_inner`](Figment::extract_inner()), but deserializes the
+    /// value at the `key` path according to its shape using `polymorphic`,
+    /// allowing the same key to take different shapes in different profiles.
+    /// See [`Polymorphic`] for details and an example.
+    pub fn extract_inner_polymorphic<T>(&self, path: &str, polymorphic: &Polymorphic<T>) -> Result<T> {
+        let value = self.lookup(path)?;
+        polymorphic.extract(self, path, &value)
+    }
+
+    /// As [`extract`](Figment::extract()), but notifies `monitor` as keys are
+    /// deserialized, allowing the extraction to report progress and to be
+    /// cancelled. See [`Monitor`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, Monitor, CancellationToken};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     hosts: Vec<String>,
+    /// }
+    ///
+    /// let figment = Figment::from(("name", "app")).merge(("hosts", vec!["a", "b", "c"]));
+    ///
+    /// let monitor = Monitor::new();
+    /// let config: Config = figment.extract_monitored(&monitor).unwrap();
+    /// assert_eq!(config.hosts.len(), 3);
+    /// assert_eq!(monitor.keys(), 5);
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    ///
+    /// let monitor = Monitor::new().cancellation(token);
+    /// let error = figment.extract_monitored::<Config>(&monitor).unwrap_err();
+    /// assert!(error.cancelled());
+    /// ```
+    pub fn extract_monitored<'a, T>(&self, monitor: &Monitor<'_>) -> Result<T>
+        where T: Deserialize<'a>
+    {
+        monitor.check()?;
         let value = self.merged()?;
-        T::deserialize(ConfiguredValueDe::<'_, LossyInterpreter>::from(self, &value))
+        let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value);
+        let result = T::deserialize(de.monitored(Some(monitor)));
+        self.check_unknown("", &value, result)
     }
 
-    /// Deserializes the value at the `key` path in the collected value into
-    /// `T`.
+    /// As [`extract_inner`](Figment::extract_inner()), but notifies `monitor`
+    /// as keys are deserialized, allowing the extraction to report progress
+    /// and to be cancelled. See [`Monitor`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Monitor};
+    ///
+    /// let figment = Figment::from(("app.ports", vec![80, 443]));
+    ///
+    /// let monitor = Monitor::new().progress(|keys| println!("{} keys processed", keys));
+    /// let ports: Vec<u16> = figment.extract_inner_monitored("app.ports", &monitor).unwrap();
+    /// assert_eq!(ports, vec![80, 443]);
+    /// assert_eq!(monitor.keys(), 2);
+    /// ```
+    pub fn extract_inner_monitored<'a, T>(&self, path: &str, monitor: &Monitor<'_>) -> Result<T>
+        where T: Deserialize<'a>
+    {
+        monitor.check()?;
+        let value = self.lookup(path)?;
+        let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value);
+        let result = T::deserialize(de.monitored(Some(monitor))).map_err(|e| e.with_path(path));
+        self.check_unknown(path, &value, result)
+    }
+
+    /// Returns the combined value of `self` as environment variables that
+    /// [`Env::prefixed(prefix)`](crate::providers::Env::prefixed()) parses
+    /// back to the same value, sorted by name. This allows a process to pass
+    /// its resolved configuration on to a child process. To export only a
+    /// subtree, [`focus`](Figment::focus()) on it first.
+    ///
+    /// There is one variable per leaf value: its name is `prefix` followed by
+    /// the value's key path, uppercased, with `.` between keys. Values are
+    /// written in `Env`'s value syntax, with strings quoted only where needed.
+    ///
+    /// Exporting doesn't round-trip everything: `Env` lowercases names, so keys
+    /// containing uppercase characters are lost, as are empty dictionaries and
+    /// metadata. Also note that some shells don't accept `.` in variable names;
+    /// [`Command::env()`](std::process::Command::env()) does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, Jail, providers::{Env, Format, Toml}, value::Dict};
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     version: String,
+    ///     ports: Vec<u16>,
+    ///     database: Dict,
+    /// }
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         name = "app"
+    ///         version = "1.0"
+    ///         ports = [80, 443]
+    ///
+    ///         [database]
+    ///         url = "postgres://localhost"
+    ///         pool = { max = 10 }
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("Config.toml"));
+    ///     let vars = figment.export_env("APP_")?;
+    ///     assert_eq!(vars[0], ("APP_DATABASE.POOL.MAX".into(), "10".into()));
+    ///     assert_eq!(vars[3], ("APP_PORTS".into(), "[80, 443]".into()));
+    ///     assert_eq!(vars[4], ("APP_VERSION".into(), r#""1.0""#.into()));
+    ///
+    ///     jail.clear_env();
+    ///     for (name, value) in &vars {
+    ///         jail.set_env(name, value);
+    ///     }
+    ///
+    ///     let exported: Config = Figment::from(Env::prefixed("APP_")).extract()?;
+    ///     assert_eq!(exported.version, "1.0");
+    ///     assert_eq!(exported.ports, [80, 443]);
+    ///     assert_eq!(exported.database["url"], "postgres://localhost".into());
+    ///
+    ///     let vars = figment.focus("database").export_env("DB_")?;
+    ///     assert_eq!(vars[1], ("DB_URL".into(), "postgres://localhost".into()));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn export_env(&self, prefix: &str) -> Result<Vec<(String, String)>> {
+        let value = self.merged()?;
+        let dict = value.as_dict().expect("merged value is a dictionary");
+        Ok(crate::providers::env::export(prefix, dict))
+    }
+
+    /// Returns the effective configuration of the selected profile as a
+    /// single [`Value`], with values redacted or encrypted per `options`.
     ///
-    /// # Example
+    /// The returned value implements [`Serialize`] and can be dumped in any
+    /// format, for instance into a support bundle, without leaking the values
+    /// selected in `options`. See [`ExportOptions`] for details.
     ///
-    /// ```rust
-    /// use figment::{Figment, providers::{Format, Toml, Json}};
+    /// # Errors
     ///
-    /// figment::Jail::expect_with(|jail| {
-    ///     jail.create_file("Config.toml", r#"
-    ///         numbers = [1, 2, 3, 10]
-    ///     "#)?;
+    /// Returns the figment's error if combining a provider failed or an error
+    /// if `options` select values to encrypt but set no cipher.
     ///
-    ///     jail.create_file("Config.json", r#"{ "debug": true } "#)?;
+    /// # Example
     ///
-    ///     let numbers: Vec<usize> = Figment::new()
-    ///         .merge(Toml::file("Config.toml"))
-    ///         .join(Json::file("Config.json"))
-    ///         .extract_inner("numbers")?;
+    /// ```rust
+    /// use figment::{Figment, ExportOptions, util::map};
     ///
-    ///     assert_eq!(numbers, vec![1, 2, 3, 10]);
+    /// let figment = Figment::from(("db", map!["url" => "postgres://db", "password" => "hunter2"]))
+    ///     .merge(("workers", 4));
     ///
-    ///     Ok(())
-    /// });
+    /// let value = figment.export(&ExportOptions::new().redact(["db.password"])).unwrap();
+    /// let json = serde_json::to_string(&value).unwrap();
+    /// assert_eq!(json, r#"{"db":{"password":"<redacted>","url":"postgres://db"},"workers":4}"#);
     /// ```
-    pub fn extract_inner<'a, T: Deserialize<'a>>(&self, path: &str) -> Result<T> {
-        let value = self.find_value(path)?;
-        let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(self, &value);
-        T::deserialize(de).map_err(|e| e.with_path(path))
+    pub fn export(&self, options: &ExportOptions) -> Result<Value> {
+        let mut value = self.merged()?;
+        options.apply(&mut value)?;
+        Ok(value)
     }
 
-    /// As [`extract`](Figment::extract_lossy), but interpret numbers and
-    /// booleans more flexibly.
+    /// Renders the effective configuration of the selected profile as text in
+    /// the format `F`, for instance to implement a `config dump` command or to
+    /// write a default configuration file on first run. To render a different
+    /// profile, [`select()`](Figment::select()) it first. To redact values,
+    /// pass the value returned by [`Figment::export()`] to
+    /// [`Format::to_string()`](crate::providers::Format::to_string()) instead.
     ///
-    /// See [`Value::to_bool_lossy`] and [`Value::to_num_lossy`] for a full
-    /// explanation of the imputs accepted.
+    /// Parsing the text with `F` results in the same configuration, provided
+    /// the format can represent every value. For instance, TOML can't
+    /// represent empty values, and JSON can't represent datetimes. Magic
+    /// values, like a serialized [`RelativePathBuf`], are rendered in their
+    /// human-readable form; see [`MagicSerialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the figment's error if combining a provider failed or an error
+    /// if the configuration can't be represented in format `F`.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use figment::{Figment, providers::{Format, Toml, Json}};
-    ///
-    /// figment::Jail::expect_with(|jail| {
-    ///     jail.create_file("Config.toml", r#"
-    ///         numbers = ["1", "2", "3", "10"]
-    ///     "#)?;
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Json, Env}};
     ///
-    ///     jail.create_file("Config.json", r#"{ "debug": true } "#)?;
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         [default]
+    ///         name = "app"
+    ///         ports = [80, 443]
     ///
-    ///     let numbers: Vec<usize> = Figment::new()
-    ///         .merge(Toml::file("Config.toml"))
-    ///         .join(Json::file("Config.json"))
-    ///         .extract_inner_lossy("numbers")?;
+    ///         [debug.database]
+    ///         url = "postgres://localhost"
+    ///     "#)?;
     ///
-    ///     assert_eq!(numbers, vec![1, 2, 3, 10]);
+    ///     jail.set_env("APP_WORKERS", 4);
+    ///     let figment = Figment::from(Toml::file("App.toml").nested())
+    ///         .merge(Env::prefixed("APP_"))
+    ///         .select("debug");
+    ///
+    ///     let toml = figment.serialize_to::<Toml>()?;
+    ///     assert_eq!(toml, concat!(
+    ///         "name = \"app\"\n",
+    ///         "ports = [\n",
+    ///         "    80,\n",
+    ///         "    443,\n",
+    ///         "]\n",
+    ///         "workers = 4\n",
+    ///         "\n",
+    ///         "[database]\n",
+    ///         "url = \"postgres://localhost\"\n",
+    ///     ));
+    ///
+    ///     let json = figment.serialize_to::<Json>()?;
+    ///     let dumped = Figment::from(Json::string(&json));
+    ///     assert!(dumped.content_eq(&Figment::from(Toml::string(&toml))));
     ///
     ///     Ok(())
     /// });
     /// ```
-    pub fn extract_inner_lossy<'a, T: Deserialize<'a>>(&self, path: &str) -> Result<T> {
-        let value = self.find_value(path)?;
-        let de = ConfiguredValueDe::<'_, LossyInterpreter>::from(self, &value);
-        T::deserialize(de).map_err(|e| e.with_path(path))
+    pub fn serialize_to<F: crate::providers::Format>(&self) -> Result<String> {
+        let mut value = self.merged()?;
+        value.walk_mut(&mut |_: &[String], value: &mut Value| {
+            if let Value::Dict(_, dict) = value {
+                if let Some(humanized) = self.humanizers.iter().find_map(|h| h(dict)) {
+                    *value = humanized;
+                }
+            }
+
+            Visit::Continue
+        });
+
+        F::to_string(&value).map_err(|e| {
+            let msg = format!("failed to serialize as {}: {}", F::NAME, e);
+            Kind::Message(msg).into()
+        })
+    }
+
+    /// Registers the magic type `T` so that [`Figment::serialize_to()`]
+    /// renders its serialized structures in their human-readable form, as
+    /// returned by [`MagicSerialize::humanize()`]. [`RelativePathBuf`],
+    /// [`Tagged`], [`Duration`], [`ByteSize`], and [`Secret`] are registered by
+    /// default. Types registered later take precedence.
+    ///
+    /// See [`MagicSerialize`] for an example.
+    pub fn serialize_magic<T: MagicSerialize>(mut self) -> Self {
+        self.humanizers.insert(0, T::humanize);
+        self
     }
 
     /// Returns an iterator over the metadata for all of the collected values in
@@ -692,12 +4118,69 @@ impl Figment {
             .flatten()
     }
 
+    /// Returns the values available for the profile parameter `name`, sorted
+    /// and without duplicates. See [`Profile::param()`] for details on
+    /// parameterized profiles.
+    ///
+    /// The values are the keys of the dictionary at `name` in the default and
+    /// global profiles as well as the values of any profiles of the form
+    /// `name:value`. Returns an empty vector if the figment fails to evaluate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, Toml}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         [default.tenant.acme]
+    ///         name = "ACME Corp."
+    ///
+    ///         [default.tenant.initech]
+    ///         name = "Initech"
+    ///
+    ///         ["tenant:hooli"]
+    ///         name = "Hooli"
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("Config.toml").nested());
+    ///     assert_eq!(figment.param_values("tenant"), vec!["acme", "hooli", "initech"]);
+    ///     assert!(figment.param_values("region").is_empty());
+    ///
+    ///     let hooli = figment.select(figment::Profile::param("tenant", "hooli"));
+    ///     assert_eq!(hooli.extract_inner::<String>("name")?, "Hooli");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn param_values(&self, name: &str) -> Vec<String> {
+        let map = match &self.value {
+            Ok(map) => map,
+            Err(_) => return vec![],
+        };
+
+        let from_profiles = map.keys()
+            .filter_map(|p| p.as_str().as_str().split_once(':'))
+            .filter(|(n, value)| uncased::eq(n, name) && !value.is_empty())
+            .map(|(_, value)| value.to_string());
+
+        let shared = [Profile::Default, Profile::Global];
+        let from_keys = shared.iter()
+            .filter_map(|p| map.get(p)?.get(name)?.as_dict())
+            .flat_map(|dict| dict.keys().cloned());
+
+        let values: std::collections::BTreeSet<_> = from_profiles.chain(from_keys).collect();
+        values.into_iter().collect()
+    }
+
     /// Finds the value at `path` in the combined value.
     ///
     /// If there is an error evaluating the combined figment, that error is
     /// returned. Otherwise if there is a value at `path`, returns `Ok(value)`,
     /// and if there is no value at `path`, returns `Err` of kind
-    /// `MissingField`.
+    /// `MissingField`. The value is the one [`Figment::extract_inner()`]
+    /// deserializes; see [`Figment::find_profile()`] for details on how
+    /// profiles are consulted.
     ///
     /// See [`Value::find()`] for details on the syntax for `path`.
     ///
@@ -739,9 +4222,121 @@ impl Figment {
     /// });
     /// ```
     pub fn find_value(&self, path: &str) -> Result<Value> {
-        self.merged()?
-            .find(path)
-            .ok_or_else(|| Kind::MissingField(path.to_string().into()).into())
+        self.lookup(path).map(Cow::into_owned)
+    }
+
+    /// Returns the profile that supplied the value at `path`: the profile of
+    /// highest precedence containing the value or, for a dictionary unioned
+    /// from several profiles, containing part of it. Returns `None` if there
+    /// is no value at `path` or if combining a provider failed.
+    ///
+    /// Like every lookup method, including [`Figment::find_value()`] and
+    /// [`Figment::find_metadata()`], profiles are consulted exactly as when
+    /// extracting: from highest to lowest precedence, the global profile, the
+    /// selected profile, the profiles it [inherits](Figment::select_level())
+    /// from, and the default profile. The value at `path` found by a lookup is
+    /// thus always the value [`Figment::extract_inner()`] deserializes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Profile, Jail, providers::{Format, Toml, Env}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         [default]
+    ///         name = "app"
+    ///         port = 80
+    ///         server = { host = "localhost" }
+    ///
+    ///         [debug]
+    ///         port = 8080
+    ///         server = { workers = 1 }
+    ///     "#)?;
+    ///
+    ///     jail.set_env("APP_NAME", "env");
+    ///     let figment = Figment::from(Toml::file("App.toml").nested())
+    ///         .merge(Env::prefixed("APP_").global())
+    ///         .select("debug");
+    ///
+    ///     assert_eq!(figment.find_profile("name"), Some(Profile::Global));
+    ///     assert_eq!(figment.find_profile("port"), Some(Profile::from("debug")));
+    ///     assert_eq!(figment.find_profile("server"), Some(Profile::from("debug")));
+    ///     assert_eq!(figment.find_profile("server.host"), Some(Profile::Default));
+    ///     assert_eq!(figment.find_profile("missing"), None);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn find_profile(&self, path: &str) -> Option<Profile> {
+        self.lookup_with_profile(path).ok().map(|(_, profile)| profile)
+    }
+
+    /// Returns the original, unparsed source text of the value at `path` and
+    /// the name of its format, for instance `"TOML"`, if the value was supplied
+    /// in its entirety by a single textual source that supports raw lookups.
+    /// Otherwise returns `None`.
+    ///
+    /// This allows opaque sections of configuration to be passed through
+    /// untouched instead of being round-tripped through [`Value`]. The
+    /// [`Toml`](crate::providers::Toml) and [`Json`](crate::providers::Json)
+    /// formats support raw lookups; see [`Format::find_raw()`] for adding
+    /// support to custom formats. Files are read again on every call.
+    ///
+    /// See [`Value::find()`] for details on the syntax for `path`.
+    ///
+    /// [`Format::find_raw()`]: crate::providers::Format::find_raw()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, Toml, Env}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         [metrics]
+    ///         interval = 10
+    ///
+    ///         [metrics.custom]
+    ///         regex = '(.*):\d+'   # keeps its comment and quoting
+    ///         action = "replace"
+    ///     "#)?;
+    ///
+    ///     jail.set_env("APP_METRICS.INTERVAL", 5);
+    ///     let figment = Figment::new()
+    ///         .merge(Toml::file("Config.toml"))
+    ///         .merge(Env::prefixed("APP_"));
+    ///
+    ///     let (raw, format) = figment.raw_inner("metrics.custom").unwrap();
+    ///     assert_eq!(format, "TOML");
+    ///     assert!(raw.contains(r#"regex = '(.*):\d+'   # keeps its comment and quoting"#));
+    ///     assert!(raw.contains(r#"action = "replace""#));
+    ///
+    ///     // `metrics` is partially supplied by `Env`, which isn't textual.
+    ///     assert!(figment.raw_inner("metrics").is_none());
+    ///     assert!(figment.raw_inner("metrics.interval").is_none());
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn raw_inner(&self, path: &str) -> Option<(String, &'static str)> {
+        fn from_source(value: &Value, tag: Tag) -> bool {
+            let same = value.tag() == tag && value.tag().profile() == tag.profile();
+            same && match value {
+                Value::Dict(_, dict) => dict.values().all(|v| from_source(v, tag)),
+                Value::Array(_, array) => array.iter().all(|v| from_source(v, tag)),
+                _ => true,
+            }
+        }
+
+        let value = self.lookup(path).ok()?;
+        if !from_source(&value, value.tag()) {
+            return None;
+        }
+
+        let keys: Vec<_> = path.split('.').collect();
+        let profile = value.tag().profile().unwrap_or_else(|| self.profile.clone());
+        self.get_metadata(value.tag())?.find_raw(&profile, &keys)
     }
 
     /// Returns `true` if the combined figment evaluates successfully and
@@ -784,7 +4379,147 @@ impl Figment {
     /// });
     /// ```
     pub fn contains(&self, path: &str) -> bool {
-        self.merged().map_or(false, |v| v.find_ref(path).is_some())
+        self.lookup(path).is_ok()
+    }
+
+    /// Returns `true` if the value at `key` path in the combined figment was
+    /// supplied by a provider and `false` otherwise. See [`Value::find()`] for
+    /// details on the syntax for `key`.
+    ///
+    /// Unlike [`Figment::contains()`] and [`Figment::find_value()`], this
+    /// method additionally requires that the value is tagged with the metadata
+    /// of a provider in this figment. A `false` return value thus indicates
+    /// that a field deserialized from `key` will be filled in by its
+    /// `#[serde(default)]`, if any, as opposed to by configuration. This makes
+    /// it possible to audit which values were explicitly configured.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    ///
+    /// use figment::{Figment, providers::{Format, Toml, Env}};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     #[serde(default)]
+    ///     port: u16,
+    ///     #[serde(default)]
+    ///     debug: bool,
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"name = "test""#)?;
+    ///     jail.set_env("APP_PORT", 8080);
+    ///
+    ///     let figment = Figment::new()
+    ///         .merge(Toml::file("Config.toml"))
+    ///         .merge(Env::prefixed("APP_"));
+    ///
+    ///     let config: Config = figment.extract()?;
+    ///     assert!(figment.provides("name"));
+    ///     assert!(figment.provides("port"));
+    ///     assert!(!figment.provides("debug"));
+    ///     assert!(!config.debug);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn provides(&self, key: &str) -> bool {
+        let tag = match self.lookup(key) {
+            Ok(value) => value.tag(),
+            Err(_) => return false,
+        };
+
+        !tag.is_default() && self.metadata.contains_key(&tag)
+    }
+
+    /// Returns every value at `key` path that was overridden by another value,
+    /// from lowest to highest precedence, along with the metadata of each
+    /// value's provider. See [`Value::find()`] for details on the syntax for
+    /// `key`.
+    ///
+    /// A value is overridden when a provider's value for `key` was discarded by
+    /// a later [`merge`](Figment::merge()) or [`join`](Figment::join()), if
+    /// enabled via [`Figment::record_shadowed()`], or when a profile of higher
+    /// precedence than the value's profile supplies a value for `key`. Dictionaries are unioned rather than overridden and are
+    /// thus only included when replaced by a non-dictionary value. The value
+    /// currently at `key`, if any, is never included; the number of times a
+    /// key was overridden is the length of the returned vector.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, Toml, Env}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         [default]
+    ///         port = 80
+    ///
+    ///         [debug]
+    ///         port = 8000
+    ///     "#)?;
+    ///
+    ///     jail.set_env("APP_PORT", 8080);
+    ///     let figment = Figment::new()
+    ///         .record_shadowed(true)
+    ///         .merge(Toml::file("Config.toml").nested())
+    ///         .merge(Env::prefixed("APP_").profile("debug"))
+    ///         .select("debug");
+    ///
+    ///     assert_eq!(figment.extract_inner::<u16>("port")?, 8080);
+    ///
+    ///     let shadowed = figment.shadowed("port");
+    ///     assert_eq!(shadowed.len(), 2);
+    ///     assert_eq!(shadowed[0].0.to_i128(), Some(80));
+    ///     assert_eq!(shadowed[1].0.to_i128(), Some(8000));
+    ///     assert!(shadowed.iter().all(|(_, md)| md.name.starts_with("TOML")));
+    ///
+    ///     assert!(figment.shadowed("host").is_empty());
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn shadowed(&self, key: &str) -> Vec<(Value, &Metadata)> {
+        fn find<'v>(dict: &'v Dict, key: &str) -> Option<&'v Value> {
+            let mut keys = key.split('.');
+            let mut value = dict.get(keys.next()?)?;
+            for key in keys {
+                value = value.as_dict()?.get(key)?;
+            }
+
+            Some(value)
+        }
+
+        let map = match &self.value {
+            Ok(map) => map,
+            Err(_) => return vec![],
+        };
+
+        let current = self.lookup(key).ok().map(|v| (v.tag(), v.tag().profile()));
+        let mut values = vec![];
+        let custom = self.cascade();
+        for profile in Some(&Profile::Default).into_iter().chain(custom).chain(Some(&Profile::Global)) {
+            let shadowed = self.shadowed.as_ref().and_then(|s| s.get(profile));
+            if let Some(discarded) = shadowed.and_then(|p| p.get(key)) {
+                values.extend(discarded.iter().cloned());
+            }
+
+            if let Some(value) = map.get(profile).and_then(|dict| find(dict, key)) {
+                let is_current = current == Some((value.tag(), value.tag().profile()));
+                if !is_current && value.as_dict().is_none() {
+                    values.push(value.clone());
+                }
+            }
+        }
+
+        values.into_iter()
+            .filter_map(|v| {
+                let metadata = self.get_metadata(v.tag())?;
+                Some((v, metadata))
+            })
+            .collect()
     }
 
     /// Finds the metadata for the value at `key` path. See [`Value::find()`]
@@ -816,7 +4551,168 @@ impl Figment {
     /// });
     /// ```
     pub fn find_metadata(&self, key: &str) -> Option<&Metadata> {
-        self.metadata.get(&self.find_value(key).ok()?.tag())
+        self.metadata.get(&self.lookup(key).ok()?.tag())
+    }
+
+    /// Returns the key path, value, and metadata of every leaf of the
+    /// effective configuration of the selected profile, sorted by key path.
+    ///
+    /// A leaf is any value that isn't a dictionary; arrays are leaves and are
+    /// attributed to the provider that supplied the array. Together, the
+    /// leaves make up an annotated "effective config" that shows which
+    /// provider, and via [`Metadata::interpolate()`], which file key or
+    /// environment variable, each value came from.
+    ///
+    /// # Errors
+    ///
+    /// Returns the figment's error if combining a provider failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Env}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         name = "app"
+    ///         [database]
+    ///         url = "postgres://localhost"
+    ///         pool = 4
+    ///     "#)?;
+    ///
+    ///     jail.set_env("APP_DATABASE.POOL", 16);
+    ///     let figment = Figment::from(Toml::file("App.toml")).merge(Env::prefixed("APP_"));
+    ///
+    ///     let leaves: Vec<_> = figment.provenance()?
+    ///         .map(|(path, value, md)| (path, value.to_u128(), md.name.to_string()))
+    ///         .collect();
+    ///
+    ///     assert_eq!(leaves, vec![
+    ///         ("database.pool".into(), Some(16), "`APP_` environment variable(s)".into()),
+    ///         ("database.url".into(), None, "TOML file".into()),
+    ///         ("name".into(), None, "TOML file".into()),
+    ///     ]);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn provenance(&self) -> Result<impl Iterator<Item = (String, Value, &Metadata)> + '_> {
+        let mut leaves = vec![];
+        self.merged()?.walk(&mut |path: &[String], value: &Value| {
+            if let Some(mut redacted) = value.as_dict().and_then(Secret::<Value>::humanize) {
+                if let Some(metadata) = self.metadata.get(&value.tag()) {
+                    redacted.map_tag(|t| *t = value.tag());
+                    leaves.push((path.join("."), redacted, metadata));
+                }
+
+                return Visit::SkipChildren;
+            }
+
+            if value.as_dict().is_none() {
+                if let Some(metadata) = self.metadata.get(&value.tag()) {
+                    leaves.push((path.join("."), value.clone(), metadata));
+                }
+
+                return Visit::SkipChildren;
+            }
+
+            Visit::Continue
+        });
+
+        Ok(leaves.into_iter())
+    }
+
+    /// Returns the metadata of every provider whose source has changed since
+    /// it was read, as determined by [`Metadata::source_changed()`], in the
+    /// order the providers were added.
+    ///
+    /// Only providers that record a checksum, like [`Data`] and [`Http`] with
+    /// `checksum(true)`, are checked; the sources of other providers are never
+    /// reported as changed. Each check reads the source again, but nothing is
+    /// parsed or merged, making this a cheap way to poll whether a figment
+    /// needs to be rebuilt.
+    ///
+    /// [`Data`]: crate::providers::Data
+    /// [`Http`]: crate::providers::Http
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml, Json}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", "port = 8080")?;
+    ///     jail.create_file("App.json", r#"{ "name": "app" }"#)?;
+    ///
+    ///     let figment = Figment::new()
+    ///         .merge(Toml::file("App.toml").checksum(true))
+    ///         .merge(Json::file("App.json").checksum(true));
+    ///
+    ///     assert!(figment.sources_changed().is_empty());
+    ///
+    ///     jail.create_file("App.toml", "port = 9090")?;
+    ///     let changed = figment.sources_changed();
+    ///     assert_eq!(changed.len(), 1);
+    ///     assert_eq!(changed[0].name, "TOML file");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn sources_changed(&self) -> Vec<&Metadata> {
+        self.metadata.values().filter(|md| md.source_changed()).collect()
+    }
+
+    /// Returns every key whose value differs between `self` and `other`, in
+    /// their selected profiles, ordered by key path. Each [`Change`] records
+    /// whether the key was added to, removed from, or changed in `other`
+    /// relative to `self` along with both values and the metadata of the
+    /// providers of each. See [`Change`] for how values are compared.
+    ///
+    /// This is useful on reload, to determine which keys changed and whether,
+    /// for instance, a restart is required.
+    ///
+    /// # Errors
+    ///
+    /// Returns the figment's error if combining a provider into either failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, ChangeKind, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         port = 80
+    ///         workers = 4
+    ///         [tls]
+    ///         cert = "a.pem"
+    ///     "#)?;
+    ///
+    ///     let old = Figment::from(Toml::file("App.toml"));
+    ///
+    ///     jail.create_file("App.toml", r#"
+    ///         port = 8080
+    ///         workers = 4
+    ///         debug = true
+    ///     "#)?;
+    ///
+    ///     let new = Figment::from(Toml::file("App.toml"));
+    ///     let changes = old.diff(&new)?;
+    ///     let keys: Vec<_> = changes.iter().map(|c| (c.key.as_str(), c.kind)).collect();
+    ///     assert_eq!(keys, [
+    ///         ("debug", ChangeKind::Added),
+    ///         ("port", ChangeKind::Changed),
+    ///         ("tls.cert", ChangeKind::Removed),
+    ///     ]);
+    ///
+    ///     let restart = changes.iter().any(|c| c.key == "port" || c.key.starts_with("tls."));
+    ///     assert!(restart);
+    ///     assert_eq!(changes[1].new_metadata.as_ref().unwrap().name, "TOML file");
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn diff(&self, other: &Figment) -> Result<Vec<Change>> {
+        crate::diff::diff(self, other)
     }
 
     /// Returns the metadata with the given `tag` if this figment contains a
@@ -873,6 +4769,100 @@ impl Default for Figment {
     }
 }
 
+/// Figments are equal if they have the same [content](Figment::content_eq())
+/// and the same selected profile, including any profiles it inherits from.
+impl PartialEq for Figment {
+    fn eq(&self, other: &Self) -> bool {
+        self.profile == other.profile
+            && self.lineage == other.lineage
+            && self.content_eq(other)
+    }
+}
+
+/// Returns the top-level keys of each profile in `old` or `new`, sorted.
+fn affected_keys(old: &Map<Profile, Dict>, new: &Map<Profile, Dict>) -> Map<Profile, Vec<String>> {
+    let mut keys: Map<Profile, Vec<String>> = Map::new();
+    for (profile, dict) in old.iter().chain(new) {
+        keys.entry(profile.clone()).or_default().extend(dict.keys().cloned());
+    }
+
+    for keys in keys.values_mut() {
+        keys.sort();
+        keys.dedup();
+    }
+
+    keys
+}
+
+/// Returns the value at `path` in `value`, descending into dictionaries by
+/// key and into arrays by index.
+/// Returns the components of `version`, of the form `major.minor.patch` with
+/// an optional pre-release or build suffix, that semver-compatible versions
+/// share: the major version or, for `0.x` versions, the leading zeros and the
+/// first non-zero component.
+fn semver_compatibility(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    let (major, minor, patch) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(match (major, minor) {
+        (0, 0) => (0, 0, patch),
+        (0, minor) => (0, minor, 0),
+        (major, _) => (major, 0, 0),
+    })
+}
+
+/// Suggests, for each missing field error in `error` for a dictionary in
+/// `value`, the value at `path`, a similarly spelled key in the dictionary.
+fn suggest_missing(error: Error, path: &str, value: &Value) -> Error {
+    let prefix: Vec<&str> = path.split('.').filter(|k| !k.is_empty()).collect();
+    let errors = error.into_iter().map(|mut e| {
+        if let (Kind::MissingField(field), None) = (&e.kind, e.suggestion()) {
+            let relative = match e.path.iter().zip(&prefix).all(|(a, b)| a == b) {
+                true if e.path.len() >= prefix.len() => &e.path[prefix.len()..],
+                _ => &e.path[..],
+            };
+
+            if let Some(Value::Dict(_, dict)) = value_at(value, relative) {
+                let keys = dict.keys().map(|k| k.as_str());
+                e.set_suggestion(crate::typos::suggest(field, keys));
+            }
+        }
+
+        e
+    });
+
+    crate::schema::join(errors.collect()).unwrap_err()
+}
+
+fn value_at<'v>(mut value: &'v Value, path: &[String]) -> Option<&'v Value> {
+    for key in path {
+        value = match value {
+            Value::Dict(_, dict) => dict.get(key)?,
+            Value::Array(_, array) => array.get(key.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+
+    Some(value)
+}
+
+/// Mutable version of [`value_at()`].
+fn value_at_mut<'v>(mut value: &'v mut Value, path: &[String]) -> Option<&'v mut Value> {
+    for key in path {
+        value = match value {
+            Value::Dict(_, dict) => dict.get_mut(key)?,
+            Value::Array(_, array) => array.get_mut(key.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+
+    Some(value)
+}
+
 #[test]
 #[cfg(test)]
 fn is_send_sync() {
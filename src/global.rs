@@ -0,0 +1,257 @@
+//! A process-wide figment, initialized once and extracted from anywhere.
+//!
+//! Many applications read one configuration at startup and consult it
+//! throughout. Rather than threading a [`Figment`] or an extracted
+//! configuration through the application, the figment can be installed
+//! process-wide with [`init()`] and a configuration extracted from it anywhere
+//! with [`get()`]. Each configuration type is extracted at most once:
+//! subsequent calls to [`get()`] return the cached value.
+//!
+//! The global figment can be initialized only once. To test code that reads
+//! the global configuration, a different figment can be installed for the
+//! current thread, which `cargo test` runs each test in, with [`scoped()`].
+//! The figment is used by the thread until the returned [`Scope`] is dropped,
+//! without affecting other threads or requiring the global figment to be
+//! initialized. To override only a few values, [`override_scoped()`] merges a
+//! provider into the figment in use instead.
+//!
+//! This module is only available with the `global` feature enabled.
+//!
+//! # Example
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use figment::{Figment, global};
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     workers: u16,
+//! }
+//!
+//! fn workers() -> u16 {
+//!     global::get::<Config>().expect("valid configuration").workers
+//! }
+//!
+//! assert!(global::get::<Config>().is_err());
+//!
+//! global::init(Figment::from(("name", "app")).merge(("workers", 8))).unwrap();
+//! assert_eq!(workers(), 8);
+//! assert_eq!(global::get::<Config>().unwrap().name, "app");
+//!
+//! // The global figment can only be initialized once.
+//! let error = global::init(Figment::from(("name", "other"))).unwrap_err();
+//! assert_eq!(error.into_inner().extract_inner::<String>("name").unwrap(), "other");
+//!
+//! // Tests can use a different figment on the current thread.
+//! let scope = global::scoped(Figment::from(("name", "test")).merge(("workers", 1)));
+//! assert_eq!(workers(), 1);
+//!
+//! drop(scope);
+//! assert_eq!(workers(), 8);
+//! ```
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde::de::DeserializeOwned;
+
+use crate::{Figment, Error, Provider};
+use crate::value::Map;
+
+/// A figment and the configurations extracted from it, by type.
+struct Global {
+    figment: Figment,
+    cache: Mutex<Map<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+/// The figment set via [`init()`], if any.
+static GLOBAL: RwLock<Option<Arc<Global>>> = RwLock::new(None);
+
+thread_local! {
+    /// The figments set via [`scoped()`] on this thread, innermost last.
+    static SCOPED: RefCell<Vec<Arc<Global>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The error returned by [`init()`] when the global figment was already
+/// initialized, holding the figment that wasn't installed.
+#[derive(Debug)]
+pub struct AlreadyInitialized(Box<Figment>);
+
+impl AlreadyInitialized {
+    /// Returns the figment that wasn't installed.
+    pub fn into_inner(self) -> Figment {
+        *self.0
+    }
+}
+
+impl std::fmt::Display for AlreadyInitialized {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the global figment is already initialized")
+    }
+}
+
+impl std::error::Error for AlreadyInitialized {}
+
+/// A guard that uses a figment in place of the global figment on the current
+/// thread until dropped. Returned by [`scoped()`].
+#[must_use = "the figment is only used until the scope is dropped"]
+pub struct Scope {
+    global: Arc<Global>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Global {
+    fn new(figment: Figment) -> Arc<Global> {
+        Arc::new(Global { figment, cache: Mutex::new(Map::new()) })
+    }
+
+    /// Returns the figment used on the current thread, if any.
+    fn current() -> Option<Arc<Global>> {
+        SCOPED.with(|scoped| scoped.borrow().last().cloned())
+            .or_else(|| GLOBAL.read().unwrap_or_else(|e| e.into_inner()).clone())
+    }
+}
+
+/// Installs `figment` as the global figment. Returns `figment` back, within
+/// an [`AlreadyInitialized`] error, if the global figment was already
+/// initialized.
+///
+/// See the [module docs](self) for an example.
+pub fn init(figment: Figment) -> Result<(), AlreadyInitialized> {
+    let mut global = GLOBAL.write().unwrap_or_else(|e| e.into_inner());
+    match *global {
+        Some(_) => Err(AlreadyInitialized(Box::new(figment))),
+        None => {
+            *global = Some(Global::new(figment));
+            Ok(())
+        }
+    }
+}
+
+/// Returns `true` if the global figment was initialized via [`init()`].
+pub fn is_initialized() -> bool {
+    GLOBAL.read().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+/// Returns a `T` extracted from the figment used on the current thread: the
+/// innermost [`scoped()`] figment, if any, or else the global figment.
+///
+/// A `T` is extracted at most once per figment; subsequent calls return the
+/// cached value. Failed extractions are not cached.
+///
+/// # Errors
+///
+/// Returns an error if the global figment was not initialized and no scoped
+/// figment is in use or if extracting a `T` fails.
+///
+/// See the [module docs](self) for an example.
+pub fn get<T>() -> Result<Arc<T>, Error>
+    where T: DeserializeOwned + Send + Sync + 'static
+{
+    let global = Global::current().ok_or("the global figment was not initialized")?;
+    let cached = global.cache.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&TypeId::of::<T>())
+        .cloned();
+
+    if let Some(Ok(value)) = cached.map(|v| v.downcast::<T>()) {
+        return Ok(value);
+    }
+
+    let value = Arc::new(global.figment.extract::<T>()?);
+    global.cache.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(TypeId::of::<T>(), value.clone());
+
+    Ok(value)
+}
+
+/// Returns a clone of the figment used on the current thread, if any: the
+/// innermost [`scoped()`] figment, if any, or else the global figment.
+///
+/// ```rust
+/// use figment::{Figment, global};
+///
+/// let _scope = global::scoped(Figment::from(("port", 8080)));
+/// let figment = global::figment().unwrap();
+/// assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 8080);
+/// ```
+pub fn figment() -> Option<Figment> {
+    Global::current().map(|global| global.figment.clone())
+}
+
+/// Uses `figment` in place of the global figment on the current thread until
+/// the returned [`Scope`] is dropped. Scopes nest: dropping a scope restores
+/// the figment in use when it was created.
+///
+/// Scopes are intended for tests. The global figment need not be initialized
+/// and is unaffected, as are other threads.
+///
+/// ```rust
+/// use figment::{Figment, global};
+///
+/// let outer = global::scoped(Figment::from(("port", 8080)));
+/// assert_eq!(global::figment().unwrap().extract_inner::<u16>("port").unwrap(), 8080);
+/// assert!(!global::is_initialized());
+///
+/// let inner = global::scoped(Figment::from(("port", 9090)));
+/// assert_eq!(global::figment().unwrap().extract_inner::<u16>("port").unwrap(), 9090);
+///
+/// drop(inner);
+/// assert_eq!(global::figment().unwrap().extract_inner::<u16>("port").unwrap(), 8080);
+///
+/// std::thread::spawn(|| assert!(global::figment().is_none())).join().unwrap();
+/// drop(outer);
+/// ```
+pub fn scoped(figment: Figment) -> Scope {
+    let global = Global::new(figment);
+    SCOPED.with(|scoped| scoped.borrow_mut().push(global.clone()));
+    Scope { global, _not_send: PhantomData }
+}
+
+/// Uses the figment in use on the current thread, with `provider` merged, in
+/// place of the global figment on the current thread until the returned
+/// [`Scope`] is dropped. Equivalent to `scoped(figment().merge(provider))`.
+///
+/// Like [`scoped()`], this is intended for tests that need to override a few
+/// values of an otherwise shared configuration.
+///
+/// # Errors
+///
+/// Returns an error if the global figment was not initialized and no scoped
+/// figment is in use.
+///
+/// ```rust
+/// use figment::{Figment, global, providers::Serialized};
+///
+/// let figment = Figment::from(Serialized::default("db.url", "postgres://prod"));
+/// let _scope = global::scoped(figment.merge(("port", 80)));
+///
+/// let guard = global::override_scoped(Serialized::default("db.url", "test://")).unwrap();
+/// let figment = global::figment().unwrap();
+/// assert_eq!(figment.extract_inner::<String>("db.url").unwrap(), "test://");
+/// assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 80);
+///
+/// drop(guard);
+/// let figment = global::figment().unwrap();
+/// assert_eq!(figment.extract_inner::<String>("db.url").unwrap(), "postgres://prod");
+/// ```
+#[track_caller]
+pub fn override_scoped<T: Provider>(provider: T) -> Result<Scope, Error> {
+    let figment = figment().ok_or("the global figment was not initialized")?;
+    Ok(scoped(figment.merge(provider)))
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        SCOPED.with(|scoped| {
+            let mut scoped = scoped.borrow_mut();
+            if let Some(i) = scoped.iter().rposition(|g| Arc::ptr_eq(g, &self.global)) {
+                scoped.remove(i);
+            }
+        });
+    }
+}
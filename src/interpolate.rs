@@ -0,0 +1,195 @@
+use crate::error::{Error, Kind, Actual};
+use crate::value::{Value, Tag, Visit};
+
+/// A piece of a string value: literal text or a `${name}` or
+/// `${name:-default}` reference.
+enum Piece<'s> {
+    Literal(&'s str),
+    Reference(&'s str, Option<&'s str>),
+}
+
+/// Returns `root` with every reference in its string values resolved as
+/// described in [`Figment::resolve_interpolation()`].
+///
+/// [`Figment::resolve_interpolation()`]: crate::Figment::resolve_interpolation()
+pub(crate) fn resolve(root: &Value) -> Result<Value, Error> {
+    let mut resolved = root.clone();
+    let mut error = None;
+    resolved.walk_mut(&mut |path: &[String], value: &mut Value| {
+        let (tag, string) = match value {
+            Value::String(tag, string) if string.contains("${") => (*tag, string.clone()),
+            _ => return Visit::Continue,
+        };
+
+        match resolve_str(root, &mut vec![path.join(".")], tag, &string) {
+            Ok(v) => {
+                *value = v;
+                Visit::SkipChildren
+            }
+            Err(e) => {
+                let mut e = Error::from(e).retagged(tag);
+                e.path = path.to_vec();
+                error = Some(e);
+                Visit::Stop
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(resolved),
+    }
+}
+
+/// Resolves the references in `value`, reached via the key paths in `stack`.
+fn resolve_value(root: &Value, stack: &mut Vec<String>, value: &Value) -> Result<Value, Kind> {
+    let path = stack.last().cloned().unwrap_or_default();
+    let child = |key: &str| match path.is_empty() {
+        true => key.to_string(),
+        false => format!("{}.{}", path, key),
+    };
+
+    Ok(match value {
+        Value::String(tag, string) if string.contains("${") => {
+            return resolve_str(root, stack, *tag, string);
+        }
+        Value::Dict(tag, dict) => {
+            let mut resolved = dict.clone();
+            for (key, value) in resolved.iter_mut() {
+                stack.push(child(key));
+                *value = resolve_value(root, stack, value)?;
+                stack.pop();
+            }
+
+            Value::Dict(*tag, resolved)
+        }
+        Value::Array(tag, array) => {
+            let mut resolved = array.clone();
+            for (i, value) in resolved.iter_mut().enumerate() {
+                stack.push(child(&i.to_string()));
+                *value = resolve_value(root, stack, value)?;
+                stack.pop();
+            }
+
+            Value::Array(*tag, resolved)
+        }
+        value => value.clone(),
+    })
+}
+
+/// Resolves the references in `string`, the string value with tag `tag` at
+/// the last key path in `stack`. A string consisting of a single reference to
+/// a key is replaced by the key's value.
+fn resolve_str(
+    root: &Value,
+    stack: &mut Vec<String>,
+    tag: Tag,
+    string: &str,
+) -> Result<Value, Kind> {
+    let pieces = parse(string)?;
+    if let [Piece::Reference(name, _)] = pieces.as_slice() {
+        if let Some(value) = root.find_ref(name) {
+            return reference(root, stack, name, value);
+        }
+    }
+
+    let mut resolved = String::with_capacity(string.len());
+    for piece in pieces {
+        let (name, default) = match piece {
+            Piece::Literal(literal) => {
+                resolved.push_str(literal);
+                continue;
+            }
+            Piece::Reference(name, default) => (name, default),
+        };
+
+        if let Some(value) = root.find_ref(name) {
+            match reference(root, stack, name, value)? {
+                Value::String(_, s) => resolved.push_str(&s),
+                Value::Char(_, c) => resolved.push(c),
+                Value::Bool(_, b) => resolved.push_str(&b.to_string()),
+                Value::Num(_, n) => match n.to_actual() {
+                    Actual::Unsigned(v) => resolved.push_str(&v.to_string()),
+                    Actual::Signed(v) => resolved.push_str(&v.to_string()),
+                    Actual::Float(v) => resolved.push_str(&v.to_string()),
+                    _ => unreachable!("numbers are unsigned, signed, or floats"),
+                },
+                value => {
+                    let msg = format!("cannot interpolate {} `{}` into a string",
+                        value.to_actual(), name);
+
+                    return Err(Kind::Message(msg));
+                }
+            }
+
+            continue;
+        }
+
+        match (std::env::var(name).ok(), default) {
+            (Some(value), Some(default)) if value.is_empty() => resolved.push_str(default),
+            (Some(value), _) => resolved.push_str(&value),
+            (None, Some(default)) => resolved.push_str(default),
+            (None, None) => {
+                let msg = format!("`${{{}}}` is neither a configured key nor a set \
+                    environment variable", name);
+
+                return Err(Kind::Message(msg));
+            }
+        }
+    }
+
+    Ok(Value::String(tag, resolved))
+}
+
+/// Resolves `value`, the value of the key `name`, failing if `name` is being
+/// resolved already.
+fn reference(root: &Value, stack: &mut Vec<String>, name: &str, value: &Value) -> Result<Value, Kind> {
+    let cyclic = stack.iter()
+        .any(|p| p == name || (p.starts_with(name) && p[name.len()..].starts_with('.')));
+
+    if cyclic {
+        let cycle = stack.join("` -> `");
+        return Err(Kind::Message(format!("cyclic reference: `{}` -> `{}`", cycle, name)));
+    }
+
+    stack.push(name.to_string());
+    let resolved = resolve_value(root, stack, value);
+    stack.pop();
+    resolved
+}
+
+/// Splits `string` into literals and references. `$${` is a literal `${`.
+fn parse(string: &str) -> Result<Vec<Piece<'_>>, Kind> {
+    let mut pieces = vec![];
+    let mut rest = string;
+    while let Some(i) = rest.find("${") {
+        if rest[..i].ends_with('$') {
+            pieces.push(Piece::Literal(&rest[..(i - 1)]));
+            pieces.push(Piece::Literal("${"));
+            rest = &rest[(i + 2)..];
+            continue;
+        }
+
+        let end = rest[i..].find('}').map(|j| i + j).ok_or_else(|| {
+            Kind::Message(format!("unterminated reference in {:?}", string))
+        })?;
+
+        let reference = &rest[(i + 2)..end];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+
+        if name.is_empty() {
+            return Err(Kind::Message(format!("empty reference in {:?}", string)));
+        }
+
+        pieces.push(Piece::Literal(&rest[..i]));
+        pieces.push(Piece::Reference(name, default));
+        rest = &rest[(end + 1)..];
+    }
+
+    pieces.push(Piece::Literal(rest));
+    pieces.retain(|piece| !matches!(piece, Piece::Literal("")));
+    Ok(pieces)
+}
@@ -20,10 +20,12 @@ use crate::error::Result;
 ///   * Synchronizes all calls to [`Jail::expect_with()`] and
 ///     [`Jail::try_with()`] to prevent environment variables races.
 ///   * Switches into a fresh temporary directory ([`Jail::directory()`]) where
-///     files can be created with [`Jail::create_file()`].
+///     files can be created with [`Jail::create_file()`] or, for a nested
+///     layout, [`Jail::create_tree()`].
 ///   * Keeps track of environment variables created with [`Jail::set_env()`]
 ///     and clears them when the `Jail` exits.
-///   * Deletes the temporary directory and all of its contents when exiting.
+///   * Deletes the temporary directory and all of its contents when exiting,
+///     failing [`Jail::try_with()`] if the directory can't be removed.
 ///
 /// Additionally, because `Jail` expects functions that return a [`Result`],
 /// the `?` operator can be used liberally in a jail:
@@ -56,7 +58,8 @@ use crate::error::Result;
 /// ```
 #[cfg_attr(nightly, doc(cfg(feature = "test")))]
 pub struct Jail {
-    _directory: TempDir,
+    /// The temporary directory, taken in `try_with()` to verify its removal.
+    directory: Option<TempDir>,
     canonical_dir: PathBuf,
     saved_env_vars: HashMap<OsString, Option<OsString>>,
     saved_cwd: PathBuf,
@@ -133,26 +136,44 @@ impl Jail {
         let directory = TempDir::new().map_err(as_string)?;
         let mut jail = Jail {
             canonical_dir: directory.path().canonicalize().map_err(as_string)?,
-            _directory: directory,
+            directory: Some(directory),
             saved_cwd: std::env::current_dir().map_err(as_string)?,
             saved_env_vars: HashMap::new(),
         };
 
         std::env::set_current_dir(jail.directory()).map_err(as_string)?;
-        f(&mut jail)
+        let result = f(&mut jail);
+
+        // Restore the environment and working directory before removing the
+        // directory so that it isn't in use while it's being removed.
+        let directory = jail.directory.take();
+        drop(jail);
+
+        let cleanup = directory.map_or(Ok(()), |dir| {
+            let path = dir.path().to_path_buf();
+            dir.close().map_err(|e| format!("Jail: failed to remove `{}`: {}", path.display(), e))
+        });
+
+        result.and(cleanup.map_err(|e| e.into()))
     }
 
     /// Returns the directory the jail has switched into. The contents of this
-    /// directory will be cleared when `Jail` is dropped.
+    /// directory will be cleared when `Jail` is dropped. If they can't be,
+    /// [`Jail::try_with()`] returns an error.
     ///
     /// # Example
     ///
     /// ```rust
+    /// let mut path = None;
     /// figment::Jail::expect_with(|jail| {
     ///     let tmp_directory = jail.directory();
+    ///     assert!(tmp_directory.exists());
     ///
+    ///     path = Some(tmp_directory.to_path_buf());
     ///     Ok(())
     /// });
+    ///
+    /// assert!(!path.unwrap().exists());
     /// ```
     pub fn directory(&self) -> &Path {
         &self.canonical_dir
@@ -215,6 +236,52 @@ impl Jail {
         Ok(writer.into_inner().map_err(as_string)?)
     }
 
+    /// Creates every file in `files`, a sequence of `(path, contents)` pairs
+    /// typically constructed with [`files!`](crate::files!), within the jail's
+    /// directory, creating missing parent directories as needed. Files and
+    /// directories are deleted when the jail is dropped.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if any path is not relative or is outside of the
+    /// jail's directory. I/O errors while creating files or directories are
+    /// returned. Files created before the error remain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, files, providers::{Format, Toml, Json}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_tree(files! {
+    ///         "conf/app.toml" => "name = 'app'",
+    ///         "conf/extra/x.json" => r#"{ "x": 1 }"#,
+    ///     })?;
+    ///
+    ///     let figment = Figment::new()
+    ///         .merge(Toml::file("conf/app.toml"))
+    ///         .merge(Json::file("conf/extra/x.json"));
+    ///
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "app");
+    ///     assert_eq!(figment.extract_inner::<u8>("x")?, 1);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn create_tree<I, P, C>(&self, files: I) -> Result<()>
+        where I: IntoIterator<Item = (P, C)>, P: AsRef<Path>, C: AsRef<[u8]>
+    {
+        for (path, contents) in files {
+            let path = self.safe_jailed_path(path.as_ref())?;
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent).map_err(as_string)?;
+            }
+
+            self.create_binary(&path, contents.as_ref())?;
+        }
+
+        Ok(())
+    }
+
     /// Creates a directory at `path` within the jail's directory and returns
     /// the relative path to the subdirectory in the jail. Recursively creates
     /// directories for all of its parent components if they are missing.
@@ -364,3 +431,22 @@ impl Drop for Jail {
         let _ = std::env::set_current_dir(&self.saved_cwd);
     }
 }
+
+/// Constructs the `(path, contents)` pairs of a file tree for
+/// [`Jail::create_tree()`].
+///
+/// ```rust
+/// let tree = figment::files! {
+///     "App.toml" => "name = 'app'",
+///     "conf/db.json" => "{}",
+/// };
+///
+/// assert_eq!(tree[1], ("conf/db.json", "{}"));
+/// ```
+#[macro_export]
+#[cfg_attr(nightly, doc(cfg(feature = "test")))]
+macro_rules! files {
+    ($($path:expr => $contents:expr),* $(,)?) => (
+        [$(($path, $contents)),*]
+    );
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+use std::ops::Deref;
+
+/// A `.`-separated key path, typically constructed with [`key_path!`].
+///
+/// `KeyPath` dereferences to `str`, so it can be passed anywhere a key path
+/// string is expected, such as [`Figment::extract_inner()`] or
+/// [`Figment::find_value()`]. Unlike a string literal, a key path constructed
+/// with [`key_path!`] can be checked at compile time against the keys of a
+/// configuration type implementing [`KnownKeys`]:
+///
+/// ```rust
+/// use figment::{Figment, KeyPath, KnownKeys, key_path, providers::Serialized};
+///
+/// struct Config;
+///
+/// impl KnownKeys for Config {
+///     const KEYS: &'static [&'static str] = &["server.port", "server.tls.cert"];
+/// }
+///
+/// const CERT: KeyPath = key_path!(Config => server.tls.cert);
+///
+/// let figment = Figment::from(Serialized::default("server.tls.cert", "cert.pem"));
+/// assert_eq!(figment.extract_inner::<String>(&CERT).unwrap(), "cert.pem");
+/// assert_eq!(CERT.keys().collect::<Vec<_>>(), ["server", "tls", "cert"]);
+/// ```
+///
+/// [`Figment::extract_inner()`]: crate::Figment::extract_inner()
+/// [`Figment::find_value()`]: crate::Figment::find_value()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyPath(&'static str);
+
+/// A configuration type with a statically known set of key paths, against
+/// which [`key_path!`] checks paths at compile time.
+///
+/// `KEYS` lists every leaf key path of the type. A path is known if it is one
+/// of `KEYS` or a prefix of one ending at a `.`, so `server` and `server.tls`
+/// are known if `server.tls.cert` is. The list can be generated by a derive or
+/// build script, or written by hand.
+pub trait KnownKeys {
+    /// Every leaf key path of the type.
+    const KEYS: &'static [&'static str];
+}
+
+impl KeyPath {
+    /// Returns the key path `path`. Prefer [`key_path!`], which checks that
+    /// `path` is well-formed and, optionally, known.
+    ///
+    /// ```rust
+    /// use figment::KeyPath;
+    ///
+    /// let path = KeyPath::new("server.port");
+    /// assert_eq!(path.as_str(), "server.port");
+    /// ```
+    pub const fn new(path: &'static str) -> KeyPath {
+        KeyPath(path)
+    }
+
+    /// Returns the key path as a string.
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Returns an iterator over the keys in the path.
+    ///
+    /// ```rust
+    /// use figment::key_path;
+    ///
+    /// let keys: Vec<_> = key_path!(a.b.c).keys().collect();
+    /// assert_eq!(keys, ["a", "b", "c"]);
+    /// ```
+    pub fn keys(&self) -> impl Iterator<Item = &'static str> {
+        self.0.split('.')
+    }
+}
+
+/// Returns `true` if `path` is in `keys` or is a prefix of a key in `keys`
+/// ending at a `.`. Used by [`key_path!`] at compile time.
+#[doc(hidden)]
+pub const fn is_known(keys: &[&str], path: &str) -> bool {
+    let path = path.as_bytes();
+    let mut i = 0;
+    while i < keys.len() {
+        let key = keys[i].as_bytes();
+        if key.len() >= path.len() && (key.len() == path.len() || key[path.len()] == b'.') {
+            let mut j = 0;
+            while j < path.len() && key[j] == path[j] {
+                j += 1;
+            }
+
+            if j == path.len() {
+                return true;
+            }
+        }
+
+        i += 1;
+    }
+
+    false
+}
+
+impl Deref for KeyPath {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl AsRef<str> for KeyPath {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// Constructs a [`KeyPath`] from identifiers separated by `.`, optionally
+/// checked against the keys of a [`KnownKeys`] type at compile time.
+///
+/// `key_path!(server.tls.cert)` is the key path `"server.tls.cert"`. Because
+/// each key must be an identifier, a malformed path like `server..cert` fails
+/// to compile. `key_path!(Config => server.tls.cert)` additionally fails to
+/// compile unless the path is known to `Config`, so renaming a configuration
+/// field breaks the build rather than a lookup at runtime.
+///
+/// ```rust
+/// use figment::{KeyPath, KnownKeys, key_path};
+///
+/// struct Config;
+///
+/// impl KnownKeys for Config {
+///     const KEYS: &'static [&'static str] = &["name", "server.port"];
+/// }
+///
+/// assert_eq!(key_path!(server.port), KeyPath::new("server.port"));
+/// assert_eq!(key_path!(Config => server).as_str(), "server");
+/// assert_eq!(key_path!(Config => server.port).as_str(), "server.port");
+/// ```
+///
+/// An unknown path fails to compile:
+///
+/// ```rust,compile_fail
+/// use figment::{KnownKeys, key_path};
+///
+/// struct Config;
+///
+/// impl KnownKeys for Config {
+///     const KEYS: &'static [&'static str] = &["name", "server.port"];
+/// }
+///
+/// let path = key_path!(Config => server.host);
+/// ```
+#[macro_export]
+macro_rules! key_path {
+    ($first:ident $(. $rest:ident)*) => (
+        $crate::KeyPath::new(concat!(stringify!($first) $(, ".", stringify!($rest))*))
+    );
+    ($T:path => $first:ident $(. $rest:ident)*) => ({
+        const PATH: &str = concat!(stringify!($first) $(, ".", stringify!($rest))*);
+        const _: () = assert!(
+            $crate::__is_known_key_path(<$T as $crate::KnownKeys>::KEYS, PATH),
+            concat!("unknown key path `", stringify!($first) $(, ".", stringify!($rest))*, "`")
+        );
+
+        $crate::KeyPath::new(PATH)
+    });
+}
@@ -0,0 +1,101 @@
+use crate::{Figment, Metadata, Profile};
+use crate::coalesce::{Order, ArrayMerge};
+use crate::error::Result;
+use crate::value::{Map, Dict, Tag};
+
+/// A provider's data as it was combined into a figment.
+#[derive(Debug, Clone)]
+pub(crate) struct Layer {
+    pub tag: Tag,
+    pub order: Order,
+    /// The strategy the layer's arrays are combined with, if set for the
+    /// layer alone.
+    pub arrays: Option<ArrayMerge>,
+    /// The profiles the provider emitted data to. Empty if it failed.
+    pub profiles: Vec<Profile>,
+    /// The provider's tagged and checked data or the error it produced, if
+    /// retained via [`Figment::retain_layers()`](crate::Figment::retain_layers()).
+    pub data: Option<Result<Map<Profile, Dict>>>,
+}
+
+impl Layer {
+    /// Returns the layer of a provider tagged `tag` that emitted `data`,
+    /// retaining a copy of `data` only if `retain`.
+    pub fn new(
+        tag: Tag,
+        order: Order,
+        arrays: Option<ArrayMerge>,
+        data: &Result<Map<Profile, Dict>>,
+        retain: bool,
+    ) -> Self {
+        let profiles = match data {
+            Ok(data) => data.keys().cloned().collect(),
+            Err(_) => vec![],
+        };
+
+        let data = if retain { Some(data.clone()) } else { None };
+        Layer { tag, order, arrays, profiles, data }
+    }
+}
+
+/// The error produced when rebuilding a figment from layers whose data wasn't
+/// retained.
+pub(crate) fn unretained() -> crate::Error {
+    crate::Error::from("layer data wasn't retained: enable `Figment::retain_layers()` \
+        before combining providers to rebuild from layers")
+}
+
+/// A provider combined into a figment, as returned by
+/// [`Figment::layers()`](crate::Figment::layers()).
+///
+/// A list of `LayerInfo`, reordered, filtered, or with modified
+/// [`order`](LayerInfo::order)s, can be passed to
+/// [`Figment::with_layers()`](crate::Figment::with_layers()) to rebuild the
+/// figment if its layers' data was retained via
+/// [`Figment::retain_layers()`](crate::Figment::retain_layers()).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerInfo {
+    /// The position of the layer in the figment, starting at `0` for the
+    /// first provider combined.
+    pub index: usize,
+    /// The tag of the layer's values. See
+    /// [`Figment::get_metadata()`](crate::Figment::get_metadata()).
+    pub tag: Tag,
+    /// The metadata of the layer's provider.
+    pub metadata: Metadata,
+    /// The strategy the layer is combined with.
+    pub order: Order,
+    /// The profiles the layer emits data to. Empty if the provider failed.
+    pub profiles: Vec<Profile>,
+}
+
+/// A guard that keeps a provider layered onto a figment until dropped.
+/// Returned by [`Figment::override_scoped()`](crate::Figment::override_scoped()).
+///
+/// The guard dereferences to the figment with the provider layered on. When
+/// the guard is dropped, the figment is restored to its prior state.
+#[must_use = "the provider is removed as soon as the guard is dropped"]
+pub struct OverrideGuard<'a> {
+    figment: &'a mut Figment,
+    saved: Figment,
+}
+
+impl<'a> OverrideGuard<'a> {
+    pub(crate) fn new(figment: &'a mut Figment, saved: Figment) -> Self {
+        OverrideGuard { figment, saved }
+    }
+}
+
+impl std::ops::Deref for OverrideGuard<'_> {
+    type Target = Figment;
+
+    fn deref(&self) -> &Figment {
+        self.figment
+    }
+}
+
+impl Drop for OverrideGuard<'_> {
+    fn drop(&mut self) {
+        std::mem::swap(self.figment, &mut self.saved);
+    }
+}
@@ -292,25 +292,52 @@
 //! To help with compilation times, types, modules, and providers are gated by
 //! features. They are:
 //!
-//! | feature | gated namespace             | description                               |
-//! |---------|-----------------------------|-------------------------------------------|
-//! | `test`  | [`Jail`]                    | Semi-sandboxed environment for testing.   |
-//! | `toml`  | [`providers::Toml`]         | TOML file/string [`Provider`].            |
-//! | `json`  | [`providers::Json`]         | JSON file/string [`Provider`].            |
-//! | `yaml`  | [`providers::Yaml`]         | YAML file/string [`Provider`].            |
-//! | `yaml`  | [`providers::YamlExtended`] | [YAML Extended] file/string [`Provider`]. |
+//! | feature       | gated namespace              | description                               |
+//! |---------------|------------------------------|-------------------------------------------|
+//! | `test`        | [`Jail`]                     | Semi-sandboxed environment for testing.   |
+//! | `toml`        | [`providers::Toml`]          | TOML file/string [`Provider`].            |
+//! | `json`        | [`providers::Json`]          | JSON file/string [`Provider`].            |
+//! | `yaml`        | [`providers::Yaml`]          | YAML file/string [`Provider`].            |
+//! | `yaml`        | [`providers::YamlExtended`]  | [YAML Extended] file/string [`Provider`]. |
+//! | `ron`         | [`providers::Ron`]           | [RON] file/string [`Provider`].           |
+//! | `json5`       | [`providers::Json5`]         | [JSON5] file/string [`Provider`].         |
+//! | `global`      | [`global`]                   | A process-wide, cached figment.           |
+//! | `app`         | [`app`]                      | Conventional application layering.        |
+//! | `watch`       | [`watch`]                    | Reloading when source files change.       |
+//! | `schema`      | [`schema::JsonSchema`]       | Validation against schemas.               |
+//! | `http`        | [`providers::Http`]          | Documents fetched over HTTP.              |
+//! | `async`       | [`AsyncProvider`]            | Providers with asynchronous data.         |
+//! | `clap`        | [`providers::CliArgs`]       | Command-line arguments via [`clap`].      |
+//! | `schemars`    | [`schema::JsonSchema::of()`] | JSON Schemas generated via [`schemars`].  |
+//! | `log`         | _none_                       | [`log`] records of combining, extracting. |
+//! | `diagnostics` | [`Error::pretty()`]          | Errors rendered with source snippets.     |
+//!
+//! With the `log` feature enabled, combining a provider and extracting a value
+//! are logged at the `debug` level, and each merge decision at the `trace`
+//! level, with the target `figment`. Records never include configuration
+//! values, and at most 64 merge decisions are logged per provider.
 //!
 //! [YAML Extended]: providers::YamlExtended::from_str()
+//! [RON]: https://github.com/ron-rs/ron
+//! [JSON5]: https://json5.org
+//! [`log`]: https://docs.rs/log/0.4
 //!
 //! # Available Providers
 //!
-//! In addition to the four gated providers above, figment provides the
+//! In addition to the seven gated providers above, figment provides the
 //! following providers out-of-the-box:
 //!
 //! | provider                              | description                            |
 //! |---------------------------------------|----------------------------------------|
 //! | [`providers::Env`]                    | Environment variable [`Provider`].     |
+//! | [`providers::DotEnv`]                 | `.env` file, without touching the env. |
 //! | [`providers::Serialized`]             | Source from any [`Serialize`] type.    |
+//! | [`providers::Directory`]              | Files in a directory as keys/values.   |
+//! | [`providers::Discover`]               | Files found walking up directories.    |
+//! | [`providers::OnFirstUse`]             | A provider created when data is read.  |
+//! | [`providers::Otel`]                   | OpenTelemetry `OTEL_*` env variables.  |
+//! | [`providers::Uri`]                    | `file:`, `env:`, or `data:` URI.       |
+//! | [`providers::Static`]                 | Source from a `static` table.          |
 //! | [`(impl AsRef<str>, impl Serialize)`] | Global source from a `("key", value)`. |
 //! | [`&T` _where_ `T: Provider`]          | Source from `T` as a reference.        |
 //!
@@ -555,6 +582,11 @@
 //! # });
 //! ```
 //!
+//! Alternatively, with the `clap` feature enabled, the
+//! [`CliArgs`](providers::CliArgs) provider emits only the arguments actually
+//! given on the command line, without their defaults, so it can be merged
+//! last to override values from every other source.
+//!
 //! See [For Application Authors](#for-application-authors) for further, general
 //! guidance on using Figment for application configuration.
 //!
@@ -574,6 +606,11 @@
 //!     error-prone.
 //!   * [`Error`] may contain more than one error: iterate over it to retrieve
 //!     all errors.
+//!   * TOML datetimes are [`Value::Datetime`](value::Value::Datetime) values
+//!     but deserialize as strings, like datetimes from every other source, so
+//!     `#[serde(deserialize_with)]` and `#[serde(with)]` functions need not
+//!     special-case TOML. Conversely, `toml` datetime types can be
+//!     deserialized from strings provided by any source.
 //!   * Using `#[serde(flatten)]` [can break error attribution], so it's best to
 //!     avoid using it when possible.
 //!
@@ -584,18 +621,65 @@ pub mod value;
 pub mod providers;
 pub mod error;
 pub mod util;
+pub mod schema;
+#[cfg(feature = "app")]
+#[cfg_attr(nightly, doc(cfg(feature = "app")))]
+pub mod app;
+#[cfg(feature = "watch")]
+#[cfg_attr(nightly, doc(cfg(feature = "watch")))]
+pub mod watch;
+#[cfg(feature = "global")]
+#[cfg_attr(nightly, doc(cfg(feature = "global")))]
+pub mod global;
 mod figment;
 mod profile;
 mod coalesce;
+mod trace;
+mod diff;
+mod typos;
+mod rename;
+mod interpolate;
+mod logging;
+#[cfg(feature = "diagnostics")] mod pretty;
 mod metadata;
+mod monitor;
+mod polymorphic;
+mod plan;
+mod deprecation;
+mod view;
+mod layer;
+mod stats;
+mod export;
 mod provider;
+mod key_path;
+#[cfg(feature = "async")] mod async_provider;
 
 #[cfg(any(test, feature = "test"))] mod jail;
 #[cfg(any(test, feature = "test"))] pub use jail::Jail;
+#[cfg(any(test, feature = "test"))]
+#[cfg_attr(nightly, doc(cfg(feature = "test")))]
+pub mod test;
 
 #[doc(inline)]
 pub use error::{Error, Result};
 pub use self::figment::Figment;
-pub use profile::Profile;
+pub use profile::{Profile, Leveled};
 pub use provider::*;
 pub use metadata::*;
+pub use trace::{MergeEvent, MergeDecision};
+pub use diff::{Change, ChangeKind};
+pub use monitor::{Monitor, CancellationToken};
+pub use polymorphic::Polymorphic;
+pub use plan::Plan;
+pub use deprecation::Deprecation;
+pub use view::FigmentView;
+pub use layer::{LayerInfo, OverrideGuard};
+pub use stats::{Stats, ProfileStats};
+pub use export::ExportOptions;
+pub use coalesce::{Order, ArrayMerge, TypeConflict};
+pub use key_path::{KeyPath, KnownKeys};
+#[cfg(feature = "async")]
+#[cfg_attr(nightly, doc(cfg(feature = "async")))]
+pub use async_provider::AsyncProvider;
+#[doc(hidden)]
+pub use key_path::is_known as __is_known_key_path;
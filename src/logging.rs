@@ -0,0 +1,90 @@
+//! [`log`] records, emitted when the `log` feature is enabled.
+//!
+//! Records use the `figment` target. Combining a provider and extracting are
+//! logged at the `debug` level and each merge decision at the `trace` level.
+//! Records name keys, providers, and profiles but never include values. As
+//! error messages may quote values, errors are counted rather than displayed.
+//! At most [`MAX_DECISIONS`] merge decisions are logged per provider.
+
+use crate::{Figment, Metadata, Profile, MergeEvent};
+use crate::coalesce::Order;
+use crate::error::Result;
+use crate::value::{Map, Dict};
+
+/// The maximum number of merge decisions logged per combined provider.
+#[cfg_attr(not(feature = "log"), allow(dead_code))]
+const MAX_DECISIONS: usize = 64;
+
+/// Logs that the provider with `metadata` was combined with `order` into a
+/// figment, providing `data`.
+pub(crate) fn provided(metadata: &Metadata, order: Order, data: &Result<Map<Profile, Dict>>) {
+    #[cfg(feature = "log")] {
+        match data {
+            Ok(data) => {
+                let profiles = data.keys().map(|p| p.as_str().as_str()).collect::<Vec<_>>();
+                let keys: usize = data.values().map(|dict| dict.len()).sum();
+                log::debug!(target: "figment", "{:?} {}: {} top-level key(s) in profile(s) {:?}",
+                    order, metadata.name, keys, profiles);
+            }
+            Err(e) => log::debug!(target: "figment", "{:?} {}: failed with {} error(s)",
+                order, metadata.name, e.count()),
+        }
+    }
+
+    #[cfg(not(feature = "log"))]
+    let _ = (metadata, order, data);
+}
+
+/// Returns `true` if merge decisions are logged and should be recorded.
+pub(crate) fn decisions_enabled() -> bool {
+    #[cfg(feature = "log")] {
+        log::log_enabled!(target: "figment", log::Level::Trace)
+    }
+
+    #[cfg(not(feature = "log"))] {
+        false
+    }
+}
+
+/// Logs the decisions made when combining a provider into a figment.
+pub(crate) fn decisions<'a, I>(events: I)
+    where I: Iterator<Item = &'a MergeEvent>
+{
+    #[cfg(feature = "log")] {
+        if !decisions_enabled() {
+            return;
+        }
+
+        let mut skipped = 0;
+        for (i, event) in events.enumerate() {
+            match i < MAX_DECISIONS {
+                true => log::trace!(target: "figment", "{:?} `{}` in {}",
+                    event.decision, event.path, event.profile),
+                false => skipped += 1,
+            }
+        }
+
+        if skipped > 0 {
+            log::trace!(target: "figment", "{} more decision(s) not logged", skipped);
+        }
+    }
+
+    #[cfg(not(feature = "log"))]
+    let _ = events;
+}
+
+/// Logs the outcome of extracting the value at `path` from `figment`.
+pub(crate) fn extracted<T>(figment: &Figment, path: &str, result: &Result<T>) {
+    #[cfg(feature = "log")] {
+        let path = if path.is_empty() { "<root>" } else { path };
+        match result {
+            Ok(_) => log::debug!(target: "figment", "extracted `{}` as {} from {}",
+                path, std::any::type_name::<T>(), figment.profile()),
+            Err(e) => log::debug!(target: "figment", "extracting `{}` as {} from {} failed with {} error(s)",
+                path, std::any::type_name::<T>(), figment.profile(), e.count()),
+        }
+    }
+
+    #[cfg(not(feature = "log"))]
+    let _ = (figment, path, result);
+}
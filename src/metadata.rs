@@ -1,7 +1,9 @@
 use std::fmt;
+use std::ops::Range;
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::panic::Location;
+use std::sync::Arc;
 
 use crate::Profile;
 
@@ -17,6 +19,8 @@ use crate::Profile;
 ///   * A default or custom [interpolater](#interpolation).
 ///   * A source [`Location`] where a value's provider was added to the
 ///   containing figment, if it is known.
+///   * The [parent](Metadata::parent()) metadata of the provider wrapped by
+///     the value's provider, if the provider is an adapter.
 ///
 /// This information is used to produce insightful error messages as well as to
 /// generate values like [`RelativePathBuf`] that know about their configuration
@@ -77,7 +81,19 @@ pub struct Metadata {
     /// The source location where this value's provider was added to the
     /// containing figment, if it is known.
     pub provide_location: Option<&'static Location<'static>>,
+    details: Box<Details>,
+}
+
+/// The private parts of a [`Metadata`], boxed to keep `Metadata`, and thus
+/// [`Error`](crate::Error), small.
+#[derive(Debug, Clone)]
+struct Details {
     interpolater: Box<dyn Interpolator>,
+    raw: Option<RawFinder>,
+    span: Option<SpanFinder>,
+    parent: Option<Box<Metadata>>,
+    expanded_env: Vec<String>,
+    checksum: Option<Checksum>,
 }
 
 impl Metadata {
@@ -157,7 +173,7 @@ impl Metadata {
     pub fn interpolater<I: Clone + Send + Sync + 'static>(mut self, f: I) -> Self
         where I: Fn(&Profile, &[&str]) -> String
     {
-        self.interpolater = Box::new(f);
+        self.details.interpolater = Box::new(f);
         self
     }
 
@@ -184,7 +200,261 @@ impl Metadata {
     /// ```
     pub fn interpolate<K: AsRef<str>>(&self, profile: &Profile, keys: &[K]) -> String {
         let keys: Vec<_> = keys.iter().map(|k| k.as_ref()).collect();
-        (self.interpolater)(profile, &keys)
+        (self.details.interpolater)(profile, &keys)
+    }
+
+    /// Sets the parent of `self` to `parent`: the metadata of the provider that
+    /// `self`'s provider wraps, if it is an adapter over another provider.
+    ///
+    /// Adapters, like a provider that renames or decrypts the values of another
+    /// provider, should set the wrapped provider's metadata as the parent of
+    /// their own. The complete chain is then available from the metadata
+    /// attached to values and errors via [`Metadata::parent()`] and
+    /// [`Metadata::ancestors()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Metadata, Profile, Provider, Error};
+    /// use figment::value::{Map, Dict};
+    /// use figment::providers::Env;
+    ///
+    /// struct Decrypted<P>(P);
+    ///
+    /// impl<P: Provider> Provider for Decrypted<P> {
+    ///     fn metadata(&self) -> Metadata {
+    ///         Metadata::named("decrypted value(s)").parent(self.0.metadata())
+    ///     }
+    ///
+    ///     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+    ///         self.0.data() /* decrypt, then return */
+    ///     }
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.set_env("APP_PORT", "not a number");
+    ///
+    ///     let figment = Figment::from(Decrypted(Env::prefixed("APP_")));
+    ///     let error = figment.extract_inner::<u16>("port").unwrap_err();
+    ///
+    ///     let metadata = error.metadata.unwrap();
+    ///     let chain: Vec<_> = metadata.ancestors().map(|m| &*m.name).collect();
+    ///     assert_eq!(chain, ["decrypted value(s)", "`APP_` environment variable(s)"]);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn parent(mut self, parent: Metadata) -> Self {
+        self.details.parent = Some(Box::new(parent));
+        self
+    }
+
+    /// Returns the metadata of the adapter `adapter` over a provider with
+    /// metadata `inner`: `inner`, renamed to note the adapter, with `inner`
+    /// as its parent.
+    pub(crate) fn adapted(inner: Metadata, adapter: &str) -> Self {
+        let mut metadata = inner.clone();
+        metadata.name = format!("{} ({})", inner.name, adapter).into();
+        metadata.parent(inner)
+    }
+
+    /// Returns the parent of `self`, if any. See [`Metadata::parent()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Metadata;
+    ///
+    /// let metadata = Metadata::named("outer").parent(Metadata::named("inner"));
+    /// assert_eq!(metadata.get_parent().unwrap().name, "inner");
+    /// assert!(metadata.get_parent().unwrap().get_parent().is_none());
+    /// ```
+    pub fn get_parent(&self) -> Option<&Metadata> {
+        self.details.parent.as_deref()
+    }
+
+    /// Returns an iterator over `self` followed by its parent, its parent's
+    /// parent, and so on. See [`Metadata::parent()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Metadata;
+    ///
+    /// let metadata = Metadata::named("c")
+    ///     .parent(Metadata::named("b").parent(Metadata::named("a")));
+    ///
+    /// let names: Vec<_> = metadata.ancestors().map(|m| &*m.name).collect();
+    /// assert_eq!(names, ["c", "b", "a"]);
+    /// ```
+    pub fn ancestors(&self) -> impl Iterator<Item = &Metadata> {
+        std::iter::successors(Some(self), |m| m.get_parent())
+    }
+
+    /// Records that the source's values reference the environment variables
+    /// named `vars`, which were expanded into the values, as by
+    /// [`Data::expand_env()`](crate::providers::Data::expand_env()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Metadata;
+    ///
+    /// let metadata = Metadata::named("template").expanded_env(vec!["HOME".into()]);
+    /// assert_eq!(metadata.get_expanded_env(), ["HOME"]);
+    /// assert!(Metadata::named("plain").get_expanded_env().is_empty());
+    /// ```
+    pub fn expanded_env(mut self, vars: Vec<String>) -> Self {
+        self.details.expanded_env = vars;
+        self
+    }
+
+    /// Returns the names of the environment variables expanded into the
+    /// source's values, if any. See [`Metadata::expanded_env()`].
+    pub fn get_expanded_env(&self) -> &[String] {
+        &self.details.expanded_env
+    }
+
+    /// Records `checksum` as the checksum of the source's contents when its
+    /// values were read, or `None` if the source didn't exist, along with
+    /// `recheck`, which computes the checksum of the source's current contents
+    /// in the same way. [`Metadata::source_changed()`] compares the two.
+    ///
+    /// The [`Data`](crate::providers::Data) and
+    /// [`Http`](crate::providers::Http) providers record checksums when
+    /// enabled via `checksum(true)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use figment::Metadata;
+    ///
+    /// let version = Arc::new(AtomicU64::new(1));
+    /// let current = version.clone();
+    /// let metadata = Metadata::named("versioned store")
+    ///     .checksum(Some(1), move || Some(current.load(Ordering::Relaxed)));
+    ///
+    /// assert_eq!(metadata.get_checksum(), Some(1));
+    /// assert!(!metadata.source_changed());
+    ///
+    /// version.store(2, Ordering::Relaxed);
+    /// assert!(metadata.source_changed());
+    /// ```
+    pub fn checksum<F>(mut self, checksum: Option<u64>, recheck: F) -> Self
+        where F: Fn() -> Option<u64> + Send + Sync + 'static
+    {
+        self.details.checksum = Some(Checksum { value: checksum, recheck: Arc::new(recheck) });
+        self
+    }
+
+    /// Returns the checksum recorded via [`Metadata::checksum()`], if any.
+    pub fn get_checksum(&self) -> Option<u64> {
+        self.details.checksum.as_ref().and_then(|checksum| checksum.value)
+    }
+
+    /// Returns `true` if a checksum was recorded via [`Metadata::checksum()`]
+    /// and the checksum of the source's current contents differs from it.
+    /// Returns `false` if no checksum was recorded.
+    ///
+    /// See [`Metadata::checksum()`] for an example.
+    pub fn source_changed(&self) -> bool {
+        match &self.details.checksum {
+            Some(checksum) => (checksum.recheck)() != checksum.value,
+            None => false,
+        }
+    }
+
+    /// Sets the raw finder of `self` to the function `f`. The raw finder can be
+    /// invoked via [`Metadata::find_raw()`].
+    ///
+    /// A raw finder takes a figment profile and key path and returns the
+    /// source's original, unparsed text for the value at that path along with
+    /// the name of the source's format, or `None` if the text is unavailable.
+    /// The [`Data`](crate::providers::Data) provider sets a raw finder for
+    /// formats that support it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Metadata, Profile};
+    ///
+    /// let metadata = Metadata::named("INI file")
+    ///     .raw_finder(|_, keys| match keys {
+    ///         ["app", "name"] => Some(("  my app ".into(), "INI")),
+    ///         _ => None,
+    ///     });
+    ///
+    /// let raw = metadata.find_raw(&Profile::Default, &["app", "name"]);
+    /// assert_eq!(raw, Some(("  my app ".into(), "INI")));
+    /// assert_eq!(metadata.find_raw(&Profile::Default, &["app"]), None);
+    /// ```
+    pub fn raw_finder<F>(mut self, f: F) -> Self
+        where F: Fn(&Profile, &[&str]) -> Option<(String, &'static str)> + Send + Sync + 'static
+    {
+        self.details.raw = Some(RawFinder(Arc::new(f)));
+        self
+    }
+
+    /// Runs the raw finder in `self`, if any, on `profile` and `keys`. Returns
+    /// `None` if `self` has no raw finder or the raw finder returns `None`.
+    ///
+    /// See [`Metadata::raw_finder()`] for an example.
+    pub fn find_raw<K: AsRef<str>>(
+        &self,
+        profile: &Profile,
+        keys: &[K]
+    ) -> Option<(String, &'static str)> {
+        let keys: Vec<_> = keys.iter().map(|k| k.as_ref()).collect();
+        (self.details.raw.as_ref()?.0)(profile, &keys)
+    }
+
+    /// Sets the span finder of `self` to the function `f`. The span finder can
+    /// be invoked via [`Metadata::find_span()`].
+    ///
+    /// A span finder takes a figment profile and key path and returns the
+    /// source's full text along with the byte range in it of the value at
+    /// that path, or `None` if either is unavailable. The
+    /// [`Data`](crate::providers::Data) provider sets a span finder for
+    /// formats that support it. Spans are used to show the offending source
+    /// line in [pretty errors](crate::Error::pretty()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Metadata, Profile};
+    ///
+    /// let source = "[app]\nname = my app\n";
+    /// let metadata = Metadata::named("INI file")
+    ///     .span_finder(move |_, keys| match keys {
+    ///         ["app", "name"] => Some((source.into(), 13..19)),
+    ///         _ => None,
+    ///     });
+    ///
+    /// let (text, span) = metadata.find_span(&Profile::Default, &["app", "name"]).unwrap();
+    /// assert_eq!(&text[span], "my app");
+    /// assert!(metadata.find_span(&Profile::Default, &["app"]).is_none());
+    /// ```
+    pub fn span_finder<F>(mut self, f: F) -> Self
+        where F: Fn(&Profile, &[&str]) -> Option<(String, Range<usize>)> + Send + Sync + 'static
+    {
+        self.details.span = Some(SpanFinder(Arc::new(f)));
+        self
+    }
+
+    /// Runs the span finder in `self`, if any, on `profile` and `keys`.
+    /// Returns `None` if `self` has no span finder or the span finder returns
+    /// `None`.
+    ///
+    /// See [`Metadata::span_finder()`] for an example.
+    pub fn find_span<K: AsRef<str>>(
+        &self,
+        profile: &Profile,
+        keys: &[K]
+    ) -> Option<(String, Range<usize>)> {
+        let keys: Vec<_> = keys.iter().map(|k| k.as_ref()).collect();
+        (self.details.span.as_ref()?.0)(profile, &keys)
     }
 }
 
@@ -200,7 +470,14 @@ impl Default for Metadata {
             name: "Default".into(),
             source: None,
             provide_location: None,
-            interpolater: Box::new(default_interpolater),
+            details: Box::new(Details {
+                interpolater: Box::new(default_interpolater),
+                raw: None,
+                span: None,
+                parent: None,
+                expanded_env: vec![],
+                checksum: None,
+            }),
         }
     }
 }
@@ -225,6 +502,10 @@ pub enum Source {
     Code(&'static Location<'static>),
     /// A custom source all-together.
     Custom(String),
+    /// A file compiled into the binary: the path to the file at compile time.
+    Embedded(PathBuf),
+    /// A remote or otherwise addressable resource: the URL of the resource.
+    Url(String),
 }
 
 impl Source {
@@ -281,11 +562,53 @@ impl Source {
             _ => None,
         }
     }
+
+    /// Returns the compile-time path to the embedded source file if `self` is
+    /// `Source::Embedded`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use figment::Source;
+    ///
+    /// let source = Source::Embedded("defaults.toml".into());
+    /// assert_eq!(source.embedded_path(), Some(Path::new("defaults.toml")));
+    /// assert_eq!(source.file_path(), None);
+    /// assert_eq!(source.to_string(), "defaults.toml (compiled-in)");
+    /// ```
+    pub fn embedded_path(&self) -> Option<&Path> {
+        match self {
+            Source::Embedded(ref p) => Some(p),
+            _ => None,
+        }
+    }
+
+    /// Returns the URL of the source if `self` is `Source::Url`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Source;
+    ///
+    /// let source = Source::Url("https://config.example.com/app".into());
+    /// assert_eq!(source.url(), Some("https://config.example.com/app"));
+    /// assert_eq!(source.custom(), None);
+    /// assert_eq!(source.to_string(), "https://config.example.com/app");
+    /// ```
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            Source::Url(ref u) => Some(u),
+            _ => None,
+        }
+    }
 }
 
-/// Displays the source. Location and custom sources are displayed directly.
+/// Displays the source. Location, custom, and URL sources are displayed
+/// directly.
 /// File paths are displayed relative to the current working directory if the
-/// relative path is shorter than the complete path.
+/// relative path is shorter than the complete path. Embedded file paths are
+/// displayed as is, followed by `(compiled-in)`.
 impl fmt::Display for Source {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -299,6 +622,8 @@ impl fmt::Display for Source {
             }
             Source::Code(l) => l.fmt(f),
             Source::Custom(c) => c.fmt(f),
+            Source::Embedded(p) => write!(f, "{} (compiled-in)", p.display()),
+            Source::Url(u) => u.fmt(f),
         }
     }
 }
@@ -331,6 +656,43 @@ crate::util::cloneable_fn_trait!(
     Interpolator: Fn(&Profile, &[&str]) -> String + Send + Sync + 'static
 );
 
+type RawFn = dyn Fn(&Profile, &[&str]) -> Option<(String, &'static str)> + Send + Sync;
+
+#[derive(Clone)]
+struct RawFinder(Arc<RawFn>);
+
+impl fmt::Debug for RawFinder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RawFinder")
+    }
+}
+
+type SpanFn = dyn Fn(&Profile, &[&str]) -> Option<(String, Range<usize>)> + Send + Sync;
+
+#[derive(Clone)]
+struct SpanFinder(Arc<SpanFn>);
+
+impl fmt::Debug for SpanFinder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SpanFinder")
+    }
+}
+
+type RecheckFn = dyn Fn() -> Option<u64> + Send + Sync;
+
+/// A recorded checksum and the function that recomputes it.
+#[derive(Clone)]
+struct Checksum {
+    value: Option<u64>,
+    recheck: Arc<RecheckFn>,
+}
+
+impl fmt::Debug for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Checksum").field(&self.value).finish()
+    }
+}
+
 fn default_interpolater(profile: &Profile, keys: &[&str]) -> String {
     format!("{}.{}", profile, keys.join("."))
 }
@@ -0,0 +1,174 @@
+use std::fmt;
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::Error;
+
+/// A shareable flag that requests that an extraction be cancelled.
+///
+/// Cloning a token produces a handle to the same flag: calling
+/// [`CancellationToken::cancel()`] on any clone, from any thread, cancels
+/// every [`Monitor`] the token was given to. A cancelled token stays
+/// cancelled.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let handle = token.clone();
+/// assert!(!token.is_cancelled());
+///
+/// std::thread::spawn(move || handle.cancel()).join().unwrap();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Returns a new, uncancelled token.
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Requests cancellation.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Observes and optionally cancels an extraction via
+/// [`Figment::extract_monitored()`] or
+/// [`Figment::extract_inner_monitored()`].
+///
+/// Every dictionary entry and array element that is deserialized counts as a
+/// processed key. Every [`interval`](Monitor::interval()) keys, the monitor
+/// checks its [`CancellationToken`], if any, failing the extraction with an
+/// error for which [`Error::cancelled()`] is `true` if cancellation was
+/// requested, and then calls its progress callback, if any, with the number of
+/// keys processed so far. Cancellation is also checked before deserialization begins. Merging
+/// providers, which precedes deserialization, is not interrupted.
+///
+/// [`Figment::extract_monitored()`]: crate::Figment::extract_monitored()
+/// [`Figment::extract_inner_monitored()`]: crate::Figment::extract_inner_monitored()
+///
+/// # Example
+///
+/// ```rust
+/// use std::cell::Cell;
+/// use figment::{Figment, Monitor, CancellationToken};
+///
+/// let figment = Figment::from(("numbers", (0..1000).collect::<Vec<u32>>()));
+///
+/// let reports = Cell::new(0);
+/// let monitor = Monitor::new()
+///     .interval(100)
+///     .progress(|keys| reports.set(keys));
+///
+/// let numbers: Vec<u32> = figment.extract_inner_monitored("numbers", &monitor).unwrap();
+/// assert_eq!(numbers.len(), 1000);
+/// assert_eq!(monitor.keys(), 1000);
+/// assert_eq!(reports.get(), 1000);
+///
+/// let token = CancellationToken::new();
+/// let monitor = Monitor::new()
+///     .cancellation(token.clone())
+///     .progress(|keys| if keys == 10 { token.cancel() });
+///
+/// let error = figment.extract_inner_monitored::<Vec<u32>>("numbers", &monitor).unwrap_err();
+/// assert!(error.cancelled());
+/// assert_eq!(monitor.keys(), 11);
+/// ```
+pub struct Monitor<'a> {
+    token: Option<CancellationToken>,
+    progress: Option<Box<dyn Fn(usize) + 'a>>,
+    interval: usize,
+    keys: Cell<usize>,
+    pending: Cell<usize>,
+}
+
+impl<'a> Monitor<'a> {
+    /// Returns a monitor with no cancellation token, no progress callback,
+    /// and an interval of `1`.
+    pub fn new() -> Self {
+        Monitor { token: None, progress: None, interval: 1, keys: Cell::new(0), pending: Cell::new(0) }
+    }
+
+    /// Sets the token checked for cancellation.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Sets the callback invoked with the number of keys processed so far.
+    pub fn progress<F>(mut self, callback: F) -> Self
+        where F: Fn(usize) + 'a
+    {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the number of keys processed between checks for cancellation and
+    /// progress reports. An `interval` of `0` is treated as `1`.
+    pub fn interval(mut self, interval: usize) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// Returns the number of keys processed so far. The count is not reset
+    /// between extractions.
+    pub fn keys(&self) -> usize {
+        self.keys.get()
+    }
+
+    /// Fails if cancellation has been requested.
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        match &self.token {
+            Some(token) if token.is_cancelled() => Err(Error::cancellation()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records one processed key, checking for cancellation and reporting
+    /// progress if an interval has elapsed.
+    pub(crate) fn tick(&self) -> Result<(), Error> {
+        let keys = self.keys.get() + 1;
+        self.keys.set(keys);
+        self.pending.set(self.pending.get() + 1);
+        if self.pending.get() < self.interval {
+            return Ok(());
+        }
+
+        self.pending.set(0);
+        self.check()?;
+        if let Some(progress) = &self.progress {
+            progress(keys);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Monitor<'_> {
+    fn default() -> Self {
+        Monitor::new()
+    }
+}
+
+impl fmt::Debug for Monitor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Monitor")
+            .field("token", &self.token)
+            .field("progress", &self.progress.is_some())
+            .field("interval", &self.interval)
+            .field("keys", &self.keys.get())
+            .finish()
+    }
+}
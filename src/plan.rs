@@ -0,0 +1,149 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+
+use crate::Figment;
+use crate::error::Result;
+use crate::schema::Schema;
+use crate::value::{Value, ConfiguredValueDe, DefaultInterpreter};
+
+/// A precompiled plan for repeatedly extracting a `T` from a figment.
+///
+/// Created via [`Figment::plan()`] or [`Figment::plan_inner()`]. Extracting
+/// via [`Figment::extract()`] merges the selected profile with its parents,
+/// the default profile, and the global profile, looks up the requested key
+/// path, clones the result, and, when an [unknown key
+/// policy](Figment::unknown_keys()) is set, derives the [`Schema`] of `T` by
+/// observing how `T` deserializes itself. A plan does all of this once:
+/// [`Plan::extract()`] deserializes `T` directly from the resolved value.
+///
+/// When the configuration is reloaded, [`Plan::reload()`] points the plan at
+/// the new figment, resolving its value again but reusing the schema of `T`.
+/// This makes plans suitable for hot paths, like frameworks that extract the
+/// same configuration on every request or after every reload.
+///
+/// Extracting via a plan is otherwise identical to extracting via the figment
+/// the plan was created from, including the errors produced.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use figment::{Figment, providers::{Format, Toml}, schema::UnknownPolicy};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// figment::Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", r#"
+///         [default]
+///         name = "app"
+///         port = 8000
+///
+///         [prod]
+///         port = 443
+///     "#)?;
+///
+///     let figment = || Figment::from(Toml::file("App.toml").nested())
+///         .unknown_keys(UnknownPolicy::DenyAll)
+///         .select("prod");
+///
+///     let mut plan = figment().plan::<Config>()?;
+///     assert_eq!(plan.extract()?, Config { name: "app".into(), port: 443 });
+///
+///     jail.create_file("App.toml", r#"
+///         [default]
+///         name = "app"
+///         port = 8000
+///
+///         [prod]
+///         port = 8443
+///         potr = 1
+///     "#)?;
+///
+///     plan.reload(&figment())?;
+///     let error = plan.extract().unwrap_err();
+///     assert_eq!(error.path, ["potr"]);
+///     assert_eq!(error.suggestion(), Some("port"));
+///
+///     Ok(())
+/// });
+/// ```
+pub struct Plan<T> {
+    figment: Figment,
+    path: String,
+    value: Value,
+    schema: Schema,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Plan<T> {
+    pub(crate) fn new(figment: &Figment, path: &str) -> Result<Self> {
+        let value = figment.resolve(path)?;
+        let schema = Schema::of::<T>();
+        Ok(Plan { figment: figment.clone(), path: path.into(), value, schema, _type: PhantomData })
+    }
+
+    /// Extracts a `T` from the value resolved when the plan was created or
+    /// last reloaded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// let figment = Figment::from(Serialized::default("server.port", 8080));
+    /// let plan = figment.plan_inner::<u16>("server.port").unwrap();
+    /// assert_eq!(plan.extract().unwrap(), 8080);
+    /// assert_eq!(plan.extract().unwrap(), 8080);
+    /// ```
+    pub fn extract(&self) -> Result<T> {
+        let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(&self.figment, &self.value);
+        let result = T::deserialize(de).map_err(|e| e.with_path(&self.path));
+        self.figment.check_unknown_with(&self.path, &self.value, result, Some(&self.schema))
+    }
+
+    /// Points `self` at `figment`, typically a figment rebuilt after the
+    /// configuration changed, resolving the value to extract from `figment`
+    /// and reusing everything that depends only on `T`. On error, `self` is
+    /// left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// let figment = Figment::from(Serialized::default("port", 8080));
+    /// let mut plan = figment.plan_inner::<u16>("port").unwrap();
+    ///
+    /// let reloaded = figment.merge(Serialized::default("port", 9090));
+    /// plan.reload(&reloaded).unwrap();
+    /// assert_eq!(plan.extract().unwrap(), 9090);
+    ///
+    /// assert!(plan.reload(&Figment::new()).is_err());
+    /// assert_eq!(plan.extract().unwrap(), 9090);
+    /// ```
+    pub fn reload(&mut self, figment: &Figment) -> Result<()> {
+        self.value = figment.resolve(&self.path)?;
+        self.figment = figment.clone();
+        Ok(())
+    }
+
+    /// Returns the figment `self` extracts from.
+    pub fn figment(&self) -> &Figment {
+        &self.figment
+    }
+}
+
+impl<T> fmt::Debug for Plan<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Plan")
+            .field("type", &std::any::type_name::<T>())
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
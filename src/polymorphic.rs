@@ -0,0 +1,198 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+
+use crate::Figment;
+use crate::error::{Error, Kind, Result};
+use crate::value::Value;
+
+/// Extracts a value that may take a different shape depending on the source
+/// or selected profile via [`Figment::extract_inner_polymorphic()`].
+///
+/// A `Polymorphic<T>` maps each accepted _shape_ of a value (a boolean, a
+/// number, a string, an array, or a dictionary) to a type to deserialize the
+/// value as and a function that converts the deserialized value into a `T`,
+/// typically a variant of an enum. On extraction, the shape of the value at the
+/// requested key path selects the arm to use.
+///
+/// Unlike an `#[serde(untagged)]` enum, which attempts every variant and
+/// reports that none matched, only the arm for the value's shape is attempted.
+/// As a result, errors are precise: a value with an unaccepted shape is
+/// reported as an invalid type listing the accepted shapes, and an error
+/// deserializing the value in the selected arm is reported as-is. Both point
+/// to the profile and provider that supplied the value.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use figment::{Figment, Polymorphic, providers::{Format, Toml}};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Remote {
+///     url: String,
+///     timeout: u32,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Endpoint {
+///     Url(String),
+///     Remote(Remote),
+/// }
+///
+/// figment::Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", r#"
+///         [dev]
+///         endpoint = "http://localhost"
+///
+///         [prod]
+///         endpoint = { url = "https://example.com", timeout = 30 }
+///
+///         [test]
+///         endpoint = 10
+///     "#)?;
+///
+///     let endpoint = Polymorphic::new()
+///         .string(Endpoint::Url)
+///         .dict(Endpoint::Remote);
+///
+///     let figment = Figment::from(Toml::file("App.toml").nested());
+///
+///     let dev = figment.clone().select("dev");
+///     let value = dev.extract_inner_polymorphic("endpoint", &endpoint)?;
+///     assert_eq!(value, Endpoint::Url("http://localhost".into()));
+///
+///     let prod = figment.clone().select("prod");
+///     let value = prod.extract_inner_polymorphic("endpoint", &endpoint)?;
+///     assert_eq!(value, Endpoint::Remote(Remote {
+///         url: "https://example.com".into(),
+///         timeout: 30,
+///     }));
+///
+///     let test = figment.select("test");
+///     let error = test.extract_inner_polymorphic("endpoint", &endpoint).unwrap_err();
+///     assert_eq!(error.profile.as_ref().unwrap(), "test");
+///     assert_eq!(error.path, vec!["endpoint"]);
+///     assert!(error.to_string().contains("expected a string or a dictionary"));
+///
+///     Ok(())
+/// });
+/// ```
+pub struct Polymorphic<T> {
+    arms: Vec<(Shape, Box<Arm<T>>)>,
+}
+
+type Arm<T> = dyn Fn(&Figment, &str) -> Result<T> + Send + Sync;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Bool,
+    Num,
+    String,
+    Array,
+    Dict,
+}
+
+impl Shape {
+    fn of(value: &Value) -> Option<Shape> {
+        match value {
+            Value::Bool(..) => Some(Shape::Bool),
+            Value::Num(..) => Some(Shape::Num),
+            Value::String(..) | Value::Char(..) | Value::Datetime(..) => Some(Shape::String),
+            Value::Array(..) => Some(Shape::Array),
+            Value::Dict(..) => Some(Shape::Dict),
+            Value::Empty(..) => None,
+        }
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shape::Bool => "a boolean".fmt(f),
+            Shape::Num => "a number".fmt(f),
+            Shape::String => "a string".fmt(f),
+            Shape::Array => "an array".fmt(f),
+            Shape::Dict => "a dictionary".fmt(f),
+        }
+    }
+}
+
+macro_rules! arm_fn {
+    ($name:ident, $shape:ident, $what:literal) => {
+        #[doc = concat!("Accepts ", $what, ", deserialized as a `V` and")]
+        /// converted via `f`. Replaces any previous arm for the same shape.
+        pub fn $name<V, F>(self, f: F) -> Self
+            where V: DeserializeOwned, F: Fn(V) -> T + Send + Sync + 'static
+        {
+            self.arm(Shape::$shape, f)
+        }
+    };
+}
+
+impl<T> Polymorphic<T> {
+    /// Returns a `Polymorphic` that accepts no shapes.
+    pub fn new() -> Self {
+        Polymorphic { arms: vec![] }
+    }
+
+    arm_fn!(bool, Bool, "a boolean");
+    arm_fn!(num, Num, "a number");
+    arm_fn!(string, String, "a string or character");
+    arm_fn!(array, Array, "an array");
+    arm_fn!(dict, Dict, "a dictionary");
+
+    fn arm<V, F>(mut self, shape: Shape, f: F) -> Self
+        where V: DeserializeOwned, F: Fn(V) -> T + Send + Sync + 'static
+    {
+        self.arms.retain(|(s, _)| *s != shape);
+        self.arms.push((shape, Box::new(move |figment, path| {
+            figment.extract_inner::<V>(path).map(&f)
+        })));
+
+        self
+    }
+
+    pub(crate) fn extract(&self, figment: &Figment, path: &str, value: &Value) -> Result<T> {
+        let shape = Shape::of(value);
+        if let Some((_, arm)) = self.arms.iter().find(|(s, _)| Some(*s) == shape) {
+            return arm(figment, path);
+        }
+
+        let expected = match self.arms.len() {
+            0 => "nothing".to_string(),
+            n => {
+                let mut expected = String::new();
+                for (i, (shape, _)) in self.arms.iter().enumerate() {
+                    match i {
+                        0 => {},
+                        i if i + 1 == n => expected.push_str(" or "),
+                        _ => expected.push_str(", "),
+                    }
+
+                    expected.push_str(&shape.to_string());
+                }
+
+                expected
+            }
+        };
+
+        Err(Error::from(Kind::InvalidType(value.to_actual(), expected))
+            .retagged(value.tag())
+            .with_path(path)
+            .resolved(figment))
+    }
+}
+
+impl<T> Default for Polymorphic<T> {
+    fn default() -> Self {
+        Polymorphic::new()
+    }
+}
+
+impl<T> fmt::Debug for Polymorphic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shapes: Vec<_> = self.arms.iter().map(|(s, _)| s).collect();
+        f.debug_struct("Polymorphic").field("shapes", &shapes).finish()
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::Error;
+use crate::error::Kind;
+
+/// A rendering of an [`Error`] resembling a compiler diagnostic.
+///
+/// Created via [`Error::pretty()`]. Each error in the chain is rendered as a
+/// block headed by the error message. When the value that errored was read
+/// from a source that records spans, like a TOML or JSON file, the block shows
+/// the offending line of the source with the value underlined, labeled with
+/// the key path. Otherwise the block names the source and key. The profile
+/// the value came from and the selected profile follow as a note.
+///
+/// ```text
+/// error: invalid type: found string "eighty", expected u16
+///  --> Config.toml:3:8
+///   |
+/// 3 | port = "eighty"
+///   |        ^^^^^^^^ for key `port`
+///   |
+///   = note: profile `default`
+/// ```
+pub struct Pretty<'e>(&'e Error);
+
+impl Error {
+    /// Returns a [`Display`](fmt::Display)able rendering of `self` that shows
+    /// the offending source line, where known, for every error in the chain.
+    /// See [`Pretty`] for details.
+    ///
+    /// Spans are recorded by the [`Data`](crate::providers::Data) provider for
+    /// formats that support them, currently TOML and JSON, and by providers
+    /// that set a [span finder](crate::Metadata::span_finder()). Files are
+    /// read again when rendering, so the rendering reflects the current
+    /// contents of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, providers::{Format, Toml, Env}};
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", "name = \"app\"\nport = \"eighty\"\n")?;
+    ///
+    ///     let figment = Figment::from(Toml::file("Config.toml"));
+    ///     let error = figment.extract::<Config>().unwrap_err();
+    ///     let pretty = error.pretty().to_string();
+    ///     assert!(pretty.contains("2 | port = \"eighty\"\n"));
+    ///     assert!(pretty.contains("  |        ^^^^^^^^ for key `port`\n"));
+    ///
+    ///     jail.set_env("APP_NAME", 5);
+    ///     let figment = figment.merge(Env::prefixed("APP_").only(&["name"]));
+    ///     let error = figment.extract::<Config>().unwrap_err();
+    ///     assert!(error.pretty().to_string().contains("= note: key `NAME`, profile `default`"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn pretty(&self) -> Pretty<'_> {
+        Pretty(self)
+    }
+}
+
+impl fmt::Display for Pretty<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.clone().into_iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n\n")?;
+            }
+
+            render(&error, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Pretty<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Pretty").field(self.0).finish()
+    }
+}
+
+struct Headline<'a>(&'a Error);
+
+impl fmt::Display for Headline<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_headline(f)
+    }
+}
+
+struct Profiles<'a>(&'a Error);
+
+impl fmt::Display for Profiles<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_profiles(f)
+    }
+}
+
+/// Renders the single error `error`, ignoring the rest of its chain.
+fn render(error: &Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "error: {}", Headline(error))?;
+
+    let profile = error.profile.as_ref().or(error.selected());
+    let key = match (&error.metadata, profile) {
+        _ if error.path.is_empty() => None,
+        (Some(md), Some(profile)) if md.source.is_none() => {
+            Some(md.interpolate(profile, &error.path))
+        }
+        _ => Some(error.path.join(".")),
+    };
+
+    let span = match (&error.metadata, profile) {
+        _ if error.path.is_empty() => None,
+        (Some(md), Some(profile)) => md.find_span(profile, &error.path),
+        _ => None,
+    };
+
+    let source = error.metadata.as_ref().map(|md| match &md.source {
+        Some(source) => source.to_string(),
+        None => md.name.to_string(),
+    });
+
+    let mut notes = vec![];
+    let gutter = match (span, source, &key) {
+        (Some((text, span)), Some(source), Some(key)) if span.start <= text.len() => {
+            let start = span.start;
+            let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = text[start..].find('\n').map_or(text.len(), |i| start + i);
+            let line = text[line_start..line_end].trim_end_matches('\r');
+            let number = text[..start].matches('\n').count() + 1;
+            let column = text[line_start..start].chars().count() + 1;
+            let end = span.end.clamp(start, line_start + line.len());
+            let width = text[start..end].chars().count().max(1);
+
+            // Pad with tabs where the line has tabs so the carets line up.
+            let padding: String = text[line_start..start].chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+
+            let label = match &error.kind {
+                Kind::MissingField(_) => format!("in `{}`", key),
+                _ => format!("for key `{}`", key),
+            };
+
+            let gutter = " ".repeat(number.to_string().len());
+            write!(f, "\n{}--> {}:{}:{}", gutter, source, number, column)?;
+            write!(f, "\n{} |", gutter)?;
+            write!(f, "\n{} | {}", number, line)?;
+            write!(f, "\n{} | {}{} {}", gutter, padding, "^".repeat(width), label)?;
+            gutter
+        }
+        (_, source, _) => {
+            if let Some(source) = source {
+                write!(f, "\n --> {}", source)?;
+            }
+
+            if let Some(key) = &key {
+                notes.push(format!("key `{}`", key));
+            }
+
+            String::new()
+        }
+    };
+
+    if profile.is_some() {
+        notes.push(Profiles(error).to_string());
+    }
+
+    if !notes.is_empty() {
+        write!(f, "\n{} |", gutter)?;
+        write!(f, "\n{} = note: {}", gutter, notes.join(", "))?;
+    }
+
+    Ok(())
+}
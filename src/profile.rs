@@ -6,8 +6,8 @@ use crate::value::{Dict, Map};
 /// A configuration profile: effectively a case-insensitive string.
 ///
 /// See [the top-level docs](crate#extracting-and-profiles) for details.
-#[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
-pub struct Profile(Uncased<'static>);
+#[derive(Clone)]
+pub struct Profile(Uncased<'static>, Option<usize>);
 
 impl Default for Profile {
     fn default() -> Self {
@@ -77,7 +77,7 @@ impl Profile {
     /// assert_eq!(profile, "STAGING");
     /// ```
     pub fn new(name: &str) -> Profile {
-        Profile(name.to_string().into())
+        Profile(name.to_string().into(), None)
     }
 
     /// A `const` to construct a profile with the name `name`.
@@ -93,7 +93,7 @@ impl Profile {
     /// assert_eq!(STAGING, "STAGING");
     /// ```
     pub const fn const_new(name: &'static str) -> Profile {
-        Profile(Uncased::from_borrowed(name))
+        Profile(Uncased::from_borrowed(name), None)
     }
 
     /// Constructs a profile from the value of the environment variable with
@@ -209,6 +209,148 @@ impl Profile {
         map.insert(self.clone(), dict);
         map
     }
+
+    /// Constructs a parameterized profile named `name:value`, for instance
+    /// `tenant:acme`.
+    ///
+    /// Only profiles constructed via this method are parameterized: a profile
+    /// constructed from a string, like `Profile::new("tenant:acme")`, is a
+    /// plain custom profile, even if it contains a `:`. The two are otherwise
+    /// equal, so data emitted to either is emitted to both.
+    ///
+    /// When a parameterized profile is selected via [`Figment::select()`], the
+    /// dictionary at the key path `name.value` in the figment's data, if any,
+    /// is merged as an overlay over the rest of the data. As with all custom
+    /// profiles, data emitted directly to the `name:value` profile is merged as
+    /// well. [`Figment::param_values()`] enumerates the available values for a
+    /// parameter.
+    ///
+    /// [`Figment::select()`]: crate::Figment::select()
+    /// [`Figment::param_values()`]: crate::Figment::param_values()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Profile, providers::{Format, Toml}};
+    ///
+    /// let profile = Profile::param("tenant", "acme");
+    /// assert_eq!(profile, "tenant:acme");
+    /// assert_eq!(profile.param_parts(), Some(("tenant", "acme")));
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"
+    ///         name = "shared"
+    ///         workers = 4
+    ///
+    ///         [tenant.acme]
+    ///         name = "ACME Corp."
+    ///
+    ///         [tenant.initech]
+    ///         workers = 16
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("Config.toml"));
+    ///     let acme = figment.clone().select(Profile::param("tenant", "acme"));
+    ///     assert_eq!(acme.extract_inner::<String>("name")?, "ACME Corp.");
+    ///     assert_eq!(acme.extract_inner::<u8>("workers")?, 4);
+    ///
+    ///     let initech = figment.select(Profile::param("tenant", "initech"));
+    ///     assert_eq!(initech.extract_inner::<String>("name")?, "shared");
+    ///     assert_eq!(initech.extract_inner::<u8>("workers")?, 16);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn param(name: &str, value: &str) -> Profile {
+        Profile(format!("{}:{}", name, value).into(), Some(name.len()))
+    }
+
+    /// Returns the parameter name and value of `self` if `self` was
+    /// constructed by [`Profile::param()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Profile;
+    ///
+    /// assert_eq!(Profile::param("region", "eu").param_parts(), Some(("region", "eu")));
+    /// assert_eq!(Profile::param("a:b", "c").param_parts(), Some(("a:b", "c")));
+    /// assert_eq!(Profile::new("region:eu").param_parts(), None);
+    /// assert_eq!(Profile::new("debug").param_parts(), None);
+    /// ```
+    pub fn param_parts(&self) -> Option<(&str, &str)> {
+        let name = self.as_str().as_str();
+        self.1.map(|len| (&name[..len], &name[len + 1..]))
+    }
+
+    /// Creates an ordered list of cumulative profiles, or _levels_, from
+    /// `levels`, lowest level first.
+    ///
+    /// Selecting a level via [`Figment::select_level()`] selects the level's
+    /// profile and additionally inherits the data of every lower level in
+    /// order, with higher levels taking precedence. This is useful for tiers
+    /// that build on one another such as verbosity or strictness levels.
+    ///
+    /// [`Figment::select_level()`]: crate::Figment::select_level()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Profile;
+    ///
+    /// let levels = Profile::leveled(&["base", "hardened", "paranoid"]);
+    /// assert_eq!(levels.levels(), &["base", "hardened", "paranoid"]);
+    /// assert_eq!(levels[1], "hardened");
+    /// assert_eq!(levels.position(&"PARANOID".into()), Some(2));
+    /// assert_eq!(levels.position(&"other".into()), None);
+    /// ```
+    pub fn leveled<S: AsRef<str>>(levels: &[S]) -> Leveled {
+        Leveled(levels.iter().map(|l| Profile::new(l.as_ref())).collect())
+    }
+}
+
+/// An ordered list of cumulative profiles created via [`Profile::leveled()`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Leveled(Vec<Profile>);
+
+impl Leveled {
+    /// Returns the levels in `self`, lowest level first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Profile;
+    ///
+    /// let levels = Profile::leveled(&["info", "debug", "trace"]);
+    /// assert_eq!(levels.levels()[0], "info");
+    /// assert_eq!(levels.levels().len(), 3);
+    /// ```
+    pub fn levels(&self) -> &[Profile] {
+        &self.0
+    }
+
+    /// Returns the index of the level `profile` in `self`, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::Profile;
+    ///
+    /// let levels = Profile::leveled(&["info", "debug", "trace"]);
+    /// assert_eq!(levels.position(&"debug".into()), Some(1));
+    /// assert_eq!(levels.position(&Profile::Default), None);
+    /// ```
+    pub fn position(&self, profile: &Profile) -> Option<usize> {
+        self.0.iter().position(|level| level == profile)
+    }
+}
+
+impl std::ops::Index<usize> for Leveled {
+    type Output = Profile;
+
+    fn index(&self, index: usize) -> &Profile {
+        &self.0[index]
+    }
 }
 
 impl<T: AsRef<str>> From<T> for Profile {
@@ -231,6 +373,38 @@ impl std::ops::Deref for Profile {
     }
 }
 
+impl std::fmt::Debug for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Profile").field(&self.0).finish()
+    }
+}
+
+impl PartialEq for Profile {
+    fn eq(&self, other: &Profile) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Profile {}
+
+impl std::hash::Hash for Profile {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl PartialOrd for Profile {
+    fn partial_cmp(&self, other: &Profile) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Profile {
+    fn cmp(&self, other: &Profile) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
 impl std::fmt::Display for Profile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.as_str().fmt(f)
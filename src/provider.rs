@@ -1,5 +1,6 @@
 use crate::{Profile, Error, Metadata};
-use crate::value::{Tag, Map, Dict};
+use crate::providers::{When, Platform, MapProfile};
+use crate::value::{Tag, Map, Dict, Value};
 
 /// Trait implemented by configuration source providers.
 ///
@@ -14,6 +15,15 @@ use crate::value::{Tag, Map, Dict};
 /// A provider may also optionally set a `Profile` for the `Figment` it is
 /// merged (but not joined) into by implementing [`Provider::profile()`].
 ///
+/// # Call Order
+///
+/// When a provider is combined into a [`Figment`], [`Provider::data()`] is
+/// called first, followed by [`Provider::profile()`] and then
+/// [`Provider::metadata()`], each exactly once. A provider may thus rely on
+/// `data()` having been called to describe the data it read, as the `Http`
+/// provider does with its checksum, or to create state that `profile()` and
+/// `metadata()` consult, as [`OnFirstUse`](crate::providers::OnFirstUse) does.
+///
 /// # Nesting
 ///
 /// A [`Provider`] meant to be consumed externally should allow for optional
@@ -37,7 +47,7 @@ use crate::value::{Tag, Map, Dict};
 ///
 /// ```rust,no_run
 /// # use serde::Deserialize;
-/// use figment::{Provider, Metadata, Profile, Error, value::{Map, Dict}};
+/// use figment::{Provider, Metadata, Profile, Source, Error, value::{Map, Dict}};
 ///
 /// # type Url = String;
 /// /// A provider that fetches its data from a given URL.
@@ -49,12 +59,12 @@ use crate::value::{Tag, Map, Dict};
 /// };
 ///
 /// impl Provider for NetProvider {
-///     /// Returns metadata with kind `Network`, custom source `self.url`,
+///     /// Returns metadata with kind `Network`, URL source `self.url`,
 ///     /// and interpolator that returns a URL of `url/a/b/c` for key `a.b.c`.
 ///     fn metadata(&self) -> Metadata {
 ///         let url = self.url.clone();
 ///         Metadata::named("Network")
-///             .source(self.url.as_str())
+///             .source(Source::Url(self.url.to_string()))
 ///             .interpolater(move |profile, keys| match profile.is_custom() {
 ///                 true => format!("{}/{}/{}", url, profile, keys.join("/")),
 ///                 false => format!("{}/{}", url, keys.join("/")),
@@ -82,7 +92,8 @@ use crate::value::{Tag, Map, Dict};
 /// [`Figment`]: crate::Figment
 pub trait Provider {
     /// Returns the [`Metadata`] for this provider, identifying itself and its
-    /// configuration sources.
+    /// configuration sources. When the provider is combined into a figment,
+    /// this method is called last; see [call order](#call-order).
     fn metadata(&self) -> Metadata;
 
     /// Returns the configuration data.
@@ -90,19 +101,185 @@ pub trait Provider {
 
     /// Optionally returns a profile to set on the [`Figment`](crate::Figment)
     /// this provider is merged into. The profile is only set if `self` is
-    /// _merged_.
+    /// _merged_. This method is called after [`Provider::data()`]; see [call
+    /// order](#call-order).
     fn profile(&self) -> Option<Profile> {
         None
     }
 
+    /// Optionally customizes how a value provided by `self` is combined with
+    /// the existing value, if any, when `self` is combined into a
+    /// [`Figment`](crate::Figment). Returns [`Resolution::Default`] by default,
+    /// resolving as the figment's strategy would.
+    ///
+    /// The hook is called for every key path `key` that `self` provides, with
+    /// the existing value at `key`, if any, and the incoming value `new`,
+    /// beginning with top-level keys. When the resolution is
+    /// `Resolution::Default` and `new` is a dictionary that isn't discarded,
+    /// the hook is then called for each key in `new`. [Merge
+    /// traces](crate::Figment::trace_merges()) and [shadowed
+    /// values](crate::Figment::shadowed()) record what the figment's strategy
+    /// would have done.
+    ///
+    /// # Example
+    ///
+    /// A provider that deletes keys set to `null`, similar to a JSON Merge
+    /// Patch:
+    ///
+    /// ```rust
+    /// use figment::{Figment, Provider, Metadata, Profile, Resolution, Error};
+    /// use figment::value::{Map, Dict, Value, Empty};
+    ///
+    /// struct Patch<P>(P);
+    ///
+    /// impl<P: Provider> Provider for Patch<P> {
+    ///     fn metadata(&self) -> Metadata { self.0.metadata() }
+    ///
+    ///     fn data(&self) -> Result<Map<Profile, Dict>, Error> { self.0.data() }
+    ///
+    ///     fn on_conflict(&self, _: &str, _: Option<&Value>, new: &Value) -> Resolution {
+    ///         match new {
+    ///             Value::Empty(_, Empty::None) => Resolution::Remove,
+    ///             _ => Resolution::Default,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let figment = Figment::from(("app", figment::util::map!["name" => "app", "port" => "80"]))
+    ///     .merge(Patch(("app.port", None::<u16>)))
+    ///     .merge(Patch(("debug", None::<bool>)));
+    ///
+    /// assert!(figment.contains("app.name"));
+    /// assert!(!figment.contains("app.port"));
+    /// assert!(!figment.contains("debug"));
+    /// ```
+    fn on_conflict(&self, key: &str, old: Option<&Value>, new: &Value) -> Resolution {
+        let _ = (key, old, new);
+        Resolution::Default
+    }
+
     /// This is used internally! Please, please don't use this externally. If
     /// you have a good usecase for this, let me know!
     #[doc(hidden)]
     fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> { None }
+
+    /// This is used internally! Applies in-place changes to the data
+    /// accumulated so far, after `self`'s data has been combined into it.
+    #[doc(hidden)]
+    fn __patch(&self, data: &mut Map<Profile, Dict>, tag: Tag) -> Result<(), Error> {
+        let _ = (data, tag);
+        Ok(())
+    }
+
+    /// Returns a provider that emits the data of `self` only when `predicate`
+    /// returns `true`. The predicate is evaluated when the data is requested.
+    /// See [`When`] for details.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Provider};
+    ///
+    /// let figment = |verbose: bool| Figment::from(("level", "info"))
+    ///     .merge(("level", "trace").when(move || verbose));
+    ///
+    /// assert_eq!(figment(false).extract_inner::<String>("level").unwrap(), "info");
+    /// assert_eq!(figment(true).extract_inner::<String>("level").unwrap(), "trace");
+    /// ```
+    fn when<F>(self, predicate: F) -> When<Self>
+        where Self: Sized, F: Fn() -> bool + 'static
+    {
+        When::new(self, predicate)
+    }
+
+    /// Returns a provider that emits the data of `self` only when the
+    /// environment variable `name` is set to a non-empty value. The variable
+    /// is read when the data is requested. See [`When`] for details.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, Provider};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     let figment = || Figment::from(("ci", false)).merge(("ci", true).when_env("CI"));
+    ///     assert!(!figment().extract_inner::<bool>("ci")?);
+    ///
+    ///     jail.set_env("CI", "");
+    ///     assert!(!figment().extract_inner::<bool>("ci")?);
+    ///
+    ///     jail.set_env("CI", "1");
+    ///     assert!(figment().extract_inner::<bool>("ci")?);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    fn when_env<N>(self, name: N) -> When<Self>
+        where Self: Sized, N: Into<String>
+    {
+        let name = name.into();
+        When::new(self, move || matches!(std::env::var_os(&name), Some(v) if !v.is_empty()))
+    }
+
+    /// Returns a provider that emits the data of `self` with every profile
+    /// renamed by `f`. Profiles that `f` maps to the same profile are merged.
+    /// See [`MapProfile`] for details.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Profile, Provider, providers::Serialized};
+    ///
+    /// let provider = Serialized::default("port", 8080).profile("release");
+    /// let provider = provider.map_profile(|p: Profile| match p == "release" {
+    ///     true => "prod".into(),
+    ///     false => p,
+    /// });
+    ///
+    /// let figment = Figment::from(provider).select("prod");
+    /// assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 8080);
+    /// ```
+    fn map_profile<F>(self, f: F) -> MapProfile<Self>
+        where Self: Sized, F: Fn(Profile) -> Profile + 'static
+    {
+        MapProfile::new(self, f)
+    }
+
+    /// Returns a provider that applies the sections of `self`'s data under
+    /// `target` that match the current platform, such as `target.linux` or
+    /// `target.windows`. See [`Platform`] for details.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Provider, providers::{Format, Toml}};
+    ///
+    /// let toml = Toml::string(r#"
+    ///     editor = "vi"
+    ///     target.windows.editor = "notepad"
+    /// "#);
+    ///
+    /// let figment = Figment::from(toml.platform_overlays().os("windows"));
+    /// assert_eq!(figment.extract_inner::<String>("editor").unwrap(), "notepad");
+    /// ```
+    fn platform_overlays(self) -> Platform<Self>
+        where Self: Sized
+    {
+        Platform::new(self)
+    }
+}
+
+/// How a value provided by a [`Provider`] is combined with the existing value,
+/// returned by [`Provider::on_conflict()`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    /// Resolve as the figment's strategy (`merge`, `join`, and so on) would.
+    Default,
+    /// Keep the existing value, if any, and discard the incoming value.
+    KeepExisting,
+    /// Use the incoming value as-is, replacing the existing value, if any.
+    UseIncoming,
+    /// Remove the key entirely, discarding both values.
+    Remove,
+    /// Use the given value. Untagged parts of the value are tagged with the
+    /// provider's metadata.
+    Value(Value),
 }
 
 /// This is exactly `<T as Provider>`.
-impl<T: Provider> Provider for &T {
+impl<T: Provider + ?Sized> Provider for &T {
     fn metadata(&self) -> Metadata { T::metadata(self) }
 
     fn data(&self) -> Result<Map<Profile, Dict>, Error> { T::data(self) }
@@ -111,10 +288,19 @@ impl<T: Provider> Provider for &T {
         T::profile(self)
     }
 
+    fn on_conflict(&self, key: &str, old: Option<&Value>, new: &Value) -> Resolution {
+        T::on_conflict(self, key, old, new)
+    }
+
     #[doc(hidden)]
     fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> {
         T::__metadata_map(self)
     }
+
+    #[doc(hidden)]
+    fn __patch(&self, data: &mut Map<Profile, Dict>, tag: Tag) -> Result<(), Error> {
+        T::__patch(self, data, tag)
+    }
 }
 
 /// This is exactly equivalent to [`Serialized::global(K, V)`].
@@ -0,0 +1,483 @@
+use std::fmt;
+
+use clap::{ArgAction, ArgMatches, Command};
+use clap::parser::ValueSource;
+
+use crate::{Profile, Provider, Metadata};
+use crate::coalesce::Coalescible;
+use crate::value::{Map, Dict, Value};
+use crate::error::Error;
+use crate::util::nest;
+
+use uncased::{Uncased, UncasedStr};
+
+crate::util::cloneable_fn_trait!(
+    FilterMap: for<'a> Fn(&'a UncasedStr) -> Option<Uncased<'a>> + 'static
+);
+
+/// A [`Provider`] that sources its values from command-line arguments parsed
+/// by [`clap`].
+///
+/// Every argument given on the command line is emitted with its argument id
+/// as its [key path]. Arguments left at their default values are not
+/// emitted, so merging a `CliArgs` last overrides only the values actually
+/// given on the command line, completing the classic defaults < file <
+/// environment < command line precedence chain. Arguments of subcommands
+/// are emitted nested under the subcommand's name.
+///
+/// Like [`Env`], keys can be filtered and renamed via [`CliArgs::map()`],
+/// [`CliArgs::filter_map()`], and friends. For example, [`CliArgs::split()`]
+/// nests arguments like `--log-level` under `log` as `level`. An argument's
+/// id is the name given to [`Arg::new()`] or, with `clap`'s derive, the name
+/// of its field.
+///
+/// [key path]: crate::Figment#extraction
+/// [`Env`]: crate::providers::Env
+/// [`Arg::new()`]: clap::Arg::new()
+///
+/// # Values
+///
+/// Argument values are parsed as a [`Value`] using the same syntax as
+/// [`Env`]. Flags set via [`ArgAction::SetTrue`] or [`ArgAction::SetFalse`]
+/// are emitted as booleans, [`ArgAction::Count`] arguments as the number of
+/// occurrences, and arguments that accept multiple values, via
+/// [`ArgAction::Append`] or multiple [`num_args`](clap::Arg::num_args()), as
+/// arrays. Values that aren't valid Unicode are converted lossily.
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider is named `command-line argument(s)`. It does not specify
+///     a [`Source`](crate::Source). Interpolation delimits path parts with `.`
+///     and prefixes them with `--`.
+///
+///   * **Data**
+///
+///     The data emitted by this provider is a dictionary with the keys and
+///     values of every argument given on the command line, as described
+///     above. The dictionary is emitted to the profile
+///     [`profile`](#structfield.profile), configurable via
+///     [`CliArgs::profile()`].
+///
+/// # Example
+///
+/// ```rust
+/// use clap::{Arg, ArgAction, Command};
+/// use serde::Deserialize;
+/// use figment::{Figment, Jail, providers::{CliArgs, Env, Format, Toml}};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Log {
+///     level: String,
+/// }
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     server: Server,
+///     log: Log,
+///     verbose: bool,
+/// }
+///
+/// let command = Command::new("app")
+///     .arg(Arg::new("server.port").long("server.port"))
+///     .arg(Arg::new("log-level").long("log-level").default_value("info"))
+///     .arg(Arg::new("verbose").short('v').action(ArgAction::SetTrue));
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", r#"
+///         verbose = false
+///
+///         [server]
+///         host = "localhost"
+///         port = 80
+///
+///         [log]
+///         level = "warn"
+///     "#)?;
+///
+///     jail.set_env("APP_SERVER.PORT", 8000);
+///
+///     let args = ["app", "--server.port", "8080", "-v"];
+///     let cli = CliArgs::try_from_args(command, args).unwrap().split("-");
+///     let config: Config = Figment::from(Toml::file("App.toml"))
+///         .merge(Env::prefixed("APP_"))
+///         .merge(cli)
+///         .extract()?;
+///
+///     // `--log-level` was not given, so its default doesn't override the file.
+///     assert_eq!(config, Config {
+///         server: Server { host: "localhost".into(), port: 8080 },
+///         log: Log { level: "warn".into() },
+///         verbose: true,
+///     });
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Clone)]
+pub struct CliArgs {
+    /// The key and value of every argument given, keyed by argument id.
+    args: Vec<(String, Value)>,
+    filter_map: Box<dyn FilterMap>,
+    /// The profile config data will be emitted to. Defaults to
+    /// [`Profile::Default`].
+    pub profile: Profile,
+}
+
+impl fmt::Debug for CliArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl CliArgs {
+    /// Parses the process's command-line arguments with `command`, exiting
+    /// the process with a usage message if they are invalid, as
+    /// [`Command::get_matches()`] does, and returns a `CliArgs` provider of
+    /// the given arguments.
+    ///
+    /// ```rust,no_run
+    /// use clap::{Arg, Command};
+    /// use figment::providers::CliArgs;
+    ///
+    /// let command = Command::new("app").arg(Arg::new("port").long("port"));
+    /// let cli = CliArgs::new(command);
+    /// ```
+    pub fn new(command: Command) -> Self {
+        let matches = command.clone().get_matches();
+        CliArgs::from_matches(&command, &matches)
+    }
+
+    /// Parses `args`, the first of which is the binary name, with `command`
+    /// and returns a `CliArgs` provider of the given arguments, or the parse
+    /// error if they are invalid.
+    ///
+    /// ```rust
+    /// use clap::{Arg, Command};
+    /// use figment::{Figment, providers::CliArgs};
+    ///
+    /// let command = Command::new("app").arg(Arg::new("port").long("port"));
+    /// let cli = CliArgs::try_from_args(command.clone(), ["app", "--port", "8080"]).unwrap();
+    /// assert_eq!(Figment::from(cli).extract_inner::<u16>("port").unwrap(), 8080);
+    ///
+    /// assert!(CliArgs::try_from_args(command, ["app", "--unknown"]).is_err());
+    /// ```
+    pub fn try_from_args<I, T>(command: Command, args: I) -> Result<Self, clap::Error>
+        where I: IntoIterator<Item = T>, T: Into<std::ffi::OsString> + Clone
+    {
+        let matches = command.clone().try_get_matches_from(args)?;
+        Ok(CliArgs::from_matches(&command, &matches))
+    }
+
+    /// Returns a `CliArgs` provider of the arguments in `matches`, which were
+    /// parsed with `command`. `command` is used to determine how to emit each
+    /// argument's values.
+    ///
+    /// ```rust
+    /// use clap::{Parser, CommandFactory};
+    /// use figment::{Figment, providers::CliArgs};
+    ///
+    /// #[derive(Parser)]
+    /// struct Cli {
+    ///     #[arg(long)]
+    ///     port: Option<u16>,
+    ///     #[arg(long)]
+    ///     host: Vec<String>,
+    /// }
+    ///
+    /// let matches = Cli::command().get_matches_from(["app", "--host", "a", "--host", "b"]);
+    /// let cli = CliArgs::from_matches(&Cli::command(), &matches);
+    /// let figment = Figment::from(cli);
+    /// assert!(!figment.contains("port"));
+    /// assert_eq!(figment.extract_inner::<Vec<String>>("host").unwrap(), ["a", "b"]);
+    /// ```
+    pub fn from_matches(command: &Command, matches: &ArgMatches) -> Self {
+        let mut command = command.clone();
+        command.build();
+
+        let mut args = vec![];
+        collect(&command, matches, "", &mut args);
+        CliArgs {
+            args,
+            filter_map: Box::new(|key| Some(key.into())),
+            profile: Profile::Default,
+        }
+    }
+
+    fn chain<F>(self, f: F) -> Self
+        where F: Clone + 'static + for<'a> Fn(Option<Uncased<'a>>) -> Option<Uncased<'a>>
+    {
+        let filter_map = self.filter_map;
+        CliArgs {
+            args: self.args,
+            filter_map: Box::new(move |key| f(filter_map(key))),
+            profile: self.profile,
+        }
+    }
+
+    /// Filters out arguments whose keys don't satisfy `filter`.
+    ///
+    /// ```rust
+    /// use clap::{Arg, Command};
+    /// use figment::providers::CliArgs;
+    ///
+    /// let command = Command::new("app")
+    ///     .arg(Arg::new("port").long("port"))
+    ///     .arg(Arg::new("config").long("config"));
+    ///
+    /// let args = ["app", "--port", "8080", "--config", "App.toml"];
+    /// let cli = CliArgs::try_from_args(command, args).unwrap()
+    ///     .filter(|key| key.as_str() != "config");
+    ///
+    /// assert_eq!(cli.iter().count(), 1);
+    /// ```
+    pub fn filter<F>(self, filter: F) -> Self
+        where F: Clone + 'static + Fn(&UncasedStr) -> bool
+    {
+        self.chain(move |prev| prev.filter(|v| filter(v)))
+    }
+
+    /// Applies an additional mapping to the keys of arguments, like
+    /// [`Env::map()`](crate::providers::Env::map()).
+    ///
+    /// ```rust
+    /// use clap::{Arg, Command};
+    /// use figment::{Figment, providers::CliArgs};
+    ///
+    /// let command = Command::new("app").arg(Arg::new("port").long("port"));
+    /// let cli = CliArgs::try_from_args(command, ["app", "--port", "8080"]).unwrap()
+    ///     .map(|key| format!("server.{}", key).into());
+    ///
+    /// let figment = Figment::from(cli);
+    /// assert_eq!(figment.extract_inner::<u16>("server.port").unwrap(), 8080);
+    /// ```
+    pub fn map<F>(self, mapper: F) -> Self
+        where F: Clone + 'static + Fn(&UncasedStr) -> Uncased<'_>
+    {
+        self.chain(move |prev| prev.map(|v| mapper(&v).into_owned()))
+    }
+
+    /// Simultaneously filters and maps the keys of arguments, like
+    /// [`Env::filter_map()`](crate::providers::Env::filter_map()).
+    ///
+    /// ```rust
+    /// use clap::{Arg, Command};
+    /// use figment::{Figment, providers::CliArgs};
+    ///
+    /// let command = Command::new("app")
+    ///     .arg(Arg::new("db-url").long("db-url"))
+    ///     .arg(Arg::new("dry-run").long("dry-run"));
+    ///
+    /// let args = ["app", "--db-url", "postgres://", "--dry-run", "true"];
+    /// let cli = CliArgs::try_from_args(command, args).unwrap()
+    ///     .filter_map(|key| {
+    ///         let key = key.as_str().strip_prefix("db-")?;
+    ///         Some(format!("database.{}", key).into())
+    ///     });
+    ///
+    /// let figment = Figment::from(cli);
+    /// assert_eq!(figment.extract_inner::<String>("database.url").unwrap(), "postgres://");
+    /// assert!(!figment.contains("dry-run"));
+    /// ```
+    pub fn filter_map<F>(self, f: F) -> Self
+        where F: Clone + 'static + Fn(&UncasedStr) -> Option<Uncased<'_>>
+    {
+        self.chain(move |prev| prev.and_then(|v| f(&v).map(|v| v.into_owned())))
+    }
+
+    /// Splits each key at every occurrence of `pattern`, creating nested
+    /// dictionaries, like [`Env::split()`](crate::providers::Env::split()).
+    ///
+    /// ```rust
+    /// use clap::{Arg, Command};
+    /// use figment::{Figment, providers::CliArgs};
+    ///
+    /// let command = Command::new("app").arg(Arg::new("log-level").long("log-level"));
+    /// let cli = CliArgs::try_from_args(command, ["app", "--log-level=debug"]).unwrap()
+    ///     .split("-");
+    ///
+    /// let figment = Figment::from(cli);
+    /// assert_eq!(figment.extract_inner::<String>("log.level").unwrap(), "debug");
+    /// ```
+    pub fn split<P: Into<String>>(self, pattern: P) -> Self {
+        let pattern = pattern.into();
+        self.map(move |key| key.as_str().replace(&pattern, ".").into())
+    }
+
+    /// Filters out all arguments whose keys are contained in `keys`.
+    ///
+    /// ```rust
+    /// use clap::{Arg, Command};
+    /// use figment::providers::CliArgs;
+    ///
+    /// let command = Command::new("app")
+    ///     .arg(Arg::new("port").long("port"))
+    ///     .arg(Arg::new("config").long("config"));
+    ///
+    /// let args = ["app", "--port", "8080", "--config", "App.toml"];
+    /// let cli = CliArgs::try_from_args(command, args).unwrap().ignore(&["config"]);
+    /// assert_eq!(cli.iter().map(|(k, _)| k).collect::<Vec<_>>(), ["port"]);
+    /// ```
+    pub fn ignore(self, keys: &[&str]) -> Self {
+        let keys: Vec<String> = keys.iter().map(|s| (*s).to_string()).collect();
+        self.filter(move |key| !keys.iter().any(|k| k.as_str() == key))
+    }
+
+    /// Filters out all arguments whose keys are _not_ contained in `keys`.
+    ///
+    /// ```rust
+    /// use clap::{Arg, Command};
+    /// use figment::providers::CliArgs;
+    ///
+    /// let command = Command::new("app")
+    ///     .arg(Arg::new("port").long("port"))
+    ///     .arg(Arg::new("config").long("config"));
+    ///
+    /// let args = ["app", "--port", "8080", "--config", "App.toml"];
+    /// let cli = CliArgs::try_from_args(command, args).unwrap().only(&["port"]);
+    /// assert_eq!(cli.iter().map(|(k, _)| k).collect::<Vec<_>>(), ["port"]);
+    /// ```
+    pub fn only(self, keys: &[&str]) -> Self {
+        let keys: Vec<String> = keys.iter().map(|s| (*s).to_string()).collect();
+        self.filter(move |key| keys.iter().any(|k| k.as_str() == key))
+    }
+
+    /// Returns an iterator over the `(key, value)` pairs of the arguments that
+    /// will be emitted by `self`, in the order the arguments were declared.
+    /// Empty keys, or partially empty keys, are not emitted.
+    ///
+    /// ```rust
+    /// use clap::{Arg, ArgAction, Command};
+    /// use figment::{providers::CliArgs, value::Value};
+    ///
+    /// let command = Command::new("app")
+    ///     .arg(Arg::new("verbose").short('v').action(ArgAction::Count))
+    ///     .arg(Arg::new("name").long("name"));
+    ///
+    /// let cli = CliArgs::try_from_args(command, ["app", "-vvv", "--name", "app"]).unwrap();
+    /// let args: Vec<_> = cli.iter().collect();
+    /// assert_eq!(args, [
+    ///     ("verbose".to_string(), Value::from(3u8)),
+    ///     ("name".to_string(), Value::from("app")),
+    /// ]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (String, Value)> + '_ {
+        self.args.iter().filter_map(move |(key, value)| {
+            let key = (self.filter_map)(UncasedStr::new(key.trim()))?;
+            let key = key.as_str().trim();
+            if key.split('.').any(|s| s.is_empty()) {
+                return None;
+            }
+
+            Some((key.to_string(), value.clone()))
+        })
+    }
+
+    /// Sets the profile config data will be emitted to.
+    ///
+    /// ```rust
+    /// use clap::Command;
+    /// use figment::{Profile, providers::CliArgs};
+    ///
+    /// let cli = CliArgs::try_from_args(Command::new("app"), ["app"]).unwrap();
+    /// assert_eq!(cli.profile, Profile::Default);
+    ///
+    /// let cli = cli.profile("debug");
+    /// assert_eq!(cli.profile, Profile::from("debug"));
+    /// ```
+    pub fn profile<P: Into<Profile>>(mut self, profile: P) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    /// Sets the profile config data will be emitted to to `global`.
+    ///
+    /// ```rust
+    /// use clap::Command;
+    /// use figment::{Profile, providers::CliArgs};
+    ///
+    /// let cli = CliArgs::try_from_args(Command::new("app"), ["app"]).unwrap().global();
+    /// assert_eq!(cli.profile, Profile::Global);
+    /// ```
+    pub fn global(mut self) -> Self {
+        self.profile = Profile::Global;
+        self
+    }
+}
+
+/// Pushes the key and value of every argument of `command` given in `matches`
+/// to `args`, prefixing keys with `prefix`, and recurses into the subcommand.
+fn collect(command: &Command, matches: &ArgMatches, prefix: &str, args: &mut Vec<(String, Value)>) {
+    for arg in command.get_arguments() {
+        let id = arg.get_id().as_str();
+        match matches.value_source(id) {
+            Some(ValueSource::DefaultValue) | None => continue,
+            Some(_) => {}
+        }
+
+        let value = match arg.get_action() {
+            ArgAction::Count => match matches.try_get_one::<u8>(id) {
+                Ok(Some(count)) => Value::from(*count),
+                _ => continue,
+            },
+            ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version => {
+                continue
+            }
+            action => {
+                let values: Vec<Value> = match matches.try_get_raw(id) {
+                    Ok(Some(raw)) => raw.map(|v| v.to_string_lossy().parse().expect("infallible"))
+                        .collect(),
+                    _ => continue,
+                };
+
+                let multiple = matches!(action, ArgAction::Append)
+                    || matches!(arg.get_num_args(), Some(n) if n.max_values() > 1);
+
+                match (multiple, values.len()) {
+                    (false, 1) => values.into_iter().next().expect("one value"),
+                    _ => Value::from(values),
+                }
+            }
+        };
+
+        args.push((format!("{}{}", prefix, id), value));
+    }
+
+    if let Some((name, matches)) = matches.subcommand() {
+        if let Some(subcommand) = command.find_subcommand(name) {
+            let prefix = format!("{}{}.", prefix, name);
+            collect(subcommand, matches, &prefix, args);
+        }
+    }
+}
+
+impl Provider for CliArgs {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("command-line argument(s)")
+            .interpolater(|_: &Profile, k: &[&str]| format!("--{}", k.join(".")))
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let mut dict = Dict::new();
+        for (key, value) in self.iter() {
+            let nested_dict = nest(&key, value)
+                .into_dict()
+                .expect("key is non-empty: must have dict");
+
+            dict = dict.merge(nested_dict);
+        }
+
+        Ok(self.profile.collect(dict))
+    }
+}
@@ -1,10 +1,13 @@
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
 
+use serde::Serialize;
 use serde::de::{self, DeserializeOwned};
 
-use crate::value::{Map, Dict};
-use crate::{Error, Profile, Provider, Metadata};
+use crate::value::{Map, Dict, Value, Tag, Visit};
+use crate::{Error, Figment, Profile, Provider, Metadata};
 use crate::error::Kind;
 
 /// A `Provider` that sources values from a file or string in a given
@@ -39,7 +42,9 @@ use crate::error::Kind;
 ///     [`Data::string()`]), where `${NAME}` is [`Format::NAME`]. When
 ///     constructed from a file, the file's path is specified as file
 ///     [`Source`](crate::Source). Path interpolation is unchanged from the
-///     default.
+///     default. With [`Data::checksum()`], the file's checksum is recorded.
+///     With [`Data::with_includes()`], each included file is a separate
+///     source.
 ///
 ///   * **Data (Unnested, _default_)**
 ///
@@ -61,18 +66,52 @@ pub struct Data<F: Format> {
     /// The profile data will be emitted to if nesting is disabled. Defaults to
     /// [`Profile::Default`].
     pub profile: Option<Profile>,
+    /// Whether YAML aliases are allowed. See [`Data::resolve_aliases()`].
+    #[cfg(feature = "yaml")]
+    aliases: bool,
+    /// Whether environment variables are expanded. See [`Data::expand_env()`].
+    expand_env: bool,
+    /// Whether a file's checksum is recorded. See [`Data::checksum()`].
+    checksum: bool,
+    /// How included files are listed, if they are. See
+    /// [`Data::with_includes()`].
+    includes: Option<Includes>,
     _format: PhantomData<F>,
 }
 
+/// The settings of [`Data::with_includes()`].
+#[derive(Debug, Clone)]
+struct Includes {
+    /// The key listing the files to include.
+    key: String,
+    /// Whether included files are loaded. `false` for the providers of the
+    /// individual files, from which only the key is removed.
+    load: bool,
+    /// The figment combining the included files and the including file, from
+    /// the last call to `__metadata_map()`, so that the metadata and data agree
+    /// on tags.
+    figment: Arc<Mutex<Option<Figment>>>,
+}
+
 #[derive(Debug, Clone)]
 enum Source {
     File { path: PathBuf, required: bool, search: bool, },
     String(String),
+    Embedded { path: &'static str, contents: &'static str },
 }
 
 impl<F: Format> Data<F> {
     fn new(profile: Option<Profile>, source: Source) -> Self {
-        Data { source, profile, _format: PhantomData }
+        Data {
+            source,
+            profile,
+            #[cfg(feature = "yaml")]
+            aliases: true,
+            expand_env: false,
+            checksum: false,
+            includes: None,
+            _format: PhantomData,
+        }
     }
 
     /// Returns a `Data` provider that sources its values by parsing the file at
@@ -152,6 +191,44 @@ impl<F: Format> Data<F> {
         Data::new(Some(Profile::Default), Source::String(string.into()))
     }
 
+    /// Returns a `Data` provider that sources its values by parsing the
+    /// compiled-in `contents` of the file at `path` as format `F`. Nesting is
+    /// not enabled by default; use [`Data::nested()`] to enable nesting.
+    ///
+    /// This is intended for default configuration files shipped inside a
+    /// binary. `contents` is typically produced by `include_str!(path)`; the
+    /// [`embedded!`](crate::providers::embedded!) macro does exactly this. The
+    /// file at `path` is never read at runtime. Instead, `path` is recorded as
+    /// the value's [`Source::Embedded`](crate::Source::Embedded), marking it
+    /// as compiled-in.
+    ///
+    /// There is not yet an integration with asset embedding crates like
+    /// `rust-embed`, whose files needn't be `'static` and are read from disk in
+    /// debug builds; it's left to a follow-up. Until then, embed files with
+    /// `include_str!` or [`embedded!`](crate::providers::embedded!).
+    ///
+    /// ```rust
+    /// use figment::{Figment, Source, providers::{Format, Toml}};
+    ///
+    /// static DEFAULTS: &str = r#"
+    ///     name = "app"
+    ///     port = 8000
+    /// "#;
+    ///
+    /// let figment = Figment::from(Toml::embedded("config/defaults.toml", DEFAULTS));
+    /// assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 8000);
+    ///
+    /// let metadata = figment.find_metadata("name").unwrap();
+    /// assert_eq!(metadata.name, "TOML file (compiled-in)");
+    ///
+    /// let source = metadata.source.as_ref().unwrap();
+    /// assert_eq!(source, &Source::Embedded("config/defaults.toml".into()));
+    /// assert_eq!(source.to_string(), "config/defaults.toml (compiled-in)");
+    /// ```
+    pub fn embedded(path: &'static str, contents: &'static str) -> Self {
+        Data::new(Some(Profile::Default), Source::Embedded { path, contents })
+    }
+
     /// Deprecated alias for `Data::file(path).search(false)`.
     ///
     /// Use [`file(path).search(false)`](Data::search) instead.
@@ -332,6 +409,266 @@ impl<F: Format> Data<F> {
         self
     }
 
+    /// Expands references to environment variables in string values, at any
+    /// depth, when the source is parsed. A reference is of the form `${VAR}`,
+    /// replaced with the value of `VAR`, or `${VAR:-default}`, replaced with
+    /// the value of `VAR` if it is set and non-empty and with `default`
+    /// otherwise. `$${` is replaced with a literal `${`. Keys are not
+    /// expanded.
+    ///
+    /// A reference to a variable that isn't set and has no default causes an
+    /// error. The names of referenced variables are recorded in the provider's
+    /// metadata and available via [`Metadata::get_expanded_env()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", r#"
+    ///         url = "postgres://${DB_USER}@${DB_HOST:-localhost}/app"
+    ///         greeting = "$${name}"
+    ///     "#)?;
+    ///
+    ///     jail.set_env("DB_USER", "admin");
+    ///
+    ///     let figment = Figment::from(Toml::file("App.toml").expand_env());
+    ///     assert_eq!(figment.extract_inner::<String>("url")?, "postgres://admin@localhost/app");
+    ///     assert_eq!(figment.extract_inner::<String>("greeting")?, "${name}");
+    ///
+    ///     let metadata = figment.find_metadata("url").unwrap();
+    ///     assert_eq!(metadata.get_expanded_env(), ["DB_HOST", "DB_USER"]);
+    ///
+    ///     jail.create_file("App.toml", "url = \"${DB_PASSWORD}\"")?;
+    ///     let figment = Figment::from(Toml::file("App.toml").expand_env());
+    ///     let error = figment.extract_inner::<String>("url").unwrap_err();
+    ///     assert!(error.to_string().contains("`DB_PASSWORD` is not set"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn expand_env(mut self) -> Self {
+        self.expand_env = true;
+        self
+    }
+
+    /// Sets whether the checksum of a file source's contents is recorded in
+    /// the provider's metadata via [`Metadata::checksum()`], so that a change
+    /// to the file can be detected with [`Metadata::source_changed()`] or
+    /// [`Figment::sources_changed()`](crate::Figment::sources_changed()).
+    /// Disabled by default. Has no effect on string and embedded sources,
+    /// which can't change.
+    ///
+    /// A missing file has no checksum: creating it is a change, as is
+    /// deleting a file that was present.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("App.toml", "port = 8080")?;
+    ///
+    ///     let figment = Figment::from(Toml::file("App.toml").checksum(true));
+    ///     let metadata = figment.find_metadata("port").unwrap();
+    ///     assert!(metadata.get_checksum().is_some());
+    ///     assert!(!metadata.source_changed());
+    ///
+    ///     jail.create_file("App.toml", "port = 9090")?;
+    ///     assert!(metadata.source_changed());
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn checksum(mut self, yes: bool) -> Self {
+        self.checksum = yes;
+        self
+    }
+
+    /// Loads the files listed by the top-level key `key` and merges them
+    /// beneath the source's own data.
+    ///
+    /// The value of `key` is a path or an array of paths. Relative paths are
+    /// relative to the directory of the including file or, for string and
+    /// embedded sources, the current working directory. The final component
+    /// of a path may contain the wildcards `*`, matching any run of
+    /// characters, and `?`, matching any single character; a wildcard path
+    /// matches every file in its directory whose name matches, in
+    /// lexicographic order, and may match none. A path without wildcards must
+    /// exist. `key` itself is removed from the data.
+    ///
+    /// Included files are parsed with the same settings as `self`, including
+    /// nesting and the profile, and may include files themselves. Files are
+    /// merged in the order they're listed, followed by the including file, so
+    /// later files take precedence over earlier ones and the including file
+    /// takes precedence over all of them. A file that includes itself,
+    /// directly or via other files, is an error.
+    ///
+    /// Each file is a separate source: every value is attributed to a
+    /// `Data` provider for the file it was read from, named and sourced as
+    /// `F::file(path)` would be.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_dir("conf.d")?;
+    ///     jail.create_file("conf.d/10-db.toml", "db = { host = \"db\", port = 5432 }")?;
+    ///     jail.create_file("conf.d/20-cache.toml", "cache = \"redis\"\ninclude = \"../base.toml\"")?;
+    ///     jail.create_file("base.toml", "name = \"base\"\ncache = \"none\"")?;
+    ///     jail.create_file("App.toml", r#"
+    ///         include = ["conf.d/*.toml"]
+    ///         name = "app"
+    ///     "#)?;
+    ///
+    ///     let figment = Figment::from(Toml::file("App.toml").with_includes("include"));
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "app");
+    ///     assert_eq!(figment.extract_inner::<String>("cache")?, "redis");
+    ///     assert_eq!(figment.extract_inner::<u16>("db.port")?, 5432);
+    ///     assert!(!figment.contains("include"));
+    ///
+    ///     let source = figment.find_metadata("db.host").and_then(|m| m.source.as_ref());
+    ///     let path = source.and_then(|s| s.file_path()).unwrap();
+    ///     assert!(path.ends_with("conf.d/10-db.toml"));
+    ///
+    ///     jail.create_file("base.toml", "include = \"App.toml\"")?;
+    ///     let figment = Figment::from(Toml::file("App.toml").with_includes("include"));
+    ///     let error = figment.extract_inner::<String>("name").unwrap_err();
+    ///     assert!(error.to_string().contains("include cycle"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn with_includes(mut self, key: &str) -> Self {
+        self.includes = Some(Includes { key: key.into(), load: true, figment: Arc::default() });
+
+        self
+    }
+
+    /// Returns a figment combining the files included by `self`, recursively,
+    /// and `self`.
+    fn include(&self, includes: &Includes) -> Result<Figment, Error> {
+        let mut files = vec![];
+        self.collect_includes(&includes.key, &mut vec![], &mut files)?;
+        Ok(files.into_iter().fold(Figment::new(), |figment, data| figment.merge(data)))
+    }
+
+    /// Pushes a provider for every file included by `self`, recursively,
+    /// followed by a provider for `self`, to `files`. `chain` contains the
+    /// canonicalized paths of the files that included `self`.
+    fn collect_includes(
+        &self,
+        key: &str,
+        chain: &mut Vec<PathBuf>,
+        files: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        let own = match &self.source {
+            Source::File { path, search, .. } => Self::resolve(path, *search)
+                .map(|path| path.canonicalize().unwrap_or(path)),
+            _ => None,
+        };
+
+        let base = match own.as_ref().and_then(|path| path.parent()) {
+            Some(dir) => dir.to_path_buf(),
+            None => std::env::current_dir().map_err(|e| e.to_string())?,
+        };
+
+        let included = self.included(key, &base).map_err(|e| match (&own, chain.is_empty()) {
+            (Some(path), false) => Error::from(format!("included file `{}`: {}", path.display(), e)),
+            _ => e,
+        })?;
+
+        let depth = chain.len();
+        chain.extend(own);
+        for path in included {
+            let path = path.canonicalize().unwrap_or(path);
+            if chain.contains(&path) {
+                let cycle = chain.iter().chain(Some(&path))
+                    .map(|path| format!("`{}`", path.display()))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+
+                return Err(Kind::Message(format!("include cycle: {}", cycle)).into());
+            }
+
+            let source = Source::File { path, required: true, search: false };
+            self.derive(source, key).collect_includes(key, chain, files)?;
+        }
+
+        chain.truncate(depth);
+        files.push(self.derive(self.source.clone(), key));
+        Ok(())
+    }
+
+    /// Returns a provider with the settings of `self` but the source `source`
+    /// that removes, but doesn't load, the files listed by `key`.
+    fn derive(&self, source: Source, key: &str) -> Self {
+        Data {
+            source,
+            profile: self.profile.clone(),
+            #[cfg(feature = "yaml")]
+            aliases: self.aliases,
+            expand_env: self.expand_env,
+            checksum: self.checksum,
+            includes: Some(Includes { key: key.into(), load: false, figment: Arc::default() }),
+            _format: PhantomData,
+        }
+    }
+
+    /// Returns the paths of the files listed by `key`, with relative paths
+    /// joined to `base` and wildcards expanded.
+    fn included(&self, key: &str, base: &Path) -> Result<Vec<PathBuf>, Error> {
+        let mut dict: Dict = self.read()?.unwrap_or_default();
+        let patterns = match dict.remove(key) {
+            None => Some(vec![]),
+            Some(Value::String(_, path)) => Some(vec![path]),
+            Some(Value::Array(_, paths)) => paths.into_iter()
+                .map(|path| match path {
+                    Value::String(_, path) => Some(path),
+                    _ => None,
+                })
+                .collect(),
+            Some(_) => None,
+        };
+
+        let patterns = patterns.ok_or_else(|| {
+            let error = Error::from(format!("`{}` must be a path or an array of paths", key));
+            error.with_path(key)
+        })?;
+
+        let mut paths = vec![];
+        for pattern in patterns {
+            let pattern = base.join(pattern);
+            let name = match pattern.file_name().and_then(|name| name.to_str()) {
+                Some(name) if name.contains(['*', '?']) => name,
+                _ => {
+                    paths.push(pattern);
+                    continue;
+                }
+            };
+
+            let dir = pattern.parent().unwrap_or(base);
+            let mut matches: Vec<_> = std::fs::read_dir(dir).into_iter()
+                .flatten()
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_file())
+                .filter(|path| {
+                    let file = path.file_name().and_then(|n| n.to_str());
+                    matches!(file, Some(file) if crate::util::glob_match(name, file))
+                })
+                .collect();
+
+            matches.sort();
+            paths.extend(matches);
+        }
+
+        Ok(paths)
+    }
+
     /// Resolves `path` to a valid file path or returns `None`. If `search` is
     /// `true` and `path` is not absolute, searches the current working
     /// directory and all parent directories until the root and return the first
@@ -352,42 +689,309 @@ impl<F: Format> Data<F> {
             cwd = cwd.parent()?;
         }
     }
+
+    /// Parses the source without expanding environment variables or loading
+    /// included files. The key listing included files, if any, is removed.
+    fn parse(&self) -> Result<Map<Profile, Dict>, Error> {
+        let includes = match &self.includes {
+            Some(includes) => includes,
+            None => return Ok(match &self.profile {
+                Some(profile) => self.read()?.map(|dict| profile.collect(dict)),
+                None => self.read()?,
+            }.unwrap_or_default()),
+        };
+
+        let mut dict: Dict = match self.read()? {
+            Some(dict) => dict,
+            None => return Ok(Map::new()),
+        };
+
+        dict.remove(&includes.key);
+        match &self.profile {
+            Some(profile) => Ok(profile.collect(dict)),
+            None => Value::from(dict).deserialize(),
+        }
+    }
+
+    /// Reads and parses the source as a `T`. Returns `None` if the source is a
+    /// file that doesn't exist and isn't required.
+    fn read<T: DeserializeOwned>(&self) -> Result<Option<T>, Error> {
+        let value = match &self.source {
+            Source::File { path, required, search } => match Self::resolve(path, *search) {
+                Some(path) => self.parse_path(&path),
+                None if !required => return Ok(None),
+                None => {
+                    let msg = format!("required file `{}` not found", path.display());
+                    return Err(Kind::Message(msg).into());
+                }
+            },
+            Source::String(s) => self.parse_str(s),
+            Source::Embedded { contents, .. } => self.parse_str(contents),
+        };
+
+        Ok(Some(value.map_err(|e| e.to_string())?))
+    }
+
+    /// Parses `string` as `F`, first checking for disallowed YAML aliases.
+    fn parse_str<T: DeserializeOwned>(&self, string: &str) -> Result<T, F::Error> {
+        #[cfg(feature = "yaml")]
+        if !self.aliases {
+            if let Some(line) = yaml_find_alias(string) {
+                let msg = format!("YAML aliases are disabled but an alias is used on line {}", line);
+                return Err(de::Error::custom(msg));
+            }
+        }
+
+        F::from_str(string)
+    }
+
+    /// Parses the file at `path` as `F`, first checking for disallowed YAML
+    /// aliases.
+    fn parse_path<T: DeserializeOwned>(&self, path: &Path) -> Result<T, F::Error> {
+        #[cfg(feature = "yaml")]
+        if !self.aliases {
+            let source = std::fs::read_to_string(path).map_err(de::Error::custom)?;
+            return self.parse_str(&source);
+        }
+
+        F::from_path(path)
+    }
 }
 
+macro_rules! impl_resolve_aliases {
+    ($F:ident) => (
+        #[cfg(feature = "yaml")]
+        #[cfg_attr(nightly, doc(cfg(feature = "yaml")))]
+        impl Data<$F> {
+            /// Sets whether YAML aliases, such as `*name`, are allowed. The
+            /// default is `true`.
+            ///
+            /// Aliases, along with the `<<` merge key, are resolved by
+            /// default. An alias can refer to a node that itself contains
+            /// aliases, so a small document can expand to an enormous value
+            /// (the "billion laughs" attack). When `false`, a source that
+            /// uses any alias fails to parse instead, protecting services
+            /// that load untrusted YAML. Anchors without aliases remain
+            /// permitted.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// use figment::{Figment, Jail, providers::{Format, Yaml}};
+            ///
+            /// Jail::expect_with(|jail| {
+            ///     jail.create_file("Config.yaml", r#"
+            ///         base: &base { x: 1, y: 2 }
+            ///         point: *base
+            ///         circle:
+            ///           <<: *base
+            ///           r: 3
+            ///     "#)?;
+            ///
+            ///     let figment = Figment::from(Yaml::file("Config.yaml"));
+            ///     assert_eq!(figment.extract_inner::<u8>("point.y")?, 2);
+            ///     assert_eq!(figment.extract_inner::<u8>("circle.x")?, 1);
+            ///
+            ///     let yaml = Yaml::file("Config.yaml").resolve_aliases(false);
+            ///     let error = Figment::from(yaml).extract_inner::<u8>("circle.r").unwrap_err();
+            ///     assert!(error.to_string().contains("aliases are disabled"));
+            ///
+            ///     Ok(())
+            /// });
+            /// ```
+            pub fn resolve_aliases(mut self, yes: bool) -> Self {
+                self.aliases = yes;
+                self
+            }
+        }
+    )
+}
+
+impl_resolve_aliases!(Yaml);
+impl_resolve_aliases!(YamlExtended);
+
 impl<F: Format> Provider for Data<F> {
     fn metadata(&self) -> Metadata {
-        use Source::*;
-        match &self.source {
-            String(_) => Metadata::named(format!("{} source string", F::NAME)),
-            File { path, search, required: _ } => {
+        // The source string or the path to the file that is read on lookup.
+        let (mut metadata, raw): (_, Result<String, PathBuf>) = match &self.source {
+            Source::String(s) => {
+                (Metadata::named(format!("{} source string", F::NAME)), Ok(s.clone()))
+            }
+            Source::Embedded { path, contents } => {
+                let name = format!("{} file (compiled-in)", F::NAME);
+                let source = crate::Source::Embedded(path.into());
+                (Metadata::from(name, source), Ok(contents.to_string()))
+            }
+            Source::File { path, search, required: _ } => {
                 let path = Self::resolve(path, *search).unwrap_or_else(|| path.clone());
-                Metadata::from(format!("{} file", F::NAME), path.as_path())
+                (Metadata::from(format!("{} file", F::NAME), path.as_path()), Err(path))
+            }
+        };
+
+        if self.expand_env {
+            let mut vars = vec![];
+            if let Ok(mut map) = self.parse() {
+                for dict in map.values_mut() {
+                    let _ = expand_env(dict, &mut vars);
+                }
             }
+
+            vars.sort();
+            metadata = metadata.expanded_env(vars);
+        }
+
+        if let (true, Err(path)) = (self.checksum, &raw) {
+            let path = path.clone();
+            let sum = move || std::fs::read(&path).ok().map(|bytes| crate::util::checksum(&bytes));
+            metadata = metadata.checksum(sum(), sum);
         }
+
+        let (name, find, nested): (&'static str, fn(&str, &[&str]) -> _, _)
+            = (F::NAME, F::find_raw, self.profile.is_none());
+
+        let source = raw.clone();
+        let find_span: fn(&str, &[&str]) -> _ = F::find_span;
+        metadata
+            .raw_finder(move |profile, keys| {
+                let string = match &raw {
+                    Ok(string) => string.clone(),
+                    Err(path) => std::fs::read_to_string(path).ok()?,
+                };
+
+                let text = match nested {
+                    true => find(&string, &[&[profile.as_str().as_str()], keys].concat()),
+                    false => find(&string, keys),
+                };
+
+                text.map(|text| (text, name))
+            })
+            .span_finder(move |profile, keys| {
+                let string = match &source {
+                    Ok(string) => string.clone(),
+                    Err(path) => std::fs::read_to_string(path).ok()?,
+                };
+
+                let span = match nested {
+                    true => find_span(&string, &[&[profile.as_str().as_str()], keys].concat()),
+                    false => find_span(&string, keys),
+                };
+
+                span.map(|span| (string, span))
+            })
     }
 
     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
-        use Source as S;
-        let map: Result<Map<Profile, Dict>, _> = match (&self.source, &self.profile) {
-            (S::File { path, required, search }, profile) => {
-                match Self::resolve(path, *search) {
-                    Some(path) => match profile {
-                        Some(prof) => F::from_path(&path).map(|v| prof.collect(v)),
-                        None => F::from_path(&path),
-                    },
-                    None if !required => Ok(Map::new()),
-                    None => {
-                        let msg = format!("required file `{}` not found", path.display());
-                        return Err(Kind::Message(msg).into());
+        if let Some(includes) = self.includes.as_ref().filter(|i| i.load) {
+            let figment = includes.figment.lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take();
+
+            return match figment {
+                Some(figment) => figment.data(),
+                None => self.include(includes)?.data(),
+            };
+        }
+
+        let mut map = self.parse()?;
+        if self.expand_env {
+            for (profile, dict) in map.iter_mut() {
+                expand_env(dict, &mut vec![]).map_err(|mut e| {
+                    e.profile = Some(profile.clone());
+                    e
+                })?;
+            }
+        }
+
+        Ok(map)
+    }
+
+    fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> {
+        let includes = self.includes.as_ref().filter(|i| i.load)?;
+        let figment = self.include(includes).ok()?;
+        let metadata = figment.metadata.clone();
+        *includes.figment.lock().unwrap_or_else(|e| e.into_inner()) = Some(figment);
+        Some(metadata)
+    }
+}
+
+/// Expands references to environment variables in the string values of
+/// `dict` as described in [`Data::expand_env()`], adding the names of
+/// referenced variables not already in `vars` to `vars`.
+fn expand_env(dict: &mut Dict, vars: &mut Vec<String>) -> Result<(), Error> {
+    for (key, value) in dict.iter_mut() {
+        let mut error = None;
+        value.walk_mut(&mut |path: &[String], value: &mut Value| {
+            if let Value::String(tag, string) = value {
+                match expand_env_str(string, vars) {
+                    Ok(Some(expanded)) => *string = expanded,
+                    Ok(None) => {},
+                    Err(e) => {
+                        let mut e = Error::from(e).retagged(*tag);
+                        e.path = std::iter::once(key).chain(path).cloned().collect();
+                        error = Some(e);
+                        return Visit::Stop;
                     }
                 }
-            },
-            (S::String(s), None) => F::from_str(s),
-            (S::String(s), Some(prof)) => F::from_str(s).map(|v| prof.collect(v)),
+            }
+
+            Visit::Continue
+        });
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `string` with environment variable references expanded or `None`
+/// if `string` contains no references.
+fn expand_env_str(string: &str, vars: &mut Vec<String>) -> Result<Option<String>, Kind> {
+    if !string.contains("${") {
+        return Ok(None);
+    }
+
+    let mut expanded = String::with_capacity(string.len());
+    let mut rest = string;
+    while let Some(i) = rest.find("${") {
+        if rest[..i].ends_with('$') {
+            expanded.push_str(&rest[..(i - 1)]);
+            expanded.push_str("${");
+            rest = &rest[(i + 2)..];
+            continue;
+        }
+
+        expanded.push_str(&rest[..i]);
+        let end = rest[i..].find('}').map(|j| i + j).ok_or_else(|| {
+            Kind::Message(format!("unterminated environment variable reference in {:?}", string))
+        })?;
+
+        let reference = &rest[(i + 2)..end];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
         };
 
-        Ok(map.map_err(|e| e.to_string())?)
+        if !vars.iter().any(|v| v == name) {
+            vars.push(name.to_string());
+        }
+
+        match (std::env::var(name).ok(), default) {
+            (Some(value), Some(default)) if value.is_empty() => expanded.push_str(default),
+            (Some(value), _) => expanded.push_str(&value),
+            (None, Some(default)) => expanded.push_str(default),
+            (None, None) => {
+                let msg = format!("environment variable `{}` is not set", name);
+                return Err(Kind::Message(msg));
+            }
+        }
+
+        rest = &rest[(end + 1)..];
     }
+
+    expanded.push_str(rest);
+    Ok(Some(expanded))
 }
 
 /// Trait implementable by text-based [`Data`] format providers.
@@ -456,6 +1060,24 @@ pub trait Format: Sized {
         Data::string(string)
     }
 
+    /// Returns a `Data` provider that sources its values by parsing the
+    /// compiled-in `contents` of the file at `path` as format `Self`. See
+    /// [`Data::embedded()`] for more details. The default implementation calls
+    /// `Data::embedded(path, contents)`.
+    fn embedded(path: &'static str, contents: &'static str) -> Data<Self> {
+        Data::embedded(path, contents)
+    }
+
+    /// Returns a `Discover` provider that sources its values by parsing every
+    /// file named `name` found by walking up the directory tree as format
+    /// `Self`. See [`Discover`] for more details. The default implementation
+    /// calls `Discover::new(name)`.
+    ///
+    /// [`Discover`]: crate::providers::Discover
+    fn discover<P: AsRef<Path>>(name: P) -> crate::providers::Discover<Self> {
+        crate::providers::Discover::new(name)
+    }
+
     /// Deprecated alias for `file(path).search(false)`.
     ///
     /// Use [`file(path).search(false)`](Data::search) instead.
@@ -482,11 +1104,62 @@ pub trait Format: Sized {
         let source = std::fs::read_to_string(path).map_err(de::Error::custom)?;
         Self::from_str(&source)
     }
+
+    /// Returns the original, unparsed text in `string` of the value at the key
+    /// path `keys` or `None` if there is no such value or the format does not
+    /// support raw lookups. The default implementation returns `None`.
+    /// **_Note:_** This method is _not_ intended to be called directly.
+    /// Instead, it is used indirectly via [`Figment::raw_inner()`].
+    ///
+    /// [`Figment::raw_inner()`]: crate::Figment::raw_inner()
+    fn find_raw(_string: &str, _keys: &[&str]) -> Option<String> {
+        None
+    }
+
+    /// Returns the byte range in `string` of the value at the key path `keys`
+    /// or `None` if there is no such value or the format does not track
+    /// spans. The default implementation returns `None`. **_Note:_** This
+    /// method is _not_ intended to be called directly. Instead, it is used
+    /// indirectly via [`Metadata::find_span()`].
+    fn find_span(_string: &str, _keys: &[&str]) -> Option<Range<usize>> {
+        None
+    }
+
+    /// Serializes `value` as the data format `Self`, formatted for humans, or
+    /// returns an error if `value` can't be represented in the format. The
+    /// default implementation returns an error. **_Note:_** This method is
+    /// _not_ intended to be called directly. Instead, it is used indirectly
+    /// via [`Figment::serialize_to()`].
+    ///
+    /// [`Figment::serialize_to()`]: crate::Figment::serialize_to()
+    fn to_string<T: Serialize>(_value: &T) -> Result<String, Self::Error> {
+        Err(de::Error::custom(format_args!("{} does not support serialization", Self::NAME)))
+    }
+}
+
+/// Returns a [`Data`] provider for format `$F` that sources its values from the
+/// file at `$path`, relative to the invoking file, embedded at compile time via
+/// `include_str!`. See [`Data::embedded()`] for details.
+///
+/// ```rust,ignore
+/// use figment::{Figment, providers::{embedded, Toml}};
+///
+/// let figment = Figment::from(embedded!(Toml, "../defaults.toml"));
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! embedded {
+    ($F:ty, $path:literal $(,)?) => (
+        <$F as $crate::providers::Format>::embedded($path, include_str!($path))
+    );
 }
 
+#[doc(inline)]
+pub use embedded;
+
 #[allow(unused_macros)]
 macro_rules! impl_format {
-    ($name:ident $NAME:literal/$string:literal: $func:expr, $E:ty, $doc:expr) => (
+    ($name:ident $NAME:literal/$string:literal: $func:expr, $ser:expr, $E:ty, $doc:expr $(; $raw:expr, $span:expr)?) => (
         #[cfg(feature = $string)]
         #[cfg_attr(nightly, doc(cfg(feature = $string)))]
         #[doc = $doc]
@@ -501,11 +1174,25 @@ macro_rules! impl_format {
             fn from_str<'de, T: DeserializeOwned>(s: &'de str) -> Result<T, $E> {
                 $func(s)
             }
+
+            fn to_string<T: Serialize>(value: &T) -> Result<String, $E> {
+                $ser(value)
+            }
+
+            $(
+                fn find_raw(s: &str, keys: &[&str]) -> Option<String> {
+                    $raw(s, keys)
+                }
+
+                fn find_span(s: &str, keys: &[&str]) -> Option<Range<usize>> {
+                    $span(s, keys)
+                }
+            )?
         }
     );
 
-    ($name:ident $NAME:literal/$string:literal: $func:expr, $E:ty) => (
-        impl_format!($name $NAME/$string: $func, $E, concat!(
+    ($name:ident $NAME:literal/$string:literal: $func:expr, $ser:expr, $E:ty $(; $raw:expr, $span:expr)?) => (
+        impl_format!($name $NAME/$string: $func, $ser, $E, concat!(
             "A ", $NAME, " [`Format`] [`Data`] provider.",
             "\n\n",
             "Static constructor methods on `", stringify!($name), "` return a
@@ -521,7 +1208,7 @@ macro_rules! impl_format {
             "\nlet provider = ", stringify!($name), r#"::file("path-to-file").nested();"#,
             "\n```",
             "\n\nSee also [`", stringify!($func), "`] for parsing details."
-        ));
+        ) $(; $raw, $span)?);
     )
 }
 
@@ -534,9 +1221,10 @@ impl YamlExtended {
     /// [`serde_yaml::Value::apply_merge()`]. This method is _not_ intended to
     /// be used directly but rather indirectly by making use of `YamlExtended`
     /// as a provider. The extension is not part of any officially supported
-    /// YAML release and is deprecated entirely since YAML 1.2. Using
-    /// `YamlExtended` instead of [`Yaml`] enables merge keys, allowing YAML
-    /// like the following to parse with key merges applied:
+    /// YAML release and is deprecated entirely since YAML 1.2. Nevertheless,
+    /// it is widely used, so [`Yaml`] applies merge keys as well; `YamlExtended`
+    /// differs only in its name. Merge keys allow YAML like the following to
+    /// parse with key merges applied:
     ///
     /// ```yaml
     /// tasks:
@@ -596,9 +1284,9 @@ impl YamlExtended {
     ///         circle3: Circle { x: 1, y: 14, r: 20 },
     ///     });
     ///
-    ///     // Note that just `Yaml` would fail, since it doesn't support merge.
-    ///     let config = Figment::from(Yaml::file("Config.yaml"));
-    ///     assert!(config.extract::<Config>().is_err());
+    ///     // `Yaml` applies merge keys as well.
+    ///     let config: Config = Figment::from(Yaml::file("Config.yaml")).extract()?;
+    ///     assert_eq!(config.circle3, Circle { x: 1, y: 14, r: 20 });
     ///
     ///     Ok(())
     /// });
@@ -610,7 +1298,119 @@ impl YamlExtended {
     }
 }
 
-impl_format!(Toml "TOML"/"toml": toml_edit::de::from_str, toml_edit::de::Error);
-impl_format!(Yaml "YAML"/"yaml": serde_yaml::from_str, serde_yaml::Error);
-impl_format!(Json "JSON"/"json": serde_json::from_str, serde_json::error::Error);
-impl_format!(YamlExtended "YAML Extended"/"yaml": YamlExtended::from_str, serde_yaml::Error);
+#[cfg(feature = "toml")]
+fn toml_find_raw(string: &str, keys: &[&str]) -> Option<String> {
+    let document: toml_edit::DocumentMut = string.parse().ok()?;
+    let mut item = document.as_item();
+    for key in keys {
+        item = item.get(key)?;
+    }
+
+    Some(item.to_string().trim().to_string())
+}
+
+#[cfg(feature = "toml")]
+fn toml_find_span(string: &str, keys: &[&str]) -> Option<Range<usize>> {
+    let document = toml_edit::ImDocument::parse(string).ok()?;
+    let mut item = document.as_item();
+    for key in keys {
+        item = item.get(key)?;
+    }
+
+    item.span()
+}
+
+/// Returns the 1-based line number of the first alias in the YAML `string`, if
+/// any, as reported by the same parser `serde_yaml` uses. Returns `None` if
+/// `string` fails to parse before an alias is found, leaving the error to be
+/// reported when deserializing.
+#[cfg(feature = "yaml")]
+fn yaml_find_alias(string: &str) -> Option<usize> {
+    use std::mem::MaybeUninit;
+    use unsafe_libyaml::{yaml_parser_t, yaml_event_t, YAML_UTF8_ENCODING};
+    use unsafe_libyaml::{YAML_ALIAS_EVENT, YAML_STREAM_END_EVENT};
+
+    // SAFETY: The parser is boxed so that it doesn't move while it holds
+    // pointers into itself. It is initialized before use, reads only from
+    // `string`, which outlives it, and is deleted exactly once. Each event is
+    // deleted after it is read and before the next is parsed.
+    unsafe {
+        let mut parser = Box::new(MaybeUninit::<yaml_parser_t>::uninit());
+        let parser = parser.as_mut_ptr();
+        if unsafe_libyaml::yaml_parser_initialize(parser).fail {
+            return None;
+        }
+
+        unsafe_libyaml::yaml_parser_set_encoding(parser, YAML_UTF8_ENCODING);
+        unsafe_libyaml::yaml_parser_set_input_string(parser, string.as_ptr(), string.len() as u64);
+
+        let mut line = None;
+        let mut event = MaybeUninit::<yaml_event_t>::uninit();
+        while unsafe_libyaml::yaml_parser_parse(parser, event.as_mut_ptr()).ok {
+            let (kind, mark) = ((*event.as_ptr()).type_, (*event.as_ptr()).start_mark);
+            unsafe_libyaml::yaml_event_delete(event.as_mut_ptr());
+            match kind {
+                YAML_ALIAS_EVENT => line = Some(mark.line as usize + 1),
+                YAML_STREAM_END_EVENT => {}
+                _ => continue,
+            }
+
+            break;
+        }
+
+        unsafe_libyaml::yaml_parser_delete(parser);
+        line
+    }
+}
+
+#[cfg(feature = "json")]
+fn json_find_raw(string: &str, keys: &[&str]) -> Option<String> {
+    json_find_raw_in(string, keys).map(|raw| raw.to_string())
+}
+
+/// Returns the text of the value at `keys` as a subslice of `string`.
+#[cfg(feature = "json")]
+fn json_find_raw_in<'s>(string: &'s str, keys: &[&str]) -> Option<&'s str> {
+    use serde_json::value::RawValue;
+
+    let mut raw: &RawValue = serde_json::from_str(string).ok()?;
+    for key in keys {
+        let map: Map<String, &RawValue> = serde_json::from_str(raw.get()).ok()?;
+        raw = map.get(*key).copied()?;
+    }
+
+    Some(raw.get().trim())
+}
+
+#[cfg(feature = "json")]
+fn json_find_span(string: &str, keys: &[&str]) -> Option<Range<usize>> {
+    let raw = json_find_raw_in(string, keys)?;
+    let start = raw.as_ptr() as usize - string.as_ptr() as usize;
+    Some(start..start + raw.len())
+}
+
+#[cfg(feature = "toml")]
+fn toml_to_string<T: Serialize>(value: &T) -> Result<String, toml_edit::de::Error> {
+    toml_edit::ser::to_string_pretty(value).map_err(de::Error::custom)
+}
+
+/// Parses the RON document `s` into a [`ron::Value`], retaining the position
+/// of an error, if any, in the error's message, and deserializes a `T` from
+/// the value. Structs are thus read as maps.
+#[cfg(feature = "ron")]
+fn ron_from_str<T: DeserializeOwned>(s: &str) -> Result<T, ron::Error> {
+    let value: ron::Value = ron::from_str(s).map_err(<ron::Error as de::Error>::custom)?;
+    T::deserialize(value)
+}
+
+#[cfg(feature = "ron")]
+fn ron_to_string<T: Serialize>(value: &T) -> Result<String, ron::Error> {
+    ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+}
+
+impl_format!(Toml "TOML"/"toml": toml_edit::de::from_str, toml_to_string, toml_edit::de::Error; toml_find_raw, toml_find_span);
+impl_format!(Yaml "YAML"/"yaml": YamlExtended::from_str, serde_yaml::to_string, serde_yaml::Error);
+impl_format!(Json "JSON"/"json": serde_json::from_str, serde_json::to_string_pretty, serde_json::error::Error; json_find_raw, json_find_span);
+impl_format!(YamlExtended "YAML Extended"/"yaml": YamlExtended::from_str, serde_yaml::to_string, serde_yaml::Error);
+impl_format!(Ron "RON"/"ron": ron_from_str, ron_to_string, ron::Error);
+impl_format!(Json5 "JSON5"/"json5": json5::from_str, json5::to_string, json5::Error);
@@ -0,0 +1,325 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Profile, Provider, Metadata};
+use crate::coalesce::Coalescible;
+use crate::value::{Map, Dict, Value};
+use crate::error::{Error, Kind};
+use crate::util::nest;
+
+/// A [`Provider`] that sources its values from a directory of files where each
+/// file name is a key and each file's contents is the key's value.
+///
+/// This is the layout used by Kubernetes for `Secret` and `ConfigMap` volumes
+/// as well as for the downward API, and by Docker and systemd for mounted
+/// credentials.
+///
+/// # Key Paths (nesting)
+///
+/// File names are emitted as [key paths]. Additionally, every occurrence of
+/// the split pattern, `__` by default, is replaced with `.`, creating a
+/// nested dictionary for each component. For example, a file named
+/// `database__password` creates the mapping `database -> password -> value`.
+/// The pattern can be changed with [`Directory::split()`]. Subdirectories are
+/// emitted as nested dictionaries as well.
+///
+/// Files and directories whose name begins with `.` are skipped. Kubernetes
+/// uses such entries (`..data`, `..2024_01_01_...`) to implement atomic
+/// updates; the visible file names are symlinks into them, and symlinks are
+/// followed.
+///
+/// # Values
+///
+/// The contents of a file that is valid UTF-8 and contains no `NUL` bytes are
+/// emitted as a string, verbatim but for a single trailing `\n` or `\r\n`,
+/// which is removed. A secret like `00123` or ` hunter2 ` is thus read exactly
+/// as written. To instead trim and parse contents as a [`Value`] using the same
+/// syntax as [`Env`](crate::providers::Env), so that `[a, b]` is read as an
+/// array, use [`Directory::parse_values()`]. All other files are considered
+/// binary and emitted as an array of bytes, which deserializes as, among
+/// others, a `Vec<u8>`.
+///
+/// [key paths]: crate::Figment#extraction
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider is named `directory`. The directory's path is specified as
+///     file [`Source`](crate::Source). Interpolation joins path parts with the
+///     split pattern, producing the name of the file for the key.
+///
+///   * **Data**
+///
+///     The data emitted by this provider is a dictionary with keys and values
+///     read from the files in the directory as described above. If the
+///     directory does not exist, an empty dictionary is emitted unless
+///     [`Directory::required()`] is set to `true`, in which case the provider
+///     fails. The dictionary is emitted to the profile
+///     [`profile`](#structfield.profile), configurable via
+///     [`Directory::profile()`].
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use figment::{Figment, Jail, providers::Directory};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Database {
+///     user: String,
+///     password: String,
+///     pin: String,
+/// }
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     database: Database,
+///     token: Vec<u8>,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     let dir = jail.create_dir("secrets")?;
+///     jail.create_file(dir.join("database__user"), "admin\n")?;
+///     jail.create_file(dir.join("database__password"), " hunter2 \n")?;
+///     jail.create_file(dir.join("database__pin"), "0042")?;
+///     jail.create_binary(dir.join("token"), &[0xFF, 0x00, 0x4F])?;
+///     jail.create_file(dir.join(".hidden"), "ignored")?;
+///
+///     let config: Config = Figment::from(Directory::new("secrets")).extract()?;
+///     assert_eq!(config, Config {
+///         database: Database {
+///             user: "admin".into(),
+///             password: " hunter2 ".into(),
+///             pin: "0042".into(),
+///         },
+///         token: vec![0xFF, 0x00, 0x4F],
+///     });
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Directory {
+    path: PathBuf,
+    pattern: String,
+    required: bool,
+    parse: bool,
+    /// The profile config data will be emitted to. Defaults to
+    /// [`Profile::Default`].
+    pub profile: Profile,
+}
+
+impl Directory {
+    /// Returns a `Directory` provider that sources its values from the files
+    /// in the directory at `path`. Relative paths are relative to the current
+    /// working directory.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::Directory};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_dir("config")?;
+    ///     jail.create_file("config/name", "figment")?;
+    ///
+    ///     let figment = Figment::from(Directory::new("config"));
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "figment");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Directory {
+            path: path.as_ref().to_path_buf(),
+            pattern: "__".into(),
+            required: false,
+            parse: false,
+            profile: Profile::Default,
+        }
+    }
+
+    /// Sets the pattern in file names that delimits nested keys. The default
+    /// is `__`. An empty pattern disables splitting.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::Directory};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_dir("config")?;
+    ///     jail.create_file("config/server-port", "8080")?;
+    ///     jail.create_file("config/server__host", "localhost")?;
+    ///
+    ///     let figment = Figment::from(Directory::new("config").split("-"));
+    ///     assert_eq!(figment.extract_inner::<u16>("server.port")?, 8080);
+    ///     assert_eq!(figment.extract_inner::<String>("server__host")?, "localhost");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn split<P: Into<String>>(mut self, pattern: P) -> Self {
+        self.pattern = pattern.into();
+        self
+    }
+
+    /// Sets whether the directory is required to be present. The default is
+    /// `false`.
+    ///
+    /// When `false`, a non-existent directory is treated as an empty source.
+    /// When `true`, a non-existent directory causes an error.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::Directory};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     let figment = Figment::from(Directory::new("missing"));
+    ///     assert!(figment.extract::<figment::value::Dict>()?.is_empty());
+    ///
+    ///     let figment = Figment::from(Directory::new("missing").required(true));
+    ///     assert!(figment.extract::<figment::value::Dict>().is_err());
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn required(mut self, yes: bool) -> Self {
+        self.required = yes;
+        self
+    }
+
+    /// Sets whether file contents are trimmed of leading and trailing
+    /// whitespace and parsed as a [`Value`] using the same syntax as
+    /// [`Env`](crate::providers::Env). The default is `false`, emitting the
+    /// contents as strings.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::Directory};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_dir("config")?;
+    ///     jail.create_file("config/port", "08080 \n")?;
+    ///     jail.create_file("config/hosts", "[a, b]")?;
+    ///
+    ///     let figment = Figment::from(Directory::new("config"));
+    ///     assert_eq!(figment.extract_inner::<String>("port")?, "08080 ");
+    ///     assert_eq!(figment.extract_inner::<String>("hosts")?, "[a, b]");
+    ///
+    ///     let figment = Figment::from(Directory::new("config").parse_values(true));
+    ///     assert_eq!(figment.extract_inner::<u16>("port")?, 8080);
+    ///     assert_eq!(figment.extract_inner::<Vec<String>>("hosts")?, ["a", "b"]);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn parse_values(mut self, yes: bool) -> Self {
+        self.parse = yes;
+        self
+    }
+
+    /// Sets the profile config data will be emitted to.
+    ///
+    /// ```rust
+    /// use figment::{Profile, providers::Directory};
+    ///
+    /// let provider = Directory::new("/etc/secrets");
+    /// assert_eq!(provider.profile, Profile::Default);
+    ///
+    /// let provider = provider.profile("debug");
+    /// assert_eq!(provider.profile, Profile::from("debug"));
+    /// ```
+    pub fn profile<P: Into<Profile>>(mut self, profile: P) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    /// Sets the profile config data will be emitted to to `global`.
+    ///
+    /// ```rust
+    /// use figment::{Profile, providers::Directory};
+    ///
+    /// let provider = Directory::new("/etc/secrets").global();
+    /// assert_eq!(provider.profile, Profile::Global);
+    /// ```
+    pub fn global(mut self) -> Self {
+        self.profile = Profile::Global;
+        self
+    }
+
+    fn key(&self, name: &str) -> String {
+        match self.pattern.is_empty() {
+            true => name.to_string(),
+            false => name.replace(&self.pattern, "."),
+        }
+    }
+
+    fn value(&self, bytes: Vec<u8>) -> Value {
+        if bytes.contains(&0) {
+            return bytes.into();
+        }
+
+        match String::from_utf8(bytes) {
+            Ok(string) if self.parse => string.trim().parse().expect("infallible"),
+            Ok(mut string) => {
+                if string.ends_with('\n') {
+                    string.pop();
+                    if string.ends_with('\r') {
+                        string.pop();
+                    }
+                }
+
+                string.into()
+            }
+            Err(e) => e.into_bytes().into(),
+        }
+    }
+
+    fn read_dir(&self, dir: &Path) -> Result<Dict, Error> {
+        let error = |e: std::io::Error| format!("{}: {}", dir.display(), e);
+        let mut dict = Dict::new();
+        for entry in fs::read_dir(dir).map_err(error)? {
+            let path = entry.map_err(error)?.path();
+            let name = match path.file_name().and_then(|n| n.to_str()) {
+                Some(name) if !name.starts_with('.') => name,
+                _ => continue,
+            };
+
+            let key = self.key(name);
+            if key.split('.').any(|k| k.is_empty()) {
+                continue;
+            }
+
+            let value = match fs::metadata(&path).map_err(error)?.is_dir() {
+                true => Value::from(self.read_dir(&path)?),
+                false => self.value(fs::read(&path).map_err(error)?),
+            };
+
+            let nested = nest(&key, value).into_dict().expect("non-empty key: dict");
+            dict = dict.merge(nested);
+        }
+
+        Ok(dict)
+    }
+}
+
+impl Provider for Directory {
+    fn metadata(&self) -> Metadata {
+        let pattern = self.pattern.clone();
+        Metadata::from("directory", self.path.as_path())
+            .interpolater(move |_: &Profile, k: &[&str]| k.join(&pattern))
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        if !self.path.is_dir() {
+            if self.required {
+                let msg = format!("required directory `{}` not found", self.path.display());
+                return Err(Kind::Message(msg).into());
+            }
+
+            return Ok(Map::new());
+        }
+
+        Ok(self.profile.collect(self.read_dir(&self.path)?))
+    }
+}
@@ -0,0 +1,319 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::{Figment, Profile, Provider, Metadata};
+use crate::error::{Error, Kind};
+use crate::providers::Format;
+use crate::value::{Map, Dict, Tag};
+
+/// A [`Provider`] that discovers configuration files of format `F` by walking
+/// up the directory tree, like `git` discovers `.gitignore` files and `cargo`
+/// discovers `Cargo.toml`, layering files closer to the starting directory
+/// over those further up.
+///
+/// Starting at the current working directory or the directory set via
+/// [`Discover::from()`], the file named `name` is looked for in each directory
+/// and then in its parent, up to and including the first directory containing
+/// a _marker_ set via [`Discover::marker()`], like `.git`, or the root of the
+/// filesystem if there is no such directory. This allows, for example, a
+/// monorepo-wide configuration at the root of a repository to be overridden
+/// per project in a subdirectory. Every path checked, whether or not it
+/// exists, is available via [`Discover::candidates()`]; the files found via
+/// [`Discover::files()`].
+///
+/// A `Discover` is typically created via [`Format::discover()`], for instance
+/// `Toml::discover("app.toml")`.
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     Each file is a separate source: every value is attributed to the
+///     [`Data`](crate::providers::Data) provider for the file it was read
+///     from, named and sourced as `F::file(path)` would be. The provider itself
+///     is named `` `name` discovery ``.
+///
+///   * **Data**
+///
+///     The data of every file found, merged from the furthest from the
+///     starting directory to the nearest, so nearer files take precedence.
+///     Each file's data is emitted to the profile set via
+///     [`Discover::profile()`], [`Profile::Default`] by default, or, if
+///     [`Discover::nested()`] is set, to the profiles nested in the file. If
+///     no file is found, an empty dictionary is emitted unless
+///     [`Discover::required()`] is set to `true`, in which case the provider
+///     fails, listing the candidates.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use figment::{Figment, Jail, providers::{Format, Toml}};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     name: String,
+///     jobs: u8,
+///     lint: bool,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_dir(".git")?;
+///     jail.create_file("tool.toml", "name = \"repo\"\njobs = 4\nlint = false")?;
+///     jail.create_dir("crates/app/src")?;
+///     jail.create_file("crates/app/tool.toml", "lint = true")?;
+///     jail.change_dir("crates/app/src")?;
+///
+///     let discover = Toml::discover("tool.toml").marker(".git");
+///     assert_eq!(discover.candidates().len(), 4);
+///     assert_eq!(discover.files().len(), 2);
+///
+///     let figment = Figment::from(discover);
+///     let config: Config = figment.extract()?;
+///     assert_eq!(config, Config { name: "repo".into(), jobs: 4, lint: true });
+///
+///     let source = figment.find_metadata("lint").and_then(|m| m.source.as_ref());
+///     let path = source.and_then(|s| s.file_path()).unwrap();
+///     assert!(path.ends_with("crates/app/tool.toml"));
+///     Ok(())
+/// });
+/// ```
+pub struct Discover<F: Format> {
+    name: PathBuf,
+    start: Option<PathBuf>,
+    markers: Vec<String>,
+    required: bool,
+    /// The profile the data of each file is emitted to if nesting is disabled.
+    /// Defaults to [`Profile::Default`].
+    pub profile: Option<Profile>,
+    /// The figment combining the files found, from the last call to
+    /// `__metadata_map()`, so that the metadata and data agree on tags.
+    figment: Arc<Mutex<Option<Figment>>>,
+    _format: PhantomData<F>,
+}
+
+impl<F: Format> Discover<F> {
+    /// Returns a `Discover` provider that looks for files named `name`,
+    /// starting at the current working directory, with no markers. `name` can
+    /// also be a relative path, like `.config/app.toml`.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Discover, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("app.toml", "name = \"root\"")?;
+    ///     jail.create_dir("sub")?;
+    ///     jail.change_dir("sub")?;
+    ///
+    ///     let figment = Figment::from(Discover::<Toml>::new("app.toml"));
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "root");
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn new<P: AsRef<Path>>(name: P) -> Self {
+        Discover {
+            name: name.as_ref().to_path_buf(),
+            start: None,
+            markers: vec![],
+            required: false,
+            profile: Some(Profile::Default),
+            figment: Arc::default(),
+            _format: PhantomData,
+        }
+    }
+
+    /// Starts the search at the directory `dir` instead of the current working
+    /// directory. A relative `dir` is relative to the current working
+    /// directory.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_dir("a/b")?;
+    ///     jail.create_file("a/app.toml", "name = \"a\"")?;
+    ///
+    ///     let figment = Figment::from(Toml::discover("app.toml").from("a/b"));
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "a");
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn from<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.start = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds `marker`, the name of a file or directory, like `.git` or
+    /// `Cargo.lock`, that marks the root of the search: the search stops after
+    /// the first directory containing any marker. Without markers, the search
+    /// continues to the root of the filesystem.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("app.toml", "name = \"outside\"")?;
+    ///     jail.create_dir("repo/.git")?;
+    ///     jail.change_dir("repo")?;
+    ///
+    ///     let figment = Figment::from(Toml::discover("app.toml").marker(".git"));
+    ///     assert!(!figment.contains("name"));
+    ///
+    ///     let figment = Figment::from(Toml::discover("app.toml"));
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "outside");
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn marker<M: Into<String>>(mut self, marker: M) -> Self {
+        self.markers.push(marker.into());
+        self
+    }
+
+    /// Sets whether at least one file is required to be found. The default is
+    /// `false`. When `true` and no file is found, the provider fails with an
+    /// error listing every candidate path.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_dir(".git")?;
+    ///
+    ///     let discover = Toml::discover("app.toml").marker(".git");
+    ///     assert!(Figment::from(discover).extract_inner::<String>("name").is_err());
+    ///
+    ///     let discover = Toml::discover("app.toml").marker(".git").required(true);
+    ///     let error = Figment::from(discover).extract::<figment::value::Dict>().unwrap_err();
+    ///     assert!(error.to_string().contains("app.toml"));
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn required(mut self, yes: bool) -> Self {
+        self.required = yes;
+        self
+    }
+
+    /// Emits the data of each file to `profile`. See
+    /// [`Data::profile()`](crate::providers::Data::profile()).
+    pub fn profile<P: Into<Profile>>(mut self, profile: P) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Enables nesting: each file's top-level keys are treated as profiles.
+    /// See [`Data::nested()`](crate::providers::Data::nested()).
+    pub fn nested(mut self) -> Self {
+        self.profile = None;
+        self
+    }
+
+    /// Returns the path of every candidate file, whether or not it exists, in
+    /// the order searched: nearest to the starting directory first.
+    ///
+    /// ```rust
+    /// use figment::{Jail, providers::{Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_dir("repo/.git")?;
+    ///     jail.create_dir("repo/crates/app")?;
+    ///
+    ///     let discover = Toml::discover("app.toml").marker(".git").from("repo/crates/app");
+    ///     let candidates = discover.candidates();
+    ///     assert_eq!(candidates.len(), 3);
+    ///     assert!(candidates[0].ends_with("repo/crates/app/app.toml"));
+    ///     assert!(candidates[2].ends_with("repo/app.toml"));
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn candidates(&self) -> Vec<PathBuf> {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let start = match &self.start {
+            Some(dir) => cwd.join(dir),
+            None => cwd,
+        };
+
+        let mut candidates = vec![];
+        for dir in start.ancestors() {
+            candidates.push(dir.join(&self.name));
+            if self.markers.iter().any(|marker| dir.join(marker).exists()) {
+                break;
+            }
+        }
+
+        candidates
+    }
+
+    /// Returns the path of every candidate file that exists, in the order
+    /// their data is merged: furthest from the starting directory first.
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<_> = self.candidates().into_iter()
+            .filter(|path| path.is_file())
+            .collect();
+
+        files.reverse();
+        files
+    }
+
+    /// Returns a figment combining every file found.
+    fn figment(&self) -> Result<Figment, Error> {
+        let files = self.files();
+        if files.is_empty() && self.required {
+            let candidates = self.candidates().iter()
+                .map(|path| format!("`{}`", path.display()))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let msg = format!("no `{}` file found; searched {}", self.name.display(), candidates);
+            return Err(Kind::Message(msg).into());
+        }
+
+        Ok(files.into_iter().fold(Figment::new(), |figment, path| {
+            let mut data = F::file(path).search(false);
+            data.profile = self.profile.clone();
+            figment.merge(data)
+        }))
+    }
+}
+
+impl<F: Format> Provider for Discover<F> {
+    fn metadata(&self) -> Metadata {
+        Metadata::named(format!("`{}` discovery", self.name.display()))
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let figment = self.figment.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+
+        match figment {
+            Some(figment) => figment.data(),
+            None => self.figment()?.data(),
+        }
+    }
+
+    fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> {
+        let figment = self.figment().ok()?;
+        let metadata = figment.metadata.clone();
+        *self.figment.lock().unwrap_or_else(|e| e.into_inner()) = Some(figment);
+        Some(metadata)
+    }
+}
+
+impl<F: Format> fmt::Debug for Discover<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Discover")
+            .field("format", &F::NAME)
+            .field("name", &self.name)
+            .field("start", &self.start)
+            .field("markers", &self.markers)
+            .field("required", &self.required)
+            .field("profile", &self.profile)
+            .finish()
+    }
+}
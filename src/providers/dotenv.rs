@@ -0,0 +1,429 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Profile, Provider, Metadata};
+use crate::value::{Map, Dict};
+use crate::error::{Error, Kind};
+use crate::providers::Env;
+
+/// A [`Provider`] that sources its values from a `.env` file without
+/// modifying the process environment.
+///
+/// The variables in the file are selected, renamed, split, and parsed exactly
+/// as if they were environment variables read by an [`Env`] provider, which
+/// can be configured via [`DotEnv::env()`]. Unlike loading the file with a
+/// crate like `dotenvy`, however, the process environment is never modified:
+/// the variables are only ever visible to the figment.
+///
+/// # Syntax
+///
+/// Each non-empty line that doesn't begin with `#` is of the form
+/// `NAME=value`, optionally prefixed with `export `. Whitespace around the
+/// name and the `=` is ignored. Values may be:
+///
+///   * **unquoted**, in which case trailing whitespace and a trailing comment,
+///     which begins with a `#` preceded by whitespace, are removed;
+///   * **single-quoted**, in which case the value is taken literally;
+///   * **double-quoted**, in which case the escapes `\n`, `\r`, `\t`, `\"`,
+///     `\\`, and `\$` are recognized.
+///
+/// Quoted values may span multiple lines and may be followed by a comment.
+/// If a variable is set more than once, the last value wins.
+///
+/// # Profiles
+///
+/// When [`DotEnv::profiles()`] is enabled, files next to the `.env` file
+/// named `.env.{profile}`, for instance `.env.debug`, are read as well. The
+/// values in each are emitted to the profile `{profile}`.
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider is named `dotenv file`, prefixed with the configured
+///     [`Env`] prefix, if any. The file's path is specified as file
+///     [`Source`](crate::Source). Interpolation is as for [`Env`].
+///
+///   * **Data**
+///
+///     The data emitted by this provider is the dictionary [`Env`] would emit
+///     if the file's variables were the process environment, emitted to the
+///     [`Env`]'s profile, which defaults to [`Profile::Default`]. If the file
+///     does not exist, an empty dictionary is emitted unless
+///     [`DotEnv::required()`] is set to `true`, in which case the provider
+///     fails. With [`DotEnv::profiles()`], a dictionary is additionally
+///     emitted for every `.env.{profile}` file.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use figment::{Figment, Jail, providers::{DotEnv, Env}};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+///     motd: String,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file(".env", r#"
+///         export APP_NAME=figment  # inline comment
+///         APP_PORT = 8080
+///         APP_MOTD="hello,\n  world!"
+///         OTHER=ignored
+///     "#)?;
+///
+///     let config: Config = Figment::new()
+///         .merge(DotEnv::new().env(Env::prefixed("APP_")))
+///         .extract()?;
+///
+///     assert_eq!(config, Config {
+///         name: "figment".into(),
+///         port: 8080,
+///         motd: "hello,\n  world!".into(),
+///     });
+///
+///     assert!(std::env::var("APP_NAME").is_err());
+///     Ok(())
+/// });
+/// ```
+#[derive(Clone)]
+pub struct DotEnv {
+    path: PathBuf,
+    env: Env,
+    profiles: bool,
+    required: bool,
+}
+
+impl std::fmt::Debug for DotEnv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DotEnv")
+            .field("path", &self.path)
+            .field("profiles", &self.profiles)
+            .field("required", &self.required)
+            .finish()
+    }
+}
+
+impl DotEnv {
+    /// Returns a `DotEnv` provider that reads the file `.env` in the current
+    /// working directory. Equivalent to `DotEnv::file(".env")`.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::DotEnv};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file(".env", "NAME=figment")?;
+    ///
+    ///     let figment = Figment::from(DotEnv::new());
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "figment");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn new() -> Self {
+        DotEnv::file(".env")
+    }
+
+    /// Returns a `DotEnv` provider that reads the file at `path`. Relative
+    /// paths are relative to the current working directory. All variables in
+    /// the file are emitted, as with [`Env::raw()`], until [`DotEnv::env()`]
+    /// is called.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::DotEnv};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("app.env", "PORT=8080")?;
+    ///
+    ///     let figment = Figment::from(DotEnv::file("app.env"));
+    ///     assert_eq!(figment.extract_inner::<u16>("port")?, 8080);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn file<P: AsRef<Path>>(path: P) -> Self {
+        DotEnv {
+            path: path.as_ref().to_path_buf(),
+            env: Env::raw(),
+            profiles: false,
+            required: false,
+        }
+    }
+
+    /// Sets the [`Env`] provider whose prefix, filters, maps, splits, and
+    /// profile are applied to the variables in the file. The process
+    /// environment is not read.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{DotEnv, Env}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file(".env", "APP_DB__URL=postgres://db\nAPP_SECRET=x")?;
+    ///
+    ///     let env = Env::prefixed("APP_").split("__").ignore(&["secret"]);
+    ///     let figment = Figment::from(DotEnv::new().env(env));
+    ///     assert_eq!(figment.extract_inner::<String>("db.url")?, "postgres://db");
+    ///     assert!(!figment.contains("secret"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn env(mut self, env: Env) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Sets whether `.env.{profile}` files next to the file are read and
+    /// emitted to the profile `{profile}`. The default is `false`.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::DotEnv};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file(".env", "PORT=80\nHOST=localhost")?;
+    ///     jail.create_file(".env.debug", "PORT=8080")?;
+    ///
+    ///     let figment = Figment::from(DotEnv::new().profiles(true));
+    ///     assert_eq!(figment.extract_inner::<u16>("port")?, 80);
+    ///
+    ///     let figment = figment.select("debug");
+    ///     assert_eq!(figment.extract_inner::<u16>("port")?, 8080);
+    ///     assert_eq!(figment.extract_inner::<String>("host")?, "localhost");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn profiles(mut self, yes: bool) -> Self {
+        self.profiles = yes;
+        self
+    }
+
+    /// Sets whether the file is required to be present. The default is
+    /// `false`.
+    ///
+    /// When `false`, a non-existent file is treated as an empty source. When
+    /// `true`, a non-existent file causes an error. `.env.{profile}` files are
+    /// never required.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::DotEnv};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     let figment = Figment::from(DotEnv::new());
+    ///     assert!(figment.extract::<figment::value::Dict>()?.is_empty());
+    ///
+    ///     let figment = Figment::from(DotEnv::new().required(true));
+    ///     assert!(figment.extract::<figment::value::Dict>().is_err());
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn required(mut self, yes: bool) -> Self {
+        self.required = yes;
+        self
+    }
+
+    /// Returns the `(profile, path)` of every `.env.{profile}` file next to
+    /// the file, sorted by path.
+    fn variants(&self) -> Result<Vec<(Profile, PathBuf)>, Error> {
+        let name = match self.path.file_name().and_then(|n| n.to_str()) {
+            Some(name) => format!("{}.", name),
+            None => return Ok(vec![]),
+        };
+
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+
+        let error = |e: std::io::Error| format!("{}: {}", dir.display(), e);
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(Kind::Message(error(e)).into()),
+        };
+
+        let mut variants = vec![];
+        for entry in entries {
+            let path = entry.map_err(error)?.path();
+            let profile = match path.file_name().and_then(|n| n.to_str()) {
+                Some(file) if file.len() > name.len() && file.starts_with(&name) => {
+                    Profile::new(&file[name.len()..])
+                }
+                _ => continue,
+            };
+
+            if path.is_file() {
+                variants.push((profile, path));
+            }
+        }
+
+        variants.sort_by(|a, b| a.1.cmp(&b.1));
+        Ok(variants)
+    }
+
+    /// Reads and parses the file at `path`. Returns `Ok(None)` if it doesn't
+    /// exist.
+    fn read(path: &Path) -> Result<Option<Vec<(String, String)>>, Error> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Kind::Message(format!("{}: {}", path.display(), e)).into()),
+        };
+
+        parse(&contents).map(Some).map_err(|(line, msg)| {
+            let msg = format!("invalid line {} in `{}`: {}", line, path.display(), msg);
+            Kind::Message(msg).into()
+        })
+    }
+
+    fn dict(&self, vars: Vec<(String, String)>) -> Dict {
+        Env::dict(self.env.select(vars))
+    }
+}
+
+impl Default for DotEnv {
+    fn default() -> Self {
+        DotEnv::new()
+    }
+}
+
+impl Provider for DotEnv {
+    fn metadata(&self) -> Metadata {
+        let mut md = self.env.metadata();
+        md.name = md.name.replace("environment variable(s)", "dotenv file").into();
+        md.source = Some(self.path.as_path().into());
+        md
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let mut map = Map::new();
+        match Self::read(&self.path)? {
+            Some(vars) => { map.insert(self.env.profile.clone(), self.dict(vars)); }
+            None if self.required => {
+                let msg = format!("required dotenv file `{}` not found", self.path.display());
+                return Err(Kind::Message(msg).into());
+            }
+            None => {}
+        }
+
+        if self.profiles {
+            for (profile, path) in self.variants()? {
+                if let Some(vars) = Self::read(&path)? {
+                    map.insert(profile, self.dict(vars));
+                }
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+/// Parses the contents of a `.env` file into `(name, value)` pairs, in order.
+/// On error, returns the 1-based line number and a description.
+fn parse(contents: &str) -> Result<Vec<(String, String)>, (usize, &'static str)> {
+    let mut vars = vec![];
+    let mut lines = contents.lines().enumerate().map(|(i, line)| (i + 1, line));
+    while let Some((n, line)) = lines.next() {
+        let line = line.trim_start();
+        if line.trim_end().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = match line.strip_prefix("export") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+            _ => line,
+        };
+
+        let (name, rest) = line.split_once('=').ok_or((n, "expected `NAME=value`"))?;
+        let name = name.trim_end();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err((n, "invalid variable name"));
+        }
+
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let mut raw = rest[1..].to_string();
+                let end = loop {
+                    if let Some(end) = closing_quote(&raw, quote) {
+                        break end;
+                    }
+
+                    let (_, next) = lines.next().ok_or((n, "unterminated quoted value"))?;
+                    raw.push('\n');
+                    raw.push_str(next);
+                };
+
+                let trailing = raw[end + 1..].trim();
+                if !trailing.is_empty() && !trailing.starts_with('#') {
+                    return Err((n, "unexpected characters after quoted value"));
+                }
+
+                raw.truncate(end);
+                match quote {
+                    '"' => unescape(&raw),
+                    _ => raw,
+                }
+            }
+            _ => {
+                let comment = rest.char_indices()
+                    .find(|&(i, c)| c == '#' && rest[..i].ends_with(char::is_whitespace));
+
+                match comment {
+                    Some((i, _)) => rest[..i].trim_end().to_string(),
+                    None => rest.trim_end().to_string(),
+                }
+            }
+        };
+
+        vars.push((name.to_string(), value));
+    }
+
+    Ok(vars)
+}
+
+/// Returns the byte index of the first unescaped `quote` in `string`.
+fn closing_quote(string: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in string.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quote == '"' => escaped = true,
+            c if c == quote => return Some(i),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Resolves the escapes recognized in double-quoted values.
+fn unescape(string: &str) -> String {
+    let mut result = String::with_capacity(string.len());
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(c @ ('"' | '\\' | '$')) => result.push(c),
+            Some(c) => { result.push('\\'); result.push(c); }
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
@@ -2,9 +2,11 @@ use std::fmt;
 
 use crate::{Profile, Provider, Metadata};
 use crate::coalesce::Coalescible;
-use crate::value::{Map, Dict};
+use crate::value::{Map, Dict, Value, Num, Empty};
 use crate::error::Error;
 use crate::util::nest;
+use crate::schema::Schema;
+use crate::providers::EnvDocs;
 
 use uncased::{Uncased, UncasedStr};
 
@@ -110,6 +112,10 @@ pub struct Env {
     prefix: Option<String>,
     /// We use this to generate better metadata when available.
     lowercase: bool,
+    /// Whether variables with empty values are skipped.
+    ignore_empty: bool,
+    /// Whether leading and trailing whitespace is trimmed from values.
+    trim: bool,
 }
 
 impl fmt::Debug for Env {
@@ -125,6 +131,8 @@ impl Env {
             profile: Profile::Default,
             prefix: None,
             lowercase: true,
+            ignore_empty: false,
+            trim: false,
         }
     }
 
@@ -137,6 +145,8 @@ impl Env {
             profile: self.profile,
             prefix: self.prefix,
             lowercase: true,
+            ignore_empty: self.ignore_empty,
+            trim: self.trim,
         }
     }
 
@@ -372,6 +382,65 @@ impl Env {
         self
     }
 
+    /// Skips environment variables whose value is empty, as if they weren't
+    /// set. When combined with [`Env::trim_values()`], values consisting only
+    /// of whitespace are skipped as well.
+    ///
+    /// Without this option, a variable set to the empty string is emitted as
+    /// an empty value which, when merged, overrides any value for the same key
+    /// from a previously merged provider. CI systems frequently export empty
+    /// variables for unset secrets or parameters.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Env, Serialized}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("APP_NAME", "");
+    ///     jail.set_env("APP_PORT", "8080");
+    ///
+    ///     let defaults = Serialized::default("name", "app");
+    ///     let figment = Figment::from(&defaults).merge(Env::prefixed("APP_"));
+    ///     assert!(figment.extract_inner::<String>("name").is_err());
+    ///
+    ///     let env = Env::prefixed("APP_").ignore_empty();
+    ///     let figment = Figment::from(&defaults).merge(env);
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "app");
+    ///     assert_eq!(figment.extract_inner::<u16>("port")?, 8080);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn ignore_empty(mut self) -> Self {
+        self.ignore_empty = true;
+        self
+    }
+
+    /// Removes leading and trailing whitespace from environment variable
+    /// values before they are parsed.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::Env};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("APP_URL", "  https://example.com/api\n");
+    ///     jail.set_env("APP_TOKEN", "   ");
+    ///
+    ///     let env = Env::prefixed("APP_").trim_values();
+    ///     let figment = Figment::from(env.clone());
+    ///     assert_eq!(figment.extract_inner::<String>("url")?, "https://example.com/api");
+    ///     assert!(figment.contains("token"));
+    ///
+    ///     let figment = Figment::from(env.ignore_empty());
+    ///     assert!(!figment.contains("token"));
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn trim_values(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
     /// Splits each environment variable key at `pattern`, creating nested
     /// dictionaries for each split. Specifically, nested dictionaries are
     /// created for components delimited by `pattern` in the environment
@@ -484,6 +553,87 @@ impl Env {
         self.filter(move |key| keys.iter().any(|k| k.as_str() == key))
     }
 
+    /// Filters out all environment variable keys matching any of the glob
+    /// patterns in `globs`.
+    ///
+    /// Patterns are the key path globs of [`ExportOptions`]: within a key, `*`
+    /// matches any run of characters and `?` any single character, while a
+    /// `**` component matches any number of keys. As with [`Env::ignore()`],
+    /// patterns are matched against keys as they are when the filter is
+    /// applied, after the prefix is removed and any preceding maps, like
+    /// [`Env::split()`], are applied, ignoring case. A `.` in a key separates
+    /// its components, so `*` doesn't match across one.
+    ///
+    /// Only globs are supported, not regular expressions. To filter keys by a
+    /// regular expression, pass a closure that matches one, for instance via
+    /// the `regex` crate, to [`Env::filter()`].
+    /// Because patterns are plain strings, they can be read from configuration
+    /// themselves, making the variables a deployment accepts a matter of
+    /// policy.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::{Env, Format, Toml}};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("APP_PORT", 8080);
+    ///     jail.set_env("APP_DB_SECRET", "hunter2");
+    ///     jail.set_env("APP_API_SECRET", "swordfish");
+    ///     jail.set_env("APP_TMP1", 1);
+    ///
+    ///     let env = Env::prefixed("APP_").exclude_globs(["*_SECRET", "tmp?"]);
+    ///     assert_eq!(env.iter().count(), 1);
+    ///
+    ///     jail.create_file("Policy.toml", r#"env.exclude = ["api_*", "port"]"#)?;
+    ///     let policy = Figment::from(Toml::file("Policy.toml"));
+    ///     let exclude: Vec<String> = policy.extract_inner("env.exclude")?;
+    ///     let env = Env::prefixed("APP_").exclude_globs(exclude);
+    ///     assert_eq!(env.iter().count(), 2);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    ///
+    /// [`ExportOptions`]: crate::ExportOptions#globs
+    pub fn exclude_globs<I, S>(self, globs: I) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        let globs = lowercase_all(globs);
+        self.filter(move |key| !matches_any(&globs, key))
+    }
+
+    /// Filters out all environment variable keys _not_ matching any of the
+    /// glob patterns in `globs`. Patterns, which can't be regular expressions,
+    /// are matched as in [`Env::exclude_globs()`]. Inclusions and exclusions chain, so a key
+    /// can be included by one list and excluded by another.
+    ///
+    /// ```rust
+    /// use figment::{Jail, providers::Env};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("APP_DB_HOST", "db");
+    ///     jail.set_env("APP_DB_PORT", 5432);
+    ///     jail.set_env("APP_DB_SECRET", "hunter2");
+    ///     jail.set_env("APP_NAME", "app");
+    ///
+    ///     let env = Env::prefixed("APP_").split("_").include_globs(["db.*"]);
+    ///     assert_eq!(env.iter().count(), 3);
+    ///
+    ///     let env = env.exclude_globs(["*.secret"]);
+    ///     assert_eq!(env.iter().count(), 2);
+    ///
+    ///     let env = Env::prefixed("APP_").split("_").include_globs(["*"]);
+    ///     assert_eq!(env.iter().count(), 1);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn include_globs<I, S>(self, globs: I) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        let globs = lowercase_all(globs);
+        self.filter(move |key| matches_any(&globs, key))
+    }
+
     /// Returns an iterator over all of the environment variable `(key, value)`
     /// pairs that will be considered by `self`. The order is not specified.
     ///
@@ -491,7 +641,9 @@ impl Env {
     /// keys, or partially empty keys, are not emitted.
     ///
     /// Any non-Unicode sequences in values are replaced with `U+FFFD
-    /// REPLACEMENT CHARACTER`. Values are otherwise unmodified.
+    /// REPLACEMENT CHARACTER`. Values are otherwise unmodified unless
+    /// [`Env::trim_values()`] is set. Variables with empty values are skipped
+    /// if [`Env::ignore_empty()`] is set.
     ///
     /// ```rust
     /// use figment::{Jail, providers::Env};
@@ -521,23 +673,64 @@ impl Env {
     /// });
     /// ```
     pub fn iter<'a>(&'a self) -> impl Iterator<Item=(Uncased<'static>, String)> + 'a {
-        std::env::vars_os()
+        let vars = std::env::vars_os()
+            .map(|(k, v)| (k.to_string_lossy().to_string(), v.to_string_lossy().to_string()));
+
+        self.select(vars)
+    }
+
+    /// Returns the `(key, value)` pairs `self` emits for the variables `vars`
+    /// in place of the environment. See [`Env::iter()`].
+    pub(crate) fn select<'a, I>(&'a self, vars: I) -> impl Iterator<Item=(Uncased<'static>, String)> + 'a
+        where I: IntoIterator<Item = (String, String)> + 'a
+    {
+        vars.into_iter()
             .filter(|(k, _)| !k.is_empty())
             .filter_map(move |(k, v)| {
-                let key = k.to_string_lossy();
-                let key = (self.filter_map)(UncasedStr::new(key.trim()))?;
-                let key = key.as_str().trim();
-                if key.split('.').any(|s| s.is_empty()) { return None }
-
-                let key = match self.lowercase {
-                    true => key.to_ascii_lowercase(),
-                    false => key.to_owned(),
+                let key = self.key(&k)?;
+                let value = match self.trim {
+                    true => v.trim().to_string(),
+                    false => v,
                 };
 
-                Some((key.into(), v.to_string_lossy().to_string()))
+                if self.ignore_empty && value.is_empty() {
+                    return None;
+                }
+
+                Some((key.into(), value))
             })
     }
 
+    /// Returns the dictionary with the `(key, value)` pairs `vars`, as emitted
+    /// by [`Env::iter()`], nested and parsed as described in [`Env`].
+    pub(crate) fn dict<I>(vars: I) -> Dict
+        where I: IntoIterator<Item = (Uncased<'static>, String)>
+    {
+        let mut dict = Dict::new();
+        for (k, v) in vars {
+            let nested_dict = nest(k.as_str(), v.parse().expect("infallible"))
+                .into_dict()
+                .expect("key is non-empty: must have dict");
+
+            dict = dict.merge(nested_dict);
+        }
+
+        dict
+    }
+
+    /// Returns the key the environment variable `name` is emitted as, if it is
+    /// considered by `self` at all.
+    pub(crate) fn key(&self, name: &str) -> Option<String> {
+        let key = (self.filter_map)(UncasedStr::new(name.trim()))?;
+        let key = key.as_str().trim();
+        if key.split('.').any(|s| s.is_empty()) { return None }
+
+        match self.lowercase {
+            true => Some(key.to_ascii_lowercase()),
+            false => Some(key.to_owned()),
+        }
+    }
+
     /// Sets the profile config data will be emitted to.
     ///
     /// ```rust
@@ -570,6 +763,53 @@ impl Env {
         self
     }
 
+    /// Returns the environment variables `self` recognizes for a configuration
+    /// of type `T`, the kind of value each expects, and the key each sets.
+    ///
+    /// A variable is listed for every field of `T` that `self` emits a key
+    /// for. Fields of nested structs are listed individually when `self` nests
+    /// variables, as with [`Env::split()`], and the struct is otherwise listed
+    /// as a single variable expecting a dictionary. Filters such as
+    /// [`Env::only()`] and [`Env::ignore()`] are respected. The environment is
+    /// not read. See [`EnvDocs`] for output formats.
+    ///
+    /// [`EnvDocs`]: crate::providers::EnvDocs
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::providers::Env;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Database {
+    ///     url: String,
+    ///     pool_size: u16,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     debug: bool,
+    ///     db: Database,
+    /// }
+    ///
+    /// let docs = Env::prefixed("APP_").document::<Config>();
+    /// assert_eq!(docs.names().collect::<Vec<_>>(), ["APP_DB", "APP_DEBUG", "APP_NAME"]);
+    ///
+    /// let docs = Env::prefixed("APP_").split("__").ignore(&["debug"]).document::<Config>();
+    /// assert_eq!(docs.to_string(), "\
+    /// APP_DB__POOL_SIZE  a u16
+    /// APP_DB__URL        a string
+    /// APP_NAME           a string
+    /// ");
+    ///
+    /// assert_eq!(docs.vars()[0].key, "db.pool_size");
+    /// ```
+    pub fn document<'de, T: serde::Deserialize<'de>>(&self) -> EnvDocs {
+        EnvDocs::new(self, self.prefix.as_deref(), self.lowercase, &Schema::of::<T>())
+    }
+
     /// A convenience method to retrieve the value for an environment variable
     /// with name `name`. Retrieval is case-insensitive.
     ///
@@ -631,15 +871,99 @@ impl Provider for Env {
     }
 
     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
-        let mut dict = Dict::new();
-        for (k, v) in self.iter() {
-            let nested_dict = nest(k.as_str(), v.parse().expect("infallible"))
-                .into_dict()
-                .expect("key is non-empty: must have dict");
+        Ok(self.profile.collect(Env::dict(self.iter())))
+    }
+}
 
-            dict = dict.merge(nested_dict);
+/// Collects the glob patterns `globs`, lowercased.
+fn lowercase_all<I, S>(globs: I) -> Vec<String>
+    where I: IntoIterator<Item = S>, S: Into<String>
+{
+    globs.into_iter().map(|glob| glob.into().to_lowercase()).collect()
+}
+
+/// Returns `true` if the key path `key`, lowercased, matches any of the
+/// lowercase glob patterns `globs`.
+fn matches_any(globs: &[String], key: &UncasedStr) -> bool {
+    let key = key.as_str().to_lowercase();
+    let path: Vec<&str> = key.split('.').collect();
+    globs.iter().any(|glob| crate::util::path_glob_match(glob, &path))
+}
+
+/// Returns the environment variables that [`Env::prefixed(prefix)`] parses to
+/// `dict`: one per leaf of `dict`, named `prefix` followed by the uppercased
+/// key path, with a value in the syntax accepted by `Env`.
+pub(crate) fn export(prefix: &str, dict: &Dict) -> Vec<(String, String)> {
+    fn export(prefix: &str, path: &mut Vec<String>, dict: &Dict, vars: &mut Vec<(String, String)>) {
+        for (key, value) in dict {
+            path.push(key.to_ascii_uppercase());
+            match value {
+                Value::Dict(_, dict) => export(prefix, path, dict, vars),
+                _ => vars.push((format!("{}{}", prefix, path.join(".")), encode(value, false))),
+            }
+
+            path.pop();
         }
+    }
+
+    let mut vars = vec![];
+    export(prefix, &mut vec![], dict, &mut vars);
+    vars
+}
 
-        Ok(self.profile.collect(dict))
+/// Encodes `value` so that it parses back to itself. Strings are quoted when
+/// `nested` in an array or dictionary or when they would otherwise parse as
+/// something else.
+pub(crate) fn encode(value: &Value, nested: bool) -> String {
+    fn quote(string: &str) -> String {
+        let mut quoted = String::with_capacity(string.len() + 2);
+        quoted.push('"');
+        for c in string.chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+
+        quoted.push('"');
+        quoted
+    }
+
+    match value {
+        Value::String(_, s) => match s.parse::<Value>() {
+            Ok(Value::String(_, parsed)) if !nested && parsed == *s => s.clone(),
+            _ => quote(s),
+        },
+        Value::Char(_, c) => match c {
+            '\'' | '\\' => quote(&c.to_string()),
+            c if c.is_whitespace() || c.is_control() => quote(&c.to_string()),
+            c => format!("'{}'", c),
+        },
+        Value::Bool(_, b) => b.to_string(),
+        Value::Datetime(_, d) => d.to_string(),
+        Value::Num(_, n) => match *n {
+            Num::F32(v) => format!("{:?}", v),
+            Num::F64(v) => format!("{:?}", v),
+            n => n.to_u128().map(|v| v.to_string())
+                .or_else(|| n.to_i128().map(|v| v.to_string()))
+                .unwrap_or_default(),
+        },
+        Value::Empty(_, Empty::None | Empty::Unit) => String::new(),
+        Value::Array(_, items) => {
+            let items: Vec<_> = items.iter().map(|v| encode(v, true)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Dict(_, dict) => {
+            let pairs: Vec<_> = dict.iter()
+                .map(|(k, v)| format!("{} = {}", quote(k), encode(v, true)))
+                .collect();
+
+            format!("{{{}}}", pairs.join(", "))
+        }
     }
 }
@@ -0,0 +1,139 @@
+use std::fmt;
+
+use serde::ser::{Serialize, Serializer, SerializeStruct};
+
+use crate::providers::Env;
+use crate::schema::Schema;
+
+/// The environment variables recognized by an [`Env`] provider for a given
+/// configuration type, generated by [`Env::document()`].
+///
+/// The recognized variables are displayed as a table with one variable per
+/// line, each followed by the kind of value it expects. `EnvDocs` also
+/// implements `Serialize`, emitting a sequence of [`EnvVar`]s, and so can be
+/// written out in any format, such as JSON, to generate documentation or shell
+/// completions.
+///
+/// See [`Env::document()`] for an example.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvDocs {
+    vars: Vec<EnvVar>,
+}
+
+/// An environment variable recognized by an [`Env`] provider. See
+/// [`EnvDocs`].
+///
+/// An `EnvVar` serializes as a struct with fields `name`, `key`, and `type`,
+/// the latter being the display string of [`EnvVar::schema`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvVar {
+    /// The name of the environment variable, for instance `APP_DB_PORT`.
+    pub name: String,
+    /// The key path the variable is emitted as, for instance `db.port`.
+    pub key: String,
+    /// The kind of value the variable is expected to contain.
+    pub schema: Schema,
+}
+
+impl EnvDocs {
+    pub(crate) fn new(env: &Env, prefix: Option<&str>, lowercase: bool, schema: &Schema) -> Self {
+        let mut vars = vec![];
+        let generator = Generator { env, prefix: prefix.unwrap_or(""), lowercase };
+        if let Schema::Struct(fields) = schema {
+            for (field, schema) in fields {
+                generator.document(&mut vec![field.as_str()], schema, &mut vars);
+            }
+        }
+
+        EnvDocs { vars }
+    }
+
+    /// Returns the recognized variables, ordered by key path.
+    pub fn vars(&self) -> &[EnvVar] {
+        &self.vars
+    }
+
+    /// Returns an iterator over the names of the recognized variables, ordered
+    /// by key path.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.vars.iter().map(|var| var.name.as_str())
+    }
+}
+
+struct Generator<'a> {
+    env: &'a Env,
+    prefix: &'a str,
+    lowercase: bool,
+}
+
+impl Generator<'_> {
+    /// Documents the value at `path` with `schema`. Nested fields of a struct
+    /// are documented individually if they can be set via variables whose
+    /// names don't contain a `.`; otherwise the struct is documented as a
+    /// single variable holding a dictionary.
+    fn document<'s>(&self, path: &mut Vec<&'s str>, schema: &'s Schema, vars: &mut Vec<EnvVar>) {
+        let mut inner = schema;
+        while let Schema::Option(schema) = inner {
+            inner = schema;
+        }
+
+        if let Schema::Struct(fields) = inner {
+            let count = vars.len();
+            for (field, schema) in fields {
+                path.push(field);
+                self.document(path, schema, vars);
+                path.pop();
+            }
+
+            if vars.len() > count {
+                return;
+            }
+        }
+
+        if let Some(name) = self.name(path) {
+            vars.push(EnvVar { name, key: path.join("."), schema: schema.clone() });
+        }
+    }
+
+    /// Returns the name of a variable `self.env` emits as the key `path`.
+    fn name(&self, path: &[&str]) -> Option<String> {
+        let parts: Vec<_> = path.iter()
+            .map(|p| match self.lowercase {
+                true => p.to_ascii_uppercase(),
+                false => p.to_string(),
+            })
+            .collect();
+
+        let key = path.join(".");
+        ["_", "__"].iter()
+            .map(|sep| format!("{}{}", self.prefix.to_ascii_uppercase(), parts.join(sep)))
+            .find(|name| self.env.key(name).as_deref() == Some(key.as_str()))
+    }
+}
+
+impl fmt::Display for EnvDocs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.vars.iter().map(|v| v.name.len()).max().unwrap_or(0);
+        for var in &self.vars {
+            writeln!(f, "{:width$}  {}", var.name, var.schema, width = width)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Serialize for EnvDocs {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        self.vars.serialize(ser)
+    }
+}
+
+impl Serialize for EnvVar {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut s = ser.serialize_struct("EnvVar", 3)?;
+        s.serialize_field("name", &self.name)?;
+        s.serialize_field("key", &self.key)?;
+        s.serialize_field("type", &self.schema.to_string())?;
+        s.end()
+    }
+}
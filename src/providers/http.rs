@@ -0,0 +1,730 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{Profile, Provider, Metadata, Source};
+use crate::value::{Map, Dict};
+use crate::error::{Error, Kind};
+use crate::providers::Format;
+
+/// A [`Provider`] that sources its values by fetching a document from a URL
+/// and parsing it in a given [`Format`].
+///
+/// # Caching
+///
+/// A successful response's `ETag` and `Last-Modified` headers, when present,
+/// are remembered along with the document. Subsequent fetches by the same
+/// provider, or any of its clones, send them back as `If-None-Match` and
+/// `If-Modified-Since`, and a `304 Not Modified` response reuses the
+/// remembered document. This makes it cheap to rebuild a figment from the
+/// same provider, as when reloading, without transferring an unchanged
+/// document again.
+///
+/// # Clients
+///
+/// Requests are made by an [`HttpClient`]. The default, [`StdClient`], uses
+/// only the standard library and so supports only plain `http://` URLs. To
+/// fetch `https://` URLs, implement `HttpClient` over a client with TLS
+/// support, such as `ureq` or `reqwest`, and set it via [`Http::client()`].
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider is named `${NAME} over HTTP`, where `${NAME}` is
+///     [`Format::NAME`]. The URL is specified as [`Source::Url`]. Path
+///     interpolation is unchanged from the default. With [`Http::checksum()`],
+///     the document's checksum is recorded.
+///
+///   * **Data**
+///
+///     The document is fetched and parsed exactly as a [`Data`] provider
+///     would parse it as a string: into the profile configurable via
+///     [`Http::profile()`], which defaults to [`Profile::Default`], or, with
+///     [`Http::nested()`], with top-level keys as profiles. If the request
+///     fails, the response status is not successful, or the document is
+///     larger than [`Http::max_size()`], the provider fails.
+///
+/// [`Data`]: crate::providers::Data
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{BufRead, BufReader, Write};
+/// use std::net::TcpListener;
+///
+/// use serde::Deserialize;
+/// use figment::{Figment, Jail, providers::{Env, Http, Toml}};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// // A config service that serves a TOML document, once.
+/// let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+/// let url = format!("http://{}/app.toml", listener.local_addr().unwrap());
+/// std::thread::spawn(move || {
+///     let (mut stream, _) = listener.accept().unwrap();
+///     let mut reader = BufReader::new(stream.try_clone().unwrap());
+///     let mut line = String::new();
+///     while reader.read_line(&mut line).unwrap() > 2 { line.clear(); }
+///
+///     let body = "name = \"app\"\nport = 80\n";
+///     let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+///     stream.write_all(head.as_bytes()).unwrap();
+///     stream.write_all(body.as_bytes()).unwrap();
+/// });
+///
+/// Jail::expect_with(|jail| {
+///     jail.set_env("APP_PORT", 8080);
+///
+///     let config: Config = Figment::from(Http::<Toml>::new(&url))
+///         .merge(Env::prefixed("APP_"))
+///         .extract()?;
+///
+///     assert_eq!(config, Config { name: "app".into(), port: 8080 });
+///     Ok(())
+/// });
+/// ```
+pub struct Http<F: Format> {
+    url: String,
+    /// The profile data will be emitted to if nesting is disabled. Defaults to
+    /// [`Profile::Default`].
+    pub profile: Option<Profile>,
+    timeout: Duration,
+    max_size: usize,
+    client: Arc<dyn HttpClient>,
+    cache: Arc<Mutex<Option<Cached>>>,
+    checksum: bool,
+    /// The checksum of the last document fetched, if checksums are enabled.
+    sum: Arc<Mutex<Option<u64>>>,
+    _format: PhantomData<F>,
+}
+
+/// A client that performs the requests made by an [`Http`] provider.
+///
+/// Implementations need not handle caching, which `Http` does itself, but
+/// should follow redirects, honor the request's `timeout`, and stop reading a
+/// response whose body exceeds the request's `max_size`.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, Error, providers::{Http, Json}};
+/// use figment::providers::{HttpClient, HttpRequest, HttpResponse};
+///
+/// /// A client that always responds with the same document.
+/// struct Canned(&'static str);
+///
+/// impl HttpClient for Canned {
+///     fn get(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+///         assert_eq!(request.url, "https://config.example.com/app.json");
+///         Ok(HttpResponse::new(200, self.0))
+///     }
+/// }
+///
+/// let http = Http::<Json>::new("https://config.example.com/app.json")
+///     .client(Canned(r#"{ "port": 8080 }"#));
+///
+/// let port: u16 = Figment::from(http).extract_inner("port").unwrap();
+/// assert_eq!(port, 8080);
+/// ```
+pub trait HttpClient: Send + Sync + 'static {
+    /// Performs the `GET` `request`, returning the final response after any
+    /// redirects. Returns an error only if no response was received: error
+    /// statuses are returned as responses.
+    fn get(&self, request: &HttpRequest) -> Result<HttpResponse, Error>;
+}
+
+/// A `GET` request made by an [`Http`] provider via an [`HttpClient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    /// The URL to fetch.
+    pub url: String,
+    /// Additional request headers, such as `If-None-Match`, as name-value
+    /// pairs.
+    pub headers: Vec<(String, String)>,
+    /// The maximum time to wait for each of connecting, sending the request,
+    /// and reading the response.
+    pub timeout: Duration,
+    /// The maximum size of the response body in bytes. A larger response is
+    /// an error.
+    pub max_size: usize,
+}
+
+/// A response to an [`HttpRequest`], returned by an [`HttpClient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    /// The response's status code.
+    pub status: u16,
+    /// The response's headers as name-value pairs.
+    pub headers: Vec<(String, String)>,
+    /// The response's body.
+    pub body: String,
+}
+
+/// An [`HttpClient`] that uses only the standard library.
+///
+/// The client is deliberately minimal. It supports plain `http://` URLs,
+/// `Content-Length`, `chunked`, and close-delimited bodies, and follows up to
+/// five redirects, resolving relative `Location`s against the request's URL.
+/// It doesn't support:
+///
+///   * TLS: `https://` URLs, like those of any other scheme, fail
+///   * persistent connections: every request uses a new connection
+///   * compression: only `identity` encoded bodies are accepted
+///   * proxies, authentication, or cookies
+///
+/// For any of these, implement [`HttpClient`] over a full-featured client.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdClient;
+
+/// The default maximum size of a document: 10MiB.
+const DEFAULT_MAX_SIZE: usize = 10 << 20;
+
+/// The maximum size of a response's status line and headers read by
+/// [`StdClient`].
+const MAX_HEAD_SIZE: usize = 64 << 10;
+
+/// A document remembered from a previous response.
+#[derive(Debug, Clone)]
+struct Cached {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+impl<F: Format> Http<F> {
+    /// Returns an `Http` provider that fetches the document at `url` with
+    /// [`StdClient`] and parses it as format `F`. Nothing is fetched until the
+    /// provider is used.
+    ///
+    /// Nesting is disabled by default. Use [`Http::nested()`] to enable it.
+    ///
+    /// ```rust
+    /// use figment::providers::{Http, Json};
+    ///
+    /// let http = Http::<Json>::new("http://config.internal/app.json");
+    /// ```
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Http {
+            url: url.into(),
+            profile: Some(Profile::Default),
+            timeout: Duration::from_secs(30),
+            max_size: DEFAULT_MAX_SIZE,
+            client: Arc::new(StdClient),
+            cache: Arc::new(Mutex::new(None)),
+            checksum: false,
+            sum: Arc::new(Mutex::new(None)),
+            _format: PhantomData,
+        }
+    }
+
+    /// Enables nesting: top-level keys in the document are treated as
+    /// profiles. See [`Data::nested()`](crate::providers::Data::nested()).
+    ///
+    /// ```rust
+    /// use figment::providers::{Http, Toml};
+    ///
+    /// let http = Http::<Toml>::new("http://config.internal/app.toml").nested();
+    /// ```
+    pub fn nested(mut self) -> Self {
+        self.profile = None;
+        self
+    }
+
+    /// Set the profile to emit data to when nesting is disabled.
+    ///
+    /// ```rust
+    /// use figment::providers::{Http, Toml};
+    ///
+    /// let http = Http::<Toml>::new("http://config.internal/app.toml").profile("debug");
+    /// ```
+    pub fn profile<P: Into<Profile>>(mut self, profile: P) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Sets the maximum time to wait for each of connecting, sending the
+    /// request, and reading the response. Defaults to 30 seconds.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use figment::providers::{Http, Toml};
+    ///
+    /// let http = Http::<Toml>::new("http://config.internal/app.toml")
+    ///     .timeout(Duration::from_secs(5));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum size of the fetched document in bytes. Fetching a
+    /// larger document fails without reading it entirely. Defaults to 10MiB.
+    ///
+    /// ```rust
+    /// use figment::providers::{Http, Toml};
+    ///
+    /// let http = Http::<Toml>::new("http://config.internal/app.toml")
+    ///     .max_size(64 * 1024);
+    /// ```
+    pub fn max_size(mut self, bytes: usize) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Sets the client used to make requests. See [`HttpClient`] for an
+    /// example.
+    pub fn client<C: HttpClient>(mut self, client: C) -> Self {
+        self.client = Arc::new(client);
+        self
+    }
+
+    /// Returns the `ETag` of the last document fetched, if any and if it had
+    /// one. Sent as `If-None-Match` in subsequent requests.
+    pub fn etag(&self) -> Option<String> {
+        self.cached().and_then(|cached| cached.etag)
+    }
+
+    /// Returns the `Last-Modified` date of the last document fetched, if any
+    /// and if it had one. Sent as `If-Modified-Since` in subsequent requests.
+    pub fn last_modified(&self) -> Option<String> {
+        self.cached().and_then(|cached| cached.last_modified)
+    }
+
+    fn cached(&self) -> Option<Cached> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Fetches the document, from the cache if it's unchanged.
+    fn fetch(&self) -> Result<String, Error> {
+        fetch(&self.url, self.timeout, self.max_size, &*self.client, &self.cache)
+    }
+
+    /// Sets whether the checksum of the fetched document is recorded in the
+    /// provider's metadata via [`Metadata::checksum()`], so that a change to
+    /// the document can be detected with [`Metadata::source_changed()`] or
+    /// [`Figment::sources_changed()`](crate::Figment::sources_changed()).
+    /// Disabled by default.
+    ///
+    /// When enabled, the checksum of the document fetched for the provider's
+    /// data is recorded; computing the metadata makes no request. Checking
+    /// for a change fetches the document again, from the cache if the server
+    /// supports it. A document that fails to fetch has no checksum.
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, Mutex};
+    /// use figment::{Figment, Error, providers::{Http, Json}};
+    /// use figment::providers::{HttpClient, HttpRequest, HttpResponse};
+    ///
+    /// struct Shared(Arc<Mutex<&'static str>>);
+    ///
+    /// impl HttpClient for Shared {
+    ///     fn get(&self, _: &HttpRequest) -> Result<HttpResponse, Error> {
+    ///         Ok(HttpResponse::new(200, *self.0.lock().unwrap()))
+    ///     }
+    /// }
+    ///
+    /// let document = Arc::new(Mutex::new(r#"{ "port": 8080 }"#));
+    /// let http = Http::<Json>::new("https://config.example.com/app.json")
+    ///     .client(Shared(document.clone()))
+    ///     .checksum(true);
+    ///
+    /// let figment = Figment::from(http);
+    /// assert!(figment.sources_changed().is_empty());
+    ///
+    /// *document.lock().unwrap() = r#"{ "port": 9090 }"#;
+    /// assert_eq!(figment.sources_changed().len(), 1);
+    /// ```
+    pub fn checksum(mut self, yes: bool) -> Self {
+        self.checksum = yes;
+        self
+    }
+}
+
+impl<F: Format> Clone for Http<F> {
+    fn clone(&self) -> Self {
+        Http {
+            url: self.url.clone(),
+            profile: self.profile.clone(),
+            timeout: self.timeout,
+            max_size: self.max_size,
+            client: self.client.clone(),
+            cache: self.cache.clone(),
+            checksum: self.checksum,
+            sum: self.sum.clone(),
+            _format: PhantomData,
+        }
+    }
+}
+
+impl<F: Format> fmt::Debug for Http<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Http")
+            .field("url", &self.url)
+            .field("profile", &self.profile)
+            .field("timeout", &self.timeout)
+            .field("max_size", &self.max_size)
+            .field("etag", &self.etag())
+            .field("last_modified", &self.last_modified())
+            .field("checksum", &self.checksum)
+            .finish()
+    }
+}
+
+impl<F: Format> Provider for Http<F> {
+    fn metadata(&self) -> Metadata {
+        let name = format!("{} over HTTP", F::NAME);
+        let metadata = Metadata::from(name, Source::Url(self.url.clone()));
+        if !self.checksum {
+            return metadata;
+        }
+
+        let (url, timeout, max_size) = (self.url.clone(), self.timeout, self.max_size);
+        let (client, cache) = (self.client.clone(), self.cache.clone());
+        let sum = move || {
+            let document = fetch(&url, timeout, max_size, &*client, &cache).ok()?;
+            Some(crate::util::checksum(document.as_bytes()))
+        };
+
+        let fetched = *self.sum.lock().unwrap_or_else(|e| e.into_inner());
+        metadata.checksum(fetched, sum)
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let document = self.fetch();
+        if self.checksum {
+            let sum = document.as_ref().ok().map(|d| crate::util::checksum(d.as_bytes()));
+            *self.sum.lock().unwrap_or_else(|e| e.into_inner()) = sum;
+        }
+
+        let mut data = F::string(&document?);
+        data.profile = self.profile.clone();
+        data.data()
+    }
+}
+
+impl HttpResponse {
+    /// Returns a response with status `status`, body `body`, and no headers.
+    ///
+    /// ```rust
+    /// use figment::providers::HttpResponse;
+    ///
+    /// let response = HttpResponse::new(200, "port = 8080")
+    ///     .with_header("ETag", "\"v1\"");
+    ///
+    /// assert_eq!(response.header("etag"), Some("\"v1\""));
+    /// ```
+    pub fn new<B: Into<String>>(status: u16, body: B) -> Self {
+        HttpResponse { status, headers: vec![], body: body.into() }
+    }
+
+    /// Adds the header `name` with value `value` to `self`.
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns the value of the first header named `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Fetches the document at `url`, from `cache` if it's unchanged.
+fn fetch(
+    url: &str,
+    timeout: Duration,
+    max_size: usize,
+    client: &dyn HttpClient,
+    cache: &Mutex<Option<Cached>>,
+) -> Result<String, Error> {
+    let error = |msg: String| -> Error {
+        Kind::Message(format!("failed to fetch `{}`: {}", url, msg)).into()
+    };
+
+    let cached = cache.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let mut request = HttpRequest {
+        url: url.to_string(),
+        headers: vec![],
+        timeout,
+        max_size,
+    };
+
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            request.headers.push(("If-None-Match".into(), etag.clone()));
+        }
+
+        if let Some(date) = &cached.last_modified {
+            request.headers.push(("If-Modified-Since".into(), date.clone()));
+        }
+    }
+
+    let response = client.get(&request).map_err(|e| error(e.to_string()))?;
+    if response.body.len() > max_size {
+        return Err(error(format!("response exceeds the maximum size of {} bytes", max_size)));
+    }
+
+    match (response.status, cached) {
+        (304, Some(cached)) => Ok(cached.body),
+        (200..=299, _) => {
+            let etag = response.header("ETag").map(String::from);
+            let last_modified = response.header("Last-Modified").map(String::from);
+            let cached = match etag.is_some() || last_modified.is_some() {
+                true => Some(Cached { etag, last_modified, body: response.body.clone() }),
+                false => None,
+            };
+
+            *cache.lock().unwrap_or_else(|e| e.into_inner()) = cached;
+            Ok(response.body)
+        }
+        (status, _) => Err(error(format!("server responded with status {}", status))),
+    }
+}
+
+/// The most redirects [`StdClient`] follows.
+const MAX_REDIRECTS: usize = 5;
+
+impl StdClient {
+    /// Performs a single request for `url`, without following redirects.
+    fn request(url: &str, request: &HttpRequest) -> Result<HttpResponse, String> {
+        let rest = match url.split_once("://") {
+            Some((scheme, rest)) if scheme.eq_ignore_ascii_case("http") => rest,
+            Some((scheme, _)) => return Err(format!("unsupported scheme `{}`: \
+                use `Http::client()` to set a client that supports it", scheme)),
+            None => return Err("URL is missing a scheme".into()),
+        };
+
+        let rest = rest.split('#').next().unwrap_or(rest);
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(i) if rest[i..].starts_with('?') => (&rest[..i], format!("/{}", &rest[i..])),
+            Some(i) => (&rest[..i], rest[i..].to_string()),
+            None => (rest, "/".to_string()),
+        };
+
+        let host_port = authority.rsplit('@').next().unwrap_or(authority);
+        let (host, port) = match host_port.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => {
+                (host, port.parse::<u16>().map_err(|_| format!("invalid port `{}`", port))?)
+            }
+            _ => (host_port, 80),
+        };
+
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let addrs = (host, port).to_socket_addrs().map_err(|e| e.to_string())?;
+        let mut last_error = format!("`{}` did not resolve to an address", host);
+        let mut stream = None;
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, request.timeout) {
+                Ok(s) => { stream = Some(s); break; }
+                Err(e) => last_error = e.to_string(),
+            }
+        }
+
+        let mut stream = stream.ok_or(last_error)?;
+        let io = |e: std::io::Error| e.to_string();
+        stream.set_write_timeout(Some(request.timeout)).map_err(io)?;
+
+        let mut head = format!("GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\
+            Accept-Encoding: identity\r\nUser-Agent: figment/{}\r\n",
+            path, host_port, env!("CARGO_PKG_VERSION"));
+
+        for (name, value) in &request.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes()).map_err(io)?;
+
+        let raw = Self::read(&mut stream, request)?;
+        Self::parse(&raw).ok_or_else(|| "malformed HTTP response".to_string())?
+    }
+
+    /// Reads a response from `stream` until the server closes the connection,
+    /// failing if reading takes longer than the request's timeout in total or
+    /// the response is larger than its maximum size permits.
+    fn read(stream: &mut TcpStream, request: &HttpRequest) -> Result<Vec<u8>, String> {
+        use std::io::ErrorKind::{Interrupted, WouldBlock, TimedOut};
+
+        let deadline = Instant::now() + request.timeout;
+        let limit = request.max_size.saturating_add(MAX_HEAD_SIZE);
+        let (mut raw, mut buf) = (vec![], [0; 8192]);
+        loop {
+            let remaining = deadline.checked_duration_since(Instant::now())
+                .filter(|remaining| !remaining.is_zero())
+                .ok_or("timed out reading the response")?;
+
+            stream.set_read_timeout(Some(remaining)).map_err(|e| e.to_string())?;
+            match stream.read(&mut buf) {
+                Ok(0) => return Ok(raw),
+                Ok(n) => raw.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == Interrupted => continue,
+                Err(e) if e.kind() == WouldBlock || e.kind() == TimedOut => {
+                    return Err("timed out reading the response".into());
+                }
+                Err(e) => return Err(e.to_string()),
+            }
+
+            if raw.len() > limit {
+                let max = request.max_size;
+                return Err(format!("response exceeds the maximum size of {} bytes", max));
+            }
+        }
+    }
+
+    /// Parses a complete HTTP/1.x response.
+    fn parse(raw: &[u8]) -> Option<Result<HttpResponse, String>> {
+        let split = raw.windows(4).position(|w| w == b"\r\n\r\n")?;
+        let (head, body) = (std::str::from_utf8(&raw[..split]).ok()?, &raw[split + 4..]);
+        let mut lines = head.split("\r\n");
+        let status = lines.next()?.split(' ').nth(1)?.parse().ok()?;
+        let headers = lines
+            .map(|line| line.split_once(':').map(|(k, v)| (k.trim().into(), v.trim().into())))
+            .collect::<Option<Vec<(String, String)>>>()?;
+
+        let mut response = HttpResponse { status, headers, body: String::new() };
+        let encoding = response.header("Transfer-Encoding");
+        let chunked = matches!(encoding, Some(v) if v.eq_ignore_ascii_case("chunked"));
+
+        let body = match (chunked, response.header("Content-Length")) {
+            (true, _) => Self::dechunk(body)?,
+            (false, Some(len)) => body.get(..len.parse().ok()?)?.to_vec(),
+            (false, None) => body.to_vec(),
+        };
+
+        match String::from_utf8(body) {
+            Ok(body) => response.body = body,
+            Err(_) => return Some(Err("response body is not valid UTF-8".into())),
+        }
+
+        Some(Ok(response))
+    }
+
+    /// Decodes a `chunked` transfer-encoded body.
+    fn dechunk(mut body: &[u8]) -> Option<Vec<u8>> {
+        let mut decoded = vec![];
+        loop {
+            let line_end = body.windows(2).position(|w| w == b"\r\n")?;
+            let size = std::str::from_utf8(&body[..line_end]).ok()?;
+            let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+            body = &body[line_end + 2..];
+            if size == 0 {
+                return Some(decoded);
+            }
+
+            decoded.extend_from_slice(body.get(..size)?);
+            body = body.get(size + 2..)?;
+        }
+    }
+}
+
+impl HttpClient for StdClient {
+    fn get(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        let mut url = request.url.clone();
+        for _ in 0..=MAX_REDIRECTS {
+            let response = Self::request(&url, request)?;
+            let location = match (response.status, response.header("Location")) {
+                (301 | 302 | 303 | 307 | 308, Some(location)) => location,
+                _ => return Ok(response),
+            };
+
+            url = resolve(&url, location);
+        }
+
+        Err(Kind::Message(format!("more than {} redirects", MAX_REDIRECTS)).into())
+    }
+}
+
+/// Resolves the URI reference `reference` against the absolute URL `base` as
+/// described in [RFC 3986 §5.2](https://www.rfc-editor.org/rfc/rfc3986#section-5.2).
+fn resolve(base: &str, reference: &str) -> String {
+    let scheme_len = reference.find(':').filter(|&i| {
+        let scheme = &reference[..i];
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    });
+
+    if scheme_len.is_some() {
+        return reference.to_string();
+    }
+
+    let base = base.split('#').next().unwrap_or(base);
+    let (scheme, rest) = base.split_once("://").unwrap_or(("http", base));
+    if let Some(reference) = reference.strip_prefix("//") {
+        return format!("{}://{}", scheme, reference);
+    }
+
+    let end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, rest) = rest.split_at(end);
+    let (path, query) = match rest.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (rest, None),
+    };
+
+    let (reference, fragment) = match reference.split_once('#') {
+        Some((reference, fragment)) => (reference, Some(fragment)),
+        None => (reference, None),
+    };
+
+    let (ref_path, ref_query) = match reference.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (reference, None),
+    };
+
+    let (path, query) = match ref_path {
+        "" => (path.to_string(), ref_query.or(query)),
+        _ if ref_path.starts_with('/') => (remove_dot_segments(ref_path), ref_query),
+        _ => {
+            let dir = &path[..path.rfind('/').map_or(0, |i| i + 1)];
+            let dir = if dir.is_empty() { "/" } else { dir };
+            (remove_dot_segments(&format!("{}{}", dir, ref_path)), ref_query)
+        }
+    };
+
+    let mut url = format!("{}://{}{}", scheme, authority, path);
+    if let Some(query) = query {
+        url.push('?');
+        url.push_str(query);
+    }
+
+    if let Some(fragment) = fragment {
+        url.push('#');
+        url.push_str(fragment);
+    }
+
+    url
+}
+
+/// Removes the `.` and `..` segments from the absolute path `path`.
+fn remove_dot_segments(path: &str) -> String {
+    let mut output: Vec<&str> = vec![];
+    let segments: Vec<&str> = path.split('/').skip(1).collect();
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i + 1 == segments.len();
+        match *segment {
+            "." => if last { output.push("") },
+            ".." => {
+                output.pop();
+                if last {
+                    output.push("");
+                }
+            }
+            segment => output.push(segment),
+        }
+    }
+
+    format!("/{}", output.join("/"))
+}
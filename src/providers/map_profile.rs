@@ -0,0 +1,118 @@
+use std::fmt;
+
+use crate::{Profile, Provider, Metadata, Resolution};
+use crate::coalesce::Coalescible;
+use crate::error::Error;
+use crate::value::{Map, Dict, Tag, Value};
+
+/// A [`Provider`] adapter that renames the profiles another provider emits.
+///
+/// Created via [`Provider::map_profile()`]. This allows providers with
+/// profile naming conventions that differ from the application's, like a
+/// third-party provider that emits a `release` profile where the application
+/// expects `prod`, to be used as-is.
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider sets the profile of the wrapped provider, if any, after
+///     mapping it.
+///
+///   * **Metadata**
+///
+///     This provider's metadata is that of the wrapped provider.
+///
+///   * **Data**
+///
+///     The data of the wrapped provider, with every profile mapped by the
+///     function. When several profiles map to the same profile, their data is
+///     merged: the data of profiles that were renamed is merged in the order
+///     of their original names, followed by the data of the profile that
+///     already had the name, if any, which thus takes precedence.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, Profile, Provider, providers::{Format, Toml}};
+///
+/// let toml = Toml::string(r#"
+///     [debug]
+///     opt_level = 0
+///
+///     [release]
+///     opt_level = 3
+///
+///     [release-lto]
+///     lto = true
+/// "#).nested();
+///
+/// let figment = Figment::from(toml.map_profile(|p: Profile| match p.as_str().as_str() {
+///     "release" | "release-lto" => "prod".into(),
+///     "debug" => "dev".into(),
+///     _ => p,
+/// }));
+///
+/// let prod = figment.clone().select("prod");
+/// assert_eq!(prod.extract_inner::<u8>("opt_level").unwrap(), 3);
+/// assert!(prod.extract_inner::<bool>("lto").unwrap());
+///
+/// let dev = figment.select("dev");
+/// assert_eq!(dev.extract_inner::<u8>("opt_level").unwrap(), 0);
+/// assert!(!dev.contains("release"));
+/// ```
+pub struct MapProfile<P> {
+    provider: P,
+    f: Box<dyn Fn(Profile) -> Profile>,
+}
+
+impl<P: Provider> MapProfile<P> {
+    pub(crate) fn new<F>(provider: P, f: F) -> Self
+        where F: Fn(Profile) -> Profile + 'static
+    {
+        MapProfile { provider, f: Box::new(f) }
+    }
+}
+
+impl<P: Provider> Provider for MapProfile<P> {
+    fn metadata(&self) -> Metadata {
+        Metadata::adapted(self.provider.metadata(), "profile mapped")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let (mut renamed, mut kept) = (Map::<Profile, Dict>::new(), Map::new());
+        for (profile, dict) in self.provider.data()? {
+            let mapped = (self.f)(profile.clone());
+            let map = if mapped == profile { &mut kept } else { &mut renamed };
+            let dict = match map.remove(&mapped) {
+                Some(existing) => existing.merge(dict),
+                None => dict,
+            };
+
+            map.insert(mapped, dict);
+        }
+
+        Ok(renamed.merge(kept))
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.provider.profile().map(|p| (self.f)(p))
+    }
+
+    fn on_conflict(&self, key: &str, old: Option<&Value>, new: &Value) -> Resolution {
+        self.provider.on_conflict(key, old, new)
+    }
+
+    #[doc(hidden)]
+    fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> {
+        self.provider.__metadata_map()
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for MapProfile<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapProfile")
+            .field("provider", &self.provider)
+            .finish_non_exhaustive()
+    }
+}
@@ -5,8 +5,45 @@
 
 mod serialized;
 mod data;
-mod env;
+pub(crate) mod env;
+mod env_docs;
+mod dotenv;
+mod directory;
+mod discover;
+mod on_first_use;
+mod map_profile;
+mod otel;
+mod uri;
+#[cfg(feature = "http")] mod http;
+#[cfg(feature = "clap")] mod cli;
+mod statics;
+mod when;
+mod patch;
+mod platform;
+mod registry;
 
 pub use self::env::Env;
+pub use self::env_docs::{EnvDocs, EnvVar};
+pub use self::dotenv::DotEnv;
+pub use self::directory::Directory;
+pub use self::discover::Discover;
+pub use self::on_first_use::OnFirstUse;
+pub use self::otel::Otel;
+pub use self::uri::Uri;
+#[cfg(feature = "http")]
+#[cfg_attr(nightly, doc(cfg(feature = "http")))]
+pub use self::http::{Http, HttpClient, HttpRequest, HttpResponse, StdClient};
+#[cfg(feature = "clap")]
+#[cfg_attr(nightly, doc(cfg(feature = "clap")))]
+pub use self::cli::CliArgs;
+pub use self::statics::{Static, StaticValue};
+pub use self::when::When;
+pub use self::map_profile::MapProfile;
+pub use self::patch::{MergePatch, JsonPatch, PatchOp};
+pub use self::platform::Platform;
+pub use self::registry::Registry;
 pub use self::serialized::Serialized;
+#[cfg(feature = "async")]
+#[cfg_attr(nightly, doc(cfg(feature = "async")))]
+pub use crate::async_provider::Blocking;
 pub use self::data::*;
@@ -0,0 +1,337 @@
+use std::fmt;
+use std::panic::Location;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, ThreadId};
+
+use serde::Serialize;
+
+use crate::{Profile, Provider, Metadata, Resolution};
+use crate::error::{Error, Kind};
+use crate::providers::Serialized;
+use crate::value::{Map, Dict, Tag, Value};
+
+/// A [`Provider`] that defers creating another provider until its data is
+/// first requested.
+///
+/// An `OnFirstUse` wraps a closure that returns a provider. The closure is
+/// called at most once, the first time the `OnFirstUse` is asked for its data,
+/// and the provider it returns is reused thereafter. This is useful for
+/// providers that are expensive to create, like those that fetch data over the
+/// network or look up credentials, when they are declared before, or without,
+/// being used.
+///
+/// A figment asks a provider for its data when the provider is merged or
+/// joined, so the closure runs when the `OnFirstUse` is combined into a
+/// figment, not when the figment is extracted from, and without knowledge of
+/// the profile the figment will select. Creating the provider only when its
+/// data is needed for the selected profile, at extraction time, is not
+/// supported: a figment holds the data of its providers, not the providers
+/// themselves. Instead, to create the provider only in some circumstances,
+/// such as when a given profile is selected, combine it conditionally via
+/// [`Provider::when()`]: the provider isn't created if the condition doesn't
+/// hold. To create the provider again for every extraction, add the
+/// `OnFirstUse` to an [`AppConfig`](crate::app::AppConfig), which combines its
+/// providers on each extraction.
+///
+/// The closure is called without holding any lock, so it may itself use other
+/// providers. If it uses the `OnFirstUse` it's creating, that use fails
+/// rather than deadlocking.
+///
+/// Creating the provider may fail: see [`OnFirstUse::try_new()`] and, for
+/// default values computed by fallible functions, [`OnFirstUse::default()`].
+/// A failure is reported as an error from the provider's data, and thus from
+/// extraction, attributed to the source location where the `OnFirstUse` was
+/// created.
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider sets the profile of the created provider, if any.
+///
+///   * **Metadata**
+///
+///     This provider's metadata is that of the created provider or, if it
+///     hasn't been created or creating it failed, is named `provider created
+///     on first use` with a source of the location where the `OnFirstUse` was
+///     created.
+///
+///   * **Data**
+///
+///     The data emitted is the data of the created provider or, if creating
+///     it failed, the error.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use figment::{Figment, Profile, Provider, providers::{OnFirstUse, Serialized}};
+///
+/// static CALLS: AtomicUsize = AtomicUsize::new(0);
+///
+/// let vault = || OnFirstUse::new(|| {
+///     CALLS.fetch_add(1, Ordering::SeqCst);
+///     Serialized::default("token", "s3cr3t")
+/// });
+///
+/// let token = vault();
+/// assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+///
+/// let figment = Figment::from(&token).merge(&token);
+/// assert_eq!(figment.extract_inner::<String>("token").unwrap(), "s3cr3t");
+/// assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+///
+/// // Only create the provider in production.
+/// let profile = Profile::from_env_or("APP_PROFILE", "debug");
+/// let figment = Figment::new()
+///     .merge(vault().when(move || profile == "prod"));
+///
+/// assert!(figment.extract_inner::<String>("token").is_err());
+/// assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+/// ```
+pub struct OnFirstUse<P> {
+    state: Mutex<State<P>>,
+    created: Condvar,
+    location: &'static Location<'static>,
+}
+
+type Init<P> = Box<dyn FnOnce() -> Result<P, Error> + Send>;
+
+enum State<P> {
+    Pending(Option<Init<P>>),
+    Creating(ThreadId),
+    Ready(Arc<Result<P, Box<Error>>>),
+}
+
+/// Resets an `OnFirstUse` whose closure panicked, waking any waiting threads.
+struct Unwind<'a, P>(&'a OnFirstUse<P>);
+
+impl<P: Provider> OnFirstUse<P> {
+    /// Returns an `OnFirstUse` that creates its provider by calling `f` when
+    /// its data is first requested.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Format, OnFirstUse, Toml}};
+    ///
+    /// figment::Jail::expect_with(|jail| {
+    ///     let provider = OnFirstUse::new(|| Toml::file("App.toml"));
+    ///
+    ///     // The file is read when the provider is used, not when it's declared.
+    ///     jail.create_file("App.toml", "name = \"app\"")?;
+    ///     let figment = Figment::from(provider);
+    ///     assert_eq!(figment.extract_inner::<String>("name")?, "app");
+    ///     Ok(())
+    /// });
+    /// ```
+    #[track_caller]
+    pub fn new<F>(f: F) -> Self
+        where F: FnOnce() -> P + Send + 'static
+    {
+        OnFirstUse::try_new(move || Ok::<_, std::convert::Infallible>(f()))
+    }
+
+    /// Returns an `OnFirstUse` that creates its provider by calling `f` when
+    /// its data is first requested, or fails with the error `f` returns.
+    ///
+    /// The error is reported as an error from the provider's data, with the
+    /// message of `f`'s error, attributed to the source location of the call
+    /// to `try_new()`.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{OnFirstUse, Serialized}};
+    ///
+    /// let token = OnFirstUse::try_new(|| match std::env::var("VAULT_TOKEN_FOR_TEST") {
+    ///     Ok(token) => Ok(Serialized::default("token", token)),
+    ///     Err(e) => Err(format!("vault token unavailable: {}", e)),
+    /// });
+    ///
+    /// let error = Figment::from(token).extract_inner::<String>("token").unwrap_err();
+    /// assert!(error.to_string().starts_with("vault token unavailable"));
+    /// assert!(error.to_string().contains("provider created on first use"));
+    /// ```
+    #[track_caller]
+    pub fn try_new<F, E>(f: F) -> Self
+        where F: FnOnce() -> Result<P, E> + Send + 'static, E: fmt::Display
+    {
+        let init = move || f().map_err(|e| Error::from(Kind::Message(e.to_string())));
+        OnFirstUse {
+            state: Mutex::new(State::Pending(Some(Box::new(init)))),
+            created: Condvar::new(),
+            location: Location::caller(),
+        }
+    }
+
+    /// Returns `true` if the provider has been created.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{OnFirstUse, Serialized}};
+    ///
+    /// let provider = OnFirstUse::new(|| Serialized::default("key", "value"));
+    /// assert!(!provider.is_ready());
+    ///
+    /// let _ = Figment::from(&provider);
+    /// assert!(provider.is_ready());
+    /// ```
+    pub fn is_ready(&self) -> bool {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        matches!(*state, State::Ready(_))
+    }
+
+    /// Calls `f` with the provider or the error creating it, creating it first
+    /// if necessary. If another thread is creating the provider, waits for it
+    /// to finish. Neither the closure creating the provider nor `f` is called
+    /// with the state locked.
+    fn with<T, F: FnOnce(&Result<P, Box<Error>>) -> T>(&self, f: F) -> T {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let provider = loop {
+            match &mut *state {
+                State::Ready(provider) => break provider.clone(),
+                State::Creating(id) if *id == thread::current().id() => {
+                    let msg = "provider was used while it was being created";
+                    return f(&Err(Box::new(Kind::Message(msg.into()).into())));
+                }
+                State::Creating(_) => {
+                    state = self.created.wait(state).unwrap_or_else(|e| e.into_inner());
+                }
+                State::Pending(init) => {
+                    let init = init.take().expect("provider creation panicked previously");
+                    *state = State::Creating(thread::current().id());
+                    drop(state);
+
+                    let unwind = Unwind(self);
+                    let provider = Arc::new(init().map_err(Box::new));
+                    std::mem::forget(unwind);
+
+                    state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                    *state = State::Ready(provider.clone());
+                    self.created.notify_all();
+                    break provider;
+                }
+            }
+        };
+
+        drop(state);
+        f(&provider)
+    }
+
+    /// Calls `f` with the provider or the error creating it, if it has been
+    /// created, or else with `None`.
+    fn peek<T, F: FnOnce(Option<&Result<P, Box<Error>>>) -> T>(&self, f: F) -> T {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let provider = match &*state {
+            State::Ready(provider) => Some(provider.clone()),
+            State::Pending(_) | State::Creating(_) => None,
+        };
+
+        drop(state);
+        f(provider.as_deref())
+    }
+}
+
+impl<T: Serialize> OnFirstUse<Serialized<T>> {
+    /// Returns an `OnFirstUse` that emits the value returned by `f` at the key
+    /// path `key` in the default profile, as [`Serialized::default()`] does, or
+    /// fails with the error `f` returns. `f` is called when the
+    /// `OnFirstUse`'s data is first requested.
+    ///
+    /// This replaces constructing a [`Serialized`] from a value computed by a
+    /// fallible function, which requires handling, or panicking on, the error
+    /// before the figment is built. Here, the error is instead reported as a
+    /// figment error attributed to the source location of the call to
+    /// `default()`, which is also the source of the value when `f` succeeds.
+    /// As with any provider, an error fails the figment even if the key is
+    /// also set by another provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::OnFirstUse};
+    ///
+    /// fn generate_id() -> Result<u64, std::io::Error> {
+    ///     Ok(42)
+    /// }
+    ///
+    /// fn read_secret() -> Result<String, std::io::Error> {
+    ///     Err(std::io::Error::new(std::io::ErrorKind::NotFound, "secret.key not found"))
+    /// }
+    ///
+    /// let figment = Figment::new().join(OnFirstUse::default("node.id", generate_id));
+    /// assert_eq!(figment.extract_inner::<u64>("node.id").unwrap(), 42);
+    ///
+    /// let figment = figment.join(OnFirstUse::default("secret", read_secret));
+    /// let error = figment.extract_inner::<String>("secret").unwrap_err();
+    /// assert!(error.to_string().starts_with("secret.key not found"));
+    /// assert!(error.metadata.unwrap().source.unwrap().code_location().is_some());
+    /// ```
+    #[track_caller]
+    pub fn default<F, E>(key: &str, f: F) -> Self
+        where F: FnOnce() -> Result<T, E> + Send + 'static, E: fmt::Display
+    {
+        let (key, location) = (key.to_string(), Location::caller());
+        OnFirstUse::try_new(move || f().map(|v| Serialized::default(&key, v).located(location)))
+    }
+}
+
+impl<P: Provider> Provider for OnFirstUse<P> {
+    fn metadata(&self) -> Metadata {
+        self.peek(|p| match p {
+            Some(Ok(p)) => p.metadata(),
+            _ => Metadata::from("provider created on first use", self.location),
+        })
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        self.with(|p| match p {
+            Ok(p) => p.data(),
+            Err(e) => Err((**e).clone()),
+        })
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.peek(|p| p?.as_ref().ok()?.profile())
+    }
+
+    fn on_conflict(&self, key: &str, old: Option<&Value>, new: &Value) -> Resolution {
+        self.peek(|p| match p {
+            Some(Ok(p)) => p.on_conflict(key, old, new),
+            _ => Resolution::Default,
+        })
+    }
+
+    #[doc(hidden)]
+    fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> {
+        self.with(|p| p.as_ref().ok()?.__metadata_map())
+    }
+
+    #[doc(hidden)]
+    fn __patch(&self, data: &mut Map<Profile, Dict>, tag: Tag) -> Result<(), Error> {
+        self.peek(|p| match p {
+            Some(Ok(p)) => p.__patch(data, tag),
+            _ => Ok(()),
+        })
+    }
+}
+
+impl<P> Drop for Unwind<'_, P> {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock().unwrap_or_else(|e| e.into_inner());
+        *state = State::Pending(None);
+        self.0.created.notify_all();
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for OnFirstUse<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut tuple = f.debug_tuple("OnFirstUse");
+        match &*state {
+            State::Ready(provider) => match &**provider {
+                Ok(provider) => tuple.field(provider).finish(),
+                Err(e) => tuple.field(e).finish(),
+            },
+            State::Pending(_) | State::Creating(_) => {
+                tuple.field(&format_args!("<pending>")).finish()
+            }
+        }
+    }
+}
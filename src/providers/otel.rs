@@ -0,0 +1,289 @@
+use crate::{Profile, Provider, Metadata};
+use crate::value::{Map, Dict};
+use crate::util::{nest, percent_decode};
+use crate::error::{Error, Kind};
+use crate::providers::Env;
+
+/// A [`Provider`] that sources OpenTelemetry SDK configuration from the
+/// standard `OTEL_*` environment variables.
+///
+/// The OpenTelemetry specification defines a set of environment variables,
+/// such as `OTEL_SERVICE_NAME` and `OTEL_RESOURCE_ATTRIBUTES`, with precedence
+/// rules between them. This provider applies those rules and emits the result
+/// into a single dictionary, `telemetry` by default, that can be extracted
+/// alongside, and overridden by, the rest of an application's configuration.
+///
+/// # Key Space
+///
+/// With the default root key `telemetry`, the following keys are emitted:
+///
+///   * `telemetry.service_name`
+///
+///     The value of `OTEL_SERVICE_NAME` if it is set. Otherwise, the value of
+///     the `service.name` resource attribute, if there is one.
+///
+///   * `telemetry.resource`
+///
+///     A dictionary of the attributes in `OTEL_RESOURCE_ATTRIBUTES`, a
+///     comma-separated list of `key=value` pairs with percent-encoded values.
+///     Values are always strings. If `OTEL_SERVICE_NAME` is set, it overrides
+///     the `service.name` attribute, as the specification requires.
+///
+///     Attribute names are emitted verbatim as dictionary keys; they are _not_
+///     treated as key paths. As such, `service.name` is a single key, and the
+///     resource is best extracted as a map, e.g. a `HashMap<String, String>`.
+///
+///   * `telemetry.{name}`
+///
+///     Every other `OTEL_{NAME}` variable is emitted as `{name}`, lowercased,
+///     with its value parsed like [`Env`] values. For instance,
+///     `OTEL_EXPORTER_OTLP_ENDPOINT` is emitted as
+///     `telemetry.exporter_otlp_endpoint` and `OTEL_SDK_DISABLED=true` as
+///     the boolean `telemetry.sdk_disabled`. Variables are not otherwise
+///     interpreted: resolving, say, `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`
+///     against `OTEL_EXPORTER_OTLP_ENDPOINT` is left to the consumer.
+///
+/// Variables set to an empty or whitespace-only value are treated as unset,
+/// per the specification.
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider is named `OpenTelemetry environment variable(s)`. It does
+///     not specify a [`Source`](crate::Source). Interpolation produces the
+///     name of the environment variable the key was read from.
+///
+///   * **Data**
+///
+///     The data emitted by this provider is a single dictionary at the
+///     [root key](Otel::key()) with the keys described above. If no `OTEL_*`
+///     variables are set, an empty dictionary is emitted. A malformed
+///     `OTEL_RESOURCE_ATTRIBUTES` causes the provider to fail. The dictionary
+///     is emitted to the profile [`profile`](#structfield.profile),
+///     configurable via [`Otel::profile()`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use serde::Deserialize;
+/// use figment::{Figment, Jail, providers::{Otel, Toml, Format}};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Telemetry {
+///     service_name: String,
+///     exporter_otlp_endpoint: String,
+///     #[serde(default)]
+///     sdk_disabled: bool,
+///     resource: HashMap<String, String>,
+/// }
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     telemetry: Telemetry,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", r#"
+///         [telemetry]
+///         exporter_otlp_endpoint = "http://localhost:4317"
+///     "#)?;
+///
+///     jail.set_env("OTEL_SERVICE_NAME", "checkout");
+///     jail.set_env("OTEL_RESOURCE_ATTRIBUTES",
+///         "service.name=ignored,deployment.environment=prod%2Ceu");
+///
+///     let config: Config = Figment::from(Toml::file("App.toml"))
+///         .merge(Otel::new())
+///         .extract()?;
+///
+///     let telemetry = config.telemetry;
+///     assert_eq!(telemetry.service_name, "checkout");
+///     assert_eq!(telemetry.exporter_otlp_endpoint, "http://localhost:4317");
+///     assert!(!telemetry.sdk_disabled);
+///     assert_eq!(telemetry.resource["service.name"], "checkout");
+///     assert_eq!(telemetry.resource["deployment.environment"], "prod,eu");
+///
+///     jail.set_env("OTEL_EXPORTER_OTLP_ENDPOINT", "https://collector:4317");
+///     jail.set_env("OTEL_SDK_DISABLED", "true");
+///
+///     let config: Config = Figment::from(Toml::file("App.toml"))
+///         .merge(Otel::new())
+///         .extract()?;
+///
+///     assert_eq!(config.telemetry.exporter_otlp_endpoint, "https://collector:4317");
+///     assert!(config.telemetry.sdk_disabled);
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Otel {
+    key: String,
+    /// The profile config data will be emitted to. Defaults to
+    /// [`Profile::Default`].
+    pub profile: Profile,
+}
+
+impl Otel {
+    const SERVICE_NAME: &'static str = "service_name";
+    const RESOURCE: &'static str = "resource";
+    const RESOURCE_ATTRIBUTES: &'static str = "resource_attributes";
+
+    /// Returns an `Otel` provider that emits to the root key `telemetry`.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::Otel};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("OTEL_TRACES_SAMPLER_ARG", "0.25");
+    ///
+    ///     let figment = Figment::from(Otel::new());
+    ///     let ratio: f64 = figment.extract_inner("telemetry.traces_sampler_arg")?;
+    ///     assert_eq!(ratio, 0.25);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn new() -> Self {
+        Otel { key: "telemetry".into(), profile: Profile::Default }
+    }
+
+    /// Sets the root key the telemetry dictionary is emitted to. The default
+    /// is `telemetry`. The key may be a key path, e.g. `observability.otel`.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::Otel};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("OTEL_SERVICE_NAME", "api");
+    ///
+    ///     let figment = Figment::from(Otel::new().key("observability.otel"));
+    ///     let name: String = figment.extract_inner("observability.otel.service_name")?;
+    ///     assert_eq!(name, "api");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn key<K: Into<String>>(mut self, key: K) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Sets the profile config data will be emitted to.
+    ///
+    /// ```rust
+    /// use figment::{Profile, providers::Otel};
+    ///
+    /// let provider = Otel::new();
+    /// assert_eq!(provider.profile, Profile::Default);
+    ///
+    /// let provider = provider.profile("debug");
+    /// assert_eq!(provider.profile, Profile::from("debug"));
+    /// ```
+    pub fn profile<P: Into<Profile>>(mut self, profile: P) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    /// Sets the profile config data will be emitted to to `global`.
+    ///
+    /// ```rust
+    /// use figment::{Profile, providers::Otel};
+    ///
+    /// let provider = Otel::new().global();
+    /// assert_eq!(provider.profile, Profile::Global);
+    /// ```
+    pub fn global(mut self) -> Self {
+        self.profile = Profile::Global;
+        self
+    }
+
+    /// Parses the value of `OTEL_RESOURCE_ATTRIBUTES`.
+    fn resource(list: &str) -> Result<Dict, Error> {
+        let error = |pair: &str| -> Error {
+            let msg = format!("invalid `OTEL_RESOURCE_ATTRIBUTES` entry `{}`", pair);
+            Kind::Message(msg).into()
+        };
+
+        let mut dict = Dict::new();
+        for pair in list.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').ok_or_else(|| error(pair))?;
+            let (key, value) = (key.trim(), value.trim());
+            if key.is_empty() {
+                return Err(error(pair));
+            }
+
+            let value = percent_decode(value).ok_or_else(|| error(pair))?;
+            dict.insert(key.to_string(), value.into());
+        }
+
+        Ok(dict)
+    }
+}
+
+impl Default for Otel {
+    fn default() -> Self {
+        Otel::new()
+    }
+}
+
+impl Provider for Otel {
+    fn metadata(&self) -> Metadata {
+        let root = self.key.split('.').count();
+        Metadata::named("OpenTelemetry environment variable(s)")
+            .interpolater(move |_: &Profile, k: &[&str]| {
+                match k.get(root) {
+                    Some(&Otel::RESOURCE) => "OTEL_RESOURCE_ATTRIBUTES".into(),
+                    Some(name) => format!("OTEL_{}", name.to_ascii_uppercase()),
+                    None => "OTEL_*".into(),
+                }
+            })
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let mut dict = Dict::new();
+        let mut resource = Dict::new();
+        let mut service_name = None;
+        for (key, value) in Env::prefixed("OTEL_").iter() {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+
+            match key.as_str() {
+                Otel::SERVICE_NAME => service_name = Some(value.to_string()),
+                Otel::RESOURCE_ATTRIBUTES => resource = Otel::resource(value)?,
+                key => {
+                    dict.insert(key.into(), value.parse().expect("infallible"));
+                }
+            }
+        }
+
+        match service_name {
+            Some(name) => {
+                resource.insert("service.name".into(), name.clone().into());
+                dict.insert(Otel::SERVICE_NAME.into(), name.into());
+            }
+            None => if let Some(name) = resource.get("service.name") {
+                dict.insert(Otel::SERVICE_NAME.into(), name.clone());
+            }
+        }
+
+        if !resource.is_empty() {
+            dict.insert(Otel::RESOURCE.into(), resource.into());
+        }
+
+        if dict.is_empty() {
+            return Ok(Map::new());
+        }
+
+        let dict = nest(&self.key, dict.into()).into_dict().unwrap_or_default();
+        Ok(self.profile.collect(dict))
+    }
+}
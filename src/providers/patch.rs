@@ -0,0 +1,528 @@
+use std::fmt;
+
+use crate::{Profile, Provider, Metadata, Resolution};
+use crate::error::Error;
+use crate::value::{Map, Dict, Tag, Value};
+
+/// A [`Provider`] adapter that applies the data of another provider as a JSON
+/// Merge Patch ([RFC 7386]).
+///
+/// The data of the wrapped provider is merged as usual except that a key set
+/// to `null`, i.e, any [`Value::Empty`], removes the key, along with any value it
+/// had, instead of setting it. As per the RFC, dictionaries are merged
+/// recursively and all other values, including arrays, replace existing
+/// values. A `MergePatch` should thus be [merged](crate::Figment::merge()):
+/// other strategies retain their usual semantics for values that aren't
+/// `null`.
+///
+/// Since TOML cannot express `null`, patches are typically written in JSON or
+/// YAML or are serialized from Rust values with `None`.
+///
+/// [RFC 7386]: https://datatracker.ietf.org/doc/html/rfc7386
+///
+/// # Provider Details
+///
+///   * **Profile**, **Metadata**, and **Data**
+///
+///     This provider forwards to the wrapped provider.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, Jail, providers::{Format, Toml, Json, MergePatch}};
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("Config.toml", r#"
+///         name = "app"
+///         debug = true
+///
+///         [server]
+///         port = 80
+///         workers = 4
+///     "#)?;
+///
+///     jail.create_file("patch.json", r#"{
+///         "debug": null,
+///         "server": { "port": 8080, "workers": null, "tls": null }
+///     }"#)?;
+///
+///     let figment = Figment::from(Toml::file("Config.toml"))
+///         .merge(MergePatch::new(Json::file("patch.json")));
+///
+///     assert_eq!(figment.extract_inner::<String>("name")?, "app");
+///     assert_eq!(figment.extract_inner::<u16>("server.port")?, 8080);
+///     assert!(!figment.contains("debug"));
+///     assert!(!figment.contains("server.workers"));
+///     assert!(!figment.contains("server.tls"));
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct MergePatch<P> {
+    provider: P,
+}
+
+impl<P: Provider> MergePatch<P> {
+    /// Returns a provider that applies the data of `provider` as a JSON Merge
+    /// Patch.
+    pub fn new(provider: P) -> Self {
+        MergePatch { provider }
+    }
+}
+
+impl<P: Provider> Provider for MergePatch<P> {
+    fn metadata(&self) -> Metadata {
+        Metadata::adapted(self.provider.metadata(), "merge patch")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        self.provider.data()
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.provider.profile()
+    }
+
+    fn on_conflict(&self, _: &str, _: Option<&Value>, new: &Value) -> Resolution {
+        match new {
+            Value::Empty(..) => Resolution::Remove,
+            _ => Resolution::Default,
+        }
+    }
+
+    #[doc(hidden)]
+    fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> {
+        self.provider.__metadata_map()
+    }
+
+    #[doc(hidden)]
+    fn __patch(&self, data: &mut Map<Profile, Dict>, tag: Tag) -> Result<(), Error> {
+        self.provider.__patch(data, tag)
+    }
+}
+
+/// A [`Provider`] that applies a JSON Patch ([RFC 6902]): a sequence of
+/// operations applied, in order, to the data of the figment it is combined
+/// into.
+///
+/// Unlike other providers, a `JsonPatch` doesn't emit data of its own.
+/// Instead, its operations are applied to the data in the profile
+/// [`JsonPatch::profile`] of the figment at the point the patch is combined,
+/// irrespective of the strategy used to combine it. Operations address values
+/// with [JSON Pointers] such as `/server/hosts/0`; the final token of the
+/// target of an `add` to an array can be `-` to append.
+///
+/// If any operation fails, because a value doesn't exist or a `test` doesn't
+/// pass, the figment fails to extract with an error identifying the operation.
+///
+/// [RFC 6902]: https://datatracker.ietf.org/doc/html/rfc6902
+/// [JSON Pointers]: https://datatracker.ietf.org/doc/html/rfc6901
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider is named `JSON Patch` when constructed from operations or
+///     a string and `JSON Patch file` when constructed from a file, in which
+///     case the file's path is specified as file [`Source`](crate::Source).
+///
+///   * **Data**
+///
+///     No data is emitted. Values added by operations carry this provider's
+///     metadata.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, util::map};
+/// use figment::providers::{JsonPatch, PatchOp, Serialized};
+///
+/// let figment = Figment::new()
+///     .merge(Serialized::default("server", map!["port" => 80, "workers" => 4]))
+///     .merge(Serialized::default("hosts", ["a.com", "b.com"]))
+///     .merge(JsonPatch::new(vec![
+///         PatchOp::Test { path: "/server/port".into(), value: 80.into() },
+///         PatchOp::Remove { path: "/server/workers".into() },
+///         PatchOp::Add { path: "/hosts/-".into(), value: "c.com".into() },
+///         PatchOp::Move { from: "/hosts/0".into(), path: "/primary".into() },
+///     ]));
+///
+/// assert!(!figment.contains("server.workers"));
+/// assert_eq!(figment.extract_inner::<Vec<String>>("hosts").unwrap(), ["b.com", "c.com"]);
+/// assert_eq!(figment.extract_inner::<String>("primary").unwrap(), "a.com");
+///
+/// let figment = figment.merge(JsonPatch::new(vec![
+///     PatchOp::Replace { path: "/server/tls".into(), value: true.into() },
+/// ]));
+///
+/// let error = figment.extract_inner::<u16>("server.port").unwrap_err();
+/// assert!(error.to_string().contains("/server/tls"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonPatch {
+    source: Source,
+    /// The profile the operations are applied to. Defaults to
+    /// [`Profile::Default`].
+    pub profile: Profile,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Ops(Vec<PatchOp>),
+    #[cfg(feature = "json")]
+    String(String),
+    #[cfg(feature = "json")]
+    File(std::path::PathBuf),
+}
+
+/// A JSON Patch operation. See [`JsonPatch`].
+///
+/// Every `path` and `from` is a [JSON Pointer]. Operations are written in JSON
+/// as objects with an `op` member naming the operation, in lowercase, and a
+/// member for each field.
+///
+/// [JSON Pointer]: https://datatracker.ietf.org/doc/html/rfc6901
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOp {
+    /// Adds `value` at `path`, replacing any existing value in a dictionary
+    /// and inserting before the existing value in an array.
+    Add {
+        /// The target location.
+        path: String,
+        /// The value to add.
+        value: Value,
+    },
+    /// Removes the value at `path`, which must exist.
+    Remove {
+        /// The target location.
+        path: String,
+    },
+    /// Replaces the value at `path`, which must exist, with `value`.
+    Replace {
+        /// The target location.
+        path: String,
+        /// The replacement value.
+        value: Value,
+    },
+    /// Removes the value at `from` and adds it at `path`.
+    Move {
+        /// The location of the value to move.
+        from: String,
+        /// The target location.
+        path: String,
+    },
+    /// Adds a copy of the value at `from` at `path`.
+    Copy {
+        /// The location of the value to copy.
+        from: String,
+        /// The target location.
+        path: String,
+    },
+    /// Checks that the value at `path` is equal to `value`.
+    Test {
+        /// The target location.
+        path: String,
+        /// The expected value.
+        value: Value,
+    },
+}
+
+impl JsonPatch {
+    /// Returns a `JsonPatch` that applies `ops` to [`Profile::Default`].
+    pub fn new<I: IntoIterator<Item = PatchOp>>(ops: I) -> Self {
+        JsonPatch { source: Source::Ops(ops.into_iter().collect()), profile: Profile::Default }
+    }
+
+    /// Returns a `JsonPatch` that applies the operations in the JSON array
+    /// `string` to [`Profile::Default`]. The string is parsed when the patch
+    /// is applied.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{JsonPatch, Serialized}};
+    ///
+    /// let figment = Figment::from(Serialized::default("name", "app"))
+    ///     .merge(JsonPatch::string(r#"[
+    ///         { "op": "copy", "from": "/name", "path": "/alias" },
+    ///         { "op": "replace", "path": "/name", "value": "new" }
+    ///     ]"#));
+    ///
+    /// assert_eq!(figment.extract_inner::<String>("alias").unwrap(), "app");
+    /// assert_eq!(figment.extract_inner::<String>("name").unwrap(), "new");
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn string(string: &str) -> Self {
+        JsonPatch { source: Source::String(string.into()), profile: Profile::Default }
+    }
+
+    /// Returns a `JsonPatch` that applies the operations in the JSON array in
+    /// the file at `path` to [`Profile::Default`]. The file is read when the
+    /// patch is applied and must exist.
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn file<P: AsRef<std::path::Path>>(path: P) -> Self {
+        JsonPatch {
+            source: Source::File(path.as_ref().to_path_buf()),
+            profile: Profile::Default,
+        }
+    }
+
+    /// Sets the profile the operations are applied to.
+    ///
+    /// ```rust
+    /// use figment::{Profile, providers::JsonPatch};
+    ///
+    /// let patch = JsonPatch::new(vec![]).profile("debug");
+    /// assert_eq!(patch.profile, Profile::from("debug"));
+    /// ```
+    pub fn profile<P: Into<Profile>>(mut self, profile: P) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    fn ops(&self) -> Result<Vec<PatchOp>, Error> {
+        #[cfg(feature = "json")]
+        fn parse(string: &str) -> Result<Vec<PatchOp>, Error> {
+            let values: Vec<Value> = serde_json::from_str(string).map_err(|e| e.to_string())?;
+            values.iter().map(PatchOp::from_value).collect()
+        }
+
+        match &self.source {
+            Source::Ops(ops) => Ok(ops.clone()),
+            #[cfg(feature = "json")]
+            Source::String(string) => parse(string),
+            #[cfg(feature = "json")]
+            Source::File(path) => {
+                let string = std::fs::read_to_string(path)
+                    .map_err(|e| format!("failed to read `{}`: {}", path.display(), e))?;
+
+                parse(&string)
+            }
+        }
+    }
+}
+
+impl PatchOp {
+    /// Parses an operation from a dictionary in JSON Patch form.
+    #[cfg(feature = "json")]
+    fn from_value(value: &Value) -> Result<PatchOp, Error> {
+        let dict = value.as_dict().ok_or("JSON Patch operation must be an object")?;
+        let field = |name: &str| dict.get(name)
+            .ok_or_else(|| format!("JSON Patch operation is missing `{}`", name));
+
+        let string = |name: &str| field(name)?.as_str()
+            .map(String::from)
+            .ok_or_else(|| format!("JSON Patch operation `{}` must be a string", name));
+
+        let op = match &*string("op")? {
+            "add" => PatchOp::Add { path: string("path")?, value: field("value")?.clone() },
+            "remove" => PatchOp::Remove { path: string("path")? },
+            "replace" => PatchOp::Replace { path: string("path")?, value: field("value")?.clone() },
+            "move" => PatchOp::Move { from: string("from")?, path: string("path")? },
+            "copy" => PatchOp::Copy { from: string("from")?, path: string("path")? },
+            "test" => PatchOp::Test { path: string("path")?, value: field("value")?.clone() },
+            op => return Err(format!("unknown JSON Patch operation `{}`", op).into()),
+        };
+
+        Ok(op)
+    }
+
+    fn apply(self, root: &mut Value) -> Result<(), String> {
+        match self {
+            PatchOp::Add { path, value } => add(root, &pointer(&path)?, value),
+            PatchOp::Remove { path } => remove(root, &pointer(&path)?).map(|_| ()),
+            PatchOp::Replace { path, value } => {
+                let tokens = pointer(&path)?;
+                remove(root, &tokens)?;
+                add(root, &tokens, value)
+            }
+            PatchOp::Move { from, path } => {
+                let (from, to) = (pointer(&from)?, pointer(&path)?);
+                if to.len() > from.len() && to.starts_with(&from) {
+                    return Err("cannot move a value into one of its children".into());
+                }
+
+                let value = remove(root, &from)?;
+                add(root, &to, value)
+            }
+            PatchOp::Copy { from, path } => {
+                let value = get(root, &pointer(&from)?)?.clone();
+                add(root, &pointer(&path)?, value)
+            }
+            PatchOp::Test { path, value } => match equal(get(root, &pointer(&path)?)?, &value) {
+                true => Ok(()),
+                false => Err("test failed: value is not equal".into()),
+            },
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PatchOp::Add { .. } => "add",
+            PatchOp::Remove { .. } => "remove",
+            PatchOp::Replace { .. } => "replace",
+            PatchOp::Move { .. } => "move",
+            PatchOp::Copy { .. } => "copy",
+            PatchOp::Test { .. } => "test",
+        }
+    }
+
+    fn path(&self) -> &str {
+        match self {
+            PatchOp::Add { path, .. } | PatchOp::Remove { path }
+                | PatchOp::Replace { path, .. } | PatchOp::Move { path, .. }
+                | PatchOp::Copy { path, .. } | PatchOp::Test { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for PatchOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchOp::Move { from, path } | PatchOp::Copy { from, path } => {
+                write!(f, "`{}` from `{}` to `{}`", self.name(), from, path)
+            }
+            _ => write!(f, "`{}` at `{}`", self.name(), self.path()),
+        }
+    }
+}
+
+/// Splits the JSON Pointer `pointer` into its unescaped reference tokens.
+fn pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(vec![]);
+    }
+
+    match pointer.strip_prefix('/') {
+        Some(rest) => Ok(rest.split('/').map(|t| t.replace("~1", "/").replace("~0", "~")).collect()),
+        None => Err(format!("invalid JSON Pointer `{}`", pointer)),
+    }
+}
+
+fn index(token: &str, len: usize) -> Result<usize, String> {
+    match token.parse::<usize>() {
+        Ok(i) if i < len && (token == "0" || !token.starts_with('0')) => Ok(i),
+        _ => Err(format!("invalid array index `{}`", token)),
+    }
+}
+
+fn get<'v>(mut value: &'v Value, tokens: &[String]) -> Result<&'v Value, String> {
+    for token in tokens {
+        value = match value {
+            Value::Dict(_, dict) => dict.get(token),
+            Value::Array(_, array) => array.get(index(token, array.len())?),
+            _ => None,
+        }.ok_or_else(|| format!("no value at `{}`", token))?;
+    }
+
+    Ok(value)
+}
+
+fn get_mut<'v>(mut value: &'v mut Value, tokens: &[String]) -> Result<&'v mut Value, String> {
+    for token in tokens {
+        value = match value {
+            Value::Dict(_, dict) => dict.get_mut(token),
+            Value::Array(_, array) => {
+                let i = index(token, array.len())?;
+                array.get_mut(i)
+            }
+            _ => None,
+        }.ok_or_else(|| format!("no value at `{}`", token))?;
+    }
+
+    Ok(value)
+}
+
+fn add(root: &mut Value, tokens: &[String], value: Value) -> Result<(), String> {
+    let (last, parent) = match tokens.split_last() {
+        Some(split) => split,
+        None if value.as_dict().is_some() => {
+            *root = value;
+            return Ok(());
+        }
+        None => return Err("the root value must be a dictionary".into()),
+    };
+
+    match get_mut(root, parent)? {
+        Value::Dict(_, dict) => { dict.insert(last.clone(), value); }
+        Value::Array(_, array) if last == "-" => array.push(value),
+        Value::Array(_, array) => {
+            let i = index(last, array.len() + 1)?;
+            array.insert(i, value);
+        }
+        _ => return Err(format!("no dictionary or array to add `{}` to", last)),
+    }
+
+    Ok(())
+}
+
+fn remove(root: &mut Value, tokens: &[String]) -> Result<Value, String> {
+    let (last, parent) = tokens.split_last().ok_or("cannot remove the root value")?;
+    match get_mut(root, parent)? {
+        Value::Dict(_, dict) => dict.remove(last).ok_or_else(|| format!("no value at `{}`", last)),
+        Value::Array(_, array) => {
+            let i = index(last, array.len())?;
+            Ok(array.remove(i))
+        }
+        _ => Err(format!("no value at `{}`", last)),
+    }
+}
+
+/// Compares `a` and `b` for JSON equality: numbers are compared by value,
+/// irrespective of their representation.
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Num(_, a), Value::Num(_, b)) => {
+            (a.to_u128_lossy().is_some() && a.to_u128_lossy() == b.to_u128_lossy())
+                || (a.to_i128().is_some() && a.to_i128() == b.to_i128())
+                || (a.to_f64().is_some() && a.to_f64() == b.to_f64())
+        }
+        (Value::Dict(_, a), Value::Dict(_, b)) => {
+            a.len() == b.len() && a.iter().all(|(k, v)| matches!(b.get(k), Some(w) if equal(v, w)))
+        }
+        (Value::Array(_, a), Value::Array(_, b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(v, w)| equal(v, w))
+        }
+        (a, b) => a == b,
+    }
+}
+
+impl Provider for JsonPatch {
+    fn metadata(&self) -> Metadata {
+        match &self.source {
+            #[cfg(feature = "json")]
+            Source::File(path) => Metadata::from("JSON Patch file", path.as_path()),
+            _ => Metadata::named("JSON Patch"),
+        }
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        Ok(Map::new())
+    }
+
+    #[doc(hidden)]
+    fn __patch(&self, data: &mut Map<Profile, Dict>, tag: Tag) -> Result<(), Error> {
+        let dict = data.remove(&self.profile).unwrap_or_default();
+        let mut root = Value::Dict(Tag::Default, dict);
+        let mut result = Ok(());
+        for mut op in self.ops()? {
+            if let PatchOp::Add { value, .. } | PatchOp::Replace { value, .. } = &mut op {
+                value.map_tag(|t| *t = tag.for_profile(&self.profile));
+            }
+
+            let desc = op.to_string();
+            if let Err(e) = op.apply(&mut root) {
+                result = Err(Error::from(format!("JSON Patch operation {} failed: {}", desc, e)));
+                break;
+            }
+        }
+
+        data.insert(self.profile.clone(), root.into_dict().expect("root is a dict"));
+        result
+    }
+}
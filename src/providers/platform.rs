@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+
+use crate::{Profile, Provider, Metadata, Resolution};
+use crate::coalesce::Coalescible;
+use crate::error::{Error, Kind};
+use crate::value::{Map, Dict, Tag, Value};
+
+/// A [`Provider`] adapter that applies platform-conditional sections of
+/// another provider's data.
+///
+/// Created via [`Provider::platform_overlays()`]. In each profile, the
+/// dictionary at the top-level key `target` (configurable via
+/// [`Platform::key()`]) holds sections named after platforms. Sections
+/// matching the current platform are merged, in order of increasing
+/// specificity, over the rest of the profile's data; other sections are
+/// discarded. The sections, from least to most specific, are named:
+///
+///   1. the OS family, such as `unix` or `windows`
+///      ([`std::env::consts::FAMILY`]),
+///   2. the OS, such as `linux`, `macos`, or `windows`
+///      ([`std::env::consts::OS`]),
+///   3. the architecture, such as `x86_64` or `aarch64`
+///      ([`std::env::consts::ARCH`]), and
+///   4. the OS and architecture joined by a `-`, such as `linux-x86_64`.
+///
+/// The platform can be overridden with [`Platform::os()`],
+/// [`Platform::family()`], and [`Platform::arch()`], for instance for testing.
+///
+/// # Provider Details
+///
+///   * **Profile** and **Metadata**
+///
+///     This provider forwards to the wrapped provider.
+///
+///   * **Data**
+///
+///     The data of the wrapped provider with the matching sections applied
+///     and the `target` key removed from every profile.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, Jail, Provider, providers::{Format, Toml}};
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", r#"
+///         cache = "/var/cache/app"
+///         threads = 4
+///
+///         [target.windows]
+///         cache = 'C:\Cache\App'
+///
+///         [target.macos]
+///         cache = "~/Library/Caches/app"
+///
+///         [target.linux-aarch64]
+///         threads = 2
+///     "#)?;
+///
+///     let toml = || Toml::file("App.toml").platform_overlays();
+///
+///     let figment = Figment::from(toml().os("windows").family("windows"));
+///     assert_eq!(figment.extract_inner::<String>("cache")?, r"C:\Cache\App");
+///     assert!(!figment.contains("target"));
+///
+///     let figment = Figment::from(toml().os("linux").arch("aarch64"));
+///     assert_eq!(figment.extract_inner::<String>("cache")?, "/var/cache/app");
+///     assert_eq!(figment.extract_inner::<u8>("threads")?, 2);
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Platform<P> {
+    provider: P,
+    key: String,
+    os: Cow<'static, str>,
+    family: Cow<'static, str>,
+    arch: Cow<'static, str>,
+}
+
+impl<P: Provider> Platform<P> {
+    pub(crate) fn new(provider: P) -> Self {
+        use std::env::consts::{OS, FAMILY, ARCH};
+
+        Platform {
+            provider,
+            key: "target".into(),
+            os: OS.into(),
+            family: FAMILY.into(),
+            arch: ARCH.into(),
+        }
+    }
+
+    /// Sets the top-level key holding the platform sections. The default is
+    /// `target`.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Provider, providers::{Format, Toml}};
+    ///
+    /// let toml = Toml::string("[platform.haiku]\nx = 1").platform_overlays();
+    /// let figment = Figment::from(toml.key("platform").os("haiku"));
+    /// assert_eq!(figment.extract_inner::<u8>("x").unwrap(), 1);
+    /// ```
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Sets the OS to apply sections for. Defaults to the current OS.
+    pub fn os<S: Into<Cow<'static, str>>>(mut self, os: S) -> Self {
+        self.os = os.into();
+        self
+    }
+
+    /// Sets the OS family to apply sections for. Defaults to the current OS
+    /// family.
+    pub fn family<S: Into<Cow<'static, str>>>(mut self, family: S) -> Self {
+        self.family = family.into();
+        self
+    }
+
+    /// Sets the architecture to apply sections for. Defaults to the current
+    /// architecture.
+    pub fn arch<S: Into<Cow<'static, str>>>(mut self, arch: S) -> Self {
+        self.arch = arch.into();
+        self
+    }
+
+    /// Returns the names of the sections to apply, least specific first.
+    fn sections(&self) -> [String; 4] {
+        [
+            self.family.to_string(),
+            self.os.to_string(),
+            self.arch.to_string(),
+            format!("{}-{}", self.os, self.arch),
+        ]
+    }
+}
+
+impl<P: Provider> Provider for Platform<P> {
+    fn metadata(&self) -> Metadata {
+        Metadata::adapted(self.provider.metadata(), "platform sections")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let mut data = self.provider.data()?;
+        for dict in data.values_mut() {
+            let mut targets = match dict.remove(&self.key) {
+                Some(Value::Dict(_, targets)) => targets,
+                Some(value) => {
+                    let kind = Kind::InvalidType(value.to_actual(), "a dictionary".into());
+                    return Err(Error::from(kind).with_path(&self.key));
+                }
+                None => continue,
+            };
+
+            for section in &self.sections() {
+                match targets.remove(section) {
+                    Some(Value::Dict(_, overlay)) => {
+                        *dict = std::mem::take(dict).merge(overlay);
+                    }
+                    Some(value) => {
+                        let kind = Kind::InvalidType(value.to_actual(), "a dictionary".into());
+                        return Err(Error::from(kind).with_path(&self.key).with_path(section));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        Ok(data)
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.provider.profile()
+    }
+
+    fn on_conflict(&self, key: &str, old: Option<&Value>, new: &Value) -> Resolution {
+        self.provider.on_conflict(key, old, new)
+    }
+
+    #[doc(hidden)]
+    fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> {
+        self.provider.__metadata_map()
+    }
+
+    #[doc(hidden)]
+    fn __patch(&self, data: &mut Map<Profile, Dict>, tag: Tag) -> Result<(), Error> {
+        self.provider.__patch(data, tag)
+    }
+}
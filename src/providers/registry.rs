@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::Provider;
+use crate::error::Error;
+use crate::providers::{Data, Format};
+
+/// A process-wide registry of data formats and URI schemes, allowing crates
+/// outside of Figment to plug providers into [`Uri`](crate::providers::Uri)
+/// and [`Figment::from_uri()`](crate::Figment::from_uri()).
+///
+/// Figment's built-in formats, [`Toml`](crate::providers::Toml),
+/// [`Json`](crate::providers::Json), and [`Yaml`](crate::providers::Yaml), are
+/// each gated behind a feature. A crate implementing another [`Format`], or a
+/// [`Provider`] for another source altogether, can make it available to `Uri`
+/// at runtime by registering it:
+///
+///   * [`Registry::register_format()`] registers a `Format` by name, also
+///     used as a file extension, and by media type, for `file:` and `data:`
+///     URIs.
+///   * [`Registry::register_scheme()`] registers a function constructing a
+///     provider from the remainder of a URI with a given scheme.
+///
+/// Registrations are consulted before the built-in formats and schemes, and
+/// later registrations take precedence over earlier ones, so a registration
+/// can replace a built-in. Registration is typically done once, early in
+/// `main()`, or by a plugin crate's initialization function. Names, extensions,
+/// media types, and schemes are matched case-insensitively.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::de::{DeserializeOwned, IntoDeserializer, value::{Error, MapDeserializer}};
+/// use figment::{Figment, Jail, Metadata, Provider, Profile};
+/// use figment::providers::{Format, Registry, Serialized};
+///
+/// /// A format of `key=value` lines.
+/// struct KeyValue;
+///
+/// impl Format for KeyValue {
+///     type Error = Error;
+///     const NAME: &'static str = "Key-Value";
+///
+///     fn from_str<'de, T: DeserializeOwned>(string: &'de str) -> Result<T, Error> {
+///         let pairs = string.lines().filter_map(|line| line.split_once('='));
+///         T::deserialize(MapDeserializer::new(pairs.map(|(k, v)| (k.trim(), v.trim()))))
+///     }
+/// }
+///
+/// Registry::register_format::<KeyValue>(&["kv"], &["text/x-kv"]);
+/// Registry::register_scheme("const", |rest: &str| -> Result<Box<dyn Provider>, figment::Error> {
+///     Ok(Box::new(Serialized::default("constant", rest.to_string())))
+/// });
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("app.kv", "name = app\nport = 80")?;
+///
+///     let figment = Figment::from_uri("file:app.kv");
+///     assert_eq!(figment.extract_inner::<String>("name")?, "app");
+///     assert_eq!(figment.extract_inner::<u16>("port")?, 80);
+///
+///     let figment = Figment::from_uri("data:text/x-kv,name=inline");
+///     assert_eq!(figment.extract_inner::<String>("name")?, "inline");
+///
+///     let figment = Figment::from_uri("const:value");
+///     assert_eq!(figment.extract_inner::<String>("constant")?, "value");
+///     Ok(())
+/// });
+///
+/// assert!(Registry::formats().contains(&"kv".to_string()));
+/// assert!(Registry::schemes().contains(&"const".to_string()));
+/// ```
+#[derive(Debug)]
+pub struct Registry {
+    _private: (),
+}
+
+/// The input to a format provider.
+pub(crate) enum Input {
+    File(PathBuf),
+    String(String),
+}
+
+/// Constructs a format provider from its input.
+pub(crate) type FormatProvider = fn(Input) -> Box<dyn Provider>;
+
+/// Constructs a provider from the remainder of a URI after the scheme.
+pub(crate) type SchemeProvider = dyn Fn(&str) -> Result<Box<dyn Provider>, Error> + Send + Sync;
+
+struct FormatEntry {
+    names: Vec<String>,
+    media_types: Vec<String>,
+    provider: FormatProvider,
+}
+
+struct SchemeEntry {
+    scheme: String,
+    provider: Arc<SchemeProvider>,
+}
+
+static FORMATS: RwLock<Vec<FormatEntry>> = RwLock::new(Vec::new());
+static SCHEMES: RwLock<Vec<SchemeEntry>> = RwLock::new(Vec::new());
+
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Returns the [`Data`] provider for `input` in format `F`. A `file:` URI
+/// names a file that must exist and is not searched for.
+pub(crate) fn data<F: Format + 'static>(input: Input) -> Box<dyn Provider> {
+    match input {
+        Input::File(path) => Box::new(Data::<F>::file(path).search(false).required(true)),
+        Input::String(string) => Box::new(Data::<F>::string(&string)),
+    }
+}
+
+impl Registry {
+    /// Registers the format `F` under each of `names`, which are also used as
+    /// file extensions, and each of `media_types`.
+    ///
+    /// A `file:` URI whose `format` query parameter or, absent one, whose
+    /// extension matches one of `names` is parsed as `F`, as is a `data:` URI
+    /// with a media type in `media_types`. See [`Registry`] for an example.
+    pub fn register_format<F: Format + 'static>(names: &[&str], media_types: &[&str]) {
+        let lowercase = |strings: &[&str]| strings.iter().map(|s| s.to_ascii_lowercase()).collect();
+        write(&FORMATS).push(FormatEntry {
+            names: lowercase(names),
+            media_types: lowercase(media_types),
+            provider: data::<F>,
+        });
+    }
+
+    /// Registers `provider` as the constructor of providers for URIs with the
+    /// scheme `scheme`, given the remainder of the URI after `scheme:`,
+    /// including any query. If `provider` returns an error, the `Uri`
+    /// provider fails with that error. See [`Registry`] for an example.
+    pub fn register_scheme<S>(scheme: &str, provider: S)
+        where S: Fn(&str) -> Result<Box<dyn Provider>, Error> + Send + Sync + 'static
+    {
+        write(&SCHEMES).push(SchemeEntry {
+            scheme: scheme.to_ascii_lowercase(),
+            provider: Arc::new(provider),
+        });
+    }
+
+    /// Returns the names of all registered formats, in registration order.
+    /// Built-in formats are not included.
+    pub fn formats() -> Vec<String> {
+        read(&FORMATS).iter().flat_map(|f| f.names.iter().cloned()).collect()
+    }
+
+    /// Returns all registered schemes, in registration order. Built-in
+    /// schemes are not included.
+    pub fn schemes() -> Vec<String> {
+        read(&SCHEMES).iter().map(|s| s.scheme.clone()).collect()
+    }
+
+    /// Returns the most recently registered format named `name`, if any.
+    pub(crate) fn format(name: &str) -> Option<FormatProvider> {
+        let name = name.to_ascii_lowercase();
+        read(&FORMATS).iter().rev()
+            .find(|f| f.names.contains(&name))
+            .map(|f| f.provider)
+    }
+
+    /// Returns the most recently registered format with the media type
+    /// `media_type`, if any.
+    pub(crate) fn media_type(media_type: &str) -> Option<FormatProvider> {
+        let media_type = media_type.to_ascii_lowercase();
+        read(&FORMATS).iter().rev()
+            .find(|f| f.media_types.contains(&media_type))
+            .map(|f| f.provider)
+    }
+
+    /// Returns the most recently registered provider for `scheme`, if any.
+    pub(crate) fn scheme(scheme: &str) -> Option<Arc<SchemeProvider>> {
+        let scheme = scheme.to_ascii_lowercase();
+        read(&SCHEMES).iter().rev()
+            .find(|s| s.scheme == scheme)
+            .map(|s| s.provider.clone())
+    }
+}
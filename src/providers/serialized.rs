@@ -1,10 +1,11 @@
+use std::borrow::Cow;
 use std::panic::Location;
 
 use serde::Serialize;
 
 use crate::{Profile, Provider, Metadata};
 use crate::error::{Error, Kind::InvalidType};
-use crate::value::{Value, Map, Dict};
+use crate::value::{Value, Map, Dict, Empty};
 
 /// A `Provider` that sources values directly from a serialize type.
 ///
@@ -16,8 +17,9 @@ use crate::value::{Value, Map, Dict};
 ///
 ///   * **Metadata**
 ///
-///     This provider is named `T` (via [`std::any::type_name`]). The source
-///     location is set to the call site of the constructor.
+///     This provider is named `T` (via [`std::any::type_name`]) unless a name
+///     is set via [`Serialized::named()`]. The source location is set to the
+///     call site of the constructor.
 ///
 ///   * **Data (Unkeyed)**
 ///
@@ -33,6 +35,10 @@ use crate::value::{Value, Map, Dict};
 ///     the `key` string, each dictionary mapping the path component to the
 ///     child, with the leaf mapping to the serialized `T`. For instance,
 ///     `a.b.c` results in `{ a: { b: { c: T }}}`.
+///
+///     With [`Serialized::skip_none()`] or [`Serialized::skip_defaults()`],
+///     `None` values and values equal to those of `T::default()`,
+///     respectively, are omitted from the emitted data.
 #[derive(Debug, Clone)]
 pub struct Serialized<T> {
     /// The value to be serialized and used as the provided data.
@@ -42,6 +48,10 @@ pub struct Serialized<T> {
     /// The profile to emit the value to. Defaults to [`Profile::Default`].
     pub profile: Profile,
     loc: &'static Location<'static>,
+    name: Option<Cow<'static, str>>,
+    skip_none: bool,
+    /// Serializes `T::default()` if default values are to be skipped.
+    defaults: Option<fn() -> Result<Value, Error>>,
 }
 
 impl<T> Serialized<T> {
@@ -79,7 +89,10 @@ impl<T> Serialized<T> {
             value,
             key: None,
             profile: profile.into(),
-            loc: Location::caller()
+            loc: Location::caller(),
+            name: None,
+            skip_none: false,
+            defaults: None,
         }
     }
 
@@ -182,15 +195,154 @@ impl<T> Serialized<T> {
         self.key = Some(key.into());
         self
     }
+
+    /// Sets the name of the provider's [`Metadata`], which otherwise is the
+    /// name of the type `T`. Metadata names appear in error messages, where a
+    /// label like `built-in defaults` is clearer to end users than a type name.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// let provider = Serialized::default("port", "eighty").named("built-in defaults");
+    /// let figment = Figment::from(provider);
+    ///
+    /// let error = figment.extract_inner::<u16>("port").unwrap_err();
+    /// assert_eq!(error.metadata.as_ref().unwrap().name, "built-in defaults");
+    /// assert!(error.to_string().contains("built-in defaults"));
+    /// ```
+    pub fn named<N: Into<Cow<'static, str>>>(mut self, name: N) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the source location reported in this provider's metadata.
+    pub(crate) fn located(mut self, loc: &'static Location<'static>) -> Self {
+        self.loc = loc;
+        self
+    }
+
+    /// Omits `None` values, at any depth, from the emitted data.
+    ///
+    /// When serializing a struct of optional overrides, such as parsed CLI
+    /// arguments, unset fields would otherwise be emitted as empty values that
+    /// replace values from lower layers when merged. With `skip_none`, only
+    /// fields that are set are emitted. `None` values in arrays are retained.
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Cli {
+    ///     port: Option<u16>,
+    ///     name: Option<String>,
+    /// }
+    ///
+    /// let cli = Cli { port: Some(8080), name: None };
+    /// let figment = Figment::new()
+    ///     .merge(Serialized::default("name", "app"))
+    ///     .merge(Serialized::defaults(cli).skip_none());
+    ///
+    /// assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 8080);
+    /// assert_eq!(figment.extract_inner::<String>("name").unwrap(), "app");
+    /// ```
+    pub fn skip_none(mut self) -> Self {
+        self.skip_none = true;
+        self
+    }
+}
+
+impl<T: Serialize + Default> Serialized<T> {
+    /// Omits values equal to the corresponding value of `T::default()` from
+    /// the emitted data. Dictionaries are compared field by field, at any
+    /// depth, and are omitted if every field is; all other values, including
+    /// arrays, are compared as a whole.
+    ///
+    /// This allows a complete options struct, where unset options hold their
+    /// defaults, to be merged as an override layer without clobbering lower
+    /// layers with defaults.
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// #[derive(Default, Serialize)]
+    /// struct Options {
+    ///     verbose: bool,
+    ///     workers: usize,
+    ///     server: Server,
+    /// }
+    ///
+    /// #[derive(Default, Serialize)]
+    /// struct Server {
+    ///     host: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let options = Options {
+    ///     workers: 8,
+    ///     server: Server { port: 80, ..Default::default() },
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let figment = Figment::new()
+    ///     .merge(Serialized::default("verbose", true))
+    ///     .merge(Serialized::default("server.host", "localhost"))
+    ///     .merge(Serialized::defaults(options).skip_defaults());
+    ///
+    /// assert_eq!(figment.extract_inner::<bool>("verbose").unwrap(), true);
+    /// assert_eq!(figment.extract_inner::<usize>("workers").unwrap(), 8);
+    /// assert_eq!(figment.extract_inner::<String>("server.host").unwrap(), "localhost");
+    /// assert_eq!(figment.extract_inner::<u16>("server.port").unwrap(), 80);
+    /// ```
+    pub fn skip_defaults(mut self) -> Self {
+        self.defaults = Some(|| Value::serialize(T::default()));
+        self
+    }
+}
+
+/// Removes from `dict` `None` values, if `none`, and values equal to those in
+/// `defaults`, recursing into non-empty dictionaries.
+fn strip(dict: &mut Dict, defaults: Option<&Dict>, none: bool) {
+    dict.retain(|key, value| {
+        let default = defaults.and_then(|d| d.get(key));
+        match value {
+            Value::Empty(_, Empty::None) if none => false,
+            Value::Dict(_, dict) if !dict.is_empty() => {
+                strip(dict, default.and_then(|d| d.as_dict()), none);
+                !dict.is_empty()
+            }
+            value => default != Some(&*value),
+        }
+    });
 }
 
 impl<T: Serialize> Provider for Serialized<T> {
     fn metadata(&self) -> Metadata {
-        Metadata::from(std::any::type_name::<T>(), self.loc)
+        match &self.name {
+            Some(name) => Metadata::from(name.clone(), self.loc),
+            None => Metadata::from(std::any::type_name::<T>(), self.loc),
+        }
     }
 
     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
-        let value = Value::serialize(&self.value)?;
+        let mut value = Value::serialize(&self.value)?;
+        let defaults = self.defaults.map(|f| f()).transpose()?;
+        if self.skip_none || defaults.is_some() {
+            let skip = match &mut value {
+                Value::Dict(_, dict) => {
+                    strip(dict, defaults.as_ref().and_then(|d| d.as_dict()), self.skip_none);
+                    false
+                }
+                Value::Empty(_, Empty::None) if self.skip_none => true,
+                value => defaults.as_ref() == Some(&*value),
+            };
+
+            if skip {
+                return Ok(Map::new());
+            }
+        }
+
         let error = InvalidType(value.to_actual(), "map".into());
         let dict = match &self.key {
             Some(key) => crate::util::nest(key, value).into_dict().ok_or(error)?,
@@ -0,0 +1,196 @@
+use crate::{Profile, Provider, Metadata};
+use crate::coalesce::Coalescible;
+use crate::error::Error;
+use crate::value::{Value, Map, Dict, Num, Empty};
+use crate::util::nest;
+
+/// A [`Provider`] that sources values from a `static` or `const` table.
+///
+/// `Static` and [`StaticValue`] can be constructed in `const` contexts, so a
+/// table of defaults can be declared once, in a `static`, and costs nothing
+/// until it is provided. Unlike
+/// [`Serialized::defaults(Config::default())`](crate::providers::Serialized),
+/// providing a `Static` doesn't invoke `serde` or construct `Config`: values
+/// are converted directly from the table.
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider is named `static values`. It does not specify a
+///     [`Source`](crate::Source).
+///
+///   * **Data**
+///
+///     The data emitted by this provider is a dictionary with one entry per
+///     entry in the table. Each entry's key is a [key path]: nested
+///     dictionaries are created for every path component delimited by `.`.
+///     Later entries are merged into earlier ones. The dictionary is emitted
+///     to the profile the provider was constructed with, [`Profile::Default`]
+///     for [`Static::new()`].
+///
+/// [key path]: crate::Figment#extraction
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use figment::{Figment, Jail, providers::{Env, Static, StaticValue as V}};
+/// use figment::value::Num;
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+///     hosts: Vec<String>,
+///     timeout: Option<u32>,
+///     tls: Tls,
+/// }
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Tls {
+///     enabled: bool,
+/// }
+///
+/// static DEFAULTS: Static = Static::new(&[
+///     ("name", V::String("app")),
+///     ("port", V::Num(Num::U16(8000))),
+///     ("hosts", V::Array(&[V::String("localhost")])),
+///     ("timeout", V::None),
+///     ("tls.enabled", V::Bool(false)),
+/// ]);
+///
+/// Jail::expect_with(|jail| {
+///     jail.set_env("APP_PORT", 9000);
+///
+///     let config: Config = Figment::from(&DEFAULTS)
+///         .merge(Env::prefixed("APP_"))
+///         .extract()?;
+///
+///     assert_eq!(config, Config {
+///         name: "app".into(),
+///         port: 9000,
+///         hosts: vec!["localhost".into()],
+///         timeout: None,
+///         tls: Tls { enabled: false },
+///     });
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Static {
+    entries: &'static [(&'static str, StaticValue)],
+    profile: Profile,
+}
+
+/// A value in a [`Static`] table.
+///
+/// Each variant corresponds to a [`Value`] variant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaticValue {
+    /// A string.
+    String(&'static str),
+    /// A character.
+    Char(char),
+    /// A boolean.
+    Bool(bool),
+    /// A number.
+    Num(Num),
+    /// No value, like `Option::None`.
+    None,
+    /// An array of values.
+    Array(&'static [StaticValue]),
+    /// A dictionary. Keys are _not_ key paths: they are used verbatim.
+    Dict(&'static [(&'static str, StaticValue)]),
+}
+
+impl Static {
+    /// Returns a `Static` provider that emits the entries in `entries` to the
+    /// default profile.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Static, StaticValue}};
+    ///
+    /// const DEFAULTS: Static = Static::new(&[("name", StaticValue::String("app"))]);
+    ///
+    /// let figment = Figment::from(DEFAULTS);
+    /// assert_eq!(figment.extract_inner::<String>("name").unwrap(), "app");
+    /// ```
+    pub const fn new(entries: &'static [(&'static str, StaticValue)]) -> Self {
+        Static { entries, profile: Profile::Default }
+    }
+
+    /// Returns a `Static` provider that emits the entries in `entries` to the
+    /// global profile.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Static, StaticValue}};
+    ///
+    /// static GLOBAL: Static = Static::global(&[("name", StaticValue::String("app"))]);
+    ///
+    /// let figment = Figment::from(&GLOBAL).select("debug");
+    /// assert_eq!(figment.extract_inner::<String>("name").unwrap(), "app");
+    /// ```
+    pub const fn global(entries: &'static [(&'static str, StaticValue)]) -> Self {
+        Static { entries, profile: Profile::Global }
+    }
+
+    /// Returns a `Static` provider that emits the entries in `entries` to the
+    /// profile named `profile`.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::{Static, StaticValue}};
+    ///
+    /// static DEBUG: Static = Static::profiled("debug", &[("level", StaticValue::String("trace"))]);
+    ///
+    /// let figment = Figment::from(&DEBUG);
+    /// assert!(figment.extract_inner::<String>("level").is_err());
+    ///
+    /// let figment = figment.select("debug");
+    /// assert_eq!(figment.extract_inner::<String>("level").unwrap(), "trace");
+    /// ```
+    pub const fn profiled(
+        profile: &'static str,
+        entries: &'static [(&'static str, StaticValue)]
+    ) -> Self {
+        Static { entries, profile: Profile::const_new(profile) }
+    }
+}
+
+impl From<&StaticValue> for Value {
+    fn from(value: &StaticValue) -> Value {
+        match *value {
+            StaticValue::String(s) => Value::from(s),
+            StaticValue::Char(c) => Value::from(c),
+            StaticValue::Bool(b) => Value::from(b),
+            StaticValue::Num(n) => Value::from(n),
+            StaticValue::None => Value::from(Empty::None),
+            StaticValue::Array(items) => Value::from(items.iter().map(Value::from).collect::<Vec<_>>()),
+            StaticValue::Dict(entries) => Value::from(entries.iter()
+                .map(|(k, v)| (k.to_string(), Value::from(v)))
+                .collect::<Dict>()),
+        }
+    }
+}
+
+impl Provider for Static {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("static values")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let mut dict = Dict::new();
+        for (key, value) in self.entries {
+            if let Some(nested) = nest(key, value.into()).into_dict() {
+                dict = dict.merge(nested);
+            }
+        }
+
+        Ok(self.profile.clone().collect(dict))
+    }
+}
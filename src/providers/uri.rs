@@ -0,0 +1,348 @@
+use std::path::PathBuf;
+
+use crate::{Profile, Provider, Metadata};
+use crate::value::{Map, Dict};
+use crate::error::{Error, Kind};
+use crate::util::percent_decode;
+use crate::providers::Env;
+use crate::providers::registry::{Registry, Input};
+
+/// A [`Provider`] that dispatches to another provider based on the scheme of
+/// a URI, allowing a single argument like `--config <uri>` to name a source of
+/// any kind.
+///
+/// Also available as [`Figment::from_uri()`](crate::Figment::from_uri()).
+///
+/// # Schemes
+///
+/// The following schemes are supported:
+///
+///   * **`file:`**
+///
+///     A file in a format determined by its extension: `.toml` for
+///     [`Toml`](crate::providers::Toml), `.json` for
+///     [`Json`](crate::providers::Json), and `.yaml` or `.yml` for
+///     [`Yaml`](crate::providers::Yaml). The format can be set explicitly with
+///     a `format` query parameter, as in `file:/etc/app.conf?format=toml`.
+///     Each format requires its feature to be enabled. Both absolute
+///     (`file:///etc/app.toml`) and relative (`file:app.toml`) paths are
+///     accepted. Unlike [`Format::file()`], the file is not searched for in
+///     parent directories and is required to exist.
+///
+///   * **`env:`**
+///
+///     Environment variables, as via [`Env::prefixed()`] with the prefix
+///     following the colon, which may be empty. A `split` query parameter
+///     applies [`Env::split()`], as in `env:APP_?split=__`.
+///
+///   * **`data:`**
+///
+///     Inline data as per [RFC 2397], as in `data:application/toml,port=8080`.
+///     The media type selects the format: `application/toml` or `text/toml`,
+///     `application/json` or `text/json`, and `application/yaml`,
+///     `application/x-yaml`, or `text/yaml`. Data is percent-decoded or, with
+///     `;base64`, base64-decoded.
+///
+///   * **`http:`** and **`https:`**
+///
+///     A document fetched by an [`Http`](crate::providers::Http) provider with
+///     its default client, in a format determined by the extension of the
+///     URL's path or set by a `format` query parameter, as for `file:`. The
+///     URL, including its query, is requested as given. Requires the `http`
+///     feature; without it, the provider fails with an error naming the
+///     feature. The default client doesn't support TLS, so fetching an
+///     `https:` URL fails unless a scheme provider with TLS support has been
+///     registered.
+///
+/// Any other scheme causes the provider to fail unless a provider for it has
+/// been registered. Formats and schemes registered via [`Registry`] are
+/// supported in addition to, and take precedence over, those above.
+///
+/// [RFC 2397]: https://www.rfc-editor.org/rfc/rfc2397
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider's metadata is that of the provider the URI dispatches
+///     to. If the URI is invalid, the provider is named `URI`.
+///
+///   * **Data**
+///
+///     The data emitted by this provider is the data emitted by the provider
+///     the URI dispatches to. If the URI is invalid, the provider fails.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use figment::{Figment, Jail, providers::Uri};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("app.toml", r#"
+///         name = "app"
+///         port = 80
+///     "#)?;
+///
+///     jail.set_env("APP_PORT", 8080);
+///
+///     let config: Config = Figment::from(Uri::new("file:app.toml"))
+///         .merge(Uri::new("env:APP_"))
+///         .extract()?;
+///
+///     assert_eq!(config, Config { name: "app".into(), port: 8080 });
+///
+///     let uri = "data:application/json,%7B%22name%22%3A%22inline%22%2C%22port%22%3A1%7D";
+///     let config: Config = Figment::from(Uri::new(uri)).extract()?;
+///     assert_eq!(config, Config { name: "inline".into(), port: 1 });
+///
+///     let figment = Figment::from(Uri::new("ftp://example.com/app.toml"));
+///     assert!(figment.extract::<Config>().is_err());
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Uri {
+    uri: String,
+}
+
+impl Uri {
+    /// Returns a `Uri` provider that dispatches to the provider for `uri`. The
+    /// URI is not parsed until the provider is used.
+    ///
+    /// ```rust
+    /// use figment::{Figment, Jail, providers::Uri};
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.set_env("MY_APP__NAME", "app");
+    ///
+    ///     let figment = Figment::from(Uri::new("env:MY_?split=__"));
+    ///     assert_eq!(figment.extract_inner::<String>("app.name")?, "app");
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn new<S: Into<String>>(uri: S) -> Self {
+        Uri { uri: uri.into() }
+    }
+
+    fn provider(&self) -> Result<Box<dyn Provider>, Error> {
+        let error = |msg: &str| -> Error {
+            Kind::Message(format!("invalid URI `{}`: {}", self.uri, msg)).into()
+        };
+
+        let (scheme, rest) = self.uri.split_once(':')
+            .ok_or_else(|| error("missing a scheme"))?;
+
+        if let Some(provider) = Registry::scheme(scheme) {
+            return provider(rest);
+        }
+
+        let (rest, query) = match scheme {
+            "data" => (rest, None),
+            _ => match rest.split_once('?') {
+                Some((rest, query)) => (rest, Some(query)),
+                None => (rest, None),
+            }
+        };
+
+        let param = |name: &str| -> Result<Option<String>, Error> {
+            let query = match query {
+                Some(query) => query,
+                None => return Ok(None),
+            };
+
+            for pair in query.split('&') {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                if key == name {
+                    let value = percent_decode(value).ok_or_else(|| error("invalid query"))?;
+                    return Ok(Some(value));
+                }
+            }
+
+            Ok(None)
+        };
+
+        match &*scheme.to_ascii_lowercase() {
+            "file" => {
+                let path = match rest.strip_prefix("//") {
+                    Some(authority) => match authority.find('/') {
+                        Some(i) if matches!(&authority[..i], "" | "localhost") => &authority[i..],
+                        _ => return Err(error("only local files are supported")),
+                    },
+                    None => rest,
+                };
+
+                let path = PathBuf::from(percent_decode(path).ok_or_else(|| error("invalid path"))?);
+                let format = match param("format")? {
+                    Some(format) => format,
+                    None => path.extension()
+                        .and_then(|ext| ext.to_str())
+                        .ok_or_else(|| error("file has no extension and no `format`"))?
+                        .to_string()
+                };
+
+                Self::data(&format, Input::File(path)).map_err(|msg| error(&msg))
+            }
+            "env" => {
+                let env = Env::prefixed(&percent_decode(rest).ok_or_else(|| error("invalid prefix"))?);
+                Ok(match param("split")? {
+                    Some(split) => Box::new(env.split(split)),
+                    None => Box::new(env),
+                })
+            }
+            "data" => {
+                let (media, data) = rest.split_once(',')
+                    .ok_or_else(|| error("missing `,` before data"))?;
+
+                let (media, data) = match media.strip_suffix(";base64") {
+                    Some(media) => (media, base64_decode(data)),
+                    None => (media, percent_decode(data)),
+                };
+
+                let data = data.ok_or_else(|| error("invalid data encoding"))?;
+                let media = media.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+                if let Some(provider) = Registry::media_type(&media) {
+                    return Ok(provider(Input::String(data)));
+                }
+
+                let format = match media.as_str() {
+                    "application/toml" | "text/toml" => "toml",
+                    "application/json" | "text/json" => "json",
+                    "application/yaml" | "application/x-yaml" | "text/yaml" => "yaml",
+                    "" => return Err(error("missing media type")),
+                    _ => return Err(error(&format!("unsupported media type `{}`", media))),
+                };
+
+                Self::data(format, Input::String(data)).map_err(|msg| error(&msg))
+            }
+            "http" | "https" => {
+                let format = match param("format")? {
+                    Some(format) => format,
+                    None => rest.rsplit('/').next()
+                        .and_then(|name| name.rsplit_once('.'))
+                        .map(|(_, ext)| ext.to_string())
+                        .ok_or_else(|| error("URL path has no extension and no `format`"))?
+                };
+
+                Self::http(&format, &self.uri).map_err(|msg| error(&msg))
+            }
+            scheme => Err(error(&format!("unsupported scheme `{}`", scheme))),
+        }
+    }
+
+    /// Returns the provider for `input` in the format named `name`.
+    fn data(name: &str, input: Input) -> Result<Box<dyn Provider>, String> {
+        if let Some(provider) = Registry::format(name) {
+            return Ok(provider(input));
+        }
+
+        let name = name.to_ascii_lowercase();
+        let feature = match name.as_str() {
+            "toml" => "toml",
+            "json" => "json",
+            "yaml" | "yml" => "yaml",
+            _ => return Err(format!("unknown format `{}`", name)),
+        };
+
+        match feature {
+            #[cfg(feature = "toml")]
+            "toml" => Ok(crate::providers::registry::data::<crate::providers::Toml>(input)),
+            #[cfg(feature = "json")]
+            "json" => Ok(crate::providers::registry::data::<crate::providers::Json>(input)),
+            #[cfg(feature = "yaml")]
+            "yaml" => Ok(crate::providers::registry::data::<crate::providers::Yaml>(input)),
+            _ => {
+                let _ = input;
+                Err(format!("format `{}` requires the `{}` feature", name, feature))
+            }
+        }
+    }
+
+    /// Returns an [`Http`](crate::providers::Http) provider for `url` in the
+    /// format named `name`.
+    #[cfg(feature = "http")]
+    fn http(name: &str, url: &str) -> Result<Box<dyn Provider>, String> {
+        let name = name.to_ascii_lowercase();
+        let feature = match name.as_str() {
+            "toml" => "toml",
+            "json" => "json",
+            "yaml" | "yml" => "yaml",
+            _ => return Err(format!("unknown format `{}`", name)),
+        };
+
+        match feature {
+            #[cfg(feature = "toml")]
+            "toml" => Ok(Box::new(crate::providers::Http::<crate::providers::Toml>::new(url))),
+            #[cfg(feature = "json")]
+            "json" => Ok(Box::new(crate::providers::Http::<crate::providers::Json>::new(url))),
+            #[cfg(feature = "yaml")]
+            "yaml" => Ok(Box::new(crate::providers::Http::<crate::providers::Yaml>::new(url))),
+            _ => {
+                let _ = url;
+                Err(format!("format `{}` requires the `{}` feature", name, feature))
+            }
+        }
+    }
+
+    #[cfg(not(feature = "http"))]
+    fn http(_: &str, _: &str) -> Result<Box<dyn Provider>, String> {
+        Err("`http:` and `https:` URIs require the `http` feature".into())
+    }
+}
+
+/// Decodes standard, padded or unpadded, base64. Returns `None` if `string`
+/// isn't valid base64 or doesn't decode to UTF-8.
+fn base64_decode(string: &str) -> Option<String> {
+    fn sextet(byte: u8) -> Option<u32> {
+        Some(match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        } as u32)
+    }
+
+    let bytes = string.trim_end_matches('=').as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len() * 3 / 4);
+    for chunk in bytes.chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+
+        let mut n = 0;
+        for (i, byte) in chunk.iter().enumerate() {
+            n |= sextet(*byte)? << (18 - 6 * i);
+        }
+
+        decoded.extend_from_slice(&n.to_be_bytes()[1..chunk.len()]);
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+impl Provider for Uri {
+    fn metadata(&self) -> Metadata {
+        match self.provider() {
+            Ok(provider) => provider.metadata(),
+            Err(_) => Metadata::named("URI"),
+        }
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        self.provider()?.data()
+    }
+}
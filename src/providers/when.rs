@@ -0,0 +1,113 @@
+use std::fmt;
+
+use crate::{Profile, Provider, Metadata, Resolution};
+use crate::error::Error;
+use crate::value::{Map, Dict, Tag, Value};
+
+/// A [`Provider`] adapter that includes the data of another provider only
+/// when a predicate holds.
+///
+/// Created via [`Provider::when()`] and [`Provider::when_env()`]. Because the
+/// adapter is the same type whether or not the provider is included,
+/// conditional layering doesn't require branches that produce differently
+/// typed values.
+///
+/// # Provider Details
+///
+///   * **Profile**
+///
+///     This provider sets the profile of the wrapped provider, if any, when
+///     the predicate holds. Otherwise, it does not set a profile.
+///
+///   * **Metadata**
+///
+///     This provider's metadata is that of the wrapped provider.
+///
+///   * **Data**
+///
+///     The predicate is evaluated every time data is requested, that is,
+///     when the provider is merged or joined into a figment. If it holds, the
+///     data emitted is the data of the wrapped provider. Otherwise, no data is
+///     emitted and the wrapped provider is not asked for its data.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, Jail, Provider, providers::{Format, Toml}};
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", "level = \"info\"")?;
+///     jail.create_file("CI.toml", "level = \"debug\"")?;
+///
+///     let figment = || Figment::from(Toml::file("App.toml"))
+///         .merge(Toml::file("CI.toml").when_env("CI"))
+///         .merge(Toml::file("Verbose.toml").when(|| cfg!(feature = "verbose")));
+///
+///     assert_eq!(figment().extract_inner::<String>("level")?, "info");
+///
+///     jail.set_env("CI", "true");
+///     assert_eq!(figment().extract_inner::<String>("level")?, "debug");
+///
+///     Ok(())
+/// });
+/// ```
+pub struct When<P> {
+    provider: P,
+    predicate: Box<dyn Fn() -> bool>,
+}
+
+impl<P: Provider> When<P> {
+    pub(crate) fn new<F>(provider: P, predicate: F) -> Self
+        where F: Fn() -> bool + 'static
+    {
+        When { provider, predicate: Box::new(predicate) }
+    }
+}
+
+impl<P: Provider> Provider for When<P> {
+    fn metadata(&self) -> Metadata {
+        Metadata::adapted(self.provider.metadata(), "conditional")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        match (self.predicate)() {
+            true => self.provider.data(),
+            false => Ok(Map::new()),
+        }
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        match (self.predicate)() {
+            true => self.provider.profile(),
+            false => None,
+        }
+    }
+
+    fn on_conflict(&self, key: &str, old: Option<&Value>, new: &Value) -> Resolution {
+        self.provider.on_conflict(key, old, new)
+    }
+
+    #[doc(hidden)]
+    fn __metadata_map(&self) -> Option<Map<Tag, Metadata>> {
+        match (self.predicate)() {
+            true => self.provider.__metadata_map(),
+            false => None,
+        }
+    }
+
+    #[doc(hidden)]
+    fn __patch(&self, data: &mut Map<Profile, Dict>, tag: Tag) -> Result<(), Error> {
+        match (self.predicate)() {
+            true => self.provider.__patch(data, tag),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for When<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("When")
+            .field("provider", &self.provider)
+            .finish_non_exhaustive()
+    }
+}
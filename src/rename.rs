@@ -0,0 +1,118 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::Profile;
+use crate::coalesce::Coalescible;
+use crate::value::{Value, Map, Dict, Tag};
+
+type RenameFn = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// Maps a key path to the key path its value should be moved to, if any.
+#[derive(Clone)]
+pub(crate) struct Renamer(pub Arc<RenameFn>);
+
+impl fmt::Debug for Renamer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Renamer")
+    }
+}
+
+/// A value moved from the key path `old` to `new` in `profile`.
+pub(crate) struct Renamed {
+    pub profile: Profile,
+    pub old: String,
+    pub new: String,
+    pub tag: Tag,
+}
+
+/// Moves every value in `data` whose key path is renamed by `renamers` to its
+/// new key path. A value already present at the new path takes precedence;
+/// dictionaries are merged. Returns every value moved.
+pub(crate) fn apply(renamers: &[Renamer], data: &mut Map<Profile, Dict>) -> Vec<Renamed> {
+    let mut renamed = vec![];
+    for (profile, dict) in data.iter_mut() {
+        let mut moves = vec![];
+        collect(renamers, dict, &mut vec![], &mut moves);
+        for (old, new) in moves {
+            let value = match take(dict, &old) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            let tag = value.tag();
+            let moved = match crate::util::nest(&new, value) {
+                Value::Dict(_, moved) => moved,
+                _ => continue,
+            };
+
+            *dict = moved.merge(std::mem::take(dict));
+            renamed.push(Renamed { profile: profile.clone(), old: old.join("."), new, tag });
+        }
+    }
+
+    renamed
+}
+
+/// Collects the key path of every value in `dict`, under `path`, that is
+/// renamed along with its new key path. Values within renamed values are
+/// moved with them and are not considered.
+fn collect(
+    renamers: &[Renamer],
+    dict: &Dict,
+    path: &mut Vec<String>,
+    moves: &mut Vec<(Vec<String>, String)>,
+) {
+    for (key, value) in dict {
+        path.push(key.clone());
+        match rename(renamers, &path.join(".")) {
+            Some(new) => moves.push((path.clone(), new)),
+            None => if let Value::Dict(_, dict) = value {
+                collect(renamers, dict, path, moves);
+            }
+        }
+
+        path.pop();
+    }
+}
+
+/// Returns the final key path `path` is renamed to, following chains of
+/// renames, or `None` if it isn't renamed.
+fn rename(renamers: &[Renamer], path: &str) -> Option<String> {
+    let mut current = path.to_string();
+    for _ in 0..=renamers.len() {
+        let next = renamers.iter()
+            .find_map(|renamer| (renamer.0)(&current))
+            .filter(|next| !next.is_empty() && *next != current);
+
+        match next {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+
+    match current != path {
+        true => Some(current),
+        false => None,
+    }
+}
+
+/// Removes and returns the value at `path` in `dict`, removing dictionaries
+/// left empty by the removal.
+fn take(dict: &mut Dict, path: &[String]) -> Option<Value> {
+    let (first, rest) = path.split_first()?;
+    if rest.is_empty() {
+        return dict.remove(first);
+    }
+
+    let child = match dict.get_mut(first)? {
+        Value::Dict(_, child) => child,
+        _ => return None,
+    };
+
+    let value = take(child, rest);
+    if child.is_empty() {
+        dict.remove(first);
+    }
+
+    value
+}
@@ -0,0 +1,610 @@
+use std::borrow::Cow;
+
+use serde::Serialize;
+
+use crate::{Figment, Error};
+use crate::error::{Actual, Kind};
+use crate::value::{Value, Dict};
+use crate::util::percent_decode;
+
+/// A [JSON Schema] that configuration values can be validated against.
+///
+/// A `JsonSchema` validates a [`Value`] via [`JsonSchema::validate()`] or the
+/// configuration of a figment's selected profile, before extraction, via
+/// [`Figment::validate()`]. Every mismatch is reported as an error whose path
+/// points to the offending value and whose metadata names the value's source,
+/// so a port that is out of range is reported along with the file or
+/// environment variable it was read from.
+///
+/// A schema can be built from any serializable JSON Schema document, such as
+/// a `serde_json::Value`, via [`JsonSchema::new()`], or, with the `schemars`
+/// feature enabled, generated from a type via [`JsonSchema::of()`].
+///
+/// # Supported Keywords
+///
+/// The following keywords are checked. All others, including `pattern`,
+/// `format`, and `patternProperties`, are ignored.
+///
+///   * **any value:** `type`, `enum`, `const`, `$ref`, `allOf`, `anyOf`,
+///     `oneOf`, `not`, `if`, `then`, `else`
+///   * **numbers:** `minimum`, `maximum`, `exclusiveMinimum`,
+///     `exclusiveMaximum`, `multipleOf`
+///   * **strings:** `minLength`, `maxLength`
+///   * **sequences:** `items`, `prefixItems`, `additionalItems`, `minItems`,
+///     `maxItems`, `uniqueItems`
+///   * **dictionaries:** `properties`, `required`, `additionalProperties`,
+///     `minProperties`, `maxProperties`
+///
+/// A `$ref` must be a JSON pointer into the schema itself, like
+/// `#/$defs/Server` or `#/definitions/Server`. Unset values are `null`, and
+/// integers are numbers without a fractional part, irrespective of how they
+/// were written. A string is anything that deserializes from a string: a
+/// string, a character, or a TOML datetime.
+///
+/// [JSON Schema]: https://json-schema.org
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, Jail, providers::{Env, Serialized}, schema::JsonSchema};
+///
+/// let schema = JsonSchema::new(serde_json::json!({
+///     "type": "object",
+///     "properties": {
+///         "server": {
+///             "type": "object",
+///             "properties": {
+///                 "host": { "type": "string" },
+///                 "port": { "type": "integer", "minimum": 1, "maximum": 65535 }
+///             },
+///             "required": ["host", "port"]
+///         }
+///     }
+/// })).unwrap();
+///
+/// Jail::expect_with(|jail| {
+///     jail.set_env("APP_SERVER_PORT", 70000);
+///
+///     let figment = Figment::from(Serialized::default("server.host", "localhost"))
+///         .merge(Env::prefixed("APP_").split("_"));
+///
+///     let error = figment.validate(&schema).unwrap_err();
+///     assert_eq!(error.path, ["server", "port"]);
+///     assert_eq!(error.to_string(), "invalid value unsigned int `70000`, \
+///         expected an integer ≤ 65535 for key \"SERVER.PORT\" \
+///         in `APP_` environment variable(s)");
+///
+///     jail.set_env("APP_SERVER_PORT", 8080);
+///     let figment = Figment::from(Serialized::default("server.host", "localhost"))
+///         .merge(Env::prefixed("APP_").split("_"));
+///
+///     assert!(figment.validate(&schema).is_ok());
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonSchema {
+    root: Value,
+}
+
+/// The most `$ref`s followed without descending into a value.
+const MAX_REFS: usize = 32;
+
+impl JsonSchema {
+    /// Returns the JSON Schema `schema`, typically a `serde_json::Value` or a
+    /// `schemars::Schema`. Fails if `schema` doesn't serialize to a dictionary
+    /// or a boolean, the two valid kinds of JSON Schema.
+    ///
+    /// ```rust
+    /// use figment::schema::JsonSchema;
+    ///
+    /// assert!(JsonSchema::new(serde_json::json!({ "type": "string" })).is_ok());
+    /// assert!(JsonSchema::new(true).is_ok());
+    /// assert!(JsonSchema::new("string").is_err());
+    /// ```
+    pub fn new<T: Serialize>(schema: T) -> Result<JsonSchema, Error> {
+        match Value::serialize(schema)? {
+            root @ Value::Dict(..) | root @ Value::Bool(..) => Ok(JsonSchema { root }),
+            root => {
+                let expected = "a JSON Schema: a dictionary or a boolean".into();
+                Err(Kind::InvalidType(root.to_actual(), expected).into())
+            }
+        }
+    }
+
+    /// Generates the JSON Schema for `T` via [`schemars`].
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{Figment, providers::Serialized, schema::JsonSchema};
+    ///
+    /// #[derive(Deserialize, schemars::JsonSchema)]
+    /// struct Config {
+    ///     name: String,
+    ///     workers: Option<u8>,
+    /// }
+    ///
+    /// let schema = JsonSchema::of::<Config>();
+    /// let figment = Figment::from(Serialized::default("name", "app"))
+    ///     .merge(Serialized::default("workers", 300));
+    ///
+    /// let error = figment.validate(&schema).unwrap_err();
+    /// assert_eq!(error.path, ["workers"]);
+    /// ```
+    #[cfg(feature = "schemars")]
+    #[cfg_attr(nightly, doc(cfg(feature = "schemars")))]
+    pub fn of<T: schemars::JsonSchema>() -> JsonSchema {
+        JsonSchema::new(schemars::schema_for!(T)).expect("schemars schema is a dictionary")
+    }
+
+    /// Returns the schema document.
+    ///
+    /// ```rust
+    /// use figment::schema::JsonSchema;
+    ///
+    /// let schema = JsonSchema::new(serde_json::json!({ "type": "string" })).unwrap();
+    /// assert_eq!(schema.as_value().find_ref("type").unwrap().as_str(), Some("string"));
+    /// ```
+    pub fn as_value(&self) -> &Value {
+        &self.root
+    }
+
+    /// Validates `value` against `self`, returning all mismatches, if any, as
+    /// a single [`Error`] with a path pointing to each offending value.
+    ///
+    /// ```rust
+    /// use figment::{schema::JsonSchema, value::Value, util::map};
+    ///
+    /// let schema = JsonSchema::new(serde_json::json!({
+    ///     "type": "object",
+    ///     "properties": { "tags": { "type": "array", "items": { "type": "string" } } },
+    ///     "additionalProperties": false,
+    /// })).unwrap();
+    ///
+    /// assert!(schema.validate(&Value::from(map!["tags" => vec!["a", "b"]])).is_ok());
+    ///
+    /// let value = Value::from(map!["tags" => Value::from(vec![1]), "name" => Value::from("app")]);
+    /// let error = schema.validate(&value).unwrap_err();
+    /// assert_eq!(error.count(), 2);
+    /// ```
+    pub fn validate(&self, value: &Value) -> Result<(), Error> {
+        super::join(self.mismatches(&Figment::new(), value))
+    }
+
+    /// Returns an error for every value in `value` that doesn't match `self`,
+    /// resolving metadata against `figment`.
+    pub(crate) fn mismatches(&self, figment: &Figment, value: &Value) -> Vec<Error> {
+        let mut errors = vec![];
+        let validator = Validator { root: &self.root, fig: figment };
+        validator.check(&self.root, value, &mut vec![], 0, &mut errors);
+        errors
+    }
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    fig: &'a Figment,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&self, kind: Kind, value: &Value, path: &[String]) -> Error {
+        let mut error = Error::from(kind);
+        error.path = path.to_vec();
+        error.retagged(value.tag()).resolved(self.fig)
+    }
+
+    fn invalid<E: Into<String>>(&self, expected: E, value: &Value, path: &[String]) -> Error {
+        self.error(Kind::InvalidValue(value.to_actual(), expected.into()), value, path)
+    }
+
+    /// Returns the errors from checking `value` against `schema`.
+    fn errors(
+        &self,
+        schema: &Value,
+        value: &Value,
+        path: &mut Vec<String>,
+        refs: usize,
+    ) -> Vec<Error> {
+        let mut errors = vec![];
+        self.check(schema, value, path, refs, &mut errors);
+        errors
+    }
+
+    fn check_in(
+        &self,
+        schema: &Value,
+        key: String,
+        value: &Value,
+        path: &mut Vec<String>,
+        errors: &mut Vec<Error>,
+    ) {
+        path.push(key);
+        self.check(schema, value, path, 0, errors);
+        path.pop();
+    }
+
+    fn check(
+        &self,
+        schema: &Value,
+        value: &Value,
+        path: &mut Vec<String>,
+        refs: usize,
+        errors: &mut Vec<Error>,
+    ) {
+        let schema = match schema {
+            Value::Bool(_, true) => return,
+            Value::Bool(_, false) => {
+                return errors.push(self.invalid("no value at all", value, path));
+            }
+            Value::Dict(_, schema) => schema,
+            _ => return,
+        };
+
+        if let Some(Value::String(_, reference)) = schema.get("$ref") {
+            match self.resolve(reference) {
+                Some(_) if refs >= MAX_REFS => {
+                    let msg = format!("schema reference `{}` is nested too deeply", reference);
+                    errors.push(self.error(Kind::Message(msg), value, path));
+                }
+                Some(target) => self.check(target, value, path, refs + 1, errors),
+                None => {
+                    let msg = format!("unresolvable schema reference `{}`", reference);
+                    errors.push(self.error(Kind::Message(msg), value, path));
+                }
+            }
+        }
+
+        let types = types(schema);
+        if !types.is_empty() && !types.iter().any(|ty| is_type(value, ty)) {
+            let expected = types.iter().map(|ty| describe(ty)).collect::<Vec<_>>().join(" or ");
+            let kind = Kind::InvalidType(value.to_actual(), expected);
+            return errors.push(self.error(kind, value, path));
+        }
+
+        if let Some(Value::Array(_, options)) = schema.get("enum") {
+            if !options.iter().any(|option| option.content_eq(value)) {
+                let options = options.iter().map(|o| o.sample(8)).collect::<Vec<_>>().join(", ");
+                errors.push(self.invalid(format!("one of {}", options), value, path));
+            }
+        }
+
+        if let Some(constant) = schema.get("const") {
+            if !constant.content_eq(value) {
+                errors.push(self.invalid(constant.sample(8), value, path));
+            }
+        }
+
+        self.check_combinators(schema, value, path, refs, errors);
+        match value {
+            Value::Num(..) => self.check_number(schema, value, path, errors),
+            Value::String(_, string) => {
+                let len = string.chars().count();
+                if let Some(min) = count(schema.get("minLength")).filter(|&min| len < min) {
+                    let expected = format!("a string of at least {} characters", min);
+                    errors.push(self.invalid(expected, value, path));
+                }
+
+                if let Some(max) = count(schema.get("maxLength")).filter(|&max| len > max) {
+                    let expected = format!("a string of at most {} characters", max);
+                    errors.push(self.invalid(expected, value, path));
+                }
+            }
+            Value::Array(_, items) => self.check_array(schema, value, items, path, errors),
+            Value::Dict(_, dict) => self.check_dict(schema, value, dict, path, errors),
+            _ => {}
+        }
+    }
+
+    fn check_combinators(
+        &self,
+        schema: &Dict,
+        value: &Value,
+        path: &mut Vec<String>,
+        refs: usize,
+        errors: &mut Vec<Error>,
+    ) {
+        if let Some(Value::Array(_, schemas)) = schema.get("allOf") {
+            for schema in schemas {
+                self.check(schema, value, path, refs, errors);
+            }
+        }
+
+        for (keyword, exactly_one) in [("anyOf", false), ("oneOf", true)] {
+            let schemas = match schema.get(keyword) {
+                Some(Value::Array(_, schemas)) => schemas,
+                _ => continue,
+            };
+
+            let results: Vec<_> = schemas.iter()
+                .map(|schema| (schema, self.errors(schema, value, path, refs)))
+                .collect();
+
+            let matches = results.iter().filter(|(_, errors)| errors.is_empty()).count();
+            match matches {
+                1 => {},
+                _ if matches > 1 && !exactly_one => {},
+                0 => {
+                    // Report the errors of the only schema for the value's
+                    // type, if there is one, like the non-null schema of an
+                    // optional value, instead of a generic mismatch.
+                    let mut candidates = results.into_iter()
+                        .filter(|(schema, _)| self.admits_type(schema, value, refs));
+
+                    match (candidates.next(), candidates.next()) {
+                        (Some((_, candidate)), None) => errors.extend(candidate),
+                        _ => {
+                            let expected = format!("a value matching a schema in `{}`", keyword);
+                            errors.push(self.invalid(expected, value, path));
+                        }
+                    }
+                }
+                n => {
+                    let expected = format!("a value matching exactly one schema in `oneOf`, \
+                        not {}", n);
+                    errors.push(self.invalid(expected, value, path));
+                }
+            }
+        }
+
+        if let Some(schema) = schema.get("not") {
+            if self.errors(schema, value, path, refs).is_empty() {
+                errors.push(self.invalid("a value not matching the schema in `not`", value, path));
+            }
+        }
+
+        if let Some(condition) = schema.get("if") {
+            let branch = match self.errors(condition, value, path, refs).is_empty() {
+                true => schema.get("then"),
+                false => schema.get("else"),
+            };
+
+            if let Some(branch) = branch {
+                self.check(branch, value, path, refs, errors);
+            }
+        }
+    }
+
+    fn check_number(&self, schema: &Dict, value: &Value, path: &[String], errors: &mut Vec<Error>) {
+        let n = match number(Some(value)) {
+            Some(n) => n,
+            None => return,
+        };
+
+        let noun = match types(schema).as_slice() {
+            ["integer"] => "an integer",
+            _ => "a number",
+        };
+
+        let bounds = [
+            ("minimum", "≥"),
+            ("maximum", "≤"),
+            ("exclusiveMinimum", ">"),
+            ("exclusiveMaximum", "<"),
+        ];
+
+        for (keyword, op) in bounds {
+            let bound = match schema.get(keyword) {
+                Some(bound @ Value::Num(..)) => bound,
+                _ => continue,
+            };
+
+            let limit = number(Some(bound)).unwrap_or(n);
+            let holds = match op {
+                "≥" => n >= limit,
+                "≤" => n <= limit,
+                ">" => n > limit,
+                _ => n < limit,
+            };
+
+            if !holds {
+                let expected = format!("{} {} {}", noun, op, bound.sample(8));
+                errors.push(self.invalid(expected, value, path));
+            }
+        }
+
+        if let Some(factor @ Value::Num(..)) = schema.get("multipleOf") {
+            let quotient = n / number(Some(factor)).unwrap_or(1.0);
+            if quotient.is_finite() && (quotient - quotient.round()).abs() > 1e-9 {
+                let expected = format!("a multiple of {}", factor.sample(8));
+                errors.push(self.invalid(expected, value, path));
+            }
+        }
+    }
+
+    fn check_array(
+        &self,
+        schema: &Dict,
+        value: &Value,
+        items: &[Value],
+        path: &mut Vec<String>,
+        errors: &mut Vec<Error>,
+    ) {
+        // `prefixItems` and `items` (2020-12) or `items` and `additionalItems`.
+        let (prefix, rest) = match (schema.get("prefixItems"), schema.get("items")) {
+            (Some(Value::Array(_, prefix)), rest) => (&prefix[..], rest),
+            (_, Some(Value::Array(_, prefix))) => (&prefix[..], schema.get("additionalItems")),
+            (_, rest) => (&[][..], rest),
+        };
+
+        for (i, item) in items.iter().enumerate() {
+            if let Some(schema) = prefix.get(i).or_else(|| rest.filter(|_| i >= prefix.len())) {
+                self.check_in(schema, i.to_string(), item, path, errors);
+            }
+        }
+
+        let len = items.len();
+        if let Some(min) = count(schema.get("minItems")).filter(|&min| len < min) {
+            let expected = format!("at least {} items", min);
+            errors.push(self.error(Kind::InvalidLength(len, expected), value, path));
+        }
+
+        if let Some(max) = count(schema.get("maxItems")).filter(|&max| len > max) {
+            let expected = format!("at most {} items", max);
+            errors.push(self.error(Kind::InvalidLength(len, expected), value, path));
+        }
+
+        if let Some(Value::Bool(_, true)) = schema.get("uniqueItems") {
+            let duplicate = items.iter().enumerate()
+                .any(|(i, a)| items[(i + 1)..].iter().any(|b| a.content_eq(b)));
+
+            if duplicate {
+                errors.push(self.invalid("a sequence of unique items", value, path));
+            }
+        }
+    }
+
+    fn check_dict(
+        &self,
+        schema: &Dict,
+        value: &Value,
+        dict: &Dict,
+        path: &mut Vec<String>,
+        errors: &mut Vec<Error>,
+    ) {
+        if let Some(Value::Array(_, required)) = schema.get("required") {
+            for field in required.iter().filter_map(|field| field.as_str()) {
+                if !dict.contains_key(field) {
+                    let kind = Kind::MissingField(Cow::Owned(field.to_string()));
+                    errors.push(self.error(kind, value, path));
+                }
+            }
+        }
+
+        let properties = match schema.get("properties") {
+            Some(Value::Dict(_, properties)) => Some(properties),
+            _ => None,
+        };
+
+        let additional = schema.get("additionalProperties");
+        for (key, value) in dict {
+            match (properties.and_then(|p| p.get(key)), additional) {
+                (Some(schema), _) => self.check_in(schema, key.clone(), value, path, errors),
+                (None, Some(Value::Bool(_, false))) => {
+                    let expected = match properties.filter(|p| !p.is_empty()) {
+                        Some(properties) => properties.keys()
+                            .map(|k| format!("`{}`", k))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        None => "there are none".to_string(),
+                    };
+
+                    let msg = format!("unknown key `{}`, expected one of {}", key, expected);
+                    path.push(key.clone());
+                    errors.push(self.error(Kind::Message(msg), value, path));
+                    path.pop();
+                }
+                (None, Some(schema)) => self.check_in(schema, key.clone(), value, path, errors),
+                (None, None) => {}
+            }
+        }
+
+        let len = dict.len();
+        if let Some(min) = count(schema.get("minProperties")).filter(|&min| len < min) {
+            let expected = format!("at least {} keys", min);
+            errors.push(self.error(Kind::InvalidLength(len, expected), value, path));
+        }
+
+        if let Some(max) = count(schema.get("maxProperties")).filter(|&max| len > max) {
+            let expected = format!("at most {} keys", max);
+            errors.push(self.error(Kind::InvalidLength(len, expected), value, path));
+        }
+    }
+
+    /// Returns `true` if `schema`'s `type`, if any, following `$ref`s, admits
+    /// the type of `value`.
+    fn admits_type(&self, schema: &Value, value: &Value, refs: usize) -> bool {
+        let schema = match schema {
+            Value::Bool(_, admits) => return *admits,
+            Value::Dict(_, schema) => schema,
+            _ => return true,
+        };
+
+        if let Some(Value::String(_, reference)) = schema.get("$ref") {
+            match self.resolve(reference) {
+                Some(target) if refs < MAX_REFS => {
+                    if !self.admits_type(target, value, refs + 1) {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+        }
+
+        let types = types(schema);
+        types.is_empty() || types.iter().any(|ty| is_type(value, ty))
+    }
+
+    /// Resolves the JSON pointer `reference`, like `#/$defs/Server`, against
+    /// the root schema.
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return None;
+        }
+
+        let mut value = self.root;
+        for token in pointer.split('/').skip(1) {
+            let token = percent_decode(token)?.replace("~1", "/").replace("~0", "~");
+            value = match value {
+                Value::Dict(_, dict) => dict.get(&token)?,
+                Value::Array(_, array) => array.get(token.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+}
+
+/// Returns the types named by `schema`'s `type` keyword, if any.
+fn types(schema: &Dict) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(_, ty)) => vec![ty.as_str()],
+        Some(Value::Array(_, types)) => types.iter().filter_map(|ty| ty.as_str()).collect(),
+        _ => vec![],
+    }
+}
+
+fn is_type(value: &Value, ty: &str) -> bool {
+    match (ty, value) {
+        ("null", Value::Empty(..)) => true,
+        ("boolean", Value::Bool(..)) => true,
+        ("string", Value::String(..) | Value::Char(..) | Value::Datetime(..)) => true,
+        ("number", Value::Num(..)) => true,
+        ("integer", Value::Num(_, n)) => match n.to_actual() {
+            Actual::Float(f) => f.fract() == 0.0,
+            _ => true,
+        },
+        ("array", Value::Array(..)) => true,
+        ("object", Value::Dict(..)) => true,
+        _ => false,
+    }
+}
+
+fn describe(ty: &str) -> Cow<'static, str> {
+    match ty {
+        "null" => "null".into(),
+        "boolean" => "a boolean".into(),
+        "string" => "a string".into(),
+        "number" => "a number".into(),
+        "integer" => "an integer".into(),
+        "array" => "a sequence".into(),
+        "object" => "a dictionary".into(),
+        _ => format!("`{}`", ty).into(),
+    }
+}
+
+/// Returns the number `value` as an `f64`, if it is one.
+fn number(value: Option<&Value>) -> Option<f64> {
+    match value?.to_num()?.to_actual() {
+        Actual::Unsigned(n) => Some(n as f64),
+        Actual::Signed(n) => Some(n as f64),
+        Actual::Float(n) => Some(n),
+        _ => None,
+    }
+}
+
+/// Returns the non-negative integer `value` as a `usize`, if it is one.
+fn count(value: Option<&Value>) -> Option<usize> {
+    number(value).filter(|n| *n >= 0.0).map(|n| n as usize)
+}
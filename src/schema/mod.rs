@@ -0,0 +1,943 @@
+//! Schemas describing the expected shape of configuration values.
+//!
+//! A [`Schema`] describes the kind of value expected at every key path. A
+//! schema can be generated from any `T: Deserialize` via [`Schema::of()`] and
+//! used to validate a [`Value`] via [`Schema::validate()`] or, at merge time, to
+//! validate the data emitted by each provider via [`Figment::check_merges()`].
+//!
+//! A [`JsonSchema`] instead validates values against a [JSON Schema], which
+//! can also express constraints beyond the kind of a value, like ranges and
+//! required keys. The configuration of a figment's selected profile can be
+//! validated against a JSON Schema before extraction via
+//! [`Figment::validate()`].
+//!
+//! Validation, including `JsonSchema`, requires the `schema` feature. Schemas
+//! themselves are always available as they also determine which keys are
+//! unknown to a type, as configured via [`UnknownPolicy`].
+//!
+//! [`Figment::check_merges()`]: crate::Figment::check_merges()
+//! [`Figment::validate()`]: crate::Figment::validate()
+//! [JSON Schema]: https://json-schema.org
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(feature = "schema")] {
+//! use serde::Deserialize;
+//! use figment::{Figment, schema::{Schema, OnMismatch}};
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     port: u16,
+//!     tags: Vec<String>,
+//! }
+//!
+//! let schema = Schema::of::<Config>();
+//! let figment = Figment::new()
+//!     .check_merges(schema, OnMismatch::Reject)
+//!     .merge(("name", "app"))
+//!     .merge(("port", 100_000))
+//!     .merge(("tags", "not-a-list"));
+//!
+//! let errors: Vec<_> = figment.extract::<Config>().unwrap_err().into_iter().collect();
+//! assert_eq!(errors.len(), 2);
+//! assert_eq!(errors[0].path, vec!["port"]);
+//! assert_eq!(errors[1].path, vec!["tags"]);
+//! # }
+//! ```
+
+#[cfg(feature = "schema")] mod json;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::de::{self, Deserialize, DeserializeSeed, Visitor, IntoDeserializer};
+
+use crate::{Figment, Error};
+use crate::error::Kind;
+use crate::value::{Value, Map};
+
+#[cfg(feature = "schema")]
+#[cfg_attr(nightly, doc(cfg(feature = "schema")))]
+pub use self::json::JsonSchema;
+
+/// The expected shape of a configuration value.
+///
+/// See the [module level docs](crate::schema) for an overview.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    /// Any value at all. Nothing is checked.
+    Any,
+    /// A `bool`.
+    Bool,
+    /// An `i8`.
+    I8,
+    /// An `i16`.
+    I16,
+    /// An `i32`.
+    I32,
+    /// An `i64`.
+    I64,
+    /// An `i128`.
+    I128,
+    /// A `u8`.
+    U8,
+    /// A `u16`.
+    U16,
+    /// A `u32`.
+    U32,
+    /// A `u64`.
+    U64,
+    /// A `u128`.
+    U128,
+    /// An `f32`.
+    F32,
+    /// An `f64`.
+    F64,
+    /// A `char`.
+    Char,
+    /// A string.
+    String,
+    /// A byte array.
+    Bytes,
+    /// A unit value: `()` or a unit struct.
+    Unit,
+    /// An optional value. The schema applies to present values.
+    Option(Box<Schema>),
+    /// A sequence with items matching the schema.
+    Seq(Box<Schema>),
+    /// A fixed-length sequence with each item matching its schema.
+    Tuple(Vec<Schema>),
+    /// A dictionary with values matching the schema.
+    Map(Box<Schema>),
+    /// A dictionary with known keys. Values for known keys must match the
+    /// key's schema. Unknown keys are not checked.
+    Struct(Map<String, Schema>),
+    /// An enum with the given variants, expressed as either a string naming
+    /// the variant or a dictionary with a single key naming the variant.
+    Enum(Vec<String>),
+}
+
+impl Schema {
+    /// Generates the schema for `T` by observing how `T` deserializes itself.
+    ///
+    /// Types that deserialize themselves from any value, like [`Value`] or
+    /// untagged enums, have a schema of [`Schema::Any`]. Types that validate
+    /// their input beyond its kind, like `std::net::SocketAddr`, have the
+    /// schema of the kind they expect, here [`Schema::String`]. Enum variant
+    /// payloads and the contents of `#[serde(flatten)]`ed fields are not
+    /// checked. A struct nested within a struct of the same name, as in
+    /// recursive types, has a schema of [`Schema::Any`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use serde::Deserialize;
+    /// use figment::{schema::Schema, util::map};
+    ///
+    /// #[derive(Deserialize)]
+    /// enum Level { Low, High }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     addr: std::net::SocketAddr,
+    ///     level: Level,
+    ///     limits: HashMap<String, Option<u8>>,
+    /// }
+    ///
+    /// assert_eq!(Schema::of::<Config>(), Schema::Struct(map! {
+    ///     "addr".into() => Schema::String,
+    ///     "level".into() => Schema::Enum(vec!["Low".into(), "High".into()]),
+    ///     "limits".into() => Schema::Map(Box::new(Schema::Option(Box::new(Schema::U8)))),
+    /// }));
+    /// ```
+    pub fn of<'de, T: Deserialize<'de>>() -> Schema {
+        let mut tracer = Trace::default();
+        loop {
+            tracer.failure = None;
+            let result = T::deserialize(Tracer { trace: &mut tracer, path: vec![], structs: vec![] });
+            match (result, tracer.failure.take()) {
+                (Err(_), Some(path)) if !path.is_empty() && tracer.skip.insert(path.clone()) => continue,
+                _ => break,
+            }
+        }
+
+        tracer.build(&mut vec![])
+    }
+
+    /// Validates `value` against `self`, returning all mismatches, if any, as
+    /// a single [`Error`] with a path pointing to each offending value.
+    ///
+    /// Numbers, booleans, and other primitives are checked by deserializing
+    /// the value into the primitive type exactly as extraction would.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use figment::{schema::Schema, value::Value, util::map};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Server { host: String, port: u16 }
+    ///
+    /// let schema = Schema::of::<Server>();
+    /// assert!(schema.validate(&Value::from(map!["port" => 8080])).is_ok());
+    ///
+    /// let value = Value::from(map!["host" => Value::from(10), "port" => Value::from(-1)]);
+    /// let error = schema.validate(&value).unwrap_err();
+    /// assert_eq!(error.count(), 2);
+    /// ```
+    #[cfg(feature = "schema")]
+    #[cfg_attr(nightly, doc(cfg(feature = "schema")))]
+    pub fn validate(&self, value: &Value) -> Result<(), Error> {
+        join(self.mismatches(&Figment::new(), value))
+    }
+
+    /// Returns an error for every value in `value` that doesn't match `self`,
+    /// resolving metadata against `figment`.
+    #[cfg(feature = "schema")]
+    pub(crate) fn mismatches(&self, figment: &Figment, value: &Value) -> Vec<Error> {
+        let mut errors = vec![];
+        self.check(figment, value, &mut vec![], &mut errors);
+        errors
+    }
+
+    #[cfg(feature = "schema")]
+    fn check(&self, fig: &Figment, value: &Value, path: &mut Vec<String>, errors: &mut Vec<Error>) {
+        use serde::de::DeserializeOwned;
+        use crate::value::{ConfiguredValueDe, DefaultInterpreter};
+
+        fn leaf<T: DeserializeOwned>(fig: &Figment, value: &Value) -> Result<(), Error> {
+            T::deserialize(ConfiguredValueDe::<'_, DefaultInterpreter>::from(fig, value)).map(|_| ())
+        }
+
+        let expected = |kind: &str| -> Result<(), Error> {
+            let kind = Kind::InvalidType(value.to_actual(), kind.into());
+            Err(Error::from(kind).retagged(value.tag()).resolved(fig))
+        };
+
+        let result = match (self, value) {
+            (Schema::Any, _) => Ok(()),
+            (Schema::Bool, _) => leaf::<bool>(fig, value),
+            (Schema::I8, _) => leaf::<i8>(fig, value),
+            (Schema::I16, _) => leaf::<i16>(fig, value),
+            (Schema::I32, _) => leaf::<i32>(fig, value),
+            (Schema::I64, _) => leaf::<i64>(fig, value),
+            (Schema::I128, _) => leaf::<i128>(fig, value),
+            (Schema::U8, _) => leaf::<u8>(fig, value),
+            (Schema::U16, _) => leaf::<u16>(fig, value),
+            (Schema::U32, _) => leaf::<u32>(fig, value),
+            (Schema::U64, _) => leaf::<u64>(fig, value),
+            (Schema::U128, _) => leaf::<u128>(fig, value),
+            (Schema::F32, _) => leaf::<f32>(fig, value),
+            (Schema::F64, _) => leaf::<f64>(fig, value),
+            (Schema::Char, _) => leaf::<char>(fig, value),
+            (Schema::String, _) => leaf::<String>(fig, value),
+            (Schema::Bytes, _) => leaf::<Vec<u8>>(fig, value),
+            (Schema::Unit, _) => leaf::<()>(fig, value),
+            (Schema::Option(_), Value::Empty(..)) => Ok(()),
+            (Schema::Option(schema), _) => return schema.check(fig, value, path, errors),
+            (Schema::Seq(schema), Value::Array(_, items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    path.push(i.to_string());
+                    schema.check(fig, item, path, errors);
+                    path.pop();
+                }
+
+                Ok(())
+            }
+            (Schema::Tuple(schemas), Value::Array(_, items)) if schemas.len() == items.len() => {
+                for (i, (schema, item)) in schemas.iter().zip(items).enumerate() {
+                    path.push(i.to_string());
+                    schema.check(fig, item, path, errors);
+                    path.pop();
+                }
+
+                Ok(())
+            }
+            (Schema::Tuple(schemas), Value::Array(_, items)) => {
+                let kind = Kind::InvalidLength(items.len(), schemas.len().to_string());
+                Err(Error::from(kind).retagged(value.tag()).resolved(fig))
+            }
+            (Schema::Map(schema), Value::Dict(_, dict)) => {
+                for (key, value) in dict {
+                    path.push(key.clone());
+                    schema.check(fig, value, path, errors);
+                    path.pop();
+                }
+
+                Ok(())
+            }
+            (Schema::Struct(fields), Value::Dict(_, dict)) => {
+                for (key, value) in dict {
+                    if let Some(schema) = fields.get(key) {
+                        path.push(key.clone());
+                        schema.check(fig, value, path, errors);
+                        path.pop();
+                    }
+                }
+
+                Ok(())
+            }
+            (Schema::Enum(variants), Value::String(_, s)) if variants.contains(s) => Ok(()),
+            (Schema::Enum(variants), Value::Dict(_, dict)) if dict.len() == 1
+                && dict.keys().all(|k| variants.contains(k)) => Ok(()),
+            (Schema::Enum(variants), _) => {
+                let variants = variants.iter()
+                    .map(|v| format!("`{}`", v))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                expected(&format!("one of the variants {}", variants))
+            }
+            (Schema::Seq(_), _) | (Schema::Tuple(_), _) => expected("a sequence"),
+            (Schema::Map(_), _) | (Schema::Struct(_), _) => expected("a dictionary"),
+        };
+
+        if let Err(e) = result {
+            let mut e = e;
+            for key in path.iter().rev() {
+                e = e.prefixed(key);
+            }
+
+            errors.push(e);
+        }
+    }
+
+    /// Returns an error for every key in `value`, the value at `path`, that
+    /// isn't a field of a struct in `self` and is denied by the figment's
+    /// [`UnknownPolicy`] for the struct's key path.
+    pub(crate) fn unknowns(&self, fig: &Figment, path: &str, value: &Value) -> Vec<Error> {
+        let mut path: Vec<String> = path.split('.')
+            .filter(|k| !k.is_empty())
+            .map(|k| k.to_string())
+            .collect();
+
+        let mut errors = vec![];
+        self.find_unknown(fig, value, &mut path, &mut errors);
+        errors
+    }
+
+    fn find_unknown_in(
+        &self,
+        fig: &Figment,
+        key: String,
+        value: &Value,
+        path: &mut Vec<String>,
+        errors: &mut Vec<Error>,
+    ) {
+        path.push(key);
+        self.find_unknown(fig, value, path, errors);
+        path.pop();
+    }
+
+    fn find_unknown(&self, fig: &Figment, value: &Value, path: &mut Vec<String>, errors: &mut Vec<Error>) {
+        match (self, value) {
+            (Schema::Option(schema), _) => schema.find_unknown(fig, value, path, errors),
+            (Schema::Seq(schema), Value::Array(_, items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    schema.find_unknown_in(fig, i.to_string(), item, path, errors);
+                }
+            }
+            (Schema::Tuple(schemas), Value::Array(_, items)) => {
+                for (i, (schema, item)) in schemas.iter().zip(items).enumerate() {
+                    schema.find_unknown_in(fig, i.to_string(), item, path, errors);
+                }
+            }
+            (Schema::Map(schema), Value::Dict(_, dict)) => {
+                for (key, value) in dict {
+                    schema.find_unknown_in(fig, key.clone(), value, path, errors);
+                }
+            }
+            (Schema::Struct(fields), Value::Dict(_, dict)) => {
+                let policy = UnknownPolicy::at(&fig.unknown, path);
+                for (key, value) in dict {
+                    let (schema, denied) = match fields.get(key) {
+                        Some(schema) => (schema, false),
+                        None => (&Schema::Any, policy.denies(value)),
+                    };
+
+                    if denied {
+                        let kind = match value {
+                            Value::Dict(..) => "section",
+                            _ => "key",
+                        };
+
+                        let expected = match fields.is_empty() {
+                            true => "there are none".to_string(),
+                            false => fields.keys()
+                                .map(|k| format!("`{}`", k))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        };
+
+                        let msg = format!("unknown {} `{}`, expected one of {}", kind, key, expected);
+                        let mut error = Error::from(Kind::Message(msg));
+                        error.path = path.iter().cloned().chain(Some(key.clone())).collect();
+                        error.set_suggestion(crate::typos::suggest(key, fields.keys().map(|k| k.as_str())));
+
+                        errors.push(error.retagged(value.tag()).resolved(fig));
+                    }
+
+                    schema.find_unknown_in(fig, key.clone(), value, path, errors);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Pushes an error for every key in `input`, the value at `path`, that is
+/// absent from `output`, the value extracted from `input` serialized back.
+/// Arrays are compared by index and dictionaries recursively. A value whose
+/// output is a magic value or of a different kind is consumed in its entirety.
+pub(crate) fn unconsumed(
+    fig: &Figment,
+    input: &Value,
+    output: &Value,
+    path: &mut Vec<String>,
+    errors: &mut Vec<Error>,
+) {
+    match (input, output) {
+        (Value::Dict(_, input), Value::Dict(_, output)) => {
+            if output.keys().any(|k| k.starts_with("___figment")) {
+                return;
+            }
+
+            for (key, value) in input {
+                path.push(key.clone());
+                match output.get(key) {
+                    Some(output) => unconsumed(fig, value, output, path, errors),
+                    None => {
+                        let kind = match value {
+                            Value::Dict(..) => "section",
+                            _ => "key",
+                        };
+
+                        let unused = output.keys()
+                            .map(|k| k.as_str())
+                            .filter(|k| !input.contains_key(*k));
+
+                        let msg = format!("unknown {} `{}`", kind, key);
+                        let mut error = Error::from(Kind::Message(msg));
+                        error.path = path.clone();
+                        error.set_suggestion(crate::typos::suggest(key, unused));
+                        errors.push(error.retagged(value.tag()).resolved(fig));
+                    }
+                }
+
+                path.pop();
+            }
+        }
+        (Value::Array(_, input), Value::Array(_, output)) => {
+            for (i, (input, output)) in input.iter().zip(output).enumerate() {
+                path.push(i.to_string());
+                unconsumed(fig, input, output, path, errors);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// What [`Figment::check_merges()`] does with provided values that don't match
+/// the schema.
+///
+/// [`Figment::check_merges()`]: crate::Figment::check_merges()
+#[cfg(feature = "schema")]
+#[cfg_attr(nightly, doc(cfg(feature = "schema")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnMismatch {
+    /// The figment fails: extraction returns an error for every mismatch.
+    Reject,
+    /// The offending value is dropped before merging and recorded as a
+    /// warning, retrievable via [`Figment::warnings()`].
+    ///
+    /// [`Figment::warnings()`]: crate::Figment::warnings()
+    Drop,
+    /// The offending value is kept and recorded as a warning, retrievable via
+    /// [`Figment::warnings()`].
+    ///
+    /// [`Figment::warnings()`]: crate::Figment::warnings()
+    Warn,
+}
+
+/// Which keys that aren't fields of the struct being extracted are errors.
+///
+/// By default, like `serde`, unknown keys are ignored. A policy can be set for
+/// the entire figment via [`Figment::unknown_keys()`] or for the subtree at a
+/// key path via [`Figment::unknown_keys_at()`]. The policy for a struct is the
+/// one set for the longest key path that contains the struct's key path.
+///
+/// Only structs with a [`Schema::Struct`] schema are checked: the contents of
+/// maps, `#[serde(flatten)]`ed fields, and enum variants are never unknown.
+///
+/// [`Figment::unknown_keys()`]: crate::Figment::unknown_keys()
+/// [`Figment::unknown_keys_at()`]: crate::Figment::unknown_keys_at()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnknownPolicy {
+    /// Unknown keys are ignored. This is the default.
+    Allow,
+    /// Unknown keys whose value is a dictionary, i.e, unknown sections, are
+    /// errors. Other unknown keys are ignored.
+    DenySections,
+    /// All unknown keys are errors.
+    DenyAll,
+}
+
+impl UnknownPolicy {
+    /// Returns the policy for the key path `path` in `policies`.
+    pub(crate) fn at(policies: &[(String, UnknownPolicy)], path: &[String]) -> UnknownPolicy {
+        let mut policy = (0, UnknownPolicy::Allow);
+        for (prefix, candidate) in policies {
+            let prefix: Vec<_> = prefix.split('.').filter(|k| !k.is_empty()).collect();
+            let contains = prefix.len() <= path.len()
+                && prefix.iter().zip(path).all(|(a, b)| a == b);
+
+            if contains && prefix.len() >= policy.0 {
+                policy = (prefix.len(), *candidate);
+            }
+        }
+
+        policy.1
+    }
+
+    fn denies(self, value: &Value) -> bool {
+        match self {
+            UnknownPolicy::Allow => false,
+            UnknownPolicy::DenySections => value.as_dict().is_some(),
+            UnknownPolicy::DenyAll => true,
+        }
+    }
+}
+
+/// Joins `errors` into a single error, the first error first.
+pub(crate) fn join(errors: Vec<Error>) -> Result<(), Error> {
+    match errors.into_iter().rev().reduce(|prev, e| prev.chain(e)) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment {
+    Field(&'static str),
+    Index(usize),
+    Item,
+    Key,
+    Value,
+    Some,
+    Variant,
+}
+
+#[derive(Debug, Clone)]
+enum Shape {
+    Leaf(Schema),
+    Option,
+    Seq,
+    Tuple(usize),
+    Map,
+    Struct(&'static [&'static str]),
+}
+
+#[derive(Default)]
+struct Trace {
+    shapes: BTreeMap<Vec<Segment>, Shape>,
+    skip: BTreeSet<Vec<Segment>>,
+    failure: Option<Vec<Segment>>,
+}
+
+impl Trace {
+    fn build(&self, path: &mut Vec<Segment>) -> Schema {
+        match self.shapes.get(&*path) {
+            None => Schema::Any,
+            Some(Shape::Leaf(schema)) => schema.clone(),
+            Some(Shape::Option) => Schema::Option(Box::new(self.child(path, Segment::Some))),
+            Some(Shape::Seq) => Schema::Seq(Box::new(self.child(path, Segment::Item))),
+            Some(Shape::Map) => Schema::Map(Box::new(self.child(path, Segment::Value))),
+            Some(Shape::Tuple(n)) => {
+                Schema::Tuple((0..*n).map(|i| self.child(path, Segment::Index(i))).collect())
+            }
+            Some(Shape::Struct(fields)) => Schema::Struct(fields.iter()
+                .map(|f| (f.to_string(), self.child(path, Segment::Field(f))))
+                .collect())
+        }
+    }
+
+    fn child(&self, path: &mut Vec<Segment>, segment: Segment) -> Schema {
+        path.push(segment);
+        let schema = self.build(path);
+        path.pop();
+        schema
+    }
+}
+
+struct Tracer<'t> {
+    trace: &'t mut Trace,
+    path: Vec<Segment>,
+    /// The names of the structs being traced, outermost first.
+    structs: Vec<&'static str>,
+}
+
+impl<'t> Tracer<'t> {
+    fn child(&mut self, segment: Segment) -> Tracer<'_> {
+        let mut path = self.path.clone();
+        path.push(segment);
+        Tracer { trace: self.trace, path, structs: self.structs.clone() }
+    }
+
+    /// Deserializes `seed` from the child at `segment`, recording the child's
+    /// path as the failure if deserialization fails and no deeper path has.
+    fn seed<'de, S>(&mut self, segment: Segment, seed: S) -> Result<S::Value, Error>
+        where S: DeserializeSeed<'de>
+    {
+        let child = self.child(segment);
+        let path = child.path.clone();
+        let result = seed.deserialize(child);
+        if result.is_err() && self.trace.failure.is_none() {
+            self.trace.failure = Some(path);
+        }
+
+        result
+    }
+
+    fn record(&mut self, shape: Shape) {
+        self.trace.shapes.insert(self.path.clone(), shape);
+    }
+
+    fn skipped(&self, segment: Segment) -> bool {
+        let mut path = self.path.clone();
+        path.push(segment);
+        self.trace.skip.contains(&path)
+    }
+
+    /// Records `schema` at the current path, then runs `f`, recording the
+    /// current path as the failure if `f` fails and no deeper path has.
+    fn leaf<T, F>(self, schema: Schema, f: F) -> Result<T, Error>
+        where F: FnOnce() -> Result<T, Error>
+    {
+        self.trace.shapes.insert(self.path.clone(), Shape::Leaf(schema));
+        self.visited(f)
+    }
+
+    fn visited<T, F>(self, f: F) -> Result<T, Error>
+        where F: FnOnce() -> Result<T, Error>
+    {
+        let result = f();
+        if result.is_err() && self.trace.failure.is_none() {
+            self.trace.failure = Some(self.path);
+        }
+
+        result
+    }
+
+    /// Runs `f` with a tracer for the current path, recording the current path
+    /// as the failure if `f` fails and no deeper path has or, if `outermost`,
+    /// irrespective of any deeper failures.
+    fn guarded<T, F>(self, outermost: bool, f: F) -> Result<T, Error>
+        where F: FnOnce(Tracer<'_>) -> Result<T, Error>
+    {
+        let failed = self.trace.failure.is_some();
+        let tracer = Tracer {
+            trace: &mut *self.trace,
+            path: self.path.clone(),
+            structs: self.structs.clone(),
+        };
+
+        let result = f(tracer);
+        if result.is_err() && (self.trace.failure.is_none() || (outermost && !failed)) {
+            self.trace.failure = Some(self.path);
+        }
+
+        result
+    }
+}
+
+macro_rules! trace_leaf {
+    ($($fn:ident => $schema:ident, $visit:ident($($arg:expr)?)),* $(,)?) => ($(
+        fn $fn<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            self.leaf(Schema::$schema, || visitor.$visit($($arg)?))
+        }
+    )*)
+}
+
+impl<'de, 't> de::Deserializer<'de> for Tracer<'t> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.leaf(Schema::Any, || visitor.visit_unit())
+    }
+
+    trace_leaf! {
+        deserialize_bool => Bool, visit_bool(false),
+        deserialize_i8 => I8, visit_i8(0),
+        deserialize_i16 => I16, visit_i16(0),
+        deserialize_i32 => I32, visit_i32(0),
+        deserialize_i64 => I64, visit_i64(0),
+        deserialize_i128 => I128, visit_i128(0),
+        deserialize_u8 => U8, visit_u8(0),
+        deserialize_u16 => U16, visit_u16(0),
+        deserialize_u32 => U32, visit_u32(0),
+        deserialize_u64 => U64, visit_u64(0),
+        deserialize_u128 => U128, visit_u128(0),
+        deserialize_f32 => F32, visit_f32(0.0),
+        deserialize_f64 => F64, visit_f64(0.0),
+        deserialize_char => Char, visit_char('a'),
+        deserialize_str => String, visit_str(""),
+        deserialize_string => String, visit_string(String::new()),
+        deserialize_identifier => String, visit_str(""),
+        deserialize_bytes => Bytes, visit_bytes(&[]),
+        deserialize_byte_buf => Bytes, visit_byte_buf(vec![]),
+        deserialize_unit => Unit, visit_unit(),
+        deserialize_ignored_any => Any, visit_unit(),
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V
+    ) -> Result<V::Value, Error> {
+        self.leaf(Schema::Unit, || visitor.visit_unit())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(Shape::Option);
+        match self.skipped(Segment::Some) {
+            true => visitor.visit_none(),
+            false => visitor.visit_some(self.child(Segment::Some)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(Shape::Seq);
+        let items = match self.skipped(Segment::Item) {
+            true => vec![],
+            false => vec![Segment::Item],
+        };
+
+        visitor.visit_seq(SeqTracer { tracer: self, items: items.into_iter() })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(mut self, n: usize, v: V) -> Result<V::Value, Error> {
+        self.record(Shape::Tuple(n));
+        let items: Vec<_> = (0..n).map(Segment::Index).collect();
+        v.visit_seq(SeqTracer { tracer: self, items: items.into_iter() })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        n: usize,
+        visitor: V
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(n, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(Shape::Map);
+        let entries = match self.skipped(Segment::Key) || self.skipped(Segment::Value) {
+            true => vec![],
+            false => vec![Entry::Map],
+        };
+
+        visitor.visit_map(MapTracer { tracer: self, entries: entries.into_iter() })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Error> {
+        use crate::value::magic::{Magic, RelativePathBuf, Duration, ByteSize};
+
+        match name {
+            Value::NAME | Duration::NAME | ByteSize::NAME => {
+                return self.leaf(Schema::Any, || Err(de::Error::custom(name)));
+            }
+            RelativePathBuf::NAME => {
+                return self.leaf(Schema::String, || Err(de::Error::custom(name)));
+            }
+            // A recursive type. Stop here instead of recursing forever.
+            _ if self.structs.contains(&name) => {
+                return self.leaf(Schema::Any, || Err(de::Error::custom(name)));
+            }
+            _ => self.record(Shape::Struct(fields)),
+        }
+
+        self.structs.push(name);
+
+        let entries: Vec<_> = fields.iter()
+            .filter(|f| !self.skipped(Segment::Field(f)))
+            .map(|f| Entry::Field(f))
+            .collect();
+
+        self.guarded(false, |tracer| {
+            visitor.visit_map(MapTracer { tracer, entries: entries.into_iter() })
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        _: &'static str,
+        variants: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Error> {
+        let schema = Schema::Enum(variants.iter().map(|v| v.to_string()).collect());
+        match variants.first() {
+            Some(variant) => {
+                self.record(Shape::Leaf(schema));
+                self.guarded(true, |tracer| visitor.visit_enum(EnumTracer { tracer, variant }))
+            }
+            None => self.leaf(schema, || Err(de::Error::custom("enum has no variants"))),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+}
+
+struct SeqTracer<'t> {
+    tracer: Tracer<'t>,
+    items: std::vec::IntoIter<Segment>,
+}
+
+impl<'de, 't> de::SeqAccess<'de> for SeqTracer<'t> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+        where T: DeserializeSeed<'de>
+    {
+        match self.items.next() {
+            Some(segment) => self.tracer.seed(segment, seed).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+enum Entry {
+    Map,
+    Field(&'static str),
+}
+
+struct MapTracer<'t> {
+    tracer: Tracer<'t>,
+    entries: std::vec::IntoIter<Entry>,
+}
+
+impl<'de, 't> de::MapAccess<'de> for MapTracer<'t> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+        where K: DeserializeSeed<'de>
+    {
+        match self.entries.as_slice().first() {
+            Some(Entry::Map) => self.tracer.seed(Segment::Key, seed).map(Some),
+            Some(Entry::Field(f)) => {
+                seed.deserialize(IntoDeserializer::<Error>::into_deserializer(*f)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+        where V: DeserializeSeed<'de>
+    {
+        match self.entries.next() {
+            Some(Entry::Map) => self.tracer.seed(Segment::Value, seed),
+            Some(Entry::Field(f)) => self.tracer.seed(Segment::Field(f), seed),
+            None => Err(de::Error::custom("value requested before key")),
+        }
+    }
+}
+
+struct EnumTracer<'t> {
+    tracer: Tracer<'t>,
+    variant: &'static str,
+}
+
+impl<'de, 't> de::EnumAccess<'de> for EnumTracer<'t> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+        where V: DeserializeSeed<'de>
+    {
+        let value = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, 't> de::VariantAccess<'de> for EnumTracer<'t> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(mut self, seed: T) -> Result<T::Value, Error>
+        where T: DeserializeSeed<'de>
+    {
+        seed.deserialize(self.tracer.child(Segment::Variant))
+    }
+
+    fn tuple_variant<V>(mut self, len: usize, visitor: V) -> Result<V::Value, Error>
+        where V: Visitor<'de>
+    {
+        de::Deserializer::deserialize_tuple(self.tracer.child(Segment::Variant), len, visitor)
+    }
+
+    fn struct_variant<V>(
+        mut self,
+        fields: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Error>
+        where V: Visitor<'de>
+    {
+        let tracer = self.tracer.child(Segment::Variant);
+        de::Deserializer::deserialize_struct(tracer, "", fields, visitor)
+    }
+}
+
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schema::Any => write!(f, "any value"),
+            Schema::Bool => write!(f, "a boolean"),
+            Schema::I8 => write!(f, "an i8"),
+            Schema::I16 => write!(f, "an i16"),
+            Schema::I32 => write!(f, "an i32"),
+            Schema::I64 => write!(f, "an i64"),
+            Schema::I128 => write!(f, "an i128"),
+            Schema::U8 => write!(f, "a u8"),
+            Schema::U16 => write!(f, "a u16"),
+            Schema::U32 => write!(f, "a u32"),
+            Schema::U64 => write!(f, "a u64"),
+            Schema::U128 => write!(f, "a u128"),
+            Schema::F32 => write!(f, "an f32"),
+            Schema::F64 => write!(f, "an f64"),
+            Schema::Char => write!(f, "a character"),
+            Schema::String => write!(f, "a string"),
+            Schema::Bytes => write!(f, "a byte array"),
+            Schema::Unit => write!(f, "a unit value"),
+            Schema::Option(s) => write!(f, "an optional {}", s),
+            Schema::Seq(s) => write!(f, "a sequence of {}", s),
+            Schema::Tuple(items) => write!(f, "a sequence of {} items", items.len()),
+            Schema::Map(s) => write!(f, "a dictionary of {}", s),
+            Schema::Struct(_) => write!(f, "a dictionary"),
+            Schema::Enum(_) => write!(f, "an enum variant"),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::mem::size_of;
+
+use crate::Profile;
+use crate::value::{Map, Dict, Value, Visit};
+
+/// Size statistics of a figment's data, returned by
+/// [`Figment::stats()`](crate::Figment::stats()).
+///
+/// Useful for diagnosing bloated configuration and for choosing sensible
+/// [`Limits`](crate::value::Limits).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    /// The number of providers combined into the figment.
+    pub providers: usize,
+    /// Statistics for each profile with data, in profile order.
+    pub profiles: Map<Profile, ProfileStats>,
+}
+
+/// Size statistics of the data in a single profile. See [`Stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProfileStats {
+    /// The total number of keys, at any depth.
+    pub keys: usize,
+    /// The total number of values, at any depth, including dictionaries and
+    /// arrays.
+    pub values: usize,
+    /// The deepest nesting of a value: `1` for a top-level value, `2` for a
+    /// value in a top-level dictionary or array, and so on. `0` if the profile
+    /// is empty.
+    pub depth: usize,
+    /// A rough estimate, in bytes, of the memory used by the profile's
+    /// values, including the inline size of each value and the contents of
+    /// keys and strings. Allocator and collection overhead are not included.
+    pub bytes: usize,
+}
+
+impl Stats {
+    pub(crate) fn new(providers: usize, data: &Map<Profile, Dict>) -> Self {
+        let profiles = data.iter()
+            .map(|(profile, dict)| (profile.clone(), ProfileStats::of(dict)))
+            .collect();
+
+        Stats { providers, profiles }
+    }
+
+    /// Returns the total number of keys, at any depth, across all profiles.
+    pub fn keys(&self) -> usize {
+        self.profiles.values().map(|s| s.keys).sum()
+    }
+
+    /// Returns the deepest nesting of a value across all profiles.
+    pub fn depth(&self) -> usize {
+        self.profiles.values().map(|s| s.depth).max().unwrap_or(0)
+    }
+
+    /// Returns the estimated memory use, in bytes, of all profiles.
+    pub fn bytes(&self) -> usize {
+        self.profiles.values().map(|s| s.bytes).sum()
+    }
+}
+
+impl ProfileStats {
+    fn of(dict: &Dict) -> Self {
+        let mut stats = ProfileStats::default();
+        for (key, value) in dict {
+            stats.keys += 1;
+            stats.bytes += key.len();
+            value.walk(&mut |path: &[String], value: &Value| {
+                stats.values += 1;
+                stats.depth = stats.depth.max(path.len() + 1);
+                stats.bytes += size_of::<Value>();
+                match value {
+                    Value::String(_, s) => stats.bytes += s.len(),
+                    Value::Dict(_, dict) => {
+                        stats.keys += dict.len();
+                        stats.bytes += dict.keys().map(|k| k.len()).sum::<usize>();
+                    }
+                    _ => {}
+                }
+
+                Visit::Continue
+            });
+        }
+
+        stats
+    }
+}
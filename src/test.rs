@@ -0,0 +1,332 @@
+//! Utilities for testing configurations and providers.
+//!
+//! This module is only available with the `test` feature enabled. It
+//! re-exports [`Jail`] for testing configurations in a sandboxed environment
+//! and provides [`ProviderHarness`] for testing [`Provider`] implementations.
+
+use std::fmt;
+
+use crate::{Profile, Provider, Metadata};
+use crate::value::{Map, Dict, Value};
+
+pub use crate::Jail;
+
+/// Checks a [`Provider`] implementation against the invariants expected of
+/// every provider.
+///
+/// Third-party providers are combined with one another in arbitrary orders
+/// and may be queried any number of times. A `ProviderHarness` calls the
+/// methods of a provider repeatedly and checks each [`Invariant`]:
+///
+///   * [`Invariant::StableMetadata`]: [`Provider::metadata()`] returns equal
+///     metadata, with a non-empty name, on every call.
+///   * [`Invariant::DeterministicData`]: [`Provider::data()`] returns equal
+///     data, or errors with equal messages, on every call.
+///   * [`Invariant::ProfileCorrectness`]: [`Provider::profile()`] returns the
+///     same profile on every call, and neither it nor any profile in the
+///     emitted data is empty.
+///   * [`Invariant::KeyValidity`]: every key in the emitted data, at any
+///     depth, is non-empty, contains no `.`, and has no leading or trailing
+///     whitespace, so that it can be addressed by key paths such as in
+///     [`Figment::extract_inner()`](crate::Figment::extract_inner()).
+///
+/// [`ProviderHarness::check()`] returns a [`Report`] listing every violation;
+/// [`ProviderHarness::assert()`] panics with the report if there are any.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Provider, Metadata, Profile, Error};
+/// use figment::value::{Map, Dict};
+/// use figment::test::{ProviderHarness, Invariant};
+/// use figment::util::map;
+///
+/// struct Good;
+///
+/// impl Provider for Good {
+///     fn metadata(&self) -> Metadata { Metadata::named("good") }
+///
+///     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+///         Ok(map![Profile::Default => map!["port".into() => 80.into()]])
+///     }
+/// }
+///
+/// ProviderHarness::new(Good).assert();
+///
+/// struct Bad;
+///
+/// impl Provider for Bad {
+///     fn metadata(&self) -> Metadata { Metadata::named("bad") }
+///
+///     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+///         Ok(map![Profile::Default => map!["server.port".into() => 80.into()]])
+///     }
+/// }
+///
+/// let report = ProviderHarness::new(Bad).check();
+/// assert!(!report.is_ok());
+/// assert_eq!(report.failures()[0].invariant, Invariant::KeyValidity);
+/// assert!(report.to_string().contains("`server.port`"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProviderHarness<P> {
+    provider: P,
+    calls: usize,
+}
+
+/// An invariant checked by a [`ProviderHarness`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Invariant {
+    /// The provider's metadata is the same on every call.
+    StableMetadata,
+    /// The provider's data is the same on every call.
+    DeterministicData,
+    /// The provider's profiles are valid and stable.
+    ProfileCorrectness,
+    /// The keys in the provider's data are addressable.
+    KeyValidity,
+}
+
+/// A violation of an [`Invariant`] found by a [`ProviderHarness`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    /// The violated invariant.
+    pub invariant: Invariant,
+    /// A description of the violation.
+    pub message: String,
+}
+
+/// The result of checking a provider with [`ProviderHarness::check()`].
+///
+/// A report displays as a human-readable summary of each [`Failure`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    name: String,
+    failures: Vec<Failure>,
+}
+
+impl<P: Provider> ProviderHarness<P> {
+    /// Returns a harness that checks `provider`, calling each method of the
+    /// provider three times.
+    pub fn new(provider: P) -> Self {
+        ProviderHarness { provider, calls: 3 }
+    }
+
+    /// Sets the number of times each method of the provider is called. Values
+    /// less than `2` are treated as `2`.
+    ///
+    /// ```rust
+    /// use figment::test::ProviderHarness;
+    ///
+    /// ProviderHarness::new(("key", "value")).calls(10).assert();
+    /// ```
+    pub fn calls(mut self, calls: usize) -> Self {
+        self.calls = calls.max(2);
+        self
+    }
+
+    /// Checks every [`Invariant`] and returns a report of the violations.
+    pub fn check(&self) -> Report {
+        let metadata = self.provider.metadata();
+        let mut report = Report { name: metadata.name.to_string(), failures: vec![] };
+        self.check_metadata(&metadata, &mut report);
+        self.check_profile(&mut report);
+        if let Some(data) = self.check_data(&mut report) {
+            for (profile, dict) in &data {
+                if profile.as_str().is_empty() {
+                    report.fail(Invariant::ProfileCorrectness, "data contains an empty profile");
+                }
+
+                check_keys(profile, &mut vec![], dict, &mut report);
+            }
+        }
+
+        report
+    }
+
+    /// Checks every [`Invariant`] and panics with a report of the violations
+    /// if there are any.
+    #[track_caller]
+    pub fn assert(&self) {
+        let report = self.check();
+        if !report.is_ok() {
+            panic!("{}", report);
+        }
+    }
+
+    fn check_metadata(&self, first: &Metadata, report: &mut Report) {
+        if first.name.is_empty() {
+            report.fail(Invariant::StableMetadata, "metadata name is empty");
+        }
+
+        for call in 2..=self.calls {
+            let metadata = self.provider.metadata();
+            if &metadata != first {
+                report.fail(Invariant::StableMetadata, format!(
+                    "metadata of call {} (`{}`) differs from call 1 (`{}`)",
+                    call, metadata.name, first.name));
+
+                break;
+            }
+        }
+    }
+
+    fn check_profile(&self, report: &mut Report) {
+        let first = self.provider.profile();
+        if matches!(&first, Some(p) if p.as_str().is_empty()) {
+            report.fail(Invariant::ProfileCorrectness, "selected profile is empty");
+        }
+
+        for call in 2..=self.calls {
+            let profile = self.provider.profile();
+            if profile != first {
+                report.fail(Invariant::ProfileCorrectness, format!(
+                    "selected profile of call {} ({:?}) differs from call 1 ({:?})",
+                    call, profile.as_ref().map(|p| p.as_str()), first.as_ref().map(|p| p.as_str())));
+
+                break;
+            }
+        }
+    }
+
+    fn check_data(&self, report: &mut Report) -> Option<Map<Profile, Dict>> {
+        let first = self.provider.data().map_err(|e| e.to_string());
+        for call in 2..=self.calls {
+            let data = self.provider.data().map_err(|e| e.to_string());
+            let difference = match (&first, &data) {
+                (Ok(a), Ok(b)) => diff(a, b),
+                (Err(a), Err(b)) if a == b => None,
+                (Err(a), Err(b)) => Some(format!("error `{}` became `{}`", a, b)),
+                (Ok(_), Err(e)) => Some(format!("data became error `{}`", e)),
+                (Err(e), Ok(_)) => Some(format!("error `{}` became data", e)),
+            };
+
+            if let Some(difference) = difference {
+                report.fail(Invariant::DeterministicData, format!(
+                    "data of call {} differs from call 1: {}", call, difference));
+
+                break;
+            }
+        }
+
+        first.ok()
+    }
+}
+
+/// Returns a description of the first difference between `a` and `b`, if any.
+fn diff(a: &Map<Profile, Dict>, b: &Map<Profile, Dict>) -> Option<String> {
+    fn diff_value(path: &mut Vec<String>, a: &Value, b: &Value) -> Option<String> {
+        match (a, b) {
+            (Value::Dict(_, a), Value::Dict(_, b)) => diff_dict(path, a, b),
+            _ if a != b => Some(format!("value at `{}` changed", path.join("."))),
+            _ => None,
+        }
+    }
+
+    fn diff_dict(path: &mut Vec<String>, a: &Dict, b: &Dict) -> Option<String> {
+        let keys = a.keys().chain(b.keys().filter(|k| !a.contains_key(*k)));
+        for key in keys {
+            path.push(key.clone());
+            let difference = match (a.get(key), b.get(key)) {
+                (Some(a), Some(b)) => diff_value(path, a, b),
+                (Some(_), None) => Some(format!("key `{}` disappeared", path.join("."))),
+                _ => Some(format!("key `{}` appeared", path.join("."))),
+            };
+
+            path.pop();
+            if difference.is_some() {
+                return difference;
+            }
+        }
+
+        None
+    }
+
+    let profiles = a.keys().chain(b.keys().filter(|p| !a.contains_key(*p)));
+    for profile in profiles {
+        let difference = match (a.get(profile), b.get(profile)) {
+            (Some(a), Some(b)) => diff_dict(&mut vec![], a, b),
+            (Some(_), None) => Some("profile disappeared".into()),
+            _ => Some("profile appeared".into()),
+        };
+
+        if let Some(difference) = difference {
+            return Some(format!("in profile `{}`, {}", profile, difference));
+        }
+    }
+
+    None
+}
+
+fn check_keys(profile: &Profile, path: &mut Vec<String>, dict: &Dict, report: &mut Report) {
+    for (key, value) in dict {
+        let problem = if key.is_empty() {
+            Some("is empty")
+        } else if key.contains('.') {
+            Some("contains a `.`")
+        } else if key.trim() != key {
+            Some("has leading or trailing whitespace")
+        } else {
+            None
+        };
+
+        path.push(key.clone());
+        if let Some(problem) = problem {
+            report.fail(Invariant::KeyValidity, format!(
+                "key `{}` in profile `{}` {}", path.join("."), profile, problem));
+        }
+
+        let mut stack = vec![value];
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::Dict(_, dict) => check_keys(profile, path, dict, report),
+                Value::Array(_, array) => stack.extend(array),
+                _ => {}
+            }
+        }
+
+        path.pop();
+    }
+}
+
+impl Report {
+    fn fail<M: Into<String>>(&mut self, invariant: Invariant, message: M) {
+        self.failures.push(Failure { invariant, message: message.into() });
+    }
+
+    /// Returns `true` if no invariant was violated.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the violations found, in the order they were found.
+    pub fn failures(&self) -> &[Failure] {
+        &self.failures
+    }
+}
+
+impl fmt::Display for Invariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Invariant::StableMetadata => "stable metadata".fmt(f),
+            Invariant::DeterministicData => "deterministic data".fmt(f),
+            Invariant::ProfileCorrectness => "profile correctness".fmt(f),
+            Invariant::KeyValidity => "key validity".fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return write!(f, "provider `{}` upholds all invariants", self.name);
+        }
+
+        write!(f, "provider `{}` violates {} invariant(s):", self.name, self.failures.len())?;
+        for failure in &self.failures {
+            write!(f, "\n  - {}: {}", failure.invariant, failure.message)?;
+        }
+
+        Ok(())
+    }
+}
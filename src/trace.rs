@@ -0,0 +1,178 @@
+use crate::{Profile, Resolution};
+use crate::coalesce::Order;
+use crate::value::{Value, Map, Tag};
+
+/// A key-level decision made while combining a provider into a figment,
+/// recorded when [`Figment::trace_merges()`] is enabled.
+///
+/// An event is recorded for every key the incoming provider supplies except
+/// for keys where both the existing and incoming values are dictionaries and
+/// neither value is chosen outright: these are unioned, and events are instead
+/// recorded for their keys. Decisions reflect conflict resolution by
+/// [`Provider::on_conflict()`](crate::Provider::on_conflict()) and the array
+/// strategy in effect, as set via [`Figment::arrays()`].
+/// The [`Metadata`](crate::Metadata) for both values can be retrieved via
+/// [`Figment::get_metadata()`].
+///
+/// [`Figment::trace_merges()`]: crate::Figment::trace_merges()
+/// [`Figment::arrays()`]: crate::Figment::arrays()
+/// [`Figment::get_metadata()`]: crate::Figment::get_metadata()
+///
+/// # Example
+///
+/// ```rust
+/// use figment::{Figment, MergeDecision};
+///
+/// let figment = Figment::new()
+///     .trace_merges(true)
+///     .merge(("name", "original"))
+///     .join(("name", "ignored"));
+///
+/// let event = &figment.merge_trace()[1];
+/// assert_eq!(event.path, "name");
+/// assert_eq!(event.decision, MergeDecision::KeptExisting);
+///
+/// let existing = figment.get_metadata(event.existing.unwrap()).unwrap();
+/// let incoming = figment.get_metadata(event.incoming).unwrap();
+/// assert_ne!(existing.provide_location, incoming.provide_location);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeEvent {
+    /// The profile the key was provided in.
+    pub profile: Profile,
+    /// The key path of the value, for instance `a.b.c`.
+    pub path: String,
+    /// The decision that was made.
+    pub decision: MergeDecision,
+    /// The tag of the existing value, if there was one.
+    pub existing: Option<Tag>,
+    /// The tag of the incoming value.
+    pub incoming: Tag,
+}
+
+/// The decision recorded by a [`MergeEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeDecision {
+    /// There was no existing value; the incoming value was added.
+    Added,
+    /// The existing value was kept; the incoming value was discarded.
+    KeptExisting,
+    /// The existing value was replaced by the incoming value.
+    Replaced,
+    /// The existing and incoming arrays were concatenated.
+    Concatenated,
+    /// The existing and incoming arrays were combined element-wise, by index
+    /// or by [key field](crate::Figment::array_key()).
+    Combined,
+    /// The key was removed; both values were discarded.
+    Removed,
+    /// Both values were discarded in favor of one supplied by
+    /// [`Provider::on_conflict()`](crate::Provider::on_conflict()).
+    Resolved,
+}
+
+/// The decisions made while coalescing a provider's data into a figment,
+/// recorded as they are made.
+pub(crate) struct Decisions {
+    /// Every decision and, if requested, the values it discarded.
+    decisions: Vec<(MergeEvent, Vec<Value>)>,
+    /// The strategy the provider is being combined with.
+    order: Order,
+    /// Whether to record discarded values.
+    shadow: bool,
+    /// The last key whose incoming dictionary was added as a whole.
+    added: Option<(Profile, String)>,
+}
+
+impl Decisions {
+    /// Returns a new recorder for a provider combined with `order` that
+    /// records discarded values if `shadow`.
+    pub(crate) fn new(order: Order, shadow: bool) -> Self {
+        Decisions { decisions: vec![], order, shadow, added: None }
+    }
+
+    /// Records the decision made for the key at `path` in `profile`, where
+    /// `existing` and `incoming` were resolved as `resolution`.
+    /// `decision`, if set, is the decision the resolution stands for.
+    pub(crate) fn record(
+        &mut self,
+        profile: &Profile,
+        path: &str,
+        existing: Option<&Value>,
+        incoming: &Value,
+        resolution: &Resolution,
+        decision: Option<MergeDecision>,
+    ) {
+        use {Value::Dict as D, Value::Array as A, Order::*, MergeDecision::*};
+
+        // Keys of a dictionary added as a whole aren't decisions of their own.
+        if let (None, Some((p, added))) = (existing, &self.added) {
+            if p == profile && path.starts_with(added.as_str()) && path[added.len()..].starts_with('.') {
+                return;
+            }
+        }
+
+        let decision = match (decision, resolution, existing, incoming, self.order) {
+            (Some(decision), ..) => decision,
+            (_, Resolution::Default, Some(D(..)), D(..), _) => return,
+            (_, Resolution::Default, None, ..) => Added,
+            (_, Resolution::Default, Some(A(..)), A(..), Adjoin | Admerge) => Concatenated,
+            (_, Resolution::Default, Some(_), _, Join | Adjoin) => KeptExisting,
+            (_, Resolution::Default, Some(_), _, Merge | Admerge) => Replaced,
+            (_, Resolution::KeepExisting, Some(_), ..) => KeptExisting,
+            (_, Resolution::KeepExisting, None, ..) | (_, Resolution::Remove, ..) => Removed,
+            (_, Resolution::UseIncoming, Some(_), ..) => Replaced,
+            (_, Resolution::UseIncoming, None, ..) => Added,
+            (_, Resolution::Value(_), ..) => Resolved,
+        };
+
+        if let (Resolution::Default, Added | Replaced, D(..)) = (resolution, decision, incoming) {
+            self.added = Some((profile.clone(), path.to_string()));
+        }
+
+        let discarded = match (self.shadow, decision) {
+            (false, _) | (_, Added | Concatenated | Combined) => vec![],
+            (true, KeptExisting) => vec![incoming.clone()],
+            (true, Replaced) => existing.into_iter().cloned().collect(),
+            (true, Removed | Resolved) => existing.into_iter().chain(Some(incoming)).cloned().collect(),
+        };
+
+        let event = MergeEvent {
+            profile: profile.clone(),
+            path: path.to_string(),
+            decision,
+            existing: existing.map(|v| v.tag()),
+            incoming: incoming.tag(),
+        };
+
+        self.decisions.push((event, discarded));
+    }
+
+    /// Returns the recorded events, in the order the decisions were made.
+    pub(crate) fn events(&self) -> impl Iterator<Item = &MergeEvent> {
+        self.decisions.iter().map(|(event, _)| event)
+    }
+
+    /// Adds the recorded events to `trace`, if it is `Some`, and the discarded
+    /// values to `shadowed`, if it is `Some`, keyed by profile and key path.
+    pub(crate) fn finish(
+        self,
+        trace: Option<&mut Vec<MergeEvent>>,
+        shadowed: Option<&mut Map<Profile, Map<String, Vec<Value>>>>,
+    ) {
+        let (mut trace, mut shadowed) = (trace, shadowed);
+        for (event, discarded) in self.decisions {
+            if let Some(shadowed) = shadowed.as_deref_mut().filter(|_| !discarded.is_empty()) {
+                shadowed.entry(event.profile.clone())
+                    .or_default()
+                    .entry(event.path.clone())
+                    .or_default()
+                    .extend(discarded);
+            }
+
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.push(event);
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{Figment, Profile};
+use crate::error::{Error, Kind};
+use crate::value::{Value, Map, Dict};
+
+/// Returns a warning for every key in `new` that is similar to, but not the
+/// same as, a sibling key in `old` absent from `new`, such as `time_out` and
+/// `timeout`. Keys are compared only within the same profile and dictionary.
+pub(crate) fn find(figment: &Figment, old: &Map<Profile, Dict>, new: &Map<Profile, Dict>) -> Vec<Error> {
+    let mut warnings = vec![];
+    for (profile, new_dict) in new {
+        if let Some(old_dict) = old.get(profile) {
+            find_in(figment, profile, &mut vec![], old_dict, new_dict, &mut warnings);
+        }
+    }
+
+    warnings
+}
+
+fn find_in(
+    figment: &Figment,
+    profile: &Profile,
+    path: &mut Vec<String>,
+    old: &Dict,
+    new: &Dict,
+    warnings: &mut Vec<Error>,
+) {
+    for (key, new_value) in new {
+        match (old.get(key), new_value) {
+            (Some(Value::Dict(_, old)), Value::Dict(_, new)) => {
+                path.push(key.clone());
+                find_in(figment, profile, path, old, new, warnings);
+                path.pop();
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                let similar = old.iter()
+                    .filter(|(k, _)| !new.contains_key(*k) && similar(k, key));
+
+                for (old_key, old_value) in similar {
+                    let source = match figment.get_metadata(old_value.tag()) {
+                        Some(md) => match &md.source {
+                            Some(source) => format!(" from {} `{}`", md.name, source),
+                            None => format!(" from {}", md.name),
+                        },
+                        None => String::new(),
+                    };
+
+                    let msg = format!("`{}` is similar to `{}`{}", key, old_key, source);
+                    let mut error = Error::from(Kind::Message(msg)).retagged(new_value.tag());
+                    error.metadata = figment.get_metadata(new_value.tag()).cloned();
+                    error.profile = Some(profile.clone());
+                    error.path = path.iter().cloned().chain(Some(key.clone())).collect();
+                    warnings.push(error);
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` if `a` and `b` likely spell the same key: if they're equal
+/// ignoring case, `_`, and `-`, or, when at least five characters long, are a
+/// single insertion, deletion, substitution, or transposition apart.
+pub(crate) fn similar(a: &str, b: &str) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    if a == b {
+        return true;
+    }
+
+    a.len().min(b.len()) >= 5 && edit_distance(&a, &b) <= 1
+}
+
+/// Returns the candidate most similar to `key`, if any is similar enough to
+/// likely be what was meant: if it differs from `key` only by case, `_`, and
+/// `-`, or by at most a third of its characters, and at least by one.
+pub(crate) fn suggest<'a, I>(key: &str, candidates: I) -> Option<&'a str>
+    where I: IntoIterator<Item = &'a str>
+{
+    let target = normalize(key);
+    let max = (target.len() / 3).max(1);
+    candidates.into_iter()
+        .filter(|candidate| *candidate != key)
+        .map(|candidate| (edit_distance(&target, &normalize(candidate)), candidate))
+        .filter(|(distance, _)| *distance <= max)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Returns the characters of `key` lowercased, without `_` and `-`.
+fn normalize(key: &str) -> Vec<char> {
+    key.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// The optimal string alignment distance between `a` and `b`.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut d = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d = d.min(rows[i - 2][j - 2] + 1);
+            }
+
+            rows[i][j] = d;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
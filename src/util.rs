@@ -283,6 +283,39 @@ pub fn nest(key: &str, value: Value) -> Value {
     value_from(key.split('.'), value)
 }
 
+/// Returns the 64-bit FNV-1a checksum of `bytes`, the checksum the built-in
+/// providers record via [`Metadata::checksum()`](crate::Metadata::checksum()).
+///
+/// The checksum is stable across processes and platforms but is not
+/// cryptographic: it detects changes, not tampering.
+///
+/// ```
+/// use figment::util::checksum;
+///
+/// assert_eq!(checksum(b""), 0xcbf29ce484222325);
+/// assert_eq!(checksum(b"port = 8080"), checksum(b"port = 8080"));
+/// assert_ne!(checksum(b"port = 8080"), checksum(b"port = 8081"));
+/// ```
+pub fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Removes the value at the key path `path` from `dict`, if there is one.
+#[cfg(feature = "schema")]
+pub(crate) fn remove_path<K: AsRef<str>>(dict: &mut Dict, path: &[K]) {
+    match path {
+        [] => {},
+        [key] => { dict.remove(key.as_ref()); },
+        [key, rest @ ..] => {
+            if let Some(Value::Dict(_, dict)) = dict.get_mut(key.as_ref()) {
+                remove_path(dict, rest);
+            }
+        }
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 /// This is a macro.
@@ -335,3 +368,79 @@ macro_rules! cloneable_fn_trait {
 }
 
 pub(crate) use cloneable_fn_trait;
+
+/// Decodes `%XX` escape sequences in `string`. Returns `None` if a sequence is
+/// malformed or the decoded bytes are not valid UTF-8.
+pub(crate) fn percent_decode(string: &str) -> Option<String> {
+    let bytes = string.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = bytes.get(i + 1..i + 3)?;
+                if !hex.iter().all(|b| b.is_ascii_hexdigit()) {
+                    return None;
+                }
+
+                let hex = std::str::from_utf8(hex).ok()?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// Returns `true` if the key path `path` matches `glob`, a `.`-separated key
+/// path whose components are matched against keys with [`glob_match()`] or
+/// are `**`, which matches any number of keys, including none.
+pub(crate) fn path_glob_match<K: AsRef<str>>(glob: &str, path: &[K]) -> bool {
+    fn keys<K: AsRef<str>>(glob: &[&str], path: &[K]) -> bool {
+        match glob.split_first() {
+            None => path.is_empty(),
+            Some((&"**", rest)) => (0..=path.len()).any(|i| keys(rest, &path[i..])),
+            Some((first, rest)) => match path.split_first() {
+                Some((key, path)) => glob_match(first, key.as_ref()) && keys(rest, path),
+                None => false,
+            },
+        }
+    }
+
+    keys(&glob.split('.').collect::<Vec<_>>(), path)
+}
+
+/// Returns `true` if `name` matches `pattern`, in which `*` matches any run of
+/// characters and `?` any single character.
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    star = Some((sp, sn + 1));
+                    p = sp + 1;
+                    n = sn + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
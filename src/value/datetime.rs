@@ -0,0 +1,263 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A TOML-style date, time, or date and time, with an optional offset.
+///
+/// `Toml` providers emit datetimes as [`Value::Datetime`](crate::value::Value)
+/// values. On extraction, a `Datetime` deserializes as its string form, like
+/// datetimes from every other source, so it can be extracted into a `String`
+/// or into any type that deserializes from a string, such as `chrono` or
+/// `time` datetimes. It can also be extracted into `toml` datetime types.
+///
+/// A `Datetime` parses from and displays as an [RFC 3339] datetime or one of
+/// its TOML variants: a local datetime without an offset, a local date, or a
+/// local time.
+///
+/// ```rust
+/// use figment::value::{Datetime, Offset};
+///
+/// let datetime: Datetime = "1979-05-27T00:32:00.999-07:00".parse().unwrap();
+/// assert_eq!(datetime.date.unwrap().year, 1979);
+/// assert_eq!(datetime.time.unwrap().nanosecond, 999_000_000);
+/// assert_eq!(datetime.offset, Some(Offset::Custom { minutes: -420 }));
+/// assert_eq!(datetime.to_string(), "1979-05-27T00:32:00.999-07:00");
+///
+/// let date: Datetime = "1979-05-27".parse().unwrap();
+/// assert!(date.time.is_none());
+///
+/// assert!("1979-13-27".parse::<Datetime>().is_err());
+/// ```
+///
+/// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Datetime {
+    /// The date, if any.
+    pub date: Option<Date>,
+    /// The time, if any.
+    pub time: Option<Time>,
+    /// The offset from UTC, if any. Only present with both a date and a time.
+    pub offset: Option<Offset>,
+}
+
+/// The date of a [`Datetime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year, `0` to `9999`.
+    pub year: u16,
+    /// The month, `1` to `12`.
+    pub month: u8,
+    /// The day of the month, `1` to `31`.
+    pub day: u8,
+}
+
+/// The time of a [`Datetime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// The hour, `0` to `23`.
+    pub hour: u8,
+    /// The minute, `0` to `59`.
+    pub minute: u8,
+    /// The second, `0` to `60` to allow for leap seconds.
+    pub second: u8,
+    /// The fraction of the second in nanoseconds.
+    pub nanosecond: u32,
+}
+
+/// The offset from UTC of a [`Datetime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Offset {
+    /// UTC, written `Z`.
+    Z,
+    /// An offset of `minutes`, written `+HH:MM` or `-HH:MM`.
+    Custom {
+        /// The offset in minutes, `-1439` to `1439`.
+        minutes: i16,
+    },
+}
+
+/// An error parsing a [`Datetime`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatetimeParseError {
+    _private: (),
+}
+
+/// Parses the unsigned decimal `digits`, which must be exactly `n` digits.
+fn digits(digits: &str, n: usize) -> Result<u32, DatetimeParseError> {
+    match digits.len() == n && digits.bytes().all(|b| b.is_ascii_digit()) {
+        true => digits.parse().map_err(|_| DatetimeParseError { _private: () }),
+        false => Err(DatetimeParseError { _private: () }),
+    }
+}
+
+impl FromStr for Date {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = DatetimeParseError { _private: () };
+        let (year, rest) = s.split_once('-').ok_or_else(|| err.clone())?;
+        let (month, day) = rest.split_once('-').ok_or_else(|| err.clone())?;
+        let date = Date {
+            year: digits(year, 4)? as u16,
+            month: digits(month, 2)? as u8,
+            day: digits(day, 2)? as u8,
+        };
+
+        let leap = matches!((date.year % 4, date.year % 100, date.year % 400), (0, 1.., _) | (_, _, 0));
+        let days = match date.month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => return Err(err),
+        };
+
+        match date.day >= 1 && date.day <= days {
+            true => Ok(date),
+            false => Err(err),
+        }
+    }
+}
+
+impl FromStr for Time {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = DatetimeParseError { _private: () };
+        let (hms, fraction) = s.split_once('.').unwrap_or((s, ""));
+        let mut parts = hms.split(':');
+        let mut part = || parts.next().ok_or_else(|| err.clone()).and_then(|p| digits(p, 2));
+        let (hour, minute, second) = (part()?, part()?, part()?);
+        if parts.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+            return Err(err);
+        }
+
+        let nanosecond = match s.contains('.') {
+            true if !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit()) => {
+                let fraction = &fraction[..fraction.len().min(9)];
+                digits(fraction, fraction.len())? * 10u32.pow(9 - fraction.len() as u32)
+            }
+            true => return Err(err),
+            false => 0,
+        };
+
+        Ok(Time { hour: hour as u8, minute: minute as u8, second: second as u8, nanosecond })
+    }
+}
+
+impl FromStr for Offset {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = DatetimeParseError { _private: () };
+        if s == "Z" || s == "z" {
+            return Ok(Offset::Z);
+        }
+
+        let (sign, hh_mm) = match s.as_bytes().first() {
+            Some(b'+') => (1, &s[1..]),
+            Some(b'-') => (-1, &s[1..]),
+            _ => return Err(err),
+        };
+
+        let (hours, minutes) = hh_mm.split_once(':').ok_or_else(|| err.clone())?;
+        let (hours, minutes) = (digits(hours, 2)?, digits(minutes, 2)?);
+        if hours > 23 || minutes > 59 {
+            return Err(err);
+        }
+
+        Ok(Offset::Custom { minutes: sign * (hours * 60 + minutes) as i16 })
+    }
+}
+
+impl FromStr for Datetime {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_date = s.len() >= 10 && s.as_bytes()[4] == b'-';
+        if !is_date {
+            return Ok(Datetime { date: None, time: Some(s.parse()?), offset: None });
+        }
+
+        let date = Some(s.get(..10).ok_or(DatetimeParseError { _private: () })?.parse()?);
+        let rest = &s[10..];
+        if rest.is_empty() {
+            return Ok(Datetime { date, time: None, offset: None });
+        }
+
+        match rest.as_bytes()[0] {
+            b'T' | b't' | b' ' => {},
+            _ => return Err(DatetimeParseError { _private: () }),
+        }
+
+        let rest = &rest[1..];
+        let offset_at = rest.find(&['Z', 'z', '+', '-'][..]).unwrap_or(rest.len());
+        let time = Some(rest[..offset_at].parse()?);
+        let offset = match &rest[offset_at..] {
+            "" => None,
+            offset => Some(offset.parse()?),
+        };
+
+        Ok(Datetime { date, time, offset })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanosecond != 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Offset::Z => f.write_str("Z"),
+            Offset::Custom { minutes } => {
+                let sign = if minutes < 0 { '-' } else { '+' };
+                let minutes = minutes.unsigned_abs();
+                write!(f, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Datetime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(date) = &self.date {
+            date.fmt(f)?;
+        }
+
+        if let Some(time) = &self.time {
+            if self.date.is_some() {
+                f.write_str("T")?;
+            }
+
+            time.fmt(f)?;
+        }
+
+        if let Some(offset) = &self.offset {
+            offset.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for DatetimeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid datetime")
+    }
+}
+
+impl std::error::Error for DatetimeParseError {}
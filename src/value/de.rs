@@ -8,8 +8,8 @@ use serde::Deserialize;
 use serde::de::{self, Deserializer, IntoDeserializer, Visitor};
 use serde::de::{SeqAccess, MapAccess, VariantAccess};
 
-use crate::Figment;
-use crate::error::{Error, Kind, Result};
+use crate::{Figment, Monitor};
+use crate::error::{Error, Kind, Result, Actual};
 use crate::value::{Value, Num, Empty, Dict, Tag};
 
 pub trait Interpreter {
@@ -40,16 +40,96 @@ impl Interpreter for LossyInterpreter {
     }
 }
 
+/// The name of the struct, and of its lone field, as which the `toml` crates
+/// represent datetimes.
+const TOML_DATETIME: &str = "$__toml_private_Datetime";
+const TOML_DATETIME_FIELD: &str = "$__toml_private_datetime";
+
+/// Returns the datetime string of `dict` if it is a TOML datetime.
+fn toml_datetime(dict: &Dict) -> Option<&str> {
+    match dict.len() {
+        1 => dict.get(TOML_DATETIME_FIELD)?.as_str(),
+        _ => None,
+    }
+}
+
 pub struct ConfiguredValueDe<'c, I = DefaultInterpreter> {
     pub config: &'c Figment,
     pub value: &'c Value,
     pub readable: Cell<bool>,
+    pub monitor: Option<&'c Monitor<'c>>,
+    pub skipped: &'c [&'c Value],
     _phantom: PhantomData<I>
 }
 
 impl<'c, I: Interpreter> ConfiguredValueDe<'c, I> {
     pub fn from(config: &'c Figment, value: &'c Value) -> Self {
-        Self { config, value, readable: Cell::from(true), _phantom: PhantomData }
+        Self {
+            config, value,
+            readable: Cell::from(true),
+            monitor: None,
+            skipped: &[],
+            _phantom: PhantomData
+        }
+    }
+
+    /// Sets the monitor notified of every key deserialized by `self` and its
+    /// children.
+    pub fn monitored(mut self, monitor: Option<&'c Monitor<'c>>) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Sets the values, identified by address, in place of which `self` and
+    /// its children deserialize a [`Placeholder`].
+    pub fn skipping(mut self, skipped: &'c [&'c Value]) -> Self {
+        self.skipped = skipped;
+        self
+    }
+
+    /// Returns a deserializer for `value`, a child of `self.value`.
+    pub fn child(&self, value: &'c Value) -> Self {
+        Self::from(self.config, value).monitored(self.monitor).skipping(self.skipped)
+    }
+
+    /// Interprets the value as a number via `I`, then, if it is still a
+    /// string, via the figment's [`NumericLeniency`](crate::value::NumericLeniency).
+    fn interpret_as_num(&self) -> Cow<'c, Value> {
+        let value = I::interpret_as_num(self.value);
+        let lenient = match &*value {
+            Value::String(tag, s) => self.config.numeric.parse(s).map(|n| Value::Num(*tag, n)),
+            _ => None,
+        };
+
+        lenient.map(Cow::Owned).unwrap_or(value)
+    }
+
+    /// Interprets the value as a number, then coerces it for extraction as an
+    /// integer of `bits` bits, signed if `signed`, per the figment's
+    /// [`NumPolicy`](crate::value::NumPolicy).
+    fn interpret_as_int(&self, bits: u32, signed: bool) -> Cow<'c, Value> {
+        let value = self.interpret_as_num();
+        let coerced = match &*value {
+            Value::Num(tag, n) => self.config.num_policy.coerce_int(n, bits, signed)
+                .map(|n| Value::Num(*tag, n)),
+            _ => None,
+        };
+
+        coerced.map(Cow::Owned).unwrap_or(value)
+    }
+
+    /// Interprets the value as a number, failing if it is a `NaN` or infinite
+    /// float that the figment's [`NumPolicy`](crate::value::NumPolicy)
+    /// rejects.
+    fn interpret_as_float(&self) -> Result<Cow<'c, Value>> {
+        let value = self.interpret_as_num();
+        if let Value::Num(_, n) = &*value {
+            self.config.num_policy.check_finite(n).map_err(|f| {
+                Error::from(Kind::InvalidValue(Actual::Float(f), "a finite number".into()))
+            })?;
+        }
+
+        Ok(value)
     }
 }
 
@@ -70,36 +150,57 @@ macro_rules! apply_then_forward_to_deserialize_any {
     }
 }
 
+/// Implements the integer `deserialize_$f` methods, each of which coerces the
+/// value via [`ConfiguredValueDe::interpret_as_int()`] with the given width
+/// and signedness before calling `deserialize_any()`.
+macro_rules! deserialize_ints {
+    ($($f:ident: $bits:expr, $signed:expr),* $(,)?) => {
+        $(
+            fn $f<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                let de = &self;
+                de.interpret_as_int($bits, $signed)
+                    .deserialize_any(visitor)
+                    .map_err(|e| contextualize(e, de.config, de.value))
+            }
+        )*
+    }
+}
+
 impl<'de: 'c, 'c, I: Interpreter> Deserializer<'de> for ConfiguredValueDe<'c, I> {
     type Error = Error;
 
     fn deserialize_any<V>(self, v: V) -> Result<V::Value>
         where V: de::Visitor<'de>
     {
-        let maker = |v| Self::from(self.config, v);
+        let maker = |v| self.child(v);
         let result = match *self.value {
             Value::String(_, ref s) => v.visit_str(s),
             Value::Char(_, c) => v.visit_char(c),
             Value::Bool(_, b) => v.visit_bool(b),
             Value::Num(_, n) => n.deserialize_any(v),
             Value::Empty(_, e) => e.deserialize_any(v),
-            Value::Dict(_, ref map) => v.visit_map(MapDe::new(map, maker)),
-            Value::Array(_, ref seq) => v.visit_seq(SeqDe::new(seq, maker)),
+            Value::Dict(_, ref map) => {
+                v.visit_map(MapDe::new(map, maker).monitored(self.monitor).skipping(self.skipped))
+            }
+            Value::Array(_, ref seq) => {
+                v.visit_seq(SeqDe::new(seq, maker).monitored(self.monitor).skipping(self.skipped))
+            }
+            Value::Datetime(_, ref d) => v.visit_string(d.to_string()),
         };
 
-        result.map_err(|e| e.retagged(self.value.tag()).resolved(self.config))
+        result.map_err(|e| contextualize(e, self.config, self.value))
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
         where V: Visitor<'de>
     {
-        let (config, tag) = (self.config, self.value.tag());
+        let (config, value) = (self.config, self.value);
         let result = match self.value {
             Value::Empty(_, val) => val.deserialize_any(visitor),
             _ => visitor.visit_some(self)
         };
 
-        result.map_err(|e| e.retagged(tag).resolved(config))
+        result.map_err(|e| contextualize(e, config, value))
     }
 
     fn deserialize_struct<V: Visitor<'de>>(
@@ -110,16 +211,30 @@ impl<'de: 'c, 'c, I: Interpreter> Deserializer<'de> for ConfiguredValueDe<'c, I>
     ) -> Result<V::Value> {
         use crate::value::magic::*;
 
-        let (config, tag) = (self.config, self.value.tag());
+        let (config, value) = (self.config, self.value);
         let result = match name {
             Value::NAME => Value::deserialize_from(self, visitor),
             RelativePathBuf::NAME => RelativePathBuf::deserialize_from(self, visitor),
             Tagged::<()>::NAME => Tagged::<()>::deserialize_from(self, visitor),
+            Duration::NAME => Duration::deserialize_from(self, visitor),
+            ByteSize::NAME => ByteSize::deserialize_from(self, visitor),
+            Secret::<()>::NAME => Secret::<()>::deserialize_from(self, visitor),
+            TOML_DATETIME => match self.value {
+                Value::String(_, s) => {
+                    let entry = std::iter::once((TOML_DATETIME_FIELD, s.clone()));
+                    visitor.visit_map(de::value::MapDeserializer::new(entry))
+                }
+                Value::Datetime(_, d) => {
+                    let entry = std::iter::once((TOML_DATETIME_FIELD, d.to_string()));
+                    visitor.visit_map(de::value::MapDeserializer::new(entry))
+                }
+                _ => self.deserialize_any(visitor),
+            },
             // SelectedProfile::NAME => SelectedProfile::deserialize_from(self, visitor),
             _ => self.deserialize_any(visitor)
         };
 
-        result.map_err(|e| e.retagged(tag).resolved(config))
+        result.map_err(|e| contextualize(e, config, value))
     }
 
     fn deserialize_enum<V: Visitor<'de>>(
@@ -130,12 +245,14 @@ impl<'de: 'c, 'c, I: Interpreter> Deserializer<'de> for ConfiguredValueDe<'c, I>
     ) -> Result<V::Value> {
         use serde::de::value::MapAccessDeserializer;
 
-        let (config, tag) = (self.config, self.value.tag());
+        let (config, value) = (self.config, self.value);
         let result = match self.value {
             Value::String(_, s) => v.visit_enum((&**s).into_deserializer()),
             Value::Dict(_, ref map) => {
-                let maker = |v| Self::from(self.config, v);
-                let map_access = MapDe::new(map, maker);
+                let maker = |v| self.child(v);
+                let map_access = MapDe::new(map, maker)
+                    .monitored(self.monitor)
+                    .skipping(self.skipped);
                 v.visit_enum(MapAccessDeserializer::new(map_access))
             }
             Value::Num(_, n) if n.to_u32().is_some() => {
@@ -145,7 +262,7 @@ impl<'de: 'c, 'c, I: Interpreter> Deserializer<'de> for ConfiguredValueDe<'c, I>
             _ => self.deserialize_any(v)
         };
 
-        result.map_err(|e| e.retagged(tag).resolved(config))
+        result.map_err(|e| contextualize(e, config, value))
     }
 
     fn deserialize_newtype_struct<V: Visitor<'de>>(
@@ -156,6 +273,18 @@ impl<'de: 'c, 'c, I: Interpreter> Deserializer<'de> for ConfiguredValueDe<'c, I>
         visitor.visit_newtype_struct(self)
     }
 
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value.as_dict().and_then(toml_datetime) {
+            Some(datetime) => visitor.visit_str(datetime)
+                .map_err(|e: Error| contextualize(e, self.config, self.value)),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
     fn is_human_readable(&self) -> bool {
         let val = self.readable.get();
         self.readable.set(!val);
@@ -165,32 +294,81 @@ impl<'de: 'c, 'c, I: Interpreter> Deserializer<'de> for ConfiguredValueDe<'c, I>
     apply_then_forward_to_deserialize_any! {
         deserialize_bool =>
             |de| I::interpret_as_bool(de.value),
-            |e| e.retagged(de.value.tag()).resolved(de.config),
-        deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64,
-        deserialize_i8, deserialize_i16, deserialize_i32, deserialize_i64,
-        deserialize_f32, deserialize_f64 =>
-            |de| I::interpret_as_num(de.value),
-            |e| e.retagged(de.value.tag()).resolved(de.config),
+            |e| contextualize(e, de.config, de.value),
+    }
+
+    deserialize_ints! {
+        deserialize_u8: 8, false,
+        deserialize_u16: 16, false,
+        deserialize_u32: 32, false,
+        deserialize_u64: 64, false,
+        deserialize_u128: 128, false,
+        deserialize_i8: 8, true,
+        deserialize_i16: 16, true,
+        deserialize_i32: 32, true,
+        deserialize_i64: 64, true,
+        deserialize_i128: 128, true,
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.interpret_as_float()
+            .and_then(|value| value.deserialize_any(visitor))
+            .map_err(|e| contextualize(e, self.config, self.value))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.interpret_as_float()
+            .and_then(|value| value.deserialize_any(visitor))
+            .map_err(|e| contextualize(e, self.config, self.value))
     }
 
     serde::forward_to_deserialize_any! {
-        char str
-        string seq bytes byte_buf map unit
+        char seq bytes byte_buf map unit
         ignored_any unit_struct tuple_struct tuple identifier
     }
 }
 
+/// Adds the context of `value`, the value being deserialized with `config`,
+/// to `error`, an error deserializing it: its tag, the metadata and profiles
+/// from `config`, and, if enabled via
+/// [`Figment::error_samples()`](crate::Figment::error_samples()) and the
+/// error occured at `value` itself, a sample of `value`.
+fn contextualize(error: Error, config: &Figment, value: &Value) -> Error {
+    let mut error = error.retagged(value.tag()).resolved(config);
+    if let Some(entries) = config.samples {
+        if error.path.is_empty() && error.sample().is_none() && !error.cancelled() {
+            error.set_sample(value.sample(entries));
+        }
+    }
+
+    error
+}
+
 use std::collections::btree_map::Iter;
 
 pub struct MapDe<'m, D, F: Fn(&'m Value) -> D> {
     iter: Iter<'m, String, Value>,
     pair: Option<(&'m String, &'m Value)>,
     make_deserializer: F,
+    monitor: Option<&'m Monitor<'m>>,
+    skipped: &'m [&'m Value],
 }
 
 impl<'m, D, F: Fn(&'m Value) -> D> MapDe<'m, D, F> {
     pub fn new(map: &'m Dict, maker: F) -> Self {
-        MapDe { iter: map.iter(), pair: None, make_deserializer: maker }
+        MapDe { iter: map.iter(), pair: None, make_deserializer: maker, monitor: None, skipped: &[] }
+    }
+
+    /// Sets the monitor notified of every entry visited.
+    pub fn monitored(mut self, monitor: Option<&'m Monitor<'m>>) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Sets the values in place of which a [`Placeholder`] is deserialized.
+    pub fn skipping(mut self, skipped: &'m [&'m Value]) -> Self {
+        self.skipped = skipped;
+        self
     }
 }
 
@@ -203,6 +381,10 @@ impl<'m, 'de, D, F> de::MapAccess<'de> for MapDe<'m, D, F>
         where K: de::DeserializeSeed<'de>
     {
         if let Some((k, v)) = self.iter.next() {
+            if let Some(monitor) = self.monitor {
+                monitor.tick().map_err(|e| e.prefixed(k).retagged(v.tag()))?;
+            }
+
             let result = seed.deserialize(k.as_str().into_deserializer())
                 .map_err(|e: Error| e.prefixed(k).retagged(v.tag()))
                 .map(Some);
@@ -219,8 +401,12 @@ impl<'m, 'de, D, F> de::MapAccess<'de> for MapDe<'m, D, F>
     {
         let (key, value) = self.pair.take().expect("visit_value called before visit_key");
         let tag = value.tag();
-        seed.deserialize((self.make_deserializer)(value))
-            .map_err(|e: Error| e.prefixed(key).retagged(tag))
+        let result = match is_skipped(self.skipped, value) {
+            true => seed.deserialize(Placeholder),
+            false => seed.deserialize((self.make_deserializer)(value)),
+        };
+
+        result.map_err(|e: Error| e.prefixed(key).retagged(tag))
     }
 }
 
@@ -228,11 +414,26 @@ pub struct SeqDe<'v, D, F: Fn(&'v Value) -> D> {
     iter: std::iter::Enumerate<std::slice::Iter<'v, Value>>,
     len: usize,
     make_deserializer: F,
+    monitor: Option<&'v Monitor<'v>>,
+    skipped: &'v [&'v Value],
 }
 
 impl<'v, D, F: Fn(&'v Value) -> D> SeqDe<'v, D, F> {
     pub fn new(seq: &'v [Value], maker: F) -> Self {
-        SeqDe { len: seq.len(), iter: seq.iter().enumerate(), make_deserializer: maker }
+        let (len, iter) = (seq.len(), seq.iter().enumerate());
+        SeqDe { len, iter, make_deserializer: maker, monitor: None, skipped: &[] }
+    }
+
+    /// Sets the monitor notified of every element visited.
+    pub fn monitored(mut self, monitor: Option<&'v Monitor<'v>>) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Sets the values in place of which a [`Placeholder`] is deserialized.
+    pub fn skipping(mut self, skipped: &'v [&'v Value]) -> Self {
+        self.skipped = skipped;
+        self
     }
 }
 
@@ -247,9 +448,16 @@ impl<'v, 'de, D, F> de::SeqAccess<'de> for SeqDe<'v, D, F>
         if let Some((i, item)) = self.iter.next() {
             // item.map_tag(|metadata| metadata.path.push(self.count.to_string()));
             self.len -= 1;
-            seed.deserialize((self.make_deserializer)(item))
-                .map_err(|e: Error| e.prefixed(&i.to_string()))
-                .map(Some)
+            if let Some(monitor) = self.monitor {
+                monitor.tick().map_err(|e| e.prefixed(&i.to_string()))?;
+            }
+
+            let result = match is_skipped(self.skipped, item) {
+                true => seed.deserialize(Placeholder),
+                false => seed.deserialize((self.make_deserializer)(item)),
+            };
+
+            result.map_err(|e: Error| e.prefixed(&i.to_string())).map(Some)
         } else {
             Ok(None)
         }
@@ -260,6 +468,172 @@ impl<'v, 'de, D, F> de::SeqAccess<'de> for SeqDe<'v, D, F>
     }
 }
 
+/// Returns `true` if `value` is, by address, one of `skipped`.
+fn is_skipped(skipped: &[&Value], value: &Value) -> bool {
+    skipped.iter().any(|s| std::ptr::eq(*s, value))
+}
+
+/// A deserializer of a zero, empty, or otherwise minimal value of whatever
+/// shape is requested: `false`, `0`, `""`, `None`, an empty sequence or map, a
+/// struct of placeholder fields, or an enum's first variant.
+///
+/// Deserialized in place of invalid values so that
+/// [`Figment::extract_all_errors()`](crate::Figment::extract_all_errors())
+/// can continue extracting past them.
+#[derive(Debug, Clone, Copy)]
+pub struct Placeholder;
+
+/// The first variant of an enum, as produced by [`Placeholder`].
+struct PlaceholderVariant(&'static str);
+
+macro_rules! placeholder_num {
+    ($($f:ident => $visit:ident($v:expr)),* $(,)?) => ($(
+        fn $f<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            visitor.$visit($v)
+        }
+    )*)
+}
+
+impl<'de> Deserializer<'de> for Placeholder {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    placeholder_num! {
+        deserialize_bool => visit_bool(false),
+        deserialize_u8 => visit_u8(0), deserialize_u16 => visit_u16(0),
+        deserialize_u32 => visit_u32(0), deserialize_u64 => visit_u64(0),
+        deserialize_u128 => visit_u128(0),
+        deserialize_i8 => visit_i8(0), deserialize_i16 => visit_i16(0),
+        deserialize_i32 => visit_i32(0), deserialize_i64 => visit_i64(0),
+        deserialize_i128 => visit_i128(0),
+        deserialize_f32 => visit_f32(0.0), deserialize_f64 => visit_f64(0.0),
+        deserialize_char => visit_char('\0'),
+        deserialize_str => visit_str(""), deserialize_string => visit_str(""),
+        deserialize_identifier => visit_str(""),
+        deserialize_bytes => visit_bytes(&[]), deserialize_byte_buf => visit_bytes(&[]),
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_none()
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_tuple(0, visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        let elements = (0..len).map(|_| Placeholder);
+        visitor.visit_seq(de::value::SeqDeserializer::new(elements))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_struct("", &[], visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value> {
+        let entries = fields.iter().map(|field| (*field, Placeholder));
+        visitor.visit_map(de::value::MapDeserializer::new(entries))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value> {
+        match variants.first() {
+            Some(variant) => visitor.visit_enum(PlaceholderVariant(variant)),
+            None => Err(de::Error::custom(format_args!("enum `{}` has no variants", name))),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Placeholder {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for PlaceholderVariant {
+    type Error = Error;
+    type Variant = Placeholder;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+        where V: de::DeserializeSeed<'de>
+    {
+        seed.deserialize(self.0.into_deserializer()).map(|v| (v, Placeholder))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Placeholder {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+        where T: de::DeserializeSeed<'de>
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value> {
+        self.deserialize_struct("", fields, visitor)
+    }
+}
+
 impl<'de> Deserializer<'de> for &Value {
     type Error = Error;
 
@@ -275,6 +649,7 @@ impl<'de> Deserializer<'de> for &Value {
             Empty(_, e) => e.deserialize_any(v),
             Dict(_, ref map) => v.visit_map(MapDe::new(map, |v| v)),
             Array(_, ref seq) => v.visit_seq(SeqDe::new(seq, |v| v)),
+            Datetime(_, ref d) => v.visit_string(d.to_string()),
         };
 
         result.map_err(|e: Error| e.retagged(self.tag()))
@@ -323,7 +698,7 @@ impl<'de> Deserializer<'de> for &Value {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str
         string seq bytes byte_buf map unit struct
         ignored_any unit_struct tuple_struct tuple identifier
     }
@@ -346,12 +721,18 @@ impl<'de> Deserializer<'de> for Num {
             Num::U16(n) => visitor.visit_u16(n),
             Num::U32(n) => visitor.visit_u32(n),
             Num::U64(n) => visitor.visit_u64(n),
-            Num::U128(n) => visitor.visit_u128(n),
+            Num::U128(n) => {
+                int_try!(n; u128 => u64 => visitor.visit_u64);
+                visitor.visit_u128(n)
+            }
             Num::I8(n) => visitor.visit_i8(n),
             Num::I16(n) => visitor.visit_i16(n),
             Num::I32(n) => visitor.visit_i32(n),
             Num::I64(n) => visitor.visit_i64(n),
-            Num::I128(n) => visitor.visit_i128(n),
+            Num::I128(n) => {
+                int_try!(n; i128 => i64 => visitor.visit_i64);
+                visitor.visit_i128(n)
+            }
             Num::F32(n) => visitor.visit_f32(n),
             Num::F64(n) => visitor.visit_f64(n),
             Num::ISize(n) => {
@@ -374,7 +755,7 @@ impl<'de> Deserializer<'de> for Num {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq enum
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq enum
         bytes byte_buf map struct unit newtype_struct
         ignored_any unit_struct tuple_struct tuple option identifier
     }
@@ -393,7 +774,7 @@ impl<'de> Deserializer<'de> for Empty {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq enum
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq enum
         bytes byte_buf map struct unit newtype_struct
         ignored_any unit_struct tuple_struct tuple option identifier
     }
@@ -401,7 +782,7 @@ impl<'de> Deserializer<'de> for Empty {
 
 /// Marker trait for "magic" values. Primarily for use with [`Either`].
 impl Value {
-    const NAME: &'static str = "___figment_value";
+    pub(crate) const NAME: &'static str = "___figment_value";
 
     const FIELDS: &'static [&'static str] = &[
         "___figment_value_id", "___figment_value_value"
@@ -414,7 +795,7 @@ impl Value {
         let mut map = Dict::new();
         map.insert(Self::FIELDS[0].into(), de.value.tag().into());
         map.insert(Self::FIELDS[1].into(), de.value.clone());
-        visitor.visit_map(MapDe::new(&map, |v| ConfiguredValueDe::<'_, I>::from(de.config, v)))
+        visitor.visit_map(MapDe::new(&map, |v| de.child(v)))
     }
 }
 
@@ -513,6 +894,11 @@ impl<'de> Visitor<'de> for ValueVisitor {
             return Ok(value.0);
         }
 
+        let datetime = toml_datetime(&dict).and_then(|d| d.parse().ok());
+        if let Some(datetime) = datetime {
+            return Ok(Value::Datetime(Tag::Default, datetime));
+        }
+
         Ok(dict.into())
     }
 
@@ -0,0 +1,180 @@
+use crate::value::Num;
+
+/// Formatting tolerated when extracting a number from a string, set via
+/// [`Figment::numeric_leniency()`](crate::Figment::numeric_leniency()).
+///
+/// By default, a string is never a number. With leniency, a string that parses
+/// as a number after removing the tolerated formatting is extracted as that
+/// number. Leading and trailing whitespace is always ignored. Each kind of
+/// formatting is opt-in:
+///
+///   * **plus:** a leading `+`, as in `+10`.
+///   * **underscores:** `_` between two digits, as in `1_000_000`.
+///   * **thousands:** a separator between groups of three digits in the
+///     integer part, as in `1,000,000` or `1,000.5` with `,` as the separator.
+///     Groups must be well-formed: `1,00` is not a number.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::value::{NumericLeniency, Num};
+///
+/// let lenient = NumericLeniency::all();
+/// assert_eq!(lenient.parse("+1_000"), Some(Num::U16(1000)));
+/// assert_eq!(lenient.parse("-1,000,000"), Some(Num::I32(-1_000_000)));
+/// assert_eq!(lenient.parse("1,000.5"), Some(Num::F64(1000.5)));
+/// assert_eq!(lenient.parse("1,00"), None);
+/// assert_eq!(lenient.parse("1__0"), None);
+///
+/// let lenient = NumericLeniency::none().underscores(true).thousands(Some('.'));
+/// assert_eq!(lenient.parse("1.000.000"), Some(Num::U32(1_000_000)));
+/// assert_eq!(lenient.parse("+1"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumericLeniency {
+    plus: bool,
+    underscores: bool,
+    thousands: Option<char>,
+}
+
+impl NumericLeniency {
+    /// No formatting is tolerated. This is the default.
+    ///
+    /// ```rust
+    /// use figment::value::NumericLeniency;
+    ///
+    /// assert_eq!(NumericLeniency::none().parse("1_000"), None);
+    /// ```
+    pub fn none() -> Self {
+        NumericLeniency { plus: false, underscores: false, thousands: None }
+    }
+
+    /// A leading `+`, underscores, and `,` as thousands separator are
+    /// tolerated.
+    ///
+    /// ```rust
+    /// use figment::value::NumericLeniency;
+    ///
+    /// assert!(NumericLeniency::all().parse("+1,000").is_some());
+    /// ```
+    pub fn all() -> Self {
+        NumericLeniency { plus: true, underscores: true, thousands: Some(',') }
+    }
+
+    /// Sets whether a leading `+` is tolerated.
+    ///
+    /// ```rust
+    /// use figment::value::{NumericLeniency, Num};
+    ///
+    /// assert_eq!(NumericLeniency::none().plus(true).parse("+7"), Some(Num::U8(7)));
+    /// ```
+    pub fn plus(mut self, yes: bool) -> Self {
+        self.plus = yes;
+        self
+    }
+
+    /// Sets whether underscores between digits are tolerated.
+    ///
+    /// ```rust
+    /// use figment::value::{NumericLeniency, Num};
+    ///
+    /// let lenient = NumericLeniency::none().underscores(true);
+    /// assert_eq!(lenient.parse("1_0"), Some(Num::U8(10)));
+    /// assert_eq!(lenient.parse("_10"), None);
+    /// ```
+    pub fn underscores(mut self, yes: bool) -> Self {
+        self.underscores = yes;
+        self
+    }
+
+    /// Sets the thousands separator that is tolerated, if any.
+    ///
+    /// ```rust
+    /// use figment::value::{NumericLeniency, Num};
+    ///
+    /// let lenient = NumericLeniency::none().thousands(Some('\''));
+    /// assert_eq!(lenient.parse("10'000"), Some(Num::U16(10_000)));
+    /// ```
+    pub fn thousands(mut self, separator: Option<char>) -> Self {
+        self.thousands = separator;
+        self
+    }
+
+    /// Parses `string` as a number tolerating the formatting allowed by
+    /// `self`. Returns `None` if `string` isn't a number.
+    pub fn parse(&self, string: &str) -> Option<Num> {
+        let string = string.trim();
+        let (sign, digits) = match string.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => match string.strip_prefix('+') {
+                Some(rest) if self.plus => ("", rest),
+                _ => ("", string),
+            }
+        };
+
+        if digits.starts_with(['+', '-']) {
+            return None;
+        }
+
+        let digits = match self.underscores {
+            true => remove_between_digits(digits, '_')?,
+            false => digits.to_string(),
+        };
+
+        let digits = match self.thousands {
+            Some(separator) => remove_thousands(&digits, separator)?,
+            None => digits,
+        };
+
+        format!("{}{}", sign, digits).parse().ok()
+    }
+}
+
+impl Default for NumericLeniency {
+    fn default() -> Self {
+        NumericLeniency::none()
+    }
+}
+
+/// Removes every `c` in `string` that is between two ASCII digits. Returns
+/// `None` if there is a `c` that isn't.
+fn remove_between_digits(string: &str, c: char) -> Option<String> {
+    let chars: Vec<char> = string.chars().collect();
+    let mut result = String::with_capacity(string.len());
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch != c {
+            result.push(ch);
+            continue;
+        }
+
+        let before = i.checked_sub(1).and_then(|i| chars.get(i));
+        let after = chars.get(i + 1);
+        match (before, after) {
+            (Some(b), Some(a)) if b.is_ascii_digit() && a.is_ascii_digit() => continue,
+            _ => return None,
+        }
+    }
+
+    Some(result)
+}
+
+/// Removes the thousands `separator` from the integer part of `string`.
+/// Returns `None` if the integer part contains a separator and isn't grouped
+/// into threes by it.
+fn remove_thousands(string: &str, separator: char) -> Option<String> {
+    let end = string.find(|c: char| c != separator && !c.is_ascii_digit())
+        .unwrap_or(string.len());
+
+    let (integer, rest) = string.split_at(end);
+    if !integer.contains(separator) {
+        return Some(string.to_string());
+    }
+
+    let mut groups = integer.split(separator);
+    let first = groups.next()?;
+    if first.is_empty() || first.len() > 3 || groups.clone().any(|g| g.len() != 3) {
+        return None;
+    }
+
+    Some(integer.replace(separator, "") + rest)
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+
+use crate::Profile;
+use crate::error::{Error, Result};
+use crate::value::{Map, Dict, Value};
+
+/// Bounds on the size of the data a provider may emit, set via
+/// [`Figment::limits()`](crate::Figment::limits()).
+///
+/// By default, there are no limits. Each limit is opt-in:
+///
+///   * **keys:** the total number of keys, at any depth and across all
+///     profiles, in the data emitted by a single provider.
+///   * **string length:** the length, in bytes, of any string value or key.
+///   * **array length:** the number of elements in any array.
+///
+/// Limits are checked as each provider is combined into a figment, before its
+/// data is merged with existing data. Data exceeding a limit fails the figment
+/// with an error pointing to the offending value and provider for which
+/// [`Error::limit()`] returns the exceeded limit. Note that the provider has already parsed its data at
+/// that point: limits bound what a figment retains and merges, not the memory
+/// used by a provider's parser.
+///
+/// # Example
+///
+/// ```rust
+/// use figment::value::Limits;
+///
+/// let limits = Limits::none().keys(1_000).string_len(4096).array_len(256);
+/// # let _ = limits;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    keys: Option<usize>,
+    string_len: Option<usize>,
+    array_len: Option<usize>,
+}
+
+/// A limit in [`Limits`] that was exceeded. See [`Error::limit()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Limit {
+    /// The total number of keys.
+    Keys,
+    /// The length of a string or key in bytes.
+    StringLength,
+    /// The number of elements in an array.
+    ArrayLength,
+}
+
+impl Limits {
+    /// No limits. This is the default.
+    pub fn none() -> Self {
+        Limits { keys: None, string_len: None, array_len: None }
+    }
+
+    /// Sets the maximum number of keys a provider may emit.
+    pub fn keys(mut self, max: usize) -> Self {
+        self.keys = Some(max);
+        self
+    }
+
+    /// Sets the maximum length, in bytes, of a string value or key.
+    pub fn string_len(mut self, max: usize) -> Self {
+        self.string_len = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of elements in an array.
+    pub fn array_len(mut self, max: usize) -> Self {
+        self.array_len = Some(max);
+        self
+    }
+
+    /// Checks `data`, emitted by a single provider, against `self`.
+    pub(crate) fn check(&self, data: &Map<Profile, Dict>) -> Result<()> {
+        if *self == Limits::none() {
+            return Ok(());
+        }
+
+        let mut keys = 0;
+        for (profile, dict) in data {
+            let mut path = vec![];
+            self.check_dict(dict, &mut keys, &mut path).map_err(|mut e| {
+                e.path = path;
+                e.profile = Some(profile.clone());
+                e
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks `dict`, adding its keys to `count`. On error, `path` is the path
+    /// to the offending value.
+    fn check_dict(&self, dict: &Dict, count: &mut usize, path: &mut Vec<String>) -> Result<()> {
+        for (key, value) in dict {
+            path.push(key.clone());
+            *count += 1;
+            if let Some(max) = self.keys.filter(|max| *count > *max) {
+                return Err(Error::limit_exceeded(Limit::Keys, max));
+            }
+
+            self.check_string(key)?;
+            self.check_value(value, count, path)?;
+            path.pop();
+        }
+
+        Ok(())
+    }
+
+    fn check_value(&self, value: &Value, count: &mut usize, path: &mut Vec<String>) -> Result<()> {
+        let error = |kind: Error| kind.retagged(value.tag());
+        match value {
+            Value::String(_, s) => self.check_string(s).map_err(error),
+            Value::Dict(_, dict) => self.check_dict(dict, count, path),
+            Value::Array(_, array) => {
+                if let Some(max) = self.array_len.filter(|max| array.len() > *max) {
+                    return Err(error(Error::limit_exceeded(Limit::ArrayLength, max)));
+                }
+
+                for (i, value) in array.iter().enumerate() {
+                    path.push(i.to_string());
+                    self.check_value(value, count, path)?;
+                    path.pop();
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_string(&self, string: &str) -> Result<()> {
+        match self.string_len.filter(|max| string.len() > *max) {
+            Some(max) => Err(Error::limit_exceeded(Limit::StringLength, max)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits::none()
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::Keys => "number of keys".fmt(f),
+            Limit::StringLength => "string length".fmt(f),
+            Limit::ArrayLength => "array length".fmt(f),
+        }
+    }
+}
@@ -1,12 +1,13 @@
 //! (De)serializable values that "magically" use information from the extracing
 //! [`Figment`](crate::Figment).
 
+use std::convert::TryFrom;
 use std::ops::Deref;
 use std::path::{PathBuf, Path};
 
 use serde::{Deserialize, Serialize, de};
 
-use crate::{Error, value::{ConfiguredValueDe, Interpreter, MapDe, Tag}};
+use crate::{Error, value::{ConfiguredValueDe, Interpreter, MapDe, Tag, Value, Dict}};
 
 /// Marker trait for "magic" values. Primarily for use with [`Either`].
 pub trait Magic: for<'de> Deserialize<'de> {
@@ -22,6 +23,69 @@ pub trait Magic: for<'de> Deserialize<'de> {
     ) -> Result<V::Value, Error>;
 }
 
+/// A type that serializes into a structure that can be rendered in a
+/// human-readable form.
+///
+/// Magic values like [`RelativePathBuf`] serialize into pseudo-structures
+/// that only they can deserialize from. When a figment containing such a
+/// structure, for instance one built from a serialized configuration, is
+/// serialized back out via [`Figment::serialize_to()`], each structure of a
+/// registered `MagicSerialize` type is replaced with its human-readable form,
+/// as returned by [`MagicSerialize::humanize()`]. [`RelativePathBuf`], which
+/// renders as its [original](RelativePathBuf::original()) path, [`Tagged`],
+/// which renders as its value, [`Duration`] and [`ByteSize`], which render in
+/// their human-friendly forms, and [`Secret`], which renders as `[REDACTED]`,
+/// are registered by default. Other types are
+/// registered via [`Figment::serialize_magic()`].
+///
+/// [`Figment::serialize_to()`]: crate::Figment::serialize_to()
+/// [`Figment::serialize_magic()`]: crate::Figment::serialize_magic()
+///
+/// # Example
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use figment::{Figment, providers::{Format, Toml, Serialized}};
+/// use figment::value::{Value, Dict, magic::MagicSerialize};
+///
+/// /// A size in bytes, written as `"4 KiB"` but serialized as a structure.
+/// #[derive(Serialize, Deserialize)]
+/// struct CacheSize {
+///     bytes: u64,
+/// }
+///
+/// impl MagicSerialize for CacheSize {
+///     fn humanize(dict: &Dict) -> Option<Value> {
+///         match (dict.len(), dict.get("bytes")?.to_u128()?) {
+///             (1, n) if n % 1024 == 0 => Some(format!("{} KiB", n / 1024).into()),
+///             (1, n) => Some(format!("{} B", n).into()),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let figment = Figment::from(Serialized::default("cache", CacheSize { bytes: 4096 }));
+/// assert_eq!(figment.serialize_to::<Toml>().unwrap(), "[cache]\nbytes = 4096\n");
+///
+/// let figment = figment.serialize_magic::<CacheSize>();
+/// assert_eq!(figment.serialize_to::<Toml>().unwrap(), "cache = \"4 KiB\"\n");
+/// ```
+pub trait MagicSerialize {
+    /// Returns the human-readable form of `dict` if it is the serialized
+    /// structure of a `Self` and `None` otherwise.
+    fn humanize(dict: &Dict) -> Option<Value>;
+}
+
+/// Returns the value of the last of `T::FIELDS` in `dict` if `dict` has it and
+/// no keys other than `T::FIELDS`.
+fn humanize_magic<T: Magic>(dict: &Dict) -> Option<Value> {
+    let value = dict.get(*T::FIELDS.last()?)?;
+    match dict.keys().all(|key| T::FIELDS.contains(&key.as_str())) {
+        true => Some(value.clone()),
+        false => None,
+    }
+}
+
 /// A [`PathBuf`] that knows the path of the file it was configured in, if any.
 ///
 /// Paths in configuration files are often desired to be relative to the
@@ -102,7 +166,10 @@ pub trait Magic: for<'de> Deserialize<'de> {
 /// not serialize into a value compatible with `PathBuf`. To serialize into a
 /// `Path`, use [`RelativePathBuf::serialize_original()`] or
 /// [`RelativePathBuf::serialize_relative()`] together with serde's
-/// `serialize_with` field attribute:
+/// `serialize_with` field attribute. A figment containing the structure is
+/// nonetheless rendered with the original path by
+/// [`Figment::serialize_to()`](crate::Figment::serialize_to()); see
+/// [`MagicSerialize`].
 ///
 /// ```rust
 /// use std::path::PathBuf;
@@ -186,7 +253,7 @@ impl Magic for RelativePathBuf {
         if let Some(d) = de.value.as_dict() {
             if let Some(mpv) = d.get(Self::FIELDS[0]) {
                 if mpv.to_empty().is_none() {
-                    let map_de = MapDe::new(d, |v| ConfiguredValueDe::<I>::from(config, v));
+                    let map_de = MapDe::new(d, |v| de.child(v));
                     return visitor.visit_map(map_de);
                 }
             }
@@ -205,7 +272,13 @@ impl Magic for RelativePathBuf {
         // If we have this struct with no metadata_path, still use the value.
         let value = de.value.find_ref(Self::FIELDS[1]).unwrap_or(de.value);
         map.insert(Self::FIELDS[1].into(), value.clone());
-        visitor.visit_map(MapDe::new(&map, |v| ConfiguredValueDe::<I>::from(config, v)))
+        visitor.visit_map(MapDe::new(&map, |v| de.child(v)))
+    }
+}
+
+impl MagicSerialize for RelativePathBuf {
+    fn humanize(dict: &Dict) -> Option<Value> {
+        humanize_magic::<Self>(dict)
     }
 }
 
@@ -241,9 +314,11 @@ impl RelativePathBuf {
     ///
     /// If the configured path was relative and it was configured from a file,
     /// this function returns that path prefixed with that file's parent
-    /// directory. Otherwise it returns the original path. Where
-    /// `config_file_path` is the location of the configuration file, this
-    /// corresponds to:
+    /// directory. Otherwise it returns the original path. Windows drive letter
+    /// (`C:\`) and UNC (`\\server\share`) paths are never relative, on
+    /// any platform. To also normalize separators, use
+    /// [`normalized()`](Self::normalized()). Where `config_file_path` is the
+    /// location of the configuration file, this corresponds to:
     ///
     /// ```rust
     /// # use figment::{Figment, value::magic::RelativePathBuf, Jail};
@@ -294,7 +369,7 @@ impl RelativePathBuf {
     /// });
     /// ```
     pub fn relative(&self) -> PathBuf {
-        if self.original().has_root() {
+        if self.original().has_root() || is_windows_absolute(self.original()) {
             return self.original().into();
         }
 
@@ -307,6 +382,75 @@ impl RelativePathBuf {
             .unwrap_or_else(|| self.original().into())
     }
 
+    /// Returns the [`relative`](Self::relative()) path, lexically normalized,
+    /// with components separated by the platform's [main
+    /// separator](std::path::MAIN_SEPARATOR).
+    ///
+    /// Equivalent to [`normalized_with(MAIN_SEPARATOR)`](Self::normalized_with()).
+    /// See [`normalized_with()`](Self::normalized_with()) for details.
+    pub fn normalized(&self) -> PathBuf {
+        self.normalized_with(std::path::MAIN_SEPARATOR)
+    }
+
+    /// Returns the [`relative`](Self::relative()) path, lexically normalized,
+    /// with components separated by `separator`.
+    ///
+    /// Configuration files are often shared between platforms, so a path may
+    /// be written with `/` or `\`, or a mix of both, irrespective of the
+    /// platform it is read on. Normalization treats both `/` and `\` as
+    /// separators and:
+    ///
+    ///   * preserves Windows drive letter (`C:`) and UNC (`\\server\share`)
+    ///     prefixes, on every platform,
+    ///   * removes repeated separators and `.` components,
+    ///   * removes `..` components along with the preceding component, if any,
+    ///   * joins the remaining components with `separator`.
+    ///
+    /// Verbatim Windows paths, prefixed with `\\?\`, are always separated by
+    /// `\`, which they require, irrespective of `separator`.
+    /// Normalization is purely lexical: it does not access the file system, so
+    /// removing `..` may change the path's meaning if the preceding component
+    /// is a symbolic link. Paths that aren't valid UTF-8 are converted lossily.
+    ///
+    /// Windows drive letter and UNC paths are considered absolute on every
+    /// platform, so they are never made relative to the configuration file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    ///
+    /// use figment::{Figment, value::magic::RelativePathBuf, Jail};
+    /// use figment::providers::{Format, Toml};
+    ///
+    /// #[derive(Debug, PartialEq, serde::Deserialize)]
+    /// struct Config {
+    ///     path: RelativePathBuf,
+    /// }
+    ///
+    /// Jail::expect_with(|jail| {
+    ///     jail.create_file("Config.toml", r#"path = 'C:\static/./css\..\img'"#)?;
+    ///     let c: Config = Figment::from(Toml::file("Config.toml")).extract()?;
+    ///     assert_eq!(c.path.relative(), Path::new(r"C:\static/./css\..\img"));
+    ///     assert_eq!(c.path.normalized_with('/'), Path::new("C:/static/img"));
+    ///     assert_eq!(c.path.normalized_with('\\'), Path::new(r"C:\static\img"));
+    ///
+    ///     jail.create_file("Config.toml", r#"path = '\\server\share/assets'"#)?;
+    ///     let c: Config = Figment::from(Toml::file("Config.toml")).extract()?;
+    ///     assert_eq!(c.path.normalized_with('/'), Path::new("//server/share/assets"));
+    ///
+    ///     jail.create_file("Config.toml", r#"path = 'static\\img/./logo.png'"#)?;
+    ///     let c: Config = Figment::from(Toml::file("Config.toml")).extract()?;
+    ///     let expected = jail.directory().join("static").join("img").join("logo.png");
+    ///     assert_eq!(c.path.normalized(), expected);
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn normalized_with(&self, separator: char) -> PathBuf {
+        normalize(&self.relative().to_string_lossy(), separator).into()
+    }
+
     /// Returns the path to the file this path was declared in, if any.
     ///
     /// # Example
@@ -386,6 +530,79 @@ impl RelativePathBuf {
     }
 }
 
+/// Returns the length of the Windows drive letter (`C:`) or UNC
+/// (`\\server\share`) prefix of `path`, with either separator, if any.
+fn windows_prefix(path: &str) -> Option<usize> {
+    let is_sep = |c: char| c == '/' || c == '\\';
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        return Some(2);
+    }
+
+    let unc = path.strip_prefix(is_sep)?.strip_prefix(is_sep)?;
+    let mut parts = unc.splitn(3, is_sep);
+    match (parts.next(), parts.next()) {
+        (Some(server), Some(share)) if !server.is_empty() && !share.is_empty() => {
+            Some(2 + server.len() + 1 + share.len())
+        }
+        _ => None,
+    }
+}
+
+/// Returns `true` if `path` is a Windows drive letter path followed by a
+/// separator, like `C:\`, or a UNC path, irrespective of the platform.
+fn is_windows_absolute(path: &Path) -> bool {
+    match path.to_str().map(|path| (path, windows_prefix(path))) {
+        Some((path, Some(2))) => path[2..].starts_with(['/', '\\']),
+        Some((_, Some(_))) => true,
+        _ => false,
+    }
+}
+
+/// Lexically normalizes `path`, treating `/` and `\` as separators, joining
+/// its components with `separator`. See [`RelativePathBuf::normalized_with()`].
+fn normalize(path: &str, separator: char) -> String {
+    if let Some(path) = path.strip_prefix(r"\\?\") {
+        return format!(r"\\?\{}", normalize(path, '\\'));
+    }
+
+    let is_sep = |c: char| c == '/' || c == '\\';
+    let (prefix, rest) = path.split_at(windows_prefix(path).unwrap_or(0));
+    let unc = prefix.len() > 2;
+    let rooted = unc || rest.starts_with(is_sep);
+    let mut components: Vec<&str> = vec![];
+    for component in rest.split(is_sep) {
+        match component {
+            "" | "." => continue,
+            ".." if matches!(components.last(), Some(c) if *c != "..") => { components.pop(); }
+            ".." if rooted => continue,
+            _ => components.push(component),
+        }
+    }
+
+    let mut normalized: String = prefix.chars()
+        .map(|c| if is_sep(c) { separator } else { c })
+        .collect();
+
+    if rooted && !unc {
+        normalized.push(separator);
+    }
+
+    for (i, component) in components.iter().enumerate() {
+        if i > 0 || unc {
+            normalized.push(separator);
+        }
+
+        normalized.push_str(component);
+    }
+
+    if normalized.is_empty() {
+        normalized.push('.');
+    }
+
+    normalized
+}
+
 // /// MAGIC
 // #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 // #[serde(rename = "___figment_selected_profile")]
@@ -577,16 +794,13 @@ impl<T: for<'de> Deserialize<'de>> Magic for Tagged<T> {
         de: ConfiguredValueDe<'c, I>,
         visitor: V
     ) -> Result<V::Value, Error>{
-        let config = de.config;
         let mut map = crate::value::Map::new();
 
         // If we have this struct with a non-default tag, use it.
         if let Some(dict) = de.value.as_dict() {
             if let Some(tagv) = dict.get(Self::FIELDS[0]) {
                 if let Ok(false) = tagv.deserialize::<Tag>().map(|t| t.is_default()) {
-                    return visitor.visit_map(MapDe::new(dict, |v| {
-                        ConfiguredValueDe::<I>::from(config, v)
-                    }));
+                    return visitor.visit_map(MapDe::new(dict, |v| de.child(v)));
                 }
             }
         }
@@ -595,7 +809,13 @@ impl<T: for<'de> Deserialize<'de>> Magic for Tagged<T> {
         let value = de.value.find_ref(Self::FIELDS[1]).unwrap_or(de.value);
         map.insert(Self::FIELDS[0].into(), de.value.tag().into());
         map.insert(Self::FIELDS[1].into(), value.clone());
-        visitor.visit_map(MapDe::new(&map, |v| ConfiguredValueDe::<I>::from(config, v)))
+        visitor.visit_map(MapDe::new(&map, |v| de.child(v)))
+    }
+}
+
+impl<T: for<'de> Deserialize<'de>> MagicSerialize for Tagged<T> {
+    fn humanize(dict: &Dict) -> Option<Value> {
+        humanize_magic::<Self>(dict)
     }
 }
 
@@ -652,6 +872,950 @@ impl<T> From<T> for Tagged<T> {
     }
 }
 
+/// A [`std::time::Duration`] written in a human-friendly form, like `"30s"`,
+/// `"5m"`, or `"1h30m"`, or as a number of seconds.
+///
+/// A `Duration` deserializes from:
+///
+///   * a non-negative integer or float: a number of seconds, like `30` or
+///     `0.25`, including a string containing only a number, like `"30"`.
+///   * a string of one or more components, each a non-negative number
+///     followed by a unit, optionally separated by whitespace: `"500ms"`,
+///     `"1h30m"`, `"1.5h"`, `"2m 30s"`. The units are `d` (days), `h`, `m`,
+///     `s`, `ms`, `us` or `µs`, and `ns`.
+///
+/// Like [`Tagged`], a `Duration` remembers the [`Tag`] of the value it was
+/// deserialized from, so the metadata of its source is available via
+/// [`Figment::get_metadata()`](crate::Figment::get_metadata()). A
+/// `Duration` serializes into a structure that deserializes as the same
+/// `Duration` with the same tag and which
+/// [`Figment::serialize_to()`](crate::Figment::serialize_to()) renders in
+/// the human-friendly form returned by `Duration`'s [`Display`]
+/// implementation.
+///
+/// [`Display`]: std::fmt::Display
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration as StdDuration;
+///
+/// use serde::{Deserialize, Serialize};
+/// use figment::{Figment, Jail, value::magic::Duration};
+/// use figment::providers::{Env, Format, Toml, Serialized};
+///
+/// #[derive(Debug, Deserialize, Serialize)]
+/// struct Config {
+///     timeout: Duration,
+///     retry: Duration,
+///     idle: Duration,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", r#"
+///         timeout = "1h30m"
+///         retry = "250ms"
+///         idle = 30
+///     "#)?;
+///
+///     jail.set_env("APP_RETRY", "2.5s");
+///     let figment = Figment::from(Toml::file("App.toml")).merge(Env::prefixed("APP_"));
+///     let config: Config = figment.extract()?;
+///
+///     assert_eq!(*config.timeout, StdDuration::from_secs(90 * 60));
+///     assert_eq!(*config.retry, StdDuration::from_millis(2500));
+///     assert_eq!(*config.idle, StdDuration::from_secs(30));
+///
+///     let metadata = figment.get_metadata(config.retry.tag()).unwrap();
+///     assert_eq!(metadata.name, "`APP_` environment variable(s)");
+///
+///     // Durations round-trip and are rendered in their human-friendly form.
+///     let figment = Figment::from(Serialized::defaults(&config));
+///     assert_eq!(figment.extract::<Config>()?.timeout, config.timeout);
+///     assert!(figment.serialize_to::<Toml>()?.contains("timeout = \"1h30m\""));
+///
+///     jail.set_env("APP_IDLE", "forever");
+///     let figment = Figment::from(Toml::file("App.toml")).merge(Env::prefixed("APP_"));
+///     assert!(figment.extract::<Config>().is_err());
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Duration {
+    tag: Tag,
+    duration: std::time::Duration,
+}
+
+impl Magic for Duration {
+    const NAME: &'static str = "___figment_duration";
+    const FIELDS: &'static [&'static str] = &[
+        "___figment_duration_tag", "___figment_duration_value"
+    ];
+
+    fn deserialize_from<'de: 'c, 'c, V: de::Visitor<'de>, I: Interpreter>(
+        de: ConfiguredValueDe<'c, I>,
+        visitor: V
+    ) -> Result<V::Value, Error>{
+        // If we have this struct with a non-default tag, use it.
+        if let Some(dict) = de.value.as_dict() {
+            if let Some(tagv) = dict.get(Self::FIELDS[0]) {
+                if let Ok(false) = tagv.deserialize::<Tag>().map(|t| t.is_default()) {
+                    return visitor.visit_map(MapDe::new(dict, |v| de.child(v)));
+                }
+            }
+        }
+
+        // Otherwise, tag the value, from this struct if it is one.
+        let value = de.value.find_ref(Self::FIELDS[1]).unwrap_or(de.value);
+        let mut map = crate::value::Map::new();
+        map.insert(Self::FIELDS[0].into(), value.tag().into());
+        map.insert(Self::FIELDS[1].into(), value.clone());
+        visitor.visit_map(MapDe::new(&map, |v| de.child(v)))
+    }
+}
+
+impl MagicSerialize for Duration {
+    fn humanize(dict: &Dict) -> Option<Value> {
+        humanize_magic::<Self>(dict)
+    }
+}
+
+impl Duration {
+    /// Returns the tag of the value `self` was deserialized from, if it is
+    /// known, or [`Tag::Default`] otherwise.
+    ///
+    /// ```rust
+    /// use figment::{Figment, value::magic::Duration};
+    ///
+    /// let figment = Figment::from(("timeout", "30s"));
+    /// let timeout = figment.extract_inner::<Duration>("timeout").unwrap();
+    /// assert!(figment.get_metadata(timeout.tag()).is_some());
+    ///
+    /// let timeout = Duration::from(std::time::Duration::from_secs(30));
+    /// assert!(timeout.tag().is_default());
+    /// ```
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Consumes `self` and returns the [`std::time::Duration`].
+    ///
+    /// ```rust
+    /// use figment::{Figment, value::magic::Duration};
+    ///
+    /// let figment = Figment::from(("timeout", "1m30s"));
+    /// let timeout = figment.extract_inner::<Duration>("timeout").unwrap();
+    /// assert_eq!(timeout.into_inner().as_secs(), 90);
+    /// ```
+    pub fn into_inner(self) -> std::time::Duration {
+        self.duration
+    }
+
+    /// Parses the human-friendly duration `string`. Returns `None` if
+    /// `string` isn't a valid duration or is out of range.
+    fn parse(string: &str) -> Option<std::time::Duration> {
+        const UNITS: &[(&str, u128)] = &[
+            ("ns", 1), ("us", 1_000), ("µs", 1_000), ("ms", 1_000_000),
+            ("s", 1_000_000_000), ("m", 60_000_000_000), ("h", 3_600_000_000_000),
+            ("d", 86_400_000_000_000),
+        ];
+
+        let string = string.trim();
+        if string.is_empty() {
+            return None;
+        }
+
+        if let Ok(secs) = string.parse::<f64>() {
+            return std::time::Duration::try_from_secs_f64(secs).ok();
+        }
+
+        let mut nanos: u128 = 0;
+        let mut rest = string;
+        while !rest.is_empty() {
+            let number_len = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+            let unit_len = rest[number_len..].find(|c: char| !c.is_alphabetic() && c != 'µ')
+                .unwrap_or(rest.len() - number_len);
+
+            let (number, unit) = (&rest[..number_len], &rest[number_len..][..unit_len]);
+            let scale = UNITS.iter().find(|(name, _)| *name == unit)?.1;
+            let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+            if whole.is_empty() && fraction.is_empty() {
+                return None;
+            }
+
+            let whole = match whole {
+                "" => 0,
+                whole => whole.parse::<u128>().ok()?,
+            };
+
+            let fraction = match fraction {
+                "" => 0,
+                f => (format!("0.{}", f).parse::<f64>().ok()? * scale as f64).round() as u128,
+            };
+
+            nanos = nanos.checked_add(whole.checked_mul(scale)?.checked_add(fraction)?)?;
+            rest = rest[(number_len + unit_len)..].trim_start();
+        }
+
+        let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+        Some(std::time::Duration::new(secs, (nanos % 1_000_000_000) as u32))
+    }
+}
+
+impl PartialEq for Duration {
+    fn eq(&self, other: &Self) -> bool {
+        self.duration == other.duration
+    }
+}
+
+impl Eq for Duration {}
+
+impl Deref for Duration {
+    type Target = std::time::Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.duration
+    }
+}
+
+impl From<std::time::Duration> for Duration {
+    fn from(duration: std::time::Duration) -> Self {
+        Duration { tag: Tag::Default, duration }
+    }
+}
+
+impl std::str::FromStr for Duration {
+    type Err = Error;
+
+    /// Parses a human-friendly duration like `"1h30m"` or a number of seconds.
+    ///
+    /// ```rust
+    /// use std::time::Duration as StdDuration;
+    /// use figment::value::magic::Duration;
+    ///
+    /// let duration: Duration = "2m 30.5s".parse().unwrap();
+    /// assert_eq!(*duration, StdDuration::from_millis(150_500));
+    /// assert!("30 parsecs".parse::<Duration>().is_err());
+    /// assert!("-1s".parse::<Duration>().is_err());
+    /// ```
+    fn from_str(string: &str) -> Result<Self, Error> {
+        match Duration::parse(string) {
+            Some(duration) => Ok(duration.into()),
+            None => Err(de::Error::invalid_value(de::Unexpected::Str(string), &EXPECTED_DURATION)),
+        }
+    }
+}
+
+impl std::fmt::Display for Duration {
+    /// Formats the duration as components from hours down to nanoseconds,
+    /// omitting components that are zero, like `1h30m` or `2s500ms`. A zero
+    /// duration is formatted as `0s`.
+    ///
+    /// ```rust
+    /// use std::time::Duration as StdDuration;
+    /// use figment::value::magic::Duration;
+    ///
+    /// assert_eq!(Duration::from(StdDuration::from_secs(5400)).to_string(), "1h30m");
+    /// assert_eq!(Duration::from(StdDuration::from_millis(2500)).to_string(), "2s500ms");
+    /// assert_eq!(Duration::from(StdDuration::ZERO).to_string(), "0s");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: &[(&str, u128)] = &[
+            ("h", 3_600_000_000_000), ("m", 60_000_000_000), ("s", 1_000_000_000),
+            ("ms", 1_000_000), ("us", 1_000), ("ns", 1),
+        ];
+
+        let mut nanos = self.duration.as_nanos();
+        if nanos == 0 {
+            return f.write_str("0s");
+        }
+
+        for (unit, scale) in UNITS {
+            if nanos >= *scale {
+                write!(f, "{}{}", nanos / scale, unit)?;
+                nanos %= scale;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const EXPECTED_DURATION: &str = "a duration like `30s`, `5m`, or `1h30m`";
+
+impl Serialize for Duration {
+    fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = ser.serialize_struct(Self::NAME, 2)?;
+        state.serialize_field(Self::FIELDS[0], &self.tag)?;
+        state.serialize_field(Self::FIELDS[1], &self.to_string())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Duration {
+    fn deserialize<D: de::Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Duration;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(EXPECTED_DURATION)
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Duration, A::Error> {
+                let (mut tag, mut duration) = (Tag::Default, None);
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        k if k == Duration::FIELDS[0] => tag = map.next_value()?,
+                        k if k == Duration::FIELDS[1] => {
+                            duration = Some(map.next_value::<Seconds>()?.0);
+                        }
+                        _ => { map.next_value::<de::IgnoredAny>()?; }
+                    }
+                }
+
+                let duration = duration
+                    .ok_or_else(|| de::Error::missing_field(Duration::FIELDS[1]))?;
+
+                Ok(Duration { tag, duration })
+            }
+        }
+
+        de.deserialize_struct(Self::NAME, Self::FIELDS, Visitor)
+    }
+}
+
+/// A duration deserialized from a number of seconds or a human-friendly
+/// string.
+struct Seconds(std::time::Duration);
+
+impl<'de> Deserialize<'de> for Seconds {
+    fn deserialize<D: de::Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Seconds;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(EXPECTED_DURATION)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Seconds, E> {
+                Ok(Seconds(std::time::Duration::from_secs(v)))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Seconds, E> {
+                match u64::try_from(v) {
+                    Ok(v) => self.visit_u64(v),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Signed(v), &self)),
+                }
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Seconds, E> {
+                std::time::Duration::try_from_secs_f64(v)
+                    .map(Seconds)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Float(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Seconds, E> {
+                Duration::parse(v)
+                    .map(Seconds)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        de.deserialize_any(Visitor)
+    }
+}
+
+/// A size in bytes written in a human-friendly form, like `"10MiB"` or
+/// `"512k"`, or as a number of bytes.
+///
+/// A `ByteSize` deserializes from:
+///
+///   * a non-negative integer: a number of bytes, like `4096`, including a
+///     string containing only an integer, like `"4096"`.
+///   * a string containing a non-negative, possibly fractional, number
+///     followed by a unit, optionally separated by whitespace: `"10MiB"`,
+///     `"1.5 GB"`, `"512k"`. Units are case-insensitive:
+///
+///     | unit                 | bytes    | unit       | bytes   |
+///     |----------------------|----------|------------|---------|
+///     | `b`                  | 1        |            |         |
+///     | `k`, `ki`, `kib`     | 1024     | `kb`       | 1000    |
+///     | `m`, `mi`, `mib`     | 1024²    | `mb`       | 1000²   |
+///     | `g`, `gi`, `gib`     | 1024³    | `gb`       | 1000³   |
+///     | `t`, `ti`, `tib`     | 1024⁴    | `tb`       | 1000⁴   |
+///     | `p`, `pi`, `pib`     | 1024⁵    | `pb`       | 1000⁵   |
+///
+///     As in most configuration formats that accept them, single-letter
+///     units like `k` are binary: `"512k"` is `524288` bytes.
+///
+/// Like [`Tagged`], a `ByteSize` remembers the [`Tag`] of the value it was
+/// deserialized from, so the metadata of its source is available via
+/// [`Figment::get_metadata()`](crate::Figment::get_metadata()). A
+/// `ByteSize` serializes into a structure that deserializes as the same
+/// `ByteSize` with the same tag and which
+/// [`Figment::serialize_to()`](crate::Figment::serialize_to()) renders in
+/// the human-friendly form returned by `ByteSize`'s [`Display`]
+/// implementation.
+///
+/// [`Display`]: std::fmt::Display
+///
+/// # Example
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use figment::{Figment, Jail, value::magic::ByteSize};
+/// use figment::providers::{Env, Format, Toml, Serialized};
+///
+/// #[derive(Debug, Deserialize, Serialize)]
+/// struct Config {
+///     cache: ByteSize,
+///     upload_limit: ByteSize,
+///     buffer: ByteSize,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", r#"
+///         cache = "10MiB"
+///         upload_limit = "1.5 GB"
+///         buffer = 8192
+///     "#)?;
+///
+///     jail.set_env("APP_BUFFER", "512k");
+///     let figment = Figment::from(Toml::file("App.toml")).merge(Env::prefixed("APP_"));
+///     let config: Config = figment.extract()?;
+///
+///     assert_eq!(config.cache.bytes(), 10 * 1024 * 1024);
+///     assert_eq!(config.upload_limit.bytes(), 1_500_000_000);
+///     assert_eq!(*config.buffer, 512 * 1024);
+///
+///     let metadata = figment.get_metadata(config.buffer.tag()).unwrap();
+///     assert_eq!(metadata.name, "`APP_` environment variable(s)");
+///
+///     // Sizes round-trip and are rendered in their human-friendly form.
+///     let figment = Figment::from(Serialized::defaults(&config));
+///     assert_eq!(figment.extract::<Config>()?.cache, config.cache);
+///     assert!(figment.serialize_to::<Toml>()?.contains("cache = \"10MiB\""));
+///
+///     jail.set_env("APP_BUFFER", "lots");
+///     let figment = Figment::from(Toml::file("App.toml")).merge(Env::prefixed("APP_"));
+///     assert!(figment.extract::<Config>().is_err());
+///
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ByteSize {
+    tag: Tag,
+    bytes: u64,
+}
+
+impl Magic for ByteSize {
+    const NAME: &'static str = "___figment_byte_size";
+    const FIELDS: &'static [&'static str] = &[
+        "___figment_byte_size_tag", "___figment_byte_size_value"
+    ];
+
+    fn deserialize_from<'de: 'c, 'c, V: de::Visitor<'de>, I: Interpreter>(
+        de: ConfiguredValueDe<'c, I>,
+        visitor: V
+    ) -> Result<V::Value, Error>{
+        // If we have this struct with a non-default tag, use it.
+        if let Some(dict) = de.value.as_dict() {
+            if let Some(tagv) = dict.get(Self::FIELDS[0]) {
+                if let Ok(false) = tagv.deserialize::<Tag>().map(|t| t.is_default()) {
+                    return visitor.visit_map(MapDe::new(dict, |v| de.child(v)));
+                }
+            }
+        }
+
+        // Otherwise, tag the value, from this struct if it is one.
+        let value = de.value.find_ref(Self::FIELDS[1]).unwrap_or(de.value);
+        let mut map = crate::value::Map::new();
+        map.insert(Self::FIELDS[0].into(), value.tag().into());
+        map.insert(Self::FIELDS[1].into(), value.clone());
+        visitor.visit_map(MapDe::new(&map, |v| de.child(v)))
+    }
+}
+
+impl MagicSerialize for ByteSize {
+    fn humanize(dict: &Dict) -> Option<Value> {
+        humanize_magic::<Self>(dict)
+    }
+}
+
+impl ByteSize {
+    /// Returns the size in bytes.
+    ///
+    /// ```rust
+    /// use figment::{Figment, value::magic::ByteSize};
+    ///
+    /// let figment = Figment::from(("cache", "4KiB"));
+    /// let cache = figment.extract_inner::<ByteSize>("cache").unwrap();
+    /// assert_eq!(cache.bytes(), 4096);
+    /// ```
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the tag of the value `self` was deserialized from, if it is
+    /// known, or [`Tag::Default`] otherwise.
+    ///
+    /// ```rust
+    /// use figment::{Figment, value::magic::ByteSize};
+    ///
+    /// let figment = Figment::from(("cache", "4KiB"));
+    /// let cache = figment.extract_inner::<ByteSize>("cache").unwrap();
+    /// assert!(figment.get_metadata(cache.tag()).is_some());
+    ///
+    /// assert!(ByteSize::from(4096).tag().is_default());
+    /// ```
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Parses the human-friendly size `string`. Returns `None` if `string`
+    /// isn't a valid size or is out of range.
+    fn parse(string: &str) -> Option<u64> {
+        let string = string.trim();
+        let number_len = string.find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(string.len());
+
+        let (number, unit) = (&string[..number_len], string[number_len..].trim_start());
+        let scale: u128 = match &*unit.to_ascii_lowercase() {
+            "" | "b" => 1,
+            "k" | "ki" | "kib" => 1 << 10,
+            "m" | "mi" | "mib" => 1 << 20,
+            "g" | "gi" | "gib" => 1 << 30,
+            "t" | "ti" | "tib" => 1 << 40,
+            "p" | "pi" | "pib" => 1 << 50,
+            "kb" => 1_000,
+            "mb" => 1_000_000,
+            "gb" => 1_000_000_000,
+            "tb" => 1_000_000_000_000,
+            "pb" => 1_000_000_000_000_000,
+            _ => return None,
+        };
+
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return None;
+        }
+
+        let whole = match whole {
+            "" => 0,
+            whole => whole.parse::<u128>().ok()?,
+        };
+
+        let fraction = match fraction {
+            "" => 0,
+            f => (format!("0.{}", f).parse::<f64>().ok()? * scale as f64).round() as u128,
+        };
+
+        u64::try_from(whole.checked_mul(scale)?.checked_add(fraction)?).ok()
+    }
+}
+
+impl PartialEq for ByteSize {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl Eq for ByteSize {}
+
+impl Deref for ByteSize {
+    type Target = u64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        ByteSize { tag: Tag::Default, bytes }
+    }
+}
+
+impl std::str::FromStr for ByteSize {
+    type Err = Error;
+
+    /// Parses a human-friendly size like `"10MiB"` or a number of bytes.
+    ///
+    /// ```rust
+    /// use figment::value::magic::ByteSize;
+    ///
+    /// assert_eq!("512k".parse::<ByteSize>().unwrap().bytes(), 524_288);
+    /// assert_eq!("2 KB".parse::<ByteSize>().unwrap().bytes(), 2_000);
+    /// assert!("10 parsecs".parse::<ByteSize>().is_err());
+    /// assert!("-1B".parse::<ByteSize>().is_err());
+    /// ```
+    fn from_str(string: &str) -> Result<Self, Error> {
+        match ByteSize::parse(string) {
+            Some(bytes) => Ok(bytes.into()),
+            None => Err(de::Error::invalid_value(de::Unexpected::Str(string), &EXPECTED_BYTE_SIZE)),
+        }
+    }
+}
+
+impl std::fmt::Display for ByteSize {
+    /// Formats the size with the largest unit that divides it exactly,
+    /// preferring binary units, like `10MiB`, `1500kB`, or `1000B`.
+    ///
+    /// ```rust
+    /// use figment::value::magic::ByteSize;
+    ///
+    /// assert_eq!(ByteSize::from(10 * 1024 * 1024).to_string(), "10MiB");
+    /// assert_eq!(ByteSize::from(1_500_000).to_string(), "1500kB");
+    /// assert_eq!(ByteSize::from(1000).to_string(), "1kB");
+    /// assert_eq!(ByteSize::from(1023).to_string(), "1023B");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: &[(&str, u64)] = &[
+            ("PiB", 1 << 50), ("PB", 1_000_000_000_000_000), ("TiB", 1 << 40),
+            ("TB", 1_000_000_000_000), ("GiB", 1 << 30), ("GB", 1_000_000_000),
+            ("MiB", 1 << 20), ("MB", 1_000_000), ("KiB", 1 << 10), ("kB", 1_000),
+        ];
+
+        let unit = UNITS.iter()
+            .find(|(_, scale)| self.bytes != 0 && self.bytes.checked_rem(*scale) == Some(0));
+        match unit {
+            Some((unit, scale)) => write!(f, "{}{}", self.bytes / scale, unit),
+            None => write!(f, "{}B", self.bytes),
+        }
+    }
+}
+
+const EXPECTED_BYTE_SIZE: &str = "a size like `4096`, `512k`, or `10MiB`";
+
+impl Serialize for ByteSize {
+    fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = ser.serialize_struct(Self::NAME, 2)?;
+        state.serialize_field(Self::FIELDS[0], &self.tag)?;
+        state.serialize_field(Self::FIELDS[1], &self.to_string())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D: de::Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = ByteSize;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(EXPECTED_BYTE_SIZE)
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<ByteSize, A::Error> {
+                let (mut tag, mut bytes) = (Tag::Default, None);
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        k if k == ByteSize::FIELDS[0] => tag = map.next_value()?,
+                        k if k == ByteSize::FIELDS[1] => {
+                            bytes = Some(map.next_value::<Bytes>()?.0);
+                        }
+                        _ => { map.next_value::<de::IgnoredAny>()?; }
+                    }
+                }
+
+                let bytes = bytes
+                    .ok_or_else(|| de::Error::missing_field(ByteSize::FIELDS[1]))?;
+
+                Ok(ByteSize { tag, bytes })
+            }
+        }
+
+        de.deserialize_struct(Self::NAME, Self::FIELDS, Visitor)
+    }
+}
+
+/// A size deserialized from a number of bytes or a human-friendly string.
+struct Bytes(u64);
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: de::Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Bytes;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(EXPECTED_BYTE_SIZE)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Bytes, E> {
+                Ok(Bytes(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Bytes, E> {
+                match u64::try_from(v) {
+                    Ok(v) => self.visit_u64(v),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Signed(v), &self)),
+                }
+            }
+
+            fn visit_u128<E: de::Error>(self, v: u128) -> Result<Bytes, E> {
+                match u64::try_from(v) {
+                    Ok(v) => self.visit_u64(v),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Other("a 128-bit integer"), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Bytes, E> {
+                ByteSize::parse(v)
+                    .map(Bytes)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        de.deserialize_any(Visitor)
+    }
+}
+
+/// A wrapper around a secret value of type `T`, like an API token, that is
+/// redacted wherever it could otherwise be logged.
+///
+/// A `Secret<T>` deserializes exactly as a `T` would and, like [`Tagged`],
+/// remembers the [`Tag`] of the value it was deserialized from. Unlike `T`,
+/// the inner value is never revealed unless explicitly requested via
+/// [`Secret::expose()`] or [`Secret::into_inner()`]:
+///
+///   * The [`Debug`] and [`Display`] implementations print `[REDACTED]`.
+///   * Errors deserializing the secret never include its value, neither in
+///     the error's [`Kind`](crate::error::Kind) nor in its sample.
+///   * A `Secret` serializes into a structure that deserializes as the same
+///     `Secret` with the same tag, so configurations round-trip through
+///     [`Serialized`](crate::providers::Serialized), but which
+///     [`Figment::serialize_to()`](crate::Figment::serialize_to()) and
+///     [`Figment::provenance()`](crate::Figment::provenance()) render as
+///     `[REDACTED]`.
+///
+/// Note that a value is only known to be secret once it is deserialized as a
+/// `Secret`, so a figment built directly from a configuration file renders the
+/// value in the clear. Serializing a `Secret` with a serializer other than a
+/// figment's includes the value.
+///
+/// [`Debug`]: std::fmt::Debug
+/// [`Display`]: std::fmt::Display
+///
+/// # Example
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use figment::{Figment, Jail, value::magic::Secret};
+/// use figment::providers::{Format, Toml, Serialized};
+///
+/// #[derive(Debug, Deserialize, Serialize)]
+/// struct Config {
+///     user: String,
+///     token: Secret<String>,
+///     pin: Secret<u16>,
+/// }
+///
+/// Jail::expect_with(|jail| {
+///     jail.create_file("App.toml", r#"
+///         user = "bob"
+///         token = "hunter2"
+///         pin = 1234
+///     "#)?;
+///
+///     let config: Config = Figment::from(Toml::file("App.toml")).extract()?;
+///     assert_eq!(config.token.expose(), "hunter2");
+///     assert_eq!(*config.pin.expose(), 1234);
+///
+///     let debug = format!("{:?}", config);
+///     assert!(debug.contains("[REDACTED]") && !debug.contains("hunter2"));
+///
+///     let figment = Figment::from(Serialized::defaults(&config));
+///     assert_eq!(figment.extract::<Config>()?.token.expose(), "hunter2");
+///     let toml = figment.serialize_to::<Toml>()?;
+///     assert!(toml.contains("token = \"[REDACTED]\"") && !toml.contains("hunter2"));
+///
+///     // Errors don't mention the value either.
+///     jail.create_file("App.toml", r#"
+///         user = "bob"
+///         token = "hunter2"
+///         pin = "secret-pin"
+///     "#)?;
+///
+///     let error = Figment::from(Toml::file("App.toml")).extract::<Config>().unwrap_err();
+///     assert!(!error.to_string().contains("secret-pin"));
+///     Ok(())
+/// });
+/// ```
+#[derive(Clone)]
+pub struct Secret<T> {
+    tag: Tag,
+    value: T,
+}
+
+/// How a secret value is rendered.
+const REDACTED: &str = "[REDACTED]";
+
+impl<T: for<'de> Deserialize<'de>> Magic for Secret<T> {
+    const NAME: &'static str = "___figment_secret";
+    const FIELDS: &'static [&'static str] = &[
+        "___figment_secret_tag", "___figment_secret_value"
+    ];
+
+    fn deserialize_from<'de: 'c, 'c, V: de::Visitor<'de>, I: Interpreter>(
+        de: ConfiguredValueDe<'c, I>,
+        visitor: V
+    ) -> Result<V::Value, Error>{
+        // Every string in the secret, which errors must not mention.
+        let value = de.value.find_ref(Self::FIELDS[1]).unwrap_or(de.value);
+        let mut secrets = vec![];
+        value.walk(&mut |_: &[String], v: &Value| {
+            if let Value::String(_, s) = v {
+                secrets.push(s.clone());
+            }
+
+            crate::value::Visit::Continue
+        });
+
+        // If we have this struct with a non-default tag, use it.
+        if let Some(dict) = de.value.as_dict() {
+            if let Some(tagv) = dict.get(Self::FIELDS[0]) {
+                if let Ok(false) = tagv.deserialize::<Tag>().map(|t| t.is_default()) {
+                    return visitor.visit_map(MapDe::new(dict, |v| de.child(v)))
+                        .map_err(|e| e.redacted(&secrets, REDACTED));
+                }
+            }
+        }
+
+        // Otherwise, tag the value, from this struct if it is one.
+        let mut map = crate::value::Map::new();
+        map.insert(Self::FIELDS[0].into(), de.value.tag().into());
+        map.insert(Self::FIELDS[1].into(), value.clone());
+        visitor.visit_map(MapDe::new(&map, |v| de.child(v)))
+            .map_err(|e| e.redacted(&secrets, REDACTED))
+    }
+}
+
+impl<T: for<'de> Deserialize<'de>> MagicSerialize for Secret<T> {
+    fn humanize(dict: &Dict) -> Option<Value> {
+        humanize_magic::<Self>(dict).map(|_| Value::from(REDACTED))
+    }
+}
+
+impl<T> Secret<T> {
+    /// Returns a reference to the secret value.
+    ///
+    /// ```rust
+    /// use figment::{Figment, value::magic::Secret};
+    ///
+    /// let figment = Figment::from(("token", "hunter2"));
+    /// let token = figment.extract_inner::<Secret<String>>("token").unwrap();
+    /// assert_eq!(token.expose(), "hunter2");
+    /// ```
+    pub fn expose(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the tag of the secret value if it is known.
+    ///
+    /// ```rust
+    /// use figment::{Figment, value::magic::Secret};
+    ///
+    /// let figment = Figment::from(("token", "hunter2"));
+    /// let token = figment.extract_inner::<Secret<String>>("token").unwrap();
+    /// assert!(figment.get_metadata(token.tag()).is_some());
+    /// ```
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Consumes `self` and returns the secret value.
+    ///
+    /// ```rust
+    /// use figment::value::magic::Secret;
+    ///
+    /// let token = Secret::from("hunter2".to_string());
+    /// assert_eq!(token.into_inner(), "hunter2");
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for Secret<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret { tag: Tag::Default, value }
+    }
+}
+
+impl<T> std::fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Secret").field(&format_args!("{}", REDACTED)).finish()
+    }
+}
+
+impl<T> std::fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: Serialize> Serialize for Secret<T> {
+    fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = ser.serialize_struct(Secret::<()>::NAME, 2)?;
+        state.serialize_field(Secret::<()>::FIELDS[0], &self.tag)?;
+        state.serialize_field(Secret::<()>::FIELDS[1], &self.value)?;
+        state.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Secret<T> {
+    fn deserialize<D: de::Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct Visitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> de::Visitor<'de> for Visitor<T> {
+            type Value = Secret<T>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a secret value")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Secret<T>, A::Error> {
+                let (mut tag, mut value) = (Tag::Default, None);
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        k if k == Secret::<()>::FIELDS[0] => tag = map.next_value()?,
+                        k if k == Secret::<()>::FIELDS[1] => value = Some(map.next_value()?),
+                        _ => { map.next_value::<de::IgnoredAny>()?; }
+                    }
+                }
+
+                let value = value
+                    .ok_or_else(|| de::Error::missing_field(Secret::<()>::FIELDS[1]))?;
+
+                Ok(Secret { tag, value })
+            }
+        }
+
+        de.deserialize_struct(Secret::<()>::NAME, Secret::<()>::FIELDS, Visitor(std::marker::PhantomData))
+    }
+}
+
 /// These were generated by serde's derive. We don't want to depend on the
 /// 'derive' feature, so we simply expand it and copy the impls here.
 mod _serde {
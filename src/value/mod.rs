@@ -6,11 +6,27 @@ mod de;
 mod tag;
 mod parse;
 mod escape;
+mod leniency;
+mod num_policy;
+mod limits;
+mod walk;
+mod normalize;
+mod template;
+mod datetime;
 
 pub mod magic;
 
 pub(crate) use {self::ser::*, self::de::*};
+pub(crate) use normalize::Spellings;
+pub(crate) use template::expand_keys;
 
 pub use tag::Tag;
+pub use leniency::NumericLeniency;
+pub use num_policy::NumPolicy;
+pub use limits::{Limits, Limit};
+pub use normalize::{KeyNormalizer, KeyCollision, Case};
+pub use walk::{ValueVisitor, ValueVisitorMut, Visit};
 pub use value::{Value, Map, Num, Dict, Empty};
+pub use parse::FormatKind;
+pub use datetime::{Datetime, Date, Time, Offset, DatetimeParseError};
 pub use uncased::{Uncased, UncasedStr};
@@ -0,0 +1,258 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::Profile;
+use crate::coalesce::Coalescible;
+use crate::value::{Map, Dict, Tag, Value};
+
+/// A normalization of keys applied to provided data, set via
+/// [`Figment::normalize_keys()`](crate::Figment::normalize_keys()).
+///
+/// Keys that look identical may differ byte-for-byte: `café` can be spelled
+/// with a precomposed `é` (U+00E9) or with `e` followed by a combining acute
+/// accent (U+0065 U+0301), and sources disagree on which they emit. Without
+/// normalization, the two spellings are distinct keys. A `KeyNormalizer` maps
+/// every key, at any depth, to a canonical spelling before data is merged so
+/// that such keys coincide.
+///
+/// Figment does not bundle Unicode normalization tables. Use the normalization
+/// forms of a crate like [`unicode-normalization`], typically NFC or, to
+/// additionally fold compatibility characters like `ﬁ` into `fi`, NFKC:
+///
+/// ```rust,ignore
+/// use unicode_normalization::UnicodeNormalization;
+/// use figment::value::KeyNormalizer;
+///
+/// let nfc = KeyNormalizer::new(|key| key.nfc().collect());
+/// let nfkc = KeyNormalizer::new(|key| key.nfkc().collect());
+/// ```
+///
+/// [`unicode-normalization`]: https://docs.rs/unicode-normalization
+#[derive(Clone)]
+pub struct KeyNormalizer(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+/// Distinct spellings of a key that normalized to the same key, returned by
+/// [`Figment::key_collisions()`](crate::Figment::key_collisions()).
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyCollision {
+    /// The profile the key is in.
+    pub profile: Profile,
+    /// The normalized key path, `.`-separated.
+    pub key: String,
+    /// Each distinct spelling of the key, in the order first provided, with the
+    /// tag of a value provided with that spelling. Each spelling is the
+    /// normalized path to the key's parent followed by the key as provided.
+    pub spellings: Vec<(String, Tag)>,
+}
+
+/// A casing convention for keys, set via
+/// [`Figment::rename_all()`](crate::Figment::rename_all()).
+///
+/// Converting a key to a `Case` splits it into words at `_` and `-` and at
+/// transitions from lowercase letters or digits to uppercase letters, then
+/// joins the lowercased words in the convention's style. Runs of uppercase
+/// letters are treated as a single word, so `HTTPServer` has the words `http`
+/// and `server`. Leading and trailing `_` and `-` are preserved, and keys of
+/// Figment's [magic](crate::value::magic) values are never converted.
+///
+/// ```rust
+/// use figment::value::Case;
+///
+/// assert_eq!(Case::Snake.convert("logLevel"), "log_level");
+/// assert_eq!(Case::Snake.convert("max-retries"), "max_retries");
+/// assert_eq!(Case::Kebab.convert("HTTPServer"), "http-server");
+/// assert_eq!(Case::Camel.convert("tls_cert_path"), "tlsCertPath");
+/// assert_eq!(Case::Camel.convert("_private_key"), "_privateKey");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// `snake_case`: lowercase words separated by `_`.
+    Snake,
+    /// `kebab-case`: lowercase words separated by `-`.
+    Kebab,
+    /// `camelCase`: the first word lowercase, subsequent words capitalized.
+    Camel,
+}
+
+/// Every spelling of every key path, by profile and normalized key path.
+pub(crate) type Spellings = Map<Profile, Map<String, Vec<(String, Tag)>>>;
+
+impl KeyNormalizer {
+    /// Returns a normalizer that maps each key to `f(key)`.
+    ///
+    /// ```rust
+    /// use figment::value::KeyNormalizer;
+    ///
+    /// let normalizer = KeyNormalizer::new(|key| key.replace('-', "_"));
+    /// assert_eq!(normalizer.normalize("log-level"), "log_level");
+    /// ```
+    pub fn new<F>(f: F) -> Self
+        where F: Fn(&str) -> String + Send + Sync + 'static
+    {
+        KeyNormalizer(Arc::new(f))
+    }
+
+    /// Returns the normalized spelling of `key`.
+    pub fn normalize(&self, key: &str) -> String {
+        (self.0)(key)
+    }
+
+    /// Normalizes every key in `data`, recording the spelling of each key in
+    /// `spellings` as its normalized parent path followed by the key as
+    /// provided. Keys in the same dictionary that normalize to the same
+    /// key are merged in key order.
+    pub(crate) fn apply(&self, data: Map<Profile, Dict>, spellings: &mut Spellings) -> Map<Profile, Dict> {
+        data.into_iter()
+            .map(|(profile, dict)| {
+                let seen = spellings.entry(profile.clone()).or_default();
+                let dict = self.dict(dict, "", seen);
+                (profile, dict)
+            })
+            .collect()
+    }
+
+    fn dict(&self, dict: Dict, prefix: &str, seen: &mut Map<String, Vec<(String, Tag)>>) -> Dict {
+        let join = |key: &str| match prefix.is_empty() {
+            true => key.to_string(),
+            false => format!("{}.{}", prefix, key),
+        };
+
+        let mut normalized = Dict::new();
+        for (key, value) in dict {
+            let normal_key = self.normalize(&key);
+            let (spelling, path) = (join(&key), join(&normal_key));
+            let spellings = seen.entry(path.clone()).or_default();
+            if !spellings.iter().any(|(s, _)| *s == spelling) {
+                spellings.push((spelling, value.tag()));
+            }
+
+            let value = self.value(value, &path, seen);
+            let value = match normalized.remove(&normal_key) {
+                Some(existing) => existing.merge(value),
+                None => value,
+            };
+
+            normalized.insert(normal_key, value);
+        }
+
+        normalized
+    }
+
+    fn value(&self, value: Value, path: &str, seen: &mut Map<String, Vec<(String, Tag)>>) -> Value {
+        match value {
+            Value::Dict(tag, dict) => Value::Dict(tag, self.dict(dict, path, seen)),
+            Value::Array(tag, array) => {
+                let array = array.into_iter()
+                    .enumerate()
+                    .map(|(i, v)| self.value(v, &format!("{}.{}", path, i), seen))
+                    .collect();
+
+                Value::Array(tag, array)
+            }
+            value => value,
+        }
+    }
+}
+
+impl Case {
+    /// Returns `key` converted to `self`'s convention.
+    pub fn convert(self, key: &str) -> String {
+        // The fields of magic pseudo-structures must be preserved verbatim.
+        if key.starts_with("___figment") {
+            return key.to_string();
+        }
+
+        let is_sep = |c: char| c == '_' || c == '-';
+        let body = key.trim_matches(is_sep);
+        let start = key.len() - key.trim_start_matches(is_sep).len();
+        let (prefix, suffix) = (&key[..start], &key[(start + body.len())..]);
+
+        let mut words: Vec<String> = vec![];
+        let mut word = String::new();
+        let chars: Vec<char> = body.chars().collect();
+        for (i, &c) in chars.iter().enumerate() {
+            if is_sep(c) {
+                words.push(std::mem::take(&mut word));
+                continue;
+            }
+
+            if c.is_uppercase() && !word.is_empty() {
+                let prev = chars[i - 1];
+                let next_is_lower = chars.get(i + 1).map(|n| n.is_lowercase()).unwrap_or(false);
+                if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lower) {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+
+            word.extend(c.to_lowercase());
+        }
+
+        words.push(word);
+        words.retain(|w| !w.is_empty());
+
+        let body = match self {
+            Case::Snake => words.join("_"),
+            Case::Kebab => words.join("-"),
+            Case::Camel => {
+                let mut camel = String::with_capacity(body.len());
+                for (i, word) in words.iter().enumerate() {
+                    let mut chars = word.chars();
+                    match (i, chars.next()) {
+                        (0, _) => camel.push_str(word),
+                        (_, Some(first)) => {
+                            camel.extend(first.to_uppercase());
+                            camel.push_str(chars.as_str());
+                        }
+                        (_, None) => {}
+                    }
+                }
+
+                camel
+            }
+        };
+
+        format!("{}{}{}", prefix, body, suffix)
+    }
+}
+
+impl From<Case> for KeyNormalizer {
+    fn from(case: Case) -> Self {
+        KeyNormalizer::new(move |key| case.convert(key))
+    }
+}
+
+impl KeyCollision {
+    /// Returns the collisions recorded in `spellings`.
+    pub(crate) fn all(spellings: &Spellings) -> Vec<KeyCollision> {
+        spellings.iter()
+            .flat_map(|(profile, keys)| keys.iter().map(move |(key, spellings)| (profile, key, spellings)))
+            .filter(|(_, _, spellings)| spellings.len() > 1)
+            .map(|(profile, key, spellings)| KeyCollision {
+                profile: profile.clone(),
+                key: key.clone(),
+                spellings: spellings.clone(),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for KeyNormalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("KeyNormalizer(..)")
+    }
+}
+
+impl fmt::Display for KeyCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` in profile `{}` was provided as ", self.key, self.profile)?;
+        for (i, (spelling, _)) in self.spellings.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{:?}", spelling)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::value::Num;
+use crate::error::Actual;
+
+/// How numbers are coerced when extracted, set via
+/// [`Figment::num_policy()`](crate::Figment::num_policy()).
+///
+/// Whether a configuration value is an integer or a float depends on how it
+/// was written and on the format it was written in: `port = 8080.0` in TOML
+/// or `"port": 8080.0` in JSON is a float, while `APP_PORT=8080` is an
+/// integer. A `NumPolicy` makes extraction consistent irrespective of the
+/// source. Each coercion is configured independently:
+///
+///   * **float to int:** a float without a fractional part, like `8080.0`,
+///     may be extracted as an integer. _Default: disallowed._
+///   * **wrap unsigned:** an unsigned integer too large for a signed integer
+///     type, like `18446744073709551615` for an `i64`, is reinterpreted as the
+///     signed integer with the same bits, here `-1`, if it fits in the
+///     unsigned integer type of the same width. Otherwise it is an error.
+///     _Default: error._
+///   * **non-finite:** `NaN` and positive or negative infinity may be
+///     extracted as floats. _Default: allowed._
+///
+/// # Example
+///
+/// ```rust
+/// use figment::value::NumPolicy;
+///
+/// let policy = NumPolicy::default();
+/// assert!(!policy.allows_float_to_int());
+/// assert!(!policy.allows_wrap_unsigned());
+/// assert!(policy.allows_non_finite());
+///
+/// let policy = NumPolicy::strict().float_to_int(true);
+/// assert!(policy.allows_float_to_int());
+/// assert!(!policy.allows_non_finite());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumPolicy {
+    float_to_int: bool,
+    wrap_unsigned: bool,
+    non_finite: bool,
+}
+
+impl NumPolicy {
+    /// No coercion is performed and `NaN` and infinities are rejected.
+    ///
+    /// ```rust
+    /// use figment::value::NumPolicy;
+    ///
+    /// let policy = NumPolicy::strict();
+    /// assert!(!policy.allows_float_to_int());
+    /// assert!(!policy.allows_wrap_unsigned());
+    /// assert!(!policy.allows_non_finite());
+    /// ```
+    pub fn strict() -> Self {
+        NumPolicy { float_to_int: false, wrap_unsigned: false, non_finite: false }
+    }
+
+    /// Sets whether floats without a fractional part may be extracted as
+    /// integers.
+    ///
+    /// ```rust
+    /// use figment::value::NumPolicy;
+    ///
+    /// assert!(NumPolicy::default().float_to_int(true).allows_float_to_int());
+    /// ```
+    pub fn float_to_int(mut self, yes: bool) -> Self {
+        self.float_to_int = yes;
+        self
+    }
+
+    /// Sets whether unsigned integers too large for a signed integer type
+    /// wrap around instead of failing to extract.
+    ///
+    /// ```rust
+    /// use figment::value::NumPolicy;
+    ///
+    /// assert!(NumPolicy::default().wrap_unsigned(true).allows_wrap_unsigned());
+    /// ```
+    pub fn wrap_unsigned(mut self, yes: bool) -> Self {
+        self.wrap_unsigned = yes;
+        self
+    }
+
+    /// Sets whether `NaN` and infinities may be extracted as floats.
+    ///
+    /// ```rust
+    /// use figment::value::NumPolicy;
+    ///
+    /// assert!(!NumPolicy::default().non_finite(false).allows_non_finite());
+    /// ```
+    pub fn non_finite(mut self, yes: bool) -> Self {
+        self.non_finite = yes;
+        self
+    }
+
+    /// Returns `true` if floats without a fractional part may be extracted as
+    /// integers.
+    pub fn allows_float_to_int(&self) -> bool {
+        self.float_to_int
+    }
+
+    /// Returns `true` if unsigned integers too large for a signed integer type
+    /// wrap around.
+    pub fn allows_wrap_unsigned(&self) -> bool {
+        self.wrap_unsigned
+    }
+
+    /// Returns `true` if `NaN` and infinities may be extracted as floats.
+    pub fn allows_non_finite(&self) -> bool {
+        self.non_finite
+    }
+
+    /// Returns `num` coerced for extraction as an integer of `bits` bits,
+    /// signed if `signed`, if `self` coerces it.
+    pub(crate) fn coerce_int(&self, num: &Num, bits: u32, signed: bool) -> Option<Num> {
+        match num.to_actual() {
+            Actual::Float(f) if self.float_to_int && f.fract() == 0.0 => {
+                match f.abs() < 2f64.powi(127) {
+                    true if f < 0.0 => Some(Num::I128(f as i128)),
+                    true => Some(Num::U128(f as u128)),
+                    false => None,
+                }
+            }
+            Actual::Unsigned(n) if self.wrap_unsigned && signed => {
+                let fits_signed = n < 1 << (bits - 1);
+                let fits_unsigned = bits == 128 || n < 1 << bits;
+                match (fits_signed, fits_unsigned) {
+                    (false, true) if bits == 128 => Some(Num::I128(n as i128)),
+                    (false, true) => Some(Num::I128(n as i128 - (1 << bits))),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `Err` with the offending float if `num` is `NaN` or infinite
+    /// and `self` doesn't allow that.
+    pub(crate) fn check_finite(&self, num: &Num) -> Result<(), f64> {
+        match num.to_actual() {
+            Actual::Float(f) if !self.non_finite && !f.is_finite() => Err(f),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for NumPolicy {
+    /// Floats aren't coerced to integers, unsigned integers don't wrap, and
+    /// `NaN` and infinities are allowed.
+    fn default() -> Self {
+        NumPolicy { float_to_int: false, wrap_unsigned: false, non_finite: true }
+    }
+}
@@ -195,6 +195,113 @@ impl std::str::FromStr for Value {
     }
 }
 
+/// The syntax of a string parsed via [`Value::parse_as()`].
+///
+/// Variants other than [`FormatKind::Infer`] are only available when the
+/// format's feature is enabled.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FormatKind {
+    /// The syntax inferred by [`Value`]'s `FromStr` implementation, as used by
+    /// [`Env`](crate::providers::Env): `true` and `false` are booleans,
+    /// numbers are numbers, `[..]` are arrays, `{..}` are dictionaries,
+    /// quoted strings are strings, and anything else is the string as-is.
+    /// Parsing never fails.
+    #[default]
+    Infer,
+    /// A TOML value, like `[1, 2]` or `{ a = 1 }`, or a TOML document.
+    #[cfg(feature = "toml")]
+    #[cfg_attr(nightly, doc(cfg(feature = "toml")))]
+    Toml,
+    /// A JSON value.
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    Json,
+    /// A YAML value, with merge keys applied as by
+    /// [`Yaml`](crate::providers::Yaml).
+    #[cfg(feature = "yaml")]
+    #[cfg_attr(nightly, doc(cfg(feature = "yaml")))]
+    Yaml,
+    /// A RON value.
+    #[cfg(feature = "ron")]
+    #[cfg_attr(nightly, doc(cfg(feature = "ron")))]
+    Ron,
+    /// A JSON5 value.
+    #[cfg(feature = "json5")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json5")))]
+    Json5,
+}
+
+impl Value {
+    /// Parses `string` as a value in the syntax `format`.
+    ///
+    /// This allows custom providers and command-line overrides to parse
+    /// strings exactly as figment's own providers do: [`FormatKind::Infer`]
+    /// as [`Env`](crate::providers::Env) does, or as a value in one of the
+    /// supported data formats. Parsing with [`FormatKind::Infer`] is
+    /// equivalent to `string.parse::<Value>()` and never fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns the format's error if `string` is not valid in `format`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::value::{Value, FormatKind};
+    ///
+    /// let value = Value::parse_as("[1, 2]", FormatKind::Infer).unwrap();
+    /// assert_eq!(value, vec![1u8, 2].into());
+    ///
+    /// let value = Value::parse_as("hello world", FormatKind::Infer).unwrap();
+    /// assert_eq!(value, "hello world".into());
+    ///
+    /// # #[cfg(all(feature = "toml", feature = "json"))] {
+    /// let value = Value::parse_as("{ port = 80, tls = true }", FormatKind::Toml).unwrap();
+    /// assert_eq!(value.find("port").and_then(|v| v.to_i128()), Some(80));
+    ///
+    /// let value = Value::parse_as(r#"{ "port": 80 }"#, FormatKind::Json).unwrap();
+    /// assert_eq!(value.find("port").and_then(|v| v.to_u128()), Some(80));
+    ///
+    /// assert!(Value::parse_as("hello world", FormatKind::Json).is_err());
+    /// # }
+    /// ```
+    pub fn parse_as(string: &str, format: FormatKind) -> std::result::Result<Value, crate::Error> {
+        #[allow(unused_imports)]
+        use crate::providers::Format;
+
+        #[allow(unused_macros)]
+        macro_rules! parse {
+            ($result:expr) => ($result.map_err(|e| crate::Error::from(e.to_string())))
+        }
+
+        match format {
+            FormatKind::Infer => match string.parse::<Value>() {
+                Ok(value) => Ok(value),
+                Err(e) => match e {},
+            },
+            #[cfg(feature = "toml")]
+            FormatKind::Toml => {
+                let value = string.parse::<toml_edit::de::ValueDeserializer>()
+                    .and_then(<Value as serde::Deserialize>::deserialize);
+
+                match value {
+                    Ok(value) => Ok(value),
+                    Err(e) => parse!(toml_edit::de::from_str::<Value>(string).map_err(|_| e)),
+                }
+            }
+            #[cfg(feature = "json")]
+            FormatKind::Json => parse!(crate::providers::Json::from_str::<Value>(string)),
+            #[cfg(feature = "yaml")]
+            FormatKind::Yaml => parse!(crate::providers::Yaml::from_str::<Value>(string)),
+            #[cfg(feature = "ron")]
+            FormatKind::Ron => parse!(<crate::providers::Ron as crate::providers::Format>::from_str::<Value>(string)),
+            #[cfg(feature = "json5")]
+            FormatKind::Json5 => parse!(crate::providers::Json5::from_str::<Value>(string)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
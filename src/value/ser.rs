@@ -31,6 +31,7 @@ impl Serialize for Value {
 
                 seq.end()
             }
+            Value::Datetime(_, v) => ser.collect_str(v),
         }
     }
 }
@@ -0,0 +1,84 @@
+use crate::Profile;
+use crate::coalesce::Coalescible;
+use crate::error::{Error, Kind, Result};
+use crate::value::{Map, Dict, Value};
+
+/// Replaces every `${NAME}` placeholder in every key of `data`, at any depth,
+/// with the value of `NAME` in `vars`. A key that contains `.` after
+/// replacement is nested as a key path. Keys that coincide after replacement
+/// are merged in key order.
+pub(crate) fn expand_keys(data: Map<Profile, Dict>, vars: &Map<String, String>) -> Result<Map<Profile, Dict>> {
+    data.into_iter()
+        .map(|(profile, dict)| {
+            let dict = expand_dict(dict, vars).map_err(|mut e| {
+                e.profile = Some(profile.clone());
+                e
+            })?;
+
+            Ok((profile, dict))
+        })
+        .collect()
+}
+
+fn expand_dict(dict: Dict, vars: &Map<String, String>) -> Result<Dict> {
+    let mut expanded = Dict::new();
+    for (key, value) in dict {
+        let tag = value.tag();
+        let value = expand_value(value, vars).map_err(|e| e.prefixed(&key))?;
+        let path = expand(&key, vars).map_err(|e| Error::from(e).prefixed(&key).retagged(tag))?;
+        let (first, rest) = path.split_once('.').unwrap_or((&path, ""));
+        let value = match rest.is_empty() {
+            true => value,
+            false => {
+                let mut nested = crate::util::nest(rest, value);
+                nested.map_tag(|t| *t = tag);
+                nested
+            }
+        };
+
+        let value = match expanded.remove(first) {
+            Some(existing) => existing.merge(value),
+            None => value,
+        };
+
+        expanded.insert(first.to_string(), value);
+    }
+
+    Ok(expanded)
+}
+
+fn expand_value(value: Value, vars: &Map<String, String>) -> Result<Value> {
+    match value {
+        Value::Dict(tag, dict) => Ok(Value::Dict(tag, expand_dict(dict, vars)?)),
+        Value::Array(tag, array) => {
+            let array = array.into_iter()
+                .enumerate()
+                .map(|(i, v)| expand_value(v, vars).map_err(|e| e.prefixed(&i.to_string())))
+                .collect::<Result<_>>()?;
+
+            Ok(Value::Array(tag, array))
+        }
+        value => Ok(value),
+    }
+}
+
+/// Replaces every `${NAME}` in `key` with `vars[NAME]`.
+fn expand(key: &str, vars: &Map<String, String>) -> std::result::Result<String, Kind> {
+    let mut expanded = String::with_capacity(key.len());
+    let mut rest = key;
+    while let Some(i) = rest.find("${") {
+        expanded.push_str(&rest[..i]);
+        let end = rest[i..].find('}')
+            .ok_or_else(|| Kind::Message(format!("unterminated placeholder in key `{}`", key)))?;
+
+        let name = &rest[(i + 2)..(i + end)];
+        let value = vars.get(name)
+            .ok_or_else(|| Kind::Message(format!("unknown key placeholder `${{{}}}`", name)))?;
+
+        expanded.push_str(value);
+        rest = &rest[(i + end + 1)..];
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
@@ -4,7 +4,7 @@ use std::str::{FromStr, Split};
 
 use serde::Serialize;
 
-use crate::value::{Tag, ValueSerializer, magic::Either};
+use crate::value::{Tag, ValueSerializer, Datetime, magic::Either};
 use crate::error::{Error, Actual};
 
 /// An alias to the type of map used in [`Value::Dict`].
@@ -39,6 +39,11 @@ pub enum Value {
     Dict(Tag, Dict),
     /// A sequence/array/vector.
     Array(Tag, Vec<Value>),
+    /// A date, time, or date and time, as emitted by `Toml` providers.
+    ///
+    /// Added in `0.11`: exhaustive matches written against `0.10` must handle
+    /// this variant.
+    Datetime(Tag, Datetime),
 }
 
 macro_rules! conversion_fn {
@@ -213,6 +218,7 @@ impl Value {
             Value::Dict(tag, ..) => tag,
             Value::Array(tag, ..) => tag,
             Value::Empty(tag, ..) => tag,
+            Value::Datetime(tag, ..) => tag,
         }
     }
 
@@ -226,6 +232,7 @@ impl Value {
     conversion_fn!(Value, Dict => Dict, into_dict);
     conversion_fn!(&Value, Array => &[Value], as_array);
     conversion_fn!(Value, Array => Vec<Value>, into_array);
+    conversion_fn!(&Value, [*]Datetime => Datetime, to_datetime);
 
     /// Converts `self` into a `u128` if `self` is an unsigned `Value::Num`
     /// variant.
@@ -391,6 +398,70 @@ impl Value {
             Value::Empty(_, e) => e.to_actual(),
             Value::Dict(_, _) => Actual::Map,
             Value::Array(_, _) => Actual::Seq,
+            Value::Datetime(_, d) => Actual::Str(d.to_string()),
+        }
+    }
+
+    /// Returns a compact, single-line rendering of `self` for error messages,
+    /// showing at most `entries` entries of every dictionary and array, long
+    /// strings truncated, and containers nested deeper than three levels
+    /// elided.
+    pub(crate) fn sample(&self, entries: usize) -> String {
+        Sample { value: self, entries, depth: 0 }.to_string()
+    }
+
+    /// Returns `true` if `self` and `other` have the same content. Unlike
+    /// `==`, integers are compared by value irrespective of signedness, so
+    /// `1u8` and `1i64` have the same content.
+    pub(crate) fn content_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Num(_, a), Value::Num(_, b)) => match (a.to_actual(), b.to_actual()) {
+                (Actual::Unsigned(u), Actual::Signed(i)) | (Actual::Signed(i), Actual::Unsigned(u)) => {
+                    i >= 0 && u == i as u128
+                }
+                (a, b) => a == b,
+            },
+            (Value::Dict(_, a), Value::Dict(_, b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|((k1, v1), (k2, v2))| {
+                    k1 == k2 && v1.content_eq(v2)
+                })
+            }
+            (Value::Array(_, a), Value::Array(_, b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(v1, v2)| v1.content_eq(v2))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Feeds the content of `self` into `state` such that values for which
+    /// [`Value::content_eq()`] is `true` hash identically.
+    pub(crate) fn hash_content<H: std::hash::Hasher>(&self, state: &mut H) {
+        use std::hash::Hash;
+
+        match self {
+            Value::String(_, v) => (0u8, v).hash(state),
+            Value::Char(_, v) => (1u8, v).hash(state),
+            Value::Bool(_, v) => (2u8, v).hash(state),
+            Value::Num(_, v) => match v.to_actual() {
+                Actual::Signed(i) if i < 0 => (3u8, i as u128).hash(state),
+                Actual::Signed(i) => (4u8, i as u128).hash(state),
+                Actual::Unsigned(u) => (4u8, u).hash(state),
+                Actual::Float(f) => (5u8, if f == 0.0 { 0 } else { f.to_bits() }).hash(state),
+                _ => unreachable!("numbers are integers or floats"),
+            },
+            Value::Empty(_, v) => (6u8, matches!(v, Empty::None)).hash(state),
+            Value::Dict(_, dict) => {
+                (7u8, dict.len()).hash(state);
+                for (key, value) in dict {
+                    key.hash(state);
+                    value.hash_content(state);
+                }
+            }
+            Value::Array(_, array) => {
+                (8u8, array.len()).hash(state);
+                array.iter().for_each(|v| v.hash_content(state));
+            }
+            Value::Datetime(_, v) => (9u8, v).hash(state),
         }
     }
 
@@ -403,6 +474,7 @@ impl Value {
             Value::Dict(tag, ..) => tag,
             Value::Array(tag, ..) => tag,
             Value::Empty(tag, ..) => tag,
+            Value::Datetime(tag, ..) => tag,
         }
     }
 
@@ -421,6 +493,71 @@ impl Value {
     }
 }
 
+/// The rendering of [`Value::sample()`].
+struct Sample<'v> {
+    value: &'v Value,
+    entries: usize,
+    depth: usize,
+}
+
+impl Sample<'_> {
+    fn child<'v>(&self, value: &'v Value) -> Sample<'v> {
+        Sample { value, entries: self.entries, depth: self.depth + 1 }
+    }
+
+    fn more(&self, f: &mut std::fmt::Formatter<'_>, len: usize) -> std::fmt::Result {
+        match (len > self.entries, self.entries) {
+            (true, 0) => write!(f, "... {} more", len),
+            (true, _) => write!(f, ", ... {} more", len - self.entries),
+            (false, _) => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for Sample<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Value::String(_, s) if s.chars().count() > 32 => {
+                write!(f, "{:?}...", s.chars().take(29).collect::<String>())
+            }
+            Value::String(_, s) => write!(f, "{:?}", s),
+            Value::Char(_, c) => write!(f, "{:?}", c),
+            Value::Bool(_, b) => write!(f, "{}", b),
+            Value::Num(_, n) => match n.to_actual() {
+                Actual::Unsigned(v) => write!(f, "{}", v),
+                Actual::Signed(v) => write!(f, "{}", v),
+                Actual::Float(v) => write!(f, "{:?}", v),
+                actual => write!(f, "{}", actual),
+            },
+            Value::Empty(_, Empty::None) => f.write_str("null"),
+            Value::Empty(_, Empty::Unit) => f.write_str("()"),
+            Value::Datetime(_, d) => write!(f, "{}", d),
+            Value::Dict(..) if self.depth >= 3 => f.write_str("{...}"),
+            Value::Array(..) if self.depth >= 3 => f.write_str("[...]"),
+            Value::Dict(_, dict) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in dict.iter().take(self.entries).enumerate() {
+                    let sep = if i > 0 { ", " } else { "" };
+                    write!(f, "{}{}: {}", sep, key, self.child(value))?;
+                }
+
+                self.more(f, dict.len())?;
+                f.write_str("}")
+            }
+            Value::Array(_, array) => {
+                f.write_str("[")?;
+                for (i, value) in array.iter().take(self.entries).enumerate() {
+                    let sep = if i > 0 { ", " } else { "" };
+                    write!(f, "{}{}", sep, self.child(value))?;
+                }
+
+                self.more(f, array.len())?;
+                f.write_str("]")
+            }
+        }
+    }
+}
+
 impl std::fmt::Debug for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -431,6 +568,7 @@ impl std::fmt::Debug for Value {
             Self::Empty(_, v) => f.debug_tuple("Empty").field(v).finish(),
             Self::Dict(_, v) => f.debug_tuple("Dict").field(v).finish(),
             Self::Array(_, v) => f.debug_tuple("Array").field(v).finish(),
+            Self::Datetime(_, v) => f.debug_tuple("Datetime").field(v).finish(),
         }
     }
 }
@@ -445,6 +583,7 @@ impl PartialEq for Value {
             (Value::Empty(_, v1), Value::Empty(_, v2)) => v1 == v2,
             (Value::Dict(_, v1), Value::Dict(_, v2)) => v1 == v2,
             (Value::Array(_, v1), Value::Array(_, v2)) => v1 == v2,
+            (Value::Datetime(_, v1), Value::Datetime(_, v2)) => v1 == v2,
             _ => false,
         }
     }
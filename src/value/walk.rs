@@ -0,0 +1,178 @@
+use crate::value::Value;
+
+/// What to do after a [`ValueVisitor`] or [`ValueVisitorMut`] visits a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visit {
+    /// Continue the walk, visiting the value's children, if any.
+    Continue,
+    /// Continue the walk but don't visit the value's children.
+    SkipChildren,
+    /// Stop the walk: no further values are visited.
+    Stop,
+}
+
+/// A visitor of every value in a [`Value`] tree, via [`Value::walk()`].
+///
+/// Values are visited depth-first, parents before their children, and the
+/// children of a dictionary in key order. Each value is visited with its key
+/// path relative to the walked value: the keys of dictionaries and the indices,
+/// as strings, of arrays. The walked value itself is visited first with an
+/// empty path. The depth of a value is the length of its path; its tag is
+/// [`Value::tag()`].
+///
+/// `ValueVisitor` is implemented for all `FnMut(&[String], &Value) -> Visit`.
+pub trait ValueVisitor {
+    /// Visits `value` at key path `path`.
+    fn visit(&mut self, path: &[String], value: &Value) -> Visit;
+}
+
+/// A visitor of every value in a [`Value`] tree that can modify values, via
+/// [`Value::walk_mut()`].
+///
+/// Values are visited in the same order and with the same paths as a
+/// [`ValueVisitor`]. The children of a value are visited after the value, so a
+/// visitor that replaces a value visits the replacement's children.
+///
+/// `ValueVisitorMut` is implemented for all
+/// `FnMut(&[String], &mut Value) -> Visit`.
+pub trait ValueVisitorMut {
+    /// Visits `value` at key path `path`.
+    fn visit(&mut self, path: &[String], value: &mut Value) -> Visit;
+}
+
+impl<F: FnMut(&[String], &Value) -> Visit> ValueVisitor for F {
+    fn visit(&mut self, path: &[String], value: &Value) -> Visit {
+        self(path, value)
+    }
+}
+
+impl<F: FnMut(&[String], &mut Value) -> Visit> ValueVisitorMut for F {
+    fn visit(&mut self, path: &[String], value: &mut Value) -> Visit {
+        self(path, value)
+    }
+}
+
+impl Value {
+    /// Visits `self` and every value nested in `self` with `visitor`. See
+    /// [`ValueVisitor`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{value::{Value, Visit}, util::map};
+    ///
+    /// let value = Value::from(map! {
+    ///     "server" => Value::from(map!["hosts" => vec!["a", "b"]]),
+    ///     "name" => Value::from(vec!["app"]),
+    /// });
+    ///
+    /// let mut paths = vec![];
+    /// value.walk(&mut |path: &[String], value: &Value| {
+    ///     if value.as_str().is_some() {
+    ///         paths.push((path.join("."), path.len()));
+    ///     }
+    ///
+    ///     Visit::Continue
+    /// });
+    ///
+    /// assert_eq!(paths, [
+    ///     ("name.0".to_string(), 2),
+    ///     ("server.hosts.0".to_string(), 3),
+    ///     ("server.hosts.1".to_string(), 3),
+    /// ]);
+    /// ```
+    pub fn walk<V: ValueVisitor + ?Sized>(&self, visitor: &mut V) {
+        fn walk<V: ValueVisitor + ?Sized>(
+            value: &Value,
+            path: &mut Vec<String>,
+            visitor: &mut V
+        ) -> bool {
+            match visitor.visit(path, value) {
+                Visit::Continue => {},
+                Visit::SkipChildren => return true,
+                Visit::Stop => return false,
+            }
+
+            let children: Box<dyn Iterator<Item = (String, &Value)>> = match value {
+                Value::Dict(_, dict) => Box::new(dict.iter().map(|(k, v)| (k.clone(), v))),
+                Value::Array(_, array) => {
+                    Box::new(array.iter().enumerate().map(|(i, v)| (i.to_string(), v)))
+                }
+                _ => return true,
+            };
+
+            for (key, child) in children {
+                path.push(key);
+                let keep_going = walk(child, path, visitor);
+                path.pop();
+                if !keep_going {
+                    return false;
+                }
+            }
+
+            true
+        }
+
+        walk(self, &mut vec![], visitor);
+    }
+
+    /// Visits `self` and every value nested in `self` with `visitor`, which
+    /// may modify the values. See [`ValueVisitorMut`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::{value::{Value, Visit}, util::map};
+    ///
+    /// let mut value = Value::from(map! {
+    ///     "db" => map!["user" => "admin", "password" => "hunter2"],
+    ///     "api" => map!["password" => "swordfish"],
+    /// });
+    ///
+    /// value.walk_mut(&mut |path: &[String], value: &mut Value| {
+    ///     if path.last().map(|k| k.as_str()) == Some("password") {
+    ///         *value = Value::from("<redacted>");
+    ///     }
+    ///
+    ///     Visit::Continue
+    /// });
+    ///
+    /// assert_eq!(value.find_ref("db.password").unwrap().as_str(), Some("<redacted>"));
+    /// assert_eq!(value.find_ref("api.password").unwrap().as_str(), Some("<redacted>"));
+    /// assert_eq!(value.find_ref("db.user").unwrap().as_str(), Some("admin"));
+    /// ```
+    pub fn walk_mut<V: ValueVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        fn walk<V: ValueVisitorMut + ?Sized>(
+            value: &mut Value,
+            path: &mut Vec<String>,
+            visitor: &mut V
+        ) -> bool {
+            match visitor.visit(path, value) {
+                Visit::Continue => {},
+                Visit::SkipChildren => return true,
+                Visit::Stop => return false,
+            }
+
+            let children: Box<dyn Iterator<Item = (String, &mut Value)>> = match value {
+                Value::Dict(_, dict) => Box::new(dict.iter_mut().map(|(k, v)| (k.clone(), v))),
+                Value::Array(_, array) => {
+                    Box::new(array.iter_mut().enumerate().map(|(i, v)| (i.to_string(), v)))
+                }
+                _ => return true,
+            };
+
+            for (key, child) in children {
+                path.push(key);
+                let keep_going = walk(child, path, visitor);
+                path.pop();
+                if !keep_going {
+                    return false;
+                }
+            }
+
+            true
+        }
+
+        walk(self, &mut vec![], visitor);
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+use std::sync::Arc;
+
+use serde::de::Deserialize;
+
+use crate::{Figment, Profile, Metadata};
+use crate::error::{Kind, Result};
+use crate::value::{Value, Tag, ConfiguredValueDe, DefaultInterpreter};
+
+/// A read-only, cheaply cloneable view of a built [`Figment`] for sharing
+/// across threads.
+///
+/// Created via [`Figment::view()`] or `FigmentView::from(figment)`. A view
+/// supports extraction and lookups but not combining providers or changing
+/// settings, making the usual lifecycle explicit: build a figment, then share
+/// a view of it. Cloning a view clones an [`Arc`], and a view is `Send` and
+/// `Sync`.
+///
+/// The configuration of the selected profile is merged once, when the view is
+/// created. Extractions and lookups read directly from it without consulting
+/// the figment's internal cache, which is shared by clones of a `Figment` and
+/// guarded by a lock. To change the configuration, build a new figment,
+/// perhaps starting from [`FigmentView::to_figment()`], and create a new view.
+///
+/// # Example
+///
+/// ```rust
+/// use std::thread;
+/// use serde::Deserialize;
+/// use figment::{Figment, FigmentView, providers::Serialized};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Config {
+///     name: String,
+///     workers: usize,
+/// }
+///
+/// let view: FigmentView = Figment::new()
+///     .merge(Serialized::default("name", "app"))
+///     .merge(Serialized::default("workers", 4))
+///     .view();
+///
+/// let handles: Vec<_> = (0..4).map(|_| {
+///     let view = view.clone();
+///     thread::spawn(move || view.extract::<Config>().unwrap())
+/// }).collect();
+///
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), Config { name: "app".into(), workers: 4 });
+/// }
+///
+/// assert_eq!(view.extract_inner::<usize>("workers").unwrap(), 4);
+/// assert!(view.find_metadata("name").is_some());
+/// ```
+#[derive(Clone)]
+pub struct FigmentView {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    figment: Figment,
+    merged: Result<Value>,
+}
+
+impl FigmentView {
+    /// Extracts the configuration of the selected profile as a `T`, as
+    /// [`Figment::extract()`] does.
+    pub fn extract<'a, T: Deserialize<'a>>(&self) -> Result<T> {
+        let figment = &self.inner.figment;
+        let value = self.inner.merged.as_ref().map_err(|e| e.clone())?;
+        let result = T::deserialize(ConfiguredValueDe::<'_, DefaultInterpreter>::from(figment, value));
+        figment.check_unknown("", value, result)
+    }
+
+    /// Extracts the value at `path` as a `T`, as [`Figment::extract_inner()`]
+    /// does.
+    pub fn extract_inner<'a, T: Deserialize<'a>>(&self, path: &str) -> Result<T> {
+        let figment = &self.inner.figment;
+        let value = self.lookup(path)?;
+        let de = ConfiguredValueDe::<'_, DefaultInterpreter>::from(figment, value);
+        let result = T::deserialize(de).map_err(|e| e.with_path(path));
+        figment.check_unknown(path, value, result)
+    }
+
+    /// Returns the value at `path`, as [`Figment::find_value()`] does.
+    pub fn find_value(&self, path: &str) -> Result<Value> {
+        self.lookup(path).cloned()
+    }
+
+    /// Returns `true` if there is a value at `path`, as
+    /// [`Figment::contains()`] does.
+    pub fn contains(&self, path: &str) -> bool {
+        self.lookup(path).is_ok()
+    }
+
+    /// Returns the selected profile.
+    pub fn profile(&self) -> &Profile {
+        self.inner.figment.profile()
+    }
+
+    /// Returns the metadata of the value at `key`, as
+    /// [`Figment::find_metadata()`] does.
+    pub fn find_metadata(&self, key: &str) -> Option<&Metadata> {
+        self.get_metadata(self.lookup(key).ok()?.tag())
+    }
+
+    /// Returns the metadata with the tag `tag`, as [`Figment::get_metadata()`]
+    /// does.
+    pub fn get_metadata(&self, tag: Tag) -> Option<&Metadata> {
+        self.inner.figment.get_metadata(tag)
+    }
+
+    /// Returns an iterator over the metadata of every provider, as
+    /// [`Figment::metadata()`] does.
+    pub fn metadata(&self) -> impl Iterator<Item = &Metadata> {
+        self.inner.figment.metadata()
+    }
+
+    /// Returns the figment `self` views.
+    ///
+    /// ```rust
+    /// use figment::{Figment, providers::Serialized};
+    ///
+    /// let view = Figment::from(Serialized::default("port", 80)).view();
+    /// let figment = view.to_figment().merge(Serialized::default("port", 8080));
+    /// assert_eq!(view.extract_inner::<u16>("port").unwrap(), 80);
+    /// assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 8080);
+    /// ```
+    pub fn to_figment(&self) -> Figment {
+        self.inner.figment.clone()
+    }
+
+    /// Returns the value at `path` in the merged value.
+    fn lookup(&self, path: &str) -> Result<&Value> {
+        let merged = self.inner.merged.as_ref().map_err(|e| e.clone())?;
+        merged.find_ref(path)
+            .ok_or_else(|| Kind::MissingField(path.to_string().into()).into())
+    }
+}
+
+impl From<Figment> for FigmentView {
+    fn from(figment: Figment) -> Self {
+        let merged = figment.merged();
+        FigmentView { inner: Arc::new(Inner { figment, merged }) }
+    }
+}
+
+impl fmt::Debug for FigmentView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FigmentView").field(&self.inner.figment).finish()
+    }
+}
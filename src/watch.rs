@@ -0,0 +1,259 @@
+//! Reloading of configurations when the files they're read from change.
+//!
+//! A [`Figment`] holds the data its providers emitted when they were combined,
+//! not the providers themselves. To reload a configuration, the figment must
+//! be rebuilt. [`Watch`] does so in a background thread: it takes a function
+//! that builds the figment, extracts a `T` from the built figment, watches
+//! every file the figment's values were read from, and rebuilds and
+//! re-extracts whenever one of them changes, delivering each extracted `T` to
+//! a callback or over a channel.
+//!
+//! Files are watched by a [`Watcher`]. By default, [`Poll`] periodically polls
+//! the files' modification times and lengths, which requires no platform
+//! support. A `Watcher` backed by platform file events, for instance via the
+//! `notify` crate, can be used instead via [`Watch::watcher()`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use serde::Deserialize;
+//! use figment::{Figment, Jail, providers::{Format, Toml}, watch::{Watch, Poll}};
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Config {
+//!     workers: u16,
+//! }
+//!
+//! Jail::expect_with(|jail| {
+//!     let path = jail.directory().join("App.toml");
+//!     jail.create_file(&path, "workers = 4")?;
+//!
+//!     let build = move || Figment::from(Toml::file(&path));
+//!     let (handle, configs) = Watch::new(build)
+//!         .watcher(Poll::every(Duration::from_millis(10)))
+//!         .channel::<Config>();
+//!
+//!     assert_eq!(configs.recv().unwrap()?.workers, 4);
+//!
+//!     jail.create_file("App.toml", "workers = 16")?;
+//!     assert_eq!(configs.recv().unwrap()?.workers, 16);
+//!
+//!     handle.stop();
+//!     Ok(())
+//! });
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::de::DeserializeOwned;
+
+use crate::{Figment, Error};
+use crate::value::Map;
+
+/// A watcher of files, used by [`Watch`] to detect changes.
+pub trait Watcher: Send + 'static {
+    /// Starts watching `paths`, replacing any previously watched paths. Called
+    /// before a figment is built with the paths the previous build read from,
+    /// so that changes made while the figment is built are detected, and again
+    /// before rebuilding if the build read from other paths. A path that
+    /// doesn't exist may be created later.
+    fn watch(&mut self, paths: &[PathBuf]);
+
+    /// Waits up to `timeout` for any watched path to change since it was
+    /// watched or since the last call to `wait()` that returned `true`.
+    /// Returns `true` if one may have changed and `false` otherwise.
+    fn wait(&mut self, timeout: Duration) -> bool;
+}
+
+/// A [`Watcher`] that polls the modification time and length of files.
+#[derive(Debug, Clone)]
+pub struct Poll {
+    interval: Duration,
+    next: Instant,
+    seen: Map<PathBuf, Option<(SystemTime, u64)>>,
+}
+
+/// A builder of a background reload of a configuration. See the [module
+/// docs](self) for details.
+pub struct Watch<B> {
+    build: B,
+    watcher: Box<dyn Watcher>,
+    paths: Vec<PathBuf>,
+}
+
+/// A handle to a running [`Watch`]. Dropping the handle stops the watch.
+#[derive(Debug)]
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Poll {
+    /// Returns a watcher that polls files every `interval`.
+    pub fn every(interval: Duration) -> Poll {
+        Poll { interval, next: Instant::now() + interval, seen: Map::new() }
+    }
+
+    fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+}
+
+impl Default for Poll {
+    /// Polls every second.
+    fn default() -> Self {
+        Poll::every(Duration::from_secs(1))
+    }
+}
+
+impl Watcher for Poll {
+    fn watch(&mut self, paths: &[PathBuf]) {
+        self.seen = paths.iter().map(|p| (p.clone(), Poll::stamp(p))).collect();
+        self.next = Instant::now() + self.interval;
+    }
+
+    fn wait(&mut self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let now = Instant::now();
+            if now >= self.next {
+                self.next = now + self.interval;
+                let mut changed = false;
+                for (path, stamp) in self.seen.iter_mut() {
+                    let current = Poll::stamp(path);
+                    if current != *stamp {
+                        *stamp = current;
+                        changed = true;
+                    }
+                }
+
+                if changed {
+                    return true;
+                }
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+
+            thread::sleep(deadline.min(self.next) - now);
+        }
+    }
+}
+
+/// The longest a watch waits before checking whether it's been stopped.
+const STOP_CHECK: Duration = Duration::from_millis(50);
+
+impl<B: Fn() -> Figment + Send + 'static> Watch<B> {
+    /// Returns a watch that builds figments with `build`, watching files with
+    /// [`Poll::default()`].
+    pub fn new(build: B) -> Self {
+        Watch { build, watcher: Box::new(Poll::default()), paths: vec![] }
+    }
+
+    /// Sets the watcher used to watch files to `watcher`.
+    pub fn watcher<W: Watcher>(mut self, watcher: W) -> Self {
+        self.watcher = Box::new(watcher);
+        self
+    }
+
+    /// Additionally watches `path`, which need not exist, such as an optional
+    /// file that's only read if it exists.
+    pub fn path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.paths.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Starts watching in a background thread, calling `callback` with the
+    /// extracted configuration once immediately and again after every change.
+    pub fn spawn<T, C>(self, callback: C) -> WatchHandle
+        where T: DeserializeOwned, C: FnMut(Result<T, Error>) + Send + 'static
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            let Watch { build, mut watcher, paths: extra } = self;
+            let mut callback = callback;
+            thread::spawn(move || {
+                let mut paths = vec![];
+                reload(&build, &extra, &mut paths, &mut *watcher, &mut callback);
+                while !stop.load(Ordering::Acquire) {
+                    if watcher.wait(STOP_CHECK) && !stop.load(Ordering::Acquire) {
+                        reload(&build, &extra, &mut paths, &mut *watcher, &mut callback);
+                    }
+                }
+            })
+        };
+
+        WatchHandle { stop, thread: Some(thread) }
+    }
+
+    /// Starts watching in a background thread, sending the extracted
+    /// configuration over the returned channel once immediately and again
+    /// after every change.
+    pub fn channel<T>(self) -> (WatchHandle, Receiver<Result<T, Error>>)
+        where T: DeserializeOwned + Send + 'static
+    {
+        let (tx, rx) = mpsc::channel();
+        let handle = self.spawn(move |config| { let _ = tx.send(config); });
+        (handle, rx)
+    }
+}
+
+/// Builds a figment with `build` and passes the extracted `T` to `callback`.
+/// `paths`, the files the last build was read from and `extra`, are watched
+/// with `watcher` before building so that no change made while building is
+/// missed. If the build reads from other files, they're watched instead and
+/// the figment is built again.
+fn reload<T, B, C>(
+    build: &B,
+    extra: &[PathBuf],
+    paths: &mut Vec<PathBuf>,
+    watcher: &mut dyn Watcher,
+    callback: &mut C,
+) where T: DeserializeOwned, B: Fn() -> Figment, C: FnMut(Result<T, Error>)
+{
+    loop {
+        watcher.watch(paths);
+        let figment = build();
+        let sources = figment.metadata().filter_map(|md| md.source.as_ref()?.file_path());
+        let mut read: Vec<PathBuf> = sources.map(|p| p.to_path_buf()).collect();
+        read.extend(extra.iter().cloned());
+        read.sort();
+        read.dedup();
+
+        if read == *paths {
+            return callback(figment.extract());
+        }
+
+        *paths = read;
+    }
+}
+
+impl WatchHandle {
+    /// Stops watching and waits for the background thread to exit.
+    pub fn stop(mut self) {
+        self.halt();
+    }
+
+    fn halt(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.halt();
+    }
+}
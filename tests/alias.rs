@@ -0,0 +1,47 @@
+use figment::{Figment, Profile, providers::Serialized, util::map};
+
+#[test]
+fn alias_new_key_takes_precedence_within_provider() {
+    let figment = Figment::new()
+        .alias("old", "new")
+        .merge(Serialized::defaults(map!["old" => 1, "new" => 2]))
+        .merge(Serialized::default("old", 3).profile("prod"));
+
+    assert_eq!(figment.extract_inner::<u8>("new").unwrap(), 2);
+    assert!(!figment.contains("old"));
+
+    let prod = figment.clone().select("prod");
+    assert_eq!(prod.extract_inner::<u8>("new").unwrap(), 3);
+
+    let warnings = figment.warnings();
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[1].profile, Some(Profile::new("prod")));
+    assert_eq!(warnings[1].path, ["old"]);
+}
+
+#[test]
+fn alias_merges_sections_and_applies_only_to_later_providers() {
+    let figment = Figment::new()
+        .merge(Serialized::default("legacy.a", 1))
+        .alias("legacy", "section")
+        .merge(Serialized::defaults(map![
+            "legacy" => map!["a" => 2, "b" => 3],
+            "section" => map!["b" => 4, "c" => 5],
+        ]));
+
+    assert_eq!(figment.extract_inner::<u8>("legacy.a").unwrap(), 1);
+    assert_eq!(figment.extract_inner::<u8>("section.a").unwrap(), 2);
+    assert_eq!(figment.extract_inner::<u8>("section.b").unwrap(), 4);
+    assert_eq!(figment.extract_inner::<u8>("section.c").unwrap(), 5);
+}
+
+#[test]
+fn alias_cycles_terminate() {
+    let figment = Figment::new()
+        .alias("a", "b")
+        .alias("b", "a")
+        .merge(Serialized::default("a", 1));
+
+    assert_eq!(figment.extract_inner::<u8>("b").unwrap(), 1);
+    assert_eq!(figment.warnings().len(), 1);
+}
@@ -0,0 +1,65 @@
+use serde::Deserialize;
+use figment::{Figment, error::Kind, util::map, value::Value};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    mode: Mode,
+    limit: Option<u32>,
+    servers: Vec<Server>,
+}
+
+#[derive(Debug, Deserialize)]
+enum Mode {
+    Fast,
+    Slow { delay: u8 },
+}
+
+#[derive(Debug, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn collects_every_error() {
+    let figment = Figment::new()
+        .merge(("mode", map!["Slow" => map!["delay" => -1]]))
+        .merge(("limit", "none"))
+        .merge(("servers", vec![map!["host" => "a"], map!["host" => "b"]]))
+        .merge(("extra", 1));
+
+    let errors: Vec<_> = figment.extract_all_errors::<Config>().unwrap_err().into_iter().collect();
+    let paths: Vec<_> = errors.iter().map(|e| e.path.join(".")).collect();
+    assert_eq!(paths, ["extra", "limit", "mode.Slow.delay", "servers.0", "servers.1"]);
+    assert!(matches!(errors[0].kind, Kind::UnknownField(..)));
+    assert!(matches!(errors[3].kind, Kind::MissingField(ref f) if f == "port"));
+}
+
+#[test]
+fn succeeds_without_errors() {
+    let figment = Figment::new()
+        .merge(("mode", "Fast"))
+        .merge(("servers", vec![map!["host" => Value::from("a"), "port" => Value::from(80)]]));
+
+    let config = figment.extract_all_errors::<Config>().unwrap();
+    assert!(matches!(config.mode, Mode::Fast));
+    assert_eq!(config.servers[0].host, "a");
+    assert_eq!(config.servers[0].port, 80);
+    assert!(config.limit.is_none());
+
+    let figment = figment.merge(("mode", map!["Slow" => map!["delay" => 5]]));
+    let config = figment.extract_all_errors::<Config>().unwrap();
+    assert!(matches!(config.mode, Mode::Slow { delay: 5 }));
+}
+
+#[test]
+fn stops_at_irreplaceable_values() {
+    let figment = Figment::from(("port", "eighty"));
+    let error = figment.extract_all_errors::<u16>().unwrap_err();
+    assert_eq!(error.count(), 1);
+
+    let figment = Figment::from(("ports", vec!["a", "b"]));
+    let error = figment.extract_all_errors::<std::collections::HashMap<String, Vec<u16>>>();
+    assert_eq!(error.unwrap_err().count(), 2);
+}
@@ -0,0 +1,47 @@
+use figment::{Figment, util::map};
+use figment::value::Value;
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct Listener {
+    name: Option<String>,
+    ports: Vec<u8>,
+}
+
+#[test]
+fn array_key_combines_by_field() {
+    let figment = Figment::new()
+        .array_key("server.listeners", "name")
+        .merge(("server", map!["listeners" => vec![
+            map!["name" => Value::from("a"), "ports" => vec![1].into()],
+            map!["name" => Value::from("b"), "ports" => vec![2].into()],
+        ]]))
+        .admerge(("server", map!["listeners" => vec![
+            map!["name" => Value::from("b"), "ports" => vec![3].into()],
+            map!["ports" => Value::from(vec![4])],
+        ]]));
+
+    let listeners: Vec<Listener> = figment.extract_inner("server.listeners").unwrap();
+    assert_eq!(listeners, [
+        Listener { name: Some("a".into()), ports: vec![1] },
+        Listener { name: Some("b".into()), ports: vec![2, 3] },
+        Listener { name: None, ports: vec![4] },
+    ]);
+
+    let focused = figment.focus("server").merge(("listeners", vec![map!["name" => "a"]]));
+    let listeners: Vec<Listener> = focused.extract_inner("listeners").unwrap();
+    assert_eq!(listeners.len(), 3);
+    assert_eq!(listeners[0].ports, [1]);
+}
+
+#[test]
+fn array_key_only_at_path() {
+    let figment = Figment::new()
+        .array_key("listeners", "name")
+        .merge(("other", vec![map!["name" => 1]]))
+        .merge(("other", vec![map!["name" => 2]]))
+        .merge(("listeners", vec![1, 2]))
+        .merge(("listeners", vec![map!["name" => 3]]));
+
+    assert_eq!(figment.extract_inner::<Vec<Value>>("other").unwrap().len(), 1);
+    assert_eq!(figment.extract_inner::<Vec<Value>>("listeners").unwrap().len(), 3);
+}
@@ -0,0 +1,98 @@
+use figment::{Figment, ArrayMerge, util::map};
+
+#[test]
+fn strategies_override_order() {
+    let figment = Figment::new()
+        .arrays(ArrayMerge::Replace)
+        .merge(("list", [1, 2]))
+        .admerge(("list", [3]));
+
+    assert_eq!(figment.extract_inner::<Vec<u8>>("list").unwrap(), [3]);
+
+    let figment = Figment::new()
+        .arrays(ArrayMerge::Replace)
+        .merge(("list", [1, 2]))
+        .adjoin(("list", [3]));
+
+    assert_eq!(figment.extract_inner::<Vec<u8>>("list").unwrap(), [1, 2]);
+}
+
+#[test]
+fn index_merges_nested_values() {
+    let figment = Figment::new()
+        .arrays(ArrayMerge::Index)
+        .merge(("servers", vec![map!["host" => "a", "port" => "80"], map!["host" => "b"]]))
+        .merge(("servers", vec![map!["port" => "8080"]]));
+
+    let servers: Vec<figment::value::Dict> = figment.extract_inner("servers").unwrap();
+    assert_eq!(servers.len(), 2);
+    assert_eq!(servers[0]["host"].as_str(), Some("a"));
+    assert_eq!(servers[0]["port"].as_str(), Some("8080"));
+}
+
+#[test]
+fn per_key_strategies_are_focused_and_rebuilt() {
+    let figment = Figment::new()
+        .arrays_at("app.plugins", ArrayMerge::Append)
+        .merge(("app", map!["plugins" => vec!["a"], "ports" => vec!["80"]]))
+        .merge(("app", map!["plugins" => vec!["b"], "ports" => vec!["443"]]));
+
+    assert_eq!(figment.extract_inner::<Vec<String>>("app.plugins").unwrap(), ["a", "b"]);
+    assert_eq!(figment.extract_inner::<Vec<String>>("app.ports").unwrap(), ["443"]);
+
+    let focused = figment.focus("app").merge(("plugins", ["c"]));
+    assert_eq!(focused.extract_inner::<Vec<String>>("plugins").unwrap(), ["a", "b", "c"]);
+
+    let figment = Figment::new()
+        .retain_layers(true)
+        .merge(("plugins", ["a"]))
+        .merge_with(("plugins", ["b"]), ArrayMerge::Prepend);
+
+    let rebuilt = figment.with_layers(&figment.layers());
+    assert_eq!(rebuilt.extract_inner::<Vec<String>>("plugins").unwrap(), ["b", "a"]);
+}
+
+#[test]
+fn strategies_are_traced_and_shadow_nothing() {
+    use figment::MergeDecision;
+
+    let strategies = [
+        (ArrayMerge::Append, MergeDecision::Concatenated),
+        (ArrayMerge::Prepend, MergeDecision::Concatenated),
+        (ArrayMerge::Index, MergeDecision::Combined),
+    ];
+
+    for (strategy, decision) in strategies.iter().copied() {
+        let figment = Figment::new()
+            .arrays(strategy)
+            .trace_merges(true)
+            .record_shadowed(true)
+            .merge(("list", [1, 2]))
+            .merge(("list", [3]));
+
+        assert!(figment.shadowed("list").is_empty(), "{:?}", strategy);
+        assert_eq!(figment.merge_trace()[1].decision, decision);
+    }
+
+    let figment = Figment::new()
+        .arrays(ArrayMerge::Replace)
+        .trace_merges(true)
+        .record_shadowed(true)
+        .merge(("list", [1, 2]))
+        .merge(("list", [3]));
+
+    let shadowed = figment.shadowed("list");
+    assert_eq!(shadowed.len(), 1);
+    assert_eq!(shadowed[0].0.clone().deserialize::<Vec<u8>>().unwrap(), [1, 2]);
+    assert_eq!(figment.merge_trace()[1].decision, MergeDecision::Replaced);
+
+    let figment = Figment::new()
+        .array_key("list", "id")
+        .trace_merges(true)
+        .record_shadowed(true)
+        .merge(("list", vec![map!["id" => 1]]))
+        .merge(("list", vec![map!["id" => 2]]));
+
+    assert!(figment.shadowed("list").is_empty());
+    assert_eq!(figment.merge_trace()[1].decision, MergeDecision::Combined);
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "async")]
+
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+
+use figment::{Figment, AsyncProvider, Metadata, Profile, Error};
+use figment::providers::Blocking;
+use figment::value::{Map, Dict};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    let waker = Arc::new(Unpark(thread::current())).into();
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(v) => return v,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// A future that is pending once, waking its task from another thread.
+struct Later(bool);
+
+impl Future for Later {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        let waker = cx.waker().clone();
+        thread::spawn(move || waker.wake());
+        Poll::Pending
+    }
+}
+
+struct Remote(Result<u16, &'static str>);
+
+impl AsyncProvider for Remote {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("Remote")
+    }
+
+    async fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        Later(false).await;
+        let port = self.0?;
+        Ok(Profile::Default.collect(figment::util::map!["port".into() => port.into()]))
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        Some("remote".into())
+    }
+}
+
+#[test]
+fn async_providers_combine_in_order() {
+    let figment = block_on(async {
+        Figment::new()
+            .merge(("port", 1))
+            .join_async(Remote(Ok(2))).await
+            .merge_async(Blocking(("name", "app"))).await
+    });
+
+    assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 1);
+    assert_eq!(figment.extract_inner::<String>("name").unwrap(), "app");
+    assert_eq!(figment.profile(), "default");
+
+    let figment = block_on(Figment::new().merge_async(Remote(Ok(3))));
+    assert_eq!(figment.profile(), "remote");
+    assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 3);
+
+    let metadata = figment.metadata().next().unwrap();
+    assert_eq!(metadata.name, "Remote");
+    assert!(metadata.provide_location.unwrap().file().ends_with("async-provider.rs"));
+}
+
+#[test]
+fn async_provider_errors_are_retained() {
+    let figment = block_on(Figment::new().merge_async(Remote(Err("unreachable"))));
+    let error = figment.extract_inner::<u16>("port").unwrap_err();
+    assert!(error.to_string().starts_with("unreachable in Remote"));
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use figment::{Figment, Jail, providers::{Format, Toml, Json, Serialized}};
+use figment::value::magic::ByteSize;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Config {
+    cache: ByteSize,
+    limits: Vec<ByteSize>,
+}
+
+fn bytes(value: &str) -> Option<u64> {
+    value.parse::<ByteSize>().ok().map(|b| b.bytes())
+}
+
+#[test]
+fn parses_human_friendly_strings() {
+    assert_eq!(bytes("0"), Some(0));
+    assert_eq!(bytes("4096"), Some(4096));
+    assert_eq!(bytes("12B"), Some(12));
+    assert_eq!(bytes("512k"), Some(512 * 1024));
+    assert_eq!(bytes("512K"), Some(512 * 1024));
+    assert_eq!(bytes("512kb"), Some(512_000));
+    assert_eq!(bytes("10MiB"), Some(10 << 20));
+    assert_eq!(bytes("10 mib"), Some(10 << 20));
+    assert_eq!(bytes("1.5GiB"), Some(3 << 29));
+    assert_eq!(bytes(".5k"), Some(512));
+    assert_eq!(bytes("2TB"), Some(2_000_000_000_000));
+    assert_eq!(bytes("1Pi"), Some(1 << 50));
+    assert_eq!(bytes("15EiB"), None);
+    assert_eq!(bytes("99999999PB"), None);
+
+    for invalid in &["", "k", ".k", "1..5k", "-1", "1k1", "1 2k", "ten"] {
+        assert_eq!(bytes(invalid), None, "{:?}", invalid);
+    }
+}
+
+#[test]
+fn deserializes_and_round_trips() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "cache = \"64MiB\"\nlimits = [1024, \"2k\", \"3 MB\"]")?;
+        let figment = Figment::from(Toml::file("App.toml"));
+        let config: Config = figment.extract()?;
+        assert_eq!(config.cache.bytes(), 64 << 20);
+        let limits: Vec<u64> = config.limits.iter().map(|b| b.bytes()).collect();
+        assert_eq!(limits, vec![1024, 2048, 3_000_000]);
+        assert!(figment.get_metadata(config.limits[1].tag()).is_some());
+
+        let serialized = Figment::from(Serialized::defaults(&config));
+        let again: Config = serialized.extract()?;
+        assert_eq!(again.cache, config.cache);
+        assert_eq!(again.limits, config.limits);
+
+        let json = serialized.serialize_to::<Json>()?;
+        assert!(json.contains("\"64MiB\""), "{}", json);
+        assert!(json.contains("\"1KiB\""), "{}", json);
+        assert!(json.contains("\"3MB\""), "{}", json);
+
+        jail.create_file("App.toml", "cache = -5\nlimits = []")?;
+        let err = Figment::from(Toml::file("App.toml")).extract::<Config>().unwrap_err();
+        assert!(err.to_string().contains("a size like"), "{}", err);
+        Ok(())
+    });
+}
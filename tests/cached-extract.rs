@@ -0,0 +1,109 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Deserialize;
+use figment::{Figment, Profile, Provider, Metadata, Error};
+use figment::value::{Map, Dict};
+use figment::providers::Serialized;
+
+static READS: AtomicUsize = AtomicUsize::new(0);
+
+struct Counting;
+
+impl Provider for Counting {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("counting")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        READS.fetch_add(1, Ordering::SeqCst);
+        Serialized::default("port", 80).data()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    port: u16,
+}
+
+#[test]
+fn repeated_extraction_is_consistent() {
+    let figment = Figment::from(Counting)
+        .merge(Serialized::default("port", 8080).profile("prod"));
+
+    for _ in 0..5 {
+        assert_eq!(figment.extract::<Config>().unwrap().port, 80);
+        assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 80);
+    }
+
+    assert_eq!(READS.load(Ordering::SeqCst), 1);
+
+    let prod = figment.clone().select("prod");
+    assert_eq!(prod.extract::<Config>().unwrap().port, 8080);
+    assert_eq!(figment.extract::<Config>().unwrap().port, 80);
+
+    let merged = figment.clone().merge(("port", 9090));
+    assert_eq!(merged.extract::<Config>().unwrap().port, 9090);
+    assert_eq!(figment.extract::<Config>().unwrap().port, 80);
+
+    let default = prod.select(Profile::Default);
+    assert_eq!(default.extract::<Config>().unwrap().port, 80);
+}
+
+#[test]
+fn invalidate_clears_cache_shared_by_clones() {
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Db {
+        url: String,
+    }
+
+    figment::Jail::expect_with(|jail| {
+        jail.set_env("DB_HOST", "one");
+
+        let figment = Figment::from(("db.url", "postgres://${DB_HOST}"))
+            .resolve_interpolation(true);
+
+        let clone = figment.clone();
+        assert_eq!(clone.extract_inner::<String>("db.url")?, "postgres://one");
+        assert_eq!(clone.extension::<Db>("db")?.url, "postgres://one");
+
+        jail.set_env("DB_HOST", "two");
+        assert_eq!(clone.extract_inner::<String>("db.url")?, "postgres://one");
+        assert_eq!(figment.extension::<Db>("db")?.url, "postgres://one");
+
+        figment.invalidate();
+        assert_eq!(clone.extract_inner::<String>("db.url")?, "postgres://two");
+        assert_eq!(clone.extension::<Db>("db")?.url, "postgres://two");
+        assert_eq!(figment.extension::<Db>("db")?.url, "postgres://two");
+        Ok(())
+    });
+}
+
+#[test]
+fn rebuilt_figments_do_not_share_cache() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct C {
+        name: String,
+    }
+
+    let figment = Figment::from(("name", "a"));
+    assert_eq!(figment.extract::<C>().unwrap().name, "a");
+
+    let rebuilt = figment.with_layers(&figment.layers());
+    assert!(rebuilt.extract::<C>().is_err());
+    assert!(rebuilt.extract_inner::<String>("name").is_err());
+
+    let empty = figment.with_layers(&[]);
+    assert!(empty.extract::<C>().is_err());
+    assert!(empty.extract_inner::<String>("name").is_err());
+
+    let replaced = figment.clone().replace_layer(0, Serialized::default("name", "b"));
+    assert!(replaced.extract::<C>().is_err());
+    assert!(replaced.extract_inner::<String>("name").is_err());
+    assert_eq!(figment.extract::<C>().unwrap().name, "a");
+
+    let figment = Figment::new().retain_layers(true).merge(("name", "a"));
+    assert_eq!(figment.extract::<C>().unwrap().name, "a");
+    let replaced = figment.clone().replace_layer(0, Serialized::default("name", "b"));
+    assert_eq!(replaced.extract::<C>().unwrap().name, "b");
+    assert_eq!(figment.extract::<C>().unwrap().name, "a");
+}
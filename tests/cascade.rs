@@ -0,0 +1,75 @@
+use figment::{Figment, Profile, providers::{Format, Toml}};
+use figment::value::Value;
+
+const TOML: &str = r#"
+    [default]
+    a = 1
+    b = { x = 1, y = 1 }
+    c = [1]
+    shadow = { inner = 1 }
+
+    [one]
+    a = 2
+    b = { y = 2, z = 2 }
+    shadow = 2
+
+    [two]
+    b = { z = 3 }
+    d = "two"
+
+    [global]
+    e = { g = true }
+    b = { w = 4 }
+"#;
+
+const PATHS: &[&str] = &[
+    "a", "b", "b.x", "b.y", "b.z", "b.w", "c", "c.0", "d", "e", "e.g",
+    "shadow", "shadow.inner", "missing", "b.missing",
+];
+
+fn assert_consistent(figment: &Figment) {
+    let merged: Value = figment.extract().unwrap();
+    for path in PATHS {
+        let found = figment.find_value(path).ok();
+        assert_eq!(found, merged.find_ref(path).cloned(), "{} in {}", path, figment.profile());
+        assert_eq!(figment.find_profile(path).is_some(), found.is_some());
+        if let Some(found) = found {
+            let extracted: Value = figment.extract_inner(path).unwrap();
+            assert_eq!(found, extracted);
+            assert_eq!(figment.find_metadata(path), figment.get_metadata(found.tag()));
+        }
+    }
+}
+
+#[test]
+fn lookups_match_extraction() {
+    let figment = Figment::from(Toml::string(TOML).nested());
+    let levels = Profile::leveled(&["one", "two"]);
+    for profile in &["default", "global", "one", "two", "none"] {
+        assert_consistent(&figment.clone().select(*profile));
+    }
+
+    assert_consistent(&figment.clone().select_level(&levels, 0));
+    assert_consistent(&figment.clone().select_level(&levels, 1));
+
+    let levels = Profile::leveled(&["two", "one", "two"]);
+    assert_consistent(&figment.clone().select_level(&levels, 1));
+    assert_consistent(&figment.select_level(&levels, 2));
+}
+
+#[test]
+fn find_profile() {
+    let figment = Figment::from(Toml::string(TOML).nested());
+    let one = figment.clone().select("one");
+    assert_eq!(one.find_profile("a"), Some("one".into()));
+    assert_eq!(one.find_profile("b"), Some(Profile::Global));
+    assert_eq!(one.find_profile("b.x"), Some(Profile::Default));
+    assert_eq!(one.find_profile("b.y"), Some("one".into()));
+    assert_eq!(one.find_profile("shadow"), Some("one".into()));
+    assert_eq!(one.find_profile("shadow.inner"), None);
+
+    let two = figment.select_level(&Profile::leveled(&["one", "two"]), 1);
+    assert_eq!(two.find_profile("a"), Some("one".into()));
+    assert_eq!(two.find_profile("b.z"), Some("two".into()));
+    assert_eq!(two.find_profile("d"), Some("two".into()));
+}
@@ -0,0 +1,40 @@
+use clap::{Arg, ArgAction, Command};
+use figment::{Figment, providers::{CliArgs, Serialized}};
+
+fn command() -> Command {
+    Command::new("app")
+        .arg(Arg::new("color").long("no-color").action(ArgAction::SetFalse))
+        .arg(Arg::new("size").long("size").num_args(2))
+        .arg(Arg::new("tags").long("tag").action(ArgAction::Append))
+        .arg(Arg::new("port").long("port"))
+        .subcommand(Command::new("serve").arg(Arg::new("workers").long("workers")))
+}
+
+#[test]
+fn values_actions_and_subcommands() {
+    let args = ["app", "--no-color", "--size", "1", "2", "--tag", "a", "serve", "--workers", "4"];
+    let figment = Figment::from(CliArgs::try_from_args(command(), args).unwrap());
+
+    assert!(!figment.extract_inner::<bool>("color").unwrap());
+    assert_eq!(figment.extract_inner::<Vec<u8>>("size").unwrap(), [1, 2]);
+    assert_eq!(figment.extract_inner::<Vec<String>>("tags").unwrap(), ["a"]);
+    assert_eq!(figment.extract_inner::<u16>("serve.workers").unwrap(), 4);
+    assert!(!figment.contains("port"));
+}
+
+#[test]
+fn defaults_are_not_emitted() {
+    let figment = Figment::from(Serialized::default("color", true))
+        .merge(("size", [3, 4]))
+        .merge(CliArgs::try_from_args(command(), ["app"]).unwrap());
+
+    assert!(figment.extract_inner::<bool>("color").unwrap());
+    assert_eq!(figment.extract_inner::<Vec<u8>>("size").unwrap(), [3, 4]);
+}
+
+#[test]
+fn errors_name_the_flag() {
+    let cli = CliArgs::try_from_args(command(), ["app", "serve", "--workers", "many"]).unwrap();
+    let error = Figment::from(cli).extract_inner::<u16>("serve.workers").unwrap_err();
+    assert!(error.to_string().contains("key \"--serve.workers\" in command-line argument(s)"));
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Deserializer};
+use figment::{Figment, providers::{Format, Toml, Yaml, Env}, Jail};
+
+fn year<'de, D: Deserializer<'de>>(de: D) -> Result<u16, D::Error> {
+    let string = String::deserialize(de)?;
+    string[..4].parse().map_err(serde::de::Error::custom)
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "$__toml_private_Datetime")]
+struct Datetime {
+    #[serde(rename = "$__toml_private_datetime")]
+    value: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    #[serde(deserialize_with = "year")]
+    year: u16,
+    date: String,
+    datetime: Datetime,
+}
+
+#[test]
+fn datetimes_are_strings() {
+    Jail::expect_with(|jail| {
+        let toml = Figment::from(Toml::string("year = 1979-05-27\ndate = 1979-05-27T07:32:00Z\ndatetime = 2000-01-01"));
+        let config: Config = toml.extract()?;
+        assert_eq!(config, Config {
+            year: 1979,
+            date: "1979-05-27T07:32:00Z".into(),
+            datetime: Datetime { value: "2000-01-01".into() },
+        });
+
+        let yaml = Figment::from(Yaml::string("year: 1979-05-27\ndate: 1979-05-27T07:32:00Z\ndatetime: 2000-01-01"));
+        assert_eq!(yaml.extract::<Config>()?, config);
+
+        jail.set_env("APP_YEAR", "1979-05-27");
+        jail.set_env("APP_DATE", "1979-05-27T07:32:00Z");
+        jail.set_env("APP_DATETIME", "2000-01-01");
+        let env = Figment::from(Env::prefixed("APP_"));
+        assert_eq!(env.extract::<Config>()?, config);
+        Ok(())
+    });
+}
+
+#[test]
+fn toml_datetimes_are_values() {
+    use figment::value::{Value, Offset};
+
+    let toml = Figment::from(Toml::string("date = 1979-05-27T07:32:00.5+01:30\ntime = 07:32:00"));
+    let datetime = toml.find_value("date").unwrap().to_datetime().unwrap();
+    assert_eq!(datetime.date.unwrap().day, 27);
+    assert_eq!(datetime.time.unwrap().nanosecond, 500_000_000);
+    assert_eq!(datetime.offset, Some(Offset::Custom { minutes: 90 }));
+    assert_eq!(toml.extract_inner::<String>("date").unwrap(), "1979-05-27T07:32:00.5+01:30");
+
+    let time = toml.find_value("time").unwrap();
+    assert!(matches!(time, Value::Datetime(..)));
+    assert!(time.to_datetime().unwrap().date.is_none());
+    assert_eq!(toml.extract_inner::<Datetime>("time").unwrap().value, "07:32:00");
+
+    let yaml = Figment::from(Yaml::string("date: 1979-05-27"));
+    assert!(yaml.find_value("date").unwrap().to_datetime().is_none());
+}
@@ -0,0 +1,25 @@
+use figment::{Figment, Profile, providers::Serialized};
+
+#[test]
+fn deprecations_follow_extraction() {
+    let figment = Figment::new()
+        .merge(Serialized::default("old.key", 1))
+        .merge(Serialized::default("old.key", 2).profile("prod"))
+        .merge(Serialized::global("other", 3))
+        .deprecate("old.key", "")
+        .deprecate("other", "gone")
+        .deprecate("other", "use `new`")
+        .select("prod");
+
+    let deprecations = figment.deprecations();
+    assert_eq!(deprecations.len(), 2);
+    assert_eq!(deprecations[0].profile, Profile::new("prod"));
+    assert!(deprecations[0].to_string().starts_with("`old.key` is deprecated (in tests/deprecate.rs:"));
+    assert_eq!(deprecations[1].key, "other");
+    assert_eq!(deprecations[1].note, "use `new`");
+    assert_eq!(deprecations[1].profile, Profile::Global);
+
+    let focused = figment.focus("old");
+    let keys: Vec<_> = focused.deprecations().into_iter().map(|d| d.key).collect();
+    assert_eq!(keys, ["key"]);
+}
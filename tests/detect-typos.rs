@@ -0,0 +1,34 @@
+use figment::{Figment, Profile, providers::Serialized};
+
+#[test]
+fn typos_reported_across_providers_only() {
+    let figment = Figment::new()
+        .detect_typos(true)
+        .merge(Serialized::default("timeout", 1))
+        .merge(Serialized::default("port", 1))
+        .merge(Serialized::default("timeout", 2))
+        .merge(Serialized::default("time-out", 3))
+        .merge(Serialized::default("post", 4))
+        .merge(Serialized::default("nested.retries", 5))
+        .merge(Serialized::default("nested.retires", 6))
+        .merge(Serialized::default("retires", 7).profile("other"));
+
+    let paths: Vec<_> = figment.warnings().iter().map(|w| w.path.join(".")).collect();
+    assert_eq!(paths, ["time-out", "nested.retires"]);
+
+    let warning = &figment.warnings()[1];
+    assert_eq!(warning.profile, Some(Profile::Default));
+    assert!(warning.to_string().contains("`retires` is similar to `retries`"));
+}
+
+#[test]
+fn typos_disabled_by_default() {
+    let figment = Figment::new()
+        .merge(Serialized::default("timeout", 1))
+        .merge(Serialized::default("time_out", 2));
+
+    assert!(figment.warnings().is_empty());
+
+    let figment = figment.detect_typos(true).merge(Serialized::default("TimeOut", 3));
+    assert_eq!(figment.warnings().len(), 2);
+}
@@ -0,0 +1,52 @@
+use figment::{Figment, ChangeKind, providers::{Format, Serialized, Toml}};
+use figment::util::map;
+use figment::value::Value;
+
+#[test]
+fn diff_reports_leaf_changes() {
+    let old = Figment::new()
+        .merge(Serialized::default("server", map!["port" => Value::from(80), "host" => Value::from("a")]))
+        .merge(Serialized::default("plugins", vec!["auth"]))
+        .merge(Serialized::default("empty", figment::value::Dict::new()))
+        .merge(Serialized::default("size", 10u8));
+
+    let new = Figment::new()
+        .merge(Serialized::default("server", "a:80"))
+        .merge(Serialized::default("plugins", vec!["auth", "tls"]))
+        .merge(Serialized::default("size", 10i64));
+
+    assert!(old.diff(&old).unwrap().is_empty());
+
+    let changes = old.diff(&new).unwrap();
+    let keys: Vec<_> = changes.iter().map(|c| (c.key.as_str(), c.kind)).collect();
+    assert_eq!(keys, [
+        ("empty", ChangeKind::Removed),
+        ("plugins", ChangeKind::Changed),
+        ("server", ChangeKind::Added),
+        ("server.host", ChangeKind::Removed),
+        ("server.port", ChangeKind::Removed),
+    ]);
+
+    let plugins = &changes[1];
+    assert_eq!(plugins.new.as_ref().unwrap().as_array().unwrap().len(), 2);
+    assert!(plugins.old_metadata.is_some() && plugins.new_metadata.is_some());
+    assert!(changes[0].new.is_none() && changes[0].new_metadata.is_none());
+
+    let reverse = new.diff(&old).unwrap();
+    assert_eq!(reverse.len(), changes.len());
+    assert_eq!(reverse[0].kind, ChangeKind::Added);
+}
+
+#[test]
+fn diff_uses_selected_profiles() {
+    let figment = Figment::new()
+        .merge(Serialized::default("port", 80))
+        .merge(Serialized::default("port", 443).profile("prod"));
+
+    let changes = figment.diff(&figment.clone().select("prod")).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].new.as_ref().and_then(|v| v.to_i128()), Some(443));
+
+    let broken = figment.clone().merge(Toml::file("missing.toml").required(true));
+    assert!(figment.diff(&broken).is_err());
+}
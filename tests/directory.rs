@@ -0,0 +1,27 @@
+use figment::{Figment, Jail, providers::Directory};
+
+#[test]
+fn secrets_are_read_verbatim() {
+    Jail::expect_with(|jail| {
+        let dir = jail.create_dir("secrets")?;
+        jail.create_file(dir.join("pin"), "00123\n")?;
+        jail.create_file(dir.join("flag"), "true")?;
+        jail.create_file(dir.join("list"), "[a, b]\r\n")?;
+        jail.create_file(dir.join("password"), "  hunter2 \n\n")?;
+        jail.create_file(dir.join("empty"), "")?;
+
+        let figment = Figment::from(Directory::new("secrets"));
+        assert_eq!(figment.extract_inner::<String>("pin")?, "00123");
+        assert_eq!(figment.extract_inner::<String>("flag")?, "true");
+        assert_eq!(figment.extract_inner::<String>("list")?, "[a, b]");
+        assert_eq!(figment.extract_inner::<String>("password")?, "  hunter2 \n");
+        assert_eq!(figment.extract_inner::<String>("empty")?, "");
+
+        let figment = Figment::from(Directory::new("secrets").parse_values(true));
+        assert_eq!(figment.extract_inner::<u32>("pin")?, 123);
+        assert!(figment.extract_inner::<bool>("flag")?);
+        assert_eq!(figment.extract_inner::<Vec<String>>("list")?, ["a", "b"]);
+        assert_eq!(figment.extract_inner::<String>("password")?, "hunter2");
+        Ok(())
+    });
+}
@@ -0,0 +1,70 @@
+use figment::{Figment, Jail, providers::{Format, Toml, Json, Discover}};
+
+#[test]
+fn nearer_files_take_precedence() {
+    Jail::expect_with(|jail| {
+        jail.create_dir("repo/.git")?;
+        jail.create_dir("repo/a/b/c")?;
+        jail.create_file("app.toml", "level = \"outside\"\noutside = true")?;
+        jail.create_file("repo/app.toml", "level = \"root\"\nroot = true")?;
+        jail.create_file("repo/a/b/app.toml", "level = \"b\"\nb = true")?;
+        jail.change_dir("repo/a/b/c")?;
+
+        let discover = Toml::discover("app.toml").marker(".git");
+        let candidates = discover.candidates();
+        assert_eq!(candidates.len(), 4);
+        assert!(candidates[0].ends_with("repo/a/b/c/app.toml"));
+        assert!(candidates[3].ends_with("repo/app.toml"));
+
+        let files = discover.files();
+        assert_eq!(files.len(), 2);
+        assert!(files[0].ends_with("repo/app.toml"));
+        assert!(files[1].ends_with("repo/a/b/app.toml"));
+
+        let figment = Figment::from(&discover).merge(&discover);
+        assert_eq!(figment.extract_inner::<String>("level")?, "b");
+        assert!(figment.extract_inner::<bool>("root")?);
+        assert!(figment.extract_inner::<bool>("b")?);
+        assert!(!figment.contains("outside"));
+
+        let path = |key: &str| figment.find_metadata(key)
+            .and_then(|m| m.source.as_ref())
+            .and_then(|s| s.file_path())
+            .map(|p| p.to_path_buf())
+            .unwrap();
+
+        assert!(path("root").ends_with("repo/app.toml"));
+        assert!(path("level").ends_with("repo/a/b/app.toml"));
+        Ok(())
+    });
+}
+
+#[test]
+fn markers_and_profiles() {
+    Jail::expect_with(|jail| {
+        jail.create_dir("ws/pkg")?;
+        jail.create_file("ws/Cargo.lock", "")?;
+        jail.create_file("ws/cfg.json", r#"{ "default": { "port": 80 }, "prod": { "port": 443 } }"#)?;
+        jail.create_file("ws/pkg/cfg.json", r#"{ "prod": { "port": 8443 } }"#)?;
+
+        let discover = Json::discover("cfg.json")
+            .marker(".git")
+            .marker("Cargo.lock")
+            .from("ws/pkg")
+            .nested();
+
+        let figment = Figment::from(discover);
+        assert_eq!(figment.extract_inner::<u16>("port")?, 80);
+        assert_eq!(figment.clone().select("prod").extract_inner::<u16>("port")?, 8443);
+
+        let figment = Figment::from(Json::discover("cfg.json").from("ws/pkg").profile("debug"));
+        assert!(!figment.contains("port"));
+        assert_eq!(figment.select("debug").extract_inner::<u16>("prod.port")?, 8443);
+
+        let discover = Discover::<Toml>::new("missing.toml").marker("Cargo.lock").from("ws/pkg");
+        assert_eq!(discover.candidates().len(), 2);
+        let error = Figment::from(discover.required(true)).extract::<figment::value::Dict>().unwrap_err();
+        assert!(error.to_string().contains("ws/pkg/missing.toml"), "{}", error);
+        Ok(())
+    });
+}
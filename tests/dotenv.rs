@@ -0,0 +1,72 @@
+use figment::{Figment, Jail, Profile, Provider, providers::{DotEnv, Env}};
+
+#[test]
+fn quoting_comments_and_escapes() {
+    Jail::expect_with(|jail| {
+        jail.create_file(".env", concat!(
+            "# comment\n",
+            "\n",
+            "PLAIN = value with spaces   # comment\n",
+            "HASH=a#b\n",
+            "SINGLE='literal \\n # not a comment'\n",
+            "DOUBLE=\"tab\\tquote\\\" dollar\\$\" # comment\n",
+            "MULTI=\"line one\n",
+            "line two\"\n",
+            "export EXPORTED=1\n",
+            "exporter=2\n",
+            "EMPTY=\n",
+            "PLAIN=overridden\n",
+        ))?;
+
+        let figment = Figment::from(DotEnv::new());
+        assert_eq!(figment.extract_inner::<String>("plain")?, "overridden");
+        assert_eq!(figment.extract_inner::<String>("hash")?, "a#b");
+        assert_eq!(figment.extract_inner::<String>("single")?, "literal \\n # not a comment");
+        assert_eq!(figment.extract_inner::<String>("double")?, "tab\tquote\" dollar$");
+        assert_eq!(figment.extract_inner::<String>("multi")?, "line one\nline two");
+        assert_eq!(figment.extract_inner::<u8>("exported")?, 1);
+        assert_eq!(figment.extract_inner::<u8>("exporter")?, 2);
+        assert!(figment.contains("empty"));
+        Ok(())
+    });
+}
+
+#[test]
+fn invalid_lines() {
+    Jail::expect_with(|jail| {
+        for (contents, line) in [("A=1\nB", 2), ("A=\"open\n", 1), ("A B=1", 1), ("A='x' y", 1)] {
+            jail.create_file(".env", contents)?;
+            let error = Figment::from(DotEnv::new()).extract::<figment::value::Dict>().unwrap_err();
+            assert!(error.to_string().contains(&format!("invalid line {} in `.env`", line)));
+        }
+
+        Ok(())
+    });
+}
+
+#[test]
+fn env_machinery_and_profiles() {
+    Jail::expect_with(|jail| {
+        jail.set_env("APP_FROM_PROCESS", "process");
+        jail.create_dir("config")?;
+        jail.create_file("config/app.env", "APP_DB__PORT=5432\nAPP_NAME=app\nOTHER=1")?;
+        jail.create_file("config/app.env.staging", "APP_DB__PORT=6543")?;
+        jail.create_file("config/app.env.release", "APP_NAME=release")?;
+
+        let env = Env::prefixed("APP_").split("__");
+        let provider = DotEnv::file("config/app.env").env(env).profiles(true);
+        assert_eq!(provider.metadata().name, "`APP_` dotenv file");
+
+        let data = provider.data()?;
+        assert_eq!(data.len(), 3);
+        assert!(data[&Profile::Default].get("from_process").is_none());
+        assert!(data[&Profile::Default].get("other").is_none());
+
+        let figment = Figment::from(provider).select("staging");
+        assert_eq!(figment.extract_inner::<u16>("db.port")?, 6543);
+        assert_eq!(figment.extract_inner::<String>("name")?, "app");
+        assert_eq!(figment.select("release").extract_inner::<String>("name")?, "release");
+        assert!(std::env::var("APP_NAME").is_err());
+        Ok(())
+    });
+}
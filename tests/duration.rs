@@ -0,0 +1,80 @@
+use std::time::Duration as StdDuration;
+
+use serde::{Deserialize, Serialize};
+use figment::{Figment, Jail, providers::{Env, Format, Toml, Serialized}};
+use figment::value::magic::Duration;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Config {
+    timeout: Duration,
+    #[serde(default)]
+    retry: Option<Duration>,
+}
+
+fn duration(value: &str) -> Option<StdDuration> {
+    value.parse::<Duration>().ok().map(|d| d.into_inner())
+}
+
+#[test]
+fn parses_human_friendly_strings() {
+    assert_eq!(duration("30s"), Some(StdDuration::from_secs(30)));
+    assert_eq!(duration("5m"), Some(StdDuration::from_secs(300)));
+    assert_eq!(duration("1h30m"), Some(StdDuration::from_secs(5400)));
+    assert_eq!(duration("1d"), Some(StdDuration::from_secs(86400)));
+    assert_eq!(duration(" 1h 2m 3s "), Some(StdDuration::from_secs(3723)));
+    assert_eq!(duration("1.5h"), Some(StdDuration::from_secs(5400)));
+    assert_eq!(duration(".5s"), Some(StdDuration::from_millis(500)));
+    assert_eq!(duration("10ms"), Some(StdDuration::from_millis(10)));
+    assert_eq!(duration("10us"), Some(StdDuration::from_micros(10)));
+    assert_eq!(duration("10µs"), Some(StdDuration::from_micros(10)));
+    assert_eq!(duration("10ns"), Some(StdDuration::from_nanos(10)));
+    assert_eq!(duration("45"), Some(StdDuration::from_secs(45)));
+    assert_eq!(duration("0"), Some(StdDuration::ZERO));
+
+    for invalid in &["", "s", "10x", "1h30", "-5s", "1..5s", ".s", "NaN", "inf"] {
+        assert_eq!(duration(invalid), None, "{:?}", invalid);
+    }
+}
+
+#[test]
+fn deserializes_from_strings_and_numbers() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "timeout = 90\nretry = 0.25")?;
+        let config: Config = Figment::from(Toml::file("App.toml")).extract()?;
+        assert_eq!(*config.timeout, StdDuration::from_secs(90));
+        assert_eq!(config.retry.map(|d| *d), Some(StdDuration::from_millis(250)));
+
+        jail.set_env("APP_TIMEOUT", "2m");
+        let figment = Figment::from(Toml::file("App.toml")).merge(Env::prefixed("APP_"));
+        let config: Config = figment.extract()?;
+        assert_eq!(*config.timeout, StdDuration::from_secs(120));
+        assert!(figment.get_metadata(config.timeout.tag()).unwrap().name.contains("APP_"));
+
+        jail.set_env("APP_TIMEOUT", "soon");
+        let figment = Figment::from(Toml::file("App.toml")).merge(Env::prefixed("APP_"));
+        let err = figment.extract::<Config>().unwrap_err();
+        assert!(err.to_string().contains("a duration like"), "{}", err);
+
+        jail.create_file("App.toml", "timeout = -1")?;
+        assert!(Figment::from(Toml::file("App.toml")).extract::<Config>().is_err());
+        Ok(())
+    });
+}
+
+#[test]
+fn round_trips_and_preserves_tag() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "timeout = \"1h30m\"")?;
+        let figment = Figment::from(Toml::file("App.toml"));
+        let config: Config = figment.extract()?;
+
+        let figment = figment.merge(Serialized::defaults(&config));
+        let again: Config = figment.extract()?;
+        assert_eq!(again.timeout, config.timeout);
+        assert_eq!(again.timeout.tag(), config.timeout.tag());
+
+        let toml = Figment::from(Serialized::defaults(&config)).serialize_to::<Toml>()?;
+        assert_eq!(toml, "timeout = \"1h30m\"\n");
+        Ok(())
+    });
+}
@@ -0,0 +1,14 @@
+use figment::{Figment, Source, providers::{embedded, Toml}};
+
+#[test]
+fn embedded_file_is_compiled_in() {
+    let figment = Figment::from(embedded!(Toml, "../Cargo.toml"));
+    assert_eq!(figment.extract_inner::<String>("package.name").unwrap(), "figment");
+
+    let metadata = figment.find_metadata("package.name").unwrap();
+    assert_eq!(metadata.name, "TOML file (compiled-in)");
+    assert_eq!(metadata.source, Some(Source::Embedded("../Cargo.toml".into())));
+
+    let (raw, format) = figment.raw_inner("package.name").unwrap();
+    assert_eq!((raw.as_str(), format), ("\"figment\"", "TOML"));
+}
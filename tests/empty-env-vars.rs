@@ -1,4 +1,4 @@
-use figment::{Figment, providers::Env};
+use figment::{Figment, providers::{Env, Serialized}};
 
 #[derive(serde::Deserialize)]
 struct Config {
@@ -58,3 +58,28 @@ fn empty_env_vars() {
         Ok(())
     });
 }
+
+#[test]
+fn ignore_empty_and_trim_values() {
+    figment::Jail::expect_with(|jail| {
+        jail.set_env("APP_FOO", "  ");
+        jail.set_env("APP_BAR", "");
+        jail.set_env("APP_PATH", "\t/usr/local/bin ");
+
+        let base = || Figment::from(Serialized::default("foo", "file"))
+            .merge(Serialized::default("bar", "file"));
+
+        let figment = base().merge(Env::prefixed("APP_").ignore_empty());
+        assert_eq!(figment.extract_inner::<String>("bar")?, "file");
+        assert!(figment.extract::<Config>().is_err());
+
+        let figment = base().merge(Env::prefixed("APP_").ignore_empty().trim_values());
+        assert_eq!(figment.extract::<Config>()?.foo, "file");
+        assert_eq!(figment.extract_inner::<String>("bar")?, "file");
+        assert_eq!(figment.extract_inner::<String>("path")?, "/usr/local/bin");
+
+        let figment = base().merge(Env::prefixed("APP_").trim_values().split("_"));
+        assert!(figment.extract::<Config>().is_err());
+        Ok(())
+    });
+}
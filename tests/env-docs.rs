@@ -0,0 +1,48 @@
+use serde::Deserialize;
+use figment::{Figment, providers::{Env, Serialized}, value::{Dict, Value}};
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Limits {
+    max_conns: u32,
+    hosts: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Config {
+    name: String,
+    limits: Limits,
+    level: Option<u8>,
+}
+
+#[test]
+fn env_docs_respect_split_and_filters() {
+    let docs = Env::prefixed("APP_").document::<Config>();
+    assert_eq!(docs.names().collect::<Vec<_>>(), ["APP_LEVEL", "APP_LIMITS", "APP_NAME"]);
+
+    // `max_conns` can't be set when splitting at `_`.
+    let docs = Env::prefixed("APP_").split("_").document::<Config>();
+    assert_eq!(docs.names().collect::<Vec<_>>(), ["APP_LEVEL", "APP_LIMITS_HOSTS", "APP_NAME"]);
+
+    let docs = Env::prefixed("APP_").split("__").only(&["name", "limits.max_conns"])
+        .document::<Config>();
+
+    assert_eq!(docs.names().collect::<Vec<_>>(), ["APP_LIMITS__MAX_CONNS", "APP_NAME"]);
+
+    let docs = Env::raw().only(&["name"]).document::<Config>();
+    assert_eq!(docs.names().collect::<Vec<_>>(), ["NAME"]);
+}
+
+#[test]
+fn env_docs_serialize() {
+    let docs = Env::prefixed("APP_").split("__").document::<Config>();
+    let vars: Vec<Dict> = Figment::from(Serialized::global("vars", &docs))
+        .extract_inner("vars")
+        .unwrap();
+
+    assert_eq!(vars.len(), 4);
+    assert_eq!(vars[1]["name"], Value::from("APP_LIMITS__HOSTS"));
+    assert_eq!(vars[1]["key"], Value::from("limits.hosts"));
+    assert_eq!(vars[1]["type"], Value::from("a sequence of a string"));
+}
@@ -0,0 +1,49 @@
+use figment::{Jail, providers::Env};
+
+fn keys(env: &Env) -> Vec<String> {
+    let mut keys: Vec<_> = env.iter().map(|(k, _)| k.to_string()).collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn globs_match_ignoring_case() {
+    Jail::expect_with(|jail| {
+        jail.set_env("APP_DB_PASSWORD", 1);
+        jail.set_env("APP_DB_HOST", 2);
+        jail.set_env("APP_CACHE_PASSWORD_FILE", 3);
+        jail.set_env("APP_X", 4);
+
+        let env = Env::prefixed("APP_").exclude_globs(["*PASSWORD*"]);
+        assert_eq!(keys(&env), ["db_host", "x"]);
+
+        let env = Env::prefixed("APP_").include_globs(["Db_*", "?"]);
+        assert_eq!(keys(&env), ["db_host", "db_password", "x"]);
+
+        let env = Env::prefixed("APP_").include_globs(["*_*_*"]);
+        assert_eq!(keys(&env), ["cache_password_file"]);
+
+        let env = Env::prefixed("APP_").include_globs(Vec::<String>::new());
+        assert!(keys(&env).is_empty());
+        Ok(())
+    });
+}
+
+#[test]
+fn globs_match_key_paths() {
+    Jail::expect_with(|jail| {
+        jail.set_env("APP_DB_PASSWORD", 1);
+        jail.set_env("APP_DB_MAIN_PASSWORD", 2);
+        jail.set_env("APP_PASSWORD", 3);
+
+        let env = Env::prefixed("APP_").split("_").exclude_globs(["*.password"]);
+        assert_eq!(keys(&env), ["db.main.password", "password"]);
+
+        let env = Env::prefixed("APP_").split("_").exclude_globs(["**.password"]);
+        assert!(keys(&env).is_empty());
+
+        let env = Env::prefixed("APP_").split("_").include_globs(["d?.**"]);
+        assert_eq!(keys(&env), ["db.main.password", "db.password"]);
+        Ok(())
+    });
+}
@@ -0,0 +1,58 @@
+use serde::{Serialize, Deserialize};
+use figment::{Figment, Jail, providers::{Env, Serialized}};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Item {
+    name: String,
+    weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Config {
+    plain: String,
+    looks_bool: String,
+    looks_num: String,
+    padded: String,
+    tricky: String,
+    empty: String,
+    letter: char,
+    quote: char,
+    flag: bool,
+    negative: i64,
+    float: f64,
+    list: Vec<String>,
+    items: Vec<Item>,
+    missing: Option<u8>,
+}
+
+#[test]
+fn export_env_round_trips() {
+    let config = Config {
+        plain: "hello world".into(),
+        looks_bool: "true".into(),
+        looks_num: "1.5".into(),
+        padded: "  spaced  ".into(),
+        tricky: "a \"quoted\" \\ [list], {dict}\n\ttab".into(),
+        empty: "".into(),
+        letter: 'x',
+        quote: '\'',
+        flag: false,
+        negative: -42,
+        float: 1.0,
+        list: vec!["a,b".into(), "".into(), "]".into()],
+        items: vec![Item { name: "first item".into(), weight: 0.25 }],
+        missing: None,
+    };
+
+    Jail::expect_with(|jail| {
+        let vars = Figment::from(Serialized::defaults(&config)).export_env("EXPORT_")?;
+        jail.clear_env();
+        for (name, value) in &vars {
+            jail.set_env(name, value);
+        }
+
+        let exported: Config = Figment::from(Env::prefixed("EXPORT_")).extract()?;
+        assert_eq!(exported, config);
+        Ok(())
+    });
+}
@@ -0,0 +1,76 @@
+use figment::{Figment, ExportOptions, util::map, value::Value};
+
+fn figment() -> Figment {
+    Figment::from(("db", map!["user" => "admin", "password" => "hunter2"]))
+        .merge(("cache.password", "swordfish"))
+        .merge(("cache.tls.key", "pem"))
+        .merge(("password", "root"))
+        .merge(("servers", vec![map!["api_token" => "a"], map!["api_token" => "b"]]))
+}
+
+fn find(value: &Value, path: &str) -> String {
+    let value = path.split('.').fold(value, |value, key| match value {
+        Value::Array(_, array) => &array[key.parse::<usize>().unwrap()],
+        _ => value.find_ref(key).unwrap(),
+    });
+
+    value.as_str().unwrap().to_string()
+}
+
+#[test]
+fn globs() {
+    let value = figment().export(&ExportOptions::new().redact(["*.password"])).unwrap();
+    assert_eq!(find(&value, "db.password"), "<redacted>");
+    assert_eq!(find(&value, "cache.password"), "<redacted>");
+    assert_eq!(find(&value, "password"), "root");
+
+    let value = figment().export(&ExportOptions::new().redact(["**.password"])).unwrap();
+    assert_eq!(find(&value, "password"), "<redacted>");
+    assert_eq!(find(&value, "db.user"), "admin");
+
+    let value = figment().export(&ExportOptions::new().redact(["servers.*.*token", "cache.tls"])).unwrap();
+    assert_eq!(find(&value, "servers.0.api_token"), "<redacted>");
+    assert_eq!(find(&value, "servers.1.api_token"), "<redacted>");
+    assert_eq!(find(&value, "cache.tls"), "<redacted>");
+    assert_eq!(find(&value, "cache.password"), "swordfish");
+
+    let value = figment().export(&ExportOptions::new().redact(["servers.?.api_*", "d?"])).unwrap();
+    assert_eq!(find(&value, "servers.1.api_token"), "<redacted>");
+    assert_eq!(find(&value, "db"), "<redacted>");
+}
+
+#[test]
+fn redaction_wins_over_encryption() {
+    let options = ExportOptions::new()
+        .encrypt(["**"])
+        .redact(["password"])
+        .encrypt_with(|plain: &str| format!("enc({})", plain));
+
+    let value = figment().focus("db").export(&options).unwrap();
+    assert_eq!(find(&value, "password"), "<redacted>");
+    assert_eq!(find(&value, "user"), "enc(\"admin\")");
+}
+
+#[test]
+fn encrypted_values_round_trip() {
+    let figment = Figment::from(("pin", "123"))
+        .merge(("flag", "true"))
+        .merge(("name", "app"))
+        .merge(("port", 8080))
+        .merge(("hosts", vec!["1", "b"]));
+
+    let options = ExportOptions::new()
+        .encrypt(["*"])
+        .encrypt_with(|plain: &str| plain.to_string());
+
+    let value = figment.export(&options).unwrap();
+    let decrypt = |key: &str| find(&value, key).parse::<Value>().unwrap();
+    assert_eq!(find(&value, "pin"), "\"123\"");
+    assert_eq!(decrypt("pin").deserialize::<String>().unwrap(), "123");
+    assert_eq!(decrypt("pin").as_str(), Some("123"));
+    assert_eq!(decrypt("flag").as_str(), Some("true"));
+    assert_eq!(decrypt("name").as_str(), Some("app"));
+    assert_eq!(decrypt("port").deserialize::<u16>().unwrap(), 8080);
+    assert!(decrypt("port").as_str().is_none());
+    assert_eq!(decrypt("hosts").deserialize::<Vec<String>>().unwrap(), ["1", "b"]);
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use figment::{Figment, Profile, providers::Serialized};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+struct Lib {
+    level: u8,
+}
+
+#[test]
+fn extension_cache_follows_changes() {
+    let figment = Figment::new()
+        .extension_defaults("lib", Lib { level: 1 })
+        .merge(Serialized::default("lib.level", 2).profile("debug"));
+
+    assert_eq!(figment.extension::<Lib>("lib").unwrap().level, 1);
+    assert_eq!(figment.extension::<figment::value::Dict>("lib").unwrap().len(), 1);
+
+    let debug = figment.clone().select("debug");
+    assert_eq!(debug.extension::<Lib>("lib").unwrap().level, 2);
+
+    let merged = figment.clone().merge(("lib", Lib { level: 3 }));
+    assert_eq!(merged.extension::<Lib>("lib").unwrap().level, 3);
+    assert_eq!(figment.extension::<Lib>("lib").unwrap().level, 1);
+
+    let metadata = figment.find_metadata("lib").unwrap();
+    assert_eq!(metadata.name, "lib defaults");
+    assert_eq!(figment.find_profile("lib"), Some(Profile::Default));
+    assert!(figment.extension::<u8>("missing").is_err());
+}
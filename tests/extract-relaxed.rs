@@ -0,0 +1,55 @@
+use serde::Deserialize;
+use figment::{Figment, providers::Serialized};
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    server: Server,
+}
+
+#[derive(Debug, Deserialize)]
+struct Server {
+    #[serde(default)]
+    port: u16,
+    #[serde(default)]
+    tls: Tls,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Tls {
+    #[serde(default)]
+    enabled: bool,
+}
+
+#[test]
+fn missing_nested_sections_are_empty() {
+    let config: Config = Figment::new().extract_relaxed().unwrap();
+    assert_eq!(config.server.port, 0);
+    assert!(!config.server.tls.enabled);
+
+    let figment = Figment::from(Serialized::default("server.port", 80));
+    let config: Config = figment.extract_relaxed().unwrap();
+    assert_eq!(config.server.port, 80);
+}
+
+#[test]
+fn required_fields_remain_missing() {
+    #[derive(Debug, Deserialize)]
+    struct Strict {
+        #[allow(dead_code)]
+        server: Named,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Named {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    let error = Figment::new().extract_relaxed::<Strict>().unwrap_err();
+    assert!(error.missing());
+    assert_eq!(error.path, Vec::<String>::new());
+
+    let figment = Figment::from(Serialized::default("server", 1));
+    let error = figment.extract_relaxed::<Config>().unwrap_err();
+    assert!(!error.missing());
+}
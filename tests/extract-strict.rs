@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use figment::{Figment, providers::Serialized};
+use figment::value::magic::RelativePathBuf;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Config {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    servers: Vec<Server>,
+    #[serde(default)]
+    path: Option<RelativePathBuf>,
+    #[serde(flatten)]
+    extra: Extra,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Server {
+    host: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Extra {
+    #[serde(default)]
+    port: Option<u16>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+#[test]
+fn strict_reports_unconsumed_keys_with_provenance() {
+    let figment = Figment::new()
+        .merge(Serialized::default("name", "app"))
+        .merge(Serialized::default("path", "a/b"))
+        .merge(Serialized::default("port", 80))
+        .merge(Serialized::default("labels", figment::util::map!["any" => "thing"]))
+        .merge(("servers", vec![figment::util::map!["host" => "a", "hots" => "b"]]))
+        .merge(Serialized::default("unused.nested", 1));
+
+    let errors: Vec<_> = figment.extract_strict::<Config>().unwrap_err().into_iter().collect();
+    let paths: Vec<_> = errors.iter().map(|e| e.path.join(".")).collect();
+    assert_eq!(paths, ["servers.0.hots", "unused"]);
+    assert!(errors[1].to_string().contains("unknown section `unused`"));
+    assert!(errors[0].metadata.is_some());
+
+    let figment = Figment::from(Serialized::default("servers", [Server { host: "a".into() }]));
+    assert_eq!(figment.extract_strict::<Config>().unwrap().servers[0].host, "a");
+}
+
+#[test]
+fn strict_accepts_catch_all_flatten() {
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Open {
+        name: String,
+        #[serde(flatten)]
+        rest: HashMap<String, figment::value::Value>,
+    }
+
+    let figment = Figment::new()
+        .merge(Serialized::default("name", "app"))
+        .merge(Serialized::default("anything.at.all", 1));
+
+    let open: Open = figment.extract_strict().unwrap();
+    assert!(open.rest.contains_key("anything"));
+}
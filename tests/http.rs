@@ -0,0 +1,219 @@
+#![cfg(feature = "http")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use figment::{Figment, Provider, providers::{Http, Json, Toml}};
+use figment::providers::{HttpClient, HttpRequest, HttpResponse};
+
+/// Serves `responses` in order, one per connection, sending each request's
+/// header lines over the returned channel.
+fn serve(responses: Vec<&'static str>) -> (String, Receiver<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut lines = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                match line.trim_end() {
+                    "" => break,
+                    line => lines.push(line.to_string()),
+                }
+            }
+
+            tx.send(lines).unwrap();
+            // The client may hang up early, as it does on an oversized response.
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    (url, rx)
+}
+
+#[test]
+fn conditional_requests_reuse_cached_document() {
+    let (url, requests) = serve(vec![
+        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nLast-Modified: Tue, 15 Oct 2024 07:28:00 GMT\r\n\
+            Content-Length: 9\r\n\r\nport = 80",
+        "HTTP/1.1 304 Not Modified\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 11\r\n\r\nport = 8080",
+    ]);
+
+    let http = Http::<Toml>::new(format!("{}/app.toml", url));
+    assert_eq!(http.metadata().source.unwrap().url(), Some(&*format!("{}/app.toml", url)));
+    assert_eq!(Figment::from(http.clone()).extract_inner::<u16>("port").unwrap(), 80);
+    assert_eq!(http.etag().as_deref(), Some("\"v1\""));
+
+    let first = requests.recv().unwrap();
+    assert_eq!(first[0], "GET /app.toml HTTP/1.1");
+    assert!(!first.iter().any(|h| h.starts_with("If-None-Match")));
+
+    assert_eq!(Figment::from(http.clone()).extract_inner::<u16>("port").unwrap(), 80);
+    let second = requests.recv().unwrap();
+    assert!(second.contains(&"If-None-Match: \"v1\"".to_string()));
+    assert!(second.contains(&"If-Modified-Since: Tue, 15 Oct 2024 07:28:00 GMT".to_string()));
+
+    assert_eq!(Figment::from(http.clone()).extract_inner::<u16>("port").unwrap(), 8080);
+    assert!(http.etag().is_none() && http.last_modified().is_none());
+}
+
+#[test]
+fn chunked_responses_and_redirects() {
+    let (url, _requests) = serve(vec![
+        "HTTP/1.1 302 Found\r\nLocation: /new.json?v=2\r\nContent-Length: 0\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+            5\r\n{\"a\":\r\n8;ext=1\r\n [1, 2]}\r\n0\r\n\r\n",
+    ]);
+
+    let figment = Figment::from(Http::<Json>::new(format!("{}/old.json", url)));
+    assert_eq!(figment.extract_inner::<Vec<u8>>("a").unwrap(), vec![1, 2]);
+}
+
+#[test]
+fn relative_redirects() {
+    let (url, requests) = serve(vec![
+        "HTTP/1.1 301 Moved Permanently\r\nLocation: config.toml\r\n\r\n",
+        "HTTP/1.1 307 Temporary Redirect\r\nLocation: ../v2/./app.toml?x=1\r\n\r\n",
+        "HTTP/1.1 302 Found\r\nLocation: ?x=2\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 9\r\n\r\nport = 80",
+    ]);
+
+    let figment = Figment::from(Http::<Toml>::new(format!("{}/v1/dir/app.toml", url)));
+    assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 80);
+
+    let paths: Vec<_> = requests.iter().take(4).map(|lines| lines[0].clone()).collect();
+    assert_eq!(paths, [
+        "GET /v1/dir/app.toml HTTP/1.1",
+        "GET /v1/dir/config.toml HTTP/1.1",
+        "GET /v1/v2/app.toml?x=1 HTTP/1.1",
+        "GET /v1/v2/app.toml?x=2 HTTP/1.1",
+    ]);
+}
+
+#[test]
+fn nested_and_profiles() {
+    let (url, _requests) = serve(vec![
+        "HTTP/1.1 200 OK\r\n\r\n[debug]\nport = 1\n[release]\nport = 2",
+        "HTTP/1.1 200 OK\r\n\r\nport = 3",
+    ]);
+
+    let figment = Figment::from(Http::<Toml>::new(&url).nested()).select("release");
+    assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 2);
+
+    let figment = Figment::from(Http::<Toml>::new(&url).profile("debug"));
+    assert!(figment.extract_inner::<u16>("port").is_err());
+    assert_eq!(figment.select("debug").extract_inner::<u16>("port").unwrap(), 3);
+}
+
+#[test]
+fn failures() {
+    let (url, _requests) = serve(vec!["HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n"]);
+    let error = Figment::from(Http::<Toml>::new(&url)).extract_inner::<u16>("port").unwrap_err();
+    assert!(error.to_string().contains("server responded with status 404"));
+
+    let https = Http::<Toml>::new("https://config.example.com/app.toml");
+    let error = Figment::from(https).extract_inner::<u16>("port").unwrap_err();
+    assert!(error.to_string().contains("unsupported scheme `https`"));
+
+    let (url, _requests) = serve(vec!["HTTP/1.1 304 Not Modified\r\n\r\n"]);
+    let error = Figment::from(Http::<Toml>::new(&url)).extract_inner::<u16>("port").unwrap_err();
+    assert!(error.to_string().contains("status 304"));
+}
+
+#[test]
+fn malformed_responses() {
+    let malformed = vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 9\r\n",
+        "HTTP/1.1 OK\r\n\r\nport = 80",
+        "HTTP/1.1 200 OK\r\nnot a header\r\n\r\nport = 80",
+        "HTTP/1.1 200 OK\r\nContent-Length: 90\r\n\r\nport = 80",
+        "HTTP/1.1 200 OK\r\nContent-Length: nine\r\n\r\nport = 80",
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\nz\r\nport = 80\r\n0\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n9\r\nport = 80\r\n",
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n20\r\nport = 80\r\n0\r\n\r\n",
+    ];
+
+    for response in malformed {
+        let (url, _requests) = serve(vec![response]);
+        let error = Figment::from(Http::<Toml>::new(&url)).extract_inner::<u16>("port").unwrap_err();
+        assert!(error.to_string().contains("malformed HTTP response"), "{:?}: {}", response, error);
+    }
+}
+
+#[test]
+fn chunked_responses() {
+    let (url, _requests) = serve(vec![
+        "HTTP/1.1 200 OK\r\ntransfer-encoding: Chunked\r\n\r\n\
+            4\r\nport\r\n1 ; name=value\r\n \r\nA\r\n= 8080\nx=1\r\n0\r\nTrailer: yes\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n",
+    ]);
+
+    let http = Http::<Toml>::new(&url);
+    assert_eq!(Figment::from(http.clone()).extract_inner::<u16>("port").unwrap(), 8080);
+    assert!(Figment::from(http).extract::<figment::value::Dict>().unwrap().is_empty());
+}
+
+#[test]
+fn oversized_and_slow_responses() {
+    let (url, _requests) = serve(vec!["HTTP/1.1 200 OK\r\nContent-Length: 11\r\n\r\nport = 8080"]);
+    let http = Http::<Toml>::new(&url).max_size(8);
+    let error = Figment::from(http).extract_inner::<u16>("port").unwrap_err();
+    assert!(error.to_string().contains("exceeds the maximum size of 8 bytes"));
+
+    let huge = format!("HTTP/1.1 200 OK\r\n\r\n{}", "#".repeat(1 << 20));
+    let (url, _requests) = serve(vec![Box::leak(huge.into_boxed_str())]);
+    let error = Figment::from(Http::<Toml>::new(&url).max_size(8)).extract::<u16>().unwrap_err();
+    assert!(error.to_string().contains("exceeds the maximum size of 8 bytes"));
+
+    // A server that sends part of a response and then stalls.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.write_all(b"HTTP/1.1 200 OK\r\n").unwrap();
+        std::thread::sleep(Duration::from_secs(5));
+    });
+
+    let start = Instant::now();
+    let http = Http::<Toml>::new(&url).timeout(Duration::from_millis(200));
+    let error = Figment::from(http).extract_inner::<u16>("port").unwrap_err();
+    assert!(error.to_string().contains("timed out"), "{}", error);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn checksum_is_computed_from_fetched_data() {
+    struct Counted(Arc<AtomicUsize>);
+
+    impl HttpClient for Counted {
+        fn get(&self, _: &HttpRequest) -> Result<HttpResponse, figment::Error> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(HttpResponse::new(200, "port = 8080"))
+        }
+    }
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    let http = Http::<Toml>::new("https://config.example.com/app.toml")
+        .client(Counted(requests.clone()))
+        .checksum(true);
+
+    assert_eq!(http.metadata().get_checksum(), None);
+    assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+    let figment = Figment::from(http);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    let metadata = figment.metadata().next().unwrap();
+    assert!(metadata.get_checksum().is_some());
+    assert!(!metadata.source_changed());
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
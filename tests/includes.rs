@@ -0,0 +1,65 @@
+use figment::{Figment, Jail, providers::{Format, Toml}};
+
+#[test]
+fn nested_includes_apply_per_profile() {
+    Jail::expect_with(|jail| {
+        jail.create_dir("extra")?;
+        jail.create_file("extra/prod.toml", "[prod]\nport = 443\nhost = \"example.com\"")?;
+        jail.create_file("App.toml", r#"
+            include = "extra/prod.toml"
+
+            [default]
+            port = 8000
+
+            [prod]
+            host = "prod.internal"
+        "#)?;
+
+        let figment = Figment::from(Toml::file("App.toml").nested().with_includes("include"));
+        assert_eq!(figment.extract_inner::<u16>("port")?, 8000);
+
+        let figment = figment.select("prod");
+        assert_eq!(figment.extract_inner::<u16>("port")?, 443);
+        assert_eq!(figment.extract_inner::<String>("host")?, "prod.internal");
+        Ok(())
+    });
+}
+
+#[test]
+fn missing_and_invalid_includes() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "include = [\"none/*.toml\"]\nname = \"app\"")?;
+        let figment = Figment::from(Toml::file("App.toml").with_includes("include"));
+        assert_eq!(figment.extract_inner::<String>("name")?, "app");
+
+        jail.create_file("App.toml", "include = \"missing.toml\"\nname = \"app\"")?;
+        let figment = Figment::from(Toml::file("App.toml").with_includes("include"));
+        let error = figment.extract_inner::<String>("name").unwrap_err();
+        assert!(error.to_string().contains("required file"));
+
+        jail.create_file("App.toml", "include = 5\nname = \"app\"")?;
+        let figment = Figment::from(Toml::file("App.toml").with_includes("include"));
+        let error = figment.extract_inner::<String>("name").unwrap_err();
+        assert_eq!(error.path, ["include"]);
+
+        let figment = Figment::from(Toml::file("App.toml"));
+        assert_eq!(figment.extract_inner::<u8>("include")?, 5);
+        Ok(())
+    });
+}
+
+#[test]
+fn string_sources_include_relative_to_cwd() {
+    Jail::expect_with(|jail| {
+        jail.create_file("a.toml", "a = 1\nb = 1")?;
+        jail.create_file("b.toml", "b = 2")?;
+
+        let toml = Toml::string("include = [\"a.toml\", \"b.toml\"]\nc = 3").with_includes("include");
+        let figment = Figment::from(toml);
+        assert_eq!(figment.extract_inner::<u8>("a")?, 1);
+        assert_eq!(figment.extract_inner::<u8>("b")?, 2);
+        assert_eq!(figment.extract_inner::<u8>("c")?, 3);
+        assert_eq!(figment.metadata().count(), 4);
+        Ok(())
+    });
+}
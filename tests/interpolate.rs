@@ -0,0 +1,77 @@
+use figment::{Figment, Jail, value::Value, providers::{Format, Toml, Serialized}};
+
+#[test]
+fn references_resolve_against_selected_profile() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", r#"
+            [default]
+            host = "localhost"
+            url = "http://${host}:${port}/"
+            port = 8000
+
+            [prod]
+            host = "example.com"
+            port = 443
+        "#)?;
+
+        let figment = Figment::from(Toml::file("App.toml").nested()).resolve_interpolation(true);
+        assert_eq!(figment.extract_inner::<String>("url")?, "http://localhost:8000/");
+
+        let figment = figment.select("prod");
+        assert_eq!(figment.extract_inner::<String>("url")?, "http://example.com:443/");
+        Ok(())
+    });
+}
+
+#[test]
+fn whole_references_preserve_type() {
+    let figment = Figment::new()
+        .merge(Serialized::default("server.port", 80))
+        .merge(Serialized::default("server.tls", true))
+        .merge(Serialized::default("copy", "${server}"))
+        .merge(Serialized::default("port", "${server.port}"))
+        .resolve_interpolation(true);
+
+    assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 80);
+    assert_eq!(figment.extract_inner::<bool>("copy.tls").unwrap(), true);
+
+    let figment = figment.merge(Serialized::default("label", "port ${server}"));
+    let error = figment.extract_inner::<Value>("label").unwrap_err();
+    assert_eq!(error.path, ["label"]);
+    assert!(error.to_string().contains("cannot interpolate"));
+}
+
+#[test]
+fn env_fallback_and_errors() {
+    Jail::expect_with(|jail| {
+        jail.set_env("DB_HOST", "db");
+        jail.set_env("EMPTY", "");
+
+        let figment = Figment::new()
+            .merge(Serialized::default("a", "${DB_HOST}:${EMPTY:-5432}"))
+            .merge(Serialized::default("b", "${MISSING}"))
+            .resolve_interpolation(true);
+
+        let error = figment.extract_inner::<String>("a").unwrap_err();
+        assert_eq!(error.path, ["b"]);
+        assert!(error.metadata.is_some());
+        assert!(error.to_string().contains("`${MISSING}` is neither"));
+
+        let figment = figment.merge(Serialized::default("b", "${MISSING:-x}"));
+        assert_eq!(figment.extract_inner::<String>("a")?, "db:5432");
+        assert_eq!(figment.extract_inner::<String>("b")?, "x");
+
+        let figment = figment.merge(Serialized::default("b", "${MISSING"));
+        assert!(figment.extract_inner::<String>("a").is_err());
+        Ok(())
+    });
+}
+
+#[test]
+fn disabled_by_default() {
+    let figment = Figment::from(Serialized::default("a", "${b}"))
+        .merge(Serialized::default("b", 1));
+
+    assert_eq!(figment.extract_inner::<String>("a").unwrap(), "${b}");
+    assert_eq!(figment.resolve_interpolation(true).extract_inner::<u8>("a").unwrap(), 1);
+}
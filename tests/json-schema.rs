@@ -0,0 +1,92 @@
+#![cfg(feature = "schema")]
+
+use figment::{Figment, providers::{Format, Toml, Serialized}, schema::JsonSchema};
+use serde_json::json;
+
+fn errors(figment: &Figment, schema: serde_json::Value) -> Vec<(String, String)> {
+    let schema = JsonSchema::new(schema).unwrap();
+    match figment.validate(&schema) {
+        Ok(()) => vec![],
+        Err(e) => e.into_iter().map(|e| (e.path.join("."), e.kind.to_string())).collect(),
+    }
+}
+
+#[test]
+fn refs_and_combinators() {
+    let schema = json!({
+        "$defs": {
+            "server": {
+                "type": "object",
+                "properties": { "port": { "type": "integer", "exclusiveMaximum": 1024 } },
+            }
+        },
+        "type": "object",
+        "properties": {
+            "primary": { "$ref": "#/$defs/server" },
+            "backup": { "anyOf": [{ "$ref": "#/$defs/server" }, { "type": "null" }] },
+            "mode": { "oneOf": [{ "const": "a" }, { "type": "string", "maxLength": 1 }] },
+            "never": { "not": {} },
+        }
+    });
+
+    let figment = Figment::from(Toml::string(r#"
+        mode = "b"
+        [primary]
+        port = 80
+        [backup]
+        port = 8080
+    "#));
+
+    assert_eq!(errors(&figment, schema.clone()), vec![
+        ("backup.port".into(), "invalid value signed int `8080`, expected an integer < 1024".into()),
+    ]);
+
+    let figment = Figment::from(Toml::string("mode = \"a\"\nnever = 1\nbackup = 1"));
+    assert_eq!(errors(&figment, schema), vec![
+        ("backup".into(), "invalid value signed int `1`, expected a value matching a schema in `anyOf`".into()),
+        ("mode".into(), "invalid value string \"a\", expected a value matching exactly one schema in `oneOf`, not 2".into()),
+        ("never".into(), "invalid value signed int `1`, expected a value not matching the schema in `not`".into()),
+    ]);
+}
+
+#[test]
+fn arrays_and_dicts() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "pair": { "type": "array", "prefixItems": [{ "type": "string" }], "items": { "type": "integer" } },
+            "tags": { "type": "array", "uniqueItems": true, "minItems": 3 },
+            "limits": { "type": "object", "additionalProperties": { "type": "number", "multipleOf": 0.5 } },
+        },
+        "required": ["name"],
+        "additionalProperties": false,
+    });
+
+    let figment = Figment::from(Serialized::defaults(json!({
+        "pair": ["a", 1, "b"],
+        "tags": ["x", "x"],
+        "limits": { "cpu": 1.5, "mem": 0.3 },
+        "extra": true,
+    })));
+
+    assert_eq!(errors(&figment, schema), vec![
+        ("".into(), "missing field `name`".into()),
+        ("extra".into(), "unknown key `extra`, expected one of `limits`, `pair`, `tags`".into()),
+        ("limits.mem".into(), "invalid value float `0.3`, expected a multiple of 0.5".into()),
+        ("pair.2".into(), "invalid type: found string \"b\", expected an integer".into()),
+        ("tags".into(), "invalid length 2, expected at least 3 items".into()),
+        ("tags".into(), "invalid value sequence, expected a sequence of unique items".into()),
+    ]);
+}
+
+#[test]
+fn unresolvable_refs() {
+    let figment = Figment::from(Serialized::default("a", 1));
+    assert_eq!(errors(&figment, json!({ "$ref": "other.json#/a" })), vec![
+        ("".into(), "unresolvable schema reference `other.json#/a`".into()),
+    ]);
+
+    let looping = errors(&figment, json!({ "$ref": "#" }));
+    assert_eq!(looping.len(), 1);
+    assert!(looping[0].1.contains("nested too deeply"));
+}
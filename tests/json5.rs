@@ -0,0 +1,51 @@
+use serde::Deserialize;
+use figment::{Figment, Jail, providers::{Format, Json, Json5}};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    name: String,
+    port: u16,
+    hosts: Vec<String>,
+    ratio: f64,
+}
+
+#[test]
+fn comments_trailing_commas_and_profiles() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.json5", r#"
+            // Hand-edited configuration.
+            {
+                default: {
+                    name: 'app',
+                    port: 0x50,
+                    hosts: ["a.com", "b.com",],
+                    ratio: .5,
+                },
+                /* Overrides for debug builds. */
+                debug: { port: 8080, },
+            }
+        "#)?;
+
+        let figment = Figment::from(Json5::file("App.json5").nested());
+        assert_eq!(figment.extract::<Config>()?, Config {
+            name: "app".into(),
+            port: 80,
+            hosts: vec!["a.com".into(), "b.com".into()],
+            ratio: 0.5,
+        });
+
+        let config: Config = figment.select("debug").extract()?;
+        assert_eq!(config.port, 8080);
+
+        jail.create_file("App.json", r#"{ "name": "json", "port": 1, "hosts": [], "ratio": 1 }"#)?;
+        let config: Config = Figment::from(Json5::file("App.json")).extract()?;
+        assert_eq!(config, Figment::from(Json::file("App.json")).extract::<Config>()?);
+        Ok(())
+    });
+}
+
+#[test]
+fn errors_name_the_file() {
+    let error = Figment::from(Json5::string("{ port: }")).extract::<Config>().unwrap_err();
+    assert!(error.to_string().contains("JSON5 source string"), "{}", error);
+}
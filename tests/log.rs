@@ -0,0 +1,49 @@
+use std::sync::Mutex;
+
+use log::{Level, Log, Metadata, Record};
+use figment::{Figment, providers::Serialized};
+
+static RECORDS: Mutex<Vec<(Level, String)>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "figment"
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            RECORDS.lock().unwrap().push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn logs_providers_decisions_and_extractions() {
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let keys: Vec<_> = (0..100).map(|i| (format!("k{}", i), i)).collect();
+    let figment = Figment::from(Serialized::defaults(&keys.iter().cloned().collect::<figment::value::Map<_, _>>()))
+        .merge(Serialized::default("secret", "hunter2"))
+        .join(Serialized::default("secret", "ignored"));
+
+    assert!(figment.extract_inner::<String>("secret").is_ok());
+    assert!(figment.extract_inner::<u8>("secret").is_err());
+
+    let records = RECORDS.lock().unwrap();
+    let debug: Vec<_> = records.iter().filter(|(l, _)| *l == Level::Debug).map(|r| &r.1).collect();
+    assert_eq!(debug.len(), 5);
+    assert!(debug[0].starts_with("Merge ") && debug[0].contains("100 top-level key(s)"));
+    assert!(debug[2].starts_with("Join "));
+    assert!(debug[3].contains("extracted `secret`"));
+    assert!(debug[4].contains("extracting `secret`") && debug[4].contains("failed"));
+
+    let trace: Vec<_> = records.iter().filter(|(l, _)| *l == Level::Trace).map(|r| &r.1).collect();
+    assert!(trace.iter().any(|r| r.contains("more decision(s) not logged")));
+    assert!(trace.iter().any(|r| r.starts_with("KeptExisting `secret`")));
+    assert!(records.iter().all(|(_, r)| !r.contains("hunter2") && !r.contains("ignored")));
+}
@@ -0,0 +1,73 @@
+use figment::{Figment, Profile, util::map, value::Value};
+use figment::providers::{Format, Toml, Serialized};
+
+const PATHS: &[&str] = &[
+    "", ".", "a", "a.", "a..b", "a.b", "a.b.c", "a.b.d", "a.x", "b", "b.c",
+    "list", "list.0", "nested.deep.leaf", "nested.deep", "missing", "t.v.x",
+];
+
+/// Checks that lookups agree with finding the path in the merged value.
+fn check(figment: &Figment) {
+    let merged: Value = figment.extract().unwrap();
+    for path in PATHS {
+        let expected = merged.clone().find(path);
+        let actual = figment.find_value(path).ok();
+        assert_eq!(actual, expected, "path `{}`", path);
+        if let (Some(actual), Some(expected)) = (&actual, &expected) {
+            assert_eq!(actual.tag(), expected.tag(), "path `{}`", path);
+        }
+
+        assert_eq!(figment.contains(path), expected.is_some(), "path `{}`", path);
+    }
+}
+
+fn figment() -> Figment {
+    Figment::from(Toml::string(r#"
+        [default]
+        a = { b = { c = 1, d = 2 } }
+        b = 1
+        list = [1, 2]
+        nested = { deep = { leaf = "default" } }
+        t = { v = { x = "default" } }
+
+        [debug]
+        a = { b = { c = 10 }, x = true }
+        b = { c = "debug" }
+        nested = "shadowed"
+
+        [prod]
+        a = { b = 5 }
+        list = [3]
+
+        [global]
+        a = { b = { d = 20 } }
+        t = { v = { x = "global" } }
+    "#).nested())
+}
+
+#[test]
+fn lookup_matches_merged_value() {
+    let figment = figment();
+    check(&figment);
+    check(&figment.clone().select("debug"));
+    check(&figment.clone().select("prod"));
+    check(&figment.clone().select("missing"));
+    check(&figment.clone().select(Profile::param("t", "v")));
+
+    let levels = Profile::leveled(&["prod", "debug"]);
+    check(&figment.clone().select_level(&levels, 1));
+    check(&figment.clone().merge(Serialized::global("a.b", "global")).select("debug"));
+    check(&figment.join(Serialized::default("list", vec![7])).select("prod"));
+}
+
+#[test]
+fn lookup_errors() {
+    let figment = Figment::from(Serialized::defaults(map!["a" => 1]));
+    let err = figment.find_value("a.b").unwrap_err();
+    assert!(err.missing());
+    assert!(!figment.contains("a.b"));
+
+    let figment = Figment::from(Toml::file("nonexistent.toml").required(true));
+    assert!(figment.find_value("a").is_err());
+    assert!(!figment.contains("a"));
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use figment::{Figment, Jail, providers::{Format, Toml, Json, Serialized}};
+use figment::value::magic::{RelativePathBuf, Tagged};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Config {
+    root: RelativePathBuf,
+    workers: Tagged<u16>,
+}
+
+#[test]
+fn magic_values_round_trip_in_original_form() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "root = \"static\"\nworkers = 4")?;
+        let config: Config = Figment::from(Toml::file("App.toml")).extract()?;
+
+        let figment = Figment::from(Serialized::defaults(&config));
+        let toml = figment.serialize_to::<Toml>()?;
+        assert_eq!(toml, "root = \"static\"\nworkers = 4\n");
+
+        let json = figment.serialize_to::<Json>()?;
+        let reparsed: Config = Figment::from(Json::string(&json)).extract()?;
+        assert_eq!(reparsed.root.original(), config.root.original());
+        assert_eq!(*reparsed.workers, 4);
+
+        // Values are only rendered when serializing, not when extracting.
+        let config: Config = figment.extract()?;
+        assert_eq!(config.root.relative(), jail.directory().join("static"));
+        Ok(())
+    });
+}
+
+#[test]
+fn lookalike_dictionaries_are_untouched() {
+    let figment = Figment::from(Serialized::default("path", figment::util::map! {
+        "___figment_relative_path" => "a",
+        "other" => "b",
+    }));
+
+    let toml = figment.serialize_to::<Toml>().unwrap();
+    assert!(toml.contains("___figment_relative_path = \"a\""));
+}
@@ -0,0 +1,51 @@
+use figment::{Figment, Profile, Provider, providers::Serialized};
+
+fn provider() -> Figment {
+    Figment::new()
+        .merge(Serialized::default("a", "release").profile("release"))
+        .merge(Serialized::default("b", "release").profile("release"))
+        .merge(Serialized::default("a", "prod").profile("prod"))
+        .merge(Serialized::default("c", "default"))
+}
+
+#[test]
+fn collapsed_profiles_prefer_unrenamed() {
+    let mapped = provider().map_profile(|p: Profile| match p == "release" {
+        true => "prod".into(),
+        false => p,
+    });
+
+    let figment = Figment::from(mapped).select("prod");
+    assert_eq!(figment.extract_inner::<String>("a").unwrap(), "prod");
+    assert_eq!(figment.extract_inner::<String>("b").unwrap(), "release");
+    assert_eq!(figment.extract_inner::<String>("c").unwrap(), "default");
+    assert!(!figment.profiles().any(|p| p == "release"));
+}
+
+#[test]
+fn selected_profile_is_mapped() {
+    let provider = provider().select("release");
+    let mapped = provider.map_profile(|p: Profile| format!("{}-mapped", p).into());
+    let figment = Figment::from(mapped);
+    assert_eq!(figment.profile(), "release-mapped");
+    assert_eq!(figment.extract_inner::<String>("b").unwrap(), "release");
+}
+
+#[test]
+fn adapter_metadata_has_parent() {
+    figment::Jail::expect_with(|jail| {
+        jail.set_env("APP_PORT", "not a number");
+
+        let mapped = figment::providers::Env::prefixed("APP_").map_profile(|p| p);
+        let error = Figment::from(mapped).extract_inner::<u16>("port").unwrap_err();
+
+        let metadata = error.metadata.unwrap();
+        let chain: Vec<_> = metadata.ancestors().map(|m| &*m.name).collect();
+        assert_eq!(chain, [
+            "`APP_` environment variable(s) (profile mapped)",
+            "`APP_` environment variable(s)",
+        ]);
+
+        Ok(())
+    });
+}
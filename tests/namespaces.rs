@@ -0,0 +1,48 @@
+use figment::{Figment, providers::Serialized};
+
+#[test]
+fn compatible_claims_are_accepted() {
+    let figment = Figment::from(Serialized::default("acme.port", 8000))
+        .register_namespace("acme", "0.3.1")
+        .register_namespace("acme", "0.3.7-beta.1")
+        .register_namespace("acme-http", "2.0.0")
+        .register_namespace("other", "0.0.4")
+        .register_namespace("other", "0.0.4+build");
+
+    assert_eq!(figment.extract_inner::<u16>("acme.port").unwrap(), 8000);
+    let claims: Vec<_> = figment.namespaces().collect();
+    assert_eq!(claims, [("acme", "0.3.1"), ("acme-http", "2.0.0"), ("other", "0.0.4")]);
+}
+
+#[test]
+fn conflicting_claims_fail() {
+    let base = Figment::from(Serialized::default("acme.port", 8000))
+        .register_namespace("acme", "0.3.1");
+
+    for (namespace, version) in [("acme", "0.4.0"), ("acme.http", "1.0.0"), ("acme", "1.2")] {
+        let figment = base.clone().register_namespace(namespace, version);
+        let error = figment.extract_inner::<u16>("acme.port").unwrap_err();
+        let msg = error.to_string();
+        assert!(msg.contains(namespace), "{}", msg);
+        assert!(msg.contains(file!()), "{}", msg);
+    }
+
+    let figment = Figment::new()
+        .register_namespace("acme.http", "1.0.0")
+        .register_namespace("acme", "1.0.0");
+
+    assert!(figment.extract_inner::<u16>("acme.port").is_err());
+    let claims: Vec<_> = figment.namespaces().collect();
+    assert_eq!(claims, [("acme.http", "1.0.0")]);
+}
+
+#[test]
+fn conflicts_chain_errors() {
+    let figment = Figment::new()
+        .register_namespace("acme", "1.0.0")
+        .register_namespace("acme", "2.0.0")
+        .register_namespace("acme", "3.0.0");
+
+    let error = figment.extract::<figment::value::Dict>().unwrap_err();
+    assert_eq!(error.count(), 2);
+}
@@ -0,0 +1,78 @@
+use figment::{Figment, Profile, util::map};
+use figment::value::KeyNormalizer;
+
+fn nfc() -> KeyNormalizer {
+    KeyNormalizer::new(|key| key.replace("e\u{301}", "\u{e9}"))
+}
+
+#[test]
+fn normalize_same_provider() {
+    let figment = Figment::new()
+        .normalize_keys(nfc())
+        .merge(("cafe\u{301}", map!["a" => 1, "b" => 1]))
+        .merge(("menu", map!["caf\u{e9}" => map!["b" => 2], "cafe\u{301}" => map!["c" => 3]]));
+
+    assert_eq!(figment.extract_inner::<u8>("caf\u{e9}.a").unwrap(), 1);
+    assert!(!figment.contains("cafe\u{301}"));
+    assert_eq!(figment.extract_inner::<u8>("menu.caf\u{e9}.b").unwrap(), 2);
+    assert_eq!(figment.extract_inner::<u8>("menu.caf\u{e9}.c").unwrap(), 3);
+
+    let collisions = figment.key_collisions();
+    assert_eq!(collisions.len(), 1);
+    assert_eq!(collisions[0].profile, Profile::Global);
+    assert_eq!(collisions[0].key, "menu.caf\u{e9}");
+    let spellings: Vec<_> = collisions[0].spellings.iter().map(|(s, _)| s.as_str()).collect();
+    assert_eq!(spellings, ["menu.cafe\u{301}", "menu.caf\u{e9}"]);
+    assert!(collisions[0].to_string().contains("menu.caf\u{e9}"));
+
+    let focused = figment.focus("menu");
+    assert_eq!(focused.key_collisions()[0].key, "caf\u{e9}");
+    assert_eq!(focused.key_collisions()[0].spellings[0].0, "cafe\u{301}");
+}
+
+#[test]
+fn normalize_across_providers() {
+    let figment = Figment::new()
+        .merge(("caf\u{e9}", 1))
+        .normalize_keys(nfc())
+        .merge(("caf\u{e9}", 2))
+        .merge(("cafe\u{301}", 3));
+
+    assert_eq!(figment.extract_inner::<u8>("caf\u{e9}").unwrap(), 3);
+    let collision = &figment.key_collisions()[0];
+    assert_eq!(collision.spellings.len(), 2);
+    assert!(figment.get_metadata(collision.spellings[1].1).is_some());
+
+    let figment = Figment::new()
+        .merge(("caf\u{e9}", 1))
+        .merge(("cafe\u{301}", 2));
+
+    assert_eq!(figment.extract_inner::<u8>("caf\u{e9}").unwrap(), 1);
+    assert!(figment.key_collisions().is_empty());
+}
+
+#[test]
+fn key_context_placeholders() {
+    let figment = Figment::new()
+        .key_context([("A", "x.y"), ("B", "b")])
+        .merge(("${A}", map!["${B}" => 1, "c" => 2]))
+        .merge(("x", map!["y" => map!["d" => 3]]))
+        .merge(("lit${B}eral", 4));
+
+    assert_eq!(figment.extract_inner::<u8>("x.y.b").unwrap(), 1);
+    assert_eq!(figment.extract_inner::<u8>("x.y.c").unwrap(), 2);
+    assert_eq!(figment.extract_inner::<u8>("x.y.d").unwrap(), 3);
+    assert_eq!(figment.extract_inner::<u8>("litberal").unwrap(), 4);
+    assert!(figment.find_metadata("x.y").is_some());
+
+    let figment = Figment::new()
+        .key_context([("A", "a")])
+        .merge(("x", map!["${A" => 1]));
+
+    let error = figment.extract_inner::<u8>("x").unwrap_err();
+    assert!(error.to_string().contains("unterminated placeholder"));
+    assert_eq!(error.path, ["x", "${A"]);
+
+    let figment = Figment::new().merge(("${A}", 1));
+    assert_eq!(figment.extract_inner::<u8>("${A}").unwrap(), 1);
+}
@@ -0,0 +1,47 @@
+use figment::{Figment, value::NumPolicy};
+
+fn figment<T: serde::Serialize>(value: T, policy: NumPolicy) -> Figment {
+    Figment::from(("n", value)).num_policy(policy)
+}
+
+#[test]
+fn float_to_int() {
+    let policy = NumPolicy::default().float_to_int(true);
+    assert_eq!(figment(-3.0, policy).extract_inner::<i8>("n").unwrap(), -3);
+    assert_eq!(figment(255.0f32, policy).extract_inner::<u8>("n").unwrap(), 255);
+    assert!(figment(256.0, policy).extract_inner::<u8>("n").is_err());
+    assert!(figment(-1.0, policy).extract_inner::<u32>("n").is_err());
+    assert!(figment(3.5, policy).extract_inner::<u64>("n").is_err());
+    assert!(figment(f64::NAN, policy).extract_inner::<u64>("n").is_err());
+    assert!(figment(f64::INFINITY, policy).extract_inner::<i128>("n").is_err());
+    assert!(figment(3.0, NumPolicy::default()).extract_inner::<u64>("n").is_err());
+
+    let figment = figment(8.0, policy);
+    assert_eq!(figment.extract_inner::<f64>("n").unwrap(), 8.0);
+    assert_eq!(figment.extract_inner_lossy::<u16>("n").unwrap(), 8);
+}
+
+#[test]
+fn wrap_unsigned() {
+    let policy = NumPolicy::default().wrap_unsigned(true);
+    assert_eq!(figment(u64::MAX, policy).extract_inner::<i64>("n").unwrap(), -1);
+    assert_eq!(figment(u32::MAX, policy).extract_inner::<i32>("n").unwrap(), -1);
+    assert_eq!(figment(128u8, policy).extract_inner::<i8>("n").unwrap(), -128);
+    assert_eq!(figment(u128::MAX, policy).extract_inner::<i128>("n").unwrap(), -1);
+    assert_eq!(figment(7u64, policy).extract_inner::<i8>("n").unwrap(), 7);
+    assert!(figment(u64::MAX, policy).extract_inner::<i32>("n").is_err());
+    assert!(figment(u64::MAX, NumPolicy::default()).extract_inner::<i64>("n").is_err());
+    assert_eq!(figment(u64::MAX, policy).extract_inner::<u64>("n").unwrap(), u64::MAX);
+}
+
+#[test]
+fn non_finite() {
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(figment(value, NumPolicy::default()).extract_inner::<f64>("n").is_ok());
+        assert!(figment(value, NumPolicy::strict()).extract_inner::<f32>("n").is_err());
+    }
+
+    assert_eq!(figment(1.5, NumPolicy::strict()).extract_inner::<f32>("n").unwrap(), 1.5);
+    let error = figment(f64::NAN, NumPolicy::strict()).extract_inner::<f64>("n").unwrap_err();
+    assert!(error.to_string().starts_with("invalid value float `NaN`, expected a finite number"));
+}
@@ -0,0 +1,43 @@
+use figment::{Figment, Provider, Metadata, Profile, Resolution, Error};
+use figment::value::{Map, Dict, Value};
+
+/// Sums numbers with existing numbers and keeps existing strings.
+struct Accumulate<P>(P);
+
+impl<P: Provider> Provider for Accumulate<P> {
+    fn metadata(&self) -> Metadata { Metadata::named("accumulate") }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> { self.0.data() }
+
+    fn on_conflict(&self, _: &str, old: Option<&Value>, new: &Value) -> Resolution {
+        match (old, new) {
+            (Some(Value::String(..)), _) => Resolution::KeepExisting,
+            (Some(old), new) => match (old.to_i128(), new.to_i128()) {
+                (Some(a), Some(b)) => Resolution::Value((a + b).into()),
+                _ => Resolution::Default,
+            },
+            (None, _) => Resolution::Default,
+        }
+    }
+}
+
+#[test]
+fn on_conflict_resolutions() {
+    let figment = Figment::new()
+        .merge(("count", 1))
+        .merge(("name", "first"))
+        .merge(Accumulate(("count", 2)))
+        .merge(Accumulate(("name", "second")))
+        .merge(Accumulate(("new", 7)));
+
+    assert_eq!(figment.extract_inner::<i32>("count").unwrap(), 3);
+    assert_eq!(figment.extract_inner::<String>("name").unwrap(), "first");
+    assert_eq!(figment.extract_inner::<i32>("new").unwrap(), 7);
+
+    let metadata = figment.find_metadata("count").unwrap();
+    assert_eq!(metadata.name, "accumulate");
+
+    // `join` would keep the existing value, but the hook takes precedence.
+    let figment = figment.join(Accumulate(("count", 10)));
+    assert_eq!(figment.extract_inner::<i32>("count").unwrap(), 13);
+}
@@ -0,0 +1,84 @@
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use figment::{Figment, Provider, providers::{OnFirstUse, Serialized}};
+
+#[test]
+fn provider_is_created_once() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let provider = OnFirstUse::new(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Serialized::default("port", 8080).profile("prod")
+    });
+
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(format!("{:?}", provider).contains("pending"));
+
+    let figment = Figment::from(&provider).merge(&provider).select("prod");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(provider.is_ready());
+    assert!(format!("{:?}", provider).contains("Serialized"));
+    assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 8080);
+    assert_eq!(figment.find_metadata("port").unwrap(), &provider.metadata());
+}
+
+#[test]
+fn default_reports_errors_at_call_site() {
+    let line = line!() + 2;
+    let figment = Figment::new()
+        .join(OnFirstUse::default("id", || "abc".parse::<u32>()))
+        .join(OnFirstUse::default("name", || Ok::<_, String>("node")));
+
+    let error = figment.extract_inner::<String>("name").unwrap_err();
+    assert_eq!(error.count(), 1);
+    let location = error.metadata.unwrap().source.unwrap().code_location().unwrap();
+    assert_eq!((location.file(), location.line()), (file!(), line));
+
+    let figment = Figment::new().join(OnFirstUse::default("name", || Ok::<_, String>("node")));
+    assert_eq!(figment.extract_inner::<String>("name").unwrap(), "node");
+    let md = figment.find_metadata("name").unwrap();
+    assert_eq!(md.source.as_ref().unwrap().code_location().unwrap().file(), file!());
+}
+
+#[test]
+fn provider_is_created_only_for_data() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let provider = OnFirstUse::new(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Serialized::default("port", 8080)
+    });
+
+    assert_eq!(provider.metadata().name, "provider created on first use");
+    assert!(provider.profile().is_none());
+    assert!(!provider.is_ready());
+
+    let figment = Figment::from(provider.when(|| false));
+    assert!(figment.extract_inner::<u16>("port").is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn provider_is_created_without_holding_lock() {
+    type Port = OnFirstUse<Serialized<u16>>;
+
+    let slot: Arc<Mutex<Option<Arc<Port>>>> = Arc::new(Mutex::new(None));
+    let inner = slot.clone();
+    let provider = Arc::new(OnFirstUse::new(move || {
+        let this = inner.lock().unwrap().clone().unwrap();
+        let error = this.data().unwrap_err();
+        assert!(error.to_string().contains("while it was being created"));
+        assert!(!this.is_ready());
+        assert_eq!(this.metadata().name, "provider created on first use");
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        Serialized::default("port", 8080)
+    }));
+
+    *slot.lock().unwrap() = Some(provider.clone());
+    let other = provider.clone();
+    let thread = std::thread::spawn(move || Figment::from(&*other).extract_inner::<u16>("port"));
+    assert_eq!(Figment::from(&*provider).extract_inner::<u16>("port").unwrap(), 8080);
+    assert_eq!(thread.join().unwrap().unwrap(), 8080);
+    slot.lock().unwrap().take();
+}
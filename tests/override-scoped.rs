@@ -0,0 +1,37 @@
+use figment::{Figment, global, providers::Serialized};
+
+#[test]
+fn figment_is_restored_when_guard_drops() {
+    let mut figment = Figment::from(("port", 80)).select("debug");
+    {
+        let guard = figment.override_scoped(Serialized::global("port", 8080));
+        assert_eq!(guard.extract_inner::<u16>("port").unwrap(), 8080);
+        assert_eq!(guard.layers().len(), 2);
+    }
+
+    {
+        let guard = figment.override_scoped(("port", "invalid"));
+        assert!(guard.extract_inner::<u16>("port").is_err());
+    }
+
+    assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 80);
+    assert_eq!(figment.profile(), "debug");
+    assert_eq!(figment.layers().len(), 1);
+}
+
+#[test]
+fn global_overrides_nest() {
+    assert!(global::override_scoped(("port", 1)).is_err());
+
+    let _scope = global::scoped(Figment::from(("port", 80)).merge(("name", "app")));
+    let outer = global::override_scoped(("port", 8080)).unwrap();
+    let inner = global::override_scoped(("name", "test")).unwrap();
+    let figment = global::figment().unwrap();
+    assert_eq!(figment.extract_inner::<u16>("port").unwrap(), 8080);
+    assert_eq!(figment.extract_inner::<String>("name").unwrap(), "test");
+
+    drop(inner);
+    assert_eq!(global::figment().unwrap().extract_inner::<String>("name").unwrap(), "app");
+    drop(outer);
+    assert_eq!(global::figment().unwrap().extract_inner::<u16>("port").unwrap(), 80);
+}
@@ -0,0 +1,23 @@
+use figment::{Figment, Profile, providers::Serialized, util::map};
+
+#[test]
+fn only_param_profiles_are_parameterized() {
+    let figment = Figment::from(Serialized::defaults(map![
+            "name" => figment::value::Value::from("shared"),
+            "env" => map!["prod" => map!["name" => "overlay"]].into(),
+        ]))
+        .merge(Serialized::default("port", 8080).profile("env:prod"));
+
+    let plain = figment.clone().select("env:prod");
+    assert_eq!(plain.profile().param_parts(), None);
+    assert_eq!(plain.extract_inner::<String>("name").unwrap(), "shared");
+    assert_eq!(plain.extract_inner::<u16>("port").unwrap(), 8080);
+
+    let param = figment.clone().select(Profile::param("env", "prod"));
+    assert_eq!(param.profile().param_parts(), Some(("env", "prod")));
+    assert_eq!(param.extract_inner::<String>("name").unwrap(), "overlay");
+    assert_eq!(param.extract_inner::<u16>("port").unwrap(), 8080);
+
+    assert_eq!(Profile::param("env", "prod"), Profile::new("ENV:prod"));
+    assert_eq!(figment.param_values("env"), ["prod"]);
+}
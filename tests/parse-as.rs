@@ -0,0 +1,33 @@
+use figment::value::{Value, FormatKind};
+
+#[test]
+fn infer_matches_from_str() {
+    for s in ["1", "-2", "1.5", "true", "[1, \"a\"]", "{a=1}", "plain text", ""] {
+        assert_eq!(Value::parse_as(s, FormatKind::Infer).unwrap(), s.parse::<Value>().unwrap());
+    }
+
+    assert_eq!(FormatKind::default(), FormatKind::Infer);
+}
+
+#[test]
+fn formats_parse_values_and_documents() {
+    let toml = Value::parse_as("[1, 2]", FormatKind::Toml).unwrap();
+    assert_eq!(toml.into_array().unwrap().len(), 2);
+
+    let toml = Value::parse_as("a = 1\n[b]\nc = \"d\"", FormatKind::Toml).unwrap();
+    assert_eq!(toml.find("b.c").and_then(|v| v.into_string()).as_deref(), Some("d"));
+    assert!(Value::parse_as("a = ", FormatKind::Toml).is_err());
+
+    let yaml = Value::parse_as("a: &x {b: 1}\nc:\n  <<: *x", FormatKind::Yaml).unwrap();
+    assert_eq!(yaml.find_ref("c.b"), yaml.find_ref("a.b"));
+    assert!(yaml.find_ref("c.b").is_some());
+
+    let ron = Value::parse_as("(a: 1, b: [true])", FormatKind::Ron).unwrap();
+    assert_eq!(ron.find("b"), Some(vec![true].into()));
+
+    let json5 = Value::parse_as("{a: 1, /* note */ b: 'x',}", FormatKind::Json5).unwrap();
+    assert_eq!(json5.find("b").and_then(|v| v.into_string()).as_deref(), Some("x"));
+
+    let error = Value::parse_as("{", FormatKind::Json).unwrap_err();
+    assert!(!error.to_string().is_empty());
+}
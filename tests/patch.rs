@@ -0,0 +1,72 @@
+use figment::{Figment, Jail};
+use figment::providers::{Format, Toml, Yaml, MergePatch, JsonPatch, PatchOp};
+
+#[test]
+fn json_patch_file() {
+    Jail::expect_with(|jail| {
+        jail.create_file("Config.toml", r#"
+            [default]
+            "a/b" = 1
+            "m~n" = [1, 2, 3]
+
+            [debug]
+            level = "info"
+        "#)?;
+
+        jail.create_file("patch.json", r#"[
+            { "op": "test", "path": "/a~1b", "value": 1 },
+            { "op": "remove", "path": "/m~0n/1" },
+            { "op": "add", "path": "/m~0n/0", "value": 0 },
+            { "op": "copy", "from": "/a~1b", "path": "/c" }
+        ]"#)?;
+
+        let figment = Figment::from(Toml::file("Config.toml").nested())
+            .merge(JsonPatch::file("patch.json"))
+            .merge(JsonPatch::new(vec![
+                PatchOp::Replace { path: "/level".into(), value: "trace".into() },
+            ]).profile("debug"));
+
+        assert_eq!(figment.extract_inner::<Vec<u8>>("m~n")?, [0, 1, 3]);
+        assert_eq!(figment.extract_inner::<u8>("c")?, 1);
+
+        let debug = figment.select("debug");
+        assert_eq!(debug.extract_inner::<String>("level")?, "trace");
+        let tag = debug.find_value("level")?.tag();
+        assert_eq!(debug.get_metadata(tag).unwrap().name, "JSON Patch");
+
+        Ok(())
+    });
+}
+
+#[test]
+fn json_patch_errors() {
+    let figment = Figment::new().merge(JsonPatch::string(r#"[{ "op": "remove", "path": "/x" }]"#));
+    let error = figment.extract_inner::<u8>("x").unwrap_err();
+    assert!(error.to_string().contains("`remove` at `/x` failed"));
+
+    let figment = Figment::new().merge(JsonPatch::string(r#"[{ "op": "frob", "path": "/x" }]"#));
+    let error = figment.extract_inner::<u8>("x").unwrap_err();
+    assert!(error.to_string().contains("unknown JSON Patch operation `frob`"));
+
+    let figment = Figment::new().merge(JsonPatch::new(vec![
+        PatchOp::Add { path: "/a".into(), value: 1.into() },
+        PatchOp::Move { from: "/a".into(), path: "/a/b".into() },
+    ]));
+
+    assert!(figment.extract_inner::<u8>("a").is_err());
+}
+
+#[test]
+fn merge_patch_yaml() {
+    Jail::expect_with(|jail| {
+        jail.create_file("Config.yaml", "server: { port: 80, hosts: [a, b] }")?;
+        jail.create_file("patch.yaml", "server: { port: ~, hosts: [c] }")?;
+
+        let figment = Figment::from(Yaml::file("Config.yaml"))
+            .merge(MergePatch::new(Yaml::file("patch.yaml")));
+
+        assert!(!figment.contains("server.port"));
+        assert_eq!(figment.extract_inner::<Vec<String>>("server.hosts")?, ["c"]);
+        Ok(())
+    });
+}
@@ -0,0 +1,50 @@
+use serde::Deserialize;
+use figment::{Figment, providers::Serialized, schema::UnknownPolicy};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn plan_matches_extract() {
+    let figment = Figment::new()
+        .merge(Serialized::default("server.host", "localhost"))
+        .merge(Serialized::default("server.port", 80))
+        .merge(Serialized::global("server.port", 8080).profile("prod"))
+        .select("prod");
+
+    let plan = figment.plan_inner::<Server>("server").unwrap();
+    assert_eq!(plan.extract().unwrap(), figment.extract_inner::<Server>("server").unwrap());
+
+    let plan = figment.plan_inner::<u8>("server.port").unwrap();
+    let (planned, direct) = (plan.extract().unwrap_err(), figment.extract_inner::<u8>("server.port").unwrap_err());
+    assert_eq!(planned.to_string(), direct.to_string());
+    assert_eq!(planned.path, ["server", "port"]);
+}
+
+#[test]
+fn plan_checks_unknown_keys() {
+    let figment = Figment::new()
+        .merge(Serialized::default("server", figment::util::map!["host" => "h", "hots" => "x"]))
+        .merge(Serialized::default("server.port", 1))
+        .unknown_keys(UnknownPolicy::DenyAll);
+
+    let plan = figment.plan_inner::<Server>("server").unwrap();
+    let (planned, direct) = (plan.extract().unwrap_err(), figment.extract_inner::<Server>("server").unwrap_err());
+    assert_eq!(planned.to_string(), direct.to_string());
+    assert_eq!(planned.path, ["server", "hots"]);
+
+    let mut plan = figment.plan_inner::<Server>("server").unwrap();
+    plan.reload(&figment.clone().unknown_keys(UnknownPolicy::Allow)).unwrap();
+    assert_eq!(plan.extract().unwrap(), Server { host: "h".into(), port: 1 });
+}
+
+#[test]
+fn plan_fails_on_failed_figment() {
+    let figment = Figment::from(Serialized::default("a", 1))
+        .merge(figment::providers::Data::<figment::providers::Toml>::string("= invalid"));
+
+    assert!(figment.plan::<figment::value::Dict>().is_err());
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "diagnostics")]
+
+use serde::Deserialize;
+use figment::{Figment, providers::{Format, Toml, Json, Serialized}};
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Config {
+    server: Server,
+}
+
+#[test]
+fn pretty_toml_snippet() {
+    figment::Jail::expect_with(|jail| {
+        jail.create_file("Config.toml", "[server]\nhost = \"localhost\"\nport =  \"eighty\" # comment\n")?;
+
+        let error = Figment::from(Toml::file("Config.toml")).extract::<Config>().unwrap_err();
+        assert_eq!(error.pretty().to_string(), "\
+error: invalid type: found string \"eighty\", expected u16
+ --> Config.toml:3:9
+  |
+3 | port =  \"eighty\" # comment
+  |         ^^^^^^^^ for key `server.port`
+  |
+  = note: profile `default`");
+
+        Ok(())
+    });
+}
+
+#[test]
+fn pretty_json_nested_profiles_and_chain() {
+    let json = Json::string(r#"{
+        "default": { "server": { "port": 8000 } },
+        "prod": { "server": { "host": 10, "port": 9000 } }
+    }"#).nested();
+
+    let error = Figment::from(json).select("prod").extract::<Config>().unwrap_err();
+    let pretty = error.pretty().to_string();
+    let blocks: Vec<_> = pretty.split("\n\n").collect();
+    assert_eq!(blocks.len(), 1, "{}", pretty);
+    assert!(pretty.contains("3 |         \"prod\": { \"server\": { \"host\": 10, \"port\": 9000 } }"));
+    assert!(pretty.contains("  |                                       ^^ for key `prod.server.host`"), "{}", pretty);
+    assert!(pretty.contains("= note: profile `prod`"));
+
+    let figment = Figment::from(Json::string(r#"{ "server": {} }"#));
+    let error = figment.extract_all_errors::<Config>().unwrap_err();
+    assert_eq!(error.count(), 2);
+    let pretty = error.pretty().to_string();
+    assert_eq!(pretty.split("\n\n").count(), 2);
+    assert!(pretty.contains("1 | { \"server\": {} }\n  |             ^^ in `default.server`"), "{}", pretty);
+}
+
+#[test]
+fn pretty_without_span() {
+    let figment = Figment::from(Serialized::default("server.port", "eighty"))
+        .merge(Serialized::default("server.host", "localhost"));
+
+    let error = figment.extract::<Config>().unwrap_err();
+    assert!(error.pretty().to_string().starts_with("error: invalid type"));
+    assert!(error.pretty().to_string().contains("\n |\n = note: key `server.port`, profile `default`"));
+}
@@ -0,0 +1,32 @@
+use figment::{Figment, providers::Serialized};
+
+fn figment() -> Figment {
+    Figment::new()
+        .merge(Serialized::default("a", "default"))
+        .merge(Serialized::default("a", "base").profile("base"))
+        .merge(Serialized::default("b", "base").profile("base"))
+        .merge(Serialized::default("b", "staging").profile("staging"))
+        .merge(Serialized::default("c", "staging").profile("staging"))
+        .merge(Serialized::default("c", "prod").profile("prod"))
+        .merge(Serialized::global("d", "global"))
+}
+
+#[test]
+fn parents_cascade_in_order() {
+    let figment = figment().select("prod").with_parent("staging").with_parent("base");
+    assert_eq!(figment.extract_inner::<String>("a").unwrap(), "base");
+    assert_eq!(figment.extract_inner::<String>("b").unwrap(), "staging");
+    assert_eq!(figment.extract_inner::<String>("c").unwrap(), "prod");
+    assert_eq!(figment.extract_inner::<String>("d").unwrap(), "global");
+
+    let figment = figment.select("prod");
+    assert_eq!(figment.extract_inner::<String>("a").unwrap(), "default");
+    assert!(figment.find_value("b").is_err());
+}
+
+#[test]
+fn parents_skip_missing_profiles() {
+    let figment = figment().select("prod").with_parent("missing").with_parent("base");
+    assert_eq!(figment.extract_inner::<String>("b").unwrap(), "base");
+    assert_eq!(figment.extract_inner::<String>("c").unwrap(), "prod");
+}
@@ -0,0 +1,34 @@
+use figment::{Figment, providers::{Serialized, Env, Format, Toml}};
+use figment::value::Dict;
+
+#[test]
+fn provenance_follows_merge_order() {
+    figment::Jail::expect_with(|jail| {
+        jail.set_env("APP_PORT", 9090);
+
+        let figment = Figment::new()
+            .merge(Serialized::default("port", 8080))
+            .merge(Serialized::default("tags", vec!["a", "b"]))
+            .merge(Serialized::default("empty", Dict::new()))
+            .merge(Env::prefixed("APP_"));
+
+        let leaves: Vec<_> = figment.provenance()?
+            .map(|(path, value, md)| (path, value.to_u128(), md.name.to_string()))
+            .collect();
+
+        assert_eq!(leaves, vec![
+            ("port".to_string(), Some(9090), "`APP_` environment variable(s)".to_string()),
+            ("tags".to_string(), None, std::any::type_name::<Vec<&str>>().to_string()),
+        ]);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn provenance_reports_errors() {
+    let figment = Figment::from(Serialized::default("a", 1))
+        .merge(Toml::string("invalid = "));
+
+    assert!(figment.provenance().is_err());
+}
@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use figment::{Provider, Metadata, Profile, Error};
+use figment::value::{Map, Dict};
+use figment::test::{ProviderHarness, Invariant};
+use figment::providers::{Format, Toml};
+use figment::util::map;
+
+struct Counter(AtomicUsize);
+
+impl Provider for Counter {
+    fn metadata(&self) -> Metadata { Metadata::named("counter") }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let n = self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(map![Profile::Default => map!["app".into() => map!["n" => n].into()]])
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        Some(Profile::new(""))
+    }
+}
+
+#[test]
+fn reports_violations() {
+    let report = ProviderHarness::new(Counter(AtomicUsize::new(0))).check();
+    let invariants: Vec<_> = report.failures().iter().map(|f| f.invariant).collect();
+    assert_eq!(invariants, [Invariant::ProfileCorrectness, Invariant::DeterministicData]);
+    assert!(report.failures()[1].message.contains("`app.n` changed"));
+    assert!(report.to_string().contains("provider `counter` violates 2 invariant(s)"));
+}
+
+#[test]
+#[should_panic(expected = "key validity")]
+fn asserts_key_validity() {
+    ProviderHarness::new(Toml::string("\" padded \" = 1")).assert();
+}
+
+#[test]
+fn builtin_providers() {
+    ProviderHarness::new(Toml::string("[a]\nb = [{ c = 1 }]").nested()).assert();
+}
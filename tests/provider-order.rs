@@ -0,0 +1,41 @@
+use std::sync::Mutex;
+
+use figment::{Figment, Provider, Metadata, Profile, Error};
+use figment::value::{Map, Dict};
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<&'static str>>);
+
+impl Recorder {
+    fn record(&self, call: &'static str) {
+        self.0.lock().unwrap().push(call);
+    }
+}
+
+impl Provider for &Recorder {
+    fn metadata(&self) -> Metadata {
+        self.record("metadata");
+        Metadata::named("recorder")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        self.record("data");
+        Ok(Map::new())
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.record("profile");
+        None
+    }
+}
+
+#[test]
+fn data_is_requested_before_profile_and_metadata() {
+    let merged = Recorder::default();
+    let _ = Figment::new().merge(&merged);
+    assert_eq!(*merged.0.lock().unwrap(), ["data", "profile", "metadata"]);
+
+    let joined = Recorder::default();
+    let _ = Figment::new().join(&joined);
+    assert_eq!(*joined.0.lock().unwrap(), ["data", "profile", "metadata"]);
+}
@@ -0,0 +1,55 @@
+use figment::{Figment, Jail, providers::{Format, Toml, Json, Yaml}};
+
+#[test]
+fn raw_json_subtree() {
+    Jail::expect_with(|jail| {
+        jail.create_file("Config.json", r#"{
+            "name": "app",
+            "relabel": [ { "regex": "(.*):\\d+",   "action": "replace" } ],
+            "nested": { "inner": {"b": 2,"a": 1} }
+        }"#)?;
+
+        let figment = Figment::from(Json::file("Config.json"));
+        let (raw, format) = figment.raw_inner("relabel").unwrap();
+        assert_eq!(format, "JSON");
+        assert_eq!(raw, r#"[ { "regex": "(.*):\\d+",   "action": "replace" } ]"#);
+
+        let (raw, _) = figment.raw_inner("nested.inner").unwrap();
+        assert_eq!(raw, r#"{"b": 2,"a": 1}"#);
+
+        let (raw, _) = figment.raw_inner("name").unwrap();
+        assert_eq!(raw, r#""app""#);
+
+        assert!(figment.raw_inner("missing").is_none());
+        Ok(())
+    });
+}
+
+#[test]
+fn raw_nested_profiles() {
+    Jail::expect_with(|jail| {
+        jail.create_file("Config.toml", r#"
+            [default]
+            value = "default"
+
+            [debug]
+            value = 0x10
+        "#)?;
+
+        let figment = Figment::from(Toml::file("Config.toml").nested());
+        assert_eq!(figment.raw_inner("value").unwrap().0, r#""default""#);
+
+        let figment = figment.select("debug");
+        assert_eq!(figment.raw_inner("value").unwrap().0, "0x10");
+        Ok(())
+    });
+}
+
+#[test]
+fn raw_string_and_unsupported() {
+    let figment = Figment::from(Toml::string("list = [1,  2]"))
+        .merge(Yaml::string("other: 1"));
+
+    assert_eq!(figment.raw_inner("list").unwrap(), ("[1,  2]".into(), "TOML"));
+    assert!(figment.raw_inner("other").is_none());
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use figment::{Figment, Jail, providers::{Env, Format, Toml, Json, Serialized}};
+use figment::value::{Case, magic::Tagged};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Camel {
+    server_port: u16,
+    db_url: String,
+}
+
+#[test]
+fn converts_each_source_to_the_target_case() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "server-port = 80\ndb-url = \"a\"")?;
+        jail.create_file("App.json", r#"{ "db_url": "b" }"#)?;
+        jail.set_env("APP_SERVER_PORT", "8080");
+
+        let figment = Figment::new()
+            .rename_all(Case::Camel)
+            .merge(Toml::file("App.toml"))
+            .merge(Json::file("App.json"))
+            .merge(Env::prefixed("APP_"));
+
+        let config: Camel = figment.extract()?;
+        assert_eq!(config.server_port, 8080);
+        assert_eq!(config.db_url, "b");
+
+        let collisions = figment.key_collisions();
+        assert_eq!(collisions.len(), 2);
+        assert!(collisions.iter().any(|c| c.key == "dbUrl"));
+        Ok(())
+    });
+}
+
+#[test]
+fn converts_nested_keys_and_preserves_magic() {
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Config {
+        http_server: Server,
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Server {
+        max_body_size: Tagged<u32>,
+    }
+
+    Jail::expect_with(|jail| {
+        jail.create_file("App.json", r#"{ "HTTPServer": { "maxBodySize": 10 } }"#)?;
+        let figment = Figment::new().rename_all(Case::Snake).merge(Json::file("App.json"));
+        let config: Config = figment.extract()?;
+        assert_eq!(*config.http_server.max_body_size, 10);
+
+        let figment = Figment::new().rename_all(Case::Snake).merge(Serialized::defaults(&config));
+        let config: Config = figment.extract()?;
+        assert_eq!(*config.http_server.max_body_size, 10);
+        Ok(())
+    });
+}
+
+#[test]
+fn converts_between_cases() {
+    let cases = [
+        ("log_level", "log_level", "log-level", "logLevel"),
+        ("logLevel", "log_level", "log-level", "logLevel"),
+        ("LogLevel", "log_level", "log-level", "logLevel"),
+        ("LOG_LEVEL", "log_level", "log-level", "logLevel"),
+        ("ipv6Addr", "ipv6_addr", "ipv6-addr", "ipv6Addr"),
+        ("parseURLPath", "parse_url_path", "parse-url-path", "parseUrlPath"),
+        ("__dunder__", "__dunder__", "__dunder__", "__dunder__"),
+        ("a--b", "a_b", "a-b", "aB"),
+        ("", "", "", ""),
+    ];
+
+    for (key, snake, kebab, camel) in cases.iter() {
+        assert_eq!(Case::Snake.convert(key), *snake, "{}", key);
+        assert_eq!(Case::Kebab.convert(key), *kebab, "{}", key);
+        assert_eq!(Case::Camel.convert(key), *camel, "{}", key);
+    }
+}
@@ -0,0 +1,61 @@
+use figment::{Figment, ArrayMerge, providers::Serialized, util::map};
+
+fn build(port: Serialized<u16>) -> Figment {
+    Figment::new()
+        .record_shadowed(true)
+        .retain_layers(true)
+        .arrays_at("tags", ArrayMerge::Append)
+        .merge(Serialized::defaults(map!["tags" => vec!["a"], "name" => vec!["app"]]))
+        .merge(("server", map!["host" => "localhost", "port" => "80"]))
+        .admerge(Serialized::default("tags", vec!["b"]))
+        .merge(port)
+        .join(Serialized::default("server.port", 1))
+        .merge(Serialized::from(map!["port" => 3], "debug").key("server"))
+}
+
+#[test]
+fn replace_layer_matches_rebuild() {
+    let replacements = [
+        Serialized::default("server.port", 9090),
+        Serialized::global("server.port", 9090),
+        Serialized::default("tags.x", 1),
+        Serialized::from(9090, "debug").key("other"),
+    ];
+
+    for replacement in replacements {
+        let figment = build(Serialized::default("server.port", 8080));
+        let replaced = figment.replace_layer(3, replacement.clone());
+        let rebuilt = build(replacement);
+
+        assert!(replaced.content_eq(&rebuilt), "{:?} != {:?}", replaced, rebuilt);
+        assert_eq!(replaced.layers().len(), rebuilt.layers().len());
+        assert_eq!(replaced.shadowed("server.port").len(), rebuilt.shadowed("server.port").len());
+        for profile in ["default", "debug"] {
+            let (replaced, rebuilt) = (replaced.clone().select(profile), rebuilt.clone().select(profile));
+            assert_eq!(replaced.extract_inner::<figment::value::Value>("server").unwrap(),
+                rebuilt.extract_inner::<figment::value::Value>("server").unwrap());
+        }
+    }
+}
+
+#[test]
+fn replace_layer_errors() {
+    let figment = Figment::new()
+        .retain_layers(true)
+        .limits(figment::value::Limits::none().string_len(4))
+        .merge(("port", 80))
+        .merge(("port", 8080));
+
+    let failing = figment.replace_layer(1, ("port", "too long"));
+    assert!(failing.extract_inner::<u16>("port").is_err());
+
+    let recovered = failing.replace_layer(1, ("port", 9090));
+    assert_eq!(recovered.extract_inner::<u16>("port").unwrap(), 9090);
+
+    let unchanged = Figment::from(("a", 1)).replace_layer(7, ("a", 2));
+    assert_eq!(unchanged.extract_inner::<u8>("a").unwrap(), 1);
+
+    let unretained = Figment::from(("a", 1)).replace_layer(0, ("a", 2));
+    let error = unretained.extract_inner::<u8>("a").unwrap_err();
+    assert!(error.to_string().contains("retain_layers"));
+}
@@ -0,0 +1,85 @@
+use serde::Deserialize;
+use figment::{Figment, Jail, providers::{Format, Ron, Toml}};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    name: String,
+    limits: Limits,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Limits {
+    workers: u16,
+    ratio: f64,
+}
+
+#[test]
+fn maps_structs_and_merging() {
+    Jail::expect_with(|jail| {
+        jail.create_file("Base.toml", "name = \"base\"\ntags = [\"a\"]\n[limits]\nworkers = 2\nratio = 0.5")?;
+        jail.create_file("App.ron", r#"
+            #![enable(implicit_some)]
+            (
+                limits: { "workers": 8 },
+                tags: ["b", "c"],
+            )
+        "#)?;
+
+        let config: Config = Figment::from(Toml::file("Base.toml"))
+            .merge(Ron::file("App.ron"))
+            .extract()?;
+
+        assert_eq!(config, Config {
+            name: "base".into(),
+            limits: Limits { workers: 8, ratio: 0.5 },
+            tags: vec!["b".into(), "c".into()],
+        });
+
+        Ok(())
+    });
+}
+
+#[test]
+fn errors_name_the_file() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.ron", "(name: \"app\",\n  tags: [1,, 2])")?;
+        let error = Figment::from(Ron::file("App.ron")).extract::<Config>().unwrap_err();
+        assert!(error.to_string().starts_with("2:12: "));
+        assert!(error.to_string().contains("RON file"));
+        Ok(())
+    });
+}
+
+#[test]
+fn nested_profiles_and_positions() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct App {
+        name: String,
+        ports: Vec<u16>,
+        tls: Option<bool>,
+    }
+
+    Jail::expect_with(|jail| {
+        jail.create_file("App.ron", r#"
+            (
+                default: (name: "app", ports: [80, 443]),
+                debug: (ports: [8080], tls: false),
+            )
+        "#)?;
+
+        let figment = Figment::from(Ron::file("App.ron").nested());
+        let config: App = figment.extract()?;
+        assert_eq!(config, App { name: "app".into(), ports: vec![80, 443], tls: None });
+
+        let config: App = figment.select("debug").extract()?;
+        assert_eq!(config, App { name: "app".into(), ports: vec![8080], tls: Some(false) });
+
+        let figment = Figment::from(Ron::string("(name: \"app\", ports: [1,])"));
+        assert_eq!(figment.extract_inner::<Vec<u16>>("ports")?, [1]);
+
+        let error = Figment::from(Ron::string("(name: )")).extract::<App>().unwrap_err();
+        assert!(error.to_string().starts_with("1:8: "));
+        Ok(())
+    });
+}
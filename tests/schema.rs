@@ -0,0 +1,150 @@
+#![cfg(feature = "schema")]
+
+use std::net::SocketAddr;
+use std::num::NonZeroU16;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use figment::{Figment, util::map, value::Value};
+use figment::schema::{Schema, OnMismatch};
+use figment::value::magic::RelativePathBuf;
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+#[serde(untagged)]
+enum Untagged { Num(u8), Str(String) }
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+enum Mode { Listen(SocketAddr), Off }
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Inner {
+    addr: SocketAddr,
+    workers: NonZeroU16,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Config {
+    inner: Inner,
+    mode: Mode,
+    either: Untagged,
+    pair: (u8, bool),
+    dir: RelativePathBuf,
+    path: PathBuf,
+    maybe: Option<Inner>,
+    list: Vec<Option<i64>>,
+    value: Value,
+    after: f32,
+}
+
+#[test]
+fn schema_of_traces_past_failing_fields() {
+    let inner = Schema::Struct(map! {
+        "addr".into() => Schema::String,
+        "workers".into() => Schema::U16,
+        "name".into() => Schema::String,
+    });
+
+    assert_eq!(Schema::of::<Config>(), Schema::Struct(map! {
+        "inner".into() => inner.clone(),
+        "mode".into() => Schema::Enum(vec!["Listen".into(), "Off".into()]),
+        "either".into() => Schema::Any,
+        "pair".into() => Schema::Tuple(vec![Schema::U8, Schema::Bool]),
+        "dir".into() => Schema::String,
+        "path".into() => Schema::String,
+        "maybe".into() => Schema::Option(Box::new(inner)),
+        "list".into() => Schema::Seq(Box::new(Schema::Option(Box::new(Schema::I64)))),
+        "value".into() => Schema::Any,
+        "after".into() => Schema::F32,
+    }));
+}
+
+#[test]
+fn check_merges_policies() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct App { port: u16, tags: Vec<String>, deep: Option<Inner> }
+
+    let bad = ("deep", map!["workers" => "many"]);
+    let warn = Figment::new()
+        .check_merges(Schema::of::<App>(), OnMismatch::Warn)
+        .merge(("port", 10))
+        .merge(bad.clone());
+
+    assert_eq!(warn.warnings().len(), 1);
+    assert_eq!(warn.warnings()[0].path, vec!["deep", "workers"]);
+    assert_eq!(warn.find_value("deep.workers").unwrap(), "many".into());
+
+    let drop = Figment::new()
+        .check_merges(Schema::of::<App>(), OnMismatch::Drop)
+        .merge(("deep.name", "ok"))
+        .merge(bad.clone());
+
+    assert_eq!(drop.warnings().len(), 1);
+    assert!(drop.find_value("deep.workers").is_err());
+    assert!(drop.find_value("deep.name").is_ok());
+
+    let reject = Figment::new()
+        .check_merges(Schema::of::<App>(), OnMismatch::Reject)
+        .merge(("port", -1))
+        .merge(bad)
+        .merge(("tags", vec![1]));
+
+    let paths: Vec<_> = reject.find_value("port").unwrap_err().into_iter().map(|e| e.path).collect();
+    assert_eq!(paths, vec![vec!["port"], vec!["deep", "workers"], vec!["tags", "0"]]);
+}
+
+#[test]
+fn unknown_key_policies() {
+    use figment::schema::UnknownPolicy;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Item { name: String }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Nested { items: Vec<Item>, nested: Option<Box<App>> }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct App { items: Vec<Item>, nested: Option<Nested> }
+
+    let figment = Figment::new()
+        .merge(("items", vec![map!["name" => "a", "extra" => "b"]]))
+        .merge(("nested.items", Vec::<u8>::new()))
+        .merge(("nested.bogus", map!["x" => 1]))
+        .merge(("nested.note", "ok"))
+        .merge(("other", map!["x" => 1]))
+        .merge(("loose", 1))
+        .unknown_keys(UnknownPolicy::DenySections)
+        .unknown_keys_at("items", UnknownPolicy::DenyAll);
+
+    let paths: Vec<_> = figment.extract::<App>().unwrap_err().into_iter().map(|e| e.path).collect();
+    assert_eq!(paths, vec![vec!["items", "0", "extra"], vec!["nested", "bogus"], vec!["other"]]);
+
+    // Focusing preserves the policies, relative to the new root.
+    let err = figment.focus("nested").extract::<Nested>().unwrap_err();
+    assert_eq!(err.count(), 1);
+    assert_eq!(err.path, vec!["bogus"]);
+
+    let figment = figment.unknown_keys_at("nested", UnknownPolicy::Allow);
+    assert!(figment.focus("nested").extract::<Nested>().is_ok());
+    assert!(figment.extract_inner::<Nested>("nested").is_ok());
+
+    // Recursive types are checked up to their first recurrence.
+    let find = |schema: &Schema, field: &str| match schema {
+        Schema::Struct(fields) => match &fields[field] {
+            Schema::Option(schema) => (**schema).clone(),
+            schema => schema.clone(),
+        },
+        _ => panic!("expected a struct"),
+    };
+
+    let nested = find(&Schema::of::<App>(), "nested");
+    assert_eq!(find(&nested, "nested"), Schema::Any);
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use figment::{Figment, Jail, providers::{Format, Toml, Json, Serialized}};
+use figment::value::magic::Secret;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Config {
+    name: String,
+    token: Secret<String>,
+    keys: Option<Secret<Vec<String>>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Credentials {
+    #[allow(dead_code)]
+    user: String,
+}
+
+#[test]
+fn secrets_are_redacted_when_formatted() {
+    let token = Secret::from("hunter2".to_string());
+    assert_eq!(format!("{:?}", token), "Secret([REDACTED])");
+    assert_eq!(token.to_string(), "[REDACTED]");
+    assert_eq!(format!("{:#?}", Some(&token)), "Some(\n    Secret(\n        [REDACTED],\n    ),\n)");
+}
+
+#[test]
+fn secrets_are_redacted_in_serialization_and_provenance() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "name = \"app\"\ntoken = \"hunter2\"\nkeys = [\"a1\", \"b2\"]")?;
+        let config: Config = Figment::from(Toml::file("App.toml")).extract()?;
+        assert_eq!(config.token.expose(), "hunter2");
+        assert_eq!(config.keys.as_ref().unwrap().expose(), &["a1", "b2"]);
+
+        let figment = Figment::from(Serialized::defaults(&config));
+        let again: Config = figment.extract()?;
+        assert_eq!(again.token, config.token);
+        assert_eq!(again.keys, config.keys);
+        assert_eq!(again.token.tag(), config.token.tag());
+
+        let json = figment.serialize_to::<Json>()?;
+        assert!(!json.contains("hunter2") && !json.contains("a1"), "{}", json);
+
+        let provenance: Vec<_> = figment.provenance()?.map(|(k, v, _)| (k, v)).collect();
+        assert_eq!(provenance.len(), 3);
+        for (key, value) in provenance {
+            match key.as_str() {
+                "token" | "keys" => assert_eq!(value.as_str(), Some("[REDACTED]")),
+                _ => assert_eq!(value.as_str(), Some("app")),
+            }
+        }
+
+        Ok(())
+    });
+}
+
+#[test]
+fn errors_do_not_mention_secrets() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "pin = \"hunter2\"\ncreds = { user = \"u\", password = \"hunter2\" }")?;
+        let figment = Figment::from(Toml::file("App.toml")).error_samples(4);
+
+        let error = figment.extract_inner::<Secret<u16>>("pin").unwrap_err();
+        assert!(!error.to_string().contains("hunter2"), "{}", error);
+        assert!(!format!("{:?}", error).contains("hunter2"), "{:?}", error);
+
+        let error = figment.extract_inner::<Secret<Credentials>>("creds").unwrap_err();
+        assert!(!format!("{:?}", error).contains("hunter2"), "{:?}", error);
+
+        // Values that aren't secret are reported as usual.
+        let error = figment.extract_inner::<u16>("pin").unwrap_err();
+        assert!(error.to_string().contains("hunter2"), "{}", error);
+        Ok(())
+    });
+}
@@ -0,0 +1,32 @@
+use figment::{Figment, Profile, providers::{Format, Serialized, Toml}};
+
+#[test]
+fn select_with_sees_provided_profiles() {
+    let figment = Figment::new()
+        .merge(Serialized::default("a", 1).profile("zeta"))
+        .merge(Serialized::global("b", 2))
+        .merge(Serialized::default("c", 3))
+        .merge(Serialized::default("d", 4).profile("alpha"));
+
+    let mut seen = vec![];
+    let figment = figment.select_with(|profiles: &[Profile]| {
+        seen = profiles.to_vec();
+        profiles.iter().find(|p| p.as_str().starts_with("z")).cloned().unwrap_or_default()
+    });
+
+    assert_eq!(seen, [Profile::from("alpha"), Profile::Default, Profile::Global, Profile::from("zeta")]);
+    assert_eq!(figment.profile(), "zeta");
+    assert_eq!(figment.extract_inner::<u8>("a").unwrap(), 1);
+    assert_eq!(figment.extract_inner::<u8>("c").unwrap(), 3);
+}
+
+#[test]
+fn select_with_failed_figment() {
+    let figment = Figment::from(Toml::file("missing.toml").required(true))
+        .select_with(|profiles: &[Profile]| {
+            assert!(profiles.is_empty());
+            "fallback"
+        });
+
+    assert_eq!(figment.profile(), "fallback");
+}
@@ -0,0 +1,55 @@
+use serde::de::DeserializeOwned;
+use figment::{Figment, util::map, value::Value};
+use figment::providers::{Format, Json, Json5, Ron, Toml, Yaml, Serialized};
+
+fn figment() -> Figment {
+    Figment::from(Serialized::defaults(map![
+        "name" => Value::from("app"),
+        "ratio" => Value::from(0.5),
+        "servers" => Value::from(vec![map!["host" => "a"], map!["host" => "b"]]),
+        "limits" => Value::from(map!["workers" => 4]),
+    ]))
+    .merge(Serialized::from(map!["workers" => 8], "debug").key("limits"))
+}
+
+fn round_trip<F: Format>(figment: &Figment) {
+    let text = figment.serialize_to::<F>().unwrap();
+    let parsed = Figment::from(F::string(&text));
+    let expected = Figment::from(Serialized::defaults(figment.extract::<Value>().unwrap()));
+    assert!(parsed.content_eq(&expected), "{}:\n{}", F::NAME, text);
+}
+
+#[test]
+fn formats_round_trip() {
+    for figment in [figment(), figment().select("debug")] {
+        round_trip::<Toml>(&figment);
+        round_trip::<Json>(&figment);
+        round_trip::<Yaml>(&figment);
+        round_trip::<Ron>(&figment);
+        round_trip::<Json5>(&figment);
+    }
+
+    let debug = Figment::from(Toml::string(&figment().select("debug").serialize_to::<Toml>().unwrap()));
+    assert_eq!(debug.extract_inner::<u8>("limits.workers").unwrap(), 8);
+}
+
+#[test]
+fn unsupported_values_and_formats() {
+    struct Custom;
+
+    impl Format for Custom {
+        type Error = serde_json::Error;
+        const NAME: &'static str = "Custom";
+
+        fn from_str<T: DeserializeOwned>(string: &str) -> Result<T, Self::Error> {
+            serde_json::from_str(string)
+        }
+    }
+
+    let error = figment().serialize_to::<Custom>().unwrap_err();
+    assert_eq!(error.to_string(), "failed to serialize as Custom: Custom does not support serialization");
+
+    let figment = Figment::from(Serialized::default("empty", ()));
+    assert!(figment.serialize_to::<Toml>().is_err());
+    assert_eq!(figment.serialize_to::<Json>().unwrap(), "{\n  \"empty\": null\n}");
+}
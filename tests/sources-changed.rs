@@ -0,0 +1,44 @@
+use figment::{Figment, Jail, providers::{Format, Toml, Env}};
+
+#[test]
+fn missing_and_deleted_files_are_changes() {
+    Jail::expect_with(|jail| {
+        jail.create_file("Base.toml", "port = 8080")?;
+        jail.set_env("APP_NAME", "app");
+
+        let figment = Figment::new()
+            .merge(Toml::file("Base.toml").checksum(true))
+            .merge(Toml::file("Local.toml").checksum(true))
+            .merge(Env::prefixed("APP_"));
+
+        assert!(figment.sources_changed().is_empty());
+
+        jail.create_file("Local.toml", "port = 9090")?;
+        let changed = figment.sources_changed();
+        assert_eq!(changed.len(), 1);
+        assert!(changed[0].source.as_ref().unwrap().file_path().unwrap().ends_with("Local.toml"));
+
+        std::fs::remove_file(jail.directory().join("Base.toml")).unwrap();
+        assert_eq!(figment.sources_changed().len(), 2);
+
+        jail.set_env("APP_NAME", "other");
+        assert_eq!(figment.sources_changed().len(), 2);
+        Ok(())
+    });
+}
+
+#[test]
+fn checksums_are_opt_in() {
+    Jail::expect_with(|jail| {
+        jail.create_file("App.toml", "port = 8080")?;
+        let figment = Figment::from(Toml::file("App.toml"));
+        assert!(figment.find_metadata("port").unwrap().get_checksum().is_none());
+
+        jail.create_file("App.toml", "port = 9090")?;
+        assert!(figment.sources_changed().is_empty());
+
+        let figment = Figment::from(Toml::string("port = 8080").checksum(true));
+        assert!(figment.find_metadata("port").unwrap().get_checksum().is_none());
+        Ok(())
+    });
+}
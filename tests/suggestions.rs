@@ -0,0 +1,55 @@
+use serde::Deserialize;
+use figment::{Figment, providers::Serialized, schema::UnknownPolicy};
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Config {
+    database: Database,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Database {
+    url: String,
+    #[serde(default)]
+    pool_size: u32,
+}
+
+#[test]
+fn missing_field_suggests_provided_key() {
+    let figment = Figment::from(Serialized::default("databse.url", "x"));
+    let error = figment.extract::<Config>().unwrap_err();
+    assert_eq!(error.suggestion(), Some("databse"));
+    assert!(error.to_string().starts_with("missing field `database`; did you misspell it as `databse`?"));
+
+    let figment = Figment::from(Serialized::default("database.URL", "x"));
+    let error = figment.extract_inner::<Database>("database").unwrap_err();
+    assert_eq!(error.path, ["database"]);
+    assert_eq!(error.suggestion(), Some("URL"));
+
+    let figment = Figment::from(Serialized::default("database.other", "x"));
+    let error = figment.extract::<Config>().unwrap_err();
+    assert_eq!(error.suggestion(), None);
+}
+
+#[test]
+fn unknown_key_suggests_field() {
+    let figment = Figment::from(Serialized::default("database.url", "x"))
+        .merge(Serialized::default("database.poolsize", 5))
+        .unknown_keys(UnknownPolicy::DenyAll);
+
+    let error = figment.extract::<Config>().unwrap_err();
+    assert_eq!(error.suggestion(), Some("pool_size"));
+    assert!(error.to_string().contains("did you mean `pool_size`?"));
+
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    #[allow(dead_code)]
+    struct Strict {
+        timeout: u32,
+    }
+
+    let figment = Figment::from(Serialized::default("timout", 5));
+    let error = figment.extract::<Strict>().unwrap_err();
+    assert_eq!(error.suggestion(), Some("timeout"));
+}
@@ -0,0 +1,76 @@
+use figment::{Figment, TypeConflict, providers::Serialized};
+use figment::error::Kind;
+use figment::util::map;
+
+fn figment(policy: TypeConflict) -> Figment {
+    Figment::new().type_conflicts(policy)
+}
+
+#[test]
+fn prefer_higher_layer_is_the_default() {
+    let figment = Figment::new()
+        .merge(Serialized::default("port", 9090))
+        .merge(Serialized::default("port", "8080"));
+
+    assert_eq!(figment.extract_inner::<String>("port").unwrap(), "8080");
+}
+
+#[test]
+fn prefer_typed_parses_strings() {
+    let merged = figment(TypeConflict::PreferTyped)
+        .merge(Serialized::default("port", 9090))
+        .merge(Serialized::default("port", "8080"));
+
+    assert_eq!(merged.find_value("port").unwrap().to_u128(), Some(8080));
+    let tag = merged.find_value("port").unwrap().tag();
+    assert_eq!(merged.get_metadata(tag).unwrap().name, merged.metadata().last().unwrap().name);
+
+    // Unparseable strings lose to the typed value.
+    let merged = merged.merge(Serialized::default("port", "eighty"));
+    assert_eq!(merged.extract_inner::<u16>("port").unwrap(), 8080);
+
+    // A joined string doesn't replace the existing value either way.
+    let joined = figment(TypeConflict::PreferTyped)
+        .merge(Serialized::default("debug", "true"))
+        .join(Serialized::default("debug", false));
+
+    assert_eq!(joined.extract_inner::<bool>("debug").unwrap(), true);
+
+    // A typed value merged over a string is used as usual.
+    let merged = figment(TypeConflict::PreferTyped)
+        .merge(Serialized::default("port", "8080"))
+        .merge(Serialized::default("port", 9090));
+
+    assert_eq!(merged.extract_inner::<u16>("port").unwrap(), 9090);
+}
+
+#[test]
+fn error_reports_each_conflict() {
+    let strict = figment(TypeConflict::Error)
+        .merge(Serialized::default("server", map!["port" => 9090]))
+        .merge(Serialized::default("server.port", 9091))
+        .merge(Serialized::default("server.host", "localhost"))
+        .merge(Serialized::default("server.port", "8080"));
+
+    let error = strict.extract_inner::<u16>("server.port").unwrap_err();
+    assert_eq!(error.count(), 1);
+    assert_eq!(error.path, vec!["server", "port"]);
+    assert!(matches!(error.kind, Kind::InvalidType(..)));
+    assert!(error.metadata.is_some());
+
+    let strict = figment(TypeConflict::Error)
+        .merge(Serialized::default("server", map!["port" => 9090]))
+        .merge(Serialized::default("server", "localhost:9090"));
+
+    assert!(strict.extract_inner::<String>("server").is_err());
+
+    // Nulls and numbers of different kinds don't conflict.
+    let lenient = figment(TypeConflict::Error)
+        .merge(Serialized::default("ratio", 1))
+        .merge(Serialized::default("ratio", -0.5))
+        .merge(Serialized::default("name", Option::<String>::None))
+        .merge(Serialized::default("name", "app"));
+
+    assert_eq!(lenient.extract_inner::<f64>("ratio").unwrap(), -0.5);
+    assert_eq!(lenient.extract_inner::<String>("name").unwrap(), "app");
+}
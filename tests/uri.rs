@@ -0,0 +1,69 @@
+use figment::{Figment, Jail};
+
+#[test]
+fn uri_schemes() {
+    Jail::expect_with(|jail| {
+        jail.create_file("app.conf", "name: yaml")?;
+        jail.create_file("my app.json", r#"{ "name": "json" }"#)?;
+
+        let absolute = jail.directory().join("my%20app.json");
+        let figment = Figment::from_uri(&format!("file://{}", absolute.display()));
+        assert_eq!(figment.extract_inner::<String>("name")?, "json");
+
+        let figment = Figment::from_uri("file://localhost/nonexistent/app.toml");
+        assert!(figment.extract_inner::<String>("name").is_err());
+
+        let figment = Figment::from_uri("file:app.conf?format=yaml");
+        assert_eq!(figment.extract_inner::<String>("name")?, "yaml");
+        assert!(Figment::from_uri("file:app.conf").extract_inner::<String>("name").is_err());
+        assert!(Figment::from_uri("file://host/app.toml").extract_inner::<String>("name").is_err());
+
+        let figment = Figment::from_uri("data:text/yaml;charset=utf-8;base64,bmFtZTogYjY0");
+        assert_eq!(figment.extract_inner::<String>("name")?, "b64");
+
+        let figment = Figment::from_uri("data:application/toml,name%20=%20%22pct%22");
+        assert_eq!(figment.extract_inner::<String>("name")?, "pct");
+
+        let err = Figment::from_uri("ftp://example.com/app.toml").extract_inner::<String>("name");
+        assert!(err.unwrap_err().to_string().contains("unsupported scheme `ftp`"));
+
+        let err = Figment::from_uri("data:;base64,AAAA").extract_inner::<String>("name");
+        assert!(err.unwrap_err().to_string().contains("missing media type"));
+        Ok(())
+    });
+}
+
+#[test]
+#[cfg(not(feature = "http"))]
+fn http_uris_name_the_feature() {
+    let err = Figment::from_uri("http://example.com/app.toml").extract_inner::<String>("name");
+    assert!(err.unwrap_err().to_string().contains("require the `http` feature"));
+}
+
+#[test]
+#[cfg(all(feature = "http", feature = "toml"))]
+fn http_uris_dispatch_to_http() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 { line.clear(); }
+
+        let body = "name = \"http\"";
+        let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+        stream.write_all(head.as_bytes()).unwrap();
+        stream.write_all(body.as_bytes()).unwrap();
+    });
+
+    let figment = Figment::from_uri(&format!("http://{}/config?format=toml", addr));
+    assert_eq!(figment.extract_inner::<String>("name").unwrap(), "http");
+
+    let figment = Figment::from_uri(&format!("http://{}/config", addr));
+    let err = figment.extract_inner::<String>("name").unwrap_err();
+    assert!(err.to_string().contains("no extension and no `format`"));
+}
@@ -0,0 +1,58 @@
+use figment::{Figment, FigmentView, providers::Serialized, schema::UnknownPolicy};
+
+fn assert_send_sync<T: Send + Sync + Clone>() {}
+
+#[test]
+fn view_is_send_sync_and_matches_figment() {
+    assert_send_sync::<FigmentView>();
+
+    let figment = Figment::new()
+        .merge(Serialized::default("a", 1))
+        .merge(Serialized::default("b", 2))
+        .unknown_keys(UnknownPolicy::DenyAll);
+
+    let view = figment.view();
+    let planned = view.extract::<figment::value::Dict>().unwrap();
+    assert_eq!(planned, figment.extract::<figment::value::Dict>().unwrap());
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct OnlyA { a: u8 }
+
+    let (viewed, direct) = (view.extract::<OnlyA>().unwrap_err(), figment.extract::<OnlyA>().unwrap_err());
+    assert_eq!(viewed.to_string(), direct.to_string());
+    assert_eq!(view.profile(), figment.profile());
+    assert_eq!(view.metadata().count(), 2);
+}
+
+#[test]
+fn view_of_failed_figment() {
+    let figment = Figment::from(Serialized::default("a", 1))
+        .merge(figment::providers::Data::<figment::providers::Toml>::string("= invalid"));
+
+    let view = FigmentView::from(figment);
+    assert!(view.extract::<figment::value::Dict>().is_err());
+    assert!(view.find_value("a").is_err());
+    assert!(!view.contains("a"));
+}
+
+#[test]
+fn view_lookups_match_figment() {
+    let figment = Figment::new()
+        .merge(Serialized::default("server.port", 80))
+        .merge(Serialized::default("server.host", "a"))
+        .merge(Serialized::default("server.port", 443).profile("prod"))
+        .select("prod");
+
+    let view = figment.view();
+    assert_eq!(view.extract_inner::<u16>("server.port").unwrap(), 443);
+    assert_eq!(view.find_value("server").unwrap(), figment.find_value("server").unwrap());
+    assert!(view.contains("server.host"));
+    assert!(!view.contains("server.tls"));
+
+    let (viewed, direct) = (view.find_metadata("server.port"), figment.find_metadata("server.port"));
+    assert_eq!(viewed.map(|m| &m.name), direct.map(|m| &m.name));
+
+    let (viewed, direct) = (view.extract_inner::<u16>("nope").unwrap_err(), figment.extract_inner::<u16>("nope").unwrap_err());
+    assert_eq!(viewed.to_string(), direct.to_string());
+}
@@ -0,0 +1,42 @@
+use figment::{Figment, util::map};
+use figment::value::{Value, Visit};
+
+#[test]
+fn walk_skip_and_stop() {
+    let value = Value::from(map! {
+        "a" => map!["x" => 1, "y" => 2],
+        "b" => map!["x" => 3],
+        "c" => map!["x" => 4],
+    });
+
+    let mut visited = vec![];
+    value.walk(&mut |path: &[String], _: &Value| {
+        visited.push(path.join("."));
+        match path.join(".").as_str() {
+            "a" => Visit::SkipChildren,
+            "b.x" => Visit::Stop,
+            _ => Visit::Continue,
+        }
+    });
+
+    assert_eq!(visited, ["", "a", "b", "b.x"]);
+}
+
+#[test]
+fn walk_tags() {
+    let figment = Figment::new()
+        .merge(("a", map!["x" => 1]))
+        .merge(("b", 2));
+
+    let value = figment.find_value("").unwrap();
+    let mut tags = vec![];
+    value.walk(&mut |path: &[String], value: &Value| {
+        if !path.is_empty() {
+            tags.push((path.join("."), figment.get_metadata(value.tag()).is_some()));
+        }
+
+        Visit::Continue
+    });
+
+    assert_eq!(tags, [("a".into(), true), ("a.x".into(), true), ("b".into(), true)]);
+}
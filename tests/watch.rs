@@ -0,0 +1,61 @@
+#![cfg(all(feature = "watch", feature = "toml"))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use serde::Deserialize;
+use figment::{Figment, Jail, providers::{Format, Toml}};
+use figment::watch::{Poll, Watch, Watcher};
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    workers: u16,
+}
+
+#[test]
+fn poll_waits_for_interval() {
+    Jail::expect_with(|jail| {
+        let path = jail.directory().join("App.toml");
+        jail.create_file(&path, "workers = 4")?;
+
+        let mut poll = Poll::every(Duration::from_millis(500));
+        poll.watch(&[path.clone()]);
+        jail.create_file(&path, "workers = 16")?;
+        assert!(!poll.wait(Duration::from_millis(10)));
+        assert!(!poll.wait(Duration::from_millis(10)));
+        assert!(poll.wait(Duration::from_secs(5)));
+        assert!(!poll.wait(Duration::from_millis(10)));
+        Ok(())
+    });
+}
+
+#[test]
+fn changes_made_while_building_are_detected() {
+    Jail::expect_with(|jail| {
+        let path = jail.directory().join("App.toml");
+        jail.create_file(&path, "workers = 4")?;
+
+        let builds = Arc::new(AtomicUsize::new(0));
+        let build = {
+            let (path, builds) = (path.clone(), builds.clone());
+            move || {
+                let figment = Figment::from(Toml::file(&path));
+                if builds.fetch_add(1, Ordering::SeqCst) == 1 {
+                    std::fs::write(&path, "workers = 16").unwrap();
+                }
+
+                figment
+            }
+        };
+
+        let (handle, configs) = Watch::new(build)
+            .watcher(Poll::every(Duration::from_millis(10)))
+            .channel::<Config>();
+
+        assert_eq!(configs.recv().unwrap()?.workers, 4);
+        assert_eq!(configs.recv_timeout(Duration::from_secs(5)).unwrap()?.workers, 16);
+        handle.stop();
+        Ok(())
+    });
+}
@@ -0,0 +1,93 @@
+use std::num::{NonZeroU8, NonZeroU32, NonZeroI64, NonZeroU128, NonZeroI128};
+
+use serde::Deserialize;
+use figment::{Figment, Jail, providers::{Env, Format, Json, Toml, Serialized}};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    id: u128,
+    offset: i128,
+    workers: NonZeroU8,
+    limit: NonZeroU128,
+    delta: NonZeroI128,
+}
+
+const MAX: &str = "340282366920938463463374607431768211455";
+const MIN: &str = "-170141183460469231731687303715884105728";
+
+#[test]
+fn wide_ints_from_strings() {
+    Jail::expect_with(|jail| {
+        jail.set_env("APP_ID", MAX);
+        jail.set_env("APP_OFFSET", MIN);
+        jail.set_env("APP_WORKERS", 4);
+        jail.set_env("APP_LIMIT", MAX);
+        jail.set_env("APP_DELTA", MIN);
+
+        let config: Config = Figment::from(Env::prefixed("APP_")).extract()?;
+        assert_eq!(config.id, u128::MAX);
+        assert_eq!(config.offset, i128::MIN);
+        assert_eq!(config.workers.get(), 4);
+        assert_eq!(config.limit.get(), u128::MAX);
+        assert_eq!(config.delta.get(), i128::MIN);
+
+        jail.create_file("Config.toml", &format!(r#"
+            id = "{}"
+            offset = -10
+            workers = 1
+            limit = 10
+            delta = "{}"
+        "#, MAX, MIN))?;
+
+        let config: Config = Figment::from(Toml::file("Config.toml")).extract()?;
+        assert_eq!(config.id, u128::MAX);
+        assert_eq!(config.offset, -10);
+        assert_eq!(config.limit.get(), 10);
+        assert_eq!(config.delta.get(), i128::MIN);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn wide_ints_from_numbers() {
+    Jail::expect_with(|jail| {
+        jail.create_file("Config.json", r#"{
+            "id": 18446744073709551615,
+            "offset": -9223372036854775808,
+            "workers": 2,
+            "limit": 1,
+            "delta": -1
+        }"#)?;
+
+        let config: Config = Figment::from(Json::file("Config.json")).extract()?;
+        assert_eq!(config.id, u64::MAX as u128);
+        assert_eq!(config.offset, i64::MIN as i128);
+
+        let figment = Figment::from(Serialized::default("id", u128::MAX));
+        assert_eq!(figment.extract_inner::<u128>("id")?, u128::MAX);
+        assert!(figment.extract_inner::<u64>("id").is_err());
+
+        let figment = Figment::from(Serialized::default("small", 5u128))
+            .merge(Serialized::default("negative", -5i128));
+
+        assert_eq!(figment.extract_inner::<u8>("small")?, 5);
+        assert_eq!(figment.extract_inner::<i16>("negative")?, -5);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn nonzero_errors_are_clear() {
+    let figment = Figment::from(("workers", 0)).merge(("id", "x"));
+    let error = figment.extract_inner::<NonZeroU32>("workers").unwrap_err();
+    assert!(error.to_string().contains("expected a nonzero u32"), "{}", error);
+    assert_eq!(error.path, vec!["workers"]);
+
+    let error = figment.extract_inner::<NonZeroI64>("id").unwrap_err();
+    assert!(error.to_string().contains("expected a nonzero i64"), "{}", error);
+
+    let error = figment.extract_inner::<u128>("id").unwrap_err();
+    assert!(error.to_string().contains("u128"), "{}", error);
+}
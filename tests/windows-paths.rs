@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use figment::{Figment, Jail, providers::{Format, Toml}, value::magic::RelativePathBuf};
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    path: RelativePathBuf,
+}
+
+fn normalized(path: &str, separator: char) -> String {
+    RelativePathBuf::from(path).normalized_with(separator).display().to_string()
+}
+
+#[test]
+fn normalizes_mixed_separators() {
+    assert_eq!(normalized(r"a\b/c", '/'), "a/b/c");
+    assert_eq!(normalized(r"a\b/c", '\\'), r"a\b\c");
+    assert_eq!(normalized(r"a//b\\\c/", '/'), "a/b/c");
+    assert_eq!(normalized(r"./a/./b/.", '/'), "a/b");
+    assert_eq!(normalized(r"a/b/../../..\c", '/'), "../c");
+    assert_eq!(normalized(r"/a/../../b", '/'), "/b");
+    assert_eq!(normalized(r"a/..", '/'), ".");
+    assert_eq!(normalized("", '/'), ".");
+}
+
+#[test]
+fn normalizes_windows_prefixes() {
+    assert_eq!(normalized(r"C:\a/b\..\c", '/'), "C:/a/c");
+    assert_eq!(normalized(r"c:/a\b", '\\'), r"c:\a\b");
+    assert_eq!(normalized(r"C:a\..\..\b", '/'), "C:../b");
+    assert_eq!(normalized(r"C:\..\a", '\\'), r"C:\a");
+    assert_eq!(normalized(r"\\server\share\a/../b", '/'), "//server/share/b");
+    assert_eq!(normalized(r"//server/share", '\\'), r"\\server\share");
+    assert_eq!(normalized(r"\\server\share\..\..", '\\'), r"\\server\share");
+    assert_eq!(normalized(r"\\?\C:\a\.\b/c", '/'), r"\\?\C:\a\b\c");
+}
+
+#[test]
+fn windows_absolute_paths_are_never_relative() {
+    Jail::expect_with(|jail| {
+        for path in [r"C:\Program Files\app", "D:/data", r"\\server\share\app", "//server/share"] {
+            jail.create_file("Config.toml", &format!("path = '{}'", path))?;
+            let config: Config = Figment::from(Toml::file("Config.toml")).extract()?;
+            assert_eq!(config.path.relative(), Path::new(path));
+        }
+
+        jail.create_file("Config.toml", r"path = 'C:relative\path'")?;
+        let config: Config = Figment::from(Toml::file("Config.toml")).extract()?;
+        assert_eq!(config.path.relative(), jail.directory().join(r"C:relative\path"));
+
+        Ok(())
+    });
+}
+
+#[test]
+fn normalized_paths_are_relative_to_the_file() {
+    Jail::expect_with(|jail| {
+        jail.create_dir("conf")?;
+        jail.create_file("conf/App.toml", r"path = '..\static/img\.\logo.png'")?;
+        let config: Config = Figment::from(Toml::file("conf/App.toml")).extract()?;
+        let expected = jail.directory().join("static").join("img").join("logo.png");
+        assert_eq!(config.path.normalized(), expected);
+
+        Ok(())
+    });
+}
+
+#[test]
+#[cfg(windows)]
+fn windows_separators_resolve_on_windows() {
+    Jail::expect_with(|jail| {
+        jail.create_dir("conf")?;
+        jail.create_file(r"conf\App.toml", "path = 'assets/css/site.css'")?;
+        let config: Config = Figment::from(Toml::file(r"conf\App.toml")).extract()?;
+        let conf = config.path.metadata_path().unwrap().parent().unwrap();
+        let expected = conf.join(r"assets\css\site.css");
+        assert_eq!(config.path.normalized(), expected);
+        assert!(config.path.normalized().display().to_string().ends_with(r"\assets\css\site.css"));
+
+        jail.create_file(r"conf\App.toml", r"path = 'C:\Windows/System32'")?;
+        let config: Config = Figment::from(Toml::file(r"conf\App.toml")).extract()?;
+        assert_eq!(config.path.relative(), Path::new(r"C:\Windows\System32"));
+        assert_eq!(config.path.normalized(), Path::new(r"C:\Windows\System32"));
+        Ok(())
+    });
+}
@@ -0,0 +1,60 @@
+use figment::{Figment, providers::{Format, Yaml}};
+
+fn allowed(source: &str) -> bool {
+    let yaml = Yaml::string(source).resolve_aliases(false);
+    Figment::from(yaml).extract::<figment::value::Value>().is_ok()
+}
+
+#[test]
+fn aliases_detected() {
+    assert!(!allowed("a: &x 1\nb: *x"));
+    assert!(!allowed("a: &x 1\nb: [1, *x]"));
+    assert!(!allowed("a: &x 1\nb: {c: *x}"));
+    assert!(!allowed("a: &x 1\nb:\n  - *x"));
+    assert!(!allowed("a: &x { b: 1 }\nc:\n  <<: *x"));
+    assert!(!allowed("a: &x 1\nb: [\n  1,\n  *x\n]"));
+}
+
+#[test]
+fn non_aliases_allowed() {
+    assert!(allowed("a: &x 1"));
+    assert!(allowed("a: '*x'\nb: \"*y\""));
+    assert!(allowed("a: 2 * 3\nb: x*y"));
+    assert!(allowed("a: 1 # *x"));
+    assert!(allowed("a: |\n  *x\n  - *y\nb: >-\n  *z\n\n  *w\nc: 1"));
+    assert!(allowed("a: \"multi\n  *line\""));
+}
+
+#[test]
+fn block_scalars_end() {
+    assert!(!allowed("a: &x 1\nb: |\n  text\nc: *x"));
+}
+
+#[test]
+fn adversarial_documents() {
+    // Aliases as keys, in nested flow collections, and after tricky scalars.
+    assert!(!allowed("a: &x k\n? *x\n: 1"));
+    assert!(!allowed("a: &x k\nb: {*x : 1}"));
+    assert!(!allowed("a: &x 1\nb: [1, \"]\", [*x]]"));
+    assert!(!allowed("a: &x 1\nb:\n- - - *x"));
+    assert!(!allowed("a: &x 1 # it's\nb: *x"));
+    assert!(!allowed("a: \"x #\n  y\"\nb: &x 1\nc: *x"));
+    assert!(!allowed("a: 'it''s'\nb: &x 1\nc: *x"));
+
+    // Text that only looks like an alias.
+    assert!(allowed("a: foo\n  *x"));
+    assert!(allowed("a: [\"*x\", '*y', z*]"));
+    assert!(allowed("a: |2\n   *x\nb: 1"));
+    assert!(allowed("a: \"\\\" *x\""));
+}
+
+#[test]
+fn alias_line_reported() {
+    let lol = "a: &a [1, 1, 1]\nb: &b [*a, *a, *a]\nc: &c [*b, *b, *b]";
+    let error = Figment::from(Yaml::string(lol).resolve_aliases(false))
+        .extract::<figment::value::Value>()
+        .unwrap_err();
+
+    assert!(error.to_string().contains("an alias is used on line 2"), "{}", error);
+    assert!(allowed("a: &a [1, 1, 1]\nb: &b [2, 2, 2]"));
+}